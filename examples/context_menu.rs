@@ -0,0 +1,39 @@
+use dioxus::prelude::*;
+use dioxus_desktop::{use_context_menu, ContextMenu, ContextMenuEvent};
+
+fn main() {
+    dioxus_desktop::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let selected = use_state(cx, || None);
+    let word_wrap = use_state(cx, || false);
+
+    let menu = use_context_menu(cx, {
+        to_owned![selected, word_wrap];
+        move |event: ContextMenuEvent| {
+            if let Some(checked) = event.checked {
+                word_wrap.set(checked);
+            }
+            selected.set(Some(event.id));
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            height: "100vh",
+            prevent_default: "oncontextmenu",
+            oncontextmenu: move |evt| {
+                let menu_items = ContextMenu::new()
+                    .item("copy", "Copy")
+                    .item("paste", "Paste")
+                    .separator()
+                    .checkbox("wrap", "Word wrap", *word_wrap.get())
+                    .submenu("Sort by", ContextMenu::new().item("name", "Name").item("date", "Date"));
+                menu.show(menu_items, &evt);
+            },
+
+            "Right click anywhere. Selected: {selected:?}, word wrap: {word_wrap}"
+        }
+    })
+}
@@ -30,6 +30,7 @@ fn app(cx: Scope) -> Element {
             h1 { "Input Roulette" }
             for i in 0..100 {
                 input {
+                    key: "{i}",
                     value: "{i}",
                     onmounted: move |cx| {
                         elements.write().push(cx.inner().clone());
@@ -18,6 +18,7 @@ fn app(cx: Scope) -> Element {
             p { "Total: {sum}" }
             for (i, counter) in counters.iter().enumerate() {
                 li {
+                    key: "{i}",
                     button { onclick: move |_| counters.make_mut()[i] -= 1, "-1" }
                     input {
                         value: "{counter}",
@@ -45,7 +45,7 @@ fn App(cx: Scope) -> Element {
 
         ul {
             for file in files_uploaded.read().iter() {
-                li { "{file}" }
+                li { key: "{file}", "{file}" }
             }
         }
     })
@@ -78,7 +78,7 @@ fn app(cx: Scope) -> Element {
                     "Add item"
                 }
                 for item in items.iter() {
-                    div { "- {item}" }
+                    div { key: "{item}", "- {item}" }
                 }
             }
         }
@@ -17,7 +17,7 @@ fn app(cx: Scope) -> Element {
             }
 
             for i in 0..100 {
-                div { "Item {i}" }
+                div { key: "{i}", "Item {i}" }
             }
 
             button {
@@ -22,7 +22,7 @@ fn app(cx: Scope) -> Element {
 
             // use a for loop where the body itself is RSX
             for name in 0..10 {
-                div {"{name}"}
+                div { key: "{name}", "{name}" }
             }
 
             // Or even use an unterminated conditional
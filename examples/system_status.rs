@@ -0,0 +1,25 @@
+use dioxus::prelude::*;
+use dioxus_desktop::{use_battery, use_online};
+
+fn main() {
+    dioxus_desktop::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let battery = use_battery(cx);
+    let online = use_online(cx);
+
+    cx.render(rsx! {
+        if !online {
+            rsx! { div { background_color: "orange", "You are offline" } }
+        }
+        match battery {
+            Some(battery) => {
+                let percent = (battery.level * 100.0).round();
+                let charging = if battery.charging { " (charging)" } else { "" };
+                rsx! { p { "Battery: {percent}%{charging}" } }
+            }
+            None => rsx! { p { "No battery" } },
+        }
+    })
+}
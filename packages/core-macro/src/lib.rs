@@ -4,6 +4,7 @@ use rsx::RenderCallBody;
 use syn::parse_macro_input;
 
 mod inlineprops;
+mod names;
 mod props;
 
 // mod rsx;
@@ -29,7 +30,7 @@ pub fn derive_typed_builder(input: proc_macro::TokenStream) -> proc_macro::Token
 /// The rsx! macro makes it easy for developers to write jsx-style markup in their components.
 #[proc_macro]
 pub fn rsx(s: TokenStream) -> TokenStream {
-    match syn::parse::<rsx::CallBody>(s) {
+    match syn::parse::<rsx::CallBody>(s).and_then(|body| names::check_names(&body).map(|_| body)) {
         Err(err) => err.to_compile_error().into(),
         Ok(body) => body.to_token_stream().into(),
     }
@@ -40,7 +41,7 @@ pub fn rsx(s: TokenStream) -> TokenStream {
 /// The render macro automatically renders rsx - making it unhygenic.
 #[proc_macro]
 pub fn render(s: TokenStream) -> TokenStream {
-    match syn::parse::<rsx::CallBody>(s) {
+    match syn::parse::<rsx::CallBody>(s).and_then(|body| names::check_names(&body).map(|_| body)) {
         Err(err) => err.to_compile_error().into(),
         Ok(body) => RenderCallBody(body).into_token_stream().into(),
     }
@@ -0,0 +1,173 @@
+//! Check the names of the elements and attributes in rsx against the definitions of dioxus-html, so misspelled names
+//! get an error that suggests the closest name instead of a missing associated item.
+
+use dioxus_html::names;
+use dioxus_rsx::{BodyNode, CallBody, ElementAttr, ElementName};
+use syn::{Error, Ident, Result};
+
+/// Check the elements and attributes of the body. Names that are not close to any name of dioxus-html are left to the
+/// compiler, so elements and attributes from other namespaces keep working. Elements are only checked for names that
+/// differ in case or underscores, because custom namespaces often define elements a few letters from an html element.
+pub(crate) fn check_names(body: &CallBody) -> Result<()> {
+    let mut error = None;
+    check_nodes(&body.roots, &mut error);
+    error.map_or(Ok(()), Err)
+}
+
+fn check_nodes(nodes: &[BodyNode], error: &mut Option<Error>) {
+    for node in nodes {
+        match node {
+            BodyNode::Element(element) => {
+                if let ElementName::Ident(tag) = &element.name {
+                    let tag_name = tag.to_string();
+                    if names::elements().contains(&tag_name.as_str()) {
+                        for attribute in &element.attributes {
+                            if let ElementAttr::AttrText { name, .. }
+                            | ElementAttr::AttrExpression { name, .. } = &attribute.attr
+                            {
+                                let known = names::attributes(&tag_name);
+                                if let Some(suggestion) = suggest(&name.to_string(), known) {
+                                    let message =
+                                        format!("unknown attribute `{}` on `{}`", name, tag_name);
+                                    push(error, unknown(name, message, suggestion));
+                                }
+                            }
+                        }
+                    } else if let Some(suggestion) =
+                        suggest_renamed(&tag_name, names::elements().iter().copied())
+                    {
+                        let message = format!("unknown element `{}`", tag_name);
+                        push(error, unknown(tag, message, suggestion));
+                    }
+                }
+                check_nodes(&element.children, error);
+            }
+            BodyNode::Component(component) => check_nodes(&component.children, error),
+            BodyNode::ForLoop(for_loop) => check_nodes(&for_loop.body, error),
+            _ => {}
+        }
+    }
+}
+
+fn unknown(name: &Ident, message: String, suggestion: &str) -> Error {
+    let mut error = Error::new(name.span(), message);
+    error.combine(Error::new(
+        name.span(),
+        format!("help: did you mean `{}`?", suggestion),
+    ));
+    error
+}
+
+fn push(error: &mut Option<Error>, new: Error) {
+    match error {
+        Some(error) => error.combine(new),
+        None => *error = Some(new),
+    }
+}
+
+/// Find the name that was most likely meant by an unknown name: a name found by [`suggest_renamed`], or else the
+/// closest name a few edits away. Returns [`None`] if the name is known.
+fn suggest(name: &str, known: impl Iterator<Item = &'static str> + Clone) -> Option<&'static str> {
+    if known.clone().any(|known| known == name) {
+        return None;
+    }
+    if let Some(known) = suggest_renamed(name, known.clone()) {
+        return Some(known);
+    }
+
+    let normalized = normalize(name);
+    let max_distance = (name.chars().count() / 3).max(1);
+    known
+        .map(|known| (edit_distance(&normalized, &normalize(known)), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Find the same name as an unknown name in a different case or without underscores, like the JSX name `tabIndex` for
+/// `tabindex`. Returns [`None`] if the name is known.
+fn suggest_renamed(
+    name: &str,
+    mut known: impl Iterator<Item = &'static str> + Clone,
+) -> Option<&'static str> {
+    // JSX renames the attributes that are reserved words in JavaScript
+    let unaliased = match name {
+        "className" => "class",
+        "htmlFor" => "for",
+        name => name,
+    };
+    let normalized = normalize(unaliased);
+    known
+        .find(|&known| normalize(known) == normalized)
+        .filter(|&known| known != name)
+}
+
+fn normalize(name: &str) -> String {
+    name.trim_start_matches("r#")
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The number of characters to insert, remove, replace or swap with their neighbor to turn one name into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // distances[i][j] is the distance between the first i characters of a and the first j characters of b
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    distances[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let replace = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = replace
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_names_from_html() {
+        let div = || names::attributes("div");
+        assert_eq!(suggest("class", div()), None);
+        assert_eq!(suggest("className", div()), Some("class"));
+        assert_eq!(suggest("tabIndex", div()), Some("tabindex"));
+        assert_eq!(suggest("clas", div()), Some("class"));
+        assert_eq!(
+            suggest("htmlFor", names::attributes("label")),
+            Some("r#for")
+        );
+        assert_eq!(
+            suggest_renamed("Div", names::elements().iter().copied()),
+            Some("div")
+        );
+        // Elements from custom namespaces can be a few letters from an html element
+        assert_eq!(
+            suggest_renamed("plot", names::elements().iter().copied()),
+            None
+        );
+        // Names that are not close to any name are left to the compiler
+        assert_eq!(suggest("my_custom_attribute", div()), None);
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("div", "div"), 0);
+        assert_eq!(edit_distance("dvi", "div"), 1);
+        assert_eq!(edit_distance("clas", "class"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -163,7 +163,7 @@ mod util {
 }
 
 mod field_info {
-    use crate::props::type_from_inside_option;
+    use crate::props::{is_slot_content, type_from_inside_option};
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::spanned::Spanned;
@@ -198,6 +198,15 @@ mod field_info {
                         Some(syn::parse(quote!(Default::default()).into()).unwrap());
                 }
 
+                // slots are automatically defaulted to an empty slot and accept anything that converts into a slot
+                if is_slot_content(&field.ty) {
+                    if builder_attr.default.is_none() {
+                        builder_attr.default =
+                            Some(syn::parse(quote!(Default::default()).into()).unwrap());
+                    }
+                    builder_attr.auto_into = true;
+                }
+
                 // auto detect optional
                 let strip_option_auto = builder_attr.strip_option
                     || !builder_attr.ignore_option
@@ -255,6 +264,53 @@ mod field_info {
         pub auto_into: bool,
         pub strip_option: bool,
         pub ignore_option: bool,
+        /// How the field is compared when the props are memoized. `None` compares it with PartialEq
+        pub memo: Option<FieldMemo>,
+    }
+
+    /// A memoization strategy for a single field: `#[props(memo = ptr)]`
+    #[derive(Debug, Clone)]
+    pub enum FieldMemo {
+        /// Compare the field with PartialEq
+        PartialEq,
+        /// Compare the address the field points to with PtrEq
+        Ptr,
+        /// Ignore the field
+        Skip,
+        /// Compare the field with a function that takes references to the old and new values
+        Custom(syn::Path),
+    }
+
+    impl FieldMemo {
+        fn parse(expr: syn::Expr) -> Result<Self, Error> {
+            let path = match expr {
+                syn::Expr::Path(path) => path.path,
+                _ => {
+                    return Err(Error::new_spanned(
+                        expr,
+                        "Expected partial_eq, ptr, skip, or the path to a function",
+                    ))
+                }
+            };
+            Ok(match path_to_single_string(&path).as_deref() {
+                Some("partial_eq") => Self::PartialEq,
+                Some("ptr") => Self::Ptr,
+                Some("skip") => Self::Skip,
+                _ => Self::Custom(path),
+            })
+        }
+
+        /// Check if the field of `self` and `other` are equal
+        pub fn compare(&self, field: &syn::Ident) -> Option<TokenStream> {
+            match self {
+                Self::PartialEq => Some(quote!(self.#field == other.#field)),
+                Self::Ptr => {
+                    Some(quote!(dioxus::prelude::PtrEq::ptr_eq(&self.#field, &other.#field)))
+                }
+                Self::Skip => None,
+                Self::Custom(function) => Some(quote!(#function(&self.#field, &other.#field))),
+            }
+        }
     }
 
     impl FieldBuilderAttr {
@@ -315,6 +371,10 @@ mod field_info {
                             self.doc = Some(*assign.right);
                             Ok(())
                         }
+                        "memo" => {
+                            self.memo = Some(FieldMemo::parse(*assign.right)?);
+                            Ok(())
+                        }
                         "default_code" => {
                             if let syn::Expr::Lit(syn::ExprLit {
                                 lit: syn::Lit::Str(code),
@@ -459,6 +519,17 @@ fn type_from_inside_option(ty: &syn::Type, check_option_name: bool) -> Option<&s
     }
 }
 
+fn is_slot_content(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "SlotContent"),
+        _ => false,
+    }
+}
+
 mod struct_info {
     use proc_macro2::TokenStream;
     use quote::quote;
@@ -466,7 +537,7 @@ mod struct_info {
     use syn::punctuated::Punctuated;
     use syn::Expr;
 
-    use super::field_info::{FieldBuilderAttr, FieldInfo};
+    use super::field_info::{FieldBuilderAttr, FieldInfo, FieldMemo};
     use super::util::{
         empty_type, empty_type_tuple, expr_to_single_string, make_punctuated_single,
         modify_types_generics_hack, path_to_single_string, strip_raw_ident_prefix, type_tuple,
@@ -617,9 +688,34 @@ Finally, call `.build()` to create the instance of `{name}`.
                     .extend(predicates.predicates.clone());
             }
 
-            let can_memoize = match are_there_generics {
-                true => quote! { false  },
-                false => quote! { self == other },
+            let can_memoize = if are_there_generics {
+                quote! { false  }
+            } else if self.builder_attr.custom_memo {
+                quote! { dioxus::prelude::Memoize::memoize(self, other) }
+            } else if self.fields.iter().any(|f| f.builder_attr.memo.is_some()) {
+                // Compare the fields one by one if any field has a custom strategy
+                let comparisons = self.fields.iter().filter_map(|f| {
+                    f.builder_attr
+                        .memo
+                        .as_ref()
+                        .unwrap_or(&FieldMemo::PartialEq)
+                        .compare(f.name)
+                });
+                quote! { true #(&& #comparisons)* }
+            } else {
+                quote! { self == other }
+            };
+
+            let props_hash = match !are_there_generics && self.builder_attr.hash {
+                true => quote! {
+                    fn props_hash(&self) -> Option<u64> {
+                        use std::hash::{Hash, Hasher};
+                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                        Hash::hash(self, &mut hasher);
+                        Some(hasher.finish())
+                    }
+                },
+                false => quote! {},
             };
 
             let is_static = match are_there_generics {
@@ -667,6 +763,7 @@ Finally, call `.build()` to create the instance of `{name}`.
                     unsafe fn memoize(&self, other: &Self) -> bool {
                         #can_memoize
                     }
+                    #props_hash
                 }
 
             })
@@ -1080,13 +1177,22 @@ Finally, call `.build()` to create the instance of `{name}`.
         pub build_method_doc: Option<syn::Expr>,
 
         pub field_defaults: FieldBuilderAttr,
+
+        /// Memoize the props with their `Memoize` implementation instead of comparing the fields: `#[props(memo = custom)]`
+        pub custom_memo: bool,
+
+        /// Implement `Properties::props_hash` with the `Hash` implementation of the props: `#[props(hash)]`
+        pub hash: bool,
     }
 
     impl TypeBuilderAttr {
         pub fn new(attrs: &[syn::Attribute]) -> Result<TypeBuilderAttr, Error> {
             let mut result = TypeBuilderAttr::default();
             for attr in attrs {
-                if path_to_single_string(attr.path()).as_deref() != Some("builder") {
+                if !matches!(
+                    path_to_single_string(attr.path()).as_deref(),
+                    Some("builder" | "props")
+                ) {
                     continue;
                 }
 
@@ -1133,6 +1239,20 @@ Finally, call `.build()` to create the instance of `{name}`.
                             self.doc = true;
                             Ok(())
                         }
+                        "memo" => match expr_to_single_string(&assign.right).as_deref() {
+                            Some("custom") => {
+                                self.custom_memo = true;
+                                Ok(())
+                            }
+                            Some("partial_eq") => {
+                                self.custom_memo = false;
+                                Ok(())
+                            }
+                            _ => Err(Error::new_spanned(
+                                &assign.right,
+                                "Expected partial_eq or custom",
+                            )),
+                        },
                         _ => Err(Error::new_spanned(
                             &assign,
                             format!("Unknown parameter {name:?}"),
@@ -1147,6 +1267,10 @@ Finally, call `.build()` to create the instance of `{name}`.
                             self.doc = true;
                             Ok(())
                         }
+                        "hash" => {
+                            self.hash = true;
+                            Ok(())
+                        }
                         _ => Err(Error::new_spanned(
                             &path,
                             format!("Unknown parameter {name:?}"),
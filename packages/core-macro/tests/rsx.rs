@@ -2,4 +2,7 @@
 fn rsx() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/rsx/trailing-comma-0.rs");
+    t.compile_fail("tests/rsx/invalid-attribute-0.rs");
+    t.compile_fail("tests/rsx/invalid-attribute-1.rs");
+    t.compile_fail("tests/rsx/for-loop-key-0.rs");
 }
@@ -0,0 +1,18 @@
+// Given an `rsx!` invocation with a for loop that renders elements without a
+// key, ensure the warning points at the loop.
+
+#![deny(deprecated)]
+
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    render! {
+        for i in 0..10 {
+            div { "{i}" }
+        }
+    }
+}
+
+fn main() {
+    let _ = app;
+}
@@ -0,0 +1,13 @@
+// Given an `rsx!` invocation with an attribute name that is valid in HTML but
+// not in rsx, ensure the error suggests the rsx spelling.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        label {
+            for: "name",
+            "Name"
+        }
+    };
+}
@@ -0,0 +1,13 @@
+// Given an `rsx!` invocation with a JSX attribute name, ensure the error
+// suggests the rsx spelling.
+
+use dioxus::prelude::*;
+
+fn main() {
+    rsx! {
+        div {
+            className: "foo bar",
+            "Hello world"
+        }
+    };
+}
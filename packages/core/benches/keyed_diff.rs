@@ -0,0 +1,51 @@
+//! Benchmarks for diffing keyed lists where the children are reordered between renders.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dioxus::prelude::*;
+use rand::prelude::*;
+
+criterion_group!(mbenches, keyed_reorder);
+criterion_main!(mbenches);
+
+fn keyed_reorder(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Keyed reorder");
+
+    for len in [100, 1000] {
+        let mut shuffled: Vec<usize> = (0..len).collect();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(0));
+        let reversed: Vec<usize> = (0..len).rev().collect();
+
+        for (name, order) in [("shuffle", shuffled), ("reverse", reversed)] {
+            group.bench_with_input(BenchmarkId::new(name, len), &len, |b, &len| {
+                let mut dom = VirtualDom::new_with_props(
+                    app,
+                    AppProps {
+                        orders: [(0..len).collect(), order.clone()],
+                    },
+                );
+                let _ = dom.rebuild();
+
+                // every render switches between the two orders, so every diff moves the children
+                b.iter(|| {
+                    dom.mark_dirty(ScopeId(0));
+                    let _ = dom.render_immediate();
+                })
+            });
+        }
+    }
+}
+
+#[derive(Props, PartialEq)]
+struct AppProps {
+    orders: [Vec<usize>; 2],
+}
+
+fn app(cx: Scope<AppProps>) -> Element {
+    let order = &cx.props.orders[cx.generation() % 2];
+
+    cx.render(rsx! {
+        order.iter().map(|i| rsx! {
+            div { key: "{i}", "{i}" }
+        })
+    })
+}
@@ -15,11 +15,13 @@ pub(crate) unsafe trait AnyProps<'a> {
     fn props_ptr(&self) -> *const ();
     fn render(&'a self, bump: &'a ScopeState) -> RenderReturn<'a>;
     unsafe fn memoize(&self, other: &dyn AnyProps) -> bool;
+    fn props_hash(&self) -> Option<u64>;
 }
 
 pub(crate) struct VProps<'a, P> {
     pub render_fn: fn(Scope<'a, P>) -> Element<'a>,
     pub memo: unsafe fn(&P, &P) -> bool,
+    pub hash: fn(&P) -> Option<u64>,
     pub props: P,
 }
 
@@ -27,11 +29,13 @@ impl<'a, P> VProps<'a, P> {
     pub(crate) fn new(
         render_fn: fn(Scope<'a, P>) -> Element<'a>,
         memo: unsafe fn(&P, &P) -> bool,
+        hash: fn(&P) -> Option<u64>,
         props: P,
     ) -> Self {
         Self {
             render_fn,
             memo,
+            hash,
             props,
         }
     }
@@ -52,6 +56,10 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         (self.memo)(real_us, real_other)
     }
 
+    fn props_hash(&self) -> Option<u64> {
+        (self.hash)(&self.props)
+    }
+
     fn render(&'a self, cx: &'a ScopeState) -> RenderReturn<'a> {
         let res = std::panic::catch_unwind(AssertUnwindSafe(move || {
             // Call the render function directly
@@ -1,14 +1,20 @@
-use std::ptr::NonNull;
+use slab::Slab;
+use std::{
+    cell::RefCell,
+    ptr::NonNull,
+    rc::{Rc, Weak},
+};
 
 use crate::{
     innerlude::DirtyScope, nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom,
-    AttributeValue, DynamicNode, ScopeId,
+    AttributeValue, DynamicNode, EventTarget, ScopeId,
 };
 
 /// An Element's unique identifier.
 ///
 /// `ElementId` is a `usize` that is unique across the entire VirtualDOM - but not unique across time. If a component is
-/// unmounted, then the `ElementId` will be reused for a new component.
+/// unmounted, then the `ElementId` will be reused for a new component. Every reuse bumps the generation of the id, which
+/// is sent with [`crate::Mutation::NewEventListener`] so renderers can tell stale events apart.
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct ElementId(pub usize);
@@ -24,6 +30,67 @@ pub(crate) struct ElementRef {
     pub scope: ScopeId,
 }
 
+/// A slot map of element references.
+///
+/// Element ids are reused once an element is removed. The arena counts how often each slot was freed, so an id together
+/// with its generation is unique across time.
+#[derive(Default)]
+pub(crate) struct ElementArena {
+    elements: Slab<ElementRef>,
+    // Shared with the event targets, so they can tell if their element was removed
+    generations: Rc<RefCell<Vec<u32>>>,
+}
+
+impl ElementArena {
+    pub(crate) fn insert(&mut self, element: ElementRef) -> ElementId {
+        let id = self.elements.insert(element);
+        let mut generations = self.generations.borrow_mut();
+        if id >= generations.len() {
+            generations.resize(id + 1, 0);
+        }
+        ElementId(id)
+    }
+
+    pub(crate) fn get(&self, id: ElementId) -> Option<&ElementRef> {
+        self.elements.get(id.0)
+    }
+
+    pub(crate) fn get_mut(&mut self, id: ElementId) -> Option<&mut ElementRef> {
+        self.elements.get_mut(id.0)
+    }
+
+    pub(crate) fn try_remove(&mut self, id: ElementId) -> Option<ElementRef> {
+        let removed = self.elements.try_remove(id.0)?;
+        let mut generations = self.generations.borrow_mut();
+        generations[id.0] = generations[id.0].wrapping_add(1);
+        Some(removed)
+    }
+
+    /// Get the generation of a live element
+    pub(crate) fn generation(&self, id: ElementId) -> Option<u32> {
+        self.elements
+            .contains(id.0)
+            .then(|| self.generations.borrow()[id.0])
+    }
+
+    /// Get a handle to a live element that stays valid until the element is removed
+    pub(crate) fn target(&self, id: ElementId) -> EventTarget {
+        match self.generation(id) {
+            Some(generation) => EventTarget {
+                id,
+                generation,
+                generations: Rc::downgrade(&self.generations),
+            },
+            // Events for elements that were already removed have a target that is never mounted
+            None => EventTarget {
+                id,
+                generation: 0,
+                generations: Weak::new(),
+            },
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum ElementPath {
     Deep(&'static [u8]),
@@ -50,25 +117,18 @@ impl VirtualDom {
     }
 
     pub(crate) fn next_null(&mut self) -> ElementId {
-        let entry = self.elements.vacant_entry();
-        let id = entry.key();
-
-        entry.insert(ElementRef::none());
-        ElementId(id)
+        self.elements.insert(ElementRef::none())
     }
 
     fn next_reference(&mut self, template: &VNode, path: ElementPath) -> ElementId {
-        let entry = self.elements.vacant_entry();
-        let id = entry.key();
         let scope = self.runtime.current_scope_id().unwrap_or(ScopeId(0));
 
-        entry.insert(ElementRef {
+        self.elements.insert(ElementRef {
             // We know this is non-null because it comes from a reference
             template: Some(unsafe { NonNull::new_unchecked(template as *const _ as *mut _) }),
             path,
             scope,
-        });
-        ElementId(id)
+        })
     }
 
     pub(crate) fn reclaim(&mut self, el: ElementId) {
@@ -84,12 +144,12 @@ impl VirtualDom {
             );
         }
 
-        self.elements.try_remove(el.0)
+        self.elements.try_remove(el)
     }
 
     pub(crate) fn update_template(&mut self, el: ElementId, node: &VNode) {
         let node: *const VNode = node as *const _;
-        self.elements[el.0].template = unsafe { std::mem::transmute(node) };
+        self.elements.get_mut(el).unwrap().template = unsafe { std::mem::transmute(node) };
     }
 
     // Drop a scope and all its children
@@ -103,6 +163,10 @@ impl VirtualDom {
 
         self.ensure_drop_safety(id);
 
+        if self.suspended_scopes.remove(&id) {
+            self.update_suspense_boundary(id, false);
+        }
+
         if recursive {
             if let Some(root) = self.scopes[id.0].try_root_node() {
                 if let RenderReturn::Ready(node) = unsafe { root.extend_lifetime_ref() } {
@@ -115,10 +179,20 @@ impl VirtualDom {
 
         // Drop all the hooks once the children are dropped
         // this means we'll drop hooks bottom-up
-        scope.hooks.get_mut().clear();
+        scope.drop_hooks();
+        let mut moved_groups = Vec::new();
         {
             let context = scope.context();
 
+            // Shut down the task groups, which may let their tasks finish in the root scope after this scope is gone
+            for group in context.task_groups.take() {
+                if let Some(group) = group.upgrade() {
+                    if group.shutdown() {
+                        moved_groups.push(Rc::downgrade(&group));
+                    }
+                }
+            }
+
             // Drop all the futures once the hooks are dropped
             for task_id in context.spawned_tasks.borrow_mut().drain() {
                 context.tasks.remove(task_id);
@@ -126,6 +200,11 @@ impl VirtualDom {
         }
 
         self.scopes.remove(id.0);
+
+        // The root scope cancels the groups if it is dropped before their shutdown timeout
+        if let Some(root) = self.scopes.get(0) {
+            root.context().task_groups.borrow_mut().extend(moved_groups);
+        }
     }
 
     fn drop_scope_inner(&mut self, node: &VNode) {
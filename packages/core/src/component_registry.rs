@@ -0,0 +1,206 @@
+use crate::{
+    innerlude::DynamicNode, Component, Properties, ScopeState, Template, TemplateNode, VNode,
+};
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use std::cell::{Cell, RefCell};
+
+type Instantiate = dyn for<'a> Fn(
+    &'a ScopeState,
+    serde_json::Value,
+) -> Result<DynamicNode<'a>, ComponentRegistryError>;
+
+/// A map of components by name, so they can be instantiated from data at runtime.
+///
+/// CMS-driven layouts and plugin systems describe the component tree with data. The registry renders a component from
+/// its name and its props as JSON:
+///
+/// ```rust, ignore
+/// #[derive(Props, PartialEq, Deserialize)]
+/// struct HeroProps {
+///     title: String,
+/// }
+///
+/// fn Hero(cx: Scope<HeroProps>) -> Element {
+///     render! { h1 { "{cx.props.title}" } }
+/// }
+///
+/// let mut registry = ComponentRegistry::new();
+/// registry.register("hero", Hero);
+///
+/// fn Page(cx: Scope) -> Element {
+///     let registry = cx.consume_context::<Rc<ComponentRegistry>>().unwrap();
+///     registry.instantiate(cx, "hero", json!({ "title": "Welcome" })).ok()
+/// }
+/// ```
+///
+/// Components registered anywhere in the binary with [`register_component!`](crate::register_component) are added
+/// to every new registry.
+pub struct ComponentRegistry {
+    components: FxHashMap<&'static str, Box<Instantiate>>,
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ComponentRegistry {
+    /// Create a registry with every component registered with [`register_component!`](crate::register_component).
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for registration in inventory::iter::<ComponentRegistration> {
+            registry
+                .components
+                .insert(registration.name, Box::new(registration.instantiate));
+        }
+        registry
+    }
+
+    /// Create a registry without any components.
+    pub fn empty() -> Self {
+        Self {
+            components: FxHashMap::default(),
+        }
+    }
+
+    /// Register a component under `name`. The props of the component are deserialized from JSON when it is
+    /// instantiated. Registering a component under a name that is already used replaces the old component.
+    pub fn register<P>(&mut self, name: &'static str, component: Component<P>)
+    where
+        P: Properties + DeserializeOwned + 'static,
+    {
+        self.components.insert(
+            name,
+            Box::new(move |cx, props| instantiate_component(cx, name, component, props)),
+        );
+    }
+
+    /// Remove the component registered under `name`. Returns true if there was a component with the name.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.components.remove(name).is_some()
+    }
+
+    /// Check if a component is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    /// The names of all registered components
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.components.keys().copied()
+    }
+
+    /// Render the component registered under `name` with props deserialized from `props`.
+    ///
+    /// The node can be rendered like any other node. Components are compared by their render function, so a
+    /// component is only created again if the name points to a different component.
+    pub fn instantiate<'a>(
+        &self,
+        cx: &'a ScopeState,
+        name: &str,
+        props: serde_json::Value,
+    ) -> Result<VNode<'a>, ComponentRegistryError> {
+        let component = match self.components.get(name) {
+            Some(instantiate) => instantiate(cx, props)?,
+            None => return Err(ComponentRegistryError::NotRegistered(name.to_string())),
+        };
+
+        Ok(VNode {
+            key: None,
+            parent: None,
+            root_ids: RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+            dynamic_nodes: cx.bump().alloc([component]),
+            dynamic_attrs: &[],
+            template: Cell::new(Template {
+                name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+                roots: &[TemplateNode::Dynamic { id: 0 }],
+                node_paths: &[&[0]],
+                attr_paths: &[],
+            }),
+        })
+    }
+}
+
+/// Create a node for `component` with props deserialized from `props`. This is called by
+/// [`ComponentRegistry::instantiate`] and the components registered with [`register_component!`](crate::register_component).
+#[doc(hidden)]
+pub fn instantiate_component<'a, P>(
+    cx: &'a ScopeState,
+    name: &'static str,
+    component: Component<P>,
+    props: serde_json::Value,
+) -> Result<DynamicNode<'a>, ComponentRegistryError>
+where
+    P: Properties + DeserializeOwned + 'static,
+{
+    let props: P = serde_json::from_value(props)
+        .map_err(|error| ComponentRegistryError::InvalidProps { name, error })?;
+    Ok(cx.component(component, props, name))
+}
+
+/// An error returned when a component can't be instantiated from a [`ComponentRegistry`].
+#[derive(Debug)]
+pub enum ComponentRegistryError {
+    /// No component is registered under the name
+    NotRegistered(String),
+    /// The props could not be deserialized into the props of the component
+    InvalidProps {
+        /// The name of the component
+        name: &'static str,
+        /// The error returned by serde_json
+        error: serde_json::Error,
+    },
+}
+
+impl std::fmt::Display for ComponentRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotRegistered(name) => write!(f, "No component is registered as `{}`", name),
+            Self::InvalidProps { name, error } => {
+                write!(f, "Invalid props for the component `{}`: {}", name, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComponentRegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotRegistered(_) => None,
+            Self::InvalidProps { error, .. } => Some(error),
+        }
+    }
+}
+
+/// A component registered with [`register_component!`](crate::register_component).
+#[doc(hidden)]
+pub struct ComponentRegistration {
+    pub name: &'static str,
+    pub instantiate: for<'a> fn(
+        &'a ScopeState,
+        serde_json::Value,
+    ) -> Result<DynamicNode<'a>, ComponentRegistryError>,
+}
+
+inventory::collect!(ComponentRegistration);
+
+/// Register a component under a name in every [`ComponentRegistry`] of the binary.
+///
+/// The registration happens at compile time, so plugins can add components without access to the registry.
+///
+/// ```rust, ignore
+/// register_component!("hero", Hero);
+/// ```
+#[macro_export]
+macro_rules! register_component {
+    ($name:expr, $component:path) => {
+        $crate::exports::inventory::submit! {
+            $crate::ComponentRegistration {
+                name: $name,
+                instantiate: |cx, props| $crate::instantiate_component(cx, $name, $component, props),
+            }
+        }
+    };
+}
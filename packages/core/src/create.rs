@@ -67,11 +67,26 @@ impl<'b> VirtualDom {
     /// This method pushes the ScopeID to the internal scopestack and returns the number of nodes created.
     pub(crate) fn create_scope(&mut self, scope: ScopeId, template: &'b VNode<'b>) -> usize {
         self.runtime.scope_stack.borrow_mut().push(scope);
+        self.marker_start(scope);
         let nodes = self.create(template);
+        self.marker_end(scope);
         self.runtime.scope_stack.borrow_mut().pop();
         nodes
     }
 
+    pub(crate) fn marker_start(&mut self, scope: ScopeId) {
+        if self.component_markers {
+            let name = self.scopes[scope.0].context().name;
+            self.mutations.push(Mutation::MarkerStart { scope, name });
+        }
+    }
+
+    pub(crate) fn marker_end(&mut self, scope: ScopeId) {
+        if self.component_markers {
+            self.mutations.push(Mutation::MarkerEnd { scope });
+        }
+    }
+
     /// Create this template and write its mutations
     pub(crate) fn create(&mut self, node: &'b VNode<'b>) -> usize {
         // check for a overriden template
@@ -311,6 +326,7 @@ impl<'b> VirtualDom {
                     // all listeners start with "on"
                     name: &unbounded_name[2..],
                     id,
+                    generation: self.elements.generation(id).unwrap_or_default(),
                 })
             }
             _ => {
@@ -10,12 +10,13 @@ use crate::{
     Attribute, TemplateNode,
 };
 
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxHashMap;
 use DynamicNode::*;
 
 impl<'b> VirtualDom {
     pub(super) fn diff_scope(&mut self, scope: ScopeId) {
         self.runtime.scope_stack.borrow_mut().push(scope);
+        self.marker_start(scope);
         let scope_state = &mut self.get_scope(scope).unwrap();
         unsafe {
             // Load the old and new bump arenas
@@ -46,6 +47,7 @@ impl<'b> VirtualDom {
                 (Aborted(l), Ready(r)) => self.replace_placeholder(l, [r]),
             };
         }
+        self.marker_end(scope);
         self.runtime.scope_stack.borrow_mut().pop();
     }
 
@@ -97,6 +99,11 @@ impl<'b> VirtualDom {
             return self.light_diff_templates(left_template, right_template);
         }
 
+        // Most re-renders only change the text of the template, so they skip diffing the attributes and dynamic nodes
+        if only_text_changed(left_template, right_template) {
+            return self.diff_text_only(left_template, right_template);
+        }
+
         // If the templates are the same, we can diff the attributes and children
         // Start with the attributes
         left_template
@@ -127,6 +134,45 @@ impl<'b> VirtualDom {
                 self.diff_dynamic_node(left_node, right_node, right_template);
             });
 
+        self.transfer_roots(left_template, right_template);
+    }
+
+    /// Diff two renders of a template where only the text of dynamic text nodes can have changed, like a counter or a
+    /// clock.
+    ///
+    /// The attributes are known to be equal and every dynamic node is text, so the elements are moved over to the new
+    /// template and [`Mutation::SetText`] is emitted for the text that changed.
+    fn diff_text_only(&mut self, left_template: &'b VNode<'b>, right_template: &'b VNode<'b>) {
+        let mut last_element = None;
+        for (left_attr, right_attr) in left_template
+            .dynamic_attrs
+            .iter()
+            .zip(right_template.dynamic_attrs.iter())
+        {
+            let element = left_attr.mounted_element.get();
+            right_attr.mounted_element.set(element);
+            // The attributes of an element are next to each other, so every element is only updated once
+            if last_element != Some(element) {
+                self.update_template(element, right_template);
+                last_element = Some(element);
+            }
+        }
+
+        for (left_node, right_node) in left_template
+            .dynamic_nodes
+            .iter()
+            .zip(right_template.dynamic_nodes.iter())
+        {
+            if let (Text(left), Text(right)) = (left_node, right_node) {
+                self.diff_vtext(left, right, right_template);
+            }
+        }
+
+        self.transfer_roots(left_template, right_template);
+    }
+
+    /// Move the root elements of the old render of a template over to the new render
+    fn transfer_roots(&mut self, left_template: &'b VNode<'b>, right_template: &'b VNode<'b>) {
         // Make sure the roots get transferred over while we're here
         {
             let mut right = right_template.root_ids.borrow_mut();
@@ -203,8 +249,20 @@ impl<'b> VirtualDom {
         // If the props are static, then we try to memoize by setting the new with the old
         // The target scopestate still has the reference to the old props, so there's no need to update anything
         // This also implicitly drops the new props since they're not used
-        if left.static_props && unsafe { old.as_ref().unwrap().memoize(new.as_ref()) } {
-            return;
+        if left.static_props {
+            let old = old.as_ref().unwrap();
+            // The hash of the old props was stored when the scope rendered with them
+            let hashes = match self.props_hashing {
+                true => self.scopes[scope_id.0].props_hash.get().zip(new.props_hash()),
+                false => None,
+            };
+            let unchanged = match hashes {
+                Some((old, new)) => old == new,
+                None => unsafe { old.memoize(new.as_ref()) },
+            };
+            if unchanged {
+                return;
+            }
         }
 
         // First, move over the props from the old to the new, dropping old props in the process
@@ -526,34 +584,43 @@ impl<'b> VirtualDom {
         debug_assert_ne!(new.first().map(|i| i.key), old.first().map(|i| i.key));
         debug_assert_ne!(new.last().map(|i| i.key), old.last().map(|i| i.key));
 
+        // The buffers are reused between diffs. Nested keyed lists are diffed while this list is being diffed, so each
+        // level of nesting takes its own buffers from the pool.
+        let mut scratch = self.keyed_diff_scratch.pop().unwrap_or_default();
+        let KeyedDiffScratch {
+            old_key_to_old_index,
+            old_is_shared,
+            new_index_to_old_index,
+            lis_sequence,
+            predecessors,
+            starts,
+        } = &mut scratch;
+
         // 1. Map the old keys into a numerical ordering based on indices.
         // 2. Create a map of old key to its index
         // IE if the keys were A B C, then we would have (A, 1) (B, 2) (C, 3).
-        let old_key_to_old_index = old
-            .iter()
-            .enumerate()
-            .map(|(i, o)| (o.key.unwrap(), i))
-            .collect::<FxHashMap<_, _>>();
-
-        let mut shared_keys = FxHashSet::default();
-
-        // 3. Map each new key to the old key, carrying over the old index.
-        let new_index_to_old_index = new
-            .iter()
-            .map(|node| {
+        old_key_to_old_index.with_keys(|old_key_to_old_index| {
+            old_key_to_old_index.extend(old.iter().enumerate().map(|(i, o)| (o.key.unwrap(), i)));
+
+            // 3. Map each new key to the old key, carrying over the old index.
+            old_is_shared.clear();
+            old_is_shared.resize(old.len(), false);
+            new_index_to_old_index.clear();
+            new_index_to_old_index.extend(new.iter().map(|node| {
                 let key = node.key.unwrap();
                 if let Some(&index) = old_key_to_old_index.get(&key) {
-                    shared_keys.insert(key);
+                    old_is_shared[index] = true;
                     index
                 } else {
                     u32::MAX as usize
                 }
-            })
-            .collect::<Vec<_>>();
+            }));
+        });
 
         // If none of the old keys are reused by the new children, then we remove all the remaining old children and
         // create the new children afresh.
-        if shared_keys.is_empty() {
+        if !old_is_shared.contains(&true) {
+            self.keyed_diff_scratch.push(scratch);
             if old.get(0).is_some() {
                 self.remove_nodes(&old[1..]);
                 self.replace(&old[0], new);
@@ -569,26 +636,27 @@ impl<'b> VirtualDom {
 
         // remove any old children that are not shared
         // todo: make this an iterator
-        for child in old {
-            let key = child.key.unwrap();
-            if !shared_keys.contains(&key) {
+        for (child, shared) in old.iter().zip(old_is_shared.iter()) {
+            if !shared {
                 self.remove_node(child, true);
             }
         }
 
         // 4. Compute the LIS of this list
-        let mut lis_sequence = Vec::default();
+        lis_sequence.clear();
         lis_sequence.reserve(new_index_to_old_index.len());
 
-        let mut predecessors = vec![0; new_index_to_old_index.len()];
-        let mut starts = vec![0; new_index_to_old_index.len()];
+        predecessors.clear();
+        predecessors.resize(new_index_to_old_index.len(), 0);
+        starts.clear();
+        starts.resize(new_index_to_old_index.len(), 0);
 
         longest_increasing_subsequence::lis_with(
-            &new_index_to_old_index,
-            &mut lis_sequence,
+            new_index_to_old_index,
+            lis_sequence,
             |a, b| a < b,
-            &mut predecessors,
-            &mut starts,
+            predecessors,
+            starts,
         );
 
         // the lis comes out backwards, I think. can't quite tell.
@@ -599,7 +667,7 @@ impl<'b> VirtualDom {
             lis_sequence.pop();
         }
 
-        for idx in &lis_sequence {
+        for idx in lis_sequence.iter() {
             self.diff_node(&old[new_index_to_old_index[*idx]], &new[*idx]);
         }
 
@@ -679,6 +747,8 @@ impl<'b> VirtualDom {
                 });
             }
         }
+
+        self.keyed_diff_scratch.push(scratch);
     }
 
     /// Push all the real nodes on the stack
@@ -726,7 +796,12 @@ impl<'b> VirtualDom {
                                 .extend_lifetime_ref()
                         } {
                             RenderReturn::Ready(node) => self.push_all_real_nodes(node),
-                            RenderReturn::Aborted(_node) => todo!(),
+                            RenderReturn::Aborted(placeholder) => {
+                                self.mutations.push(Mutation::PushRoot {
+                                    id: placeholder.id.get().unwrap(),
+                                });
+                                1
+                            }
                         }
                     }
                 }
@@ -958,7 +1033,8 @@ impl<'b> VirtualDom {
                         .extend_lifetime_ref()
                 } {
                     RenderReturn::Ready(t) => self.find_first_element(t),
-                    _ => todo!("cannot handle nonstandard nodes"),
+                    // Components that aborted rendering are mounted as a placeholder
+                    RenderReturn::Aborted(placeholder) => placeholder.id.get().unwrap(),
                 }
             }
         }
@@ -979,7 +1055,8 @@ impl<'b> VirtualDom {
                         .extend_lifetime_ref()
                 } {
                     RenderReturn::Ready(t) => self.find_last_element(t),
-                    _ => todo!("cannot handle nonstandard nodes"),
+                    // Components that aborted rendering are mounted as a placeholder
+                    RenderReturn::Aborted(placeholder) => placeholder.id.get().unwrap(),
                 }
             }
         }
@@ -1002,6 +1079,21 @@ fn templates_are_different(left_template: &VNode, right_template: &VNode) -> boo
     !std::ptr::eq(left_template_name, right_template_name)
 }
 
+/// Check if two renders of the same template can only differ in the text of their dynamic nodes
+fn only_text_changed<'a>(left_template: &VNode<'a>, right_template: &VNode<'a>) -> bool {
+    left_template.dynamic_nodes.len() == right_template.dynamic_nodes.len()
+        && left_template
+            .dynamic_nodes
+            .iter()
+            .zip(right_template.dynamic_nodes.iter())
+            .all(|nodes| matches!(nodes, (Text(_), Text(_))))
+        && left_template
+            .dynamic_attrs
+            .iter()
+            .zip(right_template.dynamic_attrs.iter())
+            .all(|(left, right)| !left.volatile && left.value == right.value)
+}
+
 fn matching_components<'a>(
     left: &'a VNode<'a>,
     right: &'a VNode<'a>,
@@ -1060,3 +1152,37 @@ fn is_dyn_node_only_child(node: &VNode, idx: usize) -> bool {
         _ => false,
     }
 }
+
+/// Buffers for diffing keyed lists that are kept on the [`VirtualDom`] and reused between diffs, so reordering a list
+/// does not allocate once the buffers have grown to fit it.
+#[derive(Default)]
+pub(crate) struct KeyedDiffScratch {
+    old_key_to_old_index: KeyMap,
+    old_is_shared: Vec<bool>,
+    new_index_to_old_index: Vec<usize>,
+    lis_sequence: Vec<usize>,
+    predecessors: Vec<usize>,
+    starts: Vec<usize>,
+}
+
+/// A map of keys to the index of their node that keeps its allocation between diffs.
+#[derive(Default)]
+struct KeyMap(FxHashMap<&'static str, usize>);
+
+impl KeyMap {
+    /// Use the map with keys that are borrowed from the nodes being diffed. The map is empty outside of this function, so
+    /// it never holds a key after the nodes are dropped.
+    fn with_keys<'a, R>(&mut self, f: impl FnOnce(&mut FxHashMap<&'a str, usize>) -> R) -> R {
+        // clear any keys left behind if the last diff panicked, without reading them
+        self.0.clear();
+        // Safety: the maps only differ in the lifetime of the keys, and the map is cleared before the borrow ends
+        let map = unsafe {
+            std::mem::transmute::<&mut FxHashMap<&'static str, usize>, &mut FxHashMap<&'a str, usize>>(
+                &mut self.0,
+            )
+        };
+        let result = f(map);
+        map.clear();
+        result
+    }
+}
@@ -1,16 +1,22 @@
 use crate::{ScopeId, ScopeState};
 use std::{
-    any::{Any, TypeId},
-    cell::RefCell,
+    any::TypeId,
+    cell::{Cell, RefCell},
     fmt::Debug,
+    rc::Rc,
 };
 
 /// A boundary that will capture any errors from child components
 pub struct ErrorBoundary {
     error: RefCell<Option<CapturedError>>,
+    handlers: RefCell<Vec<(usize, ErrorCallback)>>,
+    next_handler: Cell<usize>,
     _id: ScopeId,
 }
 
+// A callback registered with `ScopeState::on_error`
+type ErrorCallback = Rc<dyn Fn(&CapturedError)>;
+
 /// An instance of an error captured by a descendant component.
 pub struct CapturedError {
     /// The error captured by the error boundary
@@ -18,12 +24,15 @@ pub struct CapturedError {
 
     /// The scope that threw the error
     pub scope: ScopeId,
+
+    // The type of the boxed error
+    type_id: TypeId,
 }
 
 impl CapturedError {
     /// Downcast the error type into a concrete error type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
-        if TypeId::of::<T>() == self.error.type_id() {
+        if TypeId::of::<T>() == self.type_id {
             let raw = self.error.as_ref() as *const _ as *const T;
             Some(unsafe { &*raw })
         } else {
@@ -36,13 +45,62 @@ impl ErrorBoundary {
     pub fn new(id: ScopeId) -> Self {
         Self {
             error: RefCell::new(None),
+            handlers: Default::default(),
+            next_handler: Default::default(),
             _id: id,
         }
     }
 
-    /// Push an error into this Error Boundary
-    pub fn insert_error(&self, scope: ScopeId, error: Box<dyn Debug + 'static>) {
-        self.error.replace(Some(CapturedError { error, scope }));
+    /// Push an error into this Error Boundary, keeping its type so handlers can downcast it
+    pub fn insert_typed_error<E: Debug + 'static>(&self, scope: ScopeId, error: E) {
+        let error = CapturedError {
+            error: Box::new(error),
+            scope,
+            type_id: TypeId::of::<E>(),
+        };
+
+        // Handlers may add or remove other handlers, so don't hold the borrow while they run
+        let handlers: Vec<_> = self
+            .handlers
+            .borrow()
+            .iter()
+            .map(|(_, handler)| handler.clone())
+            .collect();
+        for handler in handlers {
+            handler(&error);
+        }
+
+        self.error.replace(Some(error));
+    }
+
+    pub(crate) fn add_handler(
+        self: &Rc<Self>,
+        handler: impl Fn(&CapturedError) + 'static,
+    ) -> ErrorHandler {
+        let id = self.next_handler.get();
+        self.next_handler.set(id + 1);
+        self.handlers.borrow_mut().push((id, Rc::new(handler)));
+
+        ErrorHandler {
+            boundary: self.clone(),
+            id,
+        }
+    }
+}
+
+/// A handler for the errors thrown into an error boundary, created with [`ScopeState::on_error`]. The handler is
+/// removed when this is dropped.
+pub struct ErrorHandler {
+    boundary: Rc<ErrorBoundary>,
+    id: usize,
+}
+
+impl Drop for ErrorHandler {
+    fn drop(&mut self) {
+        self.boundary
+            .handlers
+            .borrow_mut()
+            .retain(|(id, _)| *id != self.id);
     }
 }
 
@@ -1,7 +1,7 @@
-use crate::{runtime::with_runtime, ScopeId};
+use crate::{runtime::with_runtime, ElementId, ScopeId};
 use std::{
     cell::{Cell, RefCell},
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
 /// A wrapper around some generic data that handles the event's state
@@ -25,6 +25,7 @@ pub struct Event<T: 'static + ?Sized> {
     /// The data associated with this event
     pub data: Rc<T>,
     pub(crate) propagates: Rc<Cell<bool>>,
+    pub(crate) target: EventTarget,
 }
 
 impl<T> Event<T> {
@@ -80,6 +81,12 @@ impl<T> Event<T> {
     pub fn inner(&self) -> &Rc<T> {
         &self.data
     }
+
+    /// Get the element the event was dispatched to. This is the element that triggered the event, not the element
+    /// of the listener the event bubbled up to.
+    pub fn target(&self) -> &EventTarget {
+        &self.target
+    }
 }
 
 impl<T: ?Sized> Clone for Event<T> {
@@ -87,6 +94,35 @@ impl<T: ?Sized> Clone for Event<T> {
         Self {
             propagates: self.propagates.clone(),
             data: self.data.clone(),
+            target: self.target.clone(),
+        }
+    }
+}
+
+/// The element an [`Event`] was dispatched to.
+///
+/// The target can be kept after the event to check if the element is still mounted. Element ids are reused once an
+/// element is removed, but a target never refers to an element that was mounted after it.
+#[derive(Clone, Debug)]
+pub struct EventTarget {
+    pub(crate) id: ElementId,
+    pub(crate) generation: u32,
+    pub(crate) generations: Weak<RefCell<Vec<u32>>>,
+}
+
+impl EventTarget {
+    /// Get the id of the element
+    pub fn id(&self) -> ElementId {
+        self.id
+    }
+
+    /// Check if the element is still mounted in the VirtualDom
+    pub fn is_mounted(&self) -> bool {
+        // Removing an element bumps the generation of its id
+        match self.generations.upgrade() {
+            Some(generations) => generations.borrow().get(self.id.0) == Some(&self.generation),
+            // The VirtualDom was dropped
+            None => false,
         }
     }
 }
@@ -4,6 +4,8 @@
 mod any_props;
 mod arena;
 mod bump_frame;
+#[cfg(feature = "component_registry")]
+mod component_registry;
 mod create;
 mod diff;
 mod dirty_scope;
@@ -11,6 +13,7 @@ mod error_boundary;
 mod events;
 mod fragment;
 mod lazynodes;
+mod memoize;
 mod mutations;
 mod nodes;
 mod properties;
@@ -19,6 +22,7 @@ mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+mod template_registry;
 mod virtual_dom;
 
 pub(crate) mod innerlude {
@@ -28,6 +32,7 @@ pub(crate) mod innerlude {
     pub use crate::events::*;
     pub use crate::fragment::*;
     pub use crate::lazynodes::*;
+    pub use crate::memoize::*;
     pub use crate::mutations::*;
     pub use crate::nodes::RenderReturn;
     pub use crate::nodes::*;
@@ -35,6 +40,7 @@ pub(crate) mod innerlude {
     pub use crate::scheduler::*;
     pub use crate::scope_context::*;
     pub use crate::scopes::*;
+    pub use crate::template_registry::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -74,10 +80,17 @@ pub(crate) mod innerlude {
 
 pub use crate::innerlude::{
     fc_to_builder, vdom_is_rendering, AnyValue, Attribute, AttributeValue, BorrowedAttributeValue,
-    CapturedError, Component, DynamicNode, Element, ElementId, Event, Fragment, IntoDynNode,
-    LazyNodes, Mutation, Mutations, Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped,
-    TaskId, Template, TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder, VText,
-    VirtualDom,
+    CapturedError, Component, DynamicNode, Element, ElementId, ErrorHandler, Event, EventTarget,
+    Fragment, IntoDynNode, LazyNodes, Memoize, Mutation, Mutations, Properties, PtrEq,
+    RenderReturn, Scope, ScopeId, ScopeState, Scoped, SlotContent, SuspenseContext, TaskGroup,
+    TaskId, Template, TemplateAttribute, TemplateNode, TemplateRegistration, TemplateRegistry,
+    VComponent, VNode, VPlaceholder, VText, VirtualDom, VirtualSleep, VirtualTime,
+    TEMPLATE_REGISTRY,
+};
+
+#[cfg(feature = "component_registry")]
+pub use crate::component_registry::{
+    instantiate_component, ComponentRegistration, ComponentRegistry, ComponentRegistryError,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -88,9 +101,9 @@ pub mod prelude {
         consume_context, consume_context_from_scope, current_scope_id, fc_to_builder, has_context,
         provide_context, provide_context_to_scope, provide_root_context, push_future,
         remove_future, schedule_update_any, spawn, spawn_forever, suspend, throw, AnyValue,
-        Component, Element, Event, EventHandler, Fragment, IntoAttributeValue, LazyNodes,
-        Properties, Scope, ScopeId, ScopeState, Scoped, TaskId, Template, TemplateAttribute,
-        TemplateNode, Throw, VNode, VirtualDom,
+        Component, Element, Event, EventHandler, Fragment, IntoAttributeValue, LazyNodes, Memoize,
+        Properties, PtrEq, Scope, ScopeId, ScopeState, Scoped, SlotContent, TaskId, Template,
+        TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
     };
 }
 
@@ -98,4 +111,6 @@ pub mod exports {
     //! Important dependencies that are used by the rest of the library
     //! Feel free to just add the dependencies in your own Crates.toml
     pub use bumpalo;
+    #[cfg(any(feature = "template_registry", feature = "component_registry"))]
+    pub use inventory;
 }
@@ -0,0 +1,62 @@
+use std::{rc::Rc, sync::Arc};
+
+/// A custom memoization strategy for a Props type.
+///
+/// Derive Props with `#[props(memo = custom)]` to compare the props with this trait instead of [`PartialEq`]. Return
+/// true if the component does not need to rerender with the new props.
+///
+/// ```rust, ignore
+/// #[derive(Props)]
+/// #[props(memo = custom)]
+/// struct ListProps {
+///     items: Rc<Vec<String>>,
+///     version: usize,
+/// }
+///
+/// impl Memoize for ListProps {
+///     fn memoize(&self, other: &Self) -> bool {
+///         self.version == other.version
+///     }
+/// }
+/// ```
+pub trait Memoize {
+    /// Check if the props are equal for the purpose of rendering
+    fn memoize(&self, other: &Self) -> bool;
+}
+
+/// Compare two values by the address of the data they point to instead of the data itself.
+///
+/// Props fields marked with `#[props(memo = ptr)]` are compared with this trait, which is much cheaper than comparing
+/// large data behind a smart pointer. Two clones of the same [`Rc`] are equal, two [`Rc`]s with equal contents are not.
+pub trait PtrEq {
+    /// Check if both values point to the same data
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: ?Sized> PtrEq for Rc<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+impl<T: ?Sized> PtrEq for Arc<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+impl<T: ?Sized> PtrEq for &T {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        std::ptr::eq(*self, *other)
+    }
+}
+
+impl<T: PtrEq> PtrEq for Option<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
@@ -30,6 +30,12 @@ pub struct Mutations<'a> {
 
     /// Any mutations required to patch the renderer to match the layout of the VirtualDom
     pub edits: Vec<Mutation<'a>>,
+
+    /// If the update was larger than the VirtualDom's mutation budget, the rest of the edits will be returned by the
+    /// next render. Renderers should keep their stack of nodes between the two sets of edits.
+    ///
+    /// See [`VirtualDom::with_mutation_budget`](crate::VirtualDom::with_mutation_budget).
+    pub has_more: bool,
 }
 
 impl<'a> Mutations<'a> {
@@ -221,6 +227,10 @@ pub enum Mutation<'a> {
 
         /// The ID of the node to attach the listener to.
         id: ElementId,
+
+        /// The generation of the node's ID. Send it back with events so stale events can be dropped, see
+        /// [`crate::VirtualDom::handle_generational_event`].
+        generation: u32,
     },
 
     /// Remove an existing Event Listener.
@@ -243,4 +253,22 @@ pub enum Mutation<'a> {
         /// The ID of the root node to push.
         id: ElementId,
     },
+
+    /// Mark the start of the mutations produced by a component.
+    ///
+    /// This is only emitted if markers are enabled with [`crate::VirtualDom::with_component_markers`]. Renderers should
+    /// ignore it.
+    MarkerStart {
+        /// The scope of the component
+        scope: ScopeId,
+
+        /// The name of the component
+        name: &'a str,
+    },
+
+    /// Mark the end of the mutations produced by the component of the matching [`Mutation::MarkerStart`].
+    MarkerEnd {
+        /// The scope of the component
+        scope: ScopeId,
+    },
 }
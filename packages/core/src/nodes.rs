@@ -689,6 +689,70 @@ impl<'a> IntoDynNode<'a> for &Element<'a> {
     }
 }
 
+/// Named children of a component, like the `header` or `footer` of a layout.
+///
+/// Slots are optional props: a `SlotContent` field defaults to an empty slot and accepts anything that converts into a
+/// slot, like the [`Element`] returned by `render!`. Every slot is rendered into its own dynamic node of the
+/// component's template, so each slot is diffed independently of the other slots and the children.
+///
+/// ```rust, ignore
+/// #[derive(Props)]
+/// struct LayoutProps<'a> {
+///     header: SlotContent<'a>,
+///     footer: SlotContent<'a>,
+///     children: Element<'a>,
+/// }
+///
+/// fn Layout<'a>(cx: Scope<'a, LayoutProps<'a>>) -> Element<'a> {
+///     render! {
+///         header { &cx.props.header }
+///         main { &cx.props.children }
+///         footer { &cx.props.footer }
+///     }
+/// }
+///
+/// fn app(cx: Scope) -> Element {
+///     render! {
+///         Layout {
+///             header: render! { h1 { "Title" } },
+///             "Content"
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct SlotContent<'a>(Element<'a>);
+
+impl<'a> SlotContent<'a> {
+    /// Returns true if nothing was passed into this slot.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Get the nodes passed into this slot.
+    pub fn as_element(&self) -> &Element<'a> {
+        &self.0
+    }
+}
+
+impl<'a> From<Element<'a>> for SlotContent<'a> {
+    fn from(element: Element<'a>) -> Self {
+        Self(element)
+    }
+}
+
+impl<'a> From<VNode<'a>> for SlotContent<'a> {
+    fn from(node: VNode<'a>) -> Self {
+        Self(Some(node))
+    }
+}
+
+impl<'a> IntoDynNode<'a> for &SlotContent<'a> {
+    fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
+        self.as_element().into_vnode(cx)
+    }
+}
+
 impl<'a, 'b> IntoDynNode<'a> for LazyNodes<'a, 'b> {
     fn into_vnode(self, cx: &'a ScopeState) -> DynamicNode<'a> {
         DynamicNode::Fragment(cx.bump().alloc([self.call(cx)]))
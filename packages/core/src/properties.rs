@@ -32,6 +32,27 @@ use crate::innerlude::*;
 ///     data: &'a str
 /// }
 /// ```
+///
+/// ## Memoization strategies
+///
+/// The derive macro compares 'static props with [`PartialEq`] by default. The strategy can be changed per field:
+/// - `#[props(memo = ptr)]` compares the address the field points to with [`PtrEq`], for fields like `Rc<Vec<T>>`
+/// - `#[props(memo = skip)]` ignores the field
+/// - `#[props(memo = path::to::function)]` compares the field with `fn(&T, &T) -> bool`
+///
+/// Or for the whole props with `#[props(memo = custom)]`, which memoizes the props with their [`Memoize`]
+/// implementation. `#[props(hash)]` hashes the props for [`VirtualDom::with_props_hashing`].
+///
+/// ```rust, ignore
+/// // The fields are compared one by one, so the props don't need to implement PartialEq
+/// #[derive(Props)]
+/// struct TableProps {
+///     #[props(memo = ptr)]
+///     rows: Rc<Vec<Row>>,
+///     #[props(memo = skip)]
+///     on_hover: Rc<dyn Fn(usize)>,
+/// }
+/// ```
 pub trait Properties: Sized {
     /// The type of the builder for this component.
     /// Used to create "in-progress" versions of the props.
@@ -49,6 +70,14 @@ pub trait Properties: Sized {
     /// The user must know if their props are static, but if they make a mistake, UB happens
     /// Therefore it's unsafe to memoize.
     unsafe fn memoize(&self, other: &Self) -> bool;
+
+    /// A hash of the props. If [`VirtualDom::with_props_hashing`] is enabled and the hash of the new props of a
+    /// component is the same as the hash of the old props, the component is not rendered again.
+    ///
+    /// Derive Props with `#[props(hash)]` to hash the props with their [`Hash`](std::hash::Hash) implementation.
+    fn props_hash(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl Properties for () {
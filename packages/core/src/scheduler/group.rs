@@ -0,0 +1,238 @@
+use super::Scheduler;
+use crate::{innerlude::SchedulerMsg, ScopeId, TaskId};
+use futures_util::future::{poll_fn, select, LocalBoxFuture};
+use slab::Slab;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::{Rc, Weak},
+    task::{Poll, Waker},
+};
+
+/// A group of tasks that are tracked together. Created with [`crate::ScopeState::task_group`].
+///
+/// Every task in the group can be cancelled at once with [`TaskGroup::cancel`], and awaited with
+/// [`TaskGroup::join`]. When the component that created the group is unmounted, its tasks are cancelled. If the group
+/// has a shutdown timeout, the tasks are given until the timeout to finish instead.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let group = cx.use_hook(|| {
+///         cx.task_group()
+///             .with_shutdown_timeout(|| tokio::time::sleep(Duration::from_secs(1)))
+///     });
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 group.spawn(async move {
+///                     upload_file().await;
+///                 });
+///             },
+///             "Upload"
+///         }
+///         button { onclick: move |_| group.cancel(), "Cancel uploads" }
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TaskGroup {
+    inner: Rc<TaskGroupInner>,
+}
+
+pub(crate) struct TaskGroupInner {
+    // The tasks of the group hold the group, so the group can't hold the scheduler that holds the tasks
+    scheduler: Weak<Scheduler>,
+    /// The scope the tasks run in. Tasks that are still running when the scope is unmounted move to the root scope
+    /// until the shutdown timeout
+    owner: Cell<ScopeId>,
+    /// The tasks of the group that haven't finished yet
+    tasks: RefCell<Slab<Option<TaskId>>>,
+    /// Incremented every time the group is cancelled. Tasks spawned before the group was cancelled stop at their
+    /// next poll
+    generation: Cell<usize>,
+    /// The group no longer accepts tasks, because its component was unmounted
+    closed: Cell<bool>,
+    /// The futures waiting for every task in the group to finish
+    waiters: RefCell<Vec<Waker>>,
+    shutdown_timeout: RefCell<Option<ShutdownTimeout>>,
+}
+
+type ShutdownTimeout = Box<dyn FnOnce() -> LocalBoxFuture<'static, ()>>;
+
+impl TaskGroup {
+    pub(crate) fn new(owner: ScopeId, scheduler: &Rc<Scheduler>) -> Self {
+        Self {
+            inner: Rc::new(TaskGroupInner {
+                scheduler: Rc::downgrade(scheduler),
+                owner: Cell::new(owner),
+                tasks: Default::default(),
+                generation: Cell::new(0),
+                closed: Cell::new(false),
+                waiters: Default::default(),
+                shutdown_timeout: Default::default(),
+            }),
+        }
+    }
+
+    pub(crate) fn inner(&self) -> &Rc<TaskGroupInner> {
+        &self.inner
+    }
+
+    /// Give the tasks of the group until the future returned by `timeout` resolves to finish when the component is
+    /// unmounted, instead of cancelling them right away. The timeout is created when the component is unmounted.
+    pub fn with_shutdown_timeout<F: Future<Output = ()> + 'static>(
+        self,
+        timeout: impl FnOnce() -> F + 'static,
+    ) -> Self {
+        *self.inner.shutdown_timeout.borrow_mut() = Some(Box::new(move || Box::pin(timeout())));
+        self
+    }
+
+    /// Spawn a task in the group. Returns [`None`] without running the task if the component that created the group
+    /// was already unmounted, or the VirtualDom was dropped.
+    pub fn spawn(&self, task: impl Future<Output = ()> + 'static) -> Option<TaskId> {
+        let inner = &self.inner;
+        let scheduler = inner.scheduler.upgrade()?;
+        if inner.closed.get() {
+            return None;
+        }
+
+        let key = inner.tasks.borrow_mut().insert(None);
+        // The guard is moved into the task, so it is dropped when the task finishes or is removed, even if it was
+        // never polled
+        let guard = TaskGuard {
+            group: inner.clone(),
+            key,
+        };
+        let generation = inner.generation.get();
+        let mut task = Box::pin(task);
+        let id = scheduler.spawn(inner.owner.get(), async move {
+            let guard = guard;
+            poll_fn(|cx| {
+                if guard.group.generation.get() != generation {
+                    return Poll::Ready(());
+                }
+                task.as_mut().poll(cx)
+            })
+            .await
+        });
+        inner.tasks.borrow_mut()[key] = Some(id);
+        Some(id)
+    }
+
+    /// Cancel every task in the group. The tasks are dropped the next time the scheduler runs them.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// The number of tasks in the group that haven't finished yet.
+    pub fn len(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+
+    /// Check if every task in the group has finished.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait until every task in the group has finished or was cancelled.
+    pub fn join(&self) -> impl Future<Output = ()> + 'static {
+        let inner = self.inner.clone();
+        poll_fn(move |cx| {
+            if inner.tasks.borrow().is_empty() {
+                Poll::Ready(())
+            } else {
+                inner.waiters.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+impl TaskGroupInner {
+    fn cancel(&self) {
+        self.generation.set(self.generation.get() + 1);
+        let scheduler = match self.scheduler.upgrade() {
+            Some(scheduler) => scheduler,
+            None => return,
+        };
+        // Wake the tasks so they notice they were cancelled. They can't be removed here, because the group may be
+        // cancelled from one of the tasks the scheduler is running
+        for id in self.tasks.borrow().iter().filter_map(|(_, id)| *id) {
+            let _ = scheduler
+                .sender
+                .unbounded_send(SchedulerMsg::TaskNotified(id));
+        }
+    }
+
+    /// Stop accepting tasks because the owner of the group was unmounted. The running tasks are removed right away,
+    /// or moved to the root scope until the shutdown timeout. Returns true if the tasks were moved to the root scope.
+    pub(crate) fn shutdown(self: &Rc<Self>) -> bool {
+        self.closed.set(true);
+        let ids: Vec<TaskId> = self
+            .tasks
+            .borrow()
+            .iter()
+            .filter_map(|(_, id)| *id)
+            .collect();
+        let scheduler = match self.scheduler.upgrade() {
+            Some(scheduler) => scheduler,
+            None => return false,
+        };
+        if ids.is_empty() {
+            return false;
+        }
+
+        let timeout = self.shutdown_timeout.borrow_mut().take();
+        match timeout {
+            // The root scope is only unmounted when the VirtualDom is dropped, so there is nowhere to move the tasks
+            Some(timeout) if self.owner.get() != ScopeId(0) => {
+                self.owner.set(ScopeId(0));
+                {
+                    let mut tasks = scheduler.tasks.borrow_mut();
+                    for id in ids {
+                        if let Some(task) = tasks.get_mut(id.0) {
+                            task.scope = ScopeId(0);
+                        }
+                    }
+                }
+                let group = TaskGroup {
+                    inner: self.clone(),
+                };
+                scheduler.spawn(ScopeId(0), async move {
+                    select(Box::pin(group.join()), timeout()).await;
+                    group.cancel();
+                });
+                true
+            }
+            _ => {
+                for id in ids {
+                    scheduler.remove(id);
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Removes a task from its group when the task is dropped
+struct TaskGuard {
+    group: Rc<TaskGroupInner>,
+    key: usize,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let empty = {
+            let mut tasks = self.group.tasks.borrow_mut();
+            tasks.try_remove(self.key);
+            tasks.is_empty()
+        };
+        if empty {
+            for waker in self.group.waiters.take() {
+                waker.wake();
+            }
+        }
+    }
+}
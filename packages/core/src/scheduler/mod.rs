@@ -1,10 +1,16 @@
 use crate::ScopeId;
 use slab::Slab;
 
+mod group;
+mod suspense;
 mod task;
+mod virtual_time;
 mod wait;
 
+pub use group::*;
+pub use suspense::*;
 pub use task::*;
+pub use virtual_time::*;
 
 /// The type of message that can be sent to the scheduler.
 ///
@@ -1,17 +1,12 @@
-use futures_util::task::ArcWake;
-
-use super::SchedulerMsg;
-use crate::ElementId;
-use crate::{innerlude::Mutations, Element, ScopeId};
-use std::future::Future;
-use std::sync::Arc;
-use std::task::Waker;
-use std::{
-    cell::{Cell, RefCell},
-    collections::HashSet,
-};
+use crate::ScopeId;
+use std::{cell::RefCell, collections::HashSet};
 
 /// A boundary in the VirtualDom that captures all suspended components below it
+///
+/// Provide it as an `Rc<SuspenseContext>` context. Components below the boundary that call
+/// [`ScopeState::suspend`](crate::ScopeState::suspend) are tracked until they render again without suspending or are
+/// dropped. The scope of the boundary is marked dirty whenever it starts or stops waiting on suspended components.
+#[derive(Debug)]
 pub struct SuspenseContext {
     pub(crate) id: ScopeId,
     pub(crate) waiting_on: RefCell<HashSet<ScopeId>>,
@@ -26,7 +21,25 @@ impl SuspenseContext {
         }
     }
 
-    pub fn mark_suspend(&self, id: ScopeId) {
-        self.waiting_on.borrow_mut().insert(id);
+    /// The scope of the boundary
+    pub fn id(&self) -> ScopeId {
+        self.id
+    }
+
+    /// Check if any component below the boundary is suspended
+    pub fn is_suspended(&self) -> bool {
+        !self.waiting_on.borrow().is_empty()
+    }
+
+    /// Mark a component as suspended. Returns true if the boundary started waiting on suspended components.
+    pub(crate) fn mark_suspend(&self, id: ScopeId) -> bool {
+        let mut waiting_on = self.waiting_on.borrow_mut();
+        waiting_on.insert(id) && waiting_on.len() == 1
+    }
+
+    /// Mark a component as no longer suspended. Returns true if the boundary stopped waiting on suspended components.
+    pub(crate) fn mark_resolved(&self, id: ScopeId) -> bool {
+        let mut waiting_on = self.waiting_on.borrow_mut();
+        waiting_on.remove(&id) && waiting_on.is_empty()
     }
 }
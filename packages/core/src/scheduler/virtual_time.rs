@@ -0,0 +1,137 @@
+use crate::VirtualDom;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// A clock that only moves forward when it is advanced manually.
+///
+/// Tasks that sleep on a [`VirtualTime`] are woken deterministically when the clock is advanced past their deadline,
+/// which lets tests assert timeouts, debouncing and the order tasks run in without any real sleeps.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::VirtualTime;
+/// # use std::time::Duration;
+/// fn app(cx: Scope) -> Element {
+///     let time = cx.consume_context::<VirtualTime>().unwrap();
+///     let count = use_state(cx, || 0);
+///     use_effect(cx, (), |_| {
+///         to_owned![count];
+///         async move {
+///             time.sleep(Duration::from_secs(1)).await;
+///             count.set(1);
+///         }
+///     });
+///     render! { "{count}" }
+/// }
+///
+/// let time = VirtualTime::new();
+/// let mut dom = VirtualDom::new(app);
+/// dom.base_scope().provide_context(time.clone());
+/// _ = dom.rebuild();
+///
+/// time.advance_by(&mut dom, Duration::from_millis(999));
+/// assert_eq!(dioxus_ssr::render(&dom), "0");
+///
+/// time.advance_by(&mut dom, Duration::from_millis(1));
+/// _ = dom.render_immediate();
+/// assert_eq!(dioxus_ssr::render(&dom), "1");
+/// ```
+#[derive(Clone, Default)]
+pub struct VirtualTime {
+    timers: Rc<RefCell<Timers>>,
+}
+
+#[derive(Default)]
+struct Timers {
+    now: Duration,
+    next_id: usize,
+    // Ordered by deadline, then by the order the sleeps were created in
+    pending: BTreeMap<(Duration, usize), Waker>,
+}
+
+impl VirtualTime {
+    /// Create a new clock starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The time that has passed since the clock was created.
+    pub fn now(&self) -> Duration {
+        self.timers.borrow().now
+    }
+
+    /// Wait until the clock has been advanced by at least `duration`.
+    pub fn sleep(&self, duration: Duration) -> VirtualSleep {
+        let mut timers = self.timers.borrow_mut();
+        let id = timers.next_id;
+        timers.next_id += 1;
+        VirtualSleep {
+            timers: self.timers.clone(),
+            key: (timers.now + duration, id),
+        }
+    }
+
+    /// Advance the clock by `duration`, running every task in the [`VirtualDom`] that becomes ready along the way.
+    ///
+    /// Timers fire in the order of their deadlines. Tasks woken by a timer are polled before the next timer fires, so
+    /// a task that sleeps again will fire again if its new deadline is still within `duration`.
+    ///
+    /// This does not render the components the tasks marked as dirty. Call [`VirtualDom::render_immediate`] afterwards.
+    pub fn advance_by(&self, dom: &mut VirtualDom, duration: Duration) {
+        let target = self.now() + duration;
+        dom.process_events();
+
+        loop {
+            let waker = {
+                let mut timers = self.timers.borrow_mut();
+                match timers.pending.keys().next().copied() {
+                    Some(key) if key.0 <= target => {
+                        timers.now = key.0;
+                        timers.pending.remove(&key)
+                    }
+                    _ => break,
+                }
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+            dom.process_events();
+        }
+
+        self.timers.borrow_mut().now = target;
+        dom.process_events();
+    }
+}
+
+/// A future returned by [`VirtualTime::sleep`] that completes once the clock reaches its deadline.
+pub struct VirtualSleep {
+    timers: Rc<RefCell<Timers>>,
+    key: (Duration, usize),
+}
+
+impl Future for VirtualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut timers = self.timers.borrow_mut();
+        if timers.now >= self.key.0 {
+            timers.pending.remove(&self.key);
+            return Poll::Ready(());
+        }
+        timers.pending.insert(self.key, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for VirtualSleep {
+    fn drop(&mut self) {
+        self.timers.borrow_mut().pending.remove(&self.key);
+    }
+}
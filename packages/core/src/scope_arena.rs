@@ -1,12 +1,14 @@
 use crate::{
     any_props::AnyProps,
     bump_frame::BumpFrame,
-    innerlude::DirtyScope,
+    innerlude::{DirtyScope, SuspenseContext},
     nodes::RenderReturn,
+    runtime::RuntimeGuard,
     scope_context::ScopeContext,
     scopes::{ScopeId, ScopeState},
     virtual_dom::VirtualDom,
 };
+use std::rc::Rc;
 
 impl VirtualDom {
     pub(super) fn new_scope(
@@ -36,6 +38,7 @@ impl VirtualDom {
 
             borrowed_props: Default::default(),
             attributes_to_drop: Default::default(),
+            props_hash: Default::default(),
         }));
 
         let context =
@@ -64,6 +67,10 @@ impl VirtualDom {
             let props: &dyn AnyProps = scope.props.as_ref().unwrap().as_ref();
             let props: &dyn AnyProps = std::mem::transmute(props);
 
+            if self.props_hashing {
+                scope.props_hash.set(props.props_hash());
+            }
+
             props.render(scope).extend_lifetime()
         };
 
@@ -86,19 +93,42 @@ impl VirtualDom {
             id: context.id,
         });
 
-        if context.suspended.get() {
-            if matches!(allocated, RenderReturn::Aborted(_)) {
-                self.suspended_scopes.insert(context.id);
-            }
-        } else if !self.suspended_scopes.is_empty() {
-            _ = self.suspended_scopes.remove(&context.id);
-        }
+        let id = context.id;
+        let suspended = context.suspended.get();
+        drop(context);
 
         // rebind the lifetime now that its stored internally
         let result = unsafe { allocated.extend_lifetime_ref() };
 
+        if suspended {
+            if matches!(result, RenderReturn::Aborted(_)) && self.suspended_scopes.insert(id) {
+                self.update_suspense_boundary(id, true);
+            }
+        } else if !self.suspended_scopes.is_empty() && self.suspended_scopes.remove(&id) {
+            self.update_suspense_boundary(id, false);
+        }
+
         self.runtime.scope_stack.borrow_mut().pop();
 
         result
     }
+
+    /// Tell the suspense boundary of a scope that the scope started or stopped being suspended
+    pub(crate) fn update_suspense_boundary(&mut self, id: ScopeId, suspended: bool) {
+        let boundary = {
+            let _runtime = RuntimeGuard::new(self.runtime.clone());
+            self.scopes[id.0]
+                .context()
+                .consume_context::<Rc<SuspenseContext>>()
+        };
+        if let Some(boundary) = boundary {
+            let changed = match suspended {
+                true => boundary.mark_suspend(id),
+                false => boundary.mark_resolved(id),
+            };
+            if changed {
+                self.mark_dirty(boundary.id);
+            }
+        }
+    }
 }
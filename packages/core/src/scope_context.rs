@@ -1,5 +1,5 @@
 use crate::{
-    innerlude::{ErrorBoundary, Scheduler, SchedulerMsg},
+    innerlude::{ErrorBoundary, Scheduler, SchedulerMsg, TaskGroup, TaskGroupInner},
     runtime::{with_current_scope, with_runtime},
     Element, ScopeId, TaskId,
 };
@@ -9,7 +9,7 @@ use std::{
     cell::{Cell, RefCell},
     fmt::Debug,
     future::Future,
-    rc::Rc,
+    rc::{Rc, Weak},
     sync::Arc,
 };
 
@@ -29,6 +29,7 @@ pub(crate) struct ScopeContext {
 
     pub(crate) tasks: Rc<Scheduler>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<TaskId>>,
+    pub(crate) task_groups: RefCell<Vec<Weak<TaskGroupInner>>>,
 }
 
 impl ScopeContext {
@@ -48,6 +49,7 @@ impl ScopeContext {
             shared_contexts: RefCell::new(vec![]),
             tasks,
             spawned_tasks: RefCell::new(FxHashSet::default()),
+            task_groups: RefCell::new(Vec::new()),
         }
     }
 
@@ -223,13 +225,23 @@ impl ScopeContext {
         self.tasks.remove(id);
     }
 
+    /// Create a [`TaskGroup`] whose tasks run in this scope and are shut down when this component is unmounted
+    pub fn task_group(&self) -> TaskGroup {
+        let group = TaskGroup::new(self.id, &self.tasks);
+        let mut groups = self.task_groups.borrow_mut();
+        // groups without tasks or handles have nothing to shut down
+        groups.retain(|group| group.strong_count() > 0);
+        groups.push(Rc::downgrade(group.inner()));
+        group
+    }
+
     /// Inject an error into the nearest error boundary and quit rendering
     ///
     /// The error doesn't need to implement Error or any specific traits since the boundary
     /// itself will downcast the error into a trait object.
     pub fn throw(&self, error: impl Debug + 'static) -> Option<()> {
         if let Some(cx) = self.consume_context::<Rc<ErrorBoundary>>() {
-            cx.insert_error(self.scope_id(), Box::new(error));
+            cx.insert_typed_error(self.scope_id(), error);
         }
 
         // Always return none during a throw
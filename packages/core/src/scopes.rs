@@ -2,13 +2,13 @@ use crate::{
     any_props::AnyProps,
     any_props::VProps,
     bump_frame::BumpFrame,
-    innerlude::ErrorBoundary,
+    innerlude::{CapturedError, ErrorBoundary, ErrorHandler},
     innerlude::{DynamicNode, EventHandler, VComponent, VText},
     lazynodes::LazyNodes,
     nodes::{IntoAttributeValue, IntoDynNode, RenderReturn},
     runtime::Runtime,
     scope_context::ScopeContext,
-    AnyValue, Attribute, AttributeValue, Element, Event, Properties, TaskId,
+    AnyValue, Attribute, AttributeValue, Element, Event, Properties, TaskGroup, TaskId,
 };
 use bumpalo::{boxed::Box as BumpBox, Bump};
 use std::{
@@ -82,15 +82,28 @@ pub struct ScopeState {
     pub(crate) attributes_to_drop: RefCell<Vec<*const Attribute<'static>>>,
 
     pub(crate) props: Option<Box<dyn AnyProps<'static>>>,
+
+    // The hash of the props the scope last rendered with, if props hashing is enabled
+    pub(crate) props_hash: Cell<Option<u64>>,
 }
 
 impl Drop for ScopeState {
     fn drop(&mut self) {
+        self.drop_hooks();
         self.runtime.remove_context(self.context_id);
     }
 }
 
 impl<'src> ScopeState {
+    /// Drop the hooks in the reverse order they were created in, so every hook can still rely on the hooks created
+    /// before it while it is dropped
+    pub(crate) fn drop_hooks(&mut self) {
+        let hooks = self.hooks.get_mut();
+        while let Some(hook) = hooks.pop() {
+            drop(hook);
+        }
+    }
+
     pub(crate) fn context(&self) -> Ref<'_, ScopeContext> {
         self.runtime.get_context(self.context_id).unwrap()
     }
@@ -317,6 +330,16 @@ impl<'src> ScopeState {
         self.context().remove_future(id);
     }
 
+    /// Create a group of tasks that are tracked together, and can be cancelled or awaited as a unit.
+    ///
+    /// The tasks are cancelled when this component is unmounted, unless the group has a shutdown timeout. Then they
+    /// are given until the timeout to finish, so work like saving a draft isn't cut off. See [`TaskGroup`].
+    ///
+    /// This creates a new group every time it is called, so create the group in a hook.
+    pub fn task_group(&self) -> TaskGroup {
+        self.context().task_group()
+    }
+
     /// Take a lazy [`crate::VNode`] structure and actually build it with the context of the efficient [`bumpalo::Bump`] allocator.
     ///
     /// ## Example
@@ -427,7 +450,7 @@ impl<'src> ScopeState {
         P: Properties + 'child,
         'src: 'child,
     {
-        let vcomp = VProps::new(component, P::memoize, props);
+        let vcomp = VProps::new(component, P::memoize, P::props_hash, props);
 
         // cast off the lifetime of the render return
         let as_dyn: Box<dyn AnyProps<'child> + '_> = Box::new(vcomp);
@@ -470,6 +493,7 @@ impl<'src> ScopeState {
                     callback(Event {
                         propagates: event.propagates,
                         data,
+                        target: event.target,
                     });
                 }
             }))
@@ -495,13 +519,39 @@ impl<'src> ScopeState {
     /// itself will downcast the error into a trait object.
     pub fn throw(&self, error: impl Debug + 'static) -> Option<()> {
         if let Some(cx) = self.consume_context::<Rc<ErrorBoundary>>() {
-            cx.insert_error(self.scope_id(), Box::new(error));
+            cx.insert_typed_error(self.scope_id(), error);
         }
 
         // Always return none during a throw
         None
     }
 
+    /// Call `handler` with every error thrown into the nearest error boundary, until the returned [`ErrorHandler`] is
+    /// dropped.
+    ///
+    /// This lets an app react to errors centrally, like redirecting to a login page whenever a component throws an
+    /// authorization error:
+    ///
+    /// ```rust, ignore
+    /// fn app(cx: Scope) -> Element {
+    ///     cx.use_hook(|| {
+    ///         cx.on_error(|error| {
+    ///             if let Some(AuthError::Unauthorized) = error.downcast::<AuthError>() {
+    ///                 redirect_to_login();
+    ///             }
+    ///         })
+    ///     });
+    ///
+    ///     render! { Dashboard {} }
+    /// }
+    /// ```
+    ///
+    /// Returns `None` if there is no error boundary above this component.
+    pub fn on_error(&self, handler: impl Fn(&CapturedError) + 'static) -> Option<ErrorHandler> {
+        let boundary = self.consume_context::<Rc<ErrorBoundary>>()?;
+        Some(boundary.add_handler(handler))
+    }
+
     /// Mark this component as suspended and then return None
     pub fn suspend(&self) -> Option<Element> {
         let cx = self.context();
@@ -515,6 +565,8 @@ impl<'src> ScopeState {
     ///
     /// When the component is unmounted (removed from the UI), the value is dropped. This means you can return a custom type and provide cleanup code by implementing the [`Drop`] trait
     ///
+    /// Hooks are dropped after the children of the component, in the reverse order they were created in. A hook can rely on the values of hooks created before it while it is dropped.
+    ///
     /// # Example
     ///
     /// ```
@@ -551,4 +603,32 @@ impl<'src> ScopeState {
                 "#,
             )
     }
+
+    /// Run some cleanup logic exactly once when the component is unmounted.
+    ///
+    /// The closure is run when the hook is dropped, so it follows the drop order of [`ScopeState::use_hook`]: cleanup
+    /// logic registered later runs first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dioxus_core::ScopeState;
+    ///
+    /// pub fn use_goodbye_world(cx: &ScopeState) {
+    ///     cx.use_drop(|| println!("Goodbye, world!"));
+    /// }
+    /// ```
+    pub fn use_drop(&self, on_drop: impl FnOnce() + 'static) {
+        struct OnDrop<F: FnOnce()>(Option<F>);
+
+        impl<F: FnOnce()> Drop for OnDrop<F> {
+            fn drop(&mut self) {
+                if let Some(on_drop) = self.0.take() {
+                    on_drop();
+                }
+            }
+        }
+
+        self.use_hook(|| OnDrop(Some(on_drop)));
+    }
 }
@@ -0,0 +1,78 @@
+use crate::Template;
+
+/// Every template created by the `rsx!` macro in the final binary.
+///
+/// The macro registers each template it creates at compile time when the `template_registry` feature is enabled.
+/// Renderers can iterate the registry at startup to create all of the templates ahead of time instead of waiting for
+/// the first time each template is rendered.
+///
+/// ```rust, ignore
+/// for template in TEMPLATE_REGISTRY.iter() {
+///     renderer.create_template(template);
+/// }
+/// ```
+///
+/// If the feature is disabled, the registry is always empty.
+pub static TEMPLATE_REGISTRY: TemplateRegistry = TemplateRegistry { _private: () };
+
+/// The type of [`TEMPLATE_REGISTRY`].
+pub struct TemplateRegistry {
+    _private: (),
+}
+
+impl TemplateRegistry {
+    /// Iterate over all of the registered templates.
+    ///
+    /// Templates are deduplicated by name, so each template is only returned once even if the same template was registered more than once.
+    pub fn iter(&self) -> impl Iterator<Item = Template<'static>> {
+        let mut seen = rustc_hash::FxHashSet::default();
+        self.registrations()
+            .filter(move |template| seen.insert(template.name))
+    }
+
+    /// Check if no templates were registered
+    pub fn is_empty(&self) -> bool {
+        self.registrations().next().is_none()
+    }
+
+    #[cfg(feature = "template_registry")]
+    fn registrations(&self) -> impl Iterator<Item = Template<'static>> {
+        inventory::iter::<TemplateRegistration>
+            .into_iter()
+            .map(|registration| *registration.template)
+    }
+
+    #[cfg(not(feature = "template_registry"))]
+    fn registrations(&self) -> impl Iterator<Item = Template<'static>> {
+        std::iter::empty()
+    }
+}
+
+/// A template registered by the `rsx!` macro. Use [`TEMPLATE_REGISTRY`] to read the registered templates.
+#[doc(hidden)]
+pub struct TemplateRegistration {
+    pub template: &'static Template<'static>,
+}
+
+#[cfg(feature = "template_registry")]
+inventory::collect!(TemplateRegistration);
+
+/// Add a template to the [`TEMPLATE_REGISTRY`]. This is called by the `rsx!` macro.
+#[cfg(feature = "template_registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! register_template {
+    ($template:ident) => {
+        $crate::exports::inventory::submit! {
+            $crate::TemplateRegistration { template: &$template }
+        }
+    };
+}
+
+/// Add a template to the [`TEMPLATE_REGISTRY`]. This is called by the `rsx!` macro.
+#[cfg(not(feature = "template_registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! register_template {
+    ($template:ident) => {};
+}
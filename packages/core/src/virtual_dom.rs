@@ -4,7 +4,8 @@
 
 use crate::{
     any_props::VProps,
-    arena::{ElementId, ElementRef},
+    arena::{ElementArena, ElementId, ElementRef},
+    diff::KeyedDiffScratch,
     innerlude::{DirtyScope, ErrorBoundary, Mutations, Scheduler, SchedulerMsg},
     mutations::Mutation,
     nodes::RenderReturn,
@@ -183,7 +184,7 @@ pub struct VirtualDom {
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
 
     // Every element is actually a dual reference - one to the template and the other to the dynamic node in that template
-    pub(crate) elements: Slab<ElementRef>,
+    pub(crate) elements: ElementArena,
 
     pub(crate) mutations: Mutations<'static>,
 
@@ -193,6 +194,19 @@ pub struct VirtualDom {
     pub(crate) suspended_scopes: FxHashSet<ScopeId>,
 
     pub(crate) rx: futures_channel::mpsc::UnboundedReceiver<SchedulerMsg>,
+
+    // Wrap the mutations of every component in markers for devtools
+    pub(crate) component_markers: bool,
+
+    // The most edits a single render can return, and the edits of the last render that did not fit
+    pub(crate) mutation_budget: Option<usize>,
+    pub(crate) spillover: Vec<Mutation<'static>>,
+
+    // Skip rendering components whose props hash did not change
+    pub(crate) props_hashing: bool,
+
+    // Buffers reused by keyed diffs, one for each level of nested keyed lists being diffed
+    pub(crate) keyed_diff_scratch: Vec<KeyedDiffScratch>,
 }
 
 impl VirtualDom {
@@ -262,10 +276,20 @@ impl VirtualDom {
             elements: Default::default(),
             mutations: Mutations::default(),
             suspended_scopes: Default::default(),
+            component_markers: false,
+            mutation_budget: None,
+            spillover: Vec::new(),
+            props_hashing: false,
+            keyed_diff_scratch: Vec::new(),
         };
 
         let root = dom.new_scope(
-            Box::new(VProps::new(root, |_, _| unreachable!(), root_props)),
+            Box::new(VProps::new(
+                root,
+                |_, _| unreachable!(),
+                |_| None,
+                root_props,
+            )),
             "app",
         );
 
@@ -300,6 +324,41 @@ impl VirtualDom {
         self
     }
 
+    /// Wrap the mutations created or diffed by every component in [`Mutation::MarkerStart`] and [`Mutation::MarkerEnd`]
+    /// so devtools and mutation recorders can attribute changes in the DOM to components.
+    ///
+    /// Markers are disabled by default. Renderers ignore them, so they only cost the size of the mutation stream.
+    pub fn with_component_markers(mut self, enabled: bool) -> Self {
+        self.component_markers = enabled;
+        self
+    }
+
+    /// Limit the number of edits a single render can return.
+    ///
+    /// Large updates (like swapping out every row of a big table) can produce more edits than a renderer can apply in
+    /// one frame. With a budget, [`VirtualDom::render_with_deadline`] and [`VirtualDom::render_immediate`] only return
+    /// the first `budget` edits and set [`Mutations::has_more`]. The rest of the edits are returned by the next renders,
+    /// which will not run any components until every edit of the update has been returned.
+    ///
+    /// The budget does not apply to [`VirtualDom::rebuild`]. There is no budget by default.
+    pub fn with_mutation_budget(mut self, budget: usize) -> Self {
+        self.mutation_budget = Some(budget.max(1));
+        self
+    }
+
+    /// Skip rendering components whose props hash to the same value as their last props.
+    ///
+    /// Props opt into hashing with `#[props(hash)]`, see [`Properties::props_hash`]. For props that hash, the hash
+    /// replaces the memoization strategy of the props, which is useful when comparing the props is more expensive than
+    /// hashing them. Props that are not hashed are still memoized as usual. Hashing is disabled by default.
+    ///
+    /// Two different props could hash to the same value, in which case the component would not render the new props.
+    /// This is extremely unlikely, but only enable hashing if that is acceptable for your app.
+    pub fn with_props_hashing(mut self, enabled: bool) -> Self {
+        self.props_hashing = enabled;
+        self
+    }
+
     /// Manually mark a scope as requiring a re-render
     ///
     /// Whenever the Runtime "works", it will re-render this scope
@@ -310,6 +369,27 @@ impl VirtualDom {
         }
     }
 
+    /// Call a listener inside the VirtualDom with data from outside the VirtualDom, unless the listener's element was
+    /// removed since the listener was created.
+    ///
+    /// `generation` is the generation sent with the [`crate::Mutation::NewEventListener`] of the listener. If the element
+    /// id was reused for another element in the meantime, the event is stale and dropped.
+    pub fn handle_generational_event(
+        &mut self,
+        name: &str,
+        data: Rc<dyn Any>,
+        element: ElementId,
+        generation: u32,
+        bubbles: bool,
+    ) {
+        if self.elements.generation(element) != Some(generation) {
+            log::debug!("Dropping stale {name} event for {element:?} from generation {generation}");
+            return;
+        }
+
+        self.handle_event(name, data, element, bubbles)
+    }
+
     /// Call a listener inside the VirtualDom with data from outside the VirtualDom.
     ///
     /// This method will identify the appropriate element. The data must match up with the listener delcared. Note that
@@ -349,13 +429,14 @@ impl VirtualDom {
         | | |       <-- no, broke early
         |           <-- no, broke early
         */
-        let mut parent_path = self.elements.get(element.0);
+        let mut parent_path = self.elements.get(element);
         let mut listeners = vec![];
 
         // We will clone this later. The data itself is wrapped in RC to be used in callbacks if required
         let uievent = Event {
             propagates: Rc::new(Cell::new(bubbles)),
             data,
+            target: self.elements.target(element),
         };
 
         // If the event bubbles, we traverse through the tree until we find the target element.
@@ -405,7 +486,7 @@ impl VirtualDom {
                         }
                     }
 
-                    parent_path = template.parent.and_then(|id| self.elements.get(id.0));
+                    parent_path = template.parent.and_then(|id| self.elements.get(id));
                 } else {
                     break;
                 }
@@ -461,6 +542,11 @@ impl VirtualDom {
     /// let sender = dom.get_scheduler_channel();
     /// ```
     pub async fn wait_for_work(&mut self) {
+        // The edits of the last render need to be returned before anything else
+        if !self.spillover.is_empty() {
+            return;
+        }
+
         let mut some_msg = None;
 
         loop {
@@ -525,6 +611,20 @@ impl VirtualDom {
         }
     }
 
+    /// Register every template in the [`TEMPLATE_REGISTRY`](crate::TEMPLATE_REGISTRY) ahead of time, returning the mutations that create them.
+    ///
+    /// Renderers can call this before [`VirtualDom::rebuild`] to create all of the templates in the app at startup.
+    /// Templates registered here will not be sent again when they are first rendered.
+    ///
+    /// If the `template_registry` feature is disabled, no templates are registered.
+    pub fn prebake_templates(&mut self) -> Mutations {
+        for template in crate::TEMPLATE_REGISTRY.iter() {
+            self.register_template(template);
+        }
+
+        self.finalize()
+    }
+
     /// Performs a *full* rebuild of the virtual dom, returning every edit required to generate the actual dom from scratch.
     ///
     /// The mutations item expects the RealDom's stack to be the root of the application.
@@ -612,6 +712,15 @@ impl VirtualDom {
     pub async fn render_with_deadline(&mut self, deadline: impl Future<Output = ()>) -> Mutations {
         pin_mut!(deadline);
 
+        // Finish returning the edits of the last render before we run any components that could invalidate them
+        if !self.spillover.is_empty() {
+            let mutations = Mutations {
+                edits: std::mem::take(&mut self.spillover),
+                ..Default::default()
+            };
+            return self.split_at_budget(mutations);
+        }
+
         self.process_events();
 
         loop {
@@ -649,7 +758,8 @@ impl VirtualDom {
             if let Either::Left((_, _)) = select(&mut deadline, pinned).await {
                 // release the borrowed
                 drop(work);
-                return self.finalize();
+                let mutations = std::mem::take(&mut self.mutations);
+                return self.split_at_budget(mutations);
             }
         }
     }
@@ -658,6 +768,17 @@ impl VirtualDom {
     fn finalize(&mut self) -> Mutations {
         std::mem::take(&mut self.mutations)
     }
+
+    /// Save any edits over the mutation budget for the next render
+    fn split_at_budget(&mut self, mut mutations: Mutations<'static>) -> Mutations {
+        if let Some(budget) = self.mutation_budget {
+            if mutations.edits.len() > budget {
+                self.spillover = mutations.edits.split_off(budget);
+                mutations.has_more = true;
+            }
+        }
+        mutations
+    }
 }
 
 impl Drop for VirtualDom {
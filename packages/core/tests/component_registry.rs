@@ -0,0 +1,92 @@
+#![cfg(feature = "component_registry")]
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_core::{ComponentRegistry, ComponentRegistryError};
+use serde::Deserialize;
+use serde_json::json;
+use std::rc::Rc;
+
+#[derive(Props, PartialEq, Deserialize)]
+struct HeroProps {
+    title: String,
+}
+
+fn Hero(cx: Scope<HeroProps>) -> Element {
+    render! { h1 { "{cx.props.title}" } }
+}
+
+fn Footer(cx: Scope) -> Element {
+    render! { footer { "footer" } }
+}
+
+dioxus_core::register_component!("footer", Footer);
+
+// Render the page from a layout, the way a CMS would describe it
+fn app(cx: Scope<serde_json::Value>) -> Element {
+    let registry = cx.use_hook(|| {
+        let mut registry = ComponentRegistry::new();
+        registry.register("hero", Hero);
+        Rc::new(registry)
+    });
+    let sections = cx.props.as_array().unwrap().iter().map(|section| {
+        registry
+            .instantiate(
+                cx,
+                section["type"].as_str().unwrap(),
+                section["props"].clone(),
+            )
+            .unwrap()
+    });
+    render! { sections }
+}
+
+#[test]
+fn components_are_instantiated_by_name() {
+    let layout = json!([
+        { "type": "hero", "props": { "title": "Welcome" } },
+        { "type": "footer", "props": null },
+    ]);
+    let mut dom = VirtualDom::new_with_props(app, layout);
+    _ = dom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<h1>Welcome</h1><footer>footer</footer>"
+    );
+}
+
+#[test]
+fn registered_names() {
+    let mut registry = ComponentRegistry::new();
+    assert!(registry.contains("footer"));
+    assert!(!registry.contains("hero"));
+
+    registry.register("hero", Hero);
+    let mut names: Vec<_> = registry.names().collect();
+    names.sort_unstable();
+    assert_eq!(names, ["footer", "hero"]);
+
+    assert!(registry.unregister("hero"));
+    assert!(!ComponentRegistry::empty().contains("footer"));
+}
+
+#[test]
+fn instantiate_errors() {
+    fn app(cx: Scope) -> Element {
+        let mut registry = ComponentRegistry::empty();
+        registry.register("hero", Hero);
+
+        assert!(matches!(
+            registry.instantiate(cx, "missing", json!({})),
+            Err(ComponentRegistryError::NotRegistered(name)) if name == "missing"
+        ));
+        assert!(matches!(
+            registry.instantiate(cx, "hero", json!({ "title": 1 })),
+            Err(ComponentRegistryError::InvalidProps { name: "hero", .. })
+        ));
+        None
+    }
+
+    _ = VirtualDom::new(app).rebuild();
+}
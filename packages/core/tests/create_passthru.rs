@@ -104,3 +104,47 @@ fn dynamic_node_as_root() {
         ]
     )
 }
+
+/// Named slots render in their own place and are diffed independently
+#[test]
+fn slots_are_diffed_independently() {
+    fn app(cx: Scope) -> Element {
+        let count = cx.generation();
+        cx.render(rsx! {
+            slot_layout {
+                header: render! { "header {count}" },
+                "body"
+            }
+        })
+    }
+
+    #[derive(Props)]
+    struct LayoutProps<'a> {
+        header: SlotContent<'a>,
+        footer: SlotContent<'a>,
+        children: Element<'a>,
+    }
+
+    fn slot_layout<'a>(cx: Scope<'a, LayoutProps<'a>>) -> Element<'a> {
+        assert!(!cx.props.header.is_empty());
+        assert!(cx.props.footer.is_empty());
+        cx.render(rsx! {
+            div { &cx.props.header }
+            div { &cx.props.children }
+            div { &cx.props.footer }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        "<div>header 0</div><div>body</div><div></div>"
+    );
+
+    dom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [SetText { value: "header 1", id: ElementId(2) }]
+    );
+}
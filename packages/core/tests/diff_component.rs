@@ -1,3 +1,5 @@
+#![allow(non_snake_case)]
+
 use dioxus::core::{ElementId, Mutation::*};
 use dioxus::prelude::*;
 
@@ -102,3 +104,47 @@ fn component_swap() {
         ]
     );
 }
+
+#[test]
+fn component_markers_wrap_mutations() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! { Child {} })
+    }
+
+    fn Child(cx: Scope) -> Element {
+        let count = cx.generation();
+        cx.render(rsx! { div { "{count}" } })
+    }
+
+    let mut dom = VirtualDom::new(app).with_component_markers(true);
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            MarkerStart { scope: ScopeId(0), name: "app" },
+            MarkerStart { scope: ScopeId(1), name: "Child" },
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            HydrateText { path: &[0], value: "0", id: ElementId(2) },
+            MarkerEnd { scope: ScopeId(1) },
+            MarkerEnd { scope: ScopeId(0) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+
+    dom.mark_dirty(ScopeId(1));
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            MarkerStart { scope: ScopeId(1), name: "Child" },
+            SetText { value: "1", id: ElementId(2) },
+            MarkerEnd { scope: ScopeId(1) },
+        ]
+    );
+
+    // Markers are disabled by default
+    let mut dom = VirtualDom::new(app);
+    assert!(!dom
+        .rebuild()
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, MarkerStart { .. } | MarkerEnd { .. })));
+}
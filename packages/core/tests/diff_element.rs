@@ -1,3 +1,4 @@
+use bumpalo::Bump;
 use dioxus::core::Mutation::*;
 use dioxus::prelude::*;
 use dioxus_core::ElementId;
@@ -31,6 +32,48 @@ fn text_diff() {
     );
 }
 
+#[test]
+fn text_only_diff_keeps_attributes_and_listeners() {
+    fn app(cx: Scope) -> Element {
+        let gen = cx.generation();
+        let class = if gen < 2 { "small" } else { "large" };
+        cx.render(rsx!(
+            button { class: "{class}", onclick: |_| {}, "count {gen}" }
+            "total {gen}"
+        ))
+    }
+
+    let mut vdom = VirtualDom::new(app);
+    let bump = Bump::new();
+    _ = vdom.rebuild();
+
+    // only the text changed, so only the text is set
+    vdom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        vdom.render_immediate().edits,
+        [
+            SetText { value: "count 1", id: ElementId(2) },
+            SetText { value: "total 1", id: ElementId(3) },
+        ]
+    );
+
+    // an attribute changed with the text, so the attribute is set too
+    vdom.mark_dirty(ScopeId(0));
+    assert_eq!(
+        vdom.render_immediate().edits,
+        [
+            SetAttribute {
+                name: "class",
+                value: (&*bump.alloc("large".into_value(&bump))).into(),
+                id: ElementId(1),
+                ns: None
+            },
+            SetText { value: "count 2", id: ElementId(2) },
+            SetText { value: "total 2", id: ElementId(3) },
+        ]
+    );
+}
+
 #[test]
 fn element_swap() {
     fn app(cx: Scope) -> Element {
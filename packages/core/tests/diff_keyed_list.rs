@@ -356,3 +356,38 @@ fn no_common_keys() {
         ]
     );
 }
+
+/// Keyed lists nested in a keyed list are diffed while the outer list is being diffed
+#[test]
+fn nested_keyed_lists_reorder() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1, 2, 3],
+            1 => &[0, 2, 1, 3],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!(order.iter().map(|group| rsx!(div {
+            key: "{group}",
+            order.iter().map(|i| rsx!(span { key: "{i}" }))
+        }))))
+    });
+
+    _ = dom.rebuild();
+
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId(0));
+        let edits = dom.render_immediate().santize().edits;
+        // one move in the outer list and one move in each of the inner lists
+        assert_eq!(
+            edits
+                .iter()
+                .filter(|edit| matches!(edit, InsertBefore { m: 1, .. }))
+                .count(),
+            5
+        );
+        assert!(edits
+            .iter()
+            .all(|edit| matches!(edit, PushRoot { .. } | InsertBefore { m: 1, .. })));
+    }
+}
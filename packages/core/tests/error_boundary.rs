@@ -1,6 +1,10 @@
 #![allow(non_snake_case)]
 
 use dioxus::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 #[test]
 fn catches_panic() {
@@ -32,3 +36,62 @@ fn ThrowChild(cx: Scope) -> Element {
         div {}
     })
 }
+
+#[test]
+fn error_handlers_see_typed_errors() {
+    #[derive(Debug, PartialEq)]
+    enum AuthError {
+        Unauthorized,
+    }
+
+    fn app(cx: Scope) -> Element {
+        let seen = cx.use_hook(|| Rc::new(RefCell::new(Vec::new())));
+        let handler = cx.use_hook(|| {
+            let seen = seen.clone();
+            cx.on_error(move |error| {
+                seen.borrow_mut()
+                    .push(error.downcast::<AuthError>() == Some(&AuthError::Unauthorized));
+            })
+        });
+        assert!(handler.is_some());
+        cx.provide_context(seen.clone());
+
+        cx.render(rsx! { ThrowsAuthError {} })
+    }
+
+    fn ThrowsAuthError(cx: Scope) -> Element {
+        cx.throw(AuthError::Unauthorized)?;
+        cx.throw("not an auth error")?;
+        None
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let seen = dom
+        .base_scope()
+        .consume_context::<Rc<RefCell<Vec<bool>>>>()
+        .unwrap();
+    // The first throw returns early, so only the auth error reaches the handler
+    assert_eq!(*seen.borrow(), [true]);
+}
+
+#[test]
+fn dropped_error_handlers_are_removed() {
+    fn app(cx: Scope) -> Element {
+        let calls = Rc::new(Cell::new(0));
+        let handler = cx.on_error({
+            let calls = calls.clone();
+            move |_| calls.set(calls.get() + 1)
+        });
+        cx.throw("first");
+        drop(handler);
+        cx.throw("second");
+        assert_eq!(calls.get(), 1);
+
+        None
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+}
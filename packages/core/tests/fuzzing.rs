@@ -341,7 +341,7 @@ fn diff() {
             {
                 let muts = vdom.render_immediate();
                 for mut_ in muts.edits {
-                    if let Mutation::NewEventListener { name, id } = mut_ {
+                    if let Mutation::NewEventListener { name, id, .. } = mut_ {
                         println!("new event listener on {:?} for {:?}", id, name);
                         event_listeners.insert(id);
                     }
@@ -2,7 +2,7 @@
 #![allow(non_snake_case)]
 
 //! Tests for the lifecycle of components.
-use dioxus::core::{ElementId, Mutation::*};
+use dioxus::core::{ElementId, Mutation, Mutation::*};
 use dioxus::prelude::*;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -70,6 +70,58 @@ fn events_generate() {
     )
 }
 
+#[test]
+fn stale_events_are_dropped() {
+    fn app(cx: Scope) -> Element {
+        let count = cx.use_hook(|| 0);
+
+        match *count % 2 {
+            0 => cx.render(rsx! { button { onclick: move |_| *count += 1, "even" } }),
+            _ => cx.render(rsx! { div { onclick: move |_| *count += 1, "odd" } }),
+        }
+    };
+
+    fn listener_of(edits: &[Mutation]) -> (ElementId, u32) {
+        edits
+            .iter()
+            .find_map(|edit| match edit {
+                NewEventListener { id, generation, .. } => Some((*id, *generation)),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let (first, first_generation) = listener_of(&dom.rebuild().edits);
+
+    let click = |dom: &mut VirtualDom, (id, generation): (ElementId, u32)| {
+        dom.handle_generational_event("click", Rc::new(MouseData::default()), id, generation, true);
+        dom.mark_dirty(ScopeId(0));
+        listener_of(&dom.render_immediate().edits)
+    };
+
+    let second = click(&mut dom, (first, first_generation));
+    let third = click(&mut dom, second);
+
+    // The id of the first element was reused for a new element
+    assert_eq!(third.0, first);
+    assert_ne!(third.1, first_generation);
+
+    // An event for the removed element is dropped
+    dom.handle_generational_event(
+        "click",
+        Rc::new(MouseData::default()),
+        first,
+        first_generation,
+        true,
+    );
+    dom.mark_dirty(ScopeId(0));
+    assert!(dom.render_immediate().edits.is_empty());
+
+    // An event for the current element is delivered
+    click(&mut dom, third);
+}
+
 // #[test]
 // fn components_generate() {
 //     fn app(cx: Scope) -> Element {
@@ -166,3 +218,38 @@ fn events_generate() {
 //         ]
 //     );
 // }
+
+#[test]
+fn hooks_drop_in_reverse_order() {
+    fn app(cx: Scope) -> Element {
+        let show = cx.generation() == 0;
+        cx.render(rsx! {
+            if show {
+                rsx! { Child {} }
+            }
+        })
+    }
+
+    fn Child(cx: Scope) -> Element {
+        let log = cx.consume_context::<Shared<Vec<&'static str>>>().unwrap();
+        for name in ["first", "second", "third"] {
+            let log = log.clone();
+            cx.use_drop(move || log.lock().unwrap().push(name));
+        }
+        cx.render(rsx! { div {} })
+    }
+
+    let log: Shared<Vec<&'static str>> = Default::default();
+    let mut dom = VirtualDom::new(app);
+    dom.base_scope().provide_context(log.clone());
+    let _ = dom.rebuild();
+    assert!(log.lock().unwrap().is_empty());
+
+    dom.mark_dirty(ScopeId(0));
+    let _ = dom.render_immediate();
+    assert_eq!(*log.lock().unwrap(), ["third", "second", "first"]);
+
+    // The cleanup only runs once
+    drop(dom);
+    assert_eq!(log.lock().unwrap().len(), 3);
+}
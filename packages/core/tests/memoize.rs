@@ -0,0 +1,139 @@
+//! Verify the memoization strategies of derived props
+
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+thread_local! {
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn take_renders() -> usize {
+    RENDERS.with(|renders| renders.replace(0))
+}
+
+#[derive(Props)]
+struct PtrProps {
+    #[props(memo = ptr)]
+    rows: Rc<Vec<usize>>,
+    #[props(memo = skip)]
+    generation: usize,
+}
+
+fn rows_child(cx: Scope<PtrProps>) -> Element {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let _ = cx.props.generation;
+    render! { "{cx.props.rows.len()}" }
+}
+
+#[test]
+fn ptr_and_skip_fields() {
+    let shared = Rc::new(vec![1, 2, 3]);
+
+    let mut dom = VirtualDom::new_with_props(
+        |cx: Scope<Rc<Vec<usize>>>| {
+            // A new vec with the same contents on the third render
+            let rows = match cx.generation() {
+                2 => Rc::new((**cx.props).clone()),
+                _ => cx.props.clone(),
+            };
+            render! { rows_child { rows: rows, generation: cx.generation() } }
+        },
+        shared,
+    );
+    _ = dom.rebuild();
+    assert_eq!(take_renders(), 1);
+
+    // The rows point to the same vec, and the generation is skipped
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), 0);
+
+    // The rows point to a different vec with the same contents
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), 1);
+}
+
+#[derive(Props)]
+#[props(memo = custom)]
+struct VersionProps {
+    version: usize,
+    label: String,
+}
+
+impl Memoize for VersionProps {
+    fn memoize(&self, other: &Self) -> bool {
+        self.version == other.version
+    }
+}
+
+fn version_child(cx: Scope<VersionProps>) -> Element {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+    render! { "{cx.props.label}" }
+}
+
+#[test]
+fn custom_memoize() {
+    let mut dom = VirtualDom::new(|cx| {
+        let version = cx.generation() / 2;
+        render! { version_child { version: version, label: format!("{}", cx.generation()) } }
+    });
+    _ = dom.rebuild();
+    assert_eq!(take_renders(), 1);
+
+    // Same version, different label
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), 0);
+
+    // New version
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(take_renders(), 1);
+}
+
+#[derive(Props, PartialEq)]
+#[props(hash)]
+struct HashProps {
+    value: usize,
+    label: String,
+}
+
+// Only the value is hashed, so the hash changes less often than the props
+impl std::hash::Hash for HashProps {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+fn hash_child(cx: Scope<HashProps>) -> Element {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+    render! { "{cx.props.value} {cx.props.label}" }
+}
+
+fn hash_app(cx: Scope) -> Element {
+    render! { hash_child { value: cx.generation() / 2, label: cx.generation().to_string() } }
+}
+
+#[test]
+fn props_hashing() {
+    let props = |value: usize, label: &str| HashProps { value, label: label.to_string() };
+    assert_eq!(props(1, "a").props_hash(), props(1, "b").props_hash());
+    assert_ne!(props(1, "a").props_hash(), props(2, "a").props_hash());
+
+    // Without hashing the props are compared with PartialEq, so the label rerenders the component
+    for (hashing, renders) in [(false, 1), (true, 0)] {
+        take_renders();
+        let mut dom = VirtualDom::new(hash_app).with_props_hashing(hashing);
+        _ = dom.rebuild();
+        assert_eq!(take_renders(), 1);
+
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(take_renders(), renders);
+
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        assert_eq!(take_renders(), 1);
+    }
+}
@@ -0,0 +1,49 @@
+use dioxus::prelude::*;
+
+fn app(cx: Scope) -> Element {
+    let rows = if cx.generation() == 0 { 0 } else { 10 };
+
+    cx.render(rsx! {
+        div {
+            (0..rows).map(|i| rsx! {
+                div { key: "{i}", "{i}" }
+            })
+        }
+    })
+}
+
+#[test]
+fn large_updates_spill_over() {
+    let mut dom = VirtualDom::new(app).with_mutation_budget(8);
+    _ = dom.rebuild();
+
+    // Render the whole update at once without a budget to compare against
+    let mut reference = VirtualDom::new(app);
+    _ = reference.rebuild();
+    reference.mark_dirty(ScopeId(0));
+    let expected = reference.render_immediate().santize().edits;
+    assert!(expected.len() > 8);
+
+    dom.mark_dirty(ScopeId(0));
+    let mut returned = 0;
+    loop {
+        let has_more = {
+            let mutations = dom.render_immediate().santize();
+            assert!(mutations.edits.len() <= 8);
+            assert_eq!(
+                mutations.edits,
+                expected[returned..returned + mutations.edits.len()]
+            );
+            returned += mutations.edits.len();
+            mutations.has_more
+        };
+        if !has_more {
+            break;
+        }
+
+        // Components should not run again until the update is finished
+        dom.mark_dirty(ScopeId(0));
+    }
+
+    assert_eq!(returned, expected.len());
+}
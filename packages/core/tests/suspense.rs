@@ -41,3 +41,70 @@ fn suspended_child(cx: Scope) -> Element {
 
     render!("child")
 }
+
+#[test]
+fn boundary_tracks_suspended_children() {
+    use dioxus_core::SuspenseContext;
+    use std::{cell::RefCell, rc::Rc};
+
+    thread_local! {
+        static STATES: RefCell<Vec<bool>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn boundary(cx: Scope) -> Element {
+        let boundary =
+            cx.use_hook(|| cx.provide_context(Rc::new(SuspenseContext::new(cx.scope_id()))));
+        STATES.with(|states| states.borrow_mut().push(boundary.is_suspended()));
+
+        render!(suspended_child {})
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(boundary);
+            _ = dom.rebuild();
+            // the boundary rerenders once the child suspends
+            _ = dom.render_immediate();
+            dom.wait_for_suspense().await;
+            // and once the child resolves
+            _ = dom.render_immediate();
+
+            STATES.with(|states| assert_eq!(*states.borrow(), [false, true, false]));
+        });
+}
+
+#[test]
+fn suspended_children_move_in_keyed_lists() {
+    fn app(cx: Scope) -> Element {
+        let items = match cx.generation() {
+            0 => [0, 1],
+            _ => [1, 0],
+        };
+        render! {
+            for item in items {
+                item_child { key: "{item}", suspended: item == 0 }
+            }
+        }
+    }
+
+    #[inline_props]
+    fn item_child(cx: Scope, suspended: bool) -> Element {
+        if *suspended {
+            return cx.suspend()?;
+        }
+        render!("item")
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    // the placeholder of the suspended child is moved like any other node
+    dom.mark_dirty(ScopeId(0));
+    let edits = dom.render_immediate();
+    assert!(edits
+        .edits
+        .iter()
+        .any(|edit| matches!(edit, dioxus_core::Mutation::PushRoot { .. })));
+}
@@ -44,3 +44,109 @@ fn app(cx: Scope) -> Element {
 
     cx.render(rsx!(()))
 }
+
+#[test]
+fn virtual_time_fires_timers_in_order() {
+    use dioxus_core::VirtualTime;
+    use std::{cell::RefCell, rc::Rc};
+
+    fn app(cx: Scope) -> Element {
+        let time = cx.consume_context::<VirtualTime>().unwrap();
+        let log = cx.consume_context::<Rc<RefCell<Vec<u64>>>>().unwrap();
+        cx.use_hook(|| {
+            for delay in [30, 10, 20] {
+                to_owned![time, log];
+                cx.spawn(async move {
+                    loop {
+                        time.sleep(Duration::from_millis(delay)).await;
+                        log.borrow_mut().push(time.now().as_millis() as u64);
+                    }
+                });
+            }
+        });
+
+        cx.render(rsx!(()))
+    }
+
+    let time = VirtualTime::new();
+    let log = Rc::new(RefCell::new(Vec::<u64>::new()));
+    let mut dom = VirtualDom::new(app);
+    dom.base_scope().provide_context(time.clone());
+    dom.base_scope().provide_context(log.clone());
+    let _ = dom.rebuild();
+
+    time.advance_by(&mut dom, Duration::from_millis(9));
+    assert!(log.borrow().is_empty());
+
+    time.advance_by(&mut dom, Duration::from_millis(31));
+    assert_eq!(*log.borrow(), [10, 20, 20, 30, 30, 40, 40]);
+    assert_eq!(time.now(), Duration::from_millis(40));
+}
+
+#[test]
+fn task_groups_get_until_their_shutdown_timeout() {
+    use dioxus_core::VirtualTime;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn app(cx: Scope) -> Element {
+        let show = cx.consume_context::<Rc<Cell<bool>>>().unwrap();
+        cx.render(rsx! {
+            if show.get() {
+                rsx!(grouped_tasks {})
+            }
+        })
+    }
+
+    fn grouped_tasks(cx: Scope) -> Element {
+        let time = cx.consume_context::<VirtualTime>().unwrap();
+        let log = cx.consume_context::<Log>().unwrap();
+        cx.use_hook(|| {
+            let group = cx.task_group().with_shutdown_timeout({
+                to_owned![time];
+                move || time.sleep(Duration::from_millis(100))
+            });
+            for (name, delay) in [("saved", 50), ("uploaded", 200)] {
+                to_owned![time, log];
+                group.spawn(async move {
+                    time.sleep(Duration::from_millis(delay)).await;
+                    log.borrow_mut().push(name);
+                });
+            }
+
+            let cancelled = cx.task_group();
+            to_owned![time, log];
+            cancelled.spawn(async move {
+                time.sleep(Duration::from_millis(10)).await;
+                log.borrow_mut().push("cancelled");
+            });
+            cancelled.cancel();
+            group
+        });
+
+        cx.render(rsx!(()))
+    }
+
+    let time = VirtualTime::new();
+    let log = Log::default();
+    let show = Rc::new(Cell::new(true));
+    let mut dom = VirtualDom::new(app);
+    dom.base_scope().provide_context(time.clone());
+    dom.base_scope().provide_context(log.clone());
+    dom.base_scope().provide_context(show.clone());
+    let _ = dom.rebuild();
+
+    time.advance_by(&mut dom, Duration::from_millis(20));
+    assert!(log.borrow().is_empty());
+
+    // the tasks that finish before the timeout keep running after the component is unmounted
+    show.set(false);
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    time.advance_by(&mut dom, Duration::from_millis(300));
+    assert_eq!(*log.borrow(), ["saved"]);
+}
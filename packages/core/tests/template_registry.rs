@@ -0,0 +1,35 @@
+#![cfg(feature = "template_registry")]
+
+use dioxus::prelude::*;
+use dioxus_core::TEMPLATE_REGISTRY;
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div { "registered" }
+    })
+}
+
+#[test]
+fn rsx_templates_are_registered() {
+    let template = TEMPLATE_REGISTRY
+        .iter()
+        .find(|template| template.name.starts_with(file!()))
+        .expect("the template in app should be registered");
+
+    assert_eq!(
+        TEMPLATE_REGISTRY.iter().filter(|t| t == &template).count(),
+        1
+    );
+}
+
+#[test]
+fn prebaked_templates_are_not_sent_again() {
+    let mut dom = VirtualDom::new(app);
+
+    let prebaked = dom.prebake_templates().templates;
+    assert!(prebaked
+        .iter()
+        .any(|template| template.name.starts_with(file!())));
+
+    assert!(dom.rebuild().templates.is_empty());
+}
@@ -3,7 +3,6 @@ use dioxus::prelude::*;
 use dioxus_desktop::DesktopContext;
 
 pub(crate) fn check_app_exits(app: Component) {
-    use dioxus_desktop::tao::window::WindowBuilder;
     use dioxus_desktop::Config;
     // This is a deadman's switch to ensure that the app exits
     let should_panic = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
@@ -15,10 +14,7 @@ pub(crate) fn check_app_exits(app: Component) {
         }
     });
 
-    dioxus_desktop::launch_cfg(
-        app,
-        Config::new().with_window(WindowBuilder::new().with_visible(false)),
-    );
+    dioxus_desktop::launch_cfg(app, Config::new().with_headless(true));
 
     // Stop deadman's switch
     should_panic.store(false, std::sync::atomic::Ordering::SeqCst);
@@ -29,23 +25,11 @@ pub fn main() {
 }
 
 fn mock_event(cx: &ScopeState, id: &'static str, value: &'static str) {
-    let eval_provider = use_eval(cx).clone();
+    let desktop_context: DesktopContext = cx.consume_context().unwrap();
 
     use_effect(cx, (), move |_| async move {
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        let js = format!(
-            r#"
-                //console.log("ran");
-                // Dispatch a synthetic event
-                let event = {};
-                let element = document.getElementById('{}');
-                console.log(element, event);
-                element.dispatchEvent(event);
-                "#,
-            value, id
-        );
-
-        eval_provider(&js).unwrap();
+        assert!(desktop_context.dispatch_event(id, value).await.unwrap());
     })
 }
 
@@ -2,7 +2,6 @@ use dioxus::prelude::*;
 use dioxus_desktop::DesktopContext;
 
 pub(crate) fn check_app_exits(app: Component) {
-    use dioxus_desktop::tao::window::WindowBuilder;
     use dioxus_desktop::Config;
     // This is a deadman's switch to ensure that the app exits
     let should_panic = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
@@ -14,10 +13,7 @@ pub(crate) fn check_app_exits(app: Component) {
         }
     });
 
-    dioxus_desktop::launch_cfg(
-        app,
-        Config::new().with_window(WindowBuilder::new().with_visible(false)),
-    );
+    dioxus_desktop::launch_cfg(app, Config::new().with_headless(true));
 
     should_panic.store(false, std::sync::atomic::Ordering::SeqCst);
 }
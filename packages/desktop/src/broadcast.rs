@@ -0,0 +1,497 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(windows)]
+use uds_windows::{UnixListener, UnixStream};
+
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use slab::Slab;
+
+type Subscriber = Rc<RefCell<dyn FnMut(&str)>>;
+
+thread_local! {
+    // Every window runs on the main thread, so a thread local is shared between all of them
+    static CHANNELS: RefCell<HashMap<String, Slab<Subscriber>>> = RefCell::new(HashMap::new());
+
+    static PEER: Option<Peer> = match Peer::bind() {
+        Ok(peer) => Some(peer),
+        Err(err) => {
+            log::error!("Failed to share broadcast channels with other instances of the app: {err}");
+            None
+        }
+    };
+}
+
+/// The largest message another instance of the app can send
+const MAX_MESSAGE_LEN: u64 = 1 << 20;
+
+// Every peer in the process gets its own socket
+static NEXT_PEER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Message {
+    channel: String,
+    data: String,
+}
+
+/// A message sent to another instance of the app, with the token of the session it was sent in
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    token: String,
+    message: Message,
+}
+
+/// The connection of this instance of the app to the other running instances of it.
+///
+/// Every instance listens on a unix socket in a directory only the current user can access, and sends messages to the
+/// sockets of the other instances. Sockets that no longer accept connections belong to instances that exited without
+/// cleaning up, and are removed. Messages carry a random token that is created by the first instance of a session, and
+/// messages with another token are dropped.
+struct Peer {
+    directory: PathBuf,
+    socket: PathBuf,
+    receivers: Arc<Mutex<Vec<UnboundedSender<Message>>>>,
+}
+
+impl Peer {
+    fn bind() -> io::Result<Self> {
+        let exe = std::env::current_exe()?;
+        let app = exe.file_stem().unwrap_or_default().to_string_lossy();
+        Self::bind_in(&private_directory(&format!("dioxus-broadcast-{app}"))?)
+    }
+
+    fn bind_in(directory: &Path) -> io::Result<Self> {
+        let name = format!(
+            "{}-{}.sock",
+            std::process::id(),
+            NEXT_PEER.fetch_add(1, Ordering::Relaxed)
+        );
+        let socket = directory.join(name);
+        // The socket of an instance that exited with the same process id
+        _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket)?;
+
+        let peer = Self {
+            directory: directory.to_path_buf(),
+            socket,
+            receivers: Default::default(),
+        };
+        // The first instance of a session creates the token
+        if peer.peers().is_empty() {
+            new_token(directory)?;
+        }
+
+        // The messages are received on a thread, and delivered to the main thread by the tasks of the subscribers
+        std::thread::spawn({
+            let receivers = peer.receivers.clone();
+            let directory = peer.directory.clone();
+            move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let Some(message) = receive(stream, &directory) else {
+                        continue;
+                    };
+                    receivers
+                        .lock()
+                        .unwrap()
+                        .retain(|receiver| receiver.unbounded_send(message.clone()).is_ok());
+                }
+            }
+        });
+
+        Ok(peer)
+    }
+
+    /// Connect to the other running instances of the app, and remove the sockets of the instances that exited
+    fn peers(&self) -> Vec<UnixStream> {
+        let Ok(entries) = std::fs::read_dir(&self.directory) else {
+            return Vec::new();
+        };
+        let mut peers = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path == self.socket || path.extension() != Some("sock".as_ref()) {
+                continue;
+            }
+            match UnixStream::connect(&path) {
+                Ok(stream) => peers.push(stream),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::ConnectionRefused | io::ErrorKind::NotFound
+                    ) =>
+                {
+                    _ = std::fs::remove_file(&path);
+                }
+                Err(err) => log::error!("Failed to connect to another instance of the app: {err}"),
+            }
+        }
+        peers
+    }
+
+    /// Receive the messages other instances of the app send
+    fn subscribe(&self) -> futures_channel::mpsc::UnboundedReceiver<Message> {
+        let (tx, rx) = unbounded();
+        self.receivers.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Send a message to every other instance of the app
+    fn send(&self, message: &Message) {
+        let token = match read_token(&self.directory) {
+            Ok(token) => token,
+            Err(err) => {
+                log::error!("Failed to read the broadcast token: {err}");
+                return;
+            }
+        };
+        let Ok(data) = serde_json::to_vec(&Envelope {
+            token,
+            message: message.clone(),
+        }) else {
+            return;
+        };
+        for mut peer in self.peers() {
+            if let Err(err) = peer.write_all(&data) {
+                log::error!(
+                    "Failed to send broadcast message to another instance of the app: {err}"
+                );
+            }
+        }
+    }
+}
+
+impl Drop for Peer {
+    fn drop(&mut self) {
+        _ = std::fs::remove_file(&self.socket);
+    }
+}
+
+/// Read a message from another instance of the app. Messages without the token of the session are dropped.
+fn receive(stream: UnixStream, directory: &Path) -> Option<Message> {
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok()?;
+    let mut data = Vec::new();
+    stream.take(MAX_MESSAGE_LEN).read_to_end(&mut data).ok()?;
+    let envelope = serde_json::from_slice::<Envelope>(&data).ok()?;
+    if envelope.token != read_token(directory).ok()? {
+        log::warn!("Dropped a broadcast message with the wrong token");
+        return None;
+    }
+    Some(envelope.message)
+}
+
+/// Create a directory only the current user can access. On unix it is created in `XDG_RUNTIME_DIR`, which is removed
+/// when the user logs out, or in the temp dir if it is not set. On windows it is created in the local app data.
+fn private_directory(name: &str) -> io::Result<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        let base = std::env::var_os("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir);
+        let directory = base.join(name);
+        match std::fs::DirBuilder::new().mode(0o700).create(&directory) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err),
+        }
+        // Another user could have created the directory first in a shared temp dir
+        let metadata = std::fs::symlink_metadata(&directory)?;
+        if !metadata.is_dir() || metadata.permissions().mode() & 0o077 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is accessible by other users", directory.display()),
+            ));
+        }
+        Ok(directory)
+    }
+    #[cfg(windows)]
+    {
+        let base = std::env::var_os("LOCALAPPDATA")
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "LOCALAPPDATA is not set"))?;
+        let directory = PathBuf::from(base).join(name);
+        std::fs::create_dir_all(&directory)?;
+        Ok(directory)
+    }
+}
+
+fn read_token(directory: &Path) -> io::Result<String> {
+    std::fs::read_to_string(directory.join("token"))
+}
+
+/// Start a new session with a random token
+fn new_token(directory: &Path) -> io::Result<()> {
+    let mut token = [0; 16];
+    getrandom::getrandom(&mut token)?;
+    let token: String = token.iter().map(|byte| format!("{byte:02x}")).collect();
+
+    // Write the token next to the token file and move it over, so other instances never read a partial token
+    let path = directory.join(format!(
+        "{}-{}.token",
+        std::process::id(),
+        NEXT_PEER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&path)?.write_all(token.as_bytes())?;
+    std::fs::rename(&path, directory.join("token"))
+}
+
+/// Subscribe to a channel that is shared between every window of the app, and every other running instance of the app.
+///
+/// This mirrors the [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel) of
+/// the web renderer. Messages posted by one window are delivered to the handler of every other subscriber of a
+/// channel with the same name, in this instance of the app and in the other instances of the same executable that the
+/// current user runs. Messages to other instances must serialize to less than 1 MiB. Messages are never delivered back
+/// to the channel that posted them. The subscription is removed when the component is dropped.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let user = use_state(cx, || None::<String>);
+///     let channel = dioxus_desktop::use_broadcast_channel(cx, "auth", {
+///         to_owned![user];
+///         move |message: Option<String>| user.set(message)
+///     });
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 user.set(None);
+///                 channel.post(&None);
+///             },
+///             "Log out everywhere"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_broadcast_channel<'a, T: Serialize + DeserializeOwned + 'static>(
+    cx: &'a ScopeState,
+    name: &str,
+    on_message: impl FnMut(T) + 'static,
+) -> &'a BroadcastChannel<T> {
+    cx.use_hook(|| BroadcastChannel::new(cx, name, on_message))
+}
+
+/// A channel created with [`use_broadcast_channel`].
+pub struct BroadcastChannel<T> {
+    name: String,
+    id: usize,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> BroadcastChannel<T> {
+    fn new(cx: &ScopeState, name: &str, mut on_message: impl FnMut(T) + 'static) -> Self {
+        let subscriber: Subscriber = Rc::new(RefCell::new(move |data: &str| {
+            match serde_json::from_str(data) {
+                Ok(message) => on_message(message),
+                Err(err) => log::error!("Failed to deserialize broadcast message: {err}"),
+            }
+        }));
+        let id = CHANNELS.with(|channels| {
+            channels
+                .borrow_mut()
+                .entry(name.to_string())
+                .or_default()
+                .insert(subscriber.clone())
+        });
+
+        // Messages from other instances of the app are delivered by a task of the component, which is dropped with it
+        if let Some(mut messages) = PEER.with(|peer| peer.as_ref().map(Peer::subscribe)) {
+            let name = name.to_string();
+            cx.push_future(async move {
+                while let Some(message) = messages.next().await {
+                    if message.channel == name {
+                        if let Ok(mut subscriber) = subscriber.try_borrow_mut() {
+                            (*subscriber)(&message.data);
+                        }
+                    }
+                }
+            });
+        }
+
+        Self {
+            name: name.to_string(),
+            id,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Send a message to every other subscriber of the channel, in this and the other instances of the app.
+    pub fn post(&self, message: &T) {
+        let data = match serde_json::to_string(message) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("Failed to serialize broadcast message: {err}");
+                return;
+            }
+        };
+
+        // Collect the subscribers first so handlers can post or subscribe to channels themselves
+        let subscribers: Vec<Subscriber> = CHANNELS.with(|channels| {
+            channels
+                .borrow()
+                .get(&self.name)
+                .map(|subscribers| {
+                    subscribers
+                        .iter()
+                        .filter(|(id, _)| *id != self.id)
+                        .map(|(_, subscriber)| subscriber.clone())
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        for subscriber in subscribers {
+            if let Ok(mut subscriber) = subscriber.try_borrow_mut() {
+                (*subscriber)(&data);
+            }
+        }
+
+        PEER.with(|peer| {
+            if let Some(peer) = peer {
+                peer.send(&Message {
+                    channel: self.name.clone(),
+                    data,
+                });
+            }
+        });
+    }
+}
+
+impl<T> Drop for BroadcastChannel<T> {
+    fn drop(&mut self) {
+        CHANNELS.with(|channels| {
+            let mut channels = channels.borrow_mut();
+            if let Some(subscribers) = channels.get_mut(&self.name) {
+                if subscribers.contains(self.id) {
+                    subscribers.remove(self.id);
+                }
+                if subscribers.is_empty() {
+                    channels.remove(&self.name);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+
+    fn message() -> Message {
+        Message {
+            channel: "auth".to_string(),
+            data: "null".to_string(),
+        }
+    }
+
+    fn next(messages: &mut futures_channel::mpsc::UnboundedReceiver<Message>) -> Option<Message> {
+        (0..100).find_map(|_| {
+            std::thread::sleep(Duration::from_millis(10));
+            messages.next().now_or_never().flatten()
+        })
+    }
+
+    /// A private directory that is removed when the test ends
+    struct TestDirectory(PathBuf);
+
+    impl std::ops::Deref for TestDirectory {
+        type Target = Path;
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TestDirectory {
+        fn drop(&mut self) {
+            _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn directory(test: &str) -> TestDirectory {
+        let name = format!("dioxus-broadcast-{test}-{}", std::process::id());
+        TestDirectory(private_directory(&name).unwrap())
+    }
+
+    #[test]
+    fn instances_receive_messages_of_other_instances() {
+        let directory = directory("deliver");
+        let (sender, receiver) = (
+            Peer::bind_in(&directory).unwrap(),
+            Peer::bind_in(&directory).unwrap(),
+        );
+        let mut own_messages = sender.subscribe();
+        let mut messages = receiver.subscribe();
+
+        sender.send(&message());
+
+        let message = next(&mut messages).expect("the message was not delivered");
+        assert_eq!(message.channel, "auth");
+        assert_eq!(message.data, "null");
+
+        // Messages are not sent back to the instance that sent them
+        assert!(own_messages.next().now_or_never().is_none());
+    }
+
+    #[test]
+    fn messages_without_the_token_are_dropped() {
+        let directory = directory("token");
+        let receiver = Peer::bind_in(&directory).unwrap();
+        let mut messages = receiver.subscribe();
+
+        let envelope = Envelope {
+            token: "guess".to_string(),
+            message: message(),
+        };
+        UnixStream::connect(&receiver.socket)
+            .unwrap()
+            .write_all(&serde_json::to_vec(&envelope).unwrap())
+            .unwrap();
+
+        assert!(next(&mut messages).is_none());
+    }
+
+    #[test]
+    fn sockets_of_exited_instances_are_removed() {
+        let directory = directory("stale");
+        let stale = directory.join("stale.sock");
+        drop(UnixListener::bind(&stale).unwrap());
+        assert!(stale.exists());
+
+        let peer = Peer::bind_in(&directory).unwrap();
+        peer.send(&message());
+        assert!(!stale.exists());
+        assert!(peer.socket.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let directory = directory("private");
+        let mode = std::fs::metadata(&*directory).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
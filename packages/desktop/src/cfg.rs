@@ -36,6 +36,8 @@ pub struct Config {
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behaviour: WindowCloseBehaviour,
+    pub(crate) headless: bool,
+    pub(crate) mutation_budget: Option<usize>,
 }
 
 type DropHandler = Box<dyn Fn(&Window, FileDropEvent) -> bool>;
@@ -65,6 +67,8 @@ impl Config {
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behaviour: WindowCloseBehaviour::LastWindowExitsApp,
+            headless: false,
+            mutation_budget: None,
         }
     }
 
@@ -102,6 +106,26 @@ impl Config {
         self
     }
 
+    /// Run the app headless.
+    ///
+    /// The window is created offscreen and never shown or focused, but the webview still renders
+    /// and handles events normally. Combine this with [`crate::DesktopService::dom_snapshot`] to
+    /// write UI integration tests that run in CI. To test an app where no window can be opened, use
+    /// [`crate::HeadlessDom`] instead.
+    pub fn with_headless(mut self, headless: bool) -> Self {
+        self.headless = headless;
+        self
+    }
+
+    /// Limit the number of edits sent to the webview at once. Large updates are split up and sent over multiple
+    /// frames so the webview stays responsive while it applies them.
+    ///
+    /// See [`VirtualDom::with_mutation_budget`](dioxus_core::VirtualDom::with_mutation_budget).
+    pub fn with_mutation_budget(mut self, budget: usize) -> Self {
+        self.mutation_budget = Some(budget);
+        self
+    }
+
     /// Sets the behaviour of the application when the last window is closed.
     pub fn with_close_behaviour(mut self, behaviour: WindowCloseBehaviour) -> Self {
         self.last_window_close_behaviour = behaviour;
@@ -0,0 +1,540 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_core::ScopeState;
+use dioxus_html::{geometry::ClientPoint, MouseData};
+use wry::webview::WebView;
+
+use crate::{desktop_context::DesktopContext, use_window};
+
+/// A native context menu, shown with [`ContextMenuHandle::show`].
+///
+/// ```rust, ignore
+/// let menu = ContextMenu::new()
+///     .item("copy", "Copy")
+///     .item("paste", "Paste")
+///     .separator()
+///     .checkbox("wrap", "Word wrap", true)
+///     .submenu("Sort by", ContextMenu::new().item("name", "Name").item("date", "Date"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextMenu {
+    /// The items of the menu, from top to bottom
+    pub items: Vec<ContextMenuItem>,
+}
+
+/// An item of a [`ContextMenu`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextMenuItem {
+    /// An item that sends a [`ContextMenuEvent`] with its id when it is selected
+    Item {
+        id: String,
+        label: String,
+        enabled: bool,
+    },
+    /// An item with a check mark. Selecting it sends a [`ContextMenuEvent`] with the toggled state.
+    Checkbox {
+        id: String,
+        label: String,
+        checked: bool,
+        enabled: bool,
+    },
+    /// A line between groups of items
+    Separator,
+    /// An item that opens another menu
+    Submenu {
+        label: String,
+        enabled: bool,
+        menu: ContextMenu,
+    },
+}
+
+impl ContextMenu {
+    /// Create an empty context menu
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to the menu
+    pub fn push(mut self, item: ContextMenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add an item that sends a [`ContextMenuEvent`] with the id when it is selected
+    pub fn item(self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.push(ContextMenuItem::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+        })
+    }
+
+    /// Add an item with a check mark
+    pub fn checkbox(self, id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        self.push(ContextMenuItem::Checkbox {
+            id: id.into(),
+            label: label.into(),
+            checked,
+            enabled: true,
+        })
+    }
+
+    /// Add a line between groups of items
+    pub fn separator(self) -> Self {
+        self.push(ContextMenuItem::Separator)
+    }
+
+    /// Add an item that opens another menu
+    pub fn submenu(self, label: impl Into<String>, menu: ContextMenu) -> Self {
+        self.push(ContextMenuItem::Submenu {
+            label: label.into(),
+            enabled: true,
+            menu,
+        })
+    }
+
+    /// Find the item that was selected. The platforms number the selectable items in the order they appear in the menu
+    /// and its submenus, starting at 1.
+    fn selected(&self, command: usize) -> Option<ContextMenuEvent> {
+        fn find(menu: &ContextMenu, command: usize, next: &mut usize) -> Option<ContextMenuEvent> {
+            for item in &menu.items {
+                let event = match item {
+                    ContextMenuItem::Item { id, .. } => ContextMenuEvent {
+                        id: id.clone(),
+                        checked: None,
+                    },
+                    ContextMenuItem::Checkbox { id, checked, .. } => ContextMenuEvent {
+                        id: id.clone(),
+                        checked: Some(!checked),
+                    },
+                    ContextMenuItem::Separator => continue,
+                    ContextMenuItem::Submenu { menu, .. } => match find(menu, command, next) {
+                        Some(event) => return Some(event),
+                        None => continue,
+                    },
+                };
+                if *next == command {
+                    return Some(event);
+                }
+                *next += 1;
+            }
+            None
+        }
+
+        find(self, command, &mut 1)
+    }
+}
+
+/// The item of a [`ContextMenu`] the user selected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextMenuEvent {
+    /// The id of the selected item
+    pub id: String,
+    /// The new state of a checkbox item, or [`None`] if the item is not a checkbox
+    pub checked: Option<bool>,
+}
+
+type SelectHandler = Rc<RefCell<Box<dyn FnMut(ContextMenuEvent)>>>;
+
+/// A handle to show native context menus, created with [`use_context_menu`].
+pub struct ContextMenuHandle {
+    desktop: DesktopContext,
+    on_select: SelectHandler,
+}
+
+impl ContextMenuHandle {
+    /// Show the menu at the position of the mouse event, usually the event of `oncontextmenu`. The handler of
+    /// [`use_context_menu`] receives the item the user selects, nothing is sent if the menu is dismissed.
+    pub fn show(&self, menu: ContextMenu, event: &MouseData) {
+        let on_select = self.on_select.clone();
+        let menu = Rc::new(menu);
+        let select = {
+            let menu = menu.clone();
+            Rc::new(move |command: usize| {
+                if let Some(event) = menu.selected(command) {
+                    (*on_select.borrow_mut())(event);
+                }
+            })
+        };
+        popup(
+            &self.desktop.webview,
+            &menu,
+            event.client_coordinates(),
+            select,
+        );
+    }
+}
+
+/// Show native context menus and receive the item the user selects.
+///
+/// Prevent the default `oncontextmenu` behavior of the element, or the menu of the webview opens as well.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let menu = use_context_menu(cx, |event: ContextMenuEvent| log::info!("selected {}", event.id));
+///
+///     render! {
+///         div {
+///             prevent_default: "oncontextmenu",
+///             oncontextmenu: move |evt| menu.show(ContextMenu::new().item("copy", "Copy"), &evt),
+///             "Right click me"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_context_menu(
+    cx: &ScopeState,
+    on_select: impl FnMut(ContextMenuEvent) + 'static,
+) -> &ContextMenuHandle {
+    let desktop = use_window(cx);
+    let handle = cx.use_hook(|| ContextMenuHandle {
+        desktop: desktop.clone(),
+        on_select: Rc::new(RefCell::new(Box::new(|_| {}))),
+    });
+    // the handler of the latest render sees the latest state of the component
+    *handle.on_select.borrow_mut() = Box::new(on_select);
+    handle
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn popup(webview: &WebView, menu: &ContextMenu, position: ClientPoint, select: Rc<dyn Fn(usize)>) {
+    use gtk::{
+        gdk::{Gravity, Rectangle},
+        prelude::*,
+    };
+    use wry::webview::WebviewExtUnix;
+
+    fn connect(item: &gtk::MenuItem, next: &mut usize, select: &Rc<dyn Fn(usize)>) {
+        let command = *next;
+        *next += 1;
+        let select = select.clone();
+        item.connect_activate(move |_| select(command));
+    }
+
+    fn build(menu: &ContextMenu, next: &mut usize, select: &Rc<dyn Fn(usize)>) -> gtk::Menu {
+        let gtk_menu = gtk::Menu::new();
+        for item in &menu.items {
+            let gtk_item: gtk::MenuItem = match item {
+                ContextMenuItem::Item { label, enabled, .. } => {
+                    let gtk_item = gtk::MenuItem::with_label(label);
+                    gtk_item.set_sensitive(*enabled);
+                    connect(&gtk_item, next, select);
+                    gtk_item
+                }
+                ContextMenuItem::Checkbox {
+                    label,
+                    checked,
+                    enabled,
+                    ..
+                } => {
+                    let gtk_item = gtk::CheckMenuItem::with_label(label);
+                    // changing the state activates the item, so it happens before the handler is connected
+                    gtk_item.set_active(*checked);
+                    gtk_item.set_sensitive(*enabled);
+                    connect(gtk_item.upcast_ref(), next, select);
+                    gtk_item.upcast()
+                }
+                ContextMenuItem::Separator => gtk::SeparatorMenuItem::new().upcast(),
+                ContextMenuItem::Submenu {
+                    label,
+                    enabled,
+                    menu,
+                } => {
+                    let gtk_item = gtk::MenuItem::with_label(label);
+                    gtk_item.set_sensitive(*enabled);
+                    gtk_item.set_submenu(Some(&build(menu, next, select)));
+                    gtk_item
+                }
+            };
+            gtk_menu.append(&gtk_item);
+        }
+        gtk_menu
+    }
+
+    let gtk_menu = build(menu, &mut 1, &select);
+    gtk_menu.show_all();
+
+    let webview = webview.webview();
+    gtk_menu.set_attach_widget(Some(&*webview));
+    match webview.window() {
+        Some(window) => {
+            let rect = Rectangle::new(position.x as i32, position.y as i32, 1, 1);
+            gtk_menu.popup_at_rect(&window, &rect, Gravity::NorthWest, Gravity::NorthWest, None);
+        }
+        None => log::warn!("The context menu can't be shown before the webview is realized"),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn popup(webview: &WebView, menu: &ContextMenu, position: ClientPoint, select: Rc<dyn Fn(usize)>) {
+    use windows::{
+        core::{HSTRING, PCWSTR},
+        Win32::{
+            Foundation::{HWND, POINT},
+            Graphics::Gdi::ClientToScreen,
+            UI::WindowsAndMessaging::{
+                AppendMenuW, CreatePopupMenu, DestroyMenu, TrackPopupMenu, HMENU, MF_CHECKED,
+                MF_ENABLED, MF_GRAYED, MF_POPUP, MF_SEPARATOR, MF_STRING, MF_UNCHECKED,
+                TPM_RETURNCMD, TPM_RIGHTBUTTON,
+            },
+        },
+    };
+    use wry::application::platform::windows::WindowExtWindows;
+
+    unsafe fn build(menu: &ContextMenu, next: &mut usize) -> windows::core::Result<HMENU> {
+        let hmenu = CreatePopupMenu()?;
+        for item in &menu.items {
+            let enabled = |enabled: bool| if enabled { MF_ENABLED } else { MF_GRAYED };
+            match item {
+                ContextMenuItem::Item {
+                    label, enabled: e, ..
+                } => {
+                    AppendMenuW(
+                        hmenu,
+                        MF_STRING | enabled(*e),
+                        *next,
+                        &HSTRING::from(label.as_str()),
+                    )
+                    .ok()?;
+                    *next += 1;
+                }
+                ContextMenuItem::Checkbox {
+                    label,
+                    checked,
+                    enabled: e,
+                    ..
+                } => {
+                    let check = if *checked { MF_CHECKED } else { MF_UNCHECKED };
+                    AppendMenuW(
+                        hmenu,
+                        MF_STRING | check | enabled(*e),
+                        *next,
+                        &HSTRING::from(label.as_str()),
+                    )
+                    .ok()?;
+                    *next += 1;
+                }
+                ContextMenuItem::Separator => {
+                    AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null()).ok()?;
+                }
+                ContextMenuItem::Submenu {
+                    label,
+                    enabled: e,
+                    menu,
+                } => {
+                    let submenu = build(menu, next)?;
+                    AppendMenuW(
+                        hmenu,
+                        MF_POPUP | enabled(*e),
+                        submenu.0 as usize,
+                        &HSTRING::from(label.as_str()),
+                    )
+                    .ok()?;
+                }
+            }
+        }
+        Ok(hmenu)
+    }
+
+    let window = webview.window();
+    let hwnd = HWND(window.hwnd() as _);
+    // the position of the event is in css pixels relative to the webview, which fills the window
+    let scale = window.scale_factor();
+    let command = unsafe {
+        let hmenu = match build(menu, &mut 1) {
+            Ok(hmenu) => hmenu,
+            Err(err) => {
+                log::error!("Failed to create the context menu: {err}");
+                return;
+            }
+        };
+        let mut point = POINT {
+            x: (position.x * scale) as i32,
+            y: (position.y * scale) as i32,
+        };
+        ClientToScreen(hwnd, &mut point);
+        // the menu is modal, it returns the selected item or 0 if the menu was dismissed
+        let command = TrackPopupMenu(
+            hmenu,
+            TPM_RETURNCMD | TPM_RIGHTBUTTON,
+            point.x,
+            point.y,
+            0,
+            hwnd,
+            None,
+        );
+        // destroying the menu destroys its submenus as well
+        DestroyMenu(hmenu);
+        command.0 as usize
+    };
+    if command != 0 {
+        select(command);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn popup(webview: &WebView, menu: &ContextMenu, position: ClientPoint, select: Rc<dyn Fn(usize)>) {
+    use objc::{
+        declare::ClassDecl,
+        runtime::{Class, Object, Sel, BOOL, NO, YES},
+        *,
+    };
+    use std::cell::Cell;
+    use wry::webview::WebviewExtMacOS;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct NSPoint {
+        x: f64,
+        y: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct NSRect {
+        origin: NSPoint,
+        size: NSPoint,
+    }
+
+    thread_local! {
+        static SELECTED: Cell<Option<isize>> = const { Cell::new(None) };
+    }
+
+    extern "C" fn on_select(_: &Object, _: Sel, sender: *mut Object) {
+        let tag: isize = unsafe { msg_send![sender, tag] };
+        SELECTED.with(|selected| selected.set(Some(tag)));
+    }
+
+    // The target of the items, which records the tag of the selected item
+    fn target_class() -> &'static Class {
+        Class::get("DioxusContextMenuTarget").unwrap_or_else(|| {
+            let mut decl = ClassDecl::new("DioxusContextMenuTarget", class!(NSObject))
+                .expect("the class is only declared once");
+            unsafe {
+                decl.add_method(
+                    sel!(select:),
+                    on_select as extern "C" fn(&Object, Sel, *mut Object),
+                );
+            }
+            decl.register()
+        })
+    }
+
+    unsafe fn ns_string(string: &str) -> *mut Object {
+        let ns_string: *mut Object = msg_send![class!(NSString), alloc];
+        let ns_string: *mut Object = msg_send![ns_string, initWithBytes: string.as_ptr() length: string.len() encoding: 4usize];
+        msg_send![ns_string, autorelease]
+    }
+
+    unsafe fn ns_item(label: &str, enabled: bool) -> *mut Object {
+        let item: *mut Object = msg_send![class!(NSMenuItem), alloc];
+        let item: *mut Object = msg_send![item, initWithTitle: ns_string(label) action: sel!(select:) keyEquivalent: ns_string("")];
+        let enabled: BOOL = if enabled { YES } else { NO };
+        let () = msg_send![item, setEnabled: enabled];
+        msg_send![item, autorelease]
+    }
+
+    unsafe fn build(menu: &ContextMenu, next: &mut isize, target: *mut Object) -> *mut Object {
+        let ns_menu: *mut Object = msg_send![class!(NSMenu), alloc];
+        let ns_menu: *mut Object = msg_send![ns_menu, initWithTitle: ns_string("")];
+        // items keep the enabled state they are created with
+        let () = msg_send![ns_menu, setAutoenablesItems: NO];
+        for item in &menu.items {
+            let item = match item {
+                ContextMenuItem::Item { label, enabled, .. } => ns_item(label, *enabled),
+                ContextMenuItem::Checkbox {
+                    label,
+                    checked,
+                    enabled,
+                    ..
+                } => {
+                    let item = ns_item(label, *enabled);
+                    let () = msg_send![item, setState: *checked as isize];
+                    item
+                }
+                ContextMenuItem::Separator => {
+                    let item: *mut Object = msg_send![class!(NSMenuItem), separatorItem];
+                    let () = msg_send![ns_menu, addItem: item];
+                    continue;
+                }
+                ContextMenuItem::Submenu {
+                    label,
+                    enabled,
+                    menu,
+                } => {
+                    let item = ns_item(label, *enabled);
+                    let () = msg_send![item, setSubmenu: build(menu, next, target)];
+                    let () = msg_send![ns_menu, addItem: item];
+                    continue;
+                }
+            };
+            let () = msg_send![item, setTarget: target];
+            let () = msg_send![item, setTag: *next];
+            *next += 1;
+            let () = msg_send![ns_menu, addItem: item];
+        }
+        msg_send![ns_menu, autorelease]
+    }
+
+    SELECTED.with(|selected| selected.set(None));
+    let selected = unsafe {
+        let target: *mut Object = msg_send![target_class(), new];
+        let ns_menu = build(menu, &mut 1, target);
+
+        let view = webview.webview();
+        let mut location = NSPoint {
+            x: position.x,
+            y: position.y,
+        };
+        // the position of the event is measured from the top of the webview
+        let flipped: BOOL = msg_send![view, isFlipped];
+        if flipped == NO {
+            let bounds: NSRect = msg_send![view, bounds];
+            location.y = bounds.size.y - location.y;
+        }
+        // the menu is modal, the selected item records its tag before it returns
+        let nil: *mut Object = std::ptr::null_mut();
+        let _: BOOL =
+            msg_send![ns_menu, popUpMenuPositioningItem: nil atLocation: location inView: view];
+        let () = msg_send![target, release];
+        SELECTED.with(Cell::take)
+    };
+    if let Some(command) = selected {
+        select(command as usize);
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+fn popup(_: &WebView, _: &ContextMenu, _: ClientPoint, _: Rc<dyn Fn(usize)>) {
+    log::warn!("Context menus are not supported on mobile platforms");
+}
+
+#[test]
+fn selected_items_are_numbered_in_order() {
+    let menu = ContextMenu::new()
+        .item("copy", "Copy")
+        .separator()
+        .submenu(
+            "Sort by",
+            ContextMenu::new().item("name", "Name").item("date", "Date"),
+        )
+        .checkbox("wrap", "Word wrap", true);
+
+    let selected = |command| menu.selected(command).map(|event| event.id);
+    assert_eq!(selected(1).as_deref(), Some("copy"));
+    assert_eq!(selected(3).as_deref(), Some("date"));
+    assert_eq!(
+        menu.selected(4),
+        Some(ContextMenuEvent {
+            id: "wrap".to_string(),
+            checked: Some(false),
+        })
+    );
+    assert_eq!(menu.selected(5), None);
+}
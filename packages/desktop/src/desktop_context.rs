@@ -1,9 +1,12 @@
 use std::cell::RefCell;
+use std::path::Path;
 use std::rc::Rc;
 use std::rc::Weak;
 
 use crate::create_new_window;
+use crate::escape::escape_js_string;
 use crate::events::IpcMessage;
+use crate::pdf::{PdfExportError, PdfOptions};
 use crate::query::QueryEngine;
 use crate::shortcut::ShortcutId;
 use crate::shortcut::ShortcutRegistry;
@@ -14,6 +17,7 @@ use dioxus_core::ScopeState;
 use dioxus_core::VirtualDom;
 #[cfg(all(feature = "hot-reload", debug_assertions))]
 use dioxus_hot_reload::HotReloadMsg;
+use dioxus_html::prelude::EvalError;
 use slab::Slab;
 use wry::application::accelerator::Accelerator;
 use wry::application::event::Event;
@@ -198,6 +202,61 @@ impl DesktopService {
         }
     }
 
+    /// Render the current page to a PDF file at `path`.
+    ///
+    /// The page is printed with the print-to-PDF support of the webview, so it uses the print styles of the page.
+    ///
+    /// ```rust, ignore
+    /// let desktop = use_window(cx);
+    /// let export = move |_| {
+    ///     to_owned![desktop];
+    ///     cx.spawn(async move {
+    ///         let options = PdfOptions::letter().with_margins([20.0; 4]);
+    ///         if let Err(err) = desktop.export_pdf("report.pdf", options).await {
+    ///             log::error!("{err}");
+    ///         }
+    ///     });
+    /// };
+    /// ```
+    pub async fn export_pdf(
+        &self,
+        path: impl AsRef<Path>,
+        options: PdfOptions,
+    ) -> Result<(), PdfExportError> {
+        crate::pdf::export_pdf(&self.webview, path.as_ref(), options).await
+    }
+
+    /// Get the HTML currently rendered inside the root element of the webview.
+    ///
+    /// This is mostly useful for asserting on the rendered output in tests, together with
+    /// [`Config::with_headless`].
+    pub async fn dom_snapshot(self: &Rc<Self>) -> Result<String, EvalError> {
+        self.query
+            .new_query::<String>("return window.interpreter.root.innerHTML;", self.clone())
+            .resolve()
+            .await
+            .map_err(|e| EvalError::Communication(e.to_string()))
+    }
+
+    /// Dispatch an event to the element with the given `id` in the webview, as if the user triggered it.
+    ///
+    /// `event` is a JavaScript expression that creates the event, like `new MouseEvent("click", { bubbles: true })`.
+    /// Returns `false` if there is no element with the id.
+    pub async fn dispatch_event(self: &Rc<Self>, id: &str, event: &str) -> Result<bool, EvalError> {
+        let script = format!(
+            "let element = document.getElementById({});
+            if (element == null) return false;
+            element.dispatchEvent({event});
+            return true;",
+            escape_js_string(id)
+        );
+        self.query
+            .new_query::<bool>(&script, self.clone())
+            .resolve()
+            .await
+            .map_err(|e| EvalError::Communication(e.to_string()))
+    }
+
     /// Set the zoom level of the webview
     pub fn set_zoom_level(&self, level: f64) {
         self.webview.zoom(level);
@@ -1,7 +1,16 @@
-use dioxus_core::ElementId;
-use dioxus_html::{geometry::euclid::Rect, MountedResult, RenderedElementBacking};
+use dioxus_core::{
+    prelude::{push_future, remove_future},
+    ElementId, TaskId,
+};
+use dioxus_html::{
+    geometry::euclid::{Rect, Size2D},
+    MountedResult, RenderedElementBacking, ResizeObservation,
+};
 
-use crate::{desktop_context::DesktopContext, query::QueryEngine};
+use crate::{
+    desktop_context::DesktopContext,
+    query::{QueryEngine, QUEUE_NAME},
+};
 
 /// A mounted element passed to onmounted events
 pub struct DesktopElement {
@@ -102,6 +111,42 @@ impl RenderedElementBacking for DesktopElement {
             }
         })
     }
+
+    fn observe_resize(
+        &self,
+        mut on_resize: Box<dyn FnMut(Size2D<f64, f64>)>,
+    ) -> MountedResult<ResizeObservation> {
+        // The message queue of the query is replaced when the query is dropped, which disconnects the observer
+        let script = format!(
+            r#"return window.interpreter.ObserveResize({}, (size) => {{
+                if (window.{QUEUE_NAME}[_request_id] !== _message_queue) {{
+                    return false;
+                }}
+                dioxus.send(size);
+            }});"#,
+            self.id.0
+        );
+
+        let mut query = self.query.new_query::<bool>(&script, self.webview.clone());
+        let task = push_future(async move {
+            while let Ok(size) = query.recv().await {
+                if let Ok(size) = serde_json::from_value(size) {
+                    on_resize(size);
+                }
+            }
+        })
+        .ok_or(dioxus_html::MountedError::NotSupported)?;
+        Ok(ResizeObservation::new(StopObserving(task)))
+    }
+}
+
+/// Drops the task that receives the sizes of an observed element, which drops its query
+struct StopObserving(TaskId);
+
+impl Drop for StopObserving {
+    fn drop(&mut self) {
+        remove_future(self.0);
+    }
 }
 
 #[derive(Debug)]
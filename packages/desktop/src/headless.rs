@@ -0,0 +1,478 @@
+//! Render an app without a window or a webview.
+//!
+//! [`HeadlessDom`] applies the edits of the [`VirtualDom`] to a stub of the DOM instead of a webview, so UI tests can
+//! run in CI where there is no display to open a window on.
+
+use dioxus_core::{
+    BorrowedAttributeValue, Component, ElementId, Mutation, Mutations, TemplateAttribute,
+    TemplateNode, VirtualDom,
+};
+use dioxus_html::{event_bubbles, MountedData, MountedResult, RenderedElementBacking};
+use std::{any::Any, collections::HashMap, fmt::Write, rc::Rc};
+
+/// An app rendered into a stub of the DOM.
+///
+/// Events are dispatched to elements by their `id` attribute, and [`HeadlessDom::snapshot`] returns the rendered HTML
+/// like [`crate::DesktopService::dom_snapshot`] does for the webview.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_desktop::HeadlessDom;
+///
+/// fn app(cx: Scope) -> Element {
+///     let count = use_state(cx, || 0);
+///     render! { button { id: "add", onclick: move |_| count.set(count + 1), "{count}" } }
+/// }
+///
+/// let mut dom = HeadlessDom::new(app);
+/// dom.dispatch_event("add", "click", dioxus::html::MouseData::default());
+/// assert_eq!(dom.snapshot(), r#"<button id="add">1</button>"#);
+/// ```
+pub struct HeadlessDom {
+    dom: VirtualDom,
+    document: Document,
+}
+
+impl HeadlessDom {
+    /// Build an app without root props.
+    pub fn new(root: Component) -> Self {
+        Self::new_with_props(root, ())
+    }
+
+    /// Build an app with the given root props.
+    pub fn new_with_props<P: 'static>(root: Component<P>, props: P) -> Self {
+        let mut dom = VirtualDom::new_with_props(root, props);
+        let mut document = Document::new();
+        let mounted = document.apply(dom.rebuild());
+        let mut headless = Self { dom, document };
+        headless.mount(mounted);
+        headless
+    }
+
+    /// The [`VirtualDom`] of the app
+    pub fn dom(&mut self) -> &mut VirtualDom {
+        &mut self.dom
+    }
+
+    /// Dispatch an event to the element with the given `id` attribute, and render the changes it causes.
+    ///
+    /// The handler of the closest element that listens to events receives the event first. Returns `false` if there
+    /// is no element with the id.
+    pub fn dispatch_event(&mut self, id: &str, name: &str, data: impl Any) -> bool {
+        let element = match self.document.find_listener(id) {
+            Some(element) => element,
+            None => return false,
+        };
+        self.dom
+            .handle_event(name, Rc::new(data), element, event_bubbles(name));
+        self.render();
+        true
+    }
+
+    /// Wait for the tasks of the app to make progress, and render the changes they cause.
+    pub async fn wait_for_work(&mut self) {
+        self.dom.wait_for_work().await;
+        self.render();
+    }
+
+    /// Render the components that need to rerender.
+    pub fn render(&mut self) {
+        let mounted = self.document.apply(self.dom.render_immediate());
+        self.mount(mounted);
+    }
+
+    /// Get the HTML currently rendered inside the root element.
+    pub fn snapshot(&self) -> String {
+        let mut html = String::new();
+        for &child in &self.document.nodes[ROOT].children {
+            self.document.write_node(&mut html, child);
+        }
+        html
+    }
+
+    // Send the mounted events of new elements, like the webview does once it created them
+    fn mount(&mut self, mounted: Vec<ElementId>) {
+        for id in mounted {
+            let data = Rc::new(MountedData::new(HeadlessElement));
+            self.dom.handle_event("mounted", data, id, false);
+        }
+    }
+}
+
+/// The element of the DOM stub passed to mounted events. It doesn't support any of the methods of a rendered element.
+struct HeadlessElement;
+
+impl RenderedElementBacking for HeadlessElement {
+    fn get_raw_element(&self) -> MountedResult<&dyn Any> {
+        Ok(self)
+    }
+}
+
+/// The index of the root element of the document
+const ROOT: usize = 0;
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+#[derive(Clone)]
+enum NodeKind {
+    Element {
+        tag: String,
+        attributes: Vec<(String, String)>,
+        styles: Vec<(String, String)>,
+        inner_html: Option<String>,
+    },
+    Text(String),
+    Placeholder,
+}
+
+#[derive(Clone)]
+struct Node {
+    kind: NodeKind,
+    id: Option<ElementId>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+/// A stub of the DOM that handles edits the same way the interpreter of the webview does
+struct Document {
+    // Removed nodes are never reused, the document only lives as long as a test
+    nodes: Vec<Node>,
+    ids: HashMap<ElementId, usize>,
+    stack: Vec<usize>,
+    templates: HashMap<String, Vec<usize>>,
+}
+
+impl Document {
+    fn new() -> Self {
+        let mut document = Self {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            stack: vec![ROOT],
+            templates: HashMap::new(),
+        };
+        document.create(NodeKind::Element {
+            tag: "div".to_string(),
+            attributes: Vec::new(),
+            styles: Vec::new(),
+            inner_html: None,
+        });
+        document.set_node(ElementId(0), ROOT);
+        document
+    }
+
+    /// Apply the edits, returning the elements that listen to mounted events
+    fn apply(&mut self, mutations: Mutations) -> Vec<ElementId> {
+        for template in &mutations.templates {
+            let roots = template
+                .roots
+                .iter()
+                .map(|root| self.create_template_node(root))
+                .collect();
+            self.templates.insert(template.name.to_string(), roots);
+        }
+
+        let mut mounted = Vec::new();
+        for edit in mutations.edits {
+            match edit {
+                Mutation::AppendChildren { m, .. } => {
+                    let children = self.stack.split_off(self.stack.len() - m);
+                    let parent = self.top();
+                    for child in children {
+                        self.append(parent, child);
+                    }
+                }
+                Mutation::AssignId { path, id } => {
+                    let node = self.load_child(path);
+                    self.set_node(id, node);
+                }
+                Mutation::CreatePlaceholder { id } => {
+                    let node = self.create(NodeKind::Placeholder);
+                    self.set_node(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::CreateTextNode { value, id } => {
+                    let node = self.create(NodeKind::Text(value.to_string()));
+                    self.set_node(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::HydrateText { path, value, id } => {
+                    let mut node = self.load_child(path);
+                    match &mut self.nodes[node].kind {
+                        NodeKind::Text(text) => *text = value.to_string(),
+                        _ => {
+                            let text = self.create(NodeKind::Text(value.to_string()));
+                            self.replace(node, vec![text]);
+                            node = text;
+                        }
+                    }
+                    self.set_node(id, node);
+                }
+                Mutation::LoadTemplate { name, index, id } => {
+                    let node = self.clone_node(self.templates[name][index]);
+                    self.set_node(id, node);
+                    self.stack.push(node);
+                }
+                Mutation::ReplaceWith { id, m } => {
+                    let nodes = self.stack.split_off(self.stack.len() - m);
+                    self.replace(self.ids[&id], nodes);
+                }
+                Mutation::ReplacePlaceholder { path, m } => {
+                    let nodes = self.stack.split_off(self.stack.len() - m);
+                    let node = self.load_child(path);
+                    self.replace(node, nodes);
+                }
+                Mutation::InsertAfter { id, m } => {
+                    let nodes = self.stack.split_off(self.stack.len() - m);
+                    self.insert(self.ids[&id], nodes, 1);
+                }
+                Mutation::InsertBefore { id, m } => {
+                    let nodes = self.stack.split_off(self.stack.len() - m);
+                    self.insert(self.ids[&id], nodes, 0);
+                }
+                Mutation::SetAttribute {
+                    name,
+                    value,
+                    id,
+                    ns,
+                } => {
+                    let value = match value {
+                        BorrowedAttributeValue::Text(value) => Some(value.to_string()),
+                        BorrowedAttributeValue::Float(value) => Some(value.to_string()),
+                        BorrowedAttributeValue::Int(value) => Some(value.to_string()),
+                        BorrowedAttributeValue::Bool(value) => value.then(|| "true".to_string()),
+                        BorrowedAttributeValue::Any(_) | BorrowedAttributeValue::None => None,
+                    };
+                    self.set_attribute(self.ids[&id], name, value, ns);
+                }
+                Mutation::SetText { value, id } => {
+                    if let NodeKind::Text(text) = &mut self.nodes[self.ids[&id]].kind {
+                        *text = value.to_string();
+                    }
+                }
+                Mutation::NewEventListener { name, id, .. } => {
+                    if name == "mounted" {
+                        mounted.push(id);
+                    }
+                }
+                Mutation::Remove { id } => {
+                    if let Some(node) = self.ids.get(&id).copied() {
+                        self.detach(node);
+                    }
+                }
+                Mutation::PushRoot { id } => self.stack.push(self.ids[&id]),
+                Mutation::RemoveEventListener { .. }
+                | Mutation::MarkerStart { .. }
+                | Mutation::MarkerEnd { .. } => {}
+            }
+        }
+        mounted
+    }
+
+    fn create(&mut self, kind: NodeKind) -> usize {
+        self.nodes.push(Node {
+            kind,
+            id: None,
+            parent: None,
+            children: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn create_template_node(&mut self, node: &TemplateNode) -> usize {
+        match node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let element = self.create(NodeKind::Element {
+                    tag: tag.to_string(),
+                    attributes: Vec::new(),
+                    styles: Vec::new(),
+                    inner_html: None,
+                });
+                for attr in attrs.iter() {
+                    if let TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } = attr
+                    {
+                        self.set_attribute(element, name, Some(value.to_string()), *namespace);
+                    }
+                }
+                for child in children.iter() {
+                    let child = self.create_template_node(child);
+                    self.append(element, child);
+                }
+                element
+            }
+            TemplateNode::Text { text } => self.create(NodeKind::Text(text.to_string())),
+            TemplateNode::Dynamic { .. } => self.create(NodeKind::Placeholder),
+            TemplateNode::DynamicText { .. } => self.create(NodeKind::Text(String::new())),
+        }
+    }
+
+    fn clone_node(&mut self, node: usize) -> usize {
+        let kind = self.nodes[node].kind.clone();
+        let clone = self.create(kind);
+        for child in self.nodes[node].children.clone() {
+            let child = self.clone_node(child);
+            self.append(clone, child);
+        }
+        clone
+    }
+
+    fn set_node(&mut self, id: ElementId, node: usize) {
+        self.nodes[node].id = Some(id);
+        self.ids.insert(id, node);
+    }
+
+    fn top(&self) -> usize {
+        *self.stack.last().unwrap()
+    }
+
+    fn load_child(&self, path: &[u8]) -> usize {
+        path.iter().fold(self.top(), |node, &index| {
+            self.nodes[node].children[index as usize]
+        })
+    }
+
+    fn detach(&mut self, node: usize) {
+        if let Some(parent) = self.nodes[node].parent.take() {
+            self.nodes[parent].children.retain(|&child| child != node);
+        }
+    }
+
+    fn append(&mut self, parent: usize, child: usize) {
+        self.detach(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[parent].children.push(child);
+    }
+
+    // Insert the nodes next to the target, at an offset of 0 to insert them before it and 1 to insert them after it
+    fn insert(&mut self, target: usize, nodes: Vec<usize>, offset: usize) {
+        for &node in &nodes {
+            self.detach(node);
+        }
+        let parent = match self.nodes[target].parent {
+            Some(parent) => parent,
+            None => return,
+        };
+        for &node in &nodes {
+            self.nodes[node].parent = Some(parent);
+        }
+        let children = &mut self.nodes[parent].children;
+        let index = children.iter().position(|&child| child == target).unwrap() + offset;
+        children.splice(index..index, nodes);
+    }
+
+    fn replace(&mut self, target: usize, nodes: Vec<usize>) {
+        self.insert(target, nodes, 0);
+        self.detach(target);
+    }
+
+    fn set_attribute(&mut self, node: usize, name: &str, value: Option<String>, ns: Option<&str>) {
+        let (attributes, styles, inner_html) = match &mut self.nodes[node].kind {
+            NodeKind::Element {
+                attributes,
+                styles,
+                inner_html,
+                ..
+            } => (attributes, styles, inner_html),
+            _ => return,
+        };
+        if name == "dangerous_inner_html" {
+            *inner_html = value;
+            return;
+        }
+        let list = match ns {
+            Some("style") => styles,
+            _ => attributes,
+        };
+        let index = list.iter().position(|(existing, _)| existing == name);
+        match (index, value) {
+            (Some(index), Some(value)) => list[index].1 = value,
+            (None, Some(value)) => list.push((name.to_string(), value)),
+            (Some(index), None) => {
+                list.remove(index);
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Find the closest element to the element with the `id` attribute that the virtual dom knows about
+    fn find_listener(&self, id: &str) -> Option<ElementId> {
+        let mut node = self.find_by_id(ROOT, id)?;
+        loop {
+            if let Some(id) = self.nodes[node].id {
+                return Some(id);
+            }
+            node = self.nodes[node].parent?;
+        }
+    }
+
+    fn find_by_id(&self, node: usize, id: &str) -> Option<usize> {
+        if let NodeKind::Element { attributes, .. } = &self.nodes[node].kind {
+            if attributes
+                .iter()
+                .any(|(name, value)| name == "id" && value == id)
+            {
+                return Some(node);
+            }
+        }
+        self.nodes[node]
+            .children
+            .iter()
+            .find_map(|&child| self.find_by_id(child, id))
+    }
+
+    fn write_node(&self, html: &mut String, node: usize) {
+        match &self.nodes[node].kind {
+            NodeKind::Element {
+                tag,
+                attributes,
+                styles,
+                inner_html,
+            } => {
+                let _ = write!(html, "<{tag}");
+                for (name, value) in attributes {
+                    let _ = write!(html, " {name}=\"{}\"", escape(value));
+                }
+                if !styles.is_empty() {
+                    let style: Vec<_> = styles
+                        .iter()
+                        .map(|(name, value)| format!("{name}: {value};"))
+                        .collect();
+                    let _ = write!(html, " style=\"{}\"", escape(&style.join(" ")));
+                }
+                html.push('>');
+                if VOID_ELEMENTS.contains(&tag.as_str()) {
+                    return;
+                }
+                match inner_html {
+                    Some(inner_html) => html.push_str(inner_html),
+                    None => {
+                        for &child in &self.nodes[node].children {
+                            self.write_node(html, child);
+                        }
+                    }
+                }
+                let _ = write!(html, "</{tag}>");
+            }
+            NodeKind::Text(text) => html.push_str(&escape(text)),
+            NodeKind::Placeholder => html.push_str(r#"<pre hidden=""></pre>"#),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
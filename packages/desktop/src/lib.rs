@@ -3,16 +3,22 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![deny(missing_docs)]
 
+mod broadcast;
 mod cfg;
+mod context_menu;
 mod desktop_context;
 mod element;
 mod escape;
 mod eval;
 mod events;
 mod file_upload;
+mod headless;
+mod pdf;
 mod protocol;
 mod query;
+mod secure_storage;
 mod shortcut;
+mod system_status;
 mod waker;
 mod webview;
 
@@ -20,7 +26,11 @@ mod webview;
 mod mobile_shortcut;
 
 use crate::query::QueryResult;
+pub use broadcast::{use_broadcast_channel, BroadcastChannel};
 pub use cfg::{Config, WindowCloseBehaviour};
+pub use context_menu::{
+    use_context_menu, ContextMenu, ContextMenuEvent, ContextMenuHandle, ContextMenuItem,
+};
 pub use desktop_context::DesktopContext;
 pub use desktop_context::{
     use_window, use_wry_event_handler, DesktopService, WryEventHandler, WryEventHandlerId,
@@ -32,12 +42,16 @@ use dioxus_html::{native_bind::NativeFileEngine, FormData, HtmlEvent};
 use element::DesktopElement;
 use eval::init_eval;
 use futures_util::{pin_mut, FutureExt};
+pub use headless::HeadlessDom;
+pub use pdf::{PdfExportError, PdfOptions};
+pub use secure_storage::{use_secure_storage, SecureStorage, SecureStorageError};
 use shortcut::ShortcutRegistry;
 pub use shortcut::{use_global_shortcut, ShortcutHandle, ShortcutId, ShortcutRegistryError};
 use std::cell::Cell;
 use std::rc::Rc;
 use std::task::Waker;
 use std::{collections::HashMap, sync::Arc};
+pub use system_status::{use_battery, use_online, BatteryStatus};
 pub use tao::dpi::{LogicalSize, PhysicalSize};
 use tao::event_loop::{EventLoopProxy, EventLoopWindowTarget};
 pub use tao::window::WindowBuilder;
@@ -208,7 +222,10 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                 let cfg = cfg.take().unwrap();
 
                 // Create a dom
-                let dom = VirtualDom::new_with_props(root, props);
+                let mut dom = VirtualDom::new_with_props(root, props);
+                if let Some(budget) = cfg.mutation_budget {
+                    dom = dom.with_mutation_budget(budget);
+                }
 
                 let handler = create_new_window(
                     cfg,
@@ -272,6 +289,7 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
 
                     let HtmlEvent {
                         element,
+                        generation,
                         name,
                         bubbles,
                         data,
@@ -297,7 +315,12 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                         data.into_any()
                     };
 
-                    view.dom.handle_event(&name, as_any, element, bubbles);
+                    match generation {
+                        Some(generation) => view
+                            .dom
+                            .handle_generational_event(&name, as_any, element, generation, bubbles),
+                        None => view.dom.handle_event(&name, as_any, element, bubbles),
+                    }
 
                     send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
                 }
@@ -322,9 +345,18 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
 
                 EventData::Ipc(msg) if msg.method() == "initialize" => {
                     let view = webviews.get_mut(&event.1).unwrap();
+                    #[cfg(feature = "template_registry")]
+                    send_edits(view.dom.prebake_templates(), &view.desktop_context.webview);
                     send_edits(view.dom.rebuild(), &view.desktop_context.webview);
                 }
 
+                EventData::Ipc(msg) if msg.method() == "orphaned_nodes" => {
+                    log::warn!(
+                        "The interpreter tracks removed nodes with the ids {}",
+                        msg.params()["ids"]
+                    );
+                }
+
                 EventData::Ipc(msg) if msg.method() == "browser_open" => {
                     if let Some(temp) = msg.params().as_object() {
                         if temp.contains_key("href") {
@@ -0,0 +1,305 @@
+use std::path::Path;
+
+use futures_channel::oneshot;
+use thiserror::Error;
+use wry::webview::WebView;
+
+/// The page layout of a PDF exported with [`DesktopService::export_pdf`](crate::DesktopService::export_pdf).
+///
+/// All sizes are in millimeters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfOptions {
+    /// The width of the page in portrait orientation
+    pub page_width: f64,
+    /// The height of the page in portrait orientation
+    pub page_height: f64,
+    /// The margins of the page, in the order top, right, bottom, left
+    pub margins: [f64; 4],
+    /// Rotate the page to landscape orientation
+    pub landscape: bool,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self::a4()
+    }
+}
+
+impl PdfOptions {
+    /// An A4 page with 10mm margins
+    pub fn a4() -> Self {
+        Self::with_page_size(210.0, 297.0)
+    }
+
+    /// A US letter page with 10mm margins
+    pub fn letter() -> Self {
+        Self::with_page_size(215.9, 279.4)
+    }
+
+    /// A page of a custom size with 10mm margins
+    pub fn with_page_size(width: f64, height: f64) -> Self {
+        Self {
+            page_width: width,
+            page_height: height,
+            margins: [10.0; 4],
+            landscape: false,
+        }
+    }
+
+    /// Set the margins of the page, in the order top, right, bottom, left
+    pub fn with_margins(mut self, margins: [f64; 4]) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Rotate the page to landscape orientation
+    pub fn with_landscape(mut self, landscape: bool) -> Self {
+        self.landscape = landscape;
+        self
+    }
+}
+
+/// An error that can occur when exporting a PDF.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum PdfExportError {
+    /// The webview of the platform can't print to PDF.
+    #[error("Exporting a PDF is not supported on this platform")]
+    Unsupported,
+    /// The webview failed to print the page.
+    #[error("Error exporting the PDF: {0}")]
+    Failed(String),
+}
+
+type PdfResult = oneshot::Sender<Result<(), PdfExportError>>;
+
+pub(crate) async fn export_pdf(
+    webview: &WebView,
+    path: &Path,
+    options: PdfOptions,
+) -> Result<(), PdfExportError> {
+    let (tx, rx) = oneshot::channel();
+    print_to_pdf(webview, path, options, tx);
+    rx.await
+        .unwrap_or_else(|_| Err(PdfExportError::Failed("the print job was cancelled".into())))
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn print_to_pdf(webview: &WebView, path: &Path, options: PdfOptions, tx: PdfResult) {
+    use gtk::{PageOrientation, PageSetup, PaperSize, PrintSettings, Unit};
+    use std::{cell::RefCell, rc::Rc};
+    use webkit2gtk::{PrintOperation, PrintOperationExt};
+    use wry::webview::WebviewExtUnix;
+
+    let uri = match gtk::glib::filename_to_uri(path, None) {
+        Ok(uri) => uri,
+        Err(err) => {
+            let _ = tx.send(Err(PdfExportError::Failed(err.to_string())));
+            return;
+        }
+    };
+
+    let settings = PrintSettings::new();
+    settings.set_printer("Print to File");
+    settings.set(*gtk::PRINT_SETTINGS_OUTPUT_FILE_FORMAT, Some("pdf"));
+    settings.set(*gtk::PRINT_SETTINGS_OUTPUT_URI, Some(uri.as_str()));
+
+    let page_setup = PageSetup::new();
+    page_setup.set_paper_size(&PaperSize::new_custom(
+        "dioxus-pdf",
+        "PDF",
+        options.page_width,
+        options.page_height,
+        Unit::Mm,
+    ));
+    let [top, right, bottom, left] = options.margins;
+    page_setup.set_top_margin(top, Unit::Mm);
+    page_setup.set_right_margin(right, Unit::Mm);
+    page_setup.set_bottom_margin(bottom, Unit::Mm);
+    page_setup.set_left_margin(left, Unit::Mm);
+    page_setup.set_orientation(if options.landscape {
+        PageOrientation::Landscape
+    } else {
+        PageOrientation::Portrait
+    });
+
+    let operation = PrintOperation::new(&*webview.webview());
+    operation.set_print_settings(&settings);
+    operation.set_page_setup(&page_setup);
+
+    // Either finished or failed is emitted, but both handlers need to own the sender
+    let tx = Rc::new(RefCell::new(Some(tx)));
+    operation.connect_failed({
+        let tx = tx.clone();
+        move |_, err| {
+            if let Some(tx) = tx.borrow_mut().take() {
+                let _ = tx.send(Err(PdfExportError::Failed(err.to_string())));
+            }
+        }
+    });
+    operation.connect_finished(move |_| {
+        if let Some(tx) = tx.borrow_mut().take() {
+            let _ = tx.send(Ok(()));
+        }
+    });
+    operation.print();
+}
+
+#[cfg(target_os = "windows")]
+fn print_to_pdf(webview: &WebView, path: &Path, options: PdfOptions, tx: PdfResult) {
+    use webview2_com::{
+        Microsoft::Web::WebView2::Win32::{
+            ICoreWebView2Environment6, ICoreWebView2_2, ICoreWebView2_7,
+            COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE, COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+        },
+        PrintToPdfCompletedHandler,
+    };
+    use windows::core::{Interface, HSTRING};
+    use wry::webview::WebviewExtWindows;
+
+    const MM_PER_INCH: f64 = 25.4;
+
+    let print = |tx: &mut Option<PdfResult>| -> windows::core::Result<()> {
+        unsafe {
+            let core = webview.controller().CoreWebView2()?;
+            let environment = core
+                .cast::<ICoreWebView2_2>()?
+                .Environment()?
+                .cast::<ICoreWebView2Environment6>()?;
+
+            let settings = environment.CreatePrintSettings()?;
+            settings.SetPageWidth(options.page_width / MM_PER_INCH)?;
+            settings.SetPageHeight(options.page_height / MM_PER_INCH)?;
+            let [top, right, bottom, left] = options.margins;
+            settings.SetMarginTop(top / MM_PER_INCH)?;
+            settings.SetMarginRight(right / MM_PER_INCH)?;
+            settings.SetMarginBottom(bottom / MM_PER_INCH)?;
+            settings.SetMarginLeft(left / MM_PER_INCH)?;
+            settings.SetOrientation(if options.landscape {
+                COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE
+            } else {
+                COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT
+            })?;
+
+            // WebView2 runtimes before 1.0.1020 don't implement ICoreWebView2_7 and fail with E_NOINTERFACE
+            let core = core.cast::<ICoreWebView2_7>()?;
+            let tx = tx.take();
+            core.PrintToPdf(
+                &HSTRING::from(path.as_os_str()),
+                &settings,
+                &PrintToPdfCompletedHandler::create(Box::new(move |result, success| {
+                    if let Some(tx) = tx {
+                        let _ = tx.send(match result {
+                            Ok(()) if success => Ok(()),
+                            Ok(()) => {
+                                Err(PdfExportError::Failed("the page was not printed".into()))
+                            }
+                            Err(err) => Err(PdfExportError::Failed(err.to_string())),
+                        });
+                    }
+                    Ok(())
+                })),
+            )
+        }
+    };
+
+    let mut tx = Some(tx);
+    if let Err(err) = print(&mut tx) {
+        if let Some(tx) = tx {
+            let _ = tx.send(Err(PdfExportError::Failed(err.to_string())));
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn print_to_pdf(webview: &WebView, path: &Path, options: PdfOptions, tx: PdfResult) {
+    use objc::runtime::{Object, BOOL, NO, YES};
+    use objc::*;
+    use wry::webview::WebviewExtMacOS;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct NSSize {
+        width: f64,
+        height: f64,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct NSRect {
+        origin: NSSize,
+        size: NSSize,
+    }
+
+    // Cocoa measures pages in points
+    const POINTS_PER_MM: f64 = 72.0 / 25.4;
+
+    unsafe fn ns_string(string: &str) -> *mut Object {
+        let ns_string: *mut Object = msg_send![class!(NSString), alloc];
+        let ns_string: *mut Object = msg_send![ns_string, initWithBytes: string.as_ptr() length: string.len() encoding: 4usize];
+        msg_send![ns_string, autorelease]
+    }
+
+    let result = unsafe {
+        let print_info: *mut Object = msg_send![class!(NSPrintInfo), sharedPrintInfo];
+        let print_info: *mut Object = msg_send![print_info, copy];
+
+        let url: *mut Object = msg_send![
+            class!(NSURL),
+            fileURLWithPath: ns_string(&path.to_string_lossy())
+        ];
+        let dictionary: *mut Object = msg_send![print_info, dictionary];
+        let () = msg_send![dictionary, setObject: ns_string("NSPrintSaveJob") forKey: ns_string("NSJobDisposition")];
+        let () = msg_send![dictionary, setObject: url forKey: ns_string("NSJobSavingURL")];
+
+        let () = msg_send![print_info, setPaperSize: NSSize {
+            width: options.page_width * POINTS_PER_MM,
+            height: options.page_height * POINTS_PER_MM,
+        }];
+        let [top, right, bottom, left] = options.margins;
+        let () = msg_send![print_info, setTopMargin: top * POINTS_PER_MM];
+        let () = msg_send![print_info, setRightMargin: right * POINTS_PER_MM];
+        let () = msg_send![print_info, setBottomMargin: bottom * POINTS_PER_MM];
+        let () = msg_send![print_info, setLeftMargin: left * POINTS_PER_MM];
+        let () =
+            msg_send![print_info, setOrientation: if options.landscape { 1isize } else { 0isize }];
+
+        let webview = webview.webview();
+        let operation: *mut Object = msg_send![webview, printOperationWithPrintInfo: print_info];
+        let () = msg_send![operation, setShowsPrintPanel: NO];
+        let () = msg_send![operation, setShowsProgressPanel: NO];
+        // The print view of WKWebView has no size until it is given one, which prints blank pages
+        let bounds: NSRect = msg_send![webview, bounds];
+        let print_view: *mut Object = msg_send![operation, view];
+        let () = msg_send![print_view, setFrame: bounds];
+
+        let printed: BOOL = msg_send![operation, runOperation];
+        let () = msg_send![print_info, release];
+        printed == YES
+    };
+
+    let _ = tx.send(if result {
+        Ok(())
+    } else {
+        Err(PdfExportError::Failed("the page was not printed".into()))
+    });
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "windows",
+    target_os = "macos"
+)))]
+fn print_to_pdf(_: &WebView, _: &Path, _: PdfOptions, tx: PdfResult) {
+    let _ = tx.send(Err(PdfExportError::Unsupported));
+}
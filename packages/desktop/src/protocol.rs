@@ -9,6 +9,12 @@ use wry::{
 };
 
 fn module_loader(root_name: &str) -> String {
+    // In debug builds the interpreter reports nodes it still tracks after they were removed from the document
+    let report_orphans = if cfg!(debug_assertions) {
+        r#"(ids) => window.ipc.postMessage(serializeIpcMessage("orphaned_nodes", { ids }))"#
+    } else {
+        "null"
+    };
     let js = INTERPRETER_JS.replace(
         "/*POST_HANDLE_EDITS*/",
         r#"// Prevent file inputs from opening the file dialog on click
@@ -43,7 +49,7 @@ fn module_loader(root_name: &str) -> String {
     let rootname = "{root_name}";
     let root = window.document.getElementById(rootname);
     if (root != null) {{
-        window.interpreter = new Interpreter(root, new InterpreterConfig(true));
+        window.interpreter = new Interpreter(root, new InterpreterConfig(true, {report_orphans}));
         window.ipc.postMessage(serializeIpcMessage("initialize"));
     }}
 </script>
@@ -68,7 +68,7 @@ struct QueryEntry {
     return_sender: Option<tokio::sync::oneshot::Sender<Value>>,
 }
 
-const QUEUE_NAME: &str = "__msg_queues";
+pub(crate) const QUEUE_NAME: &str = "__msg_queues";
 
 /// Handles sending and receiving arbitrary queries from the webview. Queries can be resolved non-sequentially, so we use ids to track them.
 #[derive(Clone, Default)]
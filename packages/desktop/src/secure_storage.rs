@@ -0,0 +1,497 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use dioxus_core::ScopeState;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use thiserror::Error;
+
+/// Get the secure storage of the app. `service` is the name of the app in the storage.
+///
+/// Values are stored in the keychain of the OS: the Keychain on macOS and iOS, the Credential Manager on Windows and
+/// the Secret Service on Linux. If there is no keychain, for example on Linux without a Secret Service or on the BSDs,
+/// the values are stored in a file in the local data directory of the app that is encrypted with ChaCha20-Poly1305, so
+/// they are never written to disk in plain text.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let storage = dioxus_desktop::use_secure_storage(cx, "my-app");
+///     let token = use_state(cx, || storage.get("token").ok().flatten());
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 if storage.delete("token").is_ok() {
+///                     token.set(None);
+///                 }
+///             },
+///             "Log out"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_secure_storage<'a>(cx: &'a ScopeState, service: &str) -> &'a SecureStorage {
+    cx.use_hook(|| SecureStorage::new(service))
+}
+
+/// Secrets like auth tokens that are stored in the keychain of the OS, or in an encrypted file if there is no
+/// keychain. Created with [`use_secure_storage`].
+#[derive(Debug, Clone)]
+pub struct SecureStorage {
+    service: String,
+    // The directory of the encrypted file
+    directory: Option<PathBuf>,
+}
+
+// The name of the keychain entry of the key the fallback file is encrypted with
+const FILE_KEY_ENTRY: &str = "dioxus-secure-storage-key";
+
+impl SecureStorage {
+    /// Create a secure storage for the app named `service`.
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            directory: dirs::data_local_dir().map(|dir| dir.join(service).join("secure_storage")),
+        }
+    }
+
+    /// Get the value stored for `key`.
+    pub fn get(&self, key: &str) -> Result<Option<String>, SecureStorageError> {
+        #[cfg(any(
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "linux"
+        ))]
+        match keychain::entry(&self.service, key).and_then(|entry| entry.get_password()) {
+            Ok(value) => return Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => return Ok(None),
+            Err(err) if !keychain::unavailable(&err) => return Err(err.into()),
+            Err(_) => {}
+        }
+
+        Ok(self.read_file()?.remove(key))
+    }
+
+    /// Store `value` for `key`, replacing the old value.
+    pub fn set(&self, key: &str, value: &str) -> Result<(), SecureStorageError> {
+        #[cfg(any(
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "linux"
+        ))]
+        match keychain::entry(&self.service, key).and_then(|entry| entry.set_password(value)) {
+            Ok(()) => return Ok(()),
+            Err(err) if !keychain::unavailable(&err) => return Err(err.into()),
+            Err(_) => {}
+        }
+
+        let mut values = self.read_file()?;
+        values.insert(key.to_string(), value.to_string());
+        self.write_file(&values)
+    }
+
+    /// Remove the value stored for `key`, if there is one.
+    pub fn delete(&self, key: &str) -> Result<(), SecureStorageError> {
+        #[cfg(any(
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "linux"
+        ))]
+        match keychain::entry(&self.service, key).and_then(|entry| entry.delete_password()) {
+            Ok(()) | Err(keyring::Error::NoEntry) => return Ok(()),
+            Err(err) if !keychain::unavailable(&err) => return Err(err.into()),
+            Err(_) => {}
+        }
+
+        let mut values = self.read_file()?;
+        if values.remove(key).is_some() {
+            self.write_file(&values)?;
+        }
+        Ok(())
+    }
+
+    fn file(&self) -> Result<PathBuf, SecureStorageError> {
+        let directory = self
+            .directory
+            .as_ref()
+            .ok_or(SecureStorageError::NoDataDirectory)?;
+        Ok(directory.join("values"))
+    }
+
+    // The key of the encrypted file is a random key in the keychain where the keychain can store it, and is derived
+    // from the machine, the user and the app everywhere else. The key is never written next to the file.
+    fn cipher(&self) -> Result<ChaCha20Poly1305, SecureStorageError> {
+        #[cfg(any(
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "linux"
+        ))]
+        if let Some(key) = keychain::file_key(&self.service)? {
+            return Ok(ChaCha20Poly1305::new(&key));
+        }
+
+        Ok(ChaCha20Poly1305::new(&self.platform_key()?))
+    }
+
+    fn platform_key(&self) -> Result<Key, SecureStorageError> {
+        let machine = machine_id().ok_or(SecureStorageError::NoMachineId)?;
+        let user = dirs::home_dir().unwrap_or_default();
+        let mut input = machine.into_bytes();
+        input.extend_from_slice(user.to_string_lossy().as_bytes());
+
+        let mut key = Key::default();
+        Hkdf::<Sha256>::new(Some(FILE_KEY_ENTRY.as_bytes()), &input)
+            .expand(self.service.as_bytes(), &mut key)
+            .expect("32 bytes is a valid length for HKDF-SHA256");
+        Ok(key)
+    }
+
+    fn read_file(&self) -> Result<HashMap<String, String>, SecureStorageError> {
+        let data = match fs::read(self.file()?) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(err) => return Err(err.into()),
+        };
+        if data.len() < 12 {
+            return Err(SecureStorageError::Corrupted);
+        }
+
+        let (nonce, ciphertext) = data.split_at(12);
+        let plaintext = self
+            .cipher()?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| SecureStorageError::Corrupted)?;
+        serde_json::from_slice(&plaintext).map_err(|_| SecureStorageError::Corrupted)
+    }
+
+    fn write_file(&self, values: &HashMap<String, String>) -> Result<(), SecureStorageError> {
+        let cipher = self.cipher()?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(values).map_err(|_| SecureStorageError::Corrupted)?;
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| SecureStorageError::Corrupted)?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        let file = self.file()?;
+        if let Some(directory) = file.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        write_private(&file, &data)
+    }
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux"
+))]
+mod keychain {
+    use super::*;
+
+    // Every value is a separate entry in the keychain, named after the app and the key
+    pub(super) fn entry(service: &str, key: &str) -> keyring::Result<keyring::Entry> {
+        keyring::Entry::new(service, key)
+    }
+
+    pub(super) fn unavailable(err: &keyring::Error) -> bool {
+        matches!(
+            err,
+            keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+        )
+    }
+
+    /// Get the key of the encrypted file from the keychain, and create it if there is none. Returns `None` if the
+    /// keychain is not available.
+    pub(super) fn file_key(service: &str) -> Result<Option<Key>, SecureStorageError> {
+        let entry = match entry(service, FILE_KEY_ENTRY) {
+            Ok(entry) => entry,
+            Err(err) if unavailable(&err) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        match entry.get_password() {
+            Ok(key) => {
+                let key = hex_decode(&key).ok_or(SecureStorageError::Corrupted)?;
+                Ok(Some(*Key::from_slice(&key)))
+            }
+            Err(keyring::Error::NoEntry) => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                let encoded: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+                match entry.set_password(&encoded) {
+                    Ok(()) => Ok(Some(key)),
+                    Err(err) if unavailable(&err) => Ok(None),
+                    Err(err) => Err(err.into()),
+                }
+            }
+            Err(err) if unavailable(&err) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+        if hex.len() != 64 {
+            return None;
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect()
+    }
+}
+
+/// A stable id of the machine, that is not stored with the encrypted file
+fn machine_id() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("ioreg")
+            .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+            .output()
+            .ok()?;
+        let output = String::from_utf8(output.stdout).ok()?;
+        let line = output
+            .lines()
+            .find(|line| line.contains("IOPlatformUUID"))?;
+        line.rsplit('"').nth(1).map(str::to_string)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let output = std::process::Command::new("reg")
+            .args([
+                "query",
+                r"HKLM\SOFTWARE\Microsoft\Cryptography",
+                "/v",
+                "MachineGuid",
+            ])
+            .output()
+            .ok()?;
+        let output = String::from_utf8(output.stdout).ok()?;
+        let line = output.lines().find(|line| line.contains("MachineGuid"))?;
+        line.split_whitespace().last().map(str::to_string)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        ["/etc/machine-id", "/var/lib/dbus/machine-id", "/etc/hostid"]
+            .iter()
+            .find_map(|path| {
+                let id = fs::read_to_string(path).ok()?;
+                let id = id.trim();
+                (!id.is_empty()).then(|| id.to_string())
+            })
+    }
+}
+
+// Write a file that only the current user can read
+fn write_private(path: &Path, data: &[u8]) -> Result<(), SecureStorageError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, data)?;
+    Ok(())
+}
+
+/// An error that can occur when accessing the [`SecureStorage`].
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SecureStorageError {
+    /// The keychain of the OS returned an error.
+    #[error("Error accessing the keychain: {0}")]
+    Keychain(String),
+    /// Reading or writing the encrypted storage file failed.
+    #[error("Error accessing the secure storage file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The encrypted storage file or its key could not be read.
+    #[error("The secure storage file is corrupted")]
+    Corrupted,
+    /// The keychain is not available and there is no data directory to store the encrypted file in.
+    #[error("No data directory to store the secure storage file in")]
+    NoDataDirectory,
+    /// The keychain is not available and the machine has no id to derive the key of the encrypted file from.
+    #[error("No machine id to derive the key of the secure storage file from")]
+    NoMachineId,
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "linux"
+))]
+impl From<keyring::Error> for SecureStorageError {
+    fn from(err: keyring::Error) -> Self {
+        Self::Keychain(err.to_string())
+    }
+}
+
+#[cfg(all(
+    test,
+    any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "linux"
+    )
+))]
+mod tests {
+    use super::*;
+    use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+    use std::{
+        any::Any,
+        sync::{Arc, Mutex, Once},
+    };
+
+    // An in memory keychain. Apps named "locked" can't access it, like a Linux desktop without a Secret Service.
+    #[derive(Debug, Default)]
+    struct MemoryKeychain(Arc<Mutex<HashMap<(String, String), String>>>);
+
+    #[derive(Debug)]
+    struct MemoryCredential {
+        values: Arc<Mutex<HashMap<(String, String), String>>>,
+        name: (String, String),
+    }
+
+    impl CredentialBuilderApi for MemoryKeychain {
+        fn build(
+            &self,
+            _: Option<&str>,
+            service: &str,
+            user: &str,
+        ) -> keyring::Result<Box<Credential>> {
+            if service == "locked" {
+                return Err(keyring::Error::NoStorageAccess("locked".into()));
+            }
+            Ok(Box::new(MemoryCredential {
+                values: self.0.clone(),
+                name: (service.to_string(), user.to_string()),
+            }))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    impl CredentialApi for MemoryCredential {
+        fn set_password(&self, password: &str) -> keyring::Result<()> {
+            let mut values = self.values.lock().unwrap();
+            values.insert(self.name.clone(), password.to_string());
+            Ok(())
+        }
+
+        fn get_password(&self) -> keyring::Result<String> {
+            let values = self.values.lock().unwrap();
+            values
+                .get(&self.name)
+                .cloned()
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_password(&self) -> keyring::Result<()> {
+            let mut values = self.values.lock().unwrap();
+            values
+                .remove(&self.name)
+                .map(|_| ())
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn use_memory_keychain() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            keyring::set_default_credential_builder(Box::<MemoryKeychain>::default());
+        });
+    }
+
+    #[test]
+    fn stores_values_in_the_keychain() {
+        use_memory_keychain();
+        let storage = SecureStorage::new("app");
+        let other = SecureStorage::new("other-app");
+
+        assert_eq!(storage.get("token").unwrap(), None);
+        storage.set("token", "secret").unwrap();
+        assert_eq!(storage.get("token").unwrap().as_deref(), Some("secret"));
+        // every app has its own values
+        assert_eq!(other.get("token").unwrap(), None);
+
+        storage.set("token", "new secret").unwrap();
+        assert_eq!(storage.get("token").unwrap().as_deref(), Some("new secret"));
+        storage.delete("token").unwrap();
+        assert_eq!(storage.get("token").unwrap(), None);
+        // deleting a missing value is not an error
+        storage.delete("token").unwrap();
+    }
+
+    /// A storage of an app without a keychain, that stores the encrypted file in the temp dir
+    fn without_keychain(test: &str) -> SecureStorage {
+        let directory = std::env::temp_dir().join(format!(
+            "dioxus-secure-storage-{test}-{}",
+            std::process::id()
+        ));
+        _ = fs::remove_dir_all(&directory);
+        SecureStorage {
+            service: "locked".to_string(),
+            directory: Some(directory),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_an_encrypted_file() {
+        use_memory_keychain();
+        let storage = without_keychain("fallback");
+
+        assert_eq!(storage.get("token").unwrap(), None);
+        storage.set("token", "secret").unwrap();
+        assert_eq!(storage.get("token").unwrap().as_deref(), Some("secret"));
+
+        // The value is not stored in plain text, and the key is not stored with it
+        let directory = storage.directory.clone().unwrap();
+        let files: Vec<_> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["values"]);
+        let data = fs::read(directory.join("values")).unwrap();
+        assert!(!data.windows(6).any(|window| window == b"secret"));
+
+        storage.delete("token").unwrap();
+        assert_eq!(storage.get("token").unwrap(), None);
+        _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn files_of_other_apps_can_not_be_decrypted() {
+        use_memory_keychain();
+        let storage = without_keychain("other-app");
+        storage.set("token", "secret").unwrap();
+
+        let other = SecureStorage {
+            service: "other-app".to_string(),
+            directory: storage.directory.clone(),
+        };
+        assert!(matches!(
+            other
+                .platform_key()
+                .map(|key| key == storage.platform_key().unwrap()),
+            Ok(false)
+        ));
+        _ = fs::remove_dir_all(storage.directory.unwrap());
+    }
+}
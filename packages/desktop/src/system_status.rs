@@ -0,0 +1,167 @@
+use std::{cell::Cell, rc::Rc, sync::Arc, time::Duration};
+
+use dioxus_core::ScopeState;
+
+use crate::{query::QUEUE_NAME, DesktopContext};
+
+/// How often the battery is read. The OS doesn't notify apps about changes on every platform, so it is polled.
+const BATTERY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The charge of the battery of the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryStatus {
+    /// How full the battery is, from 0 to 1
+    pub level: f64,
+    /// The battery is being charged
+    pub charging: bool,
+}
+
+/// Get the charge of the battery of the device, for example to do less work in the background while it is not
+/// charging. The component rerenders when the level or the charging status changes.
+///
+/// This mirrors `use_battery` of the web renderer. The battery is read every 30 seconds. Returns [`None`] until the
+/// battery is read, and on devices without a battery. If the device has several batteries, the level is their
+/// average and the device is charging if any of them is.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let battery = dioxus_desktop::use_battery(cx);
+///     let low_power = matches!(battery, Some(battery) if !battery.charging && battery.level < 0.2);
+///
+///     render! {
+///         if low_power {
+///             rsx! { "Syncing is paused to save battery" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_battery(cx: &ScopeState) -> Option<BatteryStatus> {
+    let status = cx.use_hook(|| {
+        let status = Rc::new(Cell::new(None));
+        cx.spawn(watch_battery(status.clone(), cx.schedule_update()));
+        status
+    });
+    status.get()
+}
+
+async fn watch_battery(
+    status: Rc<Cell<Option<BatteryStatus>>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+) {
+    let mut interval = tokio::time::interval(BATTERY_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        // Reading the battery can block on some platforms
+        let new = tokio::task::spawn_blocking(read_battery)
+            .await
+            .ok()
+            .flatten();
+        if status.replace(new) != new {
+            update();
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd"
+))]
+fn read_battery() -> Option<BatteryStatus> {
+    let manager = match starship_battery::Manager::new() {
+        Ok(manager) => manager,
+        Err(err) => {
+            log::warn!("Failed to read the battery: {err}");
+            return None;
+        }
+    };
+    let batteries: Vec<_> = manager.batteries().ok()?.flatten().collect();
+    if batteries.is_empty() {
+        return None;
+    }
+
+    let level = batteries
+        .iter()
+        .map(|battery| battery.state_of_charge().value as f64)
+        .sum::<f64>()
+        / batteries.len() as f64;
+    let charging = batteries
+        .iter()
+        .any(|battery| battery.state() == starship_battery::State::Charging);
+    Some(BatteryStatus { level, charging })
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd"
+)))]
+fn read_battery() -> Option<BatteryStatus> {
+    None
+}
+
+/// Check if the device is connected to a network, for example to show a banner while the app is offline. The
+/// component rerenders when the device goes online or offline.
+///
+/// This mirrors `use_online` of the web renderer, and uses the network status the webview reports.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let online = dioxus_desktop::use_online(cx);
+///
+///     render! {
+///         if !online {
+///             rsx! { div { class: "banner", "You are offline" } }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_online(cx: &ScopeState) -> bool {
+    let online = cx.use_hook(|| {
+        // Assume the device is online until the webview reports otherwise
+        let online = Rc::new(Cell::new(true));
+        if let Some(desktop) = cx.consume_context::<DesktopContext>() {
+            cx.spawn(watch_online(desktop, online.clone(), cx.schedule_update()));
+        }
+        online
+    });
+    online.get()
+}
+
+async fn watch_online(
+    desktop: DesktopContext,
+    online: Rc<Cell<bool>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+) {
+    // The query is dropped with the task, which replaces its message queue. The listeners remove themselves once they
+    // see that, so they never send to a new query that reuses the id.
+    let script = format!(
+        r#"
+        const listener = () => {{
+            if (window.{QUEUE_NAME}[_request_id] !== _message_queue) {{
+                window.removeEventListener("online", listener);
+                window.removeEventListener("offline", listener);
+                return;
+            }}
+            dioxus.send(navigator.onLine);
+        }};
+        window.addEventListener("online", listener);
+        window.addEventListener("offline", listener);
+        dioxus.send(navigator.onLine);
+        await new Promise(() => {{}});
+        "#
+    );
+    let mut query = desktop.query.new_query::<bool>(&script, desktop.clone());
+    while let Ok(value) = query.recv().await {
+        let Some(now_online) = value.as_bool() else {
+            continue;
+        };
+        if online.replace(now_online) != now_online {
+            update();
+        }
+    }
+}
@@ -12,7 +12,10 @@ pub fn build(
     event_loop: &EventLoopWindowTarget<UserWindowEvent>,
     proxy: EventLoopProxy<UserWindowEvent>,
 ) -> (WebView, WebContext) {
-    let builder = cfg.window.clone();
+    let mut builder = cfg.window.clone();
+    if cfg.headless {
+        builder = builder.with_visible(false).with_focused(false);
+    }
     let window = builder.build(event_loop).unwrap();
     let file_handler = cfg.file_drop_handler.take();
     let custom_head = cfg.custom_head.clone();
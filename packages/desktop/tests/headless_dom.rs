@@ -0,0 +1,60 @@
+//! Verify that apps render and handle events without a window.
+#![allow(non_snake_case)]
+
+use dioxus::html::MouseData;
+use dioxus::prelude::*;
+use dioxus_desktop::HeadlessDom;
+
+fn app(cx: Scope) -> Element {
+    let items = use_state(cx, || vec![1, 2, 3]);
+    let show = use_state(cx, || true);
+    let mounted = use_state(cx, || 0);
+
+    render! {
+        div {
+            id: "list",
+            onclick: move |_| {
+                items.set(vec![3, 1, 4]);
+                show.set(!show.get());
+            },
+            span { id: "shuffle", "shuffle" }
+            ul {
+                for i in items.iter() {
+                    li { key: "{i}", color: "red", "{i}" }
+                }
+            }
+            if **show {
+                rsx! { p { "shown" } }
+            }
+            input { r#type: "checkbox", onmounted: move |_| mounted.set(mounted + 1) }
+            "{mounted}"
+        }
+    }
+}
+
+#[test]
+fn renders_without_a_window() {
+    let mut dom = HeadlessDom::new(app);
+
+    // Mounted events are sent once the elements are created
+    dom.render();
+    assert_eq!(
+        dom.snapshot(),
+        r#"<div id="list"><span id="shuffle">shuffle</span><ul><li style="color: red;">1</li><li style="color: red;">2</li><li style="color: red;">3</li></ul><p>shown</p><input type="checkbox">1</div>"#
+    );
+}
+
+#[test]
+fn injects_events_by_id() {
+    let mut dom = HeadlessDom::new(app);
+
+    // The event bubbles up from the span to the listener of the list
+    assert!(dom.dispatch_event("shuffle", "click", MouseData::default()));
+    let html = dom.snapshot();
+    assert!(html.contains(r#"<ul><li style="color: red;">3</li><li style="color: red;">1</li><li style="color: red;">4</li></ul><pre hidden=""></pre>"#));
+
+    assert!(dom.dispatch_event("shuffle", "click", MouseData::default()));
+    assert!(dom.snapshot().contains("<p>shown</p>"));
+
+    assert!(!dom.dispatch_event("missing", "click", MouseData::default()));
+}
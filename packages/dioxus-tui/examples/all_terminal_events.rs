@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use dioxus_html::{FocusData, KeyboardData, MouseData, WheelData};
+use dioxus_html::{ClipboardData, FocusData, KeyboardData, MouseData, WheelData};
 use std::rc::Rc;
 
 fn main() {
@@ -22,6 +22,8 @@ enum Event {
 
     FocusIn(Rc<FocusData>),
     FocusOut(Rc<FocusData>),
+
+    Paste(Rc<ClipboardData>),
 }
 
 const MAX_EVENTS: usize = 8;
@@ -72,6 +74,8 @@ fn app(cx: Scope) -> Element {
                 onfocusin: move |event| log_event(Event::FocusIn(event.inner().clone())),
                 onfocusout: move |event| log_event(Event::FocusOut(event.inner().clone())),
 
+                onpaste: move |event| log_event(Event::Paste(event.inner().clone())),
+
                 "Hover, click, type or scroll to see the info down below"
             },
             div {
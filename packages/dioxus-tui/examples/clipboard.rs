@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+use dioxus_tui::{Point, Selection, TuiContext};
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+/// Paste into the input, copy its value with the button, or drag the mouse over some text and copy it with
+/// `ctrl+shift+c`.
+fn app(cx: Scope) -> Element {
+    let value = use_state(cx, String::new);
+    let pasted = use_state(cx, || 0);
+    let selected = use_state(cx, String::new);
+    let ctx: TuiContext = cx.consume_context().unwrap();
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            onselect: {
+                let ctx = ctx.clone();
+                move |_| selected.set(ctx.selected_text().unwrap_or_default())
+            },
+
+            "Drag the mouse over this text to select it"
+
+            input {
+                width: "50%",
+                value: "{value}",
+                oninput: move |evt| value.set(evt.value.clone()),
+                onpaste: move |_| *pasted.make_mut() += 1,
+            }
+            "Pasted {pasted} times"
+            "Selected: {selected}"
+
+            button {
+                onclick: {
+                    let ctx = ctx.clone();
+                    move |_| ctx.copy_to_clipboard(value)
+                },
+                "Copy the value"
+            }
+            button {
+                onclick: move |_| {
+                    let selection = Selection::new(Point { x: 0, y: 0 }, Point { x: 56, y: 0 });
+                    ctx.set_selection(Some(selection));
+                },
+                "Select the first line"
+            }
+        }
+    })
+}
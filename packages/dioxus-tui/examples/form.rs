@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let name = use_state(cx, String::new);
+    let port = use_state(cx, || "8080".to_string());
+
+    let name_error = name.is_empty().then_some("The name is required");
+    let port_error = port
+        .parse::<u16>()
+        .is_err()
+        .then_some("The port must be a number between 0 and 65535");
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+
+            // labels and fields are aligned in two columns, errors are rendered in red below their field
+            fieldset {
+                width: "60%",
+                border_width: "1px",
+
+                legend { "Server configuration" }
+
+                label { "Name" }
+                input {
+                    width: "100%",
+                    height: "3px",
+                    oninput: move |evt| name.set(evt.value.clone()),
+                }
+                if let Some(error) = name_error {
+                    rsx! { div { role: "alert", "{error}" } }
+                }
+
+                label { "Listen port" }
+                input {
+                    width: "100%",
+                    height: "3px",
+                    value: "{port}",
+                    oninput: move |evt| port.set(evt.value.clone()),
+                }
+                if let Some(error) = port_error {
+                    rsx! { div { role: "alert", "{error}" } }
+                }
+            }
+        }
+    })
+}
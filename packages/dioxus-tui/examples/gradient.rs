@@ -0,0 +1,29 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            justify_content: "center",
+            align_items: "center",
+            background: "linear-gradient(to bottom right, #1e3a8a, #9333ea 60%, #f472b6)",
+
+            div {
+                width: "50%",
+                height: "50%",
+                justify_content: "center",
+                align_items: "center",
+                background_color: "white",
+                color: "black",
+                box_shadow: "2px 1px 2px rgba(0, 0, 0, 60%)",
+
+                "Gradients and shadows"
+            }
+        }
+    })
+}
@@ -0,0 +1,29 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+/// Images are drawn with the kitty graphics protocol or sixel graphics if the terminal supports them, and with half
+/// block characters otherwise.
+fn app(cx: Scope) -> Element {
+    let src = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/example.png");
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            justify_content: "center",
+            align_items: "center",
+
+            "The image is stretched to the size of the element"
+
+            img {
+                width: "80%",
+                height: "80%",
+                src: src,
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+use dioxus::prelude::*;
+use dioxus_html::input_data::keyboard_types::Code;
+use dioxus_tui::{Config, TuiContext};
+
+const FRUITS: &[&str] = &["apple", "banana", "cherry", "grape", "mango", "orange"];
+
+/// Pick a fruit in 8 rows below the prompt, without leaving the scrollback of the shell.
+fn main() {
+    dioxus_tui::launch_cfg(app, Config::default().inline(8));
+}
+
+fn app(cx: Scope) -> Element {
+    let selected = use_state(cx, || 0);
+    let ctx: TuiContext = cx.consume_context().unwrap();
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            border_width: "1px",
+            tabindex: "0",
+            onkeydown: move |e| match e.inner().code() {
+                Code::ArrowUp => selected.set(selected.saturating_sub(1)),
+                Code::ArrowDown => selected.set((**selected + 1).min(FRUITS.len() - 1)),
+                Code::Enter => ctx.quit(),
+                _ => {}
+            },
+
+            FRUITS.iter().enumerate().map(|(i, fruit)| rsx! {
+                div {
+                    key: "{fruit}",
+                    height: "1px",
+                    background_color: if i == **selected { "blue" } else { "black" },
+                    "{fruit}"
+                }
+            })
+        }
+    })
+}
@@ -0,0 +1,90 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+/// Open a dialog over the page, and a dropdown over the content below it. Positioned elements are drawn over the
+/// elements around them, and receive the clicks on the cells they cover.
+fn app(cx: Scope) -> Element {
+    let dialog_open = use_state(cx, || false);
+    let dropdown_open = use_state(cx, || false);
+    let fruit = use_state(cx, || "apple");
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+
+            div {
+                flex_direction: "row",
+                div {
+                    border_width: "1px",
+                    border_style: "solid",
+                    onclick: move |_| dialog_open.set(true),
+                    "Open dialog"
+                }
+                div {
+                    position: "relative",
+                    border_width: "1px",
+                    border_style: "solid",
+                    onclick: move |_| dropdown_open.modify(|open| !open),
+                    "Fruit: {fruit}"
+
+                    if **dropdown_open {
+                        rsx! {
+                            div {
+                                position: "absolute",
+                                top: "100%",
+                                left: "0px",
+                                z_index: "1",
+                                flex_direction: "column",
+                                background_color: "darkblue",
+                                ["apple", "banana", "cherry"].into_iter().map(|name| rsx! {
+                                    div {
+                                        key: "{name}",
+                                        onclick: move |_| fruit.set(name),
+                                        "{name}"
+                                    }
+                                })
+                            }
+                        }
+                    }
+                }
+            }
+
+            (0..20).map(|i| rsx! { div { key: "{i}", "Line {i} of the page" } })
+
+            if **dialog_open {
+                rsx! {
+                    div {
+                        position: "fixed",
+                        top: "0px",
+                        left: "0px",
+                        width: "100%",
+                        height: "100%",
+                        justify_content: "center",
+                        align_items: "center",
+                        background_color: "rgba(0, 0, 0, 0.5)",
+                        div {
+                            width: "50%",
+                            flex_direction: "column",
+                            align_items: "center",
+                            border_width: "1px",
+                            border_style: "double",
+                            background_color: "gray",
+                            "The page below can't be clicked while the dialog is open"
+                            div {
+                                border_width: "1px",
+                                border_style: "solid",
+                                onclick: move |_| dialog_open.set(false),
+                                "Close"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
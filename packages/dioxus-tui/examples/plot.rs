@@ -0,0 +1,76 @@
+use std::{collections::VecDeque, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_tui::dioxus_elements;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let samples = use_ref(cx, || VecDeque::from(vec![0.0; 60]));
+
+    use_future(cx, (), |_| {
+        to_owned![samples];
+        async move {
+            let mut t: f64 = 0.0;
+            loop {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                t += 0.2;
+                samples.with_mut(|samples| {
+                    samples.pop_front();
+                    samples.push_back(50.0 + 40.0 * t.sin() * (t / 7.0).cos());
+                });
+            }
+        }
+    });
+
+    let data = samples
+        .read()
+        .iter()
+        .map(|sample| format!("{sample:.1}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let scatter = (0..40)
+        .map(|i| {
+            let x = i as f64 / 4.0;
+            format!("{x}:{:.2}", (x * 1.7).sin() * x)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    render! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+
+            div {
+                height: "50%",
+                border_width: "1px",
+                flex_direction: "column",
+                "CPU usage (%)"
+                plot {
+                    width: "100%",
+                    flex_grow: "1",
+                    data: "{data}",
+                    range: "0..100",
+                    color: "green",
+                }
+            }
+            div {
+                height: "50%",
+                border_width: "1px",
+                flex_direction: "column",
+                "Scatter"
+                plot {
+                    width: "100%",
+                    flex_grow: "1",
+                    data: "{scatter}",
+                    kind: "scatter",
+                    color: "cyan",
+                }
+            }
+        }
+    }
+}
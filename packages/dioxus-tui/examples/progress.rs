@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_tui::{use_multi_progress, use_progress, MultiProgressBar, ProgressBar};
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let total = use_progress(cx, Some(5 * 64));
+    let downloads = use_multi_progress(cx);
+
+    use_coroutine(cx, |_: UnboundedReceiver<()>| {
+        to_owned![total, downloads];
+        async move {
+            total.set_message("Downloading");
+            let files = (1..=5).map(|i| {
+                let progress = downloads.add(format!("file-{i}.tar.gz"), Some(64));
+                let total = total.clone();
+                async move {
+                    for chunk in 0..64 {
+                        tokio::time::sleep(Duration::from_millis(20 * i + chunk % 7)).await;
+                        if i == 4 && chunk == 40 {
+                            progress.fail("file-4.tar.gz: connection reset");
+                            return;
+                        }
+                        progress.inc(1);
+                        total.inc(1);
+                    }
+                    progress.finish();
+                }
+            });
+            futures::future::join_all(files).await;
+            total.finish();
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+
+            MultiProgressBar { multi: downloads.clone() }
+            div { height: "1px" }
+            ProgressBar { progress: total.clone(), width: 50 }
+        }
+    })
+}
@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+fn app(cx: Scope) -> Element {
+    let clicks = use_state(cx, || 0);
+    let disabled = use_state(cx, || false);
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            justify_content: "center",
+            align_items: "center",
+
+            // styles prefixed with a pseudo-class override the other styles while the pseudo-class applies
+            button {
+                width: "50%",
+                height: "3px",
+                border_width: "1px",
+                border_color: "gray",
+                justify_content: "center",
+                "hover:background-color": "darkblue",
+                "focus:border-color": "cyan",
+                "active:background-color": "blue",
+                "disabled:color": "darkgray",
+                "disabled:background-color": "black",
+                disabled: "{disabled}",
+                onclick: move |_| {
+                    if !**disabled {
+                        *clicks.make_mut() += 1;
+                    }
+                },
+                "Clicked {clicks} times"
+            }
+            div {
+                "hover:font-weight": "bold",
+                "hover:text-decoration": "underline",
+                onclick: move |_| disabled.set(!disabled),
+                if **disabled { "Enable the button" } else { "Disable the button" }
+            }
+        }
+    })
+}
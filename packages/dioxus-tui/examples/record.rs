@@ -0,0 +1,41 @@
+use dioxus::prelude::*;
+use dioxus_html::input_data::keyboard_types::Code;
+use dioxus_tui::{Config, TuiContext};
+
+/// Records the session and saves it to `session.cast` when you press `s`. Replay it with `asciinema play session.cast`.
+fn main() {
+    dioxus_tui::launch_cfg(app, Config::default().with_recording());
+}
+
+fn app(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+    let saved = use_state(cx, || false);
+    let ctx: TuiContext = cx.consume_context().unwrap();
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            justify_content: "center",
+            align_items: "center",
+            tabindex: "0",
+            onkeydown: move |e| match e.inner().code() {
+                Code::KeyS => {
+                    if let Some(cast) = ctx.export_asciinema() {
+                        saved.set(std::fs::write("session.cast", cast).is_ok());
+                    }
+                }
+                _ => *count.make_mut() += 1,
+            },
+            onclick: move |_| *count.make_mut() += 1,
+
+            "Clicked or pressed a key {count} times",
+            if **saved {
+                rsx! { "Saved the recording to session.cast" }
+            } else {
+                rsx! { "Press s to save the recording" }
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+/// Scroll the list with the mouse wheel, or press tab to focus it and scroll it with the arrow keys, page up/down,
+/// home and end.
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            justify_content: "center",
+            align_items: "center",
+
+            "The list is clipped to its border"
+
+            div {
+                width: "50%",
+                height: "50%",
+                flex_direction: "column",
+                border_width: "1px",
+                border_style: "solid",
+                overflow: "auto",
+                tabindex: "0",
+
+                (0..100).map(|i| rsx! {
+                    div {
+                        key: "{i}",
+                        height: "1px",
+                        color: if i % 2 == 0 { "lightblue" } else { "white" },
+                        "Item {i}"
+                    }
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+const ROWS: &[[&str; 3]] = &[
+    ["Fruit", "Color", "Price"],
+    ["apple", "red", "1.20"],
+    ["banana", "yellow", "0.50"],
+    ["cherry", "dark red", "4.00"],
+];
+
+/// A table with `border-collapse: collapse`. The cells share their borders, which meet in junctions, instead of
+/// drawing two borders next to each other.
+fn app(cx: Scope) -> Element {
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            justify_content: "center",
+            align_items: "center",
+
+            div {
+                width: "40px",
+                flex_direction: "column",
+                border_collapse: "collapse",
+                border_width: "1px",
+                border_style: "double",
+                box_shadow: "2px 1px 1px black",
+
+                ROWS.iter().enumerate().map(|(i, row)| rsx! {
+                    // the rows collapse the borders of their cells into the borders of the table
+                    div {
+                        key: "{i}",
+                        flex_direction: "row",
+                        border_collapse: "collapse",
+                        row.iter().map(|cell| rsx! {
+                            div {
+                                key: "{cell}",
+                                flex_grow: "1",
+                                flex_basis: "0px",
+                                border_width: "1px",
+                                border_style: "solid",
+                                "{cell}"
+                            }
+                        })
+                    }
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,51 @@
+use dioxus::prelude::*;
+
+fn main() {
+    dioxus_tui::launch(app);
+}
+
+/// Colors and sizes move to their new values over time when they change, and the built in `pulse`, `blink`,
+/// `fade-in` and `fade-out` animations fade the colors of an element.
+fn app(cx: Scope) -> Element {
+    let wide = use_state(cx, || false);
+    let (width, color) = if **wide {
+        ("100%", "blue")
+    } else {
+        ("20%", "red")
+    };
+
+    cx.render(rsx! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+
+            div {
+                width: "{width}",
+                height: "33%",
+                background_color: "{color}",
+                transition: "width 1s ease-in-out, background-color 1s linear",
+                onclick: move |_| wide.set(!wide),
+
+                "Click to resize"
+            }
+
+            div {
+                height: "33%",
+                background_color: "black",
+                "hover:background-color": "green",
+                transition: "background-color 300ms",
+
+                "Hover to change the color"
+            }
+
+            div {
+                height: "33%",
+                color: "yellow",
+                animation: "pulse 2s infinite",
+
+                "Pulsing text"
+            }
+        }
+    })
+}
@@ -0,0 +1,47 @@
+//! The elements of the TUI renderer. This includes every HTML element, and elements that only the TUI renderer draws.
+//!
+//! Import the module to use the elements in `rsx!`:
+//!
+//! ```rust, ignore
+//! use dioxus::prelude::*;
+//! use dioxus_tui::dioxus_elements;
+//!
+//! fn app(cx: Scope) -> Element {
+//!     render! {
+//!         plot { width: "40", height: "10", data: "1 3 2 5 4", color: "green" }
+//!     }
+//! }
+//! ```
+pub use dioxus_html::*;
+
+/// A line or scatter chart drawn with braille characters. Every character cell holds 2 by 4 dots, so charts work in
+/// terminals without a graphics protocol. The y axis is labeled with its range on the left, and the x axis with its
+/// range at the bottom when the element is big enough.
+///
+/// The size of the chart comes from the `width` and `height` of the element.
+#[allow(non_camel_case_types)]
+pub struct plot;
+
+impl plot {
+    pub const TAG_NAME: &'static str = "plot";
+    pub const NAME_SPACE: Option<&'static str> = None;
+
+    /// The points of the chart, separated by commas or whitespace. A point is either a `y` value, which is placed at
+    /// the index of the point, or a `x:y` pair: `"1 4 2 8"` or `"0:1, 0.5:4, 2:2"`
+    #[allow(non_upper_case_globals)]
+    pub const data: AttributeDiscription = ("data", None, false);
+
+    /// `line` to connect the points (the default) or `scatter` to only draw the points
+    #[allow(non_upper_case_globals)]
+    pub const kind: AttributeDiscription = ("kind", None, false);
+
+    /// The color of the points. Defaults to the color of the text of the element
+    #[allow(non_upper_case_globals)]
+    pub const color: AttributeDiscription = ("color", None, false);
+
+    /// The range of the y axis as `min..max`, like `"0..100"`. Defaults to the range of the data
+    #[allow(non_upper_case_globals)]
+    pub const range: AttributeDiscription = ("range", None, false);
+}
+
+impl GlobalAttributes for plot {}
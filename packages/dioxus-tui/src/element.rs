@@ -11,7 +11,10 @@ use dioxus_html::{
 };
 
 use dioxus_native_core::NodeId;
-use plasmo::query::{ElementRef, Query};
+use plasmo::{
+    query::{ElementRef, Query},
+    TuiContext,
+};
 
 pub(crate) fn find_mount_events(mutations: &Mutations) -> Vec<ElementId> {
     let mut mount_events = Vec::new();
@@ -19,6 +22,7 @@ pub(crate) fn find_mount_events(mutations: &Mutations) -> Vec<ElementId> {
         if let dioxus_core::Mutation::NewEventListener {
             name: "mounted",
             id,
+            ..
         } = mutation
         {
             mount_events.push(*id);
@@ -37,9 +41,14 @@ pub(crate) fn create_mounted_events(
         .base_scope()
         .consume_context()
         .expect("Query should be in context");
+    let tui_ctx: TuiContext = vdom
+        .base_scope()
+        .consume_context()
+        .expect("TuiContext should be in context");
     for (id, node_id) in mount_events {
         let element = TuiElement {
             query: query.clone(),
+            tui_ctx: tui_ctx.clone(),
             id: node_id,
         };
         events.push((id, "mounted", Rc::new(MountedData::new(element)), false));
@@ -48,6 +57,7 @@ pub(crate) fn create_mounted_events(
 
 struct TuiElement {
     query: Query,
+    tui_ctx: TuiContext,
     id: NodeId,
 }
 
@@ -85,6 +95,21 @@ impl RenderedElementBacking for TuiElement {
     fn get_raw_element(&self) -> dioxus_html::MountedResult<&dyn std::any::Any> {
         Ok(self)
     }
+
+    fn set_focus(
+        &self,
+        focus: bool,
+    ) -> std::pin::Pin<Box<dyn futures::Future<Output = dioxus_html::MountedResult<()>>>> {
+        // The focus manager can move the focus between nodes, but it can't clear it
+        let result = match focus {
+            true => {
+                self.tui_ctx.focus(self.id);
+                Ok(())
+            }
+            false => Err(MountedError::NotSupported),
+        };
+        Box::pin(async move { result })
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use dioxus_core::prelude::{push_future, remove_future, TaskId};
+use dioxus_html::prelude::{IdleProvider, IdleWatch};
+use plasmo::TuiContext;
+
+/// Detects when the user is idle from the keys and mouse events of the terminal
+pub(crate) struct TuiIdleProvider(pub TuiContext);
+
+impl IdleProvider for TuiIdleProvider {
+    fn watch(&self, timeout: Duration, mut on_change: Box<dyn FnMut(bool)>) -> IdleWatch {
+        let mut activity = self.0.activity();
+        let task = push_future(async move {
+            loop {
+                let last_activity = *activity.borrow_and_update();
+                let deadline = tokio::time::Instant::from_std(last_activity + timeout);
+                match tokio::time::timeout_at(deadline, activity.changed()).await {
+                    // the user was active before the timeout, so wait for the next timeout
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => return,
+                    Err(_) => {
+                        on_change(true);
+                        if activity.changed().await.is_err() {
+                            return;
+                        }
+                        on_change(false);
+                    }
+                }
+            }
+        });
+
+        IdleWatch::new(StopWatching(task))
+    }
+}
+
+struct StopWatching(Option<TaskId>);
+
+impl Drop for StopWatching {
+    fn drop(&mut self) {
+        if let Some(task) = self.0 {
+            remove_future(task);
+        }
+    }
+}
@@ -1,19 +1,30 @@
+pub mod dioxus_elements;
 mod element;
+mod idle;
+mod progress;
 
 use std::{
     any::Any,
     ops::Deref,
     rc::Rc,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use dioxus_core::{Component, ElementId, VirtualDom};
 use dioxus_native_core::dioxus::{DioxusState, NodeImmutableDioxusExt};
 use dioxus_native_core::prelude::*;
 
+use dioxus_html::prelude::IdleProvider;
 use element::{create_mounted_events, find_mount_events};
-pub use plasmo::{query::Query, Config, RenderingMode, Size, TuiContext};
-use plasmo::{render, Driver};
+use idle::TuiIdleProvider;
+pub use plasmo::{
+    query::Query, Config, Palette, Point, RenderingMode, Selection, Size, TerminalGrid, TuiContext,
+};
+use plasmo::{render_with_context, Driver, Taffy};
+pub use progress::{
+    use_multi_progress, use_progress, MultiProgress, MultiProgressBar, MultiProgressBarProps,
+    Progress, ProgressBar, ProgressBarProps, ProgressState,
+};
 
 pub fn launch(app: Component<()>) {
     launch_cfg(app, Config::default())
@@ -24,18 +35,75 @@ pub fn launch_cfg(app: Component<()>, cfg: Config) {
 }
 
 pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props, cfg: Config) {
-    render(cfg, |rdom, taffy, event_tx| {
+    let mutation_budget = cfg.mutation_budget();
+    render_with_context(cfg, move |rdom, taffy, tui_ctx| {
+        DioxusRenderer::new(app, props, mutation_budget, rdom, taffy, tui_ctx)
+    })
+    .unwrap();
+}
+
+/// Draw the first frame of an app into a string of `width` by `height` cells without a terminal, for example to
+/// compare it with a snapshot in a test. Trailing whitespace is removed from every row.
+///
+/// See [`render_to_grid`] to include the colors and modifiers of the cells.
+pub fn render_to_string(app: Component<()>, width: u16, height: u16) -> String {
+    render_to_grid(app, Config::new(), width, height).to_string()
+}
+
+/// Draw the first frame of an app into a [`TerminalGrid`] of `width` by `height` cells without a terminal.
+pub fn render_to_grid(app: Component<()>, cfg: Config, width: u16, height: u16) -> TerminalGrid {
+    render_to_grid_with_props(app, (), cfg, width, height)
+}
+
+/// Draw the first frame of an app with props into a [`TerminalGrid`] of `width` by `height` cells without a terminal.
+pub fn render_to_grid_with_props<Props: 'static>(
+    app: Component<Props>,
+    props: Props,
+    cfg: Config,
+    width: u16,
+    height: u16,
+) -> TerminalGrid {
+    let mutation_budget = cfg.mutation_budget();
+    plasmo::render_to_grid(cfg, width, height, move |rdom, taffy, tui_ctx| {
+        DioxusRenderer::new(app, props, mutation_budget, rdom, taffy, tui_ctx)
+    })
+    .unwrap()
+}
+
+struct DioxusRenderer {
+    vdom: VirtualDom,
+    dioxus_state: Rc<RwLock<DioxusState>>,
+    // Events that are queued up to be sent to the vdom next time the vdom is polled
+    queued_events: Vec<(ElementId, &'static str, Rc<dyn Any>, bool)>,
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    hot_reload_rx: tokio::sync::mpsc::UnboundedReceiver<dioxus_hot_reload::HotReloadMsg>,
+}
+
+impl DioxusRenderer {
+    fn new<Props: 'static>(
+        app: Component<Props>,
+        props: Props,
+        mutation_budget: Option<usize>,
+        rdom: &Arc<RwLock<RealDom>>,
+        taffy: &Arc<Mutex<Taffy>>,
+        tui_ctx: TuiContext,
+    ) -> Self {
         let dioxus_state = {
             let mut rdom = rdom.write().unwrap();
             DioxusState::create(&mut rdom)
         };
         let dioxus_state = Rc::new(RwLock::new(dioxus_state));
+        let idle_provider: Rc<dyn IdleProvider> = Rc::new(TuiIdleProvider(tui_ctx.clone()));
         let mut vdom = VirtualDom::new_with_props(app, props)
-            .with_root_context(TuiContext::new(event_tx))
+            .with_root_context(tui_ctx)
+            .with_root_context(idle_provider)
             .with_root_context(Query::new(rdom.clone(), taffy.clone()))
             .with_root_context(DioxusElementToNodeId {
                 mapping: dioxus_state.clone(),
             });
+        if let Some(budget) = mutation_budget {
+            vdom = vdom.with_mutation_budget(budget);
+        }
         let muts = vdom.rebuild();
 
         let mut queued_events = Vec::new();
@@ -73,17 +141,7 @@ pub fn launch_cfg_with_props<Props: 'static>(app: Component<Props>, props: Props
                 hot_reload_rx
             },
         }
-    })
-    .unwrap();
-}
-
-struct DioxusRenderer {
-    vdom: VirtualDom,
-    dioxus_state: Rc<RwLock<DioxusState>>,
-    // Events that are queued up to be sent to the vdom next time the vdom is polled
-    queued_events: Vec<(ElementId, &'static str, Rc<dyn Any>, bool)>,
-    #[cfg(all(feature = "hot-reload", debug_assertions))]
-    hot_reload_rx: tokio::sync::mpsc::UnboundedReceiver<dioxus_hot_reload::HotReloadMsg>,
+    }
 }
 
 impl Driver for DioxusRenderer {
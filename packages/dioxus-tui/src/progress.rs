@@ -0,0 +1,405 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dioxus::prelude::*;
+
+/// How often progress bars are redrawn while their task is running
+const TICK: Duration = Duration::from_millis(100);
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Track the progress of a long running task. Render it with a [`ProgressBar`].
+///
+/// `length` is the number of steps in the task, or `None` if it is not known. The returned handle can be cloned into
+/// a coroutine that does the work:
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let progress = use_progress(cx, Some(100));
+///
+///     use_coroutine(cx, |_: UnboundedReceiver<()>| {
+///         to_owned![progress];
+///         async move {
+///             progress.set_message("Downloading");
+///             for _ in 0..100 {
+///                 download_chunk().await;
+///                 progress.inc(1);
+///             }
+///             progress.finish();
+///         }
+///     });
+///
+///     render! { ProgressBar { progress: progress.clone() } }
+/// }
+/// ```
+pub fn use_progress(cx: &ScopeState, length: Option<u64>) -> &Progress {
+    cx.use_hook(|| Progress::new(length))
+}
+
+/// The state of a task tracked with a [`Progress`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressState {
+    /// The task is still running
+    Running,
+    /// The task finished successfully
+    Finished,
+    /// The task failed with an error message
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct ProgressInner {
+    message: String,
+    position: u64,
+    length: Option<u64>,
+    started: Instant,
+    ended: Option<Instant>,
+    state: ProgressState,
+}
+
+/// A handle to the progress of a task. Created with [`use_progress`] or [`MultiProgress::add`].
+///
+/// Updating the progress does not render the component that owns it, the [`ProgressBar`] that shows it redraws itself
+/// ten times a second while the task is running.
+#[derive(Debug, Clone)]
+pub struct Progress {
+    inner: Rc<RefCell<ProgressInner>>,
+}
+
+impl PartialEq for Progress {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Progress {
+    /// Create the progress of a task with `length` steps
+    pub fn new(length: Option<u64>) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(ProgressInner {
+                message: String::new(),
+                position: 0,
+                length,
+                started: Instant::now(),
+                ended: None,
+                state: ProgressState::Running,
+            })),
+        }
+    }
+
+    /// Advance the task by `steps`
+    pub fn inc(&self, steps: u64) {
+        let mut inner = self.inner.borrow_mut();
+        inner.position = inner.position.saturating_add(steps);
+    }
+
+    /// Set the number of steps that are done
+    pub fn set_position(&self, position: u64) {
+        self.inner.borrow_mut().position = position;
+    }
+
+    /// Set the number of steps in the task, or `None` if it is not known
+    pub fn set_length(&self, length: Option<u64>) {
+        self.inner.borrow_mut().length = length;
+    }
+
+    /// Set the message shown next to the progress bar
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.inner.borrow_mut().message = message.into();
+    }
+
+    /// Mark the task as finished
+    pub fn finish(&self) {
+        self.end(ProgressState::Finished);
+    }
+
+    /// Mark the task as failed with an error message
+    pub fn fail(&self, error: impl Into<String>) {
+        self.end(ProgressState::Failed(error.into()));
+    }
+
+    /// Start the task again from the first step
+    pub fn reset(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.position = 0;
+        inner.started = Instant::now();
+        inner.ended = None;
+        inner.state = ProgressState::Running;
+    }
+
+    fn end(&self, state: ProgressState) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.state == ProgressState::Running {
+            inner.ended = Some(Instant::now());
+            inner.state = state;
+        }
+    }
+
+    /// Get the number of steps that are done
+    pub fn position(&self) -> u64 {
+        self.inner.borrow().position
+    }
+
+    /// Get the number of steps in the task, if it is known
+    pub fn length(&self) -> Option<u64> {
+        self.inner.borrow().length
+    }
+
+    /// Get the message shown next to the progress bar
+    pub fn message(&self) -> String {
+        self.inner.borrow().message.clone()
+    }
+
+    /// Get the state of the task
+    pub fn state(&self) -> ProgressState {
+        self.inner.borrow().state.clone()
+    }
+
+    /// Check if the task is still running
+    pub fn is_running(&self) -> bool {
+        self.inner.borrow().state == ProgressState::Running
+    }
+
+    /// Get the time the task has been running for, or ran for if it ended
+    pub fn elapsed(&self) -> Duration {
+        let inner = self.inner.borrow();
+        inner.ended.unwrap_or_else(Instant::now) - inner.started
+    }
+
+    /// Get the fraction of the task that is done, if the length is known
+    pub fn fraction(&self) -> Option<f64> {
+        let inner = self.inner.borrow();
+        inner.length.map(|length| match length {
+            0 => 1.0,
+            length => (inner.position as f64 / length as f64).min(1.0),
+        })
+    }
+
+    /// Get the average number of steps done per second
+    pub fn throughput(&self) -> f64 {
+        let elapsed = self.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.position() as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimate the time until the task is done, if the length is known and the task has made progress
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.length()?.saturating_sub(self.position());
+        let throughput = self.throughput();
+        (throughput > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / throughput))
+    }
+
+    fn ended(&self) -> Option<Instant> {
+        self.inner.borrow().ended
+    }
+}
+
+/// The props for the [`ProgressBar`] component
+#[derive(Props, PartialEq)]
+pub struct ProgressBarProps {
+    /// The progress to show
+    progress: Progress,
+    /// The width of the bar in cells
+    #[props(default = 30)]
+    width: usize,
+}
+
+/// Render the progress of a task on a single line: a spinner that shows the state of the task, the message, a bar
+/// with the fraction that is done, the number of steps, the throughput and the estimated time until the task is done.
+///
+/// If the length of the task is not known, the bar is left out.
+#[allow(non_snake_case)]
+pub fn ProgressBar(cx: Scope<ProgressBarProps>) -> Element {
+    let progress = &cx.props.progress;
+
+    // Redraw at a steady rate instead of on every update, so fast tasks don't flood the terminal with renders
+    use_future(cx, progress, |progress| {
+        let update = cx.schedule_update();
+        async move {
+            while progress.is_running() {
+                tokio::time::sleep(TICK).await;
+                update();
+            }
+        }
+    });
+
+    let (status, status_color) = match progress.state() {
+        ProgressState::Running => {
+            let frame = (progress.elapsed().as_millis() / TICK.as_millis()) as usize;
+            (SPINNER[frame % SPINNER.len()], "cyan")
+        }
+        ProgressState::Finished => ("✔", "green"),
+        ProgressState::Failed(_) => ("✘", "red"),
+    };
+    let message = match progress.state() {
+        ProgressState::Failed(error) => error,
+        _ => progress.message(),
+    };
+
+    let bar = progress.fraction().map(|fraction| {
+        let filled = (fraction * cx.props.width as f64).round() as usize;
+        (
+            "█".repeat(filled),
+            "░".repeat(cx.props.width.saturating_sub(filled)),
+        )
+    });
+
+    let count = match progress.length() {
+        Some(length) => format!("{}/{length}", progress.position()),
+        None => progress.position().to_string(),
+    };
+    let stats = match (progress.is_running(), progress.eta()) {
+        (true, Some(eta)) => format!(
+            "{:.1}/s ETA {}",
+            progress.throughput(),
+            format_duration(eta)
+        ),
+        (true, None) => format!("{:.1}/s", progress.throughput()),
+        (false, _) => format_duration(progress.elapsed()),
+    };
+
+    cx.render(rsx! {
+        div {
+            height: "1px",
+            span { color: status_color, "{status} " }
+            if !message.is_empty() {
+                rsx! { span { "{message} " } }
+            }
+            if let Some((filled, empty)) = bar {
+                rsx! {
+                    span { color: "cyan", "{filled}" }
+                    span { color: "darkgray", "{empty} " }
+                }
+            }
+            span { "{count} " }
+            span { color: "darkgray", "{stats}" }
+        }
+    })
+}
+
+/// Track the progress of several tasks at once. Render them with a [`MultiProgressBar`].
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let downloads = use_multi_progress(cx);
+///
+///     use_coroutine(cx, |mut rx: UnboundedReceiver<String>| {
+///         to_owned![downloads];
+///         async move {
+///             while let Some(url) = rx.next().await {
+///                 let progress = downloads.add(url.clone(), None);
+///                 spawn(download(url, progress));
+///             }
+///         }
+///     });
+///
+///     render! { MultiProgressBar { multi: downloads.clone() } }
+/// }
+/// ```
+pub fn use_multi_progress(cx: &ScopeState) -> &MultiProgress {
+    let update = cx.schedule_update();
+    cx.use_hook(|| MultiProgress {
+        tasks: Default::default(),
+        update,
+    })
+}
+
+/// A list of tasks with their progress. Created with [`use_multi_progress`].
+#[derive(Clone)]
+pub struct MultiProgress {
+    tasks: Rc<RefCell<Vec<Progress>>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl PartialEq for MultiProgress {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.tasks, &other.tasks)
+    }
+}
+
+impl MultiProgress {
+    /// Add a task with `length` steps to the list
+    pub fn add(&self, message: impl Into<String>, length: Option<u64>) -> Progress {
+        let progress = Progress::new(length);
+        progress.set_message(message);
+        self.tasks.borrow_mut().push(progress.clone());
+        (self.update)();
+        progress
+    }
+
+    /// Remove the tasks that ended from the list
+    pub fn clear_ended(&self) {
+        self.tasks.borrow_mut().retain(Progress::is_running);
+        (self.update)();
+    }
+
+    /// Get the tasks in the order they are shown: the tasks that ended in the order they ended, then the running tasks in
+    /// the order they were added
+    pub fn tasks(&self) -> Vec<Progress> {
+        let mut tasks = self.tasks.borrow().clone();
+        // running tasks have no end time, so they sort after every task that ended
+        tasks.sort_by_key(|task| (task.ended().is_none(), task.ended()));
+        tasks
+    }
+}
+
+/// The props for the [`MultiProgressBar`] component
+#[derive(Props, PartialEq)]
+pub struct MultiProgressBarProps {
+    /// The tasks to show
+    multi: MultiProgress,
+    /// The width of the bars in cells
+    #[props(default = 30)]
+    width: usize,
+}
+
+/// Render a [`ProgressBar`] for every task of a [`MultiProgress`]. Tasks that ended move above the running tasks, so the
+/// running tasks stay together at the bottom.
+#[allow(non_snake_case)]
+pub fn MultiProgressBar(cx: Scope<MultiProgressBarProps>) -> Element {
+    let tasks = cx.props.multi.tasks();
+    let width = cx.props.width;
+
+    // The order changes when a task ends, so check it at the same rate the bars are drawn. Tasks can be added at any
+    // time, so the future keeps ticking for as long as the component is mounted
+    use_future(cx, &cx.props.multi, |multi| {
+        let update = cx.schedule_update();
+        async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                if multi.tasks.borrow().iter().any(Progress::is_running) {
+                    update();
+                }
+            }
+        }
+    });
+
+    cx.render(rsx! {
+        div {
+            flex_direction: "column",
+            for task in tasks {
+                ProgressBar {
+                    key: "{Rc::as_ptr(&task.inner) as usize}",
+                    progress: task,
+                    width: width,
+                }
+            }
+        }
+    })
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
@@ -1,7 +1,7 @@
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent};
 use dioxus::prelude::*;
 use dioxus_html::input_data::keyboard_types::Code;
-use dioxus_tui::TuiContext;
+use dioxus_tui::{Point, Selection, TuiContext};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -376,3 +376,199 @@ fn context_menu() {
         })
     }
 }
+
+#[test]
+fn input_change() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let typed = use_state(cx, String::new);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(6).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        cx.use_hook(|| {
+            // focus the input, type into it and submit it
+            for code in [KeyCode::Tab, KeyCode::Char('a'), KeyCode::Enter] {
+                tui_ctx.inject_event(Event::Key(KeyEvent {
+                    code,
+                    modifiers: KeyModifiers::NONE,
+                    kind: crossterm::event::KeyEventKind::Press,
+                    state: crossterm::event::KeyEventState::NONE,
+                }));
+            }
+        });
+        cx.render(rsx! {
+            input {
+                width: "100%",
+                height: "100%",
+                oninput: move |evt| typed.set(evt.value.clone()),
+                onchange: move |evt| {
+                    assert_eq!(typed.get(), "a");
+                    assert_eq!(evt.value, "a");
+                    tui_ctx.quit();
+                },
+            }
+        })
+    }
+}
+
+#[test]
+fn shift_tab_skips_disabled() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(3).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        cx.use_hook(|| {
+            // terminals send BackTab for Shift+Tab, which moves the focus to the last element that isn't disabled
+            tui_ctx.inject_event(Event::Key(KeyEvent {
+                code: KeyCode::BackTab,
+                modifiers: KeyModifiers::NONE,
+                kind: crossterm::event::KeyEventKind::Press,
+                state: crossterm::event::KeyEventState::NONE,
+            }));
+        });
+        cx.render(rsx! {
+            button {
+                tabindex: "0",
+                "first"
+            }
+            button {
+                tabindex: "0",
+                onfocus: move |_| tui_ctx.quit(),
+                "last"
+            }
+            button {
+                tabindex: "0",
+                disabled: true,
+                "disabled"
+            }
+        })
+    }
+}
+
+#[test]
+fn wheel_scrolls_overflow() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(6).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        cx.use_hook(|| {
+            // scroll two rows down, and click the first visible row
+            for kind in [
+                crossterm::event::MouseEventKind::Moved,
+                crossterm::event::MouseEventKind::ScrollDown,
+                crossterm::event::MouseEventKind::ScrollDown,
+                crossterm::event::MouseEventKind::Down(MouseButton::Left),
+                crossterm::event::MouseEventKind::Up(MouseButton::Left),
+            ] {
+                tui_ctx.inject_event(Event::Mouse(MouseEvent {
+                    column: 0,
+                    row: 0,
+                    kind,
+                    modifiers: KeyModifiers::NONE,
+                }));
+            }
+        });
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "3px",
+                flex_direction: "column",
+                overflow: "scroll",
+                (0..10).map(|i| {
+                    let tui_ctx = tui_ctx.clone();
+                    rsx! {
+                        div {
+                            height: "1px",
+                            onclick: move |_| {
+                                assert_eq!(i, 2);
+                                tui_ctx.quit();
+                            },
+                            "{i}"
+                        }
+                    }
+                })
+            }
+        })
+    }
+}
+
+#[test]
+fn drag_selects_text() {
+    dioxus_tui::launch_cfg(app, dioxus_tui::Config::new().with_headless());
+
+    fn app(cx: Scope) -> Element {
+        let render_count = use_state(cx, || 0);
+        let selecting = use_state(cx, || false);
+        let tui_ctx: TuiContext = cx.consume_context().unwrap();
+        let render_count_handle = render_count.clone();
+        cx.spawn(async move {
+            PollN::new(6).await;
+            render_count_handle.modify(|x| *x + 1);
+        });
+        if *render_count.get() > 2 {
+            panic!("Event was not received");
+        }
+        cx.use_hook(|| {
+            // drag over the first five cells of the text
+            for (column, kind) in [
+                (0, crossterm::event::MouseEventKind::Down(MouseButton::Left)),
+                (2, crossterm::event::MouseEventKind::Drag(MouseButton::Left)),
+                (4, crossterm::event::MouseEventKind::Drag(MouseButton::Left)),
+                (4, crossterm::event::MouseEventKind::Up(MouseButton::Left)),
+            ] {
+                tui_ctx.inject_event(Event::Mouse(MouseEvent {
+                    column,
+                    row: 0,
+                    kind,
+                    modifiers: KeyModifiers::NONE,
+                }));
+            }
+        });
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "100%",
+                onselectstart: move |_| {
+                    // the selection starts once, when the mouse first moves
+                    assert!(!*selecting.get());
+                    selecting.set(true);
+                },
+                onselect: move |_| {
+                    assert!(*selecting.get());
+                    assert_eq!(
+                        tui_ctx.selection(),
+                        Some(Selection::new(Point { x: 0, y: 0 }, Point { x: 4, y: 0 }))
+                    );
+                    tui_ctx.quit();
+                },
+                "hello world"
+            }
+        })
+    }
+}
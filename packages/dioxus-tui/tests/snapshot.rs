@@ -0,0 +1,141 @@
+use dioxus::prelude::*;
+
+#[test]
+fn renders_app_to_string() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "100%",
+                flex_direction: "column",
+                div { "Hello" }
+                div { padding_left: "2px", "world!" }
+            }
+        })
+    }
+
+    assert_eq!(
+        dioxus_tui::render_to_string(app, 10, 3),
+        "Hello\n  world!\n"
+    );
+}
+
+#[test]
+fn renders_colors_to_ansi() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div { color: "red", "Hi" }
+        })
+    }
+
+    let grid = dioxus_tui::render_to_grid(
+        app,
+        dioxus_tui::Config::new().with_rendering_mode(dioxus_tui::RenderingMode::Rgb),
+        4,
+        1,
+    );
+    assert_eq!(grid.to_string(), "Hi");
+    assert_eq!(grid.symbol(1, 0), "i");
+    // the text is red
+    assert!(grid.to_ansi_string().contains(";38;2;255;0;0"));
+}
+
+#[test]
+fn draws_positioned_elements_over_content() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div {
+                width: "100%",
+                height: "100%",
+                flex_direction: "column",
+                // drawn over the text after it, because it is positioned
+                div { position: "absolute", top: "0px", left: "0px", "top" }
+                div { "below" }
+                // a negative z-index is drawn below the content that is not positioned
+                div { position: "absolute", top: "0px", left: "0px", z_index: "-1", "negative" }
+                // the higher z-index is drawn on top, even though it comes first
+                div { position: "absolute", top: "1px", left: "0px", z_index: "2", "high" }
+                div { position: "absolute", top: "1px", left: "0px", z_index: "1", "lo" }
+                div {
+                    height: "1px",
+                    // fixed elements are placed in the screen, not in their parent
+                    div { position: "fixed", bottom: "0px", right: "0px", "fixed" }
+                }
+            }
+        })
+    }
+
+    assert_eq!(
+        dioxus_tui::render_to_string(app, 8, 3),
+        "topowive\nhigh\n   fixed"
+    );
+}
+
+#[test]
+fn collapses_borders_into_junctions() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div {
+                width: "9px",
+                height: "3px",
+                border_collapse: "collapse",
+                border_width: "1px",
+                border_style: "solid",
+                // the shared edges of the cells and the table are drawn once, and meet in junctions
+                div { flex_grow: "1", border_width: "1px", border_style: "solid", "a" }
+                div { flex_grow: "1", border_width: "1px", border_style: "solid", "b" }
+            }
+        })
+    }
+
+    assert_eq!(
+        dioxus_tui::render_to_string(app, 9, 3),
+        "┌───┬───┐\n│a  │b  │\n└───┴───┘"
+    );
+}
+
+#[test]
+fn draws_wide_glyphs_in_two_cells() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            div {
+                width: "6px",
+                height: "3px",
+                border_width: "1px",
+                border_style: "solid",
+                // every CJK character takes up two cells, so the border still lines up
+                "中文"
+            }
+        })
+    }
+
+    assert_eq!(
+        dioxus_tui::render_to_string(app, 6, 3),
+        "┌────┐\n│中文│\n└────┘"
+    );
+}
+
+#[test]
+fn aligns_the_labels_and_fields_of_fieldsets() {
+    fn app(cx: Scope) -> Element {
+        cx.render(rsx! {
+            fieldset {
+                width: "100%",
+                legend { "Server" }
+                // the labels share a column as wide as the widest label
+                label { "Name" }
+                div { "app" }
+                div { role: "alert", "Taken" }
+                label { "Port" }
+                div { "8080" }
+                // a field without a label is placed in the second column
+                div { "Help" }
+            }
+        })
+    }
+
+    assert_eq!(
+        dioxus_tui::render_to_string(app, 12, 5),
+        "Server\nName app\n     Taken\nPort 8080\n     Help"
+    );
+}
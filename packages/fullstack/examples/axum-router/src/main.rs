@@ -39,10 +39,11 @@ fn Blog(cx: Scope, id: i32) -> Element {
         Link { to: Route::Home {}, "Go to counter" }
         table {
             tbody {
-                for _ in 0..*id {
+                for row in 0..*id {
                     tr {
-                        for _ in 0..*id {
-                            td { "hello world!" }
+                        key: "{row}",
+                        for column in 0..*id {
+                            td { key: "{column}", "hello world!" }
                         }
                     }
                 }
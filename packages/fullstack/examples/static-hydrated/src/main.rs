@@ -55,10 +55,11 @@ fn Blog(cx: Scope) -> Element {
         Link { to: Route::Home {}, "Go to counter" }
         table {
             tbody {
-                for _ in 0..100 {
+                for row in 0..100 {
                     tr {
-                        for _ in 0..100 {
-                            td { "hello world!" }
+                        key: "{row}",
+                        for column in 0..100 {
+                            td { key: "{column}", "hello world!" }
                         }
                     }
                 }
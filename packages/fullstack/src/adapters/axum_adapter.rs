@@ -58,7 +58,11 @@ use axum::{
     body::{self, Body, BoxBody},
     extract::State,
     handler::Handler,
-    http::{Request, Response, StatusCode},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderValue, Request, Response, StatusCode,
+    },
+    middleware::{self, Next},
     response::IntoResponse,
     routing::{get, post},
     Router,
@@ -134,6 +138,36 @@ pub trait DioxusRouterExt<S> {
     /// ```
     fn register_server_fns(self, server_fn_route: &'static str) -> Self;
 
+    /// Registers server functions with the default handler and shares the given resources with them. Server functions can retrieve the resources with the [`ServerContext`] extractor.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_fullstack::prelude::*;
+    ///
+    /// #[derive(Clone)]
+    /// struct Pool;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///     axum::Server::bind(&addr)
+    ///         .serve(
+    ///             axum::Router::new()
+    ///                 // Register server functions routes with a database pool
+    ///                 .register_server_fns_with_resources("", ServerResources::new().with(Pool))
+    ///                 .into_make_service(),
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    fn register_server_fns_with_resources(
+        self,
+        server_fn_route: &'static str,
+        resources: impl Into<Arc<ServerResources>>,
+    ) -> Self;
+
     /// Register the web RSX hot reloading endpoint. This will enable hot reloading for your application in debug mode when you call [`dioxus_hot_reload::hot_reload_init`].
     ///
     /// # Example
@@ -158,8 +192,73 @@ pub trait DioxusRouterExt<S> {
     /// ```
     fn connect_hot_reload(self) -> Self;
 
+    /// Serves a health check at `/healthz`. It responds with `200 OK` while the server is running, and with
+    /// `503 Service Unavailable` once the server started to shut down with [`graceful_shutdown`], so load balancers
+    /// stop sending it new requests.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dioxus_fullstack::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///     axum::Server::bind(&addr)
+    ///         .serve(
+    ///             axum::Router::new()
+    ///                 // Report the health of the server to load balancers
+    ///                 .serve_health_check()
+    ///                 .into_make_service(),
+    ///         )
+    ///         .with_graceful_shutdown(graceful_shutdown(
+    ///             async {
+    ///                 tokio::signal::ctrl_c().await.unwrap();
+    ///             },
+    ///             std::time::Duration::from_secs(30),
+    ///         ))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    fn serve_health_check(self) -> Self;
+
+    /// Serves metrics in the [Prometheus](https://prometheus.io/docs/instrumenting/exposition_formats/) text format at
+    /// `/metrics`. The metrics include a histogram of the time it takes to server side render pages, the number of pages
+    /// that are being rendered and the number of calls to each server function.
+    ///
+    /// The metrics may expose details about your application, so only serve them where your monitoring can reach them.
+    ///
+    /// # Example
+    /// ```rust
+    /// #![allow(non_snake_case)]
+    /// use dioxus::prelude::*;
+    /// use dioxus_fullstack::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = std::net::SocketAddr::from(([127, 0, 0, 1], 8080));
+    ///     axum::Server::bind(&addr)
+    ///         .serve(
+    ///             axum::Router::new()
+    ///                 // Let Prometheus scrape the metrics of the server
+    ///                 .serve_metrics()
+    ///                 .serve_dioxus_application("", ServeConfigBuilder::new(app, ()))
+    ///                 .into_make_service(),
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    /// }
+    ///
+    /// fn app(cx: Scope) -> Element {
+    ///     todo!()
+    /// }
+    /// ```
+    fn serve_metrics(self) -> Self;
+
     /// Serves the static WASM for your Dioxus application (except the generated index.html).
     ///
+    /// Fingerprinted assets from the [`AssetManifest`] in the assets folder are served with immutable caching headers.
+    ///
     /// # Example
     /// ```rust
     /// #![allow(non_snake_case)]
@@ -253,10 +352,21 @@ where
     }
 
     fn register_server_fns(self, server_fn_route: &'static str) -> Self {
+        self.register_server_fns_with_resources(server_fn_route, ServerResources::default())
+    }
+
+    fn register_server_fns_with_resources(
+        self,
+        server_fn_route: &'static str,
+        resources: impl Into<Arc<ServerResources>>,
+    ) -> Self {
+        let resources = resources.into();
         self.register_server_fns_with_handler(server_fn_route, |func| {
             use crate::layer::Service;
+            let resources = resources.clone();
             move |req: Request<Body>| {
-                let mut service = crate::server_fn_service(Default::default(), func);
+                let server_context = DioxusServerContext::default().with_resources(resources);
+                let mut service = crate::server_fn_service(server_context, func);
                 async move {
                     let (req, body) = req.into_parts();
                     let req = Request::from_parts(req, body);
@@ -274,10 +384,11 @@ where
         })
     }
 
-    fn serve_static_assets(mut self, assets_path: impl Into<std::path::PathBuf>) -> Self {
+    fn serve_static_assets(self, assets_path: impl Into<std::path::PathBuf>) -> Self {
         use tower_http::services::{ServeDir, ServeFile};
 
         let assets_path = assets_path.into();
+        let mut assets = Router::new();
 
         // Serve all files in dist folder except index.html
         let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
@@ -305,13 +416,35 @@ where
                 .join("/");
             let route = format!("/{}", route);
             if path.is_dir() {
-                self = self.nest_service(&route, ServeDir::new(path));
+                assets = assets.nest_service(&route, ServeDir::new(path));
             } else {
-                self = self.nest_service(&route, ServeFile::new(path));
+                assets = assets.nest_service(&route, ServeFile::new(path));
             }
         }
 
-        self
+        // Fingerprinted files never change, so browsers can cache them forever
+        if let Some(manifest) = crate::asset_manifest::read_asset_manifest(&assets_path) {
+            let fingerprinted = Arc::new(manifest.fingerprinted_routes());
+            assets = assets.layer(middleware::from_fn(
+                move |req: Request<Body>, next: Next<Body>| {
+                    let immutable = fingerprinted.contains(req.uri().path());
+                    async move {
+                        let mut res = next.run(req).await;
+                        if immutable && res.status().is_success() {
+                            res.headers_mut().insert(
+                                CACHE_CONTROL,
+                                HeaderValue::from_static(
+                                    crate::asset_manifest::IMMUTABLE_CACHE_CONTROL,
+                                ),
+                            );
+                        }
+                        res
+                    }
+                },
+            ));
+        }
+
+        self.merge(assets)
     }
 
     fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'static>(
@@ -325,10 +458,19 @@ where
         // Add server functions and render index.html
         self.serve_static_assets(cfg.assets_path)
             .connect_hot_reload()
-            .register_server_fns(server_fn_route)
+            .route(crate::render::EVENT_REPLAY_PATH, get(event_replay_handler))
+            .register_server_fns_with_resources(server_fn_route, cfg.resources.clone())
             .fallback(get(render_handler).with_state((cfg, ssr_state)))
     }
 
+    fn serve_health_check(self) -> Self {
+        self.route("/healthz", get(health_check_handler))
+    }
+
+    fn serve_metrics(self) -> Self {
+        self.route("/metrics", get(metrics_handler))
+    }
+
     fn connect_hot_reload(self) -> Self {
         #[cfg(all(debug_assertions, feature = "hot-reload", feature = "ssr"))]
         {
@@ -374,10 +516,23 @@ pub async fn render_handler<P: Clone + serde::Serialize + Send + Sync + 'static>
     State((cfg, ssr_state)): State<(ServeConfig<P>, SSRState)>,
     request: Request<Body>,
 ) -> impl IntoResponse {
+    // Let the renders that are in flight finish without starting new ones
+    if crate::metrics::metrics().is_draining() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "The server is shutting down",
+        )
+            .into_response();
+    }
+
     let (parts, _) = request.into_parts();
     let url = parts.uri.path_and_query().unwrap().to_string();
+    let request_info = RequestInfo::from_request_parts(&parts, &cfg.request_info);
     let parts: Arc<RwLock<http::request::Parts>> = Arc::new(RwLock::new(parts.into()));
-    let server_context = DioxusServerContext::new(parts.clone());
+    let mut server_context =
+        DioxusServerContext::new(parts.clone()).with_resources(cfg.resources.clone());
+    // Components read the request info with `use_request`
+    let _ = server_context.insert(request_info);
 
     match ssr_state.render(url, &cfg, &server_context).await {
         Ok(rendered) => {
@@ -386,6 +541,8 @@ pub async fn render_handler<P: Clone + serde::Serialize + Send + Sync + 'static>
             freshness.write(response.headers_mut());
             let headers = server_context.response_parts().unwrap().headers.clone();
             apply_request_parts_to_response(headers, &mut response);
+            // Typed errors thrown while rendering set the status of the page
+            *response.status_mut() = server_context.response_parts().unwrap().status;
             response
         }
         Err(e) => {
@@ -395,6 +552,37 @@ pub async fn render_handler<P: Clone + serde::Serialize + Send + Sync + 'static>
     }
 }
 
+/// A health check handler for Axum. See [`DioxusRouterExt::serve_health_check`].
+pub async fn health_check_handler() -> impl IntoResponse {
+    if crate::metrics::metrics().is_draining() {
+        (StatusCode::SERVICE_UNAVAILABLE, "shutting down")
+    } else {
+        (StatusCode::OK, "ok")
+    }
+}
+
+/// Serves the script that buffers the events triggered before a page is hydrated.
+async fn event_replay_handler() -> impl IntoResponse {
+    (
+        [(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/javascript"),
+        )],
+        dioxus_interpreter_js::EVENT_REPLAY_JS,
+    )
+}
+
+/// A Prometheus metrics handler for Axum. See [`DioxusRouterExt::serve_metrics`].
+pub async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+        )],
+        crate::metrics::metrics().encode(),
+    )
+}
+
 fn report_err<E: std::fmt::Display>(e: E) -> Response<BoxBody> {
     Response::builder()
         .status(StatusCode::INTERNAL_SERVER_ERROR)
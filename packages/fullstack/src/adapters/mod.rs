@@ -81,6 +81,7 @@ impl Service for ServerFnHandler {
             function,
         } = self.clone();
         Box::pin(async move {
+            crate::metrics::metrics().record_server_fn_call(function.url());
             let query = req.uri().query().unwrap_or_default().as_bytes().to_vec();
             let (parts, body) = req.into_parts();
             let body = hyper::body::to_bytes(body).await?.to_vec();
@@ -115,17 +116,29 @@ impl Service for ServerFnHandler {
             let parts = server_context.response_parts().unwrap();
             *res.headers_mut().expect("empty headers should be valid") = parts.headers.clone();
 
-            let serialized = result?;
             // if this is Accept: application/json then send a serialized JSON response
             let accept_header = accept_header.as_ref().and_then(|value| value.to_str().ok());
-            if accept_header == Some("application/json")
+            let server_fn_client = accept_header == Some("application/json")
                 || accept_header
                     == Some(
                         "application/\
                                 x-www-form-urlencoded",
                     )
-                || accept_header == Some("application/cbor")
-            {
+                || accept_header == Some("application/cbor");
+
+            let serialized = match result {
+                Ok(serialized) => serialized,
+                // Send the error to the client so typed errors can be read from it with `ServerFnErrorExt`
+                Err(err) => {
+                    let body = serde_json::to_string(&err)
+                        .map_err(|err| server_fn::ServerFnError::Serialization(err.to_string()))?;
+                    return Ok(res
+                        .status(crate::server_error::response_status(&err, server_fn_client))
+                        .header("Content-Type", "application/json")
+                        .body(body.into())?);
+                }
+            };
+            if server_fn_client {
                 res = res.status(StatusCode::OK);
             }
 
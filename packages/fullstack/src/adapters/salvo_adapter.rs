@@ -56,12 +56,14 @@ use salvo::{
     async_trait, handler,
     http::{
         cookie::{Cookie, CookieJar},
-        ParseError,
+        header::CACHE_CONTROL,
+        HeaderValue, ParseError,
     },
     serve_static::{StaticDir, StaticFile},
     Depot, Error as SalvoError, FlowCtrl, Handler, Request, Response, Router,
 };
 use server_fn::{Encoding, ServerFunctionRegistry};
+use std::collections::HashSet;
 use std::error::Error;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -143,6 +145,33 @@ pub trait DioxusRouterExt {
     /// ```
     fn register_server_fns(self, server_fn_route: &'static str) -> Self;
 
+    /// Registers server functions with the default handler and shares the given resources with them. Server functions can retrieve the resources with the [`ServerContext`] extractor.
+    ///
+    /// # Example
+    /// ```rust
+    /// use salvo::prelude::*;
+    /// use std::{net::TcpListener, sync::Arc};
+    /// use dioxus_fullstack::prelude::*;
+    ///
+    /// #[derive(Clone)]
+    /// struct Pool;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let router = Router::new()
+    ///         .register_server_fns_with_resources("", ServerResources::new().with(Pool));
+    ///     Server::new(TcpListener::bind("127.0.0.1:8080"))
+    ///         .serve(router)
+    ///         .await;
+    /// }
+    ///
+    /// ```
+    fn register_server_fns_with_resources(
+        self,
+        server_fn_route: &'static str,
+        resources: impl Into<Arc<ServerResources>>,
+    ) -> Self;
+
     /// Register the web RSX hot reloading endpoint. This will enable hot reloading for your application in debug mode when you call [`dioxus_hot_reload::hot_reload_init`].
     ///
     /// # Example
@@ -163,6 +192,8 @@ pub trait DioxusRouterExt {
 
     /// Serves the static WASM for your Dioxus application (except the generated index.html).
     ///
+    /// Fingerprinted assets from the [`AssetManifest`] in the assets folder are served with immutable caching headers.
+    ///
     /// # Example
     /// ```rust
     /// use salvo::prelude::*;
@@ -234,14 +265,25 @@ impl DioxusRouterExt for Router {
     }
 
     fn register_server_fns(self, server_fn_route: &'static str) -> Self {
+        self.register_server_fns_with_resources(server_fn_route, ServerResources::default())
+    }
+
+    fn register_server_fns_with_resources(
+        self,
+        server_fn_route: &'static str,
+        resources: impl Into<Arc<ServerResources>>,
+    ) -> Self {
+        let resources = resources.into();
         self.register_server_fns_with_handler(server_fn_route, |func| ServerFnHandler {
-            server_context: DioxusServerContext::default(),
+            server_context: DioxusServerContext::default().with_resources(resources.clone()),
             function: func,
         })
     }
 
     fn serve_static_assets(mut self, assets_path: impl Into<std::path::PathBuf>) -> Self {
         let assets_path = assets_path.into();
+        let fingerprinted = crate::asset_manifest::read_asset_manifest(&assets_path)
+            .map(|manifest| Arc::new(manifest.fingerprinted_routes()));
 
         // Serve all files in dist folder except index.html
         let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
@@ -267,15 +309,21 @@ impl DioxusRouterExt for Router {
                 })
                 .collect::<Vec<_>>()
                 .join("/");
-            if path.is_file() {
+            let router = if path.is_file() {
                 let route = format!("/{}", route);
                 let serve_dir = StaticFile::new(path.clone());
-                self = self.push(Router::with_path(route).get(serve_dir))
+                Router::with_path(route).get(serve_dir)
             } else {
                 let route = format!("/{}/<**path>", route);
                 let serve_dir = StaticDir::new([path.clone()]);
-                self = self.push(Router::with_path(route).get(serve_dir))
-            }
+                Router::with_path(route).get(serve_dir)
+            };
+            self = match &fingerprinted {
+                Some(fingerprinted) => {
+                    self.push(router.hoop(CacheFingerprintedAssets(fingerprinted.clone())))
+                }
+                None => self.push(router),
+            };
         }
 
         self
@@ -290,7 +338,8 @@ impl DioxusRouterExt for Router {
 
         self.serve_static_assets(cfg.assets_path)
             .connect_hot_reload()
-            .register_server_fns(server_fn_path)
+            .push(Router::with_path("_dioxus/event_replay.js").get(event_replay))
+            .register_server_fns_with_resources(server_fn_path, cfg.resources.clone())
             .push(Router::with_path("/<**any_path>").get(SSRHandler { cfg }))
     }
 
@@ -306,6 +355,14 @@ impl DioxusRouterExt for Router {
     }
 }
 
+/// Serves the script that buffers the events triggered before a page is hydrated.
+#[handler]
+async fn event_replay(res: &mut Response) {
+    res.render(salvo::prelude::Text::Js(
+        dioxus_interpreter_js::EVENT_REPLAY_JS,
+    ));
+}
+
 /// Extracts the parts of a request that are needed for server functions. This will take parts of the request and replace them with empty values.
 pub fn extract_parts(req: &mut Request) -> http::request::Parts {
     let mut parts = http::request::Request::new(()).into_parts().0;
@@ -375,6 +432,29 @@ async fn convert_response(response: HyperResponse, res: &mut Response) {
     }
 }
 
+/// Serves fingerprinted assets with immutable caching headers, because they never change.
+struct CacheFingerprintedAssets(Arc<HashSet<String>>);
+
+#[async_trait]
+impl Handler for CacheFingerprintedAssets {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        flow: &mut FlowCtrl,
+    ) {
+        let immutable = self.0.contains(req.uri().path());
+        flow.call_next(req, depot, res).await;
+        if immutable && res.status_code.map_or(true, |status| status.is_success()) {
+            res.headers_mut().insert(
+                CACHE_CONTROL,
+                HeaderValue::from_static(crate::asset_manifest::IMMUTABLE_CACHE_CONTROL),
+            );
+        }
+    }
+}
+
 /// A handler that renders a Dioxus application to HTML using server-side rendering.
 pub struct SSRHandler<P: Clone> {
     cfg: ServeConfig<P>,
@@ -407,7 +487,8 @@ impl<P: Clone + serde::Serialize + Send + Sync + 'static> Handler for SSRHandler
 
         let route = req.uri().path().to_string();
         let parts: Arc<RwLock<http::request::Parts>> = Arc::new(RwLock::new(extract_parts(req)));
-        let server_context = DioxusServerContext::new(parts);
+        let server_context =
+            DioxusServerContext::new(parts).with_resources(self.cfg.resources.clone());
 
         match renderer_pool
             .render(route, &self.cfg, &server_context)
@@ -421,6 +502,8 @@ impl<P: Clone + serde::Serialize + Send + Sync + 'static> Handler for SSRHandler
                 let headers = server_context.response_parts().unwrap().headers.clone();
                 apply_request_parts_to_response(headers, res);
                 freshness.write(res.headers_mut());
+                // Typed errors thrown while rendering set the status of the page
+                res.status_code = Some(server_context.response_parts().unwrap().status);
             }
             Err(err) => {
                 log::error!("Error rendering SSR: {}", err);
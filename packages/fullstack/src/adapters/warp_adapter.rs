@@ -61,7 +61,7 @@ use warp::path::FullPath;
 use warp::Rejection;
 use warp::{
     filters::BoxedFilter,
-    http::{Response, StatusCode},
+    http::{header::CACHE_CONTROL, HeaderValue, Response, StatusCode},
     hyper::body::Bytes,
     path, Filter, Reply,
 };
@@ -132,13 +132,39 @@ where
 /// }
 /// ```
 pub fn register_server_fns(server_fn_route: &'static str) -> BoxedFilter<(impl Reply,)> {
+    register_server_fns_with_resources(server_fn_route, ServerResources::default())
+}
+
+/// Registers server functions with the default handler and shares the given resources with them. Server functions can retrieve the resources with the [`ServerContext`] extractor.
+///
+/// # Example
+/// ```rust
+/// use dioxus_fullstack::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Pool;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let routes = register_server_fns_with_resources("", ServerResources::new().with(Pool));
+///     warp::serve(routes).run(([127, 0, 0, 1], 8080)).await;
+/// }
+/// ```
+pub fn register_server_fns_with_resources(
+    server_fn_route: &'static str,
+    resources: impl Into<Arc<ServerResources>>,
+) -> BoxedFilter<(impl Reply,)> {
+    let resources = resources.into();
     register_server_fns_with_handler(server_fn_route, |full_route, func| {
+        let resources = resources.clone();
         path(full_route)
             .and(warp::post().or(warp::get()).unify())
             .and(request_parts())
             .and(warp::body::bytes())
             .and_then(move |parts, bytes: bytes::Bytes| {
-                let mut service = server_fn_service(DioxusServerContext::default(), func.clone());
+                let server_context =
+                    DioxusServerContext::default().with_resources(resources.clone());
+                let mut service = server_fn_service(server_context, func.clone());
                 async move {
                     let req = warp::hyper::Request::from_parts(parts, bytes.into());
                     service.run(req).await.map_err(|err| {
@@ -175,11 +201,36 @@ pub fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'sta
 ) -> BoxedFilter<(impl Reply,)> {
     let cfg = cfg.into();
     // Serve the dist folder and the index.html file
-    let serve_dir = warp::fs::dir(cfg.assets_path);
+    let fingerprinted = Arc::new(cfg.asset_manifest.fingerprinted_routes());
+    let serve_dir = path::full().and(warp::fs::dir(cfg.assets_path)).map(
+        move |path: FullPath, file: warp::fs::File| {
+            let mut res = file.into_response();
+            // Fingerprinted files never change, so browsers can cache them forever
+            if fingerprinted.contains(path.as_str()) {
+                res.headers_mut().insert(
+                    CACHE_CONTROL,
+                    HeaderValue::from_static(crate::asset_manifest::IMMUTABLE_CACHE_CONTROL),
+                );
+            }
+            res
+        },
+    );
+
+    let event_replay = warp::path!("_dioxus" / "event_replay.js").map(|| {
+        warp::reply::with_header(
+            dioxus_interpreter_js::EVENT_REPLAY_JS,
+            "Content-Type",
+            "application/javascript",
+        )
+    });
 
     connect_hot_reload()
+        .or(event_replay)
         // First register the server functions
-        .or(register_server_fns(server_fn_route))
+        .or(register_server_fns_with_resources(
+            server_fn_route,
+            cfg.resources.clone(),
+        ))
         // Then the index route
         .or(path::end().and(render_ssr(cfg.clone())))
         // Then the static assets
@@ -201,13 +252,16 @@ pub fn render_ssr<P: Clone + serde::Serialize + Send + Sync + 'static>(
             let parts = Arc::new(RwLock::new(parts));
             let cfg = cfg.clone();
             async move {
-                let server_context = DioxusServerContext::new(parts);
+                let server_context =
+                    DioxusServerContext::new(parts).with_resources(cfg.resources.clone());
 
                 match renderer.render(route, &cfg, &server_context).await {
                     Ok(rendered) => {
                         let crate::render::RenderResponse { html, freshness } = rendered;
 
+                        // Typed errors thrown while rendering set the status of the page
                         let mut res = Response::builder()
+                            .status(server_context.response_parts().unwrap().status)
                             .header("Content-Type", "text/html")
                             .body(html)
                             .unwrap();
@@ -0,0 +1,183 @@
+//! A manifest mapping static assets to their fingerprinted file names, so they can be cached forever and are still
+//! updated as soon as their content changes.
+
+use dioxus::prelude::ScopeState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The name of the manifest in the assets directory.
+#[cfg(feature = "ssr")]
+pub(crate) const ASSET_MANIFEST_FILE: &str = "asset-manifest.json";
+
+/// The `Cache-Control` header fingerprinted assets are served with. Their content never changes without their name
+/// changing, so browsers can cache them forever.
+#[cfg(feature = "ssr")]
+pub(crate) const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// A build manifest that maps the paths of static assets to their fingerprinted file names.
+///
+/// The manifest is read from `asset-manifest.json` in the assets directory, which looks like this:
+///
+/// ```json
+/// {
+///     "assets": {
+///         "logo.png": "logo.3f2a1b9c.png",
+///         "icons/menu.svg": "icons/menu.8d1e0f42.svg"
+///     }
+/// }
+/// ```
+///
+/// All paths are relative to the assets directory. Resolve an asset with [`asset!`](crate::asset) in both server and
+/// client code: the server reads the manifest when the [`ServeConfig`](crate::prelude::ServeConfig) is built, and sends it
+/// to the client with the server rendered HTML. Fingerprinted assets are served with immutable caching headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetManifest {
+    /// The fingerprinted file name of each asset.
+    #[serde(default)]
+    pub assets: BTreeMap<String, String>,
+    /// The path the app is served under, like the `base_path` in `Dioxus.toml`. Resolved assets start with it. The
+    /// server sets it from [`ServeConfigBuilder::base_path`](crate::prelude::ServeConfigBuilder::base_path).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+}
+
+impl AssetManifest {
+    /// Read a manifest from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Get the URL of an asset. Assets that are not in the manifest are served under their original name.
+    pub fn resolve(&self, path: &str) -> String {
+        if path.contains("://") {
+            return path.to_string();
+        }
+        let path = path.trim_start_matches('/');
+        let file = self.assets.get(path).map(String::as_str).unwrap_or(path);
+        match self.base_path.as_deref().map(|base| base.trim_matches('/')) {
+            Some(base) if !base.is_empty() => format!("/{base}/{file}"),
+            _ => format!("/{file}"),
+        }
+    }
+
+    /// The routes of the fingerprinted files, which can be cached forever.
+    #[cfg(feature = "ssr")]
+    pub(crate) fn fingerprinted_routes(&self) -> std::collections::HashSet<String> {
+        self.assets
+            .values()
+            .map(|file| format!("/{}", file.trim_start_matches('/')))
+            .collect()
+    }
+}
+
+/// Read the manifest in the assets directory, if there is one.
+#[cfg(feature = "ssr")]
+pub(crate) fn read_asset_manifest(assets_path: impl AsRef<Path>) -> Option<AssetManifest> {
+    let path = assets_path.as_ref().join(ASSET_MANIFEST_FILE);
+    path.exists()
+        .then(|| AssetManifest::from_file(&path))
+        .and_then(|manifest| {
+            manifest
+                .map_err(|err| log::error!("Failed to read the asset manifest at {path:?}: {err}"))
+                .ok()
+        })
+}
+
+#[cfg(feature = "ssr")]
+/// Encode the manifest into a element, so the client resolves assets to the same files as the server.
+pub(crate) fn encode_asset_manifest_in_element(
+    manifest: &AssetManifest,
+    write_to: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    write_to.write_all(
+        r#"<meta hidden="true" id="dioxus-asset-manifest" data-serialized=""#.as_bytes(),
+    )?;
+    crate::html_storage::serialize::serde_to_writable(manifest, write_to)?;
+    write_to.write_all(r#"" />"#.as_bytes())
+}
+
+fn manifest_from_document() -> Option<AssetManifest> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let attribute = web_sys::window()?
+            .document()?
+            .get_element_by_id("dioxus-asset-manifest")?
+            .get_attribute("data-serialized")?;
+        crate::html_storage::deserialize::serde_from_bytes(attribute.as_bytes())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        None
+    }
+}
+
+/// Get the URL of an asset, using the fingerprinted file name from the [`AssetManifest`] if the asset is in it.
+///
+/// The server provides the manifest of its [`ServeConfig`](crate::prelude::ServeConfig) to the app it renders. The
+/// client reads the manifest the server sent with the page the first time an asset is resolved.
+///
+/// Prefer the [`asset!`](crate::asset) macro, which formats the path like [`format!`].
+pub fn asset(cx: &ScopeState, path: &str) -> String {
+    let manifest = cx
+        .consume_context::<Arc<AssetManifest>>()
+        .unwrap_or_else(|| {
+            cx.provide_root_context(Arc::new(manifest_from_document().unwrap_or_default()))
+        });
+    manifest.resolve(path)
+}
+
+/// Get the URL of an asset, using its fingerprinted file name if it is in the `asset-manifest.json` of the assets
+/// directory. The path is relative to the assets directory and accepts the same arguments as [`format!`] after the
+/// scope.
+///
+/// ```rust, ignore
+/// fn Logo(cx: Scope) -> Element {
+///     render! {
+///         img { src: asset!(cx, "logo.png") }
+///     }
+/// }
+/// ```
+///
+/// See [`AssetManifest`](crate::prelude::AssetManifest) for the format of the manifest.
+#[macro_export]
+macro_rules! asset {
+    ($cx:expr, $($arg:tt)*) => {
+        $crate::prelude::asset($cx, &::std::format!($($arg)*))
+    };
+}
+
+#[test]
+fn resolves_fingerprinted_assets() {
+    let manifest: AssetManifest = serde_json::from_str(
+        r#"{
+            "assets": {
+                "logo.png": "logo.3f2a1b9c.png",
+                "icons/menu.svg": "icons/menu.8d1e0f42.svg"
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(manifest.resolve("logo.png"), "/logo.3f2a1b9c.png");
+    assert_eq!(
+        manifest.resolve("/icons/menu.svg"),
+        "/icons/menu.8d1e0f42.svg"
+    );
+    assert_eq!(manifest.resolve("style.css"), "/style.css");
+    assert_eq!(
+        manifest.resolve("https://example.com/logo.png"),
+        "https://example.com/logo.png"
+    );
+
+    // Assets are served under the base path of the app
+    let manifest = AssetManifest {
+        base_path: Some("/blog/".to_string()),
+        ..manifest
+    };
+    assert_eq!(manifest.resolve("logo.png"), "/blog/logo.3f2a1b9c.png");
+    assert_eq!(manifest.resolve("style.css"), "/blog/style.css");
+}
@@ -0,0 +1,138 @@
+//! A manifest mapping routes to the JS and WASM chunks they need, so server side rendering can preload only the code
+//! each page uses.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+/// A build manifest that maps route patterns to the chunks that route needs.
+///
+/// The manifest is read from JSON that looks like this:
+///
+/// ```json
+/// {
+///     "routes": {
+///         "/": ["assets/home.js"],
+///         "/blog/:id": ["assets/blog.js", "assets/blog_bg.wasm"],
+///         "/docs/:..path": ["assets/docs.js"]
+///     }
+/// }
+/// ```
+///
+/// Route patterns use the same syntax as the router: `:name` matches a single segment and `:..name` matches the rest
+/// of the path. When multiple patterns match a route, the pattern with the most static segments wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// The chunks each route pattern needs.
+    #[serde(default)]
+    pub routes: BTreeMap<String, Vec<String>>,
+}
+
+impl BundleManifest {
+    /// Read a manifest from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Get the chunks the route needs. The route may contain a query string or hash.
+    pub fn chunks_for_route(&self, route: &str) -> &[String] {
+        let path = route.split(['?', '#']).next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        self.routes
+            .iter()
+            .filter_map(|(pattern, chunks)| {
+                match_specificity(pattern, &segments).map(|specificity| (specificity, chunks))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, chunks)| chunks.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Write the preload tags for the chunks the route needs.
+    pub(crate) fn render_preloads<W: Write + ?Sized>(
+        &self,
+        route: &str,
+        to: &mut W,
+    ) -> std::io::Result<()> {
+        for chunk in self.chunks_for_route(route) {
+            let href = if chunk.starts_with('/') || chunk.contains("://") {
+                escape_attribute(chunk)
+            } else {
+                format!("/{}", escape_attribute(chunk))
+            };
+            if chunk.ends_with(".wasm") {
+                write!(
+                    to,
+                    r#"<link rel="preload" href="{href}" as="fetch" type="application/wasm" crossorigin>"#
+                )?;
+            } else if chunk.ends_with(".js") {
+                write!(to, r#"<link rel="modulepreload" href="{href}">"#)?;
+            } else {
+                log::warn!("Skipping chunk {chunk} in the bundle manifest: only .js and .wasm chunks can be preloaded");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of static segments in the pattern if it matches the segments.
+fn match_specificity(pattern: &str, segments: &[&str]) -> Option<usize> {
+    let mut specificity = 0;
+    let mut segments = segments.iter();
+    for pattern_segment in pattern.split('/').filter(|s| !s.is_empty()) {
+        if pattern_segment.starts_with(":..") {
+            return Some(specificity);
+        }
+        let segment = segments.next()?;
+        if pattern_segment.starts_with(':') {
+            continue;
+        }
+        if pattern_segment != *segment {
+            return None;
+        }
+        specificity += 1;
+    }
+    segments.next().is_none().then_some(specificity)
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+#[test]
+fn matches_most_specific_route() {
+    let manifest: BundleManifest = serde_json::from_str(
+        r#"{
+            "routes": {
+                "/": ["home.js"],
+                "/blog/:id": ["blog.js", "blog_bg.wasm"],
+                "/blog/new": ["editor.js"],
+                "/docs/:..path": ["docs.js"]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(manifest.chunks_for_route("/"), ["home.js"]);
+    assert_eq!(
+        manifest.chunks_for_route("/blog/1?draft=true"),
+        ["blog.js", "blog_bg.wasm"]
+    );
+    assert_eq!(manifest.chunks_for_route("/blog/new"), ["editor.js"]);
+    assert_eq!(manifest.chunks_for_route("/docs/guide/intro"), ["docs.js"]);
+    assert!(manifest.chunks_for_route("/blog/1/comments").is_empty());
+
+    let mut html = Vec::new();
+    manifest.render_preloads("/blog/1", &mut html).unwrap();
+    assert_eq!(
+        String::from_utf8(html).unwrap(),
+        r#"<link rel="modulepreload" href="/blog.js"><link rel="preload" href="/blog_bg.wasm" as="fetch" type="application/wasm" crossorigin>"#
+    );
+}
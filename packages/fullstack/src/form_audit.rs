@@ -0,0 +1,326 @@
+//! Verify that forms which submit to server functions keep working without JavaScript.
+
+use crate::server_fn::DioxusServerFnRegistry;
+use dioxus::prelude::VirtualDom;
+use server_fn::{Encoding, ServerFunctionRegistry};
+
+/// Audits the forms in a page to make sure they can be submitted to server functions without JavaScript.
+///
+/// Every form must have an `action`. Forms with an action under the server function route must post url encoded data
+/// to a registered server function, and contain the hidden CSRF input if a CSRF field is set.
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// let _ = dom.rebuild();
+///
+/// FormAudit::new("/api")
+///     .csrf_field("csrf_token")
+///     .assert_dom(&dom);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FormAudit {
+    server_fn_route: String,
+    csrf_field: Option<String>,
+}
+
+/// A problem found by a [`FormAudit`]. Forms are numbered in the order they appear in the page, starting at zero.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FormAuditError {
+    /// The form has no action, so it cannot be submitted without JavaScript.
+    #[error("form {form} has no action, so it cannot be submitted without JavaScript")]
+    MissingAction {
+        /// The index of the form
+        form: usize,
+    },
+    /// The form posts to a path under the server function route that is not a registered server function.
+    #[error("form {form} submits to {action}, which is not a registered server function")]
+    UnknownServerFn {
+        /// The index of the form
+        form: usize,
+        /// The action of the form
+        action: String,
+    },
+    /// The form does not use the post method.
+    #[error("form {form} uses the {method} method, but server functions only accept forms that use the post method")]
+    InvalidMethod {
+        /// The index of the form
+        form: usize,
+        /// The method of the form
+        method: String,
+    },
+    /// The server function the form submits to does not accept url encoded data.
+    #[error("form {form} submits to {action}, which does not accept url encoded form data")]
+    UnsupportedEncoding {
+        /// The index of the form
+        form: usize,
+        /// The action of the form
+        action: String,
+    },
+    /// The form does not contain a hidden CSRF input.
+    #[error("form {form} does not contain a hidden {field} input")]
+    MissingCsrfInput {
+        /// The index of the form
+        form: usize,
+        /// The name of the CSRF field
+        field: String,
+    },
+}
+
+impl FormAudit {
+    /// Create an audit for server functions registered under `server_fn_route`.
+    pub fn new(server_fn_route: &str) -> Self {
+        Self {
+            server_fn_route: server_fn_route.trim_end_matches('/').to_string(),
+            csrf_field: None,
+        }
+    }
+
+    /// Require every server function form to contain a hidden input with this name.
+    pub fn csrf_field(mut self, name: &str) -> Self {
+        self.csrf_field = Some(name.to_string());
+        self
+    }
+
+    /// Render the [`VirtualDom`] without hydration and audit the forms it contains.
+    pub fn audit_dom(&self, dom: &VirtualDom) -> Result<(), Vec<FormAuditError>> {
+        let mut renderer = dioxus_ssr::Renderer::new();
+        renderer.pre_render = false;
+        self.audit_html(&renderer.render(dom))
+    }
+
+    /// Audit the forms in rendered HTML.
+    pub fn audit_html(&self, html: &str) -> Result<(), Vec<FormAuditError>> {
+        let mut errors = Vec::new();
+        let mut forms = Vec::new();
+        // The forms that contain the current tag. Inputs belong to the innermost form.
+        let mut open_forms: Vec<usize> = Vec::new();
+
+        for tag in tags(html) {
+            match (tag.name.as_str(), tag.closing) {
+                ("form", false) => {
+                    open_forms.push(forms.len());
+                    forms.push((tag, false));
+                }
+                ("form", true) => {
+                    open_forms.pop();
+                }
+                ("input", false) => {
+                    let (Some(&form), Some(field)) = (open_forms.last(), &self.csrf_field) else {
+                        continue;
+                    };
+                    let hidden = tag
+                        .attribute("type")
+                        .is_some_and(|ty| ty.eq_ignore_ascii_case("hidden"));
+                    if hidden && tag.attribute("name") == Some(field) {
+                        forms[form].1 = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (index, (form, has_csrf_input)) in forms.iter().enumerate() {
+            let Some(action) = form.attribute("action") else {
+                errors.push(FormAuditError::MissingAction { form: index });
+                continue;
+            };
+            let path = action.split(['?', '#']).next().unwrap_or_default();
+            let Some(server_fn_path) = path
+                .strip_prefix(&self.server_fn_route)
+                .and_then(|path| path.strip_prefix('/'))
+            else {
+                continue;
+            };
+
+            match DioxusServerFnRegistry::get_encoding(server_fn_path) {
+                Some(Encoding::Url) => {}
+                Some(_) => errors.push(FormAuditError::UnsupportedEncoding {
+                    form: index,
+                    action: action.to_string(),
+                }),
+                None => errors.push(FormAuditError::UnknownServerFn {
+                    form: index,
+                    action: action.to_string(),
+                }),
+            }
+            let method = form.attribute("method").unwrap_or("get");
+            if !method.eq_ignore_ascii_case("post") {
+                errors.push(FormAuditError::InvalidMethod {
+                    form: index,
+                    method: method.to_string(),
+                });
+            }
+            if let (Some(field), false) = (&self.csrf_field, has_csrf_input) {
+                errors.push(FormAuditError::MissingCsrfInput {
+                    form: index,
+                    field: field.clone(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Render the [`VirtualDom`] without hydration and panic if any form fails the audit.
+    pub fn assert_dom(&self, dom: &VirtualDom) {
+        if let Err(errors) = self.audit_dom(dom) {
+            panic!("{}", describe(&errors));
+        }
+    }
+
+    /// Panic if any form in the rendered HTML fails the audit.
+    pub fn assert_html(&self, html: &str) {
+        if let Err(errors) = self.audit_html(html) {
+            panic!("{}", describe(&errors));
+        }
+    }
+}
+
+fn describe(errors: &[FormAuditError]) -> String {
+    let mut message = String::from("Some forms do not work without JavaScript:");
+    for error in errors {
+        message += "\n- ";
+        message += &error.to_string();
+    }
+    message
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    attributes: Vec<(String, String)>,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A minimal tokenizer for the HTML the SSR renderer outputs. Only the tags and their attributes are returned.
+fn tags(html: &str) -> impl Iterator<Item = Tag> + '_ {
+    let mut rest = html;
+    std::iter::from_fn(move || loop {
+        let start = rest.find('<')?;
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment
+                .split_once("-->")
+                .map(|(_, rest)| rest)
+                .unwrap_or("");
+            continue;
+        }
+        let closing = rest.starts_with('/');
+        let tag = rest.trim_start_matches('/');
+        let name_len = tag
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(tag.len());
+        if name_len == 0 {
+            continue;
+        }
+        let name = tag[..name_len].to_ascii_lowercase();
+        let mut attributes = Vec::new();
+        let mut remaining = &tag[name_len..];
+        loop {
+            remaining = remaining.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if remaining.is_empty() || remaining.starts_with('>') {
+                break;
+            }
+            let attr_len = remaining
+                .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+                .unwrap_or(remaining.len());
+            let attribute = remaining[..attr_len].to_ascii_lowercase();
+            remaining = remaining[attr_len..].trim_start();
+            let mut value = String::new();
+            if let Some(after_eq) = remaining.strip_prefix('=') {
+                let after_eq = after_eq.trim_start();
+                let (raw, after) = match after_eq.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let inner = &after_eq[1..];
+                        let end = inner.find(quote).unwrap_or(inner.len());
+                        (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                    }
+                    _ => {
+                        let end = after_eq
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after_eq.len());
+                        (&after_eq[..end], &after_eq[end..])
+                    }
+                };
+                value = unescape(raw);
+                remaining = after;
+            }
+            attributes.push((attribute, value));
+        }
+        rest = remaining;
+        return Some(Tag {
+            name,
+            closing,
+            attributes,
+        });
+    })
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[test]
+fn audits_server_fn_forms() {
+    use dioxus::prelude::*;
+
+    fn app(cx: Scope) -> Element {
+        render! {
+            form { action: "/search", input { name: "query" } }
+            form {
+                action: "/api/missing",
+                method: "get",
+                input { r#type: "hidden", name: "csrf_token", value: "token" }
+            }
+            form { action: "/api/missing", method: "post" }
+            form { onsubmit: |_| {} }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+
+    let errors = FormAudit::new("/api/")
+        .csrf_field("csrf_token")
+        .audit_dom(&dom)
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            FormAuditError::UnknownServerFn {
+                form: 1,
+                action: "/api/missing".to_string()
+            },
+            FormAuditError::InvalidMethod {
+                form: 1,
+                method: "get".to_string()
+            },
+            FormAuditError::UnknownServerFn {
+                form: 2,
+                action: "/api/missing".to_string()
+            },
+            FormAuditError::MissingCsrfInput {
+                form: 2,
+                field: "csrf_token".to_string()
+            },
+            FormAuditError::MissingAction { form: 3 },
+        ]
+    );
+}
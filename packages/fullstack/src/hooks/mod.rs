@@ -1,2 +1,4 @@
+pub mod request;
 pub mod server_cached;
+pub mod server_error_handler;
 pub mod server_future;
@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Get data about the request that rendered the page, like the [`RequestInfo`](crate::prelude::RequestInfo) the
+/// axum adapter registers, or any other value inserted into the [`DioxusServerContext`](crate::prelude::DioxusServerContext).
+///
+/// While rendering on the server, the value is cloned from the server context of the request and serialized into the
+/// page. Once the page hydrates, the client reads the same value back from the page, so both render the same UI.
+///
+/// Like [`server_cached`](crate::prelude::server_cached), this must be called in the same order on the server and the
+/// client. Returns `None` if the value was not registered for the request.
+///
+/// # Example
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let locale = use_request::<RequestInfo>(cx)
+///         .and_then(|info| info.locale.clone())
+///         .unwrap_or_else(|| "en".to_string());
+///
+///     render! { Greeting { locale: locale } }
+/// }
+/// ```
+pub fn use_request<T>(cx: &ScopeState) -> Option<&T>
+where
+    T: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    cx.use_hook(|| {
+        #[cfg(feature = "ssr")]
+        {
+            let server_context = crate::prelude::server_context();
+            let value = server_context.get::<T>();
+            if let Err(err) = server_context.push_html_data(&value) {
+                log::error!("Failed to push HTML data: {}", err);
+            }
+            value
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            crate::html_storage::deserialize::take_server_data::<Option<T>>().flatten()
+        }
+    })
+    .as_ref()
+}
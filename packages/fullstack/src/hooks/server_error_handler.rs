@@ -0,0 +1,42 @@
+use crate::server_error::{ServerError, ServerFnErrorExt};
+use dioxus::prelude::*;
+use server_fn::ServerFnError;
+
+/// Handle typed errors of server functions thrown in any component below this one.
+///
+/// The handler is called with every [`ServerFnError`] thrown with [`ScopeState::throw`] that holds an `E`, before the
+/// error is shown by the error boundary. This lets you handle errors like an expired session in one place, instead of
+/// after every server call.
+///
+/// # Example
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     use_server_error_handler(cx, |error: AuthError| {
+///         if let AuthError::Unauthorized = error {
+///             redirect_to_login();
+///         }
+///     });
+///
+///     render! { Dashboard {} }
+/// }
+///
+/// fn Dashboard(cx: Scope) -> Element {
+///     let secrets = use_server_future(cx, (), |_| get_secrets())?;
+///     let secrets = secrets.value();
+///     let secrets = (&*secrets).throw(cx)?;
+///
+///     render! { "{secrets:?}" }
+/// }
+/// ```
+pub fn use_server_error_handler<E: ServerError>(cx: &ScopeState, handler: impl Fn(E) + 'static) {
+    cx.use_hook(|| {
+        cx.on_error(move |error| {
+            if let Some(error) = error
+                .downcast::<ServerFnError>()
+                .and_then(|error| error.typed::<E>())
+            {
+                handler(error)
+            }
+        })
+    });
+}
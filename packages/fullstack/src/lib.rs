@@ -12,6 +12,11 @@ pub mod router;
 
 #[cfg(feature = "ssr")]
 mod adapters;
+mod asset_manifest;
+#[cfg(feature = "ssr")]
+mod bundle_manifest;
+#[cfg(feature = "ssr")]
+mod form_audit;
 #[cfg(feature = "ssr")]
 pub use adapters::*;
 mod hooks;
@@ -21,12 +26,18 @@ pub mod launch;
 #[cfg(feature = "ssr")]
 mod layer;
 #[cfg(feature = "ssr")]
+mod metrics;
+#[cfg(feature = "ssr")]
 mod render;
+mod request_info;
 #[cfg(feature = "ssr")]
 mod serve_config;
 #[cfg(feature = "ssr")]
 mod server_context;
+mod server_error;
 mod server_fn;
+#[cfg(feature = "ssr")]
+mod transaction;
 
 /// A prelude of commonly used items in dioxus-fullstack.
 pub mod prelude {
@@ -36,14 +47,25 @@ pub mod prelude {
     pub use crate::adapters::salvo_adapter::*;
     #[cfg(feature = "warp")]
     pub use crate::adapters::warp_adapter::*;
+    pub use crate::asset;
+    pub use crate::asset_manifest::{asset, AssetManifest};
+    #[cfg(feature = "ssr")]
+    pub use crate::bundle_manifest::BundleManifest;
+    #[cfg(feature = "ssr")]
+    pub use crate::form_audit::{FormAudit, FormAuditError};
     use crate::hooks;
     #[cfg(not(feature = "ssr"))]
     pub use crate::html_storage::deserialize::get_root_props_from_document;
     pub use crate::launch::LaunchBuilder;
+    #[cfg(feature = "ssr")]
+    pub use crate::metrics::graceful_shutdown;
     #[cfg(all(feature = "ssr", feature = "router"))]
     pub use crate::render::pre_cache_static_routes_with_props;
     #[cfg(feature = "ssr")]
     pub use crate::render::SSRState;
+    #[cfg(feature = "ssr")]
+    pub use crate::request_info::RequestInfoConfig;
+    pub use crate::request_info::{RequestInfo, SENSITIVE_HEADERS};
     #[cfg(feature = "router")]
     pub use crate::router::FullstackRouterConfig;
     #[cfg(feature = "ssr")]
@@ -53,14 +75,21 @@ pub mod prelude {
     #[cfg(feature = "ssr")]
     pub use crate::server_context::{
         extract, server_context, DioxusServerContext, FromServerContext, ProvideServerContext,
+        ServerContext, ServerResources,
     };
+    pub use crate::server_error::{ServerError, ServerFnErrorExt};
     pub use crate::server_fn::DioxusServerFn;
     #[cfg(feature = "ssr")]
     pub use crate::server_fn::{ServerFnMiddleware, ServerFnTraitObj, ServerFunction};
+    #[cfg(feature = "ssr")]
+    pub use crate::transaction::{run_transactional, Transaction, TransactionSource};
     pub use dioxus_server_macro::*;
     #[cfg(feature = "ssr")]
     pub use dioxus_ssr::incremental::IncrementalRendererConfig;
     pub use server_fn::{self, ServerFn as _, ServerFnError};
 
-    pub use hooks::{server_cached::server_cached, server_future::use_server_future};
+    pub use hooks::{
+        request::use_request, server_cached::server_cached,
+        server_error_handler::use_server_error_handler, server_future::use_server_future,
+    };
 }
@@ -0,0 +1,212 @@
+//! Metrics about server side rendering and server functions, and the state of graceful shutdowns.
+
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// The upper bounds of the render latency histogram buckets, in seconds. These are the default buckets of Prometheus.
+const RENDER_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+pub(crate) struct ServerMetrics {
+    // The number of renders that took at most the bound of each bucket
+    render_buckets: [AtomicU64; RENDER_BUCKETS.len()],
+    render_count: AtomicU64,
+    render_sum_micros: AtomicU64,
+    // The number of renders that are not finished yet
+    renders_in_flight: watch::Sender<usize>,
+    // The number of calls to each server function, by its url
+    server_fn_calls: Mutex<BTreeMap<String, u64>>,
+    // Set once the server started to shut down
+    draining: AtomicBool,
+}
+
+static METRICS: Lazy<ServerMetrics> = Lazy::new(|| ServerMetrics {
+    render_buckets: Default::default(),
+    render_count: AtomicU64::new(0),
+    render_sum_micros: AtomicU64::new(0),
+    renders_in_flight: watch::channel(0).0,
+    server_fn_calls: Mutex::new(BTreeMap::new()),
+    draining: AtomicBool::new(false),
+});
+
+/// The metrics of the server.
+pub(crate) fn metrics() -> &'static ServerMetrics {
+    &METRICS
+}
+
+impl ServerMetrics {
+    /// Start tracking a render. The render is in flight until the returned guard is dropped.
+    pub(crate) fn start_render(&'static self) -> RenderGuard {
+        self.renders_in_flight.send_modify(|renders| *renders += 1);
+        RenderGuard { metrics: self }
+    }
+
+    /// Record how long a finished render took.
+    pub(crate) fn record_render(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bucket, bound) in self.render_buckets.iter().zip(RENDER_BUCKETS) {
+            if seconds <= bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.render_count.fetch_add(1, Ordering::Relaxed);
+        self.render_sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Record a call to the server function at the given url.
+    pub(crate) fn record_server_fn_call(&self, url: &str) {
+        let mut calls = self.server_fn_calls.lock().unwrap();
+        *calls.entry(url.to_string()).or_default() += 1;
+    }
+
+    /// Returns true once the server started to shut down.
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    /// Encode the metrics in the Prometheus text format.
+    pub(crate) fn encode(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP dioxus_ssr_render_duration_seconds The time it took to server side render a page."
+        );
+        let _ = writeln!(out, "# TYPE dioxus_ssr_render_duration_seconds histogram");
+        for (bucket, bound) in self.render_buckets.iter().zip(RENDER_BUCKETS) {
+            let _ = writeln!(
+                out,
+                "dioxus_ssr_render_duration_seconds_bucket{{le=\"{bound}\"}} {}",
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let count = self.render_count.load(Ordering::Relaxed);
+        let sum = self.render_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(
+            out,
+            "dioxus_ssr_render_duration_seconds_bucket{{le=\"+Inf\"}} {count}"
+        );
+        let _ = writeln!(out, "dioxus_ssr_render_duration_seconds_sum {sum}");
+        let _ = writeln!(out, "dioxus_ssr_render_duration_seconds_count {count}");
+
+        let _ = writeln!(
+            out,
+            "# HELP dioxus_ssr_renders_in_flight The number of pages that are being server side rendered."
+        );
+        let _ = writeln!(out, "# TYPE dioxus_ssr_renders_in_flight gauge");
+        let _ = writeln!(
+            out,
+            "dioxus_ssr_renders_in_flight {}",
+            *self.renders_in_flight.borrow()
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP dioxus_server_fn_calls_total The number of calls to each server function."
+        );
+        let _ = writeln!(out, "# TYPE dioxus_server_fn_calls_total counter");
+        for (url, calls) in self.server_fn_calls.lock().unwrap().iter() {
+            let url = url.replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(
+                out,
+                "dioxus_server_fn_calls_total{{function=\"{url}\"}} {calls}"
+            );
+        }
+
+        out
+    }
+}
+
+/// Keeps a render in flight until it is dropped.
+pub(crate) struct RenderGuard {
+    metrics: &'static ServerMetrics,
+}
+
+impl Drop for RenderGuard {
+    fn drop(&mut self) {
+        self.metrics
+            .renders_in_flight
+            .send_modify(|renders| *renders -= 1);
+    }
+}
+
+/// Shut the server down gracefully once `signal` resolves.
+///
+/// Once the signal resolves, the health check reports that the server is unavailable and new pages are no longer
+/// rendered. The returned future resolves once all of the pages that were being rendered are finished, or once
+/// `drain_timeout` passed, so it can be passed to the graceful shutdown of the server to drain in-flight renders before
+/// the server stops without a stuck render keeping it alive.
+///
+/// # Example
+/// ```rust, ignore
+/// axum::Server::bind(&addr)
+///     .serve(
+///         axum::Router::new()
+///             .serve_health_check()
+///             .serve_dioxus_application("", ServeConfigBuilder::new(app, ()))
+///             .into_make_service(),
+///     )
+///     .with_graceful_shutdown(graceful_shutdown(
+///         async {
+///             tokio::signal::ctrl_c().await.unwrap();
+///         },
+///         std::time::Duration::from_secs(30),
+///     ))
+///     .await
+///     .unwrap();
+/// ```
+pub async fn graceful_shutdown(
+    signal: impl std::future::Future<Output = ()>,
+    drain_timeout: Duration,
+) {
+    signal.await;
+
+    let metrics = metrics();
+    metrics.draining.store(true, Ordering::Relaxed);
+    let mut renders_in_flight = metrics.renders_in_flight.subscribe();
+    let drained = tokio::time::timeout(drain_timeout, async move {
+        while *renders_in_flight.borrow_and_update() > 0 {
+            if renders_in_flight.changed().await.is_err() {
+                break;
+            }
+        }
+    })
+    .await;
+    if drained.is_err() {
+        log::warn!(
+            "Shutting down with {} renders in flight after waiting {drain_timeout:?}",
+            *metrics.renders_in_flight.borrow()
+        );
+    }
+}
+
+#[test]
+fn encodes_prometheus_metrics() {
+    let metrics = metrics();
+    metrics.record_render(Duration::from_millis(30));
+    metrics.record_server_fn_call("/api/get_server_data");
+    metrics.record_server_fn_call("/api/get_server_data");
+
+    let encoded = metrics.encode();
+    assert!(encoded.contains("dioxus_ssr_render_duration_seconds_bucket{le=\"0.025\"} 0"));
+    assert!(encoded.contains("dioxus_ssr_render_duration_seconds_bucket{le=\"0.05\"} 1"));
+    assert!(encoded.contains("dioxus_ssr_render_duration_seconds_count 1"));
+    assert!(encoded.contains("dioxus_server_fn_calls_total{function=\"/api/get_server_data\"} 2"));
+}
+
+#[tokio::test]
+async fn shutdown_stops_waiting_for_stuck_renders() {
+    let metrics = metrics();
+    let _stuck = metrics.start_render();
+    let shutdown = graceful_shutdown(async {}, Duration::from_millis(10));
+    tokio::time::timeout(Duration::from_secs(5), shutdown)
+        .await
+        .expect("the shutdown waited for the stuck render");
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use crate::server_context::SERVER_CONTEXT;
 use dioxus::prelude::VirtualDom;
 use dioxus_ssr::{
+    critical_css::UsedSelectors,
     incremental::{IncrementalRendererConfig, RenderFreshness, WrapBody},
     Renderer,
 };
@@ -32,11 +33,15 @@ impl SsrRendererPool {
         let wrapper = FullstackRenderer {
             cfg: cfg.clone(),
             server_context: server_context.clone(),
+            head: HeadContext::new(),
+            used_selectors: Default::default(),
         };
         match self {
             Self::Renderer(pool) => {
                 let server_context = Box::new(server_context.clone());
                 let mut renderer = pool.write().unwrap().pop().unwrap_or_else(pre_renderer);
+                renderer.pre_render = cfg.hydrate;
+                renderer.lazy_hydration = cfg.lazy_hydration;
 
                 let (tx, rx) = tokio::sync::oneshot::channel();
 
@@ -45,6 +50,10 @@ impl SsrRendererPool {
                         .expect("couldn't spawn runtime")
                         .block_on(async move {
                             let mut vdom = VirtualDom::new_with_props(component, props);
+                            vdom.base_scope().provide_context(wrapper.head.clone());
+                            vdom.base_scope()
+                                .provide_context(wrapper.cfg.asset_manifest.clone());
+                            let _error_status = map_error_status(&vdom, &server_context);
                             let mut to = WriteBuffer { buffer: Vec::new() };
                             // before polling the future, we need to set the context
                             let prev_context =
@@ -56,8 +65,13 @@ impl SsrRendererPool {
                             log::info!("Suspense resolved");
                             // after polling the future, we need to restore the context
                             SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+                            if wrapper.cfg.critical_css.is_some() {
+                                *wrapper.used_selectors.write().unwrap() =
+                                    Some(UsedSelectors::from_dom(&vdom));
+                            }
 
-                            if let Err(err) = wrapper.render_before_body(&mut *to) {
+                            if let Err(err) = wrapper.render_before_body_of_route(&route, &mut *to)
+                            {
                                 let _ = tx.send(Err(err));
                                 return;
                             }
@@ -95,10 +109,18 @@ impl SsrRendererPool {
                     pool.write().unwrap().pop().unwrap_or_else(|| {
                         incremental_pre_renderer(cfg.incremental.as_ref().unwrap())
                     });
+                renderer.renderer_mut().pre_render = cfg.hydrate;
+                renderer.renderer_mut().lazy_hydration = cfg.lazy_hydration;
 
                 let (tx, rx) = tokio::sync::oneshot::channel();
 
                 let server_context = server_context.clone();
+                let head = wrapper.head.clone();
+                let asset_manifest = cfg.asset_manifest.clone();
+                let used_selectors = cfg
+                    .critical_css
+                    .is_some()
+                    .then(|| wrapper.used_selectors.clone());
                 spawn_blocking(move || {
                     tokio::runtime::Runtime::new()
                         .expect("couldn't spawn runtime")
@@ -111,7 +133,11 @@ impl SsrRendererPool {
                                     props,
                                     &mut *to,
                                     |vdom| {
+                                        vdom.base_scope().provide_context(head);
+                                        vdom.base_scope().provide_context(asset_manifest);
+                                        let error_status = map_error_status(vdom, &server_context);
                                         Box::pin(async move {
+                                            let _error_status = error_status;
                                             // before polling the future, we need to set the context
                                             let prev_context = SERVER_CONTEXT
                                                 .with(|ctx| ctx.replace(Box::new(server_context)));
@@ -122,6 +148,10 @@ impl SsrRendererPool {
                                             log::info!("Suspense resolved");
                                             // after polling the future, we need to restore the context
                                             SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+                                            if let Some(used_selectors) = used_selectors {
+                                                *used_selectors.write().unwrap() =
+                                                    Some(UsedSelectors::from_dom(vdom));
+                                            }
                                         })
                                     },
                                     &wrapper,
@@ -156,6 +186,9 @@ impl SsrRendererPool {
     }
 }
 
+/// The route the adapters serve [`dioxus_interpreter_js::EVENT_REPLAY_JS`] from for hydrated pages.
+pub(crate) const EVENT_REPLAY_PATH: &str = "/_dioxus/event_replay.js";
+
 /// State used in server side rendering. This utilizes a pool of [`dioxus_ssr::Renderer`]s to cache static templates between renders.
 #[derive(Clone)]
 pub struct SSRState {
@@ -200,10 +233,14 @@ impl SSRState {
         async move {
             let ServeConfig { app, props, .. } = cfg;
 
+            let metrics = crate::metrics::metrics();
+            let _in_flight = metrics.start_render();
+            let start = std::time::Instant::now();
             let (freshness, html) = self
                 .renderers
                 .render_to(cfg, route, *app, props.clone(), server_context)
                 .await?;
+            metrics.record_render(start.elapsed());
 
             Ok(RenderResponse { html, freshness })
         }
@@ -213,6 +250,19 @@ impl SSRState {
 struct FullstackRenderer<P: Clone + Send + Sync + 'static> {
     cfg: ServeConfig<P>,
     server_context: DioxusServerContext,
+    // The title and other metadata the app sets while it renders
+    head: HeadContext,
+    // The selectors of the rendered page, if its critical CSS is inlined
+    used_selectors: Arc<RwLock<Option<UsedSelectors>>>,
+}
+
+impl<P: Clone + Send + Sync + 'static> FullstackRenderer<P> {
+    /// The rules of the stylesheet the rendered page uses, if the critical CSS is inlined
+    fn critical_css(&self) -> Option<String> {
+        let critical_css = self.cfg.critical_css.as_ref()?;
+        let used_selectors = self.used_selectors.read().unwrap();
+        Some(critical_css.stylesheet.critical(used_selectors.as_ref()?))
+    }
 }
 
 impl<P: Clone + Serialize + Send + Sync + 'static> dioxus_ssr::incremental::WrapBody
@@ -229,34 +279,91 @@ impl<P: Clone + Serialize + Send + Sync + 'static> dioxus_ssr::incremental::Wrap
         Ok(())
     }
 
+    fn render_before_body_of_route<R: std::io::Write>(
+        &self,
+        route: &str,
+        to: &mut R,
+    ) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
+        let ServeConfig {
+            index,
+            bundle_manifest,
+            ..
+        } = &self.cfg;
+
+        match index.head_end {
+            // Add the metadata of the app and preload the chunks of the route at the end of the head
+            Some(head_end) => {
+                let (head, rest) = index.pre_main.split_at(head_end);
+                // The title of the app replaces the title of the index
+                let mut head = match self.head.title() {
+                    Some(_) => without_title(head),
+                    None => head.into(),
+                };
+                let critical_css = self.critical_css();
+                // The full stylesheet is loaded after the page is shown with its critical CSS
+                if let (Some(_), Some(cfg)) = (&critical_css, &self.cfg.critical_css) {
+                    head = defer_stylesheet(&head, cfg.href).into_owned().into();
+                }
+                to.write_all(head.as_bytes())?;
+                to.write_all(self.head.render().as_bytes())?;
+                if let Some(critical_css) = critical_css {
+                    to.write_all(b"<style>")?;
+                    to.write_all(critical_css.as_bytes())?;
+                    to.write_all(b"</style>")?;
+                }
+                // Buffer the events the user triggers before the page is hydrated, so the client can replay them.
+                // The script is served as a file instead of inlined so pages work under a strict `script-src` CSP
+                if self.cfg.hydrate {
+                    write!(to, r#"<script src="{EVENT_REPLAY_PATH}"></script>"#)?;
+                }
+                if let Some(bundle_manifest) = bundle_manifest {
+                    bundle_manifest.render_preloads(route, to)?;
+                }
+                to.write_all(rest.as_bytes())?;
+            }
+            None => to.write_all(index.pre_main.as_bytes())?,
+        }
+
+        Ok(())
+    }
+
     fn render_after_body<R: std::io::Write>(
         &self,
         to: &mut R,
     ) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
-        // serialize the props
-        crate::html_storage::serialize::encode_props_in_element(&self.cfg.props, to)?;
-        // serialize the server state
-        crate::html_storage::serialize::encode_in_element(
-            &*self.server_context.html_data().map_err(|_| {
-                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new({
-                    #[derive(Debug)]
-                    struct HTMLDataReadError;
-
-                    impl std::fmt::Display for HTMLDataReadError {
-                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                            f.write_str(
-                                "Failed to read the server data to serialize it into the HTML",
-                            )
+        if self.cfg.hydrate {
+            // serialize the props
+            crate::html_storage::serialize::encode_props_in_element(&self.cfg.props, to)?;
+            // send the asset manifest, so the client resolves assets to the same files
+            if *self.cfg.asset_manifest != Default::default() {
+                crate::asset_manifest::encode_asset_manifest_in_element(
+                    &self.cfg.asset_manifest,
+                    to,
+                )?;
+            }
+            // serialize the server state
+            crate::html_storage::serialize::encode_in_element(
+                &*self.server_context.html_data().map_err(|_| {
+                    dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new({
+                        #[derive(Debug)]
+                        struct HTMLDataReadError;
+
+                        impl std::fmt::Display for HTMLDataReadError {
+                            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                                f.write_str(
+                                    "Failed to read the server data to serialize it into the HTML",
+                                )
+                            }
                         }
-                    }
 
-                    impl std::error::Error for HTMLDataReadError {}
+                        impl std::error::Error for HTMLDataReadError {}
 
-                    HTMLDataReadError
-                }))
-            })?,
-            to,
-        )?;
+                        HTMLDataReadError
+                    }))
+                })?,
+                to,
+            )?;
+        }
 
         #[cfg(all(debug_assertions, feature = "hot-reload"))]
         {
@@ -319,6 +426,57 @@ impl RenderResponse {
     }
 }
 
+// Remove the first `<title>` element from the head of the index
+fn without_title(head: &str) -> std::borrow::Cow<'_, str> {
+    let title = head.find("<title").and_then(|start| {
+        let end = head[start..].find("</title>")? + start + "</title>".len();
+        Some((start, end))
+    });
+    match title {
+        Some((start, end)) => format!("{}{}", &head[..start], &head[end..]).into(),
+        None => head.into(),
+    }
+}
+
+// Load the stylesheet linked with `href` in the head without blocking the first paint
+fn defer_stylesheet<'a>(head: &'a str, href: &str) -> std::borrow::Cow<'a, str> {
+    let link = head
+        .find(&format!("href=\"{href}\""))
+        .and_then(|href_start| {
+            let start = head[..href_start].rfind("<link")?;
+            let end = head[href_start..].find('>')? + href_start + 1;
+            Some((start, end))
+        });
+    match link {
+        Some((start, end)) => format!(
+            r#"{}<link rel="preload" href="{href}" as="style" onload="this.onload=null;this.rel='stylesheet'"><noscript>{}</noscript>{}"#,
+            &head[..start],
+            &head[start..end],
+            &head[end..]
+        )
+        .into(),
+        None => head.into(),
+    }
+}
+
+/// Give the rendered page the status of the typed server function errors that reach the root of the app. Errors caught
+/// by an error boundary below the root don't change the status.
+fn map_error_status(
+    vdom: &VirtualDom,
+    server_context: &DioxusServerContext,
+) -> Option<dioxus::core::ErrorHandler> {
+    let server_context = server_context.clone();
+    vdom.base_scope().on_error(move |error| {
+        let status = error
+            .downcast::<ServerFnError>()
+            .and_then(|error| error.status_code())
+            .and_then(|status| http::StatusCode::from_u16(status).ok());
+        if let Some(status) = status {
+            server_context.response_parts_mut().unwrap().status = status;
+        }
+    })
+}
+
 fn pre_renderer() -> Renderer {
     let mut renderer = Renderer::default();
     renderer.pre_render = true;
@@ -345,6 +503,8 @@ where
     let wrapper = FullstackRenderer {
         cfg: cfg.clone(),
         server_context: Default::default(),
+        head: HeadContext::new(),
+        used_selectors: Default::default(),
     };
     let mut renderer = incremental_pre_renderer(
         cfg.incremental
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+
+/// Information about the request that rendered the page.
+///
+/// The axum adapter registers the request info of every page it renders, so components can read it with
+/// [`use_request`](crate::prelude::use_request) both on the server and after the page hydrates on the client.
+///
+/// The request info is written into the page so the client can read it, and pages cached with incremental rendering
+/// are served to other users. To keep credentials out of the page, cookies and the headers in [`SENSITIVE_HEADERS`]
+/// are left out unless they are exposed with [`RequestInfoConfig`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestInfo {
+    /// The headers of the request, other than the sensitive headers that were not exposed
+    pub headers: Vec<(String, String)>,
+    /// The cookies sent with the request that were exposed
+    pub cookies: Vec<(String, String)>,
+    /// The language the client prefers most, from the `Accept-Language` header
+    pub locale: Option<String>,
+    /// The `User-Agent` header of the request
+    pub user_agent: Option<String>,
+}
+
+/// The headers that are left out of the [`RequestInfo`] unless they are exposed with [`RequestInfoConfig`], because
+/// they often carry credentials or identify the client.
+pub const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "forwarded",
+    "proxy-authorization",
+    "x-api-key",
+    "x-csrf-token",
+    "x-forwarded-for",
+    "x-real-ip",
+    "x-xsrf-token",
+];
+
+/// Choose the cookies and sensitive headers that are written into the [`RequestInfo`] of every page. Nothing is exposed
+/// by default.
+///
+/// Only expose values that are safe for the client to read, and that are safe to share between users if incremental
+/// rendering caches the page.
+///
+/// ```rust, ignore
+/// let cfg = ServeConfigBuilder::new(app, ())
+///     .request_info(RequestInfoConfig::new().expose_cookie("theme"))
+///     .build();
+/// ```
+#[cfg(feature = "ssr")]
+#[derive(Clone, Debug, Default)]
+pub struct RequestInfoConfig {
+    cookies: Vec<String>,
+    headers: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl RequestInfoConfig {
+    /// Create a config that doesn't expose any cookies or sensitive headers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write the cookie with this name into the request info
+    pub fn expose_cookie(mut self, name: impl Into<String>) -> Self {
+        self.cookies.push(name.into());
+        self
+    }
+
+    /// Write the header with this name into the request info, even if it is one of the [`SENSITIVE_HEADERS`]. Header
+    /// names are case insensitive.
+    pub fn expose_header(mut self, name: impl Into<String>) -> Self {
+        self.headers.push(name.into());
+        self
+    }
+
+    fn exposes_header(&self, name: &str) -> bool {
+        !SENSITIVE_HEADERS.contains(&name)
+            || self
+                .headers
+                .iter()
+                .any(|header| header.eq_ignore_ascii_case(name))
+    }
+}
+
+impl RequestInfo {
+    /// Get the value of a header. Header names are case insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the value of a cookie
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.cookies
+            .iter()
+            .find(|(cookie, _)| cookie == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Read the request info from the parts of a request, leaving out the cookies and sensitive headers that `cfg`
+    /// doesn't expose
+    #[cfg(feature = "ssr")]
+    pub fn from_request_parts(parts: &http::request::Parts, cfg: &RequestInfoConfig) -> Self {
+        let mut info = Self::default();
+        for (name, value) in &parts.headers {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            if name == http::header::COOKIE {
+                info.cookies.extend(
+                    parse_cookies(value).filter(|(cookie, _)| cfg.cookies.contains(cookie)),
+                );
+            }
+            if cfg.exposes_header(name.as_str()) {
+                info.headers.push((name.to_string(), value.to_string()));
+            }
+        }
+        info.locale = info
+            .header(http::header::ACCEPT_LANGUAGE.as_str())
+            .and_then(preferred_language);
+        info.user_agent = info
+            .header(http::header::USER_AGENT.as_str())
+            .map(str::to_string);
+        info
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_cookies(header: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    header.split(';').filter_map(|cookie| {
+        let (name, value) = cookie.trim().split_once('=')?;
+        Some((name.to_string(), value.trim_matches('"').to_string()))
+    })
+}
+
+// Find the language with the highest quality in an Accept-Language header like "fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5"
+#[cfg(feature = "ssr")]
+fn preferred_language(header: &str) -> Option<String> {
+    let mut preferred: Option<(&str, f32)> = None;
+    for language in header.split(',') {
+        let mut parts = language.split(';');
+        let tag = parts.next().unwrap_or_default().trim();
+        let quality = parts
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .map_or(Some(1.0), |quality| quality.parse().ok());
+        let Some(quality) = quality else {
+            continue;
+        };
+        if tag.is_empty() || tag == "*" || quality <= 0.0 {
+            continue;
+        }
+        match preferred {
+            Some((_, best)) if best >= quality => {}
+            _ => preferred = Some((tag, quality)),
+        }
+    }
+    preferred.map(|(tag, _)| tag.to_string())
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    fn request_parts() -> http::request::Parts {
+        http::Request::builder()
+            .header("User-Agent", "Mozilla/5.0")
+            .header("Accept-Language", "en;q=0.8, fr-CH, *;q=0.5")
+            .header("Cookie", "theme=dark; session=\"abc\"")
+            .header("Authorization", "Bearer secret")
+            .header("X-Forwarded-For", "10.0.0.1")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0
+    }
+
+    #[test]
+    fn reads_request_parts() {
+        let info = RequestInfo::from_request_parts(&request_parts(), &RequestInfoConfig::new());
+
+        assert_eq!(info.user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(info.locale.as_deref(), Some("fr-CH"));
+        assert_eq!(
+            info.header("accept-language"),
+            Some("en;q=0.8, fr-CH, *;q=0.5")
+        );
+        // cookies and sensitive headers are not exposed by default
+        assert!(info.cookies.is_empty());
+        assert_eq!(info.header("authorization"), None);
+        assert_eq!(info.header("cookie"), None);
+        assert_eq!(info.header("x-forwarded-for"), None);
+    }
+
+    #[test]
+    fn exposes_chosen_cookies_and_headers() {
+        let cfg = RequestInfoConfig::new()
+            .expose_cookie("theme")
+            .expose_header("X-Forwarded-For");
+        let info = RequestInfo::from_request_parts(&request_parts(), &cfg);
+
+        assert_eq!(info.cookie("theme"), Some("dark"));
+        assert_eq!(info.cookie("session"), None);
+        assert_eq!(info.header("x-forwarded-for"), Some("10.0.0.1"));
+        assert_eq!(info.header("authorization"), None);
+        assert_eq!(info.header("cookie"), None);
+    }
+
+    #[test]
+    fn ignores_invalid_languages() {
+        assert_eq!(preferred_language("*, de;q=0"), None);
+        assert_eq!(
+            preferred_language("es;q=abc, pt-BR;q=0.4").as_deref(),
+            Some("pt-BR")
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 //! Configeration for how to serve a Dioxus application
 
+use crate::bundle_manifest::BundleManifest;
 #[cfg(feature = "router")]
 use crate::router::*;
 use std::fs::File;
@@ -17,8 +18,15 @@ pub struct ServeConfigBuilder<P: Clone> {
     pub(crate) root_id: Option<&'static str>,
     pub(crate) index_path: Option<&'static str>,
     pub(crate) assets_path: Option<&'static str>,
+    pub(crate) base_path: Option<&'static str>,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) resources: std::sync::Arc<crate::server_context::ServerResources>,
+    pub(crate) bundle_manifest: Option<&'static str>,
+    pub(crate) critical_css: Option<&'static str>,
+    pub(crate) hydrate: bool,
+    pub(crate) lazy_hydration: bool,
+    pub(crate) request_info: crate::request_info::RequestInfoConfig,
 }
 
 /// A template for incremental rendering that does nothing.
@@ -62,10 +70,23 @@ impl<P: Clone> ServeConfigBuilder<P> {
             root_id: None,
             index_path: None,
             assets_path: None,
+            base_path: None,
             incremental: None,
+            resources: Default::default(),
+            bundle_manifest: None,
+            critical_css: None,
+            hydrate: true,
+            lazy_hydration: false,
+            request_info: Default::default(),
         }
     }
 
+    /// Set the resources that are shared with server functions and server side rendering, like database connection pools.
+    pub fn resources(mut self, resources: crate::server_context::ServerResources) -> Self {
+        self.resources = std::sync::Arc::new(resources);
+        self
+    }
+
     /// Enable incremental static generation
     pub fn incremental(mut self, cfg: dioxus_ssr::incremental::IncrementalRendererConfig) -> Self {
         self.incremental = Some(std::sync::Arc::new(cfg));
@@ -90,6 +111,42 @@ impl<P: Clone> ServeConfigBuilder<P> {
         self
     }
 
+    /// Set the path the app is served under, like the `base_path` in `Dioxus.toml`. Assets resolved with [`asset!`](crate::asset) start with it. (defaults to None)
+    pub fn base_path(mut self, base_path: &'static str) -> Self {
+        self.base_path = Some(base_path);
+        self
+    }
+
+    /// Set the path of the manifest that maps routes to the JS and WASM chunks they need. The chunks of the rendered route are preloaded in the head of the page. See [`BundleManifest`](crate::prelude::BundleManifest) for the format. (defaults to {assets_path}/bundle-manifest.json if it exists)
+    pub fn bundle_manifest(mut self, bundle_manifest: &'static str) -> Self {
+        self.bundle_manifest = Some(bundle_manifest);
+        self
+    }
+
+    /// Inline the rules of the stylesheet linked with `href` in the index.html that the rendered page uses in the head of the page, and load the full stylesheet without blocking the first paint. The stylesheet is read from the assets folder. (defaults to None)
+    pub fn critical_css(mut self, href: &'static str) -> Self {
+        self.critical_css = Some(href);
+        self
+    }
+
+    /// Set if the page should be rendered with the data the client needs to hydrate it. Disabling hydration renders the page the way a browser without JavaScript sees it, which is useful to test that forms degrade gracefully. See [`FormAudit`](crate::prelude::FormAudit). (defaults to true)
+    pub fn hydrate(mut self, hydrate: bool) -> Self {
+        self.hydrate = hydrate;
+        self
+    }
+
+    /// Experimental: write the events each element listens to into the page so the client can put off hydrating it until the user interacts with it. The client must be launched with `dioxus_web::Config::lazy_hydration` enabled. (defaults to false)
+    pub fn lazy_hydration(mut self, lazy_hydration: bool) -> Self {
+        self.lazy_hydration = lazy_hydration;
+        self
+    }
+
+    /// Choose the cookies and sensitive headers that are written into the [`RequestInfo`](crate::prelude::RequestInfo) of every page. (defaults to none)
+    pub fn request_info(mut self, request_info: crate::request_info::RequestInfoConfig) -> Self {
+        self.request_info = request_info;
+        self
+    }
+
     /// Build the ServeConfig
     pub fn build(self) -> ServeConfig<P> {
         let assets_path = self.assets_path.unwrap_or("dist");
@@ -103,12 +160,53 @@ impl<P: Clone> ServeConfigBuilder<P> {
 
         let index = load_index_html(index_path, root_id);
 
+        let bundle_manifest = match self.bundle_manifest {
+            Some(path) => Some(BundleManifest::from_file(path).unwrap_or_else(|err| {
+                panic!("Failed to read the bundle manifest at {path}: {err}")
+            })),
+            None => {
+                let path = PathBuf::from(format!("{assets_path}/bundle-manifest.json"));
+                path.exists()
+                    .then(|| BundleManifest::from_file(&path))
+                    .and_then(|manifest| {
+                        manifest
+                            .map_err(|err| {
+                                log::error!("Failed to read the bundle manifest at {path:?}: {err}")
+                            })
+                            .ok()
+                    })
+            }
+        };
+
+        let critical_css = self.critical_css.map(|href| {
+            let path = PathBuf::from(assets_path).join(href.trim_start_matches('/'));
+            let css = std::fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("Failed to read the stylesheet at {path:?}: {err}"));
+            std::sync::Arc::new(CriticalCss {
+                href,
+                stylesheet: dioxus_ssr::critical_css::Stylesheet::parse(&css),
+            })
+        });
+
+        // Resolve assets to their fingerprinted files while rendering
+        let asset_manifest = crate::asset_manifest::AssetManifest {
+            base_path: self.base_path.map(String::from),
+            ..crate::asset_manifest::read_asset_manifest(assets_path).unwrap_or_default()
+        };
+
         ServeConfig {
             app: self.app,
             props: self.props,
             index,
             assets_path,
             incremental: self.incremental,
+            resources: self.resources,
+            bundle_manifest: bundle_manifest.map(std::sync::Arc::new),
+            asset_manifest: std::sync::Arc::new(asset_manifest),
+            critical_css,
+            hydrate: self.hydrate,
+            lazy_hydration: self.lazy_hydration,
+            request_info: self.request_info,
         }
     }
 }
@@ -126,6 +224,8 @@ fn load_index_html(path: PathBuf, root_id: &'static str) -> IndexHtml {
         panic!("Failed to find closing > after id=\"{root_id}\" in index.html.")
     });
 
+    let head_end = pre_main.find("</head>");
+
     let (pre_main, post_main) = (
         pre_main.to_string() + &format!("id=\"{root_id}\"") + post_main.0 + ">",
         post_main.1.to_string(),
@@ -134,6 +234,7 @@ fn load_index_html(path: PathBuf, root_id: &'static str) -> IndexHtml {
     IndexHtml {
         pre_main,
         post_main,
+        head_end,
     }
 }
 
@@ -141,6 +242,15 @@ fn load_index_html(path: PathBuf, root_id: &'static str) -> IndexHtml {
 pub(crate) struct IndexHtml {
     pub(crate) pre_main: String,
     pub(crate) post_main: String,
+    // The byte offset of </head> in pre_main, if the index has a head
+    pub(crate) head_end: Option<usize>,
+}
+
+/// The stylesheet the critical CSS of rendered pages is extracted from
+pub(crate) struct CriticalCss {
+    // The href the stylesheet is linked with in the index.html
+    pub(crate) href: &'static str,
+    pub(crate) stylesheet: dioxus_ssr::critical_css::Stylesheet,
 }
 
 /// Used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
@@ -153,6 +263,13 @@ pub struct ServeConfig<P: Clone> {
     pub(crate) assets_path: &'static str,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) resources: std::sync::Arc<crate::server_context::ServerResources>,
+    pub(crate) bundle_manifest: Option<std::sync::Arc<BundleManifest>>,
+    pub(crate) asset_manifest: std::sync::Arc<crate::asset_manifest::AssetManifest>,
+    pub(crate) critical_css: Option<std::sync::Arc<CriticalCss>>,
+    pub(crate) hydrate: bool,
+    pub(crate) lazy_hydration: bool,
+    pub(crate) request_info: crate::request_info::RequestInfoConfig,
 }
 
 impl<P: Clone> From<ServeConfigBuilder<P>> for ServeConfig<P> {
@@ -15,6 +15,7 @@ pub struct DioxusServerContext {
     response_parts: std::sync::Arc<std::sync::RwLock<http::response::Parts>>,
     pub(crate) parts: Arc<RwLock<http::request::Parts>>,
     html_data: Arc<RwLock<HTMLData>>,
+    resources: Arc<ServerResources>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -27,10 +28,65 @@ impl Default for DioxusServerContext {
             )),
             parts: std::sync::Arc::new(RwLock::new(http::request::Request::new(()).into_parts().0)),
             html_data: Arc::new(RwLock::new(HTMLData::default())),
+            resources: Default::default(),
         }
     }
 }
 
+/// Resources that are shared between all requests, like database connection pools.
+///
+/// Resources are registered once when the server is set up, and can be retrieved inside server
+/// functions with the [`ServerContext`] extractor.
+///
+/// ```rust
+/// # use dioxus_fullstack::prelude::*;
+/// #[derive(Clone)]
+/// struct Pool;
+///
+/// let resources = ServerResources::new().with(Pool);
+/// let server_context = DioxusServerContext::default().with_resources(resources);
+/// assert!(server_context.get::<Pool>().is_some());
+/// ```
+pub struct ServerResources {
+    resources: anymap::Map<dyn anymap::any::Any + Send + Sync + 'static>,
+}
+
+impl Default for ServerResources {
+    fn default() -> Self {
+        Self {
+            resources: anymap::Map::new(),
+        }
+    }
+}
+
+impl ServerResources {
+    /// Create an empty set of resources
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a resource. Registering a second resource of the same type replaces the first one.
+    pub fn with<T: Send + Sync + 'static>(mut self, resource: T) -> Self {
+        self.resources.insert(resource);
+        self
+    }
+
+    /// Register a resource that [transactional server functions](dioxus_server_macro::server) begin their transactions on.
+    ///
+    /// The resource itself is registered as well, so it can still be retrieved with [`ServerContext`].
+    pub fn with_transactions<T: crate::transaction::TransactionSource + Clone>(
+        self,
+        source: T,
+    ) -> Self {
+        self.with(source.clone())
+            .with(crate::transaction::TransactionProvider::new(source))
+    }
+
+    fn get<T: Send + Sync + Clone + 'static>(&self) -> Option<T> {
+        self.resources.get::<T>().cloned()
+    }
+}
+
 mod server_fn_impl {
     use super::*;
     use std::sync::LockResult;
@@ -49,12 +105,22 @@ mod server_fn_impl {
                     http::response::Response::new(()).into_parts().0,
                 )),
                 html_data: Arc::new(RwLock::new(HTMLData::default())),
+                resources: Default::default(),
             }
         }
 
-        /// Clone a value from the shared server context
+        /// Share the given resources with the server functions that use this context
+        pub fn with_resources(mut self, resources: impl Into<Arc<ServerResources>>) -> Self {
+            self.resources = resources.into();
+            self
+        }
+
+        /// Clone a value from the shared server context, or from the [`ServerResources`] if it was not inserted into the context
         pub fn get<T: Any + Send + Sync + Clone + 'static>(&self) -> Option<T> {
-            self.shared_context.read().ok()?.get::<T>().cloned()
+            if let Some(value) = self.shared_context.read().ok()?.get::<T>() {
+                return Some(value.clone());
+            }
+            self.resources.get::<T>()
         }
 
         /// Insert a value into the shared server context
@@ -196,7 +262,7 @@ pub trait FromServerContext<I = ()>: Sized {
 }
 
 /// A type was not found in the server context
-pub struct NotFoundInServerContext<T: 'static>(std::marker::PhantomData<T>);
+pub struct NotFoundInServerContext<T: 'static>(pub(crate) std::marker::PhantomData<T>);
 
 impl<T: 'static> std::fmt::Debug for NotFoundInServerContext<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -214,14 +280,36 @@ impl<T: 'static> std::fmt::Display for NotFoundInServerContext<T> {
 
 impl<T: 'static> std::error::Error for NotFoundInServerContext<T> {}
 
-pub struct FromContext<T: std::marker::Send + std::marker::Sync + Clone + 'static>(pub(crate) T);
+/// An extractor for a value in the server context, like a resource registered with [`ServerResources`].
+///
+/// ```rust
+/// # use dioxus_fullstack::prelude::*;
+/// #[derive(Clone)]
+/// struct Pool;
+///
+/// #[server(CountUsers)]
+/// async fn count_users() -> Result<usize, ServerFnError> {
+///     let ServerContext(pool) = extract::<ServerContext<Pool>, _>().await?;
+///     Ok(0)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ServerContext<T: Send + Sync + Clone + 'static>(pub T);
+
+impl<T: Send + Sync + Clone + 'static> std::ops::Deref for ServerContext<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
 #[async_trait::async_trait(?Send)]
-impl<T: Send + Sync + Clone + 'static> FromServerContext for FromContext<T> {
+impl<T: Send + Sync + Clone + 'static> FromServerContext for ServerContext<T> {
     type Rejection = NotFoundInServerContext<T>;
 
     async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
-        Ok(Self(req.clone().get::<T>().ok_or_else(|| {
+        Ok(Self(req.get::<T>().ok_or_else(|| {
             NotFoundInServerContext::<T>(std::marker::PhantomData::<T>)
         })?))
     }
@@ -0,0 +1,170 @@
+//! Typed errors for server functions.
+//!
+//! Server functions return a [`ServerFnError`], which turns every error into a string. A [`ServerError`] is serialized
+//! into that string instead, so the client can get the original error back with [`ServerFnErrorExt::typed`].
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use server_fn::ServerFnError;
+
+// Marks the errors that hold a serialized typed error, so other errors are never mistaken for one
+const TYPED_ERROR_PREFIX: &str = "dioxus-typed-error:";
+
+/// An error that can be sent from a server function to the client without losing its type.
+///
+/// ```rust, ignore
+/// #[derive(Debug, Serialize, Deserialize)]
+/// enum AuthError {
+///     Unauthorized,
+///     Forbidden { resource: String },
+/// }
+///
+/// impl ServerError for AuthError {
+///     fn status_code(&self) -> u16 {
+///         match self {
+///             AuthError::Unauthorized => 401,
+///             AuthError::Forbidden { .. } => 403,
+///         }
+///     }
+/// }
+///
+/// #[server(GetSecrets)]
+/// async fn get_secrets() -> Result<Vec<String>, ServerFnError> {
+///     let user = current_user().await.ok_or(AuthError::Unauthorized.into_server_fn_error())?;
+///     Ok(user.secrets)
+/// }
+/// ```
+pub trait ServerError: Serialize + DeserializeOwned + std::fmt::Debug + 'static {
+    /// The HTTP status code of the error. Defaults to 500 (Internal Server Error).
+    fn status_code(&self) -> u16 {
+        500
+    }
+
+    /// Serialize the error into a [`ServerFnError`] that can be returned from a server function
+    fn into_server_fn_error(self) -> ServerFnError {
+        let typed = TypedError {
+            status: self.status_code(),
+            error: &self,
+        };
+        match serde_json::to_string(&typed) {
+            Ok(json) => ServerFnError::ServerError(format!("{TYPED_ERROR_PREFIX}{json}")),
+            Err(err) => ServerFnError::Serialization(err.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TypedError<E> {
+    status: u16,
+    error: E,
+}
+
+fn typed_error_json(error: &ServerFnError) -> Option<&str> {
+    match error {
+        ServerFnError::ServerError(message) => message.strip_prefix(TYPED_ERROR_PREFIX),
+        _ => None,
+    }
+}
+
+/// Read the typed errors of server functions out of a [`ServerFnError`]
+pub trait ServerFnErrorExt {
+    /// Get the typed error, if this error was created from an `E` with [`ServerError::into_server_fn_error`]
+    fn typed<E: ServerError>(&self) -> Option<E>;
+
+    /// Get the HTTP status code of the typed error, if this error was created from a [`ServerError`]
+    fn status_code(&self) -> Option<u16>;
+}
+
+impl ServerFnErrorExt for ServerFnError {
+    fn typed<E: ServerError>(&self) -> Option<E> {
+        let typed: TypedError<E> = serde_json::from_str(typed_error_json(self)?).ok()?;
+        Some(typed.error)
+    }
+
+    fn status_code(&self) -> Option<u16> {
+        let typed: TypedError<serde::de::IgnoredAny> =
+            serde_json::from_str(typed_error_json(self)?).ok()?;
+        Some(typed.status)
+    }
+}
+
+// The server fn client only reads errors from responses with a 5xx status, so it gets typed errors with a 4xx status
+// as a 500 and reads the real status from the error itself. Every other client gets the status of the error.
+#[cfg(feature = "ssr")]
+pub(crate) fn response_status(error: &ServerFnError, server_fn_client: bool) -> http::StatusCode {
+    let status = error
+        .status_code()
+        .and_then(|status| http::StatusCode::from_u16(status).ok())
+        .filter(|status| status.is_client_error() || status.is_server_error());
+    match status {
+        Some(status) if status.is_server_error() || !server_fn_client => status,
+        _ => http::StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum AuthError {
+        Unauthorized,
+        Forbidden { resource: String },
+    }
+
+    impl ServerError for AuthError {
+        fn status_code(&self) -> u16 {
+            match self {
+                AuthError::Unauthorized => 401,
+                AuthError::Forbidden { .. } => 403,
+            }
+        }
+    }
+
+    #[test]
+    fn typed_errors_round_trip() {
+        let error = AuthError::Forbidden {
+            resource: "secrets".to_string(),
+        }
+        .into_server_fn_error();
+
+        // The error is sent to the client as JSON, like every other server fn error
+        let error: ServerFnError =
+            serde_json::from_str(&serde_json::to_string(&error).unwrap()).unwrap();
+
+        assert_eq!(error.status_code(), Some(403));
+        assert_eq!(
+            error.typed::<AuthError>(),
+            Some(AuthError::Forbidden {
+                resource: "secrets".to_string()
+            })
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn client_errors_are_only_sent_to_other_clients() {
+        let error = AuthError::Unauthorized.into_server_fn_error();
+        assert_eq!(
+            response_status(&error, false),
+            http::StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            response_status(&error, true),
+            http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        let error = ServerFnError::ServerError("database is down".to_string());
+        assert_eq!(
+            response_status(&error, false),
+            http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn untyped_errors_have_no_status() {
+        let error = ServerFnError::ServerError("database is down".to_string());
+
+        assert_eq!(error.status_code(), None);
+        assert_eq!(error.typed::<AuthError>(), None);
+    }
+}
@@ -0,0 +1,163 @@
+//! Transactions for server functions marked with `#[server(transactional)]`.
+
+use std::sync::Arc;
+
+use server_fn::ServerFnError;
+
+use crate::server_context::{
+    server_context, DioxusServerContext, FromServerContext, NotFoundInServerContext,
+};
+
+/// A resource that transactional server functions can begin transactions on, like a database connection pool.
+///
+/// Register the source with [`ServerResources::with_transactions`](crate::prelude::ServerResources::with_transactions).
+/// Every server function marked with `#[server(transactional)]` begins a transaction before its body runs. The
+/// transaction is committed if the server function returns [`Ok`] and dropped otherwise.
+///
+/// ```rust, ignore
+/// #[derive(Clone)]
+/// struct Database(sqlx::PgPool);
+///
+/// #[async_trait::async_trait]
+/// impl TransactionSource for Database {
+///     type Transaction = sqlx::Transaction<'static, sqlx::Postgres>;
+///
+///     async fn begin(&self) -> Result<Self::Transaction, ServerFnError> {
+///         self.0.begin().await.map_err(|e| ServerFnError::ServerError(e.to_string()))
+///     }
+///
+///     async fn commit(transaction: Self::Transaction) -> Result<(), ServerFnError> {
+///         transaction.commit().await.map_err(|e| ServerFnError::ServerError(e.to_string()))
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait TransactionSource: Send + Sync + 'static {
+    /// The transaction type. Dropping a transaction without committing it must roll it back.
+    type Transaction: Send + 'static;
+
+    /// Begin a new transaction.
+    async fn begin(&self) -> Result<Self::Transaction, ServerFnError>;
+
+    /// Commit a transaction.
+    async fn commit(transaction: Self::Transaction) -> Result<(), ServerFnError>;
+}
+
+/// The transaction of the current transactional server function.
+///
+/// Extract it inside a server function marked with `#[server(transactional)]`:
+///
+/// ```rust, ignore
+/// #[server(AddUser, transactional)]
+/// async fn add_user(name: String) -> Result<(), ServerFnError> {
+///     let transaction: Transaction<sqlx::Transaction<'static, sqlx::Postgres>> = extract().await?;
+///     sqlx::query("INSERT INTO users (name) VALUES ($1)")
+///         .bind(name)
+///         .execute(&mut **transaction.lock().await)
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct Transaction<T>(Arc<tokio::sync::Mutex<Option<T>>>);
+
+impl<T> Clone for Transaction<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Transaction<T> {
+    /// Lock the transaction to run queries on it.
+    ///
+    /// # Panics
+    /// - When the transaction was already committed or rolled back, which can only happen if the lock is held after the server function returned.
+    pub async fn lock(&self) -> tokio::sync::MappedMutexGuard<'_, T> {
+        tokio::sync::MutexGuard::map(self.0.lock().await, |transaction| {
+            transaction
+                .as_mut()
+                .expect("the transaction was already committed or rolled back")
+        })
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<T: Send + 'static> FromServerContext for Transaction<T> {
+    type Rejection = NotFoundInServerContext<Transaction<T>>;
+
+    async fn from_request(req: &DioxusServerContext) -> Result<Self, Self::Rejection> {
+        req.get::<Self>()
+            .ok_or_else(|| NotFoundInServerContext(std::marker::PhantomData))
+    }
+}
+
+/// The type erased transaction source registered in the server resources.
+#[derive(Clone)]
+pub(crate) struct TransactionProvider(Arc<dyn ErasedTransactionSource>);
+
+impl TransactionProvider {
+    pub(crate) fn new(source: impl TransactionSource) -> Self {
+        Self(Arc::new(source))
+    }
+}
+
+#[async_trait::async_trait]
+trait ErasedTransactionSource: Send + Sync {
+    async fn begin(&self, context: &mut DioxusServerContext) -> Result<(), ServerFnError>;
+
+    async fn commit(&self, context: &DioxusServerContext) -> Result<(), ServerFnError>;
+
+    fn rollback(&self, context: &DioxusServerContext);
+}
+
+#[async_trait::async_trait]
+impl<S: TransactionSource> ErasedTransactionSource for S {
+    async fn begin(&self, context: &mut DioxusServerContext) -> Result<(), ServerFnError> {
+        let transaction = TransactionSource::begin(self).await?;
+        context
+            .insert(Transaction(Arc::new(tokio::sync::Mutex::new(Some(
+                transaction,
+            )))))
+            .map_err(|e| ServerFnError::ServerError(e.to_string()))
+    }
+
+    async fn commit(&self, context: &DioxusServerContext) -> Result<(), ServerFnError> {
+        let transaction = match context.get::<Transaction<S::Transaction>>() {
+            Some(transaction) => transaction.0.lock().await.take(),
+            None => None,
+        };
+        match transaction {
+            Some(transaction) => S::commit(transaction).await,
+            None => Ok(()),
+        }
+    }
+
+    fn rollback(&self, context: &DioxusServerContext) {
+        if let Some(transaction) = context.get::<Transaction<S::Transaction>>() {
+            if let Ok(mut transaction) = transaction.0.try_lock() {
+                // Dropping the transaction rolls it back
+                transaction.take();
+            }
+        }
+    }
+}
+
+/// Run the body of a transactional server function. This is used by the `#[server(transactional)]` macro.
+#[doc(hidden)]
+pub async fn run_transactional<T>(
+    body: impl std::future::Future<Output = Result<T, ServerFnError>>,
+) -> Result<T, ServerFnError> {
+    let mut context = server_context();
+    let provider = context.get::<TransactionProvider>().ok_or_else(|| {
+        ServerFnError::ServerError(
+            "No transaction source was registered. Register one with `ServerResources::with_transactions`".to_string(),
+        )
+    })?;
+
+    provider.0.begin(&mut context).await?;
+    let result = body.await;
+    match &result {
+        Ok(_) => provider.0.commit(&context).await?,
+        Err(_) => provider.0.rollback(&context),
+    }
+    result
+}
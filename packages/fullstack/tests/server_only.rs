@@ -0,0 +1,49 @@
+//! Verify that the body of a #[server_only] component is only compiled into server builds. Run the tests with and without
+//! the `ssr` feature.
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_fullstack::prelude::*;
+
+// Only exists on the server, so the client build fails to compile if the body of the component is not stripped
+#[cfg(feature = "ssr")]
+fn read_article() -> &'static str {
+    "server content"
+}
+
+#[server_only]
+fn Article(cx: Scope) -> Element {
+    let article = read_article();
+    render! { p { "{article}" } }
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        h1 { "title" }
+        Article {}
+    }
+}
+
+fn render() -> String {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    dioxus_ssr::render(&dom)
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn renders_the_body_on_the_server() {
+    assert_eq!(
+        render(),
+        r#"<h1>title</h1><div data-dioxus-server-only="true" style="display: contents"><p>server content</p></div>"#
+    );
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn strips_the_body_from_the_client() {
+    assert_eq!(
+        render(),
+        r#"<h1>title</h1><div data-dioxus-server-only="true" style="display: contents"></div>"#
+    );
+}
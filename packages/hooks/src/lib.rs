@@ -84,5 +84,11 @@ pub use usecallback::*;
 mod usememo;
 pub use usememo::*;
 
+mod use_async_memo;
+pub use use_async_memo::*;
+
 mod userootcontext;
 pub use userootcontext::*;
+
+mod use_undo_redo;
+pub use use_undo_redo::*;
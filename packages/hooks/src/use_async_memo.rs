@@ -0,0 +1,193 @@
+use dioxus_core::{ScopeState, TaskId};
+use std::{any::Any, cell::Cell, future::Future, rc::Rc, sync::Arc};
+
+use crate::{use_state, UseFutureDep, UseState};
+
+/// A hook that derives a value from an async computation, recomputing it only when the dependencies change.
+///
+/// Unlike [`use_future`](crate::use_future), the computation is memoized by its dependencies: if the dependencies
+/// change while the last computation is still running, that computation is cancelled and a new one is started. The
+/// computation returns a `Result`, and the hook reports whether the value is loading, ready or failed.
+///
+/// - dependencies: a tuple of references to values that are `PartialEq` + `Clone`
+///
+/// ## Examples
+///
+/// ```rust, ignore
+/// #[inline_props]
+/// fn User(cx: Scope, id: usize) -> Element {
+///     let user = use_async_memo(cx, (id,), |(id,)| async move { fetch_user(id).await });
+///
+///     match user.state() {
+///         AsyncMemoState::Loading => render!("Loading..."),
+///         AsyncMemoState::Ready(user) => render!("{user.name}"),
+///         AsyncMemoState::Error(err) => render!("Failed to load the user: {err}"),
+///     }
+/// }
+/// ```
+pub fn use_async_memo<T, E, F, D>(
+    cx: &ScopeState,
+    dependencies: D,
+    future: impl FnOnce(D::Out) -> F,
+) -> &UseAsyncMemo<T, E>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+    D: UseFutureDep,
+{
+    let val = use_state(cx, || None);
+
+    let state = cx.use_hook(move || UseAsyncMemo {
+        update: cx.schedule_update(),
+        needs_regen: Cell::new(true),
+        value: val.clone(),
+        task: Default::default(),
+        dependencies: Vec::new(),
+    });
+
+    if dependencies.clone().apply(&mut state.dependencies) || state.needs_regen.get() {
+        // cancel the computation for the old dependencies, if it is still running
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+
+        let fut = future(dependencies.out());
+        let val = val.clone();
+        let task = state.task.clone();
+
+        state.task.set(Some(cx.push_future(async move {
+            let result = fut.await;
+            task.take();
+            val.set(Some(result));
+        })));
+
+        state.needs_regen.set(false);
+    }
+
+    // update the current value
+    state.value.current_val = val.current_val.clone();
+
+    state
+}
+
+/// The state of a value derived with [`use_async_memo`].
+pub enum AsyncMemoState<'a, T, E> {
+    /// The value is being computed
+    Loading,
+    /// The value was computed for the current dependencies
+    Ready(&'a T),
+    /// Computing the value for the current dependencies failed
+    Error(&'a E),
+}
+
+/// A value derived from an async computation. Created with [`use_async_memo`].
+pub struct UseAsyncMemo<T: 'static, E: 'static> {
+    update: Arc<dyn Fn()>,
+    needs_regen: Cell<bool>,
+    task: Rc<Cell<Option<TaskId>>>,
+    dependencies: Vec<Box<dyn Any>>,
+    value: UseState<Option<Result<T, E>>>,
+}
+
+impl<T, E> UseAsyncMemo<T, E> {
+    /// Get the current state of the value.
+    ///
+    /// While the value is recomputed for new dependencies, the state is [`AsyncMemoState::Loading`].
+    pub fn state(&self) -> AsyncMemoState<T, E> {
+        if self.task.get().is_some() {
+            return AsyncMemoState::Loading;
+        }
+
+        match self.value.current_val.as_ref() {
+            Some(Ok(value)) => AsyncMemoState::Ready(value),
+            Some(Err(err)) => AsyncMemoState::Error(err),
+            None => AsyncMemoState::Loading,
+        }
+    }
+
+    /// Get the last value that was computed successfully, even if it was computed for old dependencies.
+    pub fn value(&self) -> Option<&T> {
+        match self.value.current_val.as_ref() {
+            Some(Ok(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Check if the value is being computed
+    pub fn is_loading(&self) -> bool {
+        matches!(self.state(), AsyncMemoState::Loading)
+    }
+
+    /// Compute the value again, even if the dependencies have not changed.
+    pub fn restart(&self) {
+        self.needs_regen.set(true);
+        (self.update)();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::prelude::*;
+    use futures_util::FutureExt;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static ID: Cell<usize> = const { Cell::new(0) };
+        static STATES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app(cx: Scope) -> Element {
+        let id = ID.with(Cell::get);
+        let user = use_async_memo(cx, (&id,), |(id,)| async move {
+            match id {
+                0 => Ok(format!("user {id}")),
+                _ => Err(format!("no user {id}")),
+            }
+        });
+
+        let state = match user.state() {
+            AsyncMemoState::Loading => "loading".to_string(),
+            AsyncMemoState::Ready(user) => format!("ready {user}"),
+            AsyncMemoState::Error(err) => format!("error {err}"),
+        };
+        STATES.with(|states| states.borrow_mut().push(state));
+
+        None
+    }
+
+    // Poll the computation and render the component with the result
+    fn finish_computation(dom: &mut VirtualDom) {
+        assert!(dom.wait_for_work().now_or_never().is_some());
+        _ = dom.render_immediate();
+    }
+
+    #[test]
+    fn recomputes_when_dependencies_change() {
+        let mut dom = VirtualDom::new(app);
+        _ = dom.rebuild();
+        finish_computation(&mut dom);
+
+        // rendering again with the same id uses the memoized value
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+
+        // the id changes, so the value is computed again and this time it fails
+        ID.with(|id| id.set(1));
+        dom.mark_dirty(ScopeId(0));
+        _ = dom.render_immediate();
+        finish_computation(&mut dom);
+
+        assert_eq!(
+            STATES.with(|states| states.take()),
+            [
+                "loading",
+                "ready user 0",
+                "ready user 0",
+                "loading",
+                "error no user 1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use dioxus_core::ScopeState;
+use std::{
+    cell::{Ref, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// The default number of undo steps kept by [`use_undo_redo`].
+pub const DEFAULT_UNDO_DEPTH: usize = 100;
+
+/// Store a value together with an undo/redo history of its previous values.
+///
+/// Every call to [`UseUndoRedo::set`] creates a new undo step. Rapid edits, like typing in a text
+/// field, can be merged into a single step with [`UseUndoRedo::set_coalesced`]. At most
+/// [`DEFAULT_UNDO_DEPTH`] steps are kept, use [`use_undo_redo_with_depth`] to change that.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app(cx: Scope) -> Element {
+///     let text = use_undo_redo(cx, String::new);
+///
+///     render! {
+///         input {
+///             value: "{text.read()}",
+///             oninput: move |evt| text.set_coalesced(evt.value.clone()),
+///             onblur: move |_| text.commit(),
+///         }
+///         button { disabled: !text.can_undo(), onclick: move |_| text.undo(), "Undo" }
+///         button { disabled: !text.can_redo(), onclick: move |_| text.redo(), "Redo" }
+///     }
+/// }
+/// ```
+pub fn use_undo_redo<T: 'static>(
+    cx: &ScopeState,
+    initial_state_fn: impl FnOnce() -> T,
+) -> &UseUndoRedo<T> {
+    use_undo_redo_with_depth(cx, DEFAULT_UNDO_DEPTH, initial_state_fn)
+}
+
+/// Like [`use_undo_redo`], but keep at most `max_depth` undo steps.
+pub fn use_undo_redo_with_depth<T: 'static>(
+    cx: &ScopeState,
+    max_depth: usize,
+    initial_state_fn: impl FnOnce() -> T,
+) -> &UseUndoRedo<T> {
+    cx.use_hook(|| UseUndoRedo {
+        update: cx.schedule_update(),
+        history: Rc::new(RefCell::new(History::new(initial_state_fn(), max_depth))),
+    })
+}
+
+/// A value with an undo/redo history. Created by [`use_undo_redo`].
+pub struct UseUndoRedo<T> {
+    update: Arc<dyn Fn()>,
+    history: Rc<RefCell<History<T>>>,
+}
+
+impl<T> Clone for UseUndoRedo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            update: self.update.clone(),
+            history: self.history.clone(),
+        }
+    }
+}
+
+impl<T> UseUndoRedo<T> {
+    /// Read the current value.
+    pub fn read(&self) -> Ref<'_, T> {
+        Ref::map(self.history.borrow(), |history| &history.present)
+    }
+
+    /// Set a new value, creating a new undo step.
+    ///
+    /// This clears the redo history.
+    pub fn set(&self, new: T) {
+        self.history.borrow_mut().push(new, false);
+        (self.update)();
+    }
+
+    /// Set a new value, merging it into the undo step of the previous coalesced edit.
+    ///
+    /// Consecutive coalesced edits are undone together. The group ends with the next call to
+    /// [`UseUndoRedo::set`], [`UseUndoRedo::commit`], [`UseUndoRedo::undo`] or
+    /// [`UseUndoRedo::redo`].
+    pub fn set_coalesced(&self, new: T) {
+        self.history.borrow_mut().push(new, true);
+        (self.update)();
+    }
+
+    /// End the current group of coalesced edits, so the next edit starts a new undo step.
+    pub fn commit(&self) {
+        self.history.borrow_mut().coalescing = false;
+    }
+
+    /// Restore the previous value. Does nothing if there is nothing to undo.
+    pub fn undo(&self) {
+        if self.history.borrow_mut().undo() {
+            (self.update)();
+        }
+    }
+
+    /// Restore the value that was last undone. Does nothing if there is nothing to redo.
+    pub fn redo(&self) {
+        if self.history.borrow_mut().redo() {
+            (self.update)();
+        }
+    }
+
+    /// Check whether there is a previous value to undo to.
+    pub fn can_undo(&self) -> bool {
+        !self.history.borrow().past.is_empty()
+    }
+
+    /// Check whether there is an undone value to redo to.
+    pub fn can_redo(&self) -> bool {
+        !self.history.borrow().future.is_empty()
+    }
+
+    /// Forget all undo and redo steps, keeping only the current value.
+    pub fn clear_history(&self) {
+        let mut history = self.history.borrow_mut();
+        history.past.clear();
+        history.future.clear();
+        history.coalescing = false;
+        drop(history);
+        (self.update)();
+    }
+}
+
+struct History<T> {
+    past: VecDeque<T>,
+    present: T,
+    future: Vec<T>,
+    coalescing: bool,
+    max_depth: usize,
+}
+
+impl<T> History<T> {
+    fn new(present: T, max_depth: usize) -> Self {
+        Self {
+            past: VecDeque::new(),
+            present,
+            future: Vec::new(),
+            coalescing: false,
+            max_depth,
+        }
+    }
+
+    fn push(&mut self, new: T, coalesce: bool) {
+        let old = std::mem::replace(&mut self.present, new);
+        self.future.clear();
+
+        // The value before the first coalesced edit is already stored as an undo step
+        if !(coalesce && self.coalescing) {
+            self.past.push_back(old);
+            if self.past.len() > self.max_depth {
+                self.past.pop_front();
+            }
+        }
+
+        self.coalescing = coalesce;
+    }
+
+    fn undo(&mut self) -> bool {
+        self.coalescing = false;
+        match self.past.pop_back() {
+            Some(previous) => {
+                let current = std::mem::replace(&mut self.present, previous);
+                self.future.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn redo(&mut self) -> bool {
+        self.coalescing = false;
+        match self.future.pop() {
+            Some(next) => {
+                let current = std::mem::replace(&mut self.present, next);
+                self.past.push_back(current);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn undo_redo_round_trips() {
+        let mut history = History::new(0, 10);
+        history.push(1, false);
+        history.push(2, false);
+
+        assert!(history.undo());
+        assert_eq!(history.present, 1);
+        assert!(history.undo());
+        assert_eq!(history.present, 0);
+        assert!(!history.undo());
+
+        assert!(history.redo());
+        assert_eq!(history.present, 1);
+
+        // a new edit clears the redo history
+        history.push(3, false);
+        assert!(!history.redo());
+        assert_eq!(history.present, 3);
+    }
+
+    #[test]
+    fn coalesced_edits_are_undone_together() {
+        let mut history = History::new(String::new(), 10);
+        history.push("h".to_string(), true);
+        history.push("he".to_string(), true);
+        history.push("hey".to_string(), true);
+        history.coalescing = false;
+        history.push("hey!".to_string(), true);
+
+        assert!(history.undo());
+        assert_eq!(history.present, "hey");
+        assert!(history.undo());
+        assert_eq!(history.present, "");
+        assert!(!history.undo());
+    }
+
+    #[test]
+    fn depth_is_limited() {
+        let mut history = History::new(0, 2);
+        for i in 1..=5 {
+            history.push(i, false);
+        }
+
+        assert!(history.undo());
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert_eq!(history.present, 3);
+    }
+}
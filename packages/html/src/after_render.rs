@@ -0,0 +1,41 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static AFTER_RENDER: RefCell<Vec<Box<dyn FnOnce()>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run a callback once the renderer applied the edits of the next render to the page.
+///
+/// Libraries use this to keep changes outside of the virtual dom in sync with the page. The router updates the URL of
+/// the browser and the scroll position once the new route is on the screen, so the user never sees the new URL with the
+/// old page. Renderers that don't run these callbacks never call them, so libraries that need the callback to run
+/// should have a fallback, like an animation frame.
+pub fn after_render(callback: impl FnOnce() + 'static) {
+    AFTER_RENDER.with(|queue| queue.borrow_mut().push(Box::new(callback)));
+}
+
+/// Run the callbacks queued with [`after_render`] in the order they were queued. Renderers call this after they
+/// applied edits to the page.
+pub fn run_after_render() {
+    // callbacks may queue more callbacks, those run after the next render
+    let callbacks = AFTER_RENDER.with(|queue| std::mem::take(&mut *queue.borrow_mut()));
+    for callback in callbacks {
+        callback();
+    }
+}
+
+#[test]
+fn runs_callbacks_in_order() {
+    use std::rc::Rc;
+
+    let ran = Rc::new(RefCell::new(Vec::new()));
+    for i in 0..3 {
+        let ran = ran.clone();
+        after_render(move || ran.borrow_mut().push(i));
+    }
+    assert!(ran.borrow().is_empty());
+    run_after_render();
+    assert_eq!(*ran.borrow(), [0, 1, 2]);
+    run_after_render();
+    assert_eq!(ran.borrow().len(), 3);
+}
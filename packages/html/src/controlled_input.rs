@@ -0,0 +1,138 @@
+use crate::events::{CompositionEvent, FocusEvent, FormEvent};
+use dioxus_core::ScopeState;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Create a text input that is controlled by a value in the component.
+///
+/// Controlled inputs can fall out of sync with the value in the component: a render with an older value can overwrite
+/// characters that were typed in the meantime, an IME composition can be interrupted by a render, and the browser can
+/// ignore a value that was rejected by the component. This hook handles all three:
+/// - The value is always the latest value reported by the input, even if the component has not rendered since.
+/// - Inputs during an IME composition are only committed once the composition ends.
+/// - When the input loses focus while it shows a different value than the component, the component rerenders so the
+///   input shows the value of the component again, and the [`ControlledInput::sync_key`] changes. Use it as the key of
+///   inputs that need to be recreated, like the inputs in a keyed list.
+///
+/// Returns the current value and the bindings for the input.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let (value, bind) = use_controlled_input(cx, String::new);
+///
+///     render! {
+///         input {
+///             key: "{bind.sync_key()}",
+///             value: "{value}",
+///             oninput: move |evt| bind.oninput(evt),
+///             onblur: move |evt| bind.onblur(evt),
+///             oncompositionstart: move |evt| bind.oncompositionstart(evt),
+///             oncompositionend: move |evt| bind.oncompositionend(evt),
+///         }
+///     }
+/// }
+/// ```
+pub fn use_controlled_input(
+    cx: &ScopeState,
+    initial_value: impl FnOnce() -> String,
+) -> (String, &ControlledInput) {
+    let bind = cx.use_hook(|| ControlledInput {
+        value: Rc::new(RefCell::new(initial_value())),
+        input_value: Default::default(),
+        composing: Default::default(),
+        sync_key: Default::default(),
+        update: cx.schedule_update(),
+    });
+    (bind.value(), bind)
+}
+
+/// The bindings of an input controlled with [`use_controlled_input`].
+#[derive(Clone)]
+pub struct ControlledInput {
+    value: Rc<RefCell<String>>,
+    // The value the input showed the last time it reported a value, if it differs from the value
+    input_value: Rc<RefCell<Option<String>>>,
+    composing: Rc<Cell<bool>>,
+    sync_key: Rc<Cell<usize>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl ControlledInput {
+    /// Get the current value.
+    pub fn value(&self) -> String {
+        self.value.borrow().clone()
+    }
+
+    /// Set the value. The input will show the new value after the next render.
+    pub fn set(&self, value: impl Into<String>) {
+        *self.value.borrow_mut() = value.into();
+        (self.update)();
+    }
+
+    /// Modify the value in place. The input will show the new value after the next render.
+    pub fn with_mut(&self, f: impl FnOnce(&mut String)) {
+        f(&mut self.value.borrow_mut());
+        (self.update)();
+    }
+
+    /// A key that changes whenever the input needs to be recreated to show the value of the component.
+    pub fn sync_key(&self) -> usize {
+        self.sync_key.get()
+    }
+
+    /// Handle the `oninput` event of the input.
+    pub fn oninput(&self, event: FormEvent) {
+        self.oninput_with(event, Some)
+    }
+
+    /// Handle the `oninput` event of the input, passing the new value through a filter first. If the filter returns
+    /// [`None`] the value is rejected, and the input shows the value of the component again once it loses focus.
+    ///
+    /// Values entered with an IME are not filtered.
+    pub fn oninput_with(&self, event: FormEvent, filter: impl FnOnce(String) -> Option<String>) {
+        let input_value = event.value.clone();
+        *self.input_value.borrow_mut() = Some(input_value.clone());
+        // Don't render in the middle of a composition, it would interrupt the IME
+        if self.composing.get() {
+            return;
+        }
+        if let Some(value) = filter(input_value) {
+            *self.value.borrow_mut() = value;
+            (self.update)();
+        }
+    }
+
+    /// Handle the `onblur` event of the input.
+    pub fn onblur(&self, _: FocusEvent) {
+        self.composing.set(false);
+        let input_value = self.input_value.borrow_mut().take();
+        if input_value.is_some_and(|input_value| input_value != *self.value.borrow()) {
+            self.sync_key.set(self.sync_key.get().wrapping_add(1));
+            (self.update)();
+        }
+    }
+
+    /// Handle the `oncompositionstart` event of the input.
+    pub fn oncompositionstart(&self, _: CompositionEvent) {
+        self.composing.set(true);
+    }
+
+    /// Handle the `oncompositionend` event of the input.
+    pub fn oncompositionend(&self, _: CompositionEvent) {
+        self.composing.set(false);
+        let input_value = self.input_value.borrow().clone();
+        if let Some(input_value) = input_value {
+            *self.value.borrow_mut() = input_value;
+            (self.update)();
+        }
+    }
+}
+
+impl PartialEq for ControlledInput {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.value, &other.value)
+    }
+}
@@ -271,6 +271,19 @@ macro_rules! builder_constructors {
             }
         }
 
+        /// The names of the elements, as written in rsx
+        pub(crate) const ELEMENT_NAMES: &[&str] = &[$(stringify!($name)),*];
+
+        /// The names of the attributes of an element as written in rsx, without the global and svg attributes
+        pub(crate) fn element_attribute_names(element: &str) -> &'static [&'static str] {
+            $(
+                if element == stringify!($name) {
+                    return &[$(stringify!($fil)),*];
+                }
+            )*
+            &[]
+        }
+
         $(
             impl_element!(
                 $(#[$attr])*
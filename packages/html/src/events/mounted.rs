@@ -1,6 +1,6 @@
 //! Handles querying data from the renderer
 
-use euclid::Rect;
+use euclid::{Rect, Size2D};
 
 use std::{
     any::Any,
@@ -38,6 +38,15 @@ pub trait RenderedElementBacking {
     fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         Box::pin(async { Err(MountedError::NotSupported) })
     }
+
+    /// Call `on_resize` with the size of the element when it starts being observed and every time the size changes,
+    /// until the returned [`ResizeObservation`] is dropped
+    fn observe_resize(
+        &self,
+        _on_resize: Box<dyn FnMut(Size2D<f64, f64>)>,
+    ) -> MountedResult<ResizeObservation> {
+        Err(MountedError::NotSupported)
+    }
 }
 
 impl RenderedElementBacking for () {}
@@ -90,6 +99,29 @@ impl MountedData {
     pub fn set_focus(&self, focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         self.inner.set_focus(focus)
     }
+
+    /// Call `on_resize` with the size of the element when it starts being observed and every time the size changes,
+    /// until the returned [`ResizeObservation`] is dropped
+    pub fn observe_resize(
+        &self,
+        on_resize: impl FnMut(Size2D<f64, f64>) + 'static,
+    ) -> MountedResult<ResizeObservation> {
+        self.inner.observe_resize(Box::new(on_resize))
+    }
+}
+
+/// Observes the size of an element until it is dropped. Created with [`MountedData::observe_resize`].
+pub struct ResizeObservation {
+    _guard: Box<dyn Any>,
+}
+
+impl ResizeObservation {
+    /// Create a new ResizeObservation. The renderer stops observing the element when `guard` is dropped.
+    pub fn new(guard: impl Any) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
 }
 
 use dioxus_core::Event;
@@ -0,0 +1,134 @@
+use crate::events::{KeyboardEvent, MountedData, MountedEvent};
+use dioxus_core::{prelude::spawn, EventTarget, ScopeState};
+use keyboard_types::Key;
+use std::{
+    cell::{Cell, RefCell, RefMut},
+    rc::Rc,
+};
+
+/// Keep the keyboard focus inside a dialog or menu while it is open.
+///
+/// While `open` is true:
+/// - The first focusable element of the trap is focused when it is mounted.
+/// - Tabbing past the last element moves the focus back to the first element, and shift-tabbing past the first element
+///   moves the focus to the last element. Place an element with `tabindex: 0` that calls [`FocusTrap::focus_last`]
+///   when it is focused before the focusable elements, and one that calls [`FocusTrap::focus_first`] after them.
+/// - Pressing Escape calls `on_close`.
+///
+/// Elements that unmount while the trap is open are dropped from the trap.
+///
+/// When `open` changes back to false, the focus is restored to the element that opened the trap.
+///
+/// The elements are focused with [`MountedData::set_focus`], so the trap works with every renderer that supports
+/// focusing elements.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let open = use_state(cx, || false);
+///     let trap = use_focus_trap(cx, **open, {
+///         to_owned![open];
+///         move || open.set(false)
+///     });
+///
+///     render! {
+///         button {
+///             onmounted: move |evt| trap.opener(evt),
+///             onclick: move |_| open.set(true),
+///             "Open"
+///         }
+///         if **open {
+///             rsx! {
+///                 div {
+///                     onkeydown: move |evt| trap.onkeydown(evt),
+///                     div { tabindex: 0, onfocus: move |_| trap.focus_last() }
+///                     input { onmounted: move |evt| trap.focusable(evt) }
+///                     button { onmounted: move |evt| trap.focusable(evt), onclick: move |_| open.set(false), "Close" }
+///                     div { tabindex: 0, onfocus: move |_| trap.focus_first() }
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_focus_trap(cx: &ScopeState, open: bool, on_close: impl FnMut() + 'static) -> &FocusTrap {
+    let trap = cx.use_hook(|| FocusTrap {
+        open: Default::default(),
+        opener: Default::default(),
+        focusable: Default::default(),
+        on_close: Rc::new(RefCell::new(Box::new(|| {}))),
+    });
+    *trap.on_close.borrow_mut() = Box::new(on_close);
+
+    if trap.open.replace(open) != open {
+        // The elements of the last time the trap was open are unmounted
+        trap.focusable.borrow_mut().clear();
+        if !open {
+            if let Some(opener) = trap.opener.borrow().clone() {
+                focus(opener);
+            }
+        }
+    }
+
+    trap
+}
+
+/// The bindings of a focus trap created with [`use_focus_trap`].
+#[derive(Clone)]
+pub struct FocusTrap {
+    open: Rc<Cell<bool>>,
+    opener: Rc<RefCell<Option<Rc<MountedData>>>>,
+    // The focusable elements in the trap, in tab order
+    focusable: Rc<RefCell<Vec<(EventTarget, Rc<MountedData>)>>>,
+    on_close: Rc<RefCell<Box<dyn FnMut()>>>,
+}
+
+impl FocusTrap {
+    /// Set the element that opens the trap. The focus is restored to it when the trap closes. Use it as the `onmounted` handler of the element.
+    pub fn opener(&self, event: MountedEvent) {
+        *self.opener.borrow_mut() = Some(event.inner().clone());
+    }
+
+    /// Add a focusable element to the trap. Elements are expected to be mounted in tab order. Use it as the `onmounted` handler of the element.
+    pub fn focusable(&self, event: MountedEvent) {
+        let mut focusable = self.mounted();
+        focusable.push((event.target().clone(), event.inner().clone()));
+        if focusable.len() == 1 && self.open.get() {
+            focus(event.inner().clone());
+        }
+    }
+
+    /// Move the focus to the first element of the trap
+    pub fn focus_first(&self) {
+        if let Some((_, first)) = self.mounted().first().cloned() {
+            focus(first);
+        }
+    }
+
+    /// Move the focus to the last element of the trap
+    pub fn focus_last(&self) {
+        if let Some((_, last)) = self.mounted().last().cloned() {
+            focus(last);
+        }
+    }
+
+    /// Get the focusable elements, without the ones that were unmounted since they were added
+    fn mounted(&self) -> RefMut<'_, Vec<(EventTarget, Rc<MountedData>)>> {
+        let mut focusable = self.focusable.borrow_mut();
+        focusable.retain(|(target, _)| target.is_mounted());
+        focusable
+    }
+
+    /// Close the trap when Escape is pressed. Use it as the `onkeydown` handler of the element that contains the trap.
+    pub fn onkeydown(&self, event: KeyboardEvent) {
+        if self.open.get() && event.key() == Key::Escape {
+            (self.on_close.borrow_mut())();
+        }
+    }
+}
+
+fn focus(element: Rc<MountedData>) {
+    // Renderers that can't focus elements return an error, there is nothing else the trap can do for them
+    spawn(async move {
+        _ = element.set_focus(true).await;
+    });
+}
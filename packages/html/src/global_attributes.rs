@@ -39,6 +39,7 @@ macro_rules! trait_methods {
         $(#[$trait_attr:meta])*
         $trait:ident;
         $fn:ident;
+        $names:ident;
         $(
             $(#[$attr:meta])*
             $name:ident $(: $($arg:literal),*)*;
@@ -52,6 +53,9 @@ macro_rules! trait_methods {
             )*
         }
 
+        /// The names of the attributes, as written in rsx
+        pub(crate) const $names: &[&str] = &[$(stringify!($name)),*];
+
         #[cfg(feature = "hot-reload-context")]
         pub(crate) fn $fn(attr: &str) -> Option<(&'static str, Option<&'static str>)> {
             $(
@@ -79,6 +83,7 @@ trait_methods! {
 
     GlobalAttributes;
     map_global_attributes;
+    GLOBAL_ATTRIBUTE_NAMES;
 
     /// Prevent the default action for this element.
     ///
@@ -1590,6 +1595,7 @@ trait_methods! {
     @base
     SvgAttributes;
     map_svg_attributes;
+    SVG_ATTRIBUTE_NAMES;
 
     /// Prevent the default action for this element.
     ///
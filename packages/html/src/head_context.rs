@@ -0,0 +1,285 @@
+use crate::prelude::EvalProvider;
+use dioxus_core::ScopeState;
+use std::{
+    fmt::Write,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+/// Set the title of the document. The title is updated whenever `title` changes, and goes back to the title the
+/// document had before once the component is dropped.
+///
+/// When rendering on the server, the title is collected in the [`HeadContext`] and written to the head of the page, so
+/// the server rendered page already has the same title the client sets once it hydrates.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let unread = use_state(cx, || 0);
+///     use_title(cx, format!("Inbox ({unread})"));
+///
+///     render! { button { onclick: move |_| unread += 1, "New message" } }
+/// }
+/// ```
+pub fn use_title(cx: &ScopeState, title: impl ToString) {
+    use_head(cx, HeadField::Title, title.to_string());
+}
+
+/// Set the icon of the document to the image at `href`. The icon is updated whenever `href` changes, and goes back to
+/// the icon the document had before once the component is dropped.
+///
+/// When rendering on the server, the icon is collected in the [`HeadContext`] and linked from the head of the page.
+pub fn use_favicon(cx: &ScopeState, href: impl ToString) {
+    use_head(cx, HeadField::Favicon, href.to_string());
+}
+
+/// Set the content of the `<meta>` tag with the given `name`, like a `description` of the page. The tag is created if
+/// it does not exist, and updated whenever `content` changes. Once the component is dropped, the tag goes back to the
+/// content it had before, or is removed if it didn't exist.
+///
+/// When rendering on the server, the tag is collected in the [`HeadContext`] and written to the head of the page.
+pub fn use_meta(cx: &ScopeState, name: &'static str, content: impl ToString) {
+    use_head(cx, HeadField::Meta(name), content.to_string());
+}
+
+// Update the head whenever the value changes: in the head context if the app is rendered on the server, or with
+// JavaScript if the renderer can evaluate it. Renderers without a document, like the TUI, ignore the value.
+fn use_head(cx: &ScopeState, field: HeadField, value: String) {
+    let hook = cx.use_hook(|| HeadHook {
+        field,
+        id: NEXT_HEAD_HOOK.fetch_add(1, Ordering::Relaxed),
+        last: None,
+        head: cx.consume_context::<HeadContext>(),
+        eval_provider: cx.consume_context::<Rc<dyn EvalProvider>>(),
+    });
+    if hook.last.as_ref() == Some(&value) {
+        return;
+    }
+
+    if let Some(head) = &hook.head {
+        hook.field.set(head, value.clone());
+    } else if let Some(eval_provider) = &hook.eval_provider {
+        let mut script = String::new();
+        if hook.last.is_none() {
+            // Remember the value of the document to restore it once the hook is dropped
+            let _ = writeln!(
+                script,
+                "window.__dioxusHead ??= {{}}; window.__dioxusHead[{}] = {};",
+                hook.id,
+                hook.field.current_js()
+            );
+        }
+        script.push_str(&hook.field.apply_js(&js_string(&value)));
+        // The script doesn't return anything, so there is nothing to wait for
+        let _ = eval_provider.new_evaluator(script);
+    }
+    hook.last = Some(value);
+}
+
+// Every hook gets its own slot to remember the value of the document in
+static NEXT_HEAD_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+struct HeadHook {
+    field: HeadField,
+    id: usize,
+    // The value this hook set last
+    last: Option<String>,
+    head: Option<HeadContext>,
+    eval_provider: Option<Rc<dyn EvalProvider>>,
+}
+
+// The head context is read once the app is rendered, often after the VirtualDom is dropped, so only the document goes
+// back to its previous value
+impl Drop for HeadHook {
+    fn drop(&mut self) {
+        if self.head.is_some() || self.last.is_none() {
+            return;
+        }
+        if let Some(eval_provider) = &self.eval_provider {
+            let script = format!(
+                "let previous = window.__dioxusHead[{id}]; delete window.__dioxusHead[{id}];\n{}",
+                self.field.apply_js("previous"),
+                id = self.id
+            );
+            let _ = eval_provider.new_evaluator(script);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum HeadField {
+    Title,
+    Favicon,
+    Meta(&'static str),
+}
+
+impl HeadField {
+    fn set(&self, head: &HeadContext, value: String) {
+        match self {
+            Self::Title => head.set_title(value),
+            Self::Favicon => head.set_favicon(value),
+            Self::Meta(name) => head.set_meta(name, value),
+        }
+    }
+
+    // An expression with the value of the document, or null if the element doesn't exist
+    fn current_js(&self) -> String {
+        match self {
+            Self::Title => "document.title".to_string(),
+            Self::Favicon => {
+                r#"document.querySelector("link[rel~='icon']")?.getAttribute("href") ?? null"#
+                    .to_string()
+            }
+            Self::Meta(name) => format!(
+                "document.querySelector(`meta[name=${{CSS.escape({})}}]`)?.content ?? null",
+                js_string(name)
+            ),
+        }
+    }
+
+    // Set the value of the document to the value of the expression, or remove the element if it is null
+    fn apply_js(&self, value: &str) -> String {
+        match self {
+            Self::Title => format!(r#"document.title = {value} ?? "";"#),
+            Self::Favicon => format!(
+                r#"let value = {value};
+let link = document.querySelector("link[rel~='icon']");
+if (value === null) {{
+    link?.remove();
+}} else {{
+    if (!link) {{
+        link = document.createElement("link");
+        link.rel = "icon";
+        document.head.appendChild(link);
+    }}
+    link.href = value;
+}}"#
+            ),
+            Self::Meta(name) => format!(
+                r#"let value = {value};
+let meta = document.querySelector(`meta[name=${{CSS.escape({name})}}]`);
+if (value === null) {{
+    meta?.remove();
+}} else {{
+    if (!meta) {{
+        meta = document.createElement("meta");
+        meta.name = {name};
+        document.head.appendChild(meta);
+    }}
+    meta.content = value;
+}}"#,
+                name = js_string(name),
+            ),
+        }
+    }
+}
+
+fn js_string(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+/// The metadata of the document collected while rendering on the server.
+///
+/// Server renderers provide a `HeadContext` at the root of the app and write it to the head of the page with
+/// [`HeadContext::render`] once the app is rendered. [`use_title`], [`use_favicon`] and [`use_meta`] record their values
+/// here instead of changing the document.
+#[derive(Debug, Clone, Default)]
+pub struct HeadContext {
+    inner: Arc<RwLock<HeadData>>,
+}
+
+#[derive(Debug, Default)]
+struct HeadData {
+    title: Option<String>,
+    favicon: Option<String>,
+    meta: Vec<(&'static str, String)>,
+}
+
+impl HeadContext {
+    /// Create an empty head context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the title of the document, if one was set
+    pub fn title(&self) -> Option<String> {
+        self.inner.read().unwrap().title.clone()
+    }
+
+    /// Set the title of the document
+    pub fn set_title(&self, title: impl Into<String>) {
+        self.inner.write().unwrap().title = Some(title.into());
+    }
+
+    /// Get the href of the icon of the document, if one was set
+    pub fn favicon(&self) -> Option<String> {
+        self.inner.read().unwrap().favicon.clone()
+    }
+
+    /// Set the href of the icon of the document
+    pub fn set_favicon(&self, href: impl Into<String>) {
+        self.inner.write().unwrap().favicon = Some(href.into());
+    }
+
+    /// Get the content of the `<meta>` tag with the given name, if one was set
+    pub fn meta(&self, name: &str) -> Option<String> {
+        let inner = self.inner.read().unwrap();
+        inner
+            .meta
+            .iter()
+            .find(|(meta_name, _)| *meta_name == name)
+            .map(|(_, content)| content.clone())
+    }
+
+    /// Set the content of the `<meta>` tag with the given name
+    pub fn set_meta(&self, name: &'static str, content: impl Into<String>) {
+        let content = content.into();
+        let mut inner = self.inner.write().unwrap();
+        match inner
+            .meta
+            .iter_mut()
+            .find(|(meta_name, _)| *meta_name == name)
+        {
+            Some((_, old)) => *old = content,
+            None => inner.meta.push((name, content)),
+        }
+    }
+
+    /// Render the collected metadata as the tags that go in the head of the page
+    pub fn render(&self) -> String {
+        let inner = self.inner.read().unwrap();
+        let mut html = String::new();
+        if let Some(title) = &inner.title {
+            let _ = write!(html, "<title>{}</title>", escape_html(title));
+        }
+        if let Some(favicon) = &inner.favicon {
+            let _ = write!(html, r#"<link rel="icon" href="{}">"#, escape_html(favicon));
+        }
+        for (name, content) in &inner.meta {
+            let _ = write!(
+                html,
+                r#"<meta name="{}" content="{}">"#,
+                escape_html(name),
+                escape_html(content)
+            );
+        }
+        html
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,16 @@
+/// When the content of a component becomes interactive after the client hydrated a server rendered page.
+///
+/// Components provide the priority of their content as a context. Routes declare it with `#[meta(hydration = "...")]`
+/// when deriving `Routable`, and outlets provide the priority of the route they render. The rest of the page is
+/// interactive right away. Events the user triggers on content that isn't interactive yet are replayed once it is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HydrationPriority {
+    /// Make the content interactive as soon as the page is hydrated. Use this for content the user interacts with
+    /// right away.
+    #[default]
+    Critical,
+    /// Make the content interactive once it is scrolled into view.
+    Visible,
+    /// Make the content interactive once the browser is idle. Use this for content that is mostly static.
+    Idle,
+}
@@ -0,0 +1,150 @@
+use crate::prelude::{EvalProvider, UseEval};
+use dioxus_core::{ScopeState, TaskId};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// Detect when the user has been inactive for `timeout`. Returns true while the user is idle, and the component is
+/// rerendered when the user becomes idle or active again.
+///
+/// Moving or pressing the pointer, pressing keys, scrolling and touching the screen count as activity. Renderers that
+/// can evaluate JavaScript listen for these events in the document. Other renderers, like the TUI, report the activity
+/// of the user with an [`IdleProvider`]. Renderers without any input, like the server side renderer, are never idle.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let idle = use_idle(cx, Duration::from_secs(15 * 60));
+///
+///     if idle {
+///         render! { "You were logged out because you were inactive" }
+///     } else {
+///         render! { Dashboard {} }
+///     }
+/// }
+/// ```
+pub fn use_idle(cx: &ScopeState, timeout: Duration) -> bool {
+    let state = cx.use_hook(|| IdleState {
+        idle: Rc::new(Cell::new(false)),
+        timeout: Cell::new(None),
+        watch: RefCell::new(None),
+    });
+
+    if state.timeout.replace(Some(timeout)) != Some(timeout) {
+        if let Some(Watch::Script { task, .. }) = state.watch.borrow_mut().take() {
+            cx.remove_future(task);
+        }
+        state.idle.set(false);
+        *state.watch.borrow_mut() = watch(cx, timeout, state.idle.clone());
+    }
+
+    state.idle.get()
+}
+
+struct IdleState {
+    idle: Rc<Cell<bool>>,
+    timeout: Cell<Option<Duration>>,
+    watch: RefCell<Option<Watch>>,
+}
+
+enum Watch {
+    // the watches stop when they are dropped
+    Provider { _watch: IdleWatch },
+    Script { _script: StopOnDrop, task: TaskId },
+}
+
+fn watch(cx: &ScopeState, timeout: Duration, idle: Rc<Cell<bool>>) -> Option<Watch> {
+    let update = cx.schedule_update();
+    let on_change = move |is_idle: bool| {
+        if idle.replace(is_idle) != is_idle {
+            update();
+        }
+    };
+
+    if let Some(provider) = cx.consume_context::<Rc<dyn IdleProvider>>() {
+        return Some(Watch::Provider {
+            _watch: provider.watch(timeout, Box::new(on_change)),
+        });
+    }
+
+    let eval_provider = cx.consume_context::<Rc<dyn EvalProvider>>()?;
+    let script = IDLE_SCRIPT.replace("{TIMEOUT}", &timeout.as_millis().to_string());
+    let eval = UseEval::new(eval_provider.new_evaluator(script).ok()?);
+    let task = cx.push_future({
+        let eval = eval.clone();
+        async move {
+            while let Ok(value) = eval.recv().await {
+                if let Some(is_idle) = value.as_bool() {
+                    on_change(is_idle);
+                }
+            }
+        }
+    });
+
+    Some(Watch::Script {
+        _script: StopOnDrop(eval),
+        task,
+    })
+}
+
+// Sends true once the user was inactive for the timeout, and false once the user is active again. The listeners are
+// removed once any message is received.
+const IDLE_SCRIPT: &str = r#"
+const events = ["pointermove", "pointerdown", "keydown", "wheel", "touchstart", "scroll"];
+let idle = false;
+let timer;
+const active = () => {
+    if (idle) {
+        idle = false;
+        dioxus.send(false);
+    }
+    clearTimeout(timer);
+    timer = setTimeout(() => {
+        idle = true;
+        dioxus.send(true);
+    }, {TIMEOUT});
+};
+for (const event of events) {
+    window.addEventListener(event, active, { capture: true, passive: true });
+}
+active();
+
+await dioxus.recv();
+clearTimeout(timer);
+for (const event of events) {
+    window.removeEventListener(event, active, { capture: true });
+}
+"#;
+
+/// Stops the idle script when the timeout changes or the component is unmounted
+struct StopOnDrop(UseEval);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.send(serde_json::Value::Null);
+    }
+}
+
+/// Renderers that can't evaluate JavaScript provide an `IdleProvider` as a root context of type
+/// `Rc<dyn IdleProvider>` to let [`use_idle`] know when the user is active.
+pub trait IdleProvider {
+    /// Call `on_change` with true once the user was inactive for `timeout`, and with false once the user is active
+    /// again, until the returned [`IdleWatch`] is dropped.
+    fn watch(&self, timeout: Duration, on_change: Box<dyn FnMut(bool)>) -> IdleWatch;
+}
+
+/// Watches the activity of the user until it is dropped. Created with [`IdleProvider::watch`].
+pub struct IdleWatch {
+    _guard: Box<dyn Any>,
+}
+
+impl IdleWatch {
+    /// Create a new IdleWatch. The renderer stops watching the activity of the user when `guard` is dropped.
+    pub fn new(guard: impl Any) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
+}
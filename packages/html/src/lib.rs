@@ -20,6 +20,7 @@ pub mod events;
 pub mod geometry;
 mod global_attributes;
 pub mod input_data;
+pub mod names;
 #[cfg(feature = "native-bind")]
 pub mod native_bind;
 mod render_template;
@@ -37,9 +38,23 @@ pub use events::*;
 pub use global_attributes::*;
 pub use render_template::*;
 
+mod after_render;
+mod controlled_input;
 mod eval;
+mod focus_trap;
+mod head_context;
+mod hydration;
+mod idle;
+mod resize_observer;
 
 pub mod prelude {
+    pub use crate::after_render::*;
+    pub use crate::controlled_input::*;
     pub use crate::eval::*;
     pub use crate::events::*;
+    pub use crate::focus_trap::*;
+    pub use crate::head_context::*;
+    pub use crate::hydration::*;
+    pub use crate::idle::*;
+    pub use crate::resize_observer::*;
 }
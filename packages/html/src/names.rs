@@ -0,0 +1,21 @@
+//! The names of the elements and attributes of this crate, as written in rsx. The rsx macros suggest them when an
+//! element or attribute is misspelled.
+
+use crate::{
+    elements::{element_attribute_names, ELEMENT_NAMES},
+    global_attributes::{GLOBAL_ATTRIBUTE_NAMES, SVG_ATTRIBUTE_NAMES},
+};
+
+/// The names of every element
+pub fn elements() -> &'static [&'static str] {
+    ELEMENT_NAMES
+}
+
+/// The names of the attributes of an element, including the global and svg attributes
+pub fn attributes(element: &str) -> impl Iterator<Item = &'static str> + Clone {
+    element_attribute_names(element)
+        .iter()
+        .chain(GLOBAL_ATTRIBUTE_NAMES)
+        .chain(SVG_ATTRIBUTE_NAMES)
+        .copied()
+}
@@ -0,0 +1,124 @@
+use crate::events::{MountedEvent, ResizeObservation};
+use dioxus_core::{prelude::spawn, ScopeState};
+use euclid::Size2D;
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    hash::Hash,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Track the size of a dynamic set of elements, for example the items of a grid or masonry layout.
+///
+/// Every element is registered with an id by using [`ResizeObserver::observe`] as its `onmounted` handler. The
+/// component is rerendered every time the size of an observed element changes, and the sizes are read by id with
+/// [`ResizeObserver::get`].
+///
+/// The web, desktop and liveview renderers observe the elements with the native ResizeObserver of the browser. Other
+/// renderers measure each element once when it is mounted with [`crate::MountedData::get_client_rect`].
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let items = use_state(cx, || vec![1, 2, 3]);
+///     let sizes = use_resize_observer(cx);
+///     // forget the sizes of items that were removed
+///     sizes.retain(|id| items.contains(id));
+///
+///     render! {
+///         div {
+///             display: "flex",
+///             flex_wrap: "wrap",
+///             for item in items.iter().copied() {
+///                 div {
+///                     key: "{item}",
+///                     onmounted: move |evt| sizes.observe(item, evt),
+///                     "Item {item} is {sizes.get(&item).unwrap_or_default():?}"
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_resize_observer<K: Clone + Eq + Hash + 'static>(cx: &ScopeState) -> &ResizeObserver<K> {
+    cx.use_hook(|| ResizeObserver {
+        sizes: Default::default(),
+        observations: Default::default(),
+        update: cx.schedule_update(),
+    })
+}
+
+/// The sizes of the elements observed with a [`use_resize_observer`] hook.
+pub struct ResizeObserver<K> {
+    sizes: Rc<RefCell<HashMap<K, Size2D<f64, f64>>>>,
+    observations: Rc<RefCell<HashMap<K, Option<ResizeObservation>>>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl<K> Clone for ResizeObserver<K> {
+    fn clone(&self) -> Self {
+        Self {
+            sizes: self.sizes.clone(),
+            observations: self.observations.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash + 'static> ResizeObserver<K> {
+    /// Observe the size of an element with the given id. Use it as the `onmounted` handler of the element.
+    ///
+    /// Observing another element with the same id stops observing the previous element.
+    pub fn observe(&self, id: K, event: MountedEvent) {
+        let set_size = {
+            let sizes = self.sizes.clone();
+            let update = self.update.clone();
+            let id = id.clone();
+            move |size: Size2D<f64, f64>| {
+                if sizes.borrow_mut().insert(id.clone(), size) != Some(size) {
+                    update();
+                }
+            }
+        };
+
+        let observation = match event.inner().observe_resize(set_size.clone()) {
+            Ok(observation) => Some(observation),
+            Err(_) => {
+                // The renderer can't observe the element, so measure it once instead
+                let element = event.inner().clone();
+                spawn(async move {
+                    if let Ok(rect) = element.get_client_rect().await {
+                        set_size(rect.size);
+                    }
+                });
+                None
+            }
+        };
+        self.observations.borrow_mut().insert(id, observation);
+    }
+
+    /// Stop observing the element with the given id and forget its size
+    pub fn unobserve(&self, id: &K) {
+        self.observations.borrow_mut().remove(id);
+        self.sizes.borrow_mut().remove(id);
+    }
+
+    /// Only keep observing the elements with ids that match the predicate
+    pub fn retain(&self, mut keep: impl FnMut(&K) -> bool) {
+        self.observations.borrow_mut().retain(|id, _| keep(id));
+        let observations = self.observations.borrow();
+        self.sizes
+            .borrow_mut()
+            .retain(|id, _| observations.contains_key(id));
+    }
+
+    /// The last size of the element with the given id, if it was measured
+    pub fn get(&self, id: &K) -> Option<Size2D<f64, f64>> {
+        self.sizes.borrow().get(id).copied()
+    }
+
+    /// The last sizes of all the measured elements
+    pub fn sizes(&self) -> Ref<'_, HashMap<K, Size2D<f64, f64>>> {
+        self.sizes.borrow()
+    }
+}
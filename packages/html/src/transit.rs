@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct HtmlEvent {
     pub element: ElementId,
+    /// The generation of the element's id when the listener was created, if the renderer tracks it
+    pub generation: Option<u32>,
     pub name: String,
     pub bubbles: bool,
     pub data: EventData,
@@ -20,6 +22,8 @@ impl<'de> Deserialize<'de> for HtmlEvent {
         #[derive(Deserialize, Debug, Clone)]
         struct Inner {
             element: ElementId,
+            #[serde(default)]
+            generation: Option<u32>,
             name: String,
             bubbles: bool,
             data: serde_value::Value,
@@ -27,6 +31,7 @@ impl<'de> Deserialize<'de> for HtmlEvent {
 
         let Inner {
             element,
+            generation,
             name,
             bubbles,
             data,
@@ -35,6 +40,7 @@ impl<'de> Deserialize<'de> for HtmlEvent {
         Ok(HtmlEvent {
             data: fun_name(&name, data).unwrap(),
             element,
+            generation,
             bubbles,
             name,
         })
@@ -189,6 +195,7 @@ impl EventData {
 fn test_back_and_forth() {
     let data = HtmlEvent {
         element: ElementId(0),
+        generation: None,
         data: EventData::Mouse(MouseData::default()),
         name: "click".to_string(),
         bubbles: true,
@@ -249,6 +249,13 @@ impl crate::RenderedElementBacking for web_sys::Element {
             });
         Box::pin(async { result })
     }
+
+    fn observe_resize(
+        &self,
+        on_resize: Box<dyn FnMut(euclid::Size2D<f64, f64>)>,
+    ) -> crate::MountedResult<crate::ResizeObservation> {
+        Ok(super::resize_observer::observe_resize(self, on_resize))
+    }
 }
 
 #[derive(Debug)]
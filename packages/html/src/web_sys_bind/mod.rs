@@ -1 +1,3 @@
-mod events;
+mod events;
+#[cfg(feature = "mounted")]
+mod resize_observer;
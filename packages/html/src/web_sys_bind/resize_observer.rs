@@ -0,0 +1,126 @@
+//! Every element observed with [`crate::MountedData::observe_resize`] shares one browser ResizeObserver
+
+use std::{cell::RefCell, rc::Rc};
+
+use euclid::Size2D;
+use wasm_bindgen::prelude::*;
+
+use crate::ResizeObservation;
+
+// The ResizeObserver bindings of web-sys are unstable in the versions we support
+#[wasm_bindgen]
+extern "C" {
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor)]
+    fn new(callback: &JsValue) -> ResizeObserver;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ResizeObserver, target: &web_sys::Element);
+
+    #[wasm_bindgen(method)]
+    fn unobserve(this: &ResizeObserver, target: &web_sys::Element);
+
+    type ResizeObserverEntries;
+
+    #[wasm_bindgen(method, getter)]
+    fn length(this: &ResizeObserverEntries) -> u32;
+
+    #[wasm_bindgen(method, indexing_getter)]
+    fn get(this: &ResizeObserverEntries, index: u32) -> ResizeObserverEntry;
+
+    type ResizeObserverEntry;
+
+    #[wasm_bindgen(method, getter)]
+    fn target(this: &ResizeObserverEntry) -> web_sys::Element;
+}
+
+struct Observed {
+    id: usize,
+    element: web_sys::Element,
+    on_resize: Box<dyn FnMut(Size2D<f64, f64>)>,
+}
+
+struct SharedObserver {
+    observer: ResizeObserver,
+    observed: Rc<RefCell<Vec<Observed>>>,
+    next_id: usize,
+    _callback: Closure<dyn FnMut(ResizeObserverEntries)>,
+}
+
+impl SharedObserver {
+    fn new() -> Self {
+        let observed: Rc<RefCell<Vec<Observed>>> = Default::default();
+        let callback = Closure::<dyn FnMut(ResizeObserverEntries)>::new({
+            let observed = observed.clone();
+            move |entries: ResizeObserverEntries| {
+                let mut observed = observed.borrow_mut();
+                for index in 0..entries.length() {
+                    let target = entries.get(index).target();
+                    // the size includes the border and padding, like the rect of get_client_rect
+                    let rect = target.get_bounding_client_rect();
+                    let size = Size2D::new(rect.width(), rect.height());
+                    for observed in observed.iter_mut().filter(|o| o.element == target) {
+                        (observed.on_resize)(size);
+                    }
+                }
+            }
+        });
+
+        Self {
+            observer: ResizeObserver::new(callback.as_ref()),
+            observed,
+            next_id: 0,
+            _callback: callback,
+        }
+    }
+}
+
+thread_local! {
+    static OBSERVER: RefCell<Option<SharedObserver>> = const { RefCell::new(None) };
+}
+
+/// Stops observing the element when it is dropped
+struct Unobserve {
+    id: usize,
+    element: web_sys::Element,
+}
+
+impl Drop for Unobserve {
+    fn drop(&mut self) {
+        OBSERVER.with(|observer| {
+            if let Some(shared) = &*observer.borrow() {
+                let mut observed = shared.observed.borrow_mut();
+                observed.retain(|o| o.id != self.id);
+                // other observations of the same element still need the browser to observe it
+                if !observed.iter().any(|o| o.element == self.element) {
+                    shared.observer.unobserve(&self.element);
+                }
+            }
+        });
+    }
+}
+
+pub(crate) fn observe_resize(
+    element: &web_sys::Element,
+    on_resize: Box<dyn FnMut(Size2D<f64, f64>)>,
+) -> ResizeObservation {
+    OBSERVER.with(|observer| {
+        let mut observer = observer.borrow_mut();
+        let shared = observer.get_or_insert_with(SharedObserver::new);
+        let id = shared.next_id;
+        shared.next_id += 1;
+        shared.observed.borrow_mut().push(Observed {
+            id,
+            element: element.clone(),
+            on_resize,
+        });
+        // the browser calls the callback with the current size after the element starts being observed
+        shared.observer.observe(element);
+
+        ResizeObservation::new(Unobserve {
+            id,
+            element: element.clone(),
+        })
+    })
+}
@@ -0,0 +1,188 @@
+//! Verify that use_controlled_input keeps the value of the component in sync with the value of the input.
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use dioxus::{
+    core::{BorrowedAttributeValue, ElementId, Mutation},
+    html::{CompositionData, FocusData, FormData},
+    prelude::*,
+};
+
+thread_local! {
+    // The value and the sync key of the last render
+    static RENDERED: RefCell<(String, usize)> = RefCell::new(Default::default());
+}
+
+fn rendered() -> (String, usize) {
+    RENDERED.with(|rendered| rendered.borrow().clone())
+}
+
+fn app(cx: Scope) -> Element {
+    let (value, bind) = use_controlled_input(cx, String::new);
+    RENDERED.with(|rendered| *rendered.borrow_mut() = (value.clone(), bind.sync_key()));
+
+    render! {
+        input {
+            key: "{bind.sync_key()}",
+            value: "{value}",
+            oninput: move |evt| bind.oninput(evt),
+            onblur: move |evt| bind.onblur(evt),
+            oncompositionstart: move |evt| bind.oncompositionstart(evt),
+            oncompositionend: move |evt| bind.oncompositionend(evt),
+        }
+    }
+}
+
+// Only accepts digits
+fn digits(cx: Scope) -> Element {
+    let (value, bind) = use_controlled_input(cx, String::new);
+    RENDERED.with(|rendered| *rendered.borrow_mut() = (value.clone(), bind.sync_key()));
+
+    render! {
+        input {
+            key: "{bind.sync_key()}",
+            value: "{value}",
+            oninput: move |evt| bind.oninput_with(evt, |value| {
+                value.chars().all(|c| c.is_ascii_digit()).then_some(value)
+            }),
+            onblur: move |evt| bind.onblur(evt),
+            oncompositionstart: move |evt| bind.oncompositionstart(evt),
+            oncompositionend: move |evt| bind.oncompositionend(evt),
+        }
+    }
+}
+
+/// The input of the app
+struct Input {
+    dom: VirtualDom,
+    id: ElementId,
+}
+
+impl Input {
+    fn new(app: fn(Scope) -> Element) -> Self {
+        let mut dom = VirtualDom::new(app);
+        let id = dom
+            .rebuild()
+            .edits
+            .iter()
+            .find_map(|edit| match edit {
+                Mutation::NewEventListener {
+                    name: "input", id, ..
+                } => Some(*id),
+                _ => None,
+            })
+            .unwrap();
+        Self { dom, id }
+    }
+
+    fn event(&mut self, name: &'static str, data: Rc<dyn Any>) {
+        self.dom.handle_event(name, data, self.id, true);
+    }
+
+    /// Render the app, returning the value that was written to the input
+    fn render(&mut self) -> Option<String> {
+        self.dom
+            .render_immediate()
+            .edits
+            .iter()
+            .find_map(|edit| match edit {
+                Mutation::SetAttribute {
+                    name: "value",
+                    value: BorrowedAttributeValue::Text(value),
+                    ..
+                } => Some(value.to_string()),
+                _ => None,
+            })
+    }
+
+    fn input(&mut self, value: &str) {
+        let data = FormData {
+            value: value.to_string(),
+            values: Default::default(),
+            files: None,
+        };
+        self.event("input", Rc::new(data));
+    }
+
+    fn blur(&mut self) {
+        self.event("blur", Rc::new(FocusData {}));
+    }
+
+    fn composition(&mut self, name: &'static str, data: &str) {
+        let data = CompositionData {
+            data: data.to_string(),
+        };
+        self.event(name, Rc::new(data));
+    }
+}
+
+#[test]
+fn keeps_the_latest_value_of_the_input() {
+    let mut input = Input::new(app);
+
+    input.input("a");
+    _ = input.render();
+    assert_eq!(rendered(), ("a".to_string(), 0));
+
+    // Inputs between renders are not lost
+    input.input("ab");
+    input.input("abc");
+    _ = input.render();
+    assert_eq!(rendered(), ("abc".to_string(), 0));
+
+    input.blur();
+    _ = input.render();
+    assert_eq!(rendered(), ("abc".to_string(), 0));
+}
+
+#[test]
+fn commits_compositions_when_they_end() {
+    let mut input = Input::new(app);
+
+    input.composition("compositionstart", "");
+    input.input("k");
+    input.input("か");
+    _ = input.render();
+    assert_eq!(rendered(), (String::new(), 0));
+
+    input.composition("compositionend", "か");
+    _ = input.render();
+    assert_eq!(rendered(), ("か".to_string(), 0));
+}
+
+#[test]
+fn recreates_inputs_that_show_a_rejected_value() {
+    let mut input = Input::new(digits);
+
+    input.input("1");
+    _ = input.render();
+    assert_eq!(rendered(), ("1".to_string(), 0));
+
+    // The rejected value stays in the input until it loses focus
+    input.input("1a");
+    _ = input.render();
+    assert_eq!(rendered(), ("1".to_string(), 0));
+
+    // Once it loses focus, the input shows the value of the component again
+    input.blur();
+    assert_eq!(input.render().as_deref(), Some("1"));
+    assert_eq!(rendered(), ("1".to_string(), 1));
+
+    // An input that shows the value of the component is left alone
+    input.input("12");
+    _ = input.render();
+    input.blur();
+    assert_eq!(input.render(), None);
+    assert_eq!(rendered(), ("12".to_string(), 1));
+}
+
+#[test]
+fn does_not_filter_compositions() {
+    let mut input = Input::new(digits);
+
+    input.composition("compositionstart", "");
+    input.input("か");
+    input.composition("compositionend", "か");
+    _ = input.render();
+    assert_eq!(rendered(), ("か".to_string(), 0));
+}
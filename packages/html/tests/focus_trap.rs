@@ -0,0 +1,225 @@
+//! Verify that use_focus_trap moves the focus between the elements of the trap, and back to the opener once it closes.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+use dioxus::{
+    core::{ElementId, Mutation, Mutations},
+    html::{
+        input_data::keyboard_types::{Code, Key, Location, Modifiers},
+        FocusData, KeyboardData, MountedData, MountedResult, RenderedElementBacking,
+    },
+    prelude::*,
+};
+
+thread_local! {
+    static OPEN: Cell<bool> = const { Cell::new(false) };
+    static CLOSED: Cell<usize> = const { Cell::new(0) };
+    static SHOW_BUTTON: Cell<bool> = const { Cell::new(true) };
+    // The names of the elements that were focused, in order
+    static FOCUSED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app(cx: Scope) -> Element {
+    let open = OPEN.with(Cell::get);
+    let trap = use_focus_trap(cx, open, || {
+        CLOSED.with(|closed| closed.set(closed.get() + 1))
+    });
+
+    render! {
+        button { onmounted: move |evt| trap.opener(evt) }
+        if open {
+            rsx! {
+                div {
+                    onkeydown: move |evt| trap.onkeydown(evt),
+                    div { tabindex: 0, onfocus: move |_| trap.focus_last() }
+                    input { onmounted: move |evt| trap.focusable(evt) }
+                    button { onmounted: move |evt| trap.focusable(evt) }
+                    div { tabindex: 0, onfocus: move |_| trap.focus_first() }
+                }
+            }
+        }
+    }
+}
+
+/// An open trap with an element that can be unmounted
+fn app_with_optional_button(cx: Scope) -> Element {
+    let trap = use_focus_trap(cx, true, || {});
+    let show_button = SHOW_BUTTON.with(Cell::get);
+
+    render! {
+        div {
+            onkeydown: move |evt| trap.onkeydown(evt),
+            div { tabindex: 0, onfocus: move |_| trap.focus_last() }
+            input { onmounted: move |evt| trap.focusable(evt) }
+            if show_button {
+                rsx! { button { onmounted: move |evt| trap.focusable(evt) } }
+            }
+            div { tabindex: 0, onfocus: move |_| trap.focus_first() }
+        }
+    }
+}
+
+/// An element that records when it is focused
+struct FakeElement(&'static str);
+
+impl RenderedElementBacking for FakeElement {
+    fn get_raw_element(&self) -> MountedResult<&dyn Any> {
+        Ok(self)
+    }
+
+    fn set_focus(&self, focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        if focus {
+            FOCUSED.with(|focused| focused.borrow_mut().push(self.0));
+        }
+        Box::pin(async { Ok(()) })
+    }
+}
+
+fn focused() -> Vec<&'static str> {
+    FOCUSED.with(|focused| focused.borrow().clone())
+}
+
+fn listeners(mutations: &Mutations, event: &str) -> Vec<ElementId> {
+    mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener { name, id, .. } if *name == event => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The elements of the open trap
+struct Trap {
+    keydown: ElementId,
+    // The elements that move the focus to the last and first element of the trap
+    focus_last: ElementId,
+    focus_first: ElementId,
+}
+
+/// Render the app, and mount the new elements with the given names in order
+async fn render(dom: &mut VirtualDom, names: &[&'static str]) -> Option<Trap> {
+    let (mounted, keydown, focus) = {
+        let mutations = dom.render_immediate();
+        (
+            listeners(&mutations, "mounted"),
+            listeners(&mutations, "keydown"),
+            listeners(&mutations, "focus"),
+        )
+    };
+    assert_eq!(mounted.len(), names.len());
+    for (id, name) in mounted.into_iter().zip(names) {
+        dom.handle_event(
+            "mounted",
+            Rc::new(MountedData::new(FakeElement(name))),
+            id,
+            false,
+        );
+    }
+    // The elements are focused by tasks
+    _ = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work()).await;
+
+    keydown.first().map(|&keydown| Trap {
+        keydown,
+        focus_last: focus[0],
+        focus_first: focus[1],
+    })
+}
+
+fn set_open(dom: &mut VirtualDom, open: bool) {
+    OPEN.with(|o| o.set(open));
+    dom.mark_dirty(ScopeId(0));
+}
+
+fn press(dom: &mut VirtualDom, trap: &Trap, key: Key) {
+    let data = KeyboardData::new(
+        key,
+        Code::Unidentified,
+        Location::Standard,
+        false,
+        Modifiers::empty(),
+    );
+    dom.handle_event("keydown", Rc::new(data), trap.keydown, true);
+}
+
+#[tokio::test]
+async fn traps_and_restores_the_focus() {
+    let mut dom = VirtualDom::new(app);
+    let mounted = listeners(&dom.rebuild(), "mounted");
+    dom.handle_event(
+        "mounted",
+        Rc::new(MountedData::new(FakeElement("opener"))),
+        mounted[0],
+        false,
+    );
+
+    // The first element is focused when the trap opens
+    set_open(&mut dom, true);
+    let trap = render(&mut dom, &["input", "button"]).await.unwrap();
+    assert_eq!(focused(), ["input"]);
+
+    // Tabbing past the last element moves the focus to the first element and the other way around
+    dom.handle_event("focus", Rc::new(FocusData {}), trap.focus_first, false);
+    dom.handle_event("focus", Rc::new(FocusData {}), trap.focus_last, false);
+    _ = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work()).await;
+    assert_eq!(focused(), ["input", "input", "button"]);
+
+    // Only escape closes the trap
+    press(&mut dom, &trap, Key::Character("a".to_string()));
+    assert_eq!(CLOSED.with(Cell::get), 0);
+    press(&mut dom, &trap, Key::Escape);
+    assert_eq!(CLOSED.with(Cell::get), 1);
+
+    // The focus goes back to the opener once the trap closes
+    set_open(&mut dom, false);
+    assert!(render(&mut dom, &[]).await.is_none());
+    assert_eq!(focused().last(), Some(&"opener"));
+
+    // The elements of the last time the trap was open are forgotten
+    set_open(&mut dom, true);
+    let trap = render(&mut dom, &["new input", "new button"])
+        .await
+        .unwrap();
+    assert_eq!(focused().last(), Some(&"new input"));
+    dom.handle_event("focus", Rc::new(FocusData {}), trap.focus_last, false);
+    _ = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work()).await;
+    assert_eq!(focused().last(), Some(&"new button"));
+}
+
+#[tokio::test]
+async fn forgets_elements_that_unmount() {
+    let mut dom = VirtualDom::new(app_with_optional_button);
+    let (mounted, focus) = {
+        let mutations = dom.rebuild();
+        (
+            listeners(&mutations, "mounted"),
+            listeners(&mutations, "focus"),
+        )
+    };
+    for (id, name) in mounted.into_iter().zip(["input", "button"]) {
+        dom.handle_event(
+            "mounted",
+            Rc::new(MountedData::new(FakeElement(name))),
+            id,
+            false,
+        );
+    }
+    _ = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work()).await;
+    assert_eq!(focused(), ["input"]);
+
+    // Once the button unmounts, shift-tabbing past the first element wraps around to the input
+    SHOW_BUTTON.with(|show| show.set(false));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    dom.handle_event("focus", Rc::new(FocusData {}), focus[0], false);
+    _ = tokio::time::timeout(Duration::from_millis(50), dom.wait_for_work()).await;
+    assert_eq!(focused(), ["input", "input"]);
+}
@@ -0,0 +1,179 @@
+//! Verify that use_resize_observer tracks the sizes of the observed elements and stops observing the elements that
+//! are removed, with and without a native observer.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+use dioxus::{
+    core::{ElementId, Mutation},
+    html::{
+        geometry::euclid::{Point2D, Rect, Size2D},
+        MountedData, MountedError, MountedResult, RenderedElementBacking, ResizeObservation,
+    },
+    prelude::*,
+};
+
+type OnResize = Box<dyn FnMut(Size2D<f64, f64>)>;
+
+thread_local! {
+    static IDS: RefCell<Vec<usize>> = RefCell::new(vec![1, 2]);
+    static SIZES: RefCell<Option<ResizeObserver<usize>>> = const { RefCell::new(None) };
+    static RENDERS: Cell<usize> = const { Cell::new(0) };
+    // The callbacks of the elements the fake renderer is observing
+    static OBSERVING: RefCell<HashMap<usize, OnResize>> = RefCell::new(HashMap::new());
+}
+
+fn app(cx: Scope) -> Element {
+    RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let sizes = use_resize_observer(cx);
+    SIZES.with(|s| *s.borrow_mut() = Some(sizes.clone()));
+    let ids = IDS.with(|ids| ids.borrow().clone());
+    sizes.retain(|id| ids.contains(id));
+
+    render! {
+        for id in ids {
+            div {
+                key: "{id}",
+                onmounted: move |evt| sizes.observe(id, evt),
+            }
+        }
+    }
+}
+
+/// An element of a renderer that may or may not have a native resize observer
+struct FakeElement {
+    id: usize,
+    native: bool,
+}
+
+impl FakeElement {
+    fn size(&self) -> Size2D<f64, f64> {
+        Size2D::new(self.id as f64 * 10.0, 5.0)
+    }
+}
+
+impl RenderedElementBacking for FakeElement {
+    fn get_raw_element(&self) -> MountedResult<&dyn Any> {
+        Ok(self)
+    }
+
+    fn get_client_rect(&self) -> Pin<Box<dyn Future<Output = MountedResult<Rect<f64, f64>>>>> {
+        let rect = Rect::new(Point2D::zero(), self.size());
+        Box::pin(async move { Ok(rect) })
+    }
+
+    fn observe_resize(&self, mut on_resize: OnResize) -> MountedResult<ResizeObservation> {
+        if !self.native {
+            return Err(MountedError::NotSupported);
+        }
+        on_resize(self.size());
+        OBSERVING.with(|observing| observing.borrow_mut().insert(self.id, on_resize));
+        Ok(ResizeObservation::new(StopObserving(self.id)))
+    }
+}
+
+struct StopObserving(usize);
+
+impl Drop for StopObserving {
+    fn drop(&mut self) {
+        OBSERVING.with(|observing| observing.borrow_mut().remove(&self.0));
+    }
+}
+
+fn sizes() -> ResizeObserver<usize> {
+    SIZES.with(|sizes| sizes.borrow().clone().unwrap())
+}
+
+fn observing() -> Vec<usize> {
+    let mut ids: Vec<_> = OBSERVING.with(|observing| observing.borrow().keys().copied().collect());
+    ids.sort();
+    ids
+}
+
+fn resize(id: usize, size: Size2D<f64, f64>) {
+    let mut on_resize = OBSERVING.with(|observing| observing.borrow_mut().remove(&id).unwrap());
+    on_resize(size);
+    OBSERVING.with(|observing| observing.borrow_mut().insert(id, on_resize));
+}
+
+/// Mount the elements of the app, in the order of their ids
+fn mount(dom: &mut VirtualDom, native: bool) {
+    let mounted: Vec<ElementId> = dom
+        .rebuild()
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::NewEventListener {
+                name: "mounted",
+                id,
+                ..
+            } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    for (element, id) in mounted.into_iter().zip([1, 2]) {
+        let data = Rc::new(MountedData::new(FakeElement { id, native }));
+        dom.handle_event("mounted", data, element, false);
+    }
+}
+
+#[test]
+fn tracks_sizes_with_a_native_observer() {
+    let mut dom = VirtualDom::new(app);
+    mount(&mut dom, true);
+
+    assert_eq!(observing(), [1, 2]);
+    assert_eq!(sizes().get(&1), Some(Size2D::new(10.0, 5.0)));
+    assert_eq!(sizes().get(&2), Some(Size2D::new(20.0, 5.0)));
+
+    // A new size rerenders the component, the same size doesn't
+    _ = dom.render_immediate();
+    let renders = RENDERS.with(Cell::get);
+    resize(1, Size2D::new(30.0, 5.0));
+    _ = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), renders + 1);
+    assert_eq!(sizes().get(&1), Some(Size2D::new(30.0, 5.0)));
+    resize(1, Size2D::new(30.0, 5.0));
+    _ = dom.render_immediate();
+    assert_eq!(RENDERS.with(Cell::get), renders + 1);
+
+    // Retaining the ids of the remaining elements stops observing the removed elements and forgets their size
+    IDS.with(|ids| ids.borrow_mut().retain(|id| *id != 2));
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+    assert_eq!(observing(), [1]);
+    assert_eq!(sizes().get(&2), None);
+    assert_eq!(sizes().sizes().len(), 1);
+
+    sizes().unobserve(&1);
+    assert!(observing().is_empty());
+    assert_eq!(sizes().get(&1), None);
+}
+
+#[tokio::test]
+async fn measures_elements_once_without_a_native_observer() {
+    let mut dom = VirtualDom::new(app);
+    mount(&mut dom, false);
+    assert!(observing().is_empty());
+
+    // Each element is measured by a task
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while sizes().sizes().len() < 2 {
+            dom.wait_for_work().await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(sizes().get(&1), Some(Size2D::new(10.0, 5.0)));
+    assert_eq!(sizes().get(&2), Some(Size2D::new(20.0, 5.0)));
+
+    sizes().unobserve(&2);
+    assert_eq!(sizes().get(&2), None);
+}
@@ -28,8 +28,116 @@ const bool_attrs = {
   webkitdirectory: true,
 };
 
+// Attributes that navigate to or load a url
+const url_attrs = {
+  action: true,
+  formaction: true,
+  href: true,
+  src: true,
+  "xlink:href": true,
+};
+
+let trustedSinks = null;
+
+// Check if loading the url runs script, like `javascript:alert(1)`. The url is parsed by the browser, so it is read the
+// same way the browser reads it when it is loaded.
+function isScriptURL(value) {
+  try {
+    return new URL(value, document.baseURI).protocol === "javascript:";
+  } catch {
+    return false;
+  }
+}
+
+// Find a sanitizer for html: the one the app passed in, or DOMPurify if the page loaded it. Returns null if there is
+// none. The interpreter does not sanitize html itself.
+function findSanitizer(sanitize) {
+  if (sanitize !== null && sanitize !== undefined) {
+    return sanitize;
+  }
+  if (window.DOMPurify !== undefined && window.DOMPurify.isSupported) {
+    return (html) => window.DOMPurify.sanitize(html);
+  }
+  return null;
+}
+
+// Create the Trusted Types policy the interpreter uses to write to dangerous sinks like innerHTML.
+// See https://developer.mozilla.org/en-US/docs/Web/API/Trusted_Types_API
+//
+// Html is passed through `sanitize`, or DOMPurify if the page loaded it. Without either, `dangerous_inner_html` is
+// written with the Sanitizer API (`Element.setHTML`) where the browser supports it, and rejected everywhere else.
+// Script urls must come from the same origin as the page. `javascript:` urls are never written to url attributes, even
+// if the browser does not support Trusted Types. Everything that is rejected or blocked, and Trusted Types violations,
+// are passed to `report` as `(sink, sample)`.
+//
+// Returns the sinks, so other interpreters can share the policy. Browsers only let a page create a policy with the
+// same name once.
+export function createTrustedSinks(policyName, sanitize, report) {
+  sanitize = findSanitizer(sanitize);
+  const sanitizeHTML = (value) => {
+    if (sanitize === null) {
+      report("html", value);
+      return "";
+    }
+    return sanitize(value);
+  };
+  let policy = null;
+  if (window.trustedTypes !== undefined) {
+    policy = window.trustedTypes.createPolicy(policyName, {
+      createHTML: sanitizeHTML,
+      createScriptURL: (value) => {
+        const url = new URL(value, document.baseURI);
+        if (url.origin !== window.location.origin) {
+          report("script src", value);
+          return "about:blank";
+        }
+        return url.href;
+      },
+    });
+  }
+  const trustedHTML = (value) =>
+    policy === null ? sanitizeHTML(value) : policy.createHTML(value);
+  document.addEventListener("securitypolicyviolation", (event) => {
+    if (
+      event.violatedDirective === "require-trusted-types-for" ||
+      event.violatedDirective === "trusted-types"
+    ) {
+      report(event.violatedDirective, event.sample);
+    }
+  });
+  trustedSinks = {
+    // Convert the value of an attribute into the type its sink accepts
+    attribute(node, name, value) {
+      if (url_attrs.hasOwnProperty(name) && isScriptURL(value)) {
+        report(name, value);
+        return "about:blank";
+      }
+      if (name === "srcdoc" && node.tagName === "IFRAME") {
+        return trustedHTML(value);
+      }
+      if (name === "src" && node.tagName === "SCRIPT" && policy !== null) {
+        return policy.createScriptURL(value);
+      }
+      return value;
+    },
+    // Write the inner html of an element
+    html(node, value) {
+      if (sanitize === null && typeof node.setHTML === "function") {
+        // The Sanitizer API removes everything that could run script
+        node.setHTML(value);
+      } else {
+        node.innerHTML = trustedHTML(value);
+      }
+    },
+  };
+  return trustedSinks;
+}
+
 export function setAttributeInner(node, field, value, ns) {
   const name = field;
+  if (trustedSinks !== null && ns !== "style" && name !== "dangerous_inner_html") {
+    value = trustedSinks.attribute(node, name, value);
+  }
   if (ns === "style") {
     // ????? why do we need to do this
     if (node.style === undefined) {
@@ -41,6 +149,15 @@ export function setAttributeInner(node, field, value, ns) {
   } else {
     switch (name) {
       case "value":
+        // Writing the value while an IME composition is in progress cancels the composition.
+        // The input event fired when the composition ends will sync the value instead.
+        if (node.dioxusComposing) {
+          break;
+        }
+        if (node.tagName === "SELECT") {
+          setSelectValue(node, value);
+          break;
+        }
         if (value !== node.value) {
           node.value = value;
         }
@@ -55,7 +172,11 @@ export function setAttributeInner(node, field, value, ns) {
         node.selected = truthy(value);
         break;
       case "dangerous_inner_html":
-        node.innerHTML = value;
+        if (trustedSinks !== null) {
+          trustedSinks.html(node, value);
+        } else {
+          node.innerHTML = value;
+        }
         break;
       default:
         // https://github.com/facebook/react/blob/8b88ac2592c5f555f315f9440cbb665dd1e7457a/packages/react-dom/src/shared/DOMProperty.js#L352-L364
@@ -68,6 +189,94 @@ export function setAttributeInner(node, field, value, ns) {
   }
 }
 
+// Select the option of a select element that matches the value. The options are often rendered after the value is set,
+// so the value is applied again whenever the options change.
+export function setSelectValue(node, value) {
+  node.dioxusValue = value;
+  syncSelectValue(node);
+  if (node.dioxusOptionsObserver === undefined) {
+    node.dioxusOptionsObserver = new MutationObserver(() =>
+      syncSelectValue(node)
+    );
+    node.dioxusOptionsObserver.observe(node, {
+      childList: true,
+      subtree: true,
+      attributes: true,
+      attributeFilter: ["value"],
+    });
+  }
+}
+
+export function clearSelectValue(node) {
+  if (node.dioxusOptionsObserver !== undefined) {
+    node.dioxusOptionsObserver.disconnect();
+    node.dioxusOptionsObserver = undefined;
+  }
+  node.dioxusValue = undefined;
+  node.value = "";
+}
+
+function syncSelectValue(node) {
+  if (node.dioxusValue !== undefined && node.value !== node.dioxusValue) {
+    node.value = node.dioxusValue;
+  }
+}
+
+// Set the text of a text node. Changing the text moves the caret to the start of the node, so if the user is editing
+// the node in a contenteditable element, the caret is moved back to where it was.
+export function setTextInner(node, text) {
+  if (node.textContent === text) {
+    return;
+  }
+  const selection =
+    node.parentElement !== null && node.parentElement.isContentEditable
+      ? window.getSelection()
+      : null;
+  if (selection !== null && selection.anchorNode === node) {
+    const offset = selection.anchorOffset;
+    node.textContent = text;
+    selection.collapse(node, Math.min(offset, text.length));
+  } else {
+    node.textContent = text;
+  }
+}
+
+// The values of the options selected in a select element with the multiple attribute
+export function selectedValues(node) {
+  return Array.from(node.selectedOptions, (option) => option.value);
+}
+
+// Replay the events replay.js buffered before the page was hydrated, in the order the user triggered them. Call this
+// once the listeners of the app are attached. Returns false if the page did not buffer events.
+export function replayQueuedEvents() {
+  const queue = window.__dioxusEventQueue;
+  if (queue === undefined) {
+    return false;
+  }
+  for (const type of queue.types) {
+    window.removeEventListener(type, queue.listener, true);
+  }
+  delete window.__dioxusEventQueue;
+
+  for (const event of queue.events) {
+    // Nodes that did not survive hydration can't receive the event
+    if (!event.target.isConnected) {
+      continue;
+    }
+    const copy = new event.constructor(event.type, event);
+    // The browser already ran the default action of the original event, don't run it twice
+    const preventDefault = (replayed) => {
+      if (replayed === copy) {
+        replayed.preventDefault();
+      }
+    };
+    window.addEventListener(event.type, preventDefault);
+    event.target.dispatchEvent(copy);
+    window.removeEventListener(event.type, preventDefault);
+  }
+  return true;
+}
+
 function truthy(val) {
   return val === "true" || val === true;
 }
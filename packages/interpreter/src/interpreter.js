@@ -1,4 +1,9 @@
-import { setAttributeInner } from "./common.js";
+import {
+  clearSelectValue,
+  selectedValues,
+  setAttributeInner,
+  setTextInner,
+} from "./common.js";
 
 class ListenerMap {
   constructor(root) {
@@ -56,8 +61,10 @@ class ListenerMap {
 }
 
 class InterpreterConfig {
-  constructor(intercept_link_redirects) {
+  constructor(intercept_link_redirects, report_orphans) {
     this.intercept_link_redirects = intercept_link_redirects;
+    // called with the ids of nodes that are still tracked after they were removed from the document. Only set in debug builds
+    this.report_orphans = report_orphans;
   }
 }
 
@@ -66,11 +73,13 @@ class Interpreter {
     this.config = config;
     this.root = root;
     this.listeners = new ListenerMap(root);
-    this.nodes = [root];
+    this.nodes = new Map([[0, root]]);
+    this.lastOrphanCheck = 0;
     this.stack = [root];
     this.handlers = {};
     this.templates = {};
     this.lastNodeWasText = false;
+    trackCompositions(root);
   }
   top() {
     return this.stack[this.stack.length - 1];
@@ -82,17 +91,44 @@ class Interpreter {
     this.AppendChildren(this.stack.length - 1);
   }
   SetNode(id, node) {
-    this.nodes[id] = node;
+    node.dioxusNodeId = id;
+    this.nodes.set(id, node);
+  }
+  // Stop tracking a node that was removed and all of its children
+  ForgetNodes(node) {
+    const walker = document.createTreeWalker(node, NodeFilter.SHOW_ALL);
+    for (let current = node; current != null; current = walker.nextNode()) {
+      const id = current.dioxusNodeId;
+      if (id !== undefined && this.nodes.get(id) === current) {
+        this.nodes.delete(id);
+      }
+    }
+  }
+  // Report nodes that are tracked but no longer in the document
+  CheckOrphans() {
+    const now = Date.now();
+    if (!this.config.report_orphans || now - this.lastOrphanCheck < 1000) {
+      return;
+    }
+    this.lastOrphanCheck = now;
+    const orphans = [];
+    for (const [id, node] of this.nodes) {
+      if (!node.isConnected) {
+        orphans.push(id);
+      }
+    }
+    if (orphans.length > 0) {
+      this.config.report_orphans(orphans);
+    }
   }
   PushRoot(root) {
-    const node = this.nodes[root];
+    const node = this.nodes.get(root);
     this.stack.push(node);
   }
   PopRoot() {
     this.stack.pop();
   }
   AppendChildren(many) {
-    // let root = this.nodes[id];
     let root = this.stack[this.stack.length - 1 - many];
     let to_add = this.stack.splice(this.stack.length - many);
     for (let i = 0; i < many; i++) {
@@ -100,70 +136,78 @@ class Interpreter {
     }
   }
   ReplaceWith(root_id, m) {
-    let root = this.nodes[root_id];
+    let root = this.nodes.get(root_id);
     let els = this.stack.splice(this.stack.length - m);
     if (is_element_node(root.nodeType)) {
       this.listeners.removeAllNonBubbling(root);
     }
     root.replaceWith(...els);
+    this.ForgetNodes(root);
   }
   InsertAfter(root, n) {
-    let old = this.nodes[root];
+    let old = this.nodes.get(root);
     let new_nodes = this.stack.splice(this.stack.length - n);
     old.after(...new_nodes);
   }
   InsertBefore(root, n) {
-    let old = this.nodes[root];
+    let old = this.nodes.get(root);
     let new_nodes = this.stack.splice(this.stack.length - n);
     old.before(...new_nodes);
   }
   Remove(root) {
-    let node = this.nodes[root];
+    let node = this.nodes.get(root);
     if (node !== undefined) {
       if (is_element_node(node)) {
         this.listeners.removeAllNonBubbling(node);
       }
       node.remove();
+      this.ForgetNodes(node);
     }
   }
   CreateTextNode(text, root) {
     const node = document.createTextNode(text);
-    this.nodes[root] = node;
+    this.SetNode(root, node);
     this.stack.push(node);
   }
   CreatePlaceholder(root) {
     let el = document.createElement("pre");
     el.hidden = true;
     this.stack.push(el);
-    this.nodes[root] = el;
+    this.SetNode(root, el);
   }
-  NewEventListener(event_name, root, bubbles, handler) {
-    const element = this.nodes[root];
+  NewEventListener(event_name, root, bubbles, handler, generation) {
+    const element = this.nodes.get(root);
     element.setAttribute("data-dioxus-id", `${root}`);
+    // the generation is sent back with events so stale events for reused ids can be dropped
+    element.dioxusGeneration = generation;
     this.listeners.create(event_name, element, handler, bubbles);
   }
   RemoveEventListener(root, event_name, bubbles) {
-    const element = this.nodes[root];
+    const element = this.nodes.get(root);
     element.removeAttribute(`data-dioxus-id`);
     this.listeners.remove(element, event_name, bubbles);
   }
   SetText(root, text) {
-    this.nodes[root].textContent = text;
+    setTextInner(this.nodes.get(root), text);
   }
   SetAttribute(id, field, value, ns) {
     if (value === null) {
       this.RemoveAttribute(id, field, ns);
     } else {
-      const node = this.nodes[id];
+      const node = this.nodes.get(id);
       setAttributeInner(node, field, value, ns);
     }
   }
   RemoveAttribute(root, field, ns) {
-    const node = this.nodes[root];
+    const node = this.nodes.get(root);
     if (!ns) {
       switch (field) {
         case "value":
-          node.value = "";
+          if (node.tagName === "SELECT") {
+            clearSelectValue(node);
+          } else {
+            node.value = "";
+          }
           break;
         case "checked":
           node.checked = false;
@@ -186,7 +230,7 @@ class Interpreter {
   }
 
   GetClientRect(id) {
-    const node = this.nodes[id];
+    const node = this.nodes.get(id);
     if (!node) {
       return;
     }
@@ -199,7 +243,7 @@ class Interpreter {
   }
 
   ScrollTo(id, behavior) {
-    const node = this.nodes[id];
+    const node = this.nodes.get(id);
     if (!node) {
       return false;
     }
@@ -211,7 +255,7 @@ class Interpreter {
 
   /// Set the focus on the element
   SetFocus(id, focus) {
-    const node = this.nodes[id];
+    const node = this.nodes.get(id);
     if (!node) {
       return false;
     }
@@ -223,6 +267,23 @@ class Interpreter {
     return true;
   }
 
+  /// Call onResize with the size of the element every time it changes, until onResize returns false
+  ObserveResize(id, onResize) {
+    const node = this.nodes.get(id);
+    if (!node) {
+      return false;
+    }
+    const observer = new ResizeObserver(() => {
+      // the size includes the border and padding, like the rect of GetClientRect
+      const rect = node.getBoundingClientRect();
+      if (onResize([rect.width, rect.height]) === false) {
+        observer.disconnect();
+      }
+    });
+    observer.observe(node);
+    return true;
+  }
+
   handleEdits(edits) {
     for (let template of edits.templates) {
       this.SaveTemplate(template);
@@ -232,6 +293,8 @@ class Interpreter {
       this.handleEdit(edit);
     }
 
+    this.CheckOrphans();
+
     /*POST_HANDLE_EDITS*/
   }
 
@@ -276,7 +339,7 @@ class Interpreter {
     }
   }
   AssignId(path, id) {
-    this.nodes[id] = this.LoadChild(path);
+    this.SetNode(id, this.LoadChild(path));
   }
   LoadChild(path) {
     // iterate through each number and get that child
@@ -300,7 +363,7 @@ class Interpreter {
       node = text;
     }
 
-    this.nodes[id] = node;
+    this.SetNode(id, node);
   }
   ReplacePlaceholder(path, m) {
     let els = this.stack.splice(this.stack.length - m);
@@ -309,7 +372,7 @@ class Interpreter {
   }
   LoadTemplate(name, index, id) {
     let node = this.templates[name][index].cloneNode(true);
-    this.nodes[id] = node;
+    this.SetNode(id, node);
     this.stack.push(node);
   }
   handleEdit(edit) {
@@ -362,6 +425,10 @@ class Interpreter {
       case "RemoveEventListener":
         this.RemoveEventListener(edit.id, edit.name);
         break;
+      case "MarkerStart":
+      case "MarkerEnd":
+        // component markers are only used by devtools
+        break;
       case "NewEventListener":
         let bubbles = event_bubbles(edit.name);
 
@@ -371,14 +438,21 @@ class Interpreter {
             serializeIpcMessage("user_event", {
               name: edit.name,
               element: edit.id,
+              generation: edit.generation,
               data: null,
               bubbles,
             })
           );
         } else {
-          this.NewEventListener(edit.name, edit.id, bubbles, (event) => {
-            handler(event, edit.name, bubbles, this.config);
-          });
+          this.NewEventListener(
+            edit.name,
+            edit.id,
+            bubbles,
+            (event) => {
+              handler(event, edit.name, bubbles, this.config);
+            },
+            edit.generation
+          );
         }
         break;
     }
@@ -426,6 +500,7 @@ function handler(event, name, bubbles, config) {
     }
 
     const realId = find_real_id(target);
+    const realElement = target.closest("[data-dioxus-id]");
 
     if (
       preventDefaultRequests &&
@@ -466,6 +541,7 @@ function handler(event, name, bubbles, config) {
       serializeIpcMessage("user_event", {
         name: name,
         element: parseInt(realId),
+        generation: realElement && realElement.dioxusGeneration,
         data: contents,
         bubbles,
       })
@@ -473,6 +549,25 @@ function handler(event, name, bubbles, config) {
   }
 }
 
+// Mark nodes that are in the middle of an IME composition so that controlled inputs don't
+// overwrite the text the user is still composing
+function trackCompositions(root) {
+  root.addEventListener(
+    "compositionstart",
+    (event) => {
+      event.target.dioxusComposing = true;
+    },
+    true
+  );
+  root.addEventListener(
+    "compositionend",
+    (event) => {
+      event.target.dioxusComposing = false;
+    },
+    true
+  );
+}
+
 function find_real_id(target) {
   let realId = target.getAttribute(`data-dioxus-id`);
   // walk the tree to find the real element
@@ -523,11 +618,20 @@ function get_mouse_data(event) {
   };
 }
 
+// Select elements with the multiple attribute report all selected options under their name, or "value" if they
+// don't have a name
+function serializeSelectedValues(target) {
+  if (target.tagName !== "SELECT" || !target.multiple) {
+    return {};
+  }
+  return { [target.name || "value"]: selectedValues(target) };
+}
+
 function serialize_event(event) {
   switch (event.type) {
     case "copy":
     case "cut":
-    case "past": {
+    case "paste": {
       return {};
     }
     case "compositionend":
@@ -582,7 +686,7 @@ function serialize_event(event) {
       }
       return {
         value: value,
-        values: {},
+        values: serializeSelectedValues(target),
       };
     }
     case "input":
@@ -598,7 +702,7 @@ function serialize_event(event) {
 
       return {
         value: value,
-        values: {},
+        values: serializeSelectedValues(target),
       };
     }
     case "drag":
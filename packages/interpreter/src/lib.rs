@@ -1,5 +1,8 @@
 pub static INTERPRETER_JS: &str = include_str!("./interpreter.js");
 pub static COMMON_JS: &str = include_str!("./common.js");
+/// A script that buffers the events the user triggers before a server rendered page is hydrated. Load it from a
+/// synchronous `<script src>` in the head of the page (inlining it breaks under a strict `script-src` CSP), and call `replayQueuedEvents` once the page is hydrated to replay them.
+pub static EVENT_REPLAY_JS: &str = include_str!("./replay.js");
 
 #[cfg(feature = "sledgehammer")]
 mod sledgehammer_bindings;
@@ -15,9 +18,24 @@ pub use bindings::Interpreter;
 // Common bindings for minimal usage.
 #[cfg(feature = "minimal_bindings")]
 pub mod minimal_bindings {
+    use js_sys::Function;
     use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
     #[wasm_bindgen(module = "/src/common.js")]
     extern "C" {
         pub fn setAttributeInner(node: JsValue, name: &str, value: JsValue, ns: Option<&str>);
+
+        /// Create a Trusted Types policy with the given name, and guard the attributes written with
+        /// [`setAttributeInner`]. Html is passed through `sanitize` (a function from html to html, or null to use
+        /// DOMPurify or the Sanitizer API when the browser has them). `report` is called with the sink and a sample of
+        /// the value when a write is blocked.
+        pub fn createTrustedSinks(
+            policy_name: &str,
+            sanitize: &JsValue,
+            report: &Function,
+        ) -> JsValue;
+
+        /// Replay the events buffered by [`EVENT_REPLAY_JS`](crate::EVENT_REPLAY_JS) in order. Returns false if the
+        /// page did not buffer events.
+        pub fn replayQueuedEvents() -> bool;
     }
 }
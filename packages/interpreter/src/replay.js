@@ -0,0 +1,57 @@
+// Buffer the events the user triggers on a server rendered page before it is hydrated.
+// This runs as a classic inline script in the head, so it starts listening before the page is painted.
+// The queue is drained by replayQueuedEvents in common.js once the app's listeners are attached.
+(function () {
+  if (window.__dioxusEventQueue) {
+    return;
+  }
+
+  const types = [
+    "click",
+    "dblclick",
+    "contextmenu",
+    "auxclick",
+    "mousedown",
+    "mouseup",
+    "pointerdown",
+    "pointerup",
+    "touchstart",
+    "touchend",
+    "keydown",
+    "keyup",
+    "keypress",
+    "input",
+    "change",
+    "submit",
+    "reset",
+    "focusin",
+    "focusout",
+  ];
+
+  const queue = {
+    events: [],
+    types,
+    listener(event) {
+      if (!(event.target instanceof Node)) {
+        return;
+      }
+      // The prevent default requests of the app are written into the page, so they can be honored before it is
+      // hydrated. Other default actions are left alone, we don't know if the app would prevent them.
+      let target = event.target;
+      while (target != null) {
+        const requests = target.getAttribute && target.getAttribute("dioxus-prevent-default");
+        if (requests && requests.includes(`on${event.type}`)) {
+          event.preventDefault();
+          break;
+        }
+        target = target.parentNode;
+      }
+      queue.events.push(event);
+    },
+  };
+
+  for (const type of types) {
+    window.addEventListener(type, queue.listener, true);
+  }
+  window.__dioxusEventQueue = queue;
+})();
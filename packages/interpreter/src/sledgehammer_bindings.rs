@@ -1,5 +1,6 @@
 use js_sys::Function;
 use sledgehammer_bindgen::bindgen;
+use wasm_bindgen::JsValue;
 use web_sys::Node;
 
 #[bindgen]
@@ -57,8 +58,16 @@ mod js {
             delete this.local[id];
         }
     }
+    // The Trusted Types sinks created by createTrustedSinks in common.js
+    let trusted_sinks = null;
+    export function set_trusted_sinks(sinks) {
+        trusted_sinks = sinks;
+    }
     function SetAttributeInner(node, field, value, ns) {
         const name = field;
+        if (trusted_sinks !== null && ns !== "style" && name !== "dangerous_inner_html") {
+            value = trusted_sinks.attribute(node, name, value);
+        }
         if (ns === "style") {
             // ????? why do we need to do this
             if (node.style === undefined) {
@@ -70,6 +79,14 @@ mod js {
         } else {
             switch (name) {
                 case "value":
+                    // Writing the value while an IME composition is in progress cancels the composition
+                    if (node.dioxusComposing) {
+                        break;
+                    }
+                    if (node.tagName === "SELECT") {
+                        SetSelectValue(node, value);
+                        break;
+                    }
                     if (value !== node.value) {
                         node.value = value;
                     }
@@ -84,7 +101,11 @@ mod js {
                     node.selected = truthy(value);
                     break;
                 case "dangerous_inner_html":
-                    node.innerHTML = value;
+                    if (trusted_sinks !== null) {
+                        trusted_sinks.html(node, value);
+                    } else {
+                        node.innerHTML = value;
+                    }
                     break;
                 default:
                     // https://github.com/facebook/react/blob/8b88ac2592c5f555f315f9440cbb665dd1e7457a/packages/react-dom/src/shared/DOMProperty.js#L352-L364
@@ -96,6 +117,44 @@ mod js {
             }
         }
     }
+    // Select the option of a select element that matches the value. The options are often rendered after the value
+    // is set, so the value is applied again whenever the options change.
+    function SetSelectValue(node, value) {
+        node.dioxusValue = value;
+        SyncSelectValue(node);
+        if (node.dioxusOptionsObserver === undefined) {
+            node.dioxusOptionsObserver = new MutationObserver(() => SyncSelectValue(node));
+            node.dioxusOptionsObserver.observe(node, { childList: true, subtree: true, attributes: true, attributeFilter: ["value"] });
+        }
+    }
+    function ClearSelectValue(node) {
+        if (node.dioxusOptionsObserver !== undefined) {
+            node.dioxusOptionsObserver.disconnect();
+            node.dioxusOptionsObserver = undefined;
+        }
+        node.dioxusValue = undefined;
+        node.value = "";
+    }
+    function SyncSelectValue(node) {
+        if (node.dioxusValue !== undefined && node.value !== node.dioxusValue) {
+            node.value = node.dioxusValue;
+        }
+    }
+    // Changing the text moves the caret to the start of the node, so if the user is editing the node in a
+    // contenteditable element, the caret is moved back to where it was
+    function SetText(node, text) {
+        if (node.textContent === text) {
+            return;
+        }
+        const selection = node.parentElement !== null && node.parentElement.isContentEditable ? window.getSelection() : null;
+        if (selection !== null && selection.anchorNode === node) {
+            const offset = selection.anchorOffset;
+            node.textContent = text;
+            selection.collapse(node, Math.min(offset, text.length));
+        } else {
+            node.textContent = text;
+        }
+    }
     function LoadChild(ptr, len) {
         // iterate through each number and get that child
         node = stack[stack.length - 1];
@@ -109,28 +168,99 @@ mod js {
         return node;
     }
     const listeners = new ListenerMap();
-    let nodes = [];
+    let nodes = new Map();
     let stack = [];
     let root;
     const templates = {};
     let node, els, end, ptr_end, k;
+    // For each root of each template, the order of every node in the root by its path
+    const template_paths = {};
+    function PathKey(ptr, len) {
+        let key = "";
+        for (const key_end = ptr + len; ptr < key_end; ptr++) {
+            key += String.fromCharCode(m.getUint8(ptr));
+        }
+        return key;
+    }
+    function IndexPaths(root) {
+        const paths = new Map();
+        let order = 0;
+        const walk = (node, key) => {
+            paths.set(key, order++);
+            let i = 0;
+            for (let child = node.firstChild; child != null; child = child.nextSibling) {
+                walk(child, key + String.fromCharCode(i++));
+            }
+        };
+        walk(root, "");
+        return paths;
+    }
+    // Assign ids to many nodes in the template on top of the stack with a single walk through it
+    // The batch is a list of (path length, path, id) sorted by path, which is the order the nodes appear in the template
+    function HydratePaths(paths, ptr, len) {
+        const walker = document.createTreeWalker(stack[stack.length - 1], NodeFilter.SHOW_ALL);
+        let order = 0;
+        for (const batch_end = ptr + len; ptr < batch_end;) {
+            const path_len = m.getUint8(ptr++);
+            const target = paths.get(PathKey(ptr, path_len));
+            let found;
+            if (target === undefined || target < order) {
+                found = LoadChild(ptr, path_len);
+            } else {
+                for (; order < target; order++) {
+                    walker.nextNode();
+                }
+                found = walker.currentNode;
+            }
+            ptr += path_len;
+            SetNode(m.getUint32(ptr, true), found);
+            ptr += 4;
+        }
+    }
     export function save_template(nodes, tmpl_id) {
         templates[tmpl_id] = nodes;
+        template_paths[tmpl_id] = nodes.map(IndexPaths);
+    }
+    function SetNode(id, node) {
+        node.dioxusNodeId = id;
+        nodes.set(id, node);
+    }
+    // Stop tracking a node that was removed and all of its children
+    function ForgetNodes(node) {
+        const walker = document.createTreeWalker(node, NodeFilter.SHOW_ALL);
+        for (let current = node; current != null; current = walker.nextNode()) {
+            const id = current.dioxusNodeId;
+            if (id !== undefined && nodes.get(id) === current) {
+                nodes.delete(id);
+            }
+        }
     }
     export function set_node(id, node) {
-        nodes[id] = node;
+        SetNode(id, node);
+    }
+    // The ids of nodes that are tracked but no longer in the document
+    export function orphaned_nodes() {
+        const orphans = [];
+        for (const [id, node] of nodes) {
+            if (!node.isConnected) {
+                orphans.push(id);
+            }
+        }
+        return orphans;
     }
     export function get_node(id) {
-        return nodes[id];
+        return nodes.get(id);
     }
     export function initilize(root, handler) {
         listeners.handler = handler;
-        nodes = [root];
+        nodes = new Map([[0, root]]);
         stack = [root];
         listeners.root = root;
+        root.addEventListener("compositionstart", (event) => { event.target.dioxusComposing = true; }, true);
+        root.addEventListener("compositionend", (event) => { event.target.dioxusComposing = false; }, true);
     }
     function AppendChildren(id, many){
-        root = nodes[id];
+        root = nodes.get(id);
         els = stack.splice(stack.length-many);
         for (k = 0; k < many; k++) {
             root.appendChild(els[k]);
@@ -182,13 +312,19 @@ mod js {
 
         #[wasm_bindgen]
         pub fn initilize(root: Node, handler: &Function);
+
+        #[wasm_bindgen]
+        pub fn orphaned_nodes() -> Vec<u32>;
+
+        #[wasm_bindgen]
+        pub fn set_trusted_sinks(sinks: JsValue);
     }
 
     fn mount_to_root() {
         "{AppendChildren(root, stack.length-1);}"
     }
     fn push_root(root: u32) {
-        "{stack.push(nodes[$root$]);}"
+        "{stack.push(nodes.get($root$));}"
     }
     fn append_children(id: u32, many: u32) {
         "{AppendChildren($id$, $many$);}"
@@ -197,45 +333,49 @@ mod js {
         "{stack.pop();}"
     }
     fn replace_with(id: u32, n: u32) {
-        "{root = nodes[$id$]; els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } root.replaceWith(...els);}"
+        "{root = nodes.get($id$); els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } root.replaceWith(...els); ForgetNodes(root);}"
     }
     fn insert_after(id: u32, n: u32) {
-        "{nodes[$id$].after(...stack.splice(stack.length-$n$));}"
+        "{nodes.get($id$).after(...stack.splice(stack.length-$n$));}"
     }
     fn insert_before(id: u32, n: u32) {
-        "{nodes[$id$].before(...stack.splice(stack.length-$n$));}"
+        "{nodes.get($id$).before(...stack.splice(stack.length-$n$));}"
     }
     fn remove(id: u32) {
-        "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); }}"
+        "{node = nodes.get($id$); if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); ForgetNodes(node); }}"
     }
     fn create_raw_text(text: &str) {
         "{stack.push(document.createTextNode($text$));}"
     }
     fn create_text_node(text: &str, id: u32) {
-        "{node = document.createTextNode($text$); nodes[$id$] = node; stack.push(node);}"
+        "{node = document.createTextNode($text$); SetNode($id$, node); stack.push(node);}"
     }
     fn create_placeholder(id: u32) {
-        "{node = document.createElement('pre'); node.hidden = true; stack.push(node); nodes[$id$] = node;}"
+        "{node = document.createElement('pre'); node.hidden = true; stack.push(node); SetNode($id$, node);}"
     }
     fn new_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        r#"node = nodes[id]; if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$);"#
+        r#"node = nodes.get(id); if(node.listening){node.listening += 1;}else{node.listening = 1;} node.setAttribute('data-dioxus-id', `\${id}`); listeners.create($event_name$, node, $bubbles$);"#
     }
     fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-        "{node = nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
+        "{node = nodes.get($id$); node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
     }
     fn set_text(id: u32, text: &str) {
-        "{nodes[$id$].textContent = $text$;}"
+        "{SetText(nodes.get($id$), $text$);}"
     }
     fn set_attribute(id: u32, field: &str<u8, attr>, value: &str, ns: &str<u8, ns_cache>) {
-        "{node = nodes[$id$]; SetAttributeInner(node, $field$, $value$, $ns$);}"
+        "{node = nodes.get($id$); SetAttributeInner(node, $field$, $value$, $ns$);}"
     }
     fn remove_attribute(id: u32, field: &str<u8, attr>, ns: &str<u8, ns_cache>) {
         r#"{
-            node = nodes[$id$];
+            node = nodes.get($id$);
             if (!ns) {
                 switch (field) {
                     case "value":
-                        node.value = "";
+                        if (node.tagName === "SELECT") {
+                            ClearSelectValue(node);
+                        } else {
+                            node.value = "";
+                        }
                         break;
                     case "checked":
                         node.checked = false;
@@ -244,7 +384,8 @@ mod js {
                         node.selected = false;
                         break;
                     case "dangerous_inner_html":
-                        node.innerHTML = "";
+                        // textContent is not a Trusted Types sink, so this works under require-trusted-types-for
+                        node.textContent = "";
                         break;
                     default:
                         node.removeAttribute(field);
@@ -258,7 +399,7 @@ mod js {
         }"#
     }
     fn assign_id(ptr: u32, len: u8, id: u32) {
-        "{nodes[$id$] = LoadChild($ptr$, $len$);}"
+        "{SetNode($id$, LoadChild($ptr$, $len$));}"
     }
     fn hydrate_text(ptr: u32, len: u8, value: &str, id: u32) {
         r#"{
@@ -270,13 +411,16 @@ mod js {
                 node.replaceWith(text);
                 node = text;
             }
-            nodes[$id$] = node;
+            SetNode($id$, node);
         }"#
     }
     fn replace_placeholder(ptr: u32, len: u8, n: u32) {
         "{els = stack.splice(stack.length - $n$); node = LoadChild($ptr$, $len$); node.replaceWith(...els);}"
     }
+    fn hydrate_paths(tmpl_id: u32, index: u32, ptr: u32, len: u32) {
+        "{HydratePaths(template_paths[$tmpl_id$][$index$], $ptr$, $len$);}"
+    }
     fn load_template(tmpl_id: u32, index: u32, id: u32) {
-        "{node = templates[$tmpl_id$][$index$].cloneNode(true); nodes[$id$] = node; stack.push(node);}"
+        "{node = templates[$tmpl_id$][$index$].cloneNode(true); SetNode($id$, node); stack.push(node);}"
     }
 }
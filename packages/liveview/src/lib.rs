@@ -80,7 +80,10 @@ static INTERPRETER_JS: Lazy<String> = Lazy::new(|| {
     }"#;
 
     let interpreter = interpreter.replace("/*POST_EVENT_SERIALIZATION*/", serialize_file_uploads);
-    interpreter.replace("import { setAttributeInner } from \"./common.js\";", "")
+    interpreter.replace(
+        "import {\n  clearSelectValue,\n  selectedValues,\n  setAttributeInner,\n  setTextInner,\n} from \"./common.js\";",
+        "",
+    )
 });
 
 static COMMON_JS: Lazy<String> = Lazy::new(|| {
@@ -167,22 +167,27 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                             match message {
                                 IpcMessage::Event(evt) => {
                                     // Intercept the mounted event and insert a custom element type
-                                    if let EventData::Mounted = &evt.data {
+                                    let data = if let EventData::Mounted = &evt.data {
                                         let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        vdom.handle_event(
+                                        Rc::new(MountedData::new(element)) as Rc<dyn std::any::Any>
+                                    }
+                                    else{
+                                        evt.data.into_any()
+                                    };
+                                    match evt.generation {
+                                        Some(generation) => vdom.handle_generational_event(
                                             &evt.name,
-                                            Rc::new(MountedData::new(element)),
+                                            data,
                                             evt.element,
+                                            generation,
                                             evt.bubbles,
-                                        );
-                                    }
-                                    else{
-                                        vdom.handle_event(
+                                        ),
+                                        None => vdom.handle_event(
                                             &evt.name,
-                                            evt.data.into_any(),
+                                            data,
                                             evt.element,
                                             evt.bubbles,
-                                        );
+                                        ),
                                     }
                                 }
                                 IpcMessage::Query(result) => {
@@ -148,6 +148,15 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
         .find_map(|(i, ident)| (this_type == *ident).then_some(i))
         .unwrap();
     let this_view = format_ident!("__data{}", this_type_idx);
+    // The states of this type are passed into the update function as a reference, every other view is borrowed
+    let borrow_view = |i: usize| {
+        let ident = format_ident!("__data{}", i);
+        if i == this_type_idx {
+            quote! {#ident}
+        } else {
+            quote! {&#ident}
+        }
+    };
 
     let combined_dependencies_quote = combined_dependencies.iter().map(|ident| {
         if ident == &this_type {
@@ -185,7 +194,7 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
 
     let node_view = node_dependencies_idxes
         .iter()
-        .map(|i| format_ident!("__data{}", i))
+        .map(|i| borrow_view(*i))
         .collect::<Vec<_>>();
     let get_node_view = {
         if node_dependencies.is_empty() {
@@ -198,7 +207,7 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
                 .collect::<Vec<_>>();
             quote! {
                 let raw_node: (#(*const #node_dependencies,)*) = {
-                    let (#(#temps,)*) = (#(&#node_view,)*).get(id).unwrap_or_else(|err| panic!("Failed to get node view {:?}", err));
+                    let (#(#temps,)*) = (#(#node_view,)*).get(id).unwrap_or_else(|err| panic!("Failed to get node view {:?}", err));
                     (#(#temps as *const _,)*)
                 };
             }
@@ -219,20 +228,20 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
 
     let parent_view = parent_dependancies_idxes
         .iter()
-        .map(|i| format_ident!("__data{}", i))
+        .map(|i| borrow_view(*i))
         .collect::<Vec<_>>();
     let get_parent_view = {
         if parent_dependencies.is_empty() {
             quote! {
-                let raw_parent = tree.parent_id_advanced(id, Self::TRAVERSE_SHADOW_DOM).map(|_| ());
+                let raw_parent = tree.parent_id_advanced(id, traverse_shadow_dom).map(|_| ());
             }
         } else {
             let temps = (0..parent_dependencies.len())
                 .map(|i| format_ident!("__temp{}", i))
                 .collect::<Vec<_>>();
             quote! {
-                let raw_parent = tree.parent_id_advanced(id, Self::TRAVERSE_SHADOW_DOM).and_then(|parent_id| {
-                    let raw_parent: Option<(#(*const #parent_dependencies,)*)> = (#(&#parent_view,)*).get(parent_id).ok().map(|c| {
+                let raw_parent = tree.parent_id_advanced(id, traverse_shadow_dom).and_then(|parent_id| {
+                    let raw_parent: Option<(#(*const #parent_dependencies,)*)> = (#(#parent_view,)*).get(parent_id).ok().map(|c| {
                         let (#(#temps,)*) = c;
                         (#(#temps as *const _,)*)
                     });
@@ -256,20 +265,20 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
 
     let child_view = child_dependencies_idxes
         .iter()
-        .map(|i| format_ident!("__data{}", i))
+        .map(|i| borrow_view(*i))
         .collect::<Vec<_>>();
     let get_child_view = {
         if child_dependencies.is_empty() {
             quote! {
-                let raw_children: Vec<_> = tree.children_ids_advanced(id, Self::TRAVERSE_SHADOW_DOM).into_iter().map(|_| ()).collect();
+                let raw_children: Vec<_> = tree.children_ids_advanced(id, traverse_shadow_dom).into_iter().map(|_| ()).collect();
             }
         } else {
             let temps = (0..child_dependencies.len())
                 .map(|i| format_ident!("__temp{}", i))
                 .collect::<Vec<_>>();
             quote! {
-                let raw_children: Vec<_> = tree.children_ids_advanced(id, Self::TRAVERSE_SHADOW_DOM).into_iter().filter_map(|id| {
-                    let raw_children: Option<(#(*const #child_dependencies,)*)> = (#(&#child_view,)*).get(id).ok().map(|c| {
+                let raw_children: Vec<_> = tree.children_ids_advanced(id, traverse_shadow_dom).into_iter().filter_map(|id| {
+                    let raw_children: Option<(#(*const #child_dependencies,)*)> = (#(#child_view,)*).get(id).ok().map(|c| {
                         let (#(#temps,)*) = c;
                         (#(#temps as *const _,)*)
                     });
@@ -291,14 +300,8 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
-    let trait_generics = trait_
-        .as_ref()
-        .unwrap()
-        .segments
-        .last()
-        .unwrap()
-        .arguments
-        .clone();
+    let trait_path = trait_.as_ref().unwrap();
+    let trait_generics = trait_path.segments.last().unwrap().arguments.clone();
 
     // if a create function is defined, we don't generate one
     // otherwise we generate a default one that uses the update function and the default constructor
@@ -326,40 +329,35 @@ pub fn partial_derive_state(_: TokenStream, input: TokenStream) -> TokenStream {
             #(#items)*
 
             fn workload_system(type_id: std::any::TypeId, dependants: std::sync::Arc<dioxus_native_core::prelude::Dependants>, pass_direction: dioxus_native_core::prelude::PassDirection) -> dioxus_native_core::exports::shipyard::WorkloadSystem {
-                use dioxus_native_core::exports::shipyard::{IntoWorkloadSystem, Get, AddComponent};
+                use dioxus_native_core::exports::shipyard::{IntoWorkloadSystem, Get};
                 use dioxus_native_core::tree::TreeRef;
                 use dioxus_native_core::prelude::{NodeType, NodeView};
 
-                let node_mask = Self::NODE_MASK.build();
+                // the trait is named explicitly, because a state can implement it for any custom attribute value
+                let node_mask = <Self as #trait_path>::NODE_MASK.build();
+                let traverse_shadow_dom = <Self as #trait_path>::TRAVERSE_SHADOW_DOM;
 
                 (move |data: #combined_dependencies_quote, run_view: dioxus_native_core::prelude::RunPassView #trait_generics| {
                     let (#(#split_views,)*) = data;
                     let tree = run_view.tree.clone();
                     let node_types = run_view.node_type.clone();
-                    dioxus_native_core::prelude::run_pass(type_id, dependants.clone(), pass_direction, run_view, |id, context| {
+                    let pseudo_classes = run_view.pseudo_classes.clone();
+                    dioxus_native_core::prelude::run_pass(type_id, dependants.clone(), pass_direction, run_view, &mut #this_view, |id, myself, #this_view, context| {
                         let node_data: &NodeType<_> = node_types.get(id).unwrap_or_else(|err| panic!("Failed to get node type {:?}", err));
                         // get all of the states from the tree view
-                        // Safety: No node has itself as a parent or child.
-                        let raw_myself: Option<*mut Self> = (&mut #this_view).get(id).ok().map(|c| c as *mut _);
                         #get_node_view
                         #get_parent_view
                         #get_child_view
 
-                        let myself: Option<&mut Self> = unsafe { raw_myself.map(|val| &mut *val) };
+                        // Safety: No node has itself as a parent or child.
                         #deref_node_view
                         #deref_parent_view
                         #deref_child_view
 
-                        let view = NodeView::new(id, node_data, &node_mask);
-                        if let Some(myself) = myself {
-                            myself
-                                .update(view, node, parent, children, context)
-                        }
-                        else {
-                            (&mut #this_view).add_component_unchecked(
-                                id,
-                                Self::create(view, node, parent, children, context));
-                            true
+                        let view = NodeView::new(id, node_data, &node_mask).with_pseudo_classes(pseudo_classes.get(id).map(|classes| *classes).unwrap_or_default());
+                        match myself {
+                            Some(myself) => dioxus_native_core::prelude::NodeUpdate::Updated(myself.update(view, node, parent, children, context)),
+                            None => dioxus_native_core::prelude::NodeUpdate::Created(<Self as #trait_path>::create(view, node, parent, children, context)),
                         }
                     })
                 }).into_workload_system().unwrap()
@@ -0,0 +1,298 @@
+//! Benchmarks for applying mutations from the VirtualDom to the RealDom and updating the state of the nodes.
+//!
+//! Each benchmark generates a synthetic stream of mutations by rendering a VirtualDom, then only measures the time
+//! it takes the RealDom to apply those mutations and run the state passes.
+
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use dioxus::prelude::*;
+use dioxus_core::Mutations;
+use dioxus_native_core::prelude::*;
+use dioxus_native_core_macro::partial_derive_state;
+use rand::prelude::*;
+use shipyard::Component;
+
+criterion_group!(mbenches, deep_trees, wide_trees, churny_lists);
+criterion_main!(mbenches);
+
+/// Build trees that are one long chain of elements.
+fn deep_trees(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Create deep tree");
+    for depth in [10, 50, 100, 200] {
+        bench_create(&mut group, depth, deep_app, DepthProps { depth });
+    }
+}
+
+/// Build trees where every element is a child of the same parent.
+fn wide_trees(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Create wide tree");
+    for width in [10, 100, 1000, 5000] {
+        bench_create(&mut group, width, wide_app, WidthProps { width });
+    }
+}
+
+/// Update a keyed list where items are inserted, removed and moved on every render.
+fn churny_lists(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Update churny list");
+    for len in [10, 100, 1000] {
+        bench_update(&mut group, len, churny_app, ChurnProps { len });
+    }
+}
+
+fn bench_create<P: Clone + 'static>(
+    group: &mut criterion::BenchmarkGroup<'_, criterion::measurement::WallTime>,
+    size: usize,
+    app: dioxus_core::Component<P>,
+    props: P,
+) {
+    group.bench_with_input(BenchmarkId::new("size", size), &size, |b, _| {
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                let mut vdom = VirtualDom::new_with_props(app, props.clone());
+                let (mut rdom, mut dioxus_state) = create_real_dom();
+                let mutations = vdom.rebuild();
+                elapsed += apply(&mut rdom, &mut dioxus_state, mutations);
+            }
+            elapsed
+        })
+    });
+}
+
+fn bench_update<P: Clone + 'static>(
+    group: &mut criterion::BenchmarkGroup<'_, criterion::measurement::WallTime>,
+    size: usize,
+    app: dioxus_core::Component<P>,
+    props: P,
+) {
+    group.bench_with_input(BenchmarkId::new("size", size), &size, |b, _| {
+        let mut vdom = VirtualDom::new_with_props(app, props.clone());
+        let (mut rdom, mut dioxus_state) = create_real_dom();
+        let mutations = vdom.rebuild();
+        apply(&mut rdom, &mut dioxus_state, mutations);
+
+        b.iter_custom(|iters| {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iters {
+                vdom.mark_dirty(ScopeId(0));
+                let mutations = vdom.render_immediate();
+                elapsed += apply(&mut rdom, &mut dioxus_state, mutations);
+            }
+            elapsed
+        })
+    });
+}
+
+fn create_real_dom() -> (RealDom, DioxusState) {
+    let mut rdom = RealDom::new([
+        Depth::to_type_erased(),
+        Descendants::to_type_erased(),
+        Color::to_type_erased(),
+    ]);
+    let dioxus_state = DioxusState::create(&mut rdom);
+    (rdom, dioxus_state)
+}
+
+// Measure the time it takes to apply the mutations and update the state of the RealDom
+fn apply(rdom: &mut RealDom, dioxus_state: &mut DioxusState, mutations: Mutations) -> Duration {
+    let start = Instant::now();
+    dioxus_state.apply_mutations(rdom, mutations);
+    rdom.update_state(SendAnyMap::new());
+    start.elapsed()
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct DepthProps {
+    depth: usize,
+}
+
+fn deep_app(cx: Scope<DepthProps>) -> Element {
+    let depth = cx.props.depth;
+    if depth == 0 {
+        return cx.render(rsx! { "leaf" });
+    }
+
+    cx.render(rsx! {
+        div {
+            color: "red",
+            deep_app { depth: depth - 1 }
+        }
+    })
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct WidthProps {
+    width: usize,
+}
+
+fn wide_app(cx: Scope<WidthProps>) -> Element {
+    cx.render(rsx! {
+        div {
+            (0..cx.props.width).map(|i| rsx! {
+                div {
+                    key: "{i}",
+                    color: "blue",
+                    "{i}"
+                }
+            })
+        }
+    })
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ChurnProps {
+    len: usize,
+}
+
+fn churny_app(cx: Scope<ChurnProps>) -> Element {
+    let next_key = cx.use_hook(|| cx.props.len);
+    let items = cx.use_hook(|| (0..cx.props.len).collect::<Vec<_>>());
+    // seed the rng with the next key so every run generates the same mutations
+    let mut rng = StdRng::seed_from_u64(*next_key as u64);
+
+    // replace about a tenth of the items and move another tenth
+    for _ in 0..(items.len() / 10).max(1) {
+        let index = rng.gen_range(0..items.len());
+        items.remove(index);
+        let index = rng.gen_range(0..=items.len());
+        items.insert(index, *next_key);
+        *next_key += 1;
+
+        let from = rng.gen_range(0..items.len());
+        let to = rng.gen_range(0..items.len());
+        items.swap(from, to);
+    }
+
+    cx.render(rsx! {
+        ul {
+            items.iter().map(|item| rsx! {
+                li {
+                    key: "{item}",
+                    color: if item % 2 == 0 { "red" } else { "blue" },
+                    "item {item}"
+                }
+            })
+        }
+    })
+}
+
+/// The depth of the node in the tree (a parent to child pass)
+#[derive(Debug, Clone, PartialEq, Default, Component)]
+struct Depth(usize);
+
+#[partial_derive_state]
+impl State for Depth {
+    type ParentDependencies = (Self,);
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+    fn update<'a>(
+        &mut self,
+        _: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let new = Depth(parent.map_or(0, |(parent,)| parent.0 + 1));
+        let changed = *self != new;
+        *self = new;
+        changed
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+/// The number of nodes below the node (a child to parent pass)
+#[derive(Debug, Clone, PartialEq, Default, Component)]
+struct Descendants(usize);
+
+#[partial_derive_state]
+impl State for Descendants {
+    type ParentDependencies = ();
+    type ChildDependencies = (Self,);
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+    fn update<'a>(
+        &mut self,
+        _: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let new = Descendants(children.iter().map(|(child,)| child.0 + 1).sum());
+        let changed = *self != new;
+        *self = new;
+        changed
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+/// The color attribute of the node (a node pass)
+#[derive(Debug, Clone, PartialEq, Default, Component)]
+struct Color(Option<String>);
+
+#[partial_derive_state]
+impl State for Color {
+    type ParentDependencies = ();
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> =
+        NodeMaskBuilder::new().with_attrs(AttributeMaskBuilder::Some(&["color"]));
+
+    fn update<'a>(
+        &mut self,
+        node_view: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let new = Color(node_view.attributes().and_then(|mut attrs| {
+            attrs.find_map(|attr| attr.value.as_text().map(|color| color.to_string()))
+        }));
+        let changed = *self != new;
+        *self = new;
+        changed
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
@@ -0,0 +1,749 @@
+//! Keyframe animations that are resolved with the style of the nodes.
+//!
+//! Renderers register [`Keyframes`] by name with an [`AnimationTimeline`] and add [`AnimatedStyle`] to the states of
+//! the [`RealDom`]. Elements play keyframes with the `animation` attributes, like `animation: fade 1s infinite`.
+//!
+//! The renderer supplies the time: every frame it moves the timeline with [`AnimationTimeline::tick`], which marks the
+//! nodes that play an animation as dirty, and passes the timeline to [`RealDom::update_state`] in the context. The
+//! style passes of the renderer depend on [`AnimatedStyle`] and apply its [`AnimatedStyle::values`] over the
+//! attributes of the element, so animated values are resolved like any other style.
+//!
+//! ```rust
+//! use dioxus_native_core::animation::Keyframes;
+//!
+//! let fade = Keyframes::parse("from { opacity: 0 } to { opacity: 1 }").unwrap();
+//! assert_eq!(fade.values(0.5), [("opacity".to_string(), "0.5".to_string())]);
+//! ```
+
+use std::{any::TypeId, sync::Arc, time::Duration};
+
+use dioxus_native_core_macro::partial_derive_state;
+use parking_lot::RwLock;
+use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::Component;
+
+use crate::{
+    node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView},
+    prelude::*,
+};
+
+/// How a value moves between its start and end value
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimingFunction {
+    /// A cubic bezier curve from `(0, 0)` to `(1, 1)` with the control points `(x1, y1)` and `(x2, y2)`
+    CubicBezier(f32, f32, f32, f32),
+    /// Jumps between a number of equally sized steps
+    Steps(u32, StepPosition),
+}
+
+/// When the value of a [`TimingFunction::Steps`] jumps
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StepPosition {
+    /// the value jumps at the start of each step
+    Start,
+    /// the value jumps at the end of each step
+    End,
+}
+
+impl Default for TimingFunction {
+    fn default() -> Self {
+        Self::EASE
+    }
+}
+
+impl TimingFunction {
+    /// The value moves at the same speed from start to end
+    pub const LINEAR: Self = Self::CubicBezier(0.0, 0.0, 1.0, 1.0);
+    /// The value speeds up quickly and slows down at the end
+    pub const EASE: Self = Self::CubicBezier(0.25, 0.1, 0.25, 1.0);
+
+    /// Parse a css timing function like `ease-in` or `cubic-bezier(0.1, 0.7, 1, 0.1)`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "linear" => Some(Self::LINEAR),
+            "ease" => Some(Self::EASE),
+            "ease-in" => Some(Self::CubicBezier(0.42, 0.0, 1.0, 1.0)),
+            "ease-out" => Some(Self::CubicBezier(0.0, 0.0, 0.58, 1.0)),
+            "ease-in-out" => Some(Self::CubicBezier(0.42, 0.0, 0.58, 1.0)),
+            "step-start" => Some(Self::Steps(1, StepPosition::Start)),
+            "step-end" => Some(Self::Steps(1, StepPosition::End)),
+            _ => {
+                let (name, args) = value.strip_suffix(')')?.split_once('(')?;
+                let args: Vec<_> = args.split(',').map(str::trim).collect();
+                match (name, args.as_slice()) {
+                    ("cubic-bezier", [x1, y1, x2, y2]) => Some(Self::CubicBezier(
+                        // the curve has to move forward in time
+                        x1.parse::<f32>().ok()?.clamp(0.0, 1.0),
+                        y1.parse().ok()?,
+                        x2.parse::<f32>().ok()?.clamp(0.0, 1.0),
+                        y2.parse().ok()?,
+                    )),
+                    ("steps", [steps]) => Some(Self::Steps(steps.parse().ok()?, StepPosition::End)),
+                    ("steps", [steps, position]) => {
+                        let position = match *position {
+                            "start" | "jump-start" => StepPosition::Start,
+                            "end" | "jump-end" => StepPosition::End,
+                            _ => return None,
+                        };
+                        Some(Self::Steps(steps.parse().ok()?, position))
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// How far the value moved at a point in time, where 0 is the start and 1 is the end of the transition
+    pub fn ease(self, time: f32) -> f32 {
+        match self {
+            Self::CubicBezier(x1, y1, x2, y2) => {
+                fn bezier(p1: f32, p2: f32, t: f32) -> f32 {
+                    let rt = 1.0 - t;
+                    3.0 * p1 * t * rt * rt + 3.0 * p2 * t * t * rt + t * t * t
+                }
+                // x only grows along the curve, so the point at the time can be found with a binary search
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..20 {
+                    let mid = (low + high) / 2.0;
+                    if bezier(x1, x2, mid) < time {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                bezier(y1, y2, (low + high) / 2.0)
+            }
+            Self::Steps(steps, position) => {
+                let steps = steps.max(1) as f32;
+                let step = match position {
+                    StepPosition::Start => (time * steps).floor() + 1.0,
+                    StepPosition::End => (time * steps).floor(),
+                };
+                (step / steps).min(1.0)
+            }
+        }
+    }
+}
+
+/// When and how fast a transition or animation moves
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Timing {
+    /// How long one iteration takes
+    pub duration: Duration,
+    /// How long to wait before the first iteration starts
+    pub delay: Duration,
+    /// How the value moves during an iteration
+    pub timing_function: TimingFunction,
+}
+
+/// Parse a time like `1s` or `250ms`
+pub fn parse_time(value: &str) -> Option<Duration> {
+    let seconds = if let Some(millis) = value.strip_suffix("ms") {
+        millis.parse::<f32>().ok()? / 1000.0
+    } else {
+        value.strip_suffix('s')?.parse::<f32>().ok()?
+    };
+    (seconds >= 0.0).then(|| Duration::from_secs_f32(seconds))
+}
+
+/// Split a value at the separators that are not inside of parentheses, like the commas between the items of
+/// `color 1s cubic-bezier(0.1, 0.7, 1, 0.1), width 2s`
+fn split_outside_parentheses(value: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && separator(c) => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// Split a css list like `color 1s, width 2s ease-in` into its items
+pub fn split_list(value: &str) -> Vec<&str> {
+    split_outside_parentheses(value, |c| c == ',')
+}
+
+/// Split an item of a css list like `width 2s cubic-bezier(0.1, 0.7, 1, 0.1)` into its words
+pub fn split_words(value: &str) -> Vec<&str> {
+    split_outside_parentheses(value, char::is_whitespace)
+}
+
+/// The direction an animation plays in
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AnimationDirection {
+    /// Every iteration plays from the start to the end
+    #[default]
+    Normal,
+    /// Every iteration plays from the end to the start
+    Reverse,
+    /// The first iteration plays forwards, and every other iteration plays backwards
+    Alternate,
+    /// The first iteration plays backwards, and every other iteration plays forwards
+    AlternateReverse,
+}
+
+/// If the first keyframe applies before an animation starts, and the last keyframe applies after it ends
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FillMode {
+    /// The keyframes only apply while the animation plays
+    #[default]
+    None,
+    /// The keyframes apply after the animation ends
+    Forwards,
+    /// The keyframes apply during the delay before the animation starts
+    Backwards,
+    /// The keyframes apply before and after the animation
+    Both,
+}
+
+/// The animation of an element, set with the `animation` attributes. Only one animation can be set per element
+#[derive(Clone, PartialEq, Debug)]
+pub struct Animation {
+    name: String,
+    timing: Timing,
+    iteration_count: f32,
+    direction: AnimationDirection,
+    fill_mode: FillMode,
+    paused: bool,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            name: "none".to_string(),
+            timing: Timing::default(),
+            iteration_count: 1.0,
+            direction: AnimationDirection::default(),
+            fill_mode: FillMode::default(),
+            paused: false,
+        }
+    }
+}
+
+impl Animation {
+    /// Apply the value of one of the `animation` attributes, like `animation-duration` or the `animation` shorthand
+    pub fn apply(&mut self, name: &str, value: &str) {
+        // only the first animation of a list is played
+        let Some(value) = split_list(value).first().copied() else {
+            return;
+        };
+        match name {
+            "animation" => {
+                *self = Self::default();
+                let mut duration = None;
+                for word in split_words(value) {
+                    if let Some(time) = parse_time(word) {
+                        // the first time is the duration and the second time is the delay
+                        if duration.is_none() {
+                            duration = Some(time);
+                            self.timing.duration = time;
+                        } else {
+                            self.timing.delay = time;
+                        }
+                    } else if let Some(function) = TimingFunction::parse(word) {
+                        self.timing.timing_function = function;
+                    } else if !self.apply_keyword(word) {
+                        self.name = word.to_string();
+                    }
+                }
+            }
+            "animation-delay" => self.timing.delay = parse_time(value).unwrap_or_default(),
+            "animation-duration" => self.timing.duration = parse_time(value).unwrap_or_default(),
+            "animation-name" => self.name = value.to_string(),
+            "animation-timing-function" => {
+                self.timing.timing_function = TimingFunction::parse(value).unwrap_or_default()
+            }
+            "animation-direction"
+            | "animation-fill-mode"
+            | "animation-iteration-count"
+            | "animation-play-state" => {
+                self.apply_keyword(value);
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply a value of the direction, fill mode, iteration count or play state. Returns false if the value is none
+    /// of them
+    fn apply_keyword(&mut self, value: &str) -> bool {
+        match value {
+            "normal" => self.direction = AnimationDirection::Normal,
+            "reverse" => self.direction = AnimationDirection::Reverse,
+            "alternate" => self.direction = AnimationDirection::Alternate,
+            "alternate-reverse" => self.direction = AnimationDirection::AlternateReverse,
+            "forwards" => self.fill_mode = FillMode::Forwards,
+            "backwards" => self.fill_mode = FillMode::Backwards,
+            "both" => self.fill_mode = FillMode::Both,
+            "infinite" => self.iteration_count = f32::INFINITY,
+            "running" => self.paused = false,
+            "paused" => self.paused = true,
+            _ => match value.parse::<f32>() {
+                Ok(count) if count >= 0.0 => self.iteration_count = count,
+                _ => return false,
+            },
+        }
+        true
+    }
+
+    /// The name of the keyframes the animation plays, or `none`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns true if the animation is paused with `animation-play-state`
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns true if the animations play the same keyframes, regardless of if they are paused
+    pub fn same_as(&self, other: &Animation) -> bool {
+        Animation {
+            paused: other.paused,
+            ..self.clone()
+        } == *other
+    }
+
+    /// How far the animation moved through its keyframes after some time, or [`None`] if the keyframes don't apply
+    pub fn progress(&self, elapsed: Duration) -> Option<f32> {
+        let fills_backwards = matches!(self.fill_mode, FillMode::Backwards | FillMode::Both);
+        let fills_forwards = matches!(self.fill_mode, FillMode::Forwards | FillMode::Both);
+
+        let (iteration, progress) = match elapsed.checked_sub(self.timing.delay) {
+            None if fills_backwards => (0.0, 0.0),
+            None => return None,
+            Some(active) => {
+                let duration = self.timing.duration.as_secs_f32();
+                let iterations = if duration > 0.0 {
+                    active.as_secs_f32() / duration
+                } else {
+                    f32::INFINITY
+                };
+                if iterations < self.iteration_count {
+                    (iterations.floor(), iterations.fract())
+                } else if fills_forwards {
+                    // the animation stays at the point where the last iteration ended
+                    let iteration = (self.iteration_count.ceil() - 1.0).max(0.0);
+                    (iteration, self.iteration_count - iteration)
+                } else {
+                    return None;
+                }
+            }
+        };
+
+        let odd_iteration = iteration % 2.0 == 1.0;
+        let reversed = match self.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => odd_iteration,
+            AnimationDirection::AlternateReverse => !odd_iteration,
+        };
+        let progress = if reversed { 1.0 - progress } else { progress };
+        Some(self.timing.timing_function.ease(progress))
+    }
+
+    /// Returns true if the animation is over after some time
+    pub fn finished(&self, elapsed: Duration) -> bool {
+        let active = self.timing.duration.as_secs_f32() * self.iteration_count;
+        elapsed.as_secs_f32() >= self.timing.delay.as_secs_f32() + active
+    }
+}
+
+/// The values of some properties at a point of an iteration of an animation
+#[derive(Clone, PartialEq, Debug)]
+pub struct Keyframe {
+    /// The point of the iteration, from 0 at the start to 1 at the end
+    pub offset: f32,
+    /// The properties and their values at this point
+    pub properties: Vec<(String, String)>,
+}
+
+impl Keyframe {
+    fn value(&self, property: &str) -> Option<&str> {
+        // like in css, the last declaration of a property wins
+        self.properties
+            .iter()
+            .rev()
+            .find(|(name, _)| name == property)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The keyframes an animation moves through, like the body of a `@keyframes` rule in css
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Keyframes {
+    frames: Vec<Keyframe>,
+}
+
+impl Keyframes {
+    /// Create keyframes from a list of keyframes in any order
+    pub fn new(frames: impl IntoIterator<Item = Keyframe>) -> Self {
+        let mut frames: Vec<_> = frames.into_iter().collect();
+        // the sort is stable, so the later of two keyframes at the same point wins
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        Self { frames }
+    }
+
+    /// Parse the body of a `@keyframes` rule, like `from { opacity: 0 } 50%, 75% { opacity: 0.8 } to { opacity: 1 }`.
+    ///
+    /// Returns [`None`] if the keyframes are invalid.
+    pub fn parse(body: &str) -> Option<Self> {
+        let mut frames = Vec::new();
+        let mut rest = body.trim();
+        while !rest.is_empty() {
+            let (selectors, after) = rest.split_once('{')?;
+            let (declarations, after) = after.split_once('}')?;
+            let properties = declarations
+                .split(';')
+                .map(str::trim)
+                .filter(|declaration| !declaration.is_empty())
+                .map(|declaration| {
+                    let (name, value) = declaration.split_once(':')?;
+                    Some((name.trim().to_string(), value.trim().to_string()))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            for selector in selectors.split(',') {
+                let offset = match selector.trim() {
+                    "from" => 0.0,
+                    "to" => 1.0,
+                    percent => percent.strip_suffix('%')?.trim().parse::<f32>().ok()? / 100.0,
+                };
+                if !(0.0..=1.0).contains(&offset) {
+                    return None;
+                }
+                frames.push(Keyframe {
+                    offset,
+                    properties: properties.clone(),
+                });
+            }
+            rest = after.trim();
+        }
+        Some(Self::new(frames))
+    }
+
+    /// The keyframes, ordered by their offset
+    pub fn frames(&self) -> &[Keyframe] {
+        &self.frames
+    }
+
+    /// The values of the animated properties at a point of an iteration, from 0 at the start to 1 at the end. Values
+    /// between the keyframes that set a property are interpolated with [`interpolate`].
+    ///
+    /// Unlike css, a property is not animated from or to the value the element is styled with: before the first and
+    /// after the last keyframe that sets a property, the property keeps the value of that keyframe.
+    pub fn values(&self, progress: f32) -> Vec<(String, String)> {
+        let mut names: Vec<&str> = Vec::new();
+        for frame in &self.frames {
+            for (name, _) in &frame.properties {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                // the closest keyframes before and after the point that set the property
+                let before = self
+                    .frames
+                    .iter()
+                    .rev()
+                    .filter(|frame| frame.offset <= progress)
+                    .find_map(|frame| Some((frame.offset, frame.value(name)?)));
+                let after = self
+                    .frames
+                    .iter()
+                    .filter(|frame| frame.offset >= progress)
+                    .find_map(|frame| Some((frame.offset, frame.value(name)?)));
+                let value = match (before, after) {
+                    (Some((from_offset, from)), Some((to_offset, to)))
+                        if to_offset > from_offset =>
+                    {
+                        let progress = (progress - from_offset) / (to_offset - from_offset);
+                        interpolate(from, to, progress)
+                    }
+                    (Some((_, value)), _) | (None, Some((_, value))) => value.to_string(),
+                    (None, None) => return None,
+                };
+                Some((name.to_string(), value))
+            })
+            .collect()
+    }
+}
+
+/// Interpolate between two values of a property, where a progress of 0 is the first value and 1 is the second value.
+///
+/// Numbers with the same unit, like `0.5` and `1`, or `10px` and `20px`, and colors, like `#ff0000` and
+/// `rgba(0, 0, 255, 0.5)`, move between the values. Other values switch halfway through.
+pub fn interpolate(from: &str, to: &str, progress: f32) -> String {
+    if let (Some((from, from_unit)), Some((to, to_unit))) = (parse_number(from), parse_number(to)) {
+        if from_unit == to_unit {
+            return format!("{}{from_unit}", round(lerp(from, to, progress)));
+        }
+    }
+    if let (Some(from), Some(to)) = (parse_color(from), parse_color(to)) {
+        let channel = |i: usize| lerp(from[i], to[i], progress).round().clamp(0.0, 255.0);
+        let alpha = round(lerp(from[3], to[3], progress).clamp(0.0, 1.0));
+        return format!(
+            "rgba({}, {}, {}, {alpha})",
+            channel(0),
+            channel(1),
+            channel(2)
+        );
+    }
+    if progress < 0.5 { from } else { to }.to_string()
+}
+
+fn lerp(from: f32, to: f32, progress: f32) -> f32 {
+    from + (to - from) * progress
+}
+
+/// Round a number to three decimals, so interpolated values don't show float errors
+fn round(value: f32) -> f32 {
+    // adding zero turns -0 into 0
+    (value * 1000.0).round() / 1000.0 + 0.0
+}
+
+/// Parse a number with an optional unit, like `0.5`, `10px` or `50%`
+fn parse_number(value: &str) -> Option<(f32, &str)> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| c.is_alphabetic() || c == '%')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(unit_start);
+    if !unit.chars().all(|c| c.is_alphabetic() || c == '%') {
+        return None;
+    }
+    Some((number.parse().ok()?, unit))
+}
+
+/// Parse a color like `#f00`, `#ff0000`, `rgb(255, 0, 0)` or `rgba(255, 0, 0, 0.5)` into its red, green, blue and
+/// alpha channels. The alpha channel is between 0 and 1
+fn parse_color(value: &str) -> Option<[f32; 4]> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        let [r, g, b] = match digits.as_slice() {
+            [r, g, b] => [r * 17, g * 17, b * 17],
+            [r1, r2, g1, g2, b1, b2] => [r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2],
+            _ => return None,
+        };
+        return Some([r as f32, g as f32, b as f32, 1.0]);
+    }
+
+    let args = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let args: Vec<_> = args.split(',').map(str::trim).collect();
+    let channel = |arg: &str| match arg.strip_suffix('%') {
+        Some(percent) => Some(percent.parse::<f32>().ok()? / 100.0 * 255.0),
+        None => arg.parse::<f32>().ok(),
+    };
+    let alpha = |arg: &str| match arg.strip_suffix('%') {
+        Some(percent) => Some(percent.parse::<f32>().ok()? / 100.0),
+        None => arg.parse::<f32>().ok(),
+    };
+    match args.as_slice() {
+        [r, g, b] => Some([channel(r)?, channel(g)?, channel(b)?, 1.0]),
+        [r, g, b, a] => Some([channel(r)?, channel(g)?, channel(b)?, alpha(a)?]),
+        _ => None,
+    }
+}
+
+/// The clock the animations of a dom play on, and the keyframes they can play. Clones of the timeline share the same
+/// clock and keyframes.
+#[derive(Clone, Default)]
+pub struct AnimationTimeline {
+    inner: Arc<RwLock<Timeline>>,
+}
+
+#[derive(Default)]
+struct Timeline {
+    keyframes: FxHashMap<String, Arc<Keyframes>>,
+    now: Duration,
+    /// The nodes that play an animation that changes over time
+    playing: FxHashSet<NodeId>,
+}
+
+impl AnimationTimeline {
+    /// Create a timeline at time zero without any keyframes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register keyframes that elements can play with `animation-name`. Register the keyframes before the elements
+    /// that play them are added, or the elements start playing them the next time their animation changes.
+    pub fn add_keyframes(&self, name: impl Into<String>, keyframes: Keyframes) {
+        self.inner
+            .write()
+            .keyframes
+            .insert(name.into(), Arc::new(keyframes));
+    }
+
+    /// The keyframes registered with a name
+    pub fn keyframes(&self, name: &str) -> Option<Arc<Keyframes>> {
+        self.inner.read().keyframes.get(name).cloned()
+    }
+
+    /// The current time of the timeline
+    pub fn now(&self) -> Duration {
+        self.inner.read().now
+    }
+
+    /// Move the timeline to a point in time, like the time since the renderer started when it draws a frame. The
+    /// nodes that play an animation are marked as dirty, so the next [`RealDom::update_state`] resolves their
+    /// [`AnimatedStyle`] at the new time.
+    pub fn tick<V: FromAnyValue + Send + Sync>(&self, rdom: &mut RealDom<V>, now: Duration) {
+        let mut timeline = self.inner.write();
+        timeline.now = now;
+        timeline.playing.retain(|id| rdom.contains(*id));
+        for &id in &timeline.playing {
+            rdom.dirty_nodes
+                .mark_state_dirty(id, TypeId::of::<AnimatedStyle>());
+        }
+    }
+
+    /// Returns true if any node plays an animation that changes over time, so the renderer needs to draw more frames
+    pub fn is_playing(&self) -> bool {
+        !self.inner.read().playing.is_empty()
+    }
+
+    fn set_playing(&self, id: NodeId, playing: bool) {
+        let mut timeline = self.inner.write();
+        if playing {
+            timeline.playing.insert(id);
+        } else {
+            timeline.playing.remove(&id);
+        }
+    }
+}
+
+/// The values of the properties an element is animated with at the current time of the [`AnimationTimeline`].
+///
+/// The timeline is read from the context passed to [`RealDom::update_state`]. Without a timeline, nothing is animated.
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct AnimatedStyle {
+    /// The animation the element plays
+    pub animation: Animation,
+    /// The time on the timeline the animation started at
+    start: Duration,
+    /// The time on the timeline the animation was paused at
+    paused_at: Option<Duration>,
+    values: Vec<(String, String)>,
+}
+
+impl AnimatedStyle {
+    /// The animated properties and their values at the current time, in the order they first appear in the
+    /// keyframes. The values override the values the element is styled with.
+    pub fn values(&self) -> &[(String, String)] {
+        &self.values
+    }
+}
+
+#[partial_derive_state]
+impl<V: FromAnyValue + Send + Sync> State<V> for AnimatedStyle {
+    type ParentDependencies = ();
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> =
+        NodeMaskBuilder::new().with_attrs(AttributeMaskBuilder::Some(ANIMATION_ATTRIBUTES));
+
+    fn update<'a>(
+        &mut self,
+        node_view: NodeView<V>,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> bool {
+        let mut animation = Animation::default();
+        if let Some(attributes) = node_view.attributes() {
+            for attribute in attributes {
+                if let Some(value) = attribute.value.as_text() {
+                    animation.apply(&attribute.attribute.name, value);
+                }
+            }
+        }
+
+        let new = match context.get::<AnimationTimeline>() {
+            Some(timeline) => {
+                let now = timeline.now();
+                let (mut start, mut paused_at) = (self.start, self.paused_at);
+                if self.animation.same_as(&animation) {
+                    // pausing and resuming an animation keeps the time it played for
+                    match (paused_at, animation.paused()) {
+                        (None, true) => paused_at = Some(now),
+                        (Some(paused), false) => {
+                            start += now.saturating_sub(paused);
+                            paused_at = None;
+                        }
+                        _ => {}
+                    }
+                } else {
+                    start = now;
+                    paused_at = animation.paused().then_some(now);
+                }
+
+                let elapsed = paused_at.unwrap_or(now).saturating_sub(start);
+                let keyframes = timeline.keyframes(animation.name());
+                let values = keyframes
+                    .as_ref()
+                    .and_then(|keyframes| Some(keyframes.values(animation.progress(elapsed)?)))
+                    .unwrap_or_default();
+                timeline.set_playing(
+                    node_view.node_id(),
+                    keyframes.is_some() && paused_at.is_none() && !animation.finished(elapsed),
+                );
+
+                AnimatedStyle {
+                    animation,
+                    start,
+                    paused_at,
+                    values,
+                }
+            }
+            None => AnimatedStyle {
+                animation,
+                ..Default::default()
+            },
+        };
+
+        if *self != new {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// sorted so the mask can be built from it
+const ANIMATION_ATTRIBUTES: &[&str] = &[
+    "animation",
+    "animation-delay",
+    "animation-direction",
+    "animation-duration",
+    "animation-fill-mode",
+    "animation-iteration-count",
+    "animation-name",
+    "animation-play-state",
+    "animation-timing-function",
+];
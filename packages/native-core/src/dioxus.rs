@@ -71,6 +71,8 @@ impl DioxusState {
     }
 
     /// Updates the dom with some mutations and return a set of nodes that were updated. Pass the dirty nodes to update_state.
+    ///
+    /// Mutations that change a node in a frozen subtree unfreeze the subtree. See [`RealDom::freeze`].
     pub fn apply_mutations<V: FromAnyValue + Send + Sync>(
         &mut self,
         rdom: &mut RealDom<V>,
@@ -207,7 +209,7 @@ impl DioxusState {
                         *text.text_mut() = value.to_string();
                     }
                 }
-                NewEventListener { name, id } => {
+                NewEventListener { name, id, .. } => {
                     let node_id = self.element_to_node_id(id);
                     let mut node = rdom.get_mut(node_id).unwrap();
                     node.add_event_listener(name);
@@ -225,6 +227,7 @@ impl DioxusState {
                     let node_id = self.element_to_node_id(id);
                     self.stack.push(node_id);
                 }
+                MarkerStart { .. } | MarkerEnd { .. } => {}
             }
         }
     }
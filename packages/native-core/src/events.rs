@@ -0,0 +1,112 @@
+//! Resolve the path events take through the [`RealDom`] so every renderer propagates events the same way.
+//!
+//! Events follow the same phases as in the browser: the capture phase visits every ancestor of the target from the
+//! root down, then the target itself is visited, and finally the bubble phase visits the ancestors from the target
+//! up to the root if the event bubbles. Events that are dispatched into a shadow tree propagate out of it through the
+//! node the shadow tree is attached to.
+
+use crate::node::FromAnyValue;
+use crate::real_dom::RealDom;
+use crate::tree::TreeRef;
+use crate::NodeId;
+
+/// The phase of an event while it propagates through the dom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPhase {
+    /// The event is travelling from the root down to the target
+    Capturing,
+    /// The event is at the target
+    AtTarget,
+    /// The event is travelling from the target up to the root
+    Bubbling,
+}
+
+/// Returned from the handler passed to [`PropagationPath::dispatch`] to control if the event keeps propagating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    /// Continue to the next node in the path
+    Continue,
+    /// Stop the event from reaching any other node
+    Stop,
+}
+
+/// The path an event takes from the root of the dom to its target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropagationPath {
+    target: NodeId,
+    // The ancestors of the target, starting with the parent of the target
+    ancestors: Vec<NodeId>,
+    bubbles: bool,
+}
+
+impl PropagationPath {
+    /// Resolve the path of an event dispatched to `target`.
+    pub fn new(tree: &impl TreeRef, target: NodeId, bubbles: bool) -> Self {
+        let mut ancestors = Vec::new();
+        let mut current = target;
+        while let Some(parent) = tree.parent_id_advanced(current, true) {
+            ancestors.push(parent);
+            current = parent;
+        }
+        Self {
+            target,
+            ancestors,
+            bubbles,
+        }
+    }
+
+    /// The node the event was dispatched to.
+    pub fn target(&self) -> NodeId {
+        self.target
+    }
+
+    /// Returns true if the event bubbles up to the ancestors of the target after reaching the target.
+    pub fn bubbles(&self) -> bool {
+        self.bubbles
+    }
+
+    /// The target and all of its ancestors, starting with the target.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::once(self.target).chain(self.ancestors.iter().copied())
+    }
+
+    /// Returns true if the node is the target or one of its ancestors.
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.nodes().any(|node| node == id)
+    }
+
+    /// Visit every node in the path in the order the event reaches them.
+    ///
+    /// The handler is called for every ancestor in the capture phase, then for the target, then for every ancestor in
+    /// the bubble phase if the event bubbles. Propagation ends as soon as the handler returns [`Propagation::Stop`].
+    ///
+    /// Returns [`Propagation::Stop`] if the propagation was stopped.
+    pub fn dispatch(
+        &self,
+        mut handler: impl FnMut(NodeId, EventPhase) -> Propagation,
+    ) -> Propagation {
+        for &id in self.ancestors.iter().rev() {
+            if handler(id, EventPhase::Capturing) == Propagation::Stop {
+                return Propagation::Stop;
+            }
+        }
+        if handler(self.target, EventPhase::AtTarget) == Propagation::Stop {
+            return Propagation::Stop;
+        }
+        if self.bubbles {
+            for &id in &self.ancestors {
+                if handler(id, EventPhase::Bubbling) == Propagation::Stop {
+                    return Propagation::Stop;
+                }
+            }
+        }
+        Propagation::Continue
+    }
+}
+
+impl<V: FromAnyValue + Send + Sync> RealDom<V> {
+    /// Resolve the path of an event dispatched to `target`. See [`PropagationPath`] for more information.
+    pub fn propagation_path(&self, target: NodeId, bubbles: bool) -> PropagationPath {
+        PropagationPath::new(&self.tree_ref(), target, bubbles)
+    }
+}
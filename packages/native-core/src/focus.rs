@@ -0,0 +1,307 @@
+//! Keyboard focus and accessibility information for the nodes of the dom.
+//!
+//! Add [`FocusState`] to the states of a [`RealDom`] to resolve which nodes can be focused, the order the focus moves
+//! through them with tab and the ARIA role of every node. Renderers move the focus with [`next_focus`] instead of
+//! walking the tree themselves.
+
+use std::{cmp::Ordering, num::NonZeroU16};
+
+use dioxus_native_core_macro::partial_derive_state;
+use shipyard::Component;
+
+use crate::{
+    node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView},
+    prelude::*,
+};
+
+/// If and when a node is focused while the focus moves through the dom with tab.
+///
+/// Levels are ordered like the focus moves: every [`FocusLevel::Ordered`] node comes before the
+/// [`FocusLevel::Focusable`] nodes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FocusLevel {
+    /// The node can't be focused
+    #[default]
+    Unfocusable,
+    /// The node is focused in the order of the tree, after the nodes with a positive `tabindex`
+    Focusable,
+    /// The node has a positive `tabindex`. Nodes with a lower index are focused first
+    Ordered(NonZeroU16),
+}
+
+impl FocusLevel {
+    /// Returns true if the node can be focused
+    pub fn focusable(&self) -> bool {
+        match self {
+            FocusLevel::Unfocusable => false,
+            FocusLevel::Focusable => true,
+            FocusLevel::Ordered(_) => true,
+        }
+    }
+}
+
+impl PartialOrd for FocusLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FocusLevel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (FocusLevel::Unfocusable, FocusLevel::Unfocusable) => Ordering::Equal,
+            (FocusLevel::Unfocusable, FocusLevel::Focusable) => Ordering::Less,
+            (FocusLevel::Unfocusable, FocusLevel::Ordered(_)) => Ordering::Less,
+            (FocusLevel::Focusable, FocusLevel::Unfocusable) => Ordering::Greater,
+            (FocusLevel::Focusable, FocusLevel::Focusable) => Ordering::Equal,
+            (FocusLevel::Focusable, FocusLevel::Ordered(_)) => Ordering::Greater,
+            (FocusLevel::Ordered(_), FocusLevel::Unfocusable) => Ordering::Greater,
+            (FocusLevel::Ordered(_), FocusLevel::Focusable) => Ordering::Less,
+            (FocusLevel::Ordered(a), FocusLevel::Ordered(b)) => a.cmp(b),
+        }
+    }
+}
+
+/// The ARIA role of a node, which tells assistive technology what the node is.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// A message that needs the attention of the user
+    Alert,
+    /// Performs an action when it is activated
+    Button,
+    /// A checkbox that can be checked and unchecked
+    Checkbox,
+    /// An input that shows a list of options to pick from
+    ComboBox,
+    /// A window over the rest of the app
+    Dialog,
+    /// A form to fill out
+    Form,
+    /// The heading of a section
+    Heading,
+    /// An image
+    Img,
+    /// Navigates to another page when it is activated
+    Link,
+    /// A list of items
+    List,
+    /// An item of a list
+    ListItem,
+    /// The main content of the app
+    Main,
+    /// A section with links to navigate the app
+    Navigation,
+    /// A bar that shows the progress of a task
+    ProgressBar,
+    /// One of a group of options where only one can be checked
+    Radio,
+    /// An input that picks a value from a range
+    Slider,
+    /// An input that picks a number
+    SpinButton,
+    /// A table of data
+    Table,
+    /// An input to type text in
+    TextBox,
+    /// Any other role, set with the `role` attribute
+    Other(String),
+}
+
+impl Role {
+    /// Parse the value of a `role` attribute. The first role in the list is used
+    pub fn parse(value: &str) -> Option<Self> {
+        let role = value.split_whitespace().next()?;
+        Some(match role {
+            "alert" => Role::Alert,
+            "button" => Role::Button,
+            "checkbox" => Role::Checkbox,
+            "combobox" => Role::ComboBox,
+            "dialog" => Role::Dialog,
+            "form" => Role::Form,
+            "heading" => Role::Heading,
+            "img" => Role::Img,
+            "link" => Role::Link,
+            "list" => Role::List,
+            "listitem" => Role::ListItem,
+            "main" => Role::Main,
+            "navigation" => Role::Navigation,
+            "progressbar" => Role::ProgressBar,
+            "radio" => Role::Radio,
+            "slider" => Role::Slider,
+            "spinbutton" => Role::SpinButton,
+            "table" => Role::Table,
+            "textbox" => Role::TextBox,
+            other => Role::Other(other.to_string()),
+        })
+    }
+
+    /// The role elements with a tag have without a `role` attribute
+    fn implicit<V: FromAnyValue>(tag: &str, node_view: &NodeView<V>) -> Option<Self> {
+        Some(match tag {
+            "a" | "area" if attribute(node_view, "href").is_some() => Role::Link,
+            "button" => Role::Button,
+            "input" => match attribute(node_view, "type").and_then(|t| t.as_text()) {
+                Some("checkbox") => Role::Checkbox,
+                Some("radio") => Role::Radio,
+                Some("range") => Role::Slider,
+                Some("number") => Role::SpinButton,
+                Some("button" | "submit" | "reset") => Role::Button,
+                _ => Role::TextBox,
+            },
+            "textarea" => Role::TextBox,
+            "select" => Role::ComboBox,
+            "dialog" => Role::Dialog,
+            "form" => Role::Form,
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Role::Heading,
+            "img" => Role::Img,
+            "ul" | "ol" => Role::List,
+            "li" => Role::ListItem,
+            "main" => Role::Main,
+            "nav" => Role::Navigation,
+            "progress" => Role::ProgressBar,
+            "table" => Role::Table,
+            _ => return None,
+        })
+    }
+}
+
+/// The focus and accessibility state of a node: if and in which order it can be focused, and its ARIA role.
+///
+/// A node can be focused if it has a `tabindex` of 0 or more, if it is an interactive element like a `button` or an
+/// `input`, or if it listens to keyboard events. Disabled nodes can't be focused.
+#[derive(Clone, PartialEq, Debug, Default, Component)]
+pub struct FocusState {
+    /// If and when the node is focused while the focus moves with tab
+    pub level: FocusLevel,
+    /// The role of the node, set with the `role` attribute or implied by the tag of the element
+    pub role: Option<Role>,
+}
+
+#[partial_derive_state]
+impl<V: FromAnyValue + Send + Sync> State<V> for FocusState {
+    type ParentDependencies = ();
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new()
+        .with_attrs(AttributeMaskBuilder::Some(FOCUS_ATTRIBUTES))
+        .with_tag()
+        .with_listeners();
+
+    fn update<'a>(
+        &mut self,
+        node_view: NodeView<V>,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let tag = node_view.tag();
+        let disabled = attribute(&node_view, "disabled").is_some_and(|value| {
+            value.as_bool().unwrap_or(true) && value.as_text() != Some("false")
+        });
+        let tabindex = attribute(&node_view, "tabindex").map(|value| {
+            value
+                .as_int()
+                .or_else(|| value.as_text().and_then(|v| v.trim().parse::<i64>().ok()))
+        });
+
+        let level = if disabled {
+            // Disabled elements can't be focused, even if they have a tabindex
+            FocusLevel::Unfocusable
+        } else if let Some(index) = tabindex {
+            match index {
+                Some(0) => FocusLevel::Focusable,
+                Some(index) if index > 0 => {
+                    FocusLevel::Ordered(NonZeroU16::new(index.min(u16::MAX as i64) as u16).unwrap())
+                }
+                // A negative or invalid tabindex removes the element from the tab order
+                _ => FocusLevel::Unfocusable,
+            }
+        } else if tag.is_some_and(|tag| is_interactive(tag, &node_view))
+            || node_view
+                .listeners()
+                .is_some_and(|mut listeners| listeners.any(|l| FOCUS_EVENTS.contains(&l)))
+        {
+            FocusLevel::Focusable
+        } else {
+            FocusLevel::Unfocusable
+        };
+
+        let role = attribute(&node_view, "role")
+            .and_then(|value| value.as_text())
+            .and_then(Role::parse)
+            .or_else(|| tag.and_then(|tag| Role::implicit(tag, &node_view)));
+
+        let new = FocusState { level, role };
+        if *self != new {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// sorted so the mask can be built from it
+const FOCUS_ATTRIBUTES: &[&str] = &["disabled", "href", "role", "tabindex", "type"];
+const FOCUS_EVENTS: &[&str] = &["keydown", "keypress", "keyup"];
+
+fn attribute<'a, V: FromAnyValue>(
+    node_view: &NodeView<'a, V>,
+    name: &str,
+) -> Option<&'a crate::node::OwnedAttributeValue<V>> {
+    node_view
+        .attributes()?
+        .find(|a| a.attribute.name == name)
+        .map(|a| a.value)
+}
+
+/// Elements that can be focused without a `tabindex`
+fn is_interactive<V: FromAnyValue>(tag: &str, node_view: &NodeView<V>) -> bool {
+    match tag {
+        "button" | "select" | "textarea" => true,
+        "input" => attribute(node_view, "type").and_then(|t| t.as_text()) != Some("hidden"),
+        "a" | "area" => attribute(node_view, "href").is_some(),
+        _ => false,
+    }
+}
+
+/// The nodes that can be focused, in the order the focus moves through them with tab. Every node with a positive
+/// `tabindex` comes first, ordered by the index, followed by the other nodes in the order of the tree.
+///
+/// The dom must have the [`FocusState`] state.
+pub fn tab_order<V: FromAnyValue + Send + Sync>(rdom: &RealDom<V>) -> Vec<NodeId> {
+    let mut order = Vec::new();
+    rdom.traverse_depth_first(|node| {
+        if let Some(focus) = node.get::<FocusState>() {
+            if focus.level.focusable() {
+                order.push((focus.level, node.id()));
+            }
+        }
+    });
+    // the sort is stable, so nodes with the same level stay in the order of the tree
+    order.sort_by_key(|(level, _)| *level);
+    order.into_iter().map(|(_, id)| id).collect()
+}
+
+/// Find the node the focus moves to from the `focused` node when tab is pressed, or shift+tab if `forward` is false.
+/// The focus wraps around at the end of the [`tab_order`]. If no node is focused, or the focused node can't be focused
+/// anymore, the focus moves to the first node, or the last node when moving backwards.
+///
+/// Returns [`None`] if no node can be focused.
+pub fn next_focus<V: FromAnyValue + Send + Sync>(
+    rdom: &RealDom<V>,
+    focused: Option<NodeId>,
+    forward: bool,
+) -> Option<NodeId> {
+    let order = tab_order(rdom);
+    let current = focused.and_then(|focused| order.iter().position(|id| *id == focused));
+    let next = match (current, forward) {
+        (Some(current), true) => (current + 1) % order.len(),
+        (Some(current), false) => (current + order.len() - 1) % order.len(),
+        (None, true) => 0,
+        (None, false) => order.len().checked_sub(1)?,
+    };
+    order.get(next).copied()
+}
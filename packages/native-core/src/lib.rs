@@ -7,16 +7,26 @@ use std::hash::BuildHasherDefault;
 use node_ref::NodeMask;
 use rustc_hash::FxHasher;
 
+// the state derive macro refers to this crate by name, which lets the built-in states use it
+extern crate self as dioxus_native_core;
+
+pub mod animation;
 pub mod custom_element;
 #[cfg(feature = "dioxus")]
 pub mod dioxus;
+pub mod events;
+pub mod focus;
 #[cfg(feature = "layout-attributes")]
 pub mod layout_attributes;
 pub mod node;
+pub mod node_data;
 pub mod node_ref;
 pub mod node_watcher;
 mod passes;
+pub mod query;
 pub mod real_dom;
+#[cfg(feature = "serialize")]
+pub mod snapshot;
 pub mod tree;
 pub mod utils;
 
@@ -35,9 +45,14 @@ pub mod exports {
 pub mod prelude {
     #[cfg(feature = "dioxus")]
     pub use crate::dioxus::*;
-    pub use crate::node::{ElementNode, FromAnyValue, NodeType, OwnedAttributeView, TextNode};
+    pub use crate::events::{EventPhase, Propagation, PropagationPath};
+    pub use crate::node::{
+        ElementNode, FromAnyValue, NodeType, OwnedAttributeView, PseudoClass, PseudoClasses,
+        TextNode,
+    };
+    pub use crate::node_data::NodeHandle;
     pub use crate::node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView};
-    pub use crate::passes::{run_pass, PassDirection, RunPassView, TypeErasedState};
+    pub use crate::passes::{run_pass, NodeUpdate, PassDirection, RunPassView, TypeErasedState};
     pub use crate::passes::{Dependancy, DependancyView, Dependants, State};
     pub use crate::real_dom::{NodeImmutable, NodeMut, NodeRef, RealDom};
     pub use crate::NodeId;
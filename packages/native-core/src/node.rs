@@ -32,6 +32,82 @@ impl ElementNode {
     }
 }
 
+/// An interaction state of an element that can be used for styling, like the [`:hover`](https://developer.mozilla.org/en-US/docs/Web/CSS/:hover) pseudo-class in CSS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PseudoClass {
+    /// The pointer is over the element or one of its children
+    Hover,
+    /// The element has the keyboard focus
+    Focus,
+    /// The element or one of its children is being pressed
+    Active,
+    /// The element can not be interacted with
+    Disabled,
+}
+
+impl PseudoClass {
+    /// All pseudo-classes in the order their styles are applied. Later pseudo-classes override the styles of earlier ones.
+    pub const ALL: [PseudoClass; 4] = [
+        PseudoClass::Hover,
+        PseudoClass::Focus,
+        PseudoClass::Active,
+        PseudoClass::Disabled,
+    ];
+
+    /// Get the name of the pseudo-class without the leading colon
+    pub fn name(&self) -> &'static str {
+        match self {
+            PseudoClass::Hover => "hover",
+            PseudoClass::Focus => "focus",
+            PseudoClass::Active => "active",
+            PseudoClass::Disabled => "disabled",
+        }
+    }
+}
+
+/// The set of [`PseudoClass`]es that currently apply to an element. These are the interaction states set by the
+/// renderer, like `:hover` or `:focus`, and are stored next to the [`ElementNode`] in the RealDom.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Component)]
+pub struct PseudoClasses {
+    /// The pointer is over the element or one of its children
+    pub hover: bool,
+    /// The element has the keyboard focus
+    pub focus: bool,
+    /// The element or one of its children is being pressed
+    pub active: bool,
+    /// The element can not be interacted with
+    pub disabled: bool,
+}
+
+impl PseudoClasses {
+    /// Check if the pseudo-class applies to the element
+    pub fn contains(&self, class: PseudoClass) -> bool {
+        match class {
+            PseudoClass::Hover => self.hover,
+            PseudoClass::Focus => self.focus,
+            PseudoClass::Active => self.active,
+            PseudoClass::Disabled => self.disabled,
+        }
+    }
+
+    /// Set if the pseudo-class applies to the element
+    pub fn set(&mut self, class: PseudoClass, value: bool) {
+        match class {
+            PseudoClass::Hover => self.hover = value,
+            PseudoClass::Focus => self.focus = value,
+            PseudoClass::Active => self.active = value,
+            PseudoClass::Disabled => self.disabled = value,
+        }
+    }
+
+    /// Iterate over the pseudo-classes that apply to the element in the order their styles are applied
+    pub fn iter(&self) -> impl Iterator<Item = PseudoClass> + '_ {
+        PseudoClass::ALL
+            .into_iter()
+            .filter(move |class| self.contains(*class))
+    }
+}
+
 /// A text node in the RealDom
 #[derive(Debug, Clone, Default)]
 pub struct TextNode {
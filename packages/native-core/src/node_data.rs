@@ -0,0 +1,132 @@
+//! Weak handles to nodes and data owned by the renderer that is attached to nodes.
+//!
+//! Renderers often need to keep data next to the nodes of the [`RealDom`] that the dom itself does not know about, like GPU textures or cached glyph runs. Instead of keeping a `HashMap<NodeId, T>` that must be cleaned up manually, the data can be attached to a node with [`RealDom::insert_node_data`]. It is dropped automatically when the node is removed.
+
+use std::any::{Any, TypeId};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    node::FromAnyValue,
+    real_dom::{NodeMut, NodeRef, RealDom},
+    NodeId,
+};
+
+/// A weak reference to a node in a [`RealDom`].
+///
+/// Unlike a [`NodeRef`], a handle does not borrow the dom, so it can be stored by a renderer. The handle does not keep the node alive, and it will not resolve to a different node that reuses the id after the node is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    id: NodeId,
+}
+
+impl NodeHandle {
+    /// Create a handle to the node with the given id
+    pub fn new(id: NodeId) -> Self {
+        Self { id }
+    }
+
+    /// Get the id of the node this handle points to. The node may have been removed.
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Check if the node this handle points to is still in the dom
+    pub fn is_alive<V: FromAnyValue + Send + Sync>(&self, rdom: &RealDom<V>) -> bool {
+        rdom.contains(self.id)
+    }
+
+    /// Get a reference to the node, if it was not removed
+    pub fn upgrade<'a, V: FromAnyValue + Send + Sync>(
+        &self,
+        rdom: &'a RealDom<V>,
+    ) -> Option<NodeRef<'a, V>> {
+        rdom.get(self.id)
+    }
+
+    /// Get a mutable reference to the node, if it was not removed
+    pub fn upgrade_mut<'a, V: FromAnyValue + Send + Sync>(
+        &self,
+        rdom: &'a mut RealDom<V>,
+    ) -> Option<NodeMut<'a, V>> {
+        rdom.get_mut(self.id)
+    }
+}
+
+impl From<NodeId> for NodeHandle {
+    fn from(id: NodeId) -> Self {
+        Self::new(id)
+    }
+}
+
+/// A table of data of a single type, keyed by the node the data is attached to
+trait NodeDataTable: Any + Send + Sync {
+    fn remove_node(&mut self, id: NodeId);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> NodeDataTable for FxHashMap<NodeId, T> {
+    fn remove_node(&mut self, id: NodeId) {
+        self.remove(&id);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The data attached to nodes, with one table for every type of data
+#[derive(Default)]
+pub(crate) struct NodeData {
+    tables: FxHashMap<TypeId, Box<dyn NodeDataTable>>,
+}
+
+impl NodeData {
+    fn table<T: Send + Sync + 'static>(&self) -> Option<&FxHashMap<NodeId, T>> {
+        self.tables
+            .get(&TypeId::of::<T>())
+            .and_then(|table| table.as_any().downcast_ref())
+    }
+
+    fn table_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut FxHashMap<NodeId, T>> {
+        self.tables
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|table| table.as_any_mut().downcast_mut())
+    }
+
+    pub(crate) fn insert<T: Send + Sync + 'static>(&mut self, id: NodeId, value: T) -> Option<T> {
+        self.tables
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::<FxHashMap<NodeId, T>>::default())
+            .as_any_mut()
+            .downcast_mut::<FxHashMap<NodeId, T>>()
+            .unwrap()
+            .insert(id, value)
+    }
+
+    pub(crate) fn get<T: Send + Sync + 'static>(&self, id: NodeId) -> Option<&T> {
+        self.table()?.get(&id)
+    }
+
+    pub(crate) fn get_mut<T: Send + Sync + 'static>(&mut self, id: NodeId) -> Option<&mut T> {
+        self.table_mut()?.get_mut(&id)
+    }
+
+    pub(crate) fn remove<T: Send + Sync + 'static>(&mut self, id: NodeId) -> Option<T> {
+        self.table_mut()?.remove(&id)
+    }
+
+    /// Drop all data attached to a node
+    pub(crate) fn remove_node(&mut self, id: NodeId) {
+        for table in self.tables.values_mut() {
+            table.remove_node(id);
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use rustc_hash::FxHashSet;
 
 use crate::{
-    node::{ElementNode, FromAnyValue, NodeType, OwnedAttributeView},
+    node::{ElementNode, FromAnyValue, NodeType, OwnedAttributeView, PseudoClasses},
     NodeId,
 };
 
@@ -12,6 +12,7 @@ use crate::{
 pub struct NodeView<'a, V: FromAnyValue = ()> {
     id: NodeId,
     inner: &'a NodeType<V>,
+    pseudo_classes: PseudoClasses,
     mask: &'a NodeMask,
 }
 
@@ -20,11 +21,19 @@ impl<'a, V: FromAnyValue> NodeView<'a, V> {
     pub fn new(id: NodeId, node: &'a NodeType<V>, view: &'a NodeMask) -> Self {
         Self {
             inner: node,
+            pseudo_classes: PseudoClasses::default(),
             mask: view,
             id,
         }
     }
 
+    /// Set the pseudo-classes of the element. The pseudo-classes are stored next to the node, so they are not part of
+    /// the [NodeType].
+    pub fn with_pseudo_classes(mut self, pseudo_classes: PseudoClasses) -> Self {
+        self.pseudo_classes = pseudo_classes;
+        self
+    }
+
     /// Get the node id of the node
     pub fn node_id(&self) -> NodeId {
         self.id
@@ -95,6 +104,17 @@ impl<'a, V: FromAnyValue> NodeView<'a, V> {
             None
         }
     }
+
+    /// Get the pseudo-classes of the element if they are enabled in the mask
+    pub fn pseudo_classes(&self) -> Option<PseudoClasses> {
+        self.mask
+            .pseudo_classes
+            .then_some(match &self.inner {
+                NodeType::Element(_) => Some(self.pseudo_classes),
+                _ => None,
+            })
+            .flatten()
+    }
 }
 
 /// A mask that contains a list of attributes that are visible.
@@ -159,6 +179,7 @@ pub struct NodeMask {
     namespace: bool,
     text: bool,
     listeners: bool,
+    pseudo_classes: bool,
 }
 
 impl NodeMask {
@@ -169,6 +190,7 @@ impl NodeMask {
             || self.attritutes.overlaps(&other.attritutes)
             || (self.text && other.text)
             || (self.listeners && other.listeners)
+            || (self.pseudo_classes && other.pseudo_classes)
     }
 
     /// Combine two node masks
@@ -179,6 +201,7 @@ impl NodeMask {
             namespace: self.namespace | other.namespace,
             text: self.text | other.text,
             listeners: self.listeners | other.listeners,
+            pseudo_classes: self.pseudo_classes | other.pseudo_classes,
         }
     }
 
@@ -231,6 +254,16 @@ impl NodeMask {
     pub fn listeners(&self) -> bool {
         self.listeners
     }
+
+    /// Set the mask to view the pseudo-classes
+    pub fn set_pseudo_classes(&mut self) {
+        self.pseudo_classes = true;
+    }
+
+    /// Get the mask for the pseudo-classes
+    pub fn pseudo_classes(&self) -> bool {
+        self.pseudo_classes
+    }
 }
 
 /// A builder for a mask that controls what attributes are visible.
@@ -256,6 +289,7 @@ pub struct NodeMaskBuilder<'a> {
     namespace: bool,
     text: bool,
     listeners: bool,
+    pseudo_classes: bool,
 }
 
 impl<'a> NodeMaskBuilder<'a> {
@@ -266,7 +300,8 @@ impl<'a> NodeMaskBuilder<'a> {
         .with_attrs(AttributeMaskBuilder::All)
         .with_text()
         .with_element()
-        .with_listeners();
+        .with_listeners()
+        .with_pseudo_classes();
 
     /// Create a empty node mask
     pub const fn new() -> Self {
@@ -276,6 +311,7 @@ impl<'a> NodeMaskBuilder<'a> {
             namespace: false,
             text: false,
             listeners: false,
+            pseudo_classes: false,
         }
     }
 
@@ -314,6 +350,12 @@ impl<'a> NodeMaskBuilder<'a> {
         self
     }
 
+    /// Allow the mask to view the pseudo-classes
+    pub const fn with_pseudo_classes(mut self) -> Self {
+        self.pseudo_classes = true;
+        self
+    }
+
     /// Build the mask
     pub fn build(self) -> NodeMask {
         NodeMask {
@@ -327,6 +369,7 @@ impl<'a> NodeMaskBuilder<'a> {
             namespace: self.namespace,
             text: self.text,
             listeners: self.listeners,
+            pseudo_classes: self.pseudo_classes,
         }
     }
 }
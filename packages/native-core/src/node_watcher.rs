@@ -10,10 +10,18 @@ pub trait NodeWatcher<V: FromAnyValue + Send + Sync> {
     fn on_node_removed(&mut self, _node: NodeMut<V>) {}
     /// Called after a node is moved to a new parent.
     fn on_node_moved(&mut self, _node: NodeMut<V>) {}
+    /// Check if the watcher still needs to be called. Watchers that are no longer active are removed from the dom.
+    fn is_active(&self) -> bool {
+        true
+    }
 }
 
 /// A trait for watching for changes to attributes of an element.
 pub trait AttributeWatcher<V: FromAnyValue + Send + Sync> {
     /// Called before update_state is called on the RealDom
     fn on_attributes_changed(&self, _node: NodeMut<V>, _attributes: &AttributeMask) {}
+    /// Check if the watcher still needs to be called. Watchers that are no longer active are removed from the dom.
+    fn is_active(&self) -> bool {
+        true
+    }
 }
@@ -1,13 +1,18 @@
 use parking_lot::RwLock;
+use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
-use shipyard::{Borrow, BorrowInfo, Component, Unique, UniqueView, View, WorkloadSystem};
+use shipyard::track::Untracked;
+use shipyard::{
+    AddComponent, Borrow, BorrowInfo, Component, Remove, Unique, UniqueView, View, ViewMut,
+    WorkloadSystem,
+};
 use std::any::{Any, TypeId};
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::sync::Arc;
 
-use crate::node::{FromAnyValue, NodeType};
+use crate::node::{FromAnyValue, NodeType, PseudoClasses};
 use crate::node_ref::{NodeMaskBuilder, NodeView};
 use crate::real_dom::{DirtyNodesResult, SendAnyMapWrapper};
 use crate::tree::{TreeRef, TreeRefView};
@@ -23,23 +28,16 @@ impl DirtyNodes {
     pub fn add_node(&mut self, node_id: NodeId) {
         self.nodes_dirty.insert(node_id);
     }
-
-    pub fn is_empty(&self) -> bool {
-        self.nodes_dirty.is_empty()
-    }
-
-    pub fn pop(&mut self) -> Option<NodeId> {
-        self.nodes_dirty.iter().next().copied().map(|id| {
-            self.nodes_dirty.remove(&id);
-            id
-        })
-    }
 }
 
 /// Tracks the dirty nodes sorted by height for each pass. We resolve passes based on the height of the node in order to avoid resolving any node twice in a pass.
 #[derive(Clone, Unique)]
 pub struct DirtyNodeStates {
     dirty: Arc<FxHashMap<TypeId, RwLock<BTreeMap<u16, DirtyNodes>>>>,
+    /// The nodes in frozen subtrees, which are never marked as dirty
+    frozen: Arc<FxHashMap<NodeId, NodeId>>,
+    /// Resolve the dirty nodes at each height on the rayon thread pool
+    parallel: bool,
 }
 
 impl DirtyNodeStates {
@@ -50,10 +48,25 @@ impl DirtyNodeStates {
                     .map(|pass| (pass, RwLock::new(BTreeMap::new())))
                     .collect(),
             ),
+            frozen: Default::default(),
+            parallel: false,
         }
     }
 
+    /// Skip the nodes in frozen subtrees when marking nodes as dirty
+    pub(crate) fn with_frozen(self, frozen: Arc<FxHashMap<NodeId, NodeId>>) -> Self {
+        Self { frozen, ..self }
+    }
+
+    /// Resolve the nodes at each height in parallel
+    pub(crate) fn with_parallel(self, parallel: bool) -> Self {
+        Self { parallel, ..self }
+    }
+
     pub fn insert(&self, pass_id: TypeId, node_id: NodeId, height: u16) {
+        if self.frozen.contains_key(&node_id) {
+            return;
+        }
         if let Some(btree) = self.dirty.get(&pass_id) {
             let mut write = btree.write();
             if let Some(entry) = write.get_mut(&height) {
@@ -66,30 +79,16 @@ impl DirtyNodeStates {
         }
     }
 
-    fn pop_front(&self, pass_id: TypeId) -> Option<(u16, NodeId)> {
+    /// Take every dirty node at the next height the pass is resolved at. Passes from the parent to the children start
+    /// at the root, every other pass starts at the leaves
+    fn pop_level(&self, pass_id: TypeId, direction: PassDirection) -> Option<(u16, Vec<NodeId>)> {
         let mut values = self.dirty.get(&pass_id)?.write();
-        let mut value = values.first_entry()?;
-        let height = *value.key();
-        let ids = value.get_mut();
-        let id = ids.pop()?;
-        if ids.is_empty() {
-            value.remove_entry();
-        }
+        let (height, nodes) = match direction {
+            PassDirection::ParentToChild => values.pop_first()?,
+            PassDirection::ChildToParent | PassDirection::AnyOrder => values.pop_last()?,
+        };
 
-        Some((height, id))
-    }
-
-    fn pop_back(&self, pass_id: TypeId) -> Option<(u16, NodeId)> {
-        let mut values = self.dirty.get(&pass_id)?.write();
-        let mut value = values.last_entry()?;
-        let height = *value.key();
-        let ids = value.get_mut();
-        let id = ids.pop()?;
-        if ids.is_empty() {
-            value.remove_entry();
-        }
-
-        Some((height, id))
+        Some((height, nodes.nodes_dirty.into_iter().collect()))
     }
 }
 
@@ -181,20 +180,36 @@ fn pass_direction<V: FromAnyValue + Send + Sync, S: State<V>>() -> PassDirection
 pub struct RunPassView<'a, V: FromAnyValue + Send + Sync = ()> {
     pub tree: TreeRefView<'a>,
     pub node_type: View<'a, NodeType<V>>,
+    pub pseudo_classes: View<'a, PseudoClasses>,
     dirty_nodes_result: UniqueView<'a, DirtyNodesResult>,
     node_states: UniqueView<'a, DirtyNodeStates>,
     any_map: UniqueView<'a, SendAnyMapWrapper>,
 }
 
+/// The result of resolving a state of a node
+#[doc(hidden)]
+pub enum NodeUpdate<S> {
+    /// The node already had the state. Contains if the state changed
+    Updated(bool),
+    /// The node didn't have the state yet, so a new state was created
+    Created(S),
+}
+
 // This is used by the macro
 /// Updates the given pass, marking any nodes that were changed
+///
+/// The nodes are resolved one height at a time. A node only reads the states of nodes at other heights, so every
+/// node at a height is resolved independently and, for [`crate::prelude::RealDom::update_state_parallel`], in
+/// parallel. The states of the nodes at a height are moved out of the storage while they are resolved, and put back
+/// after every node at the height is resolved.
 #[doc(hidden)]
-pub fn run_pass<V: FromAnyValue + Send + Sync>(
+pub fn run_pass<V: FromAnyValue + Send + Sync, S: Component<Tracking = Untracked> + Send + Sync>(
     type_id: TypeId,
     dependants: Arc<Dependants>,
     pass_direction: PassDirection,
     view: RunPassView<V>,
-    mut update_node: impl FnMut(NodeId, &SendAnyMap) -> bool,
+    states: &mut ViewMut<S>,
+    update_node: impl Fn(NodeId, Option<&mut S>, &ViewMut<S>, &SendAnyMap) -> NodeUpdate<S> + Sync,
 ) {
     let RunPassView {
         tree,
@@ -204,29 +219,36 @@ pub fn run_pass<V: FromAnyValue + Send + Sync>(
         ..
     } = view;
     let ctx = ctx.as_ref();
-    match pass_direction {
-        PassDirection::ParentToChild => {
-            while let Some((height, id)) = dirty.pop_front(type_id) {
-                if (update_node)(id, ctx) {
-                    nodes_updated.insert(id);
-                    dependants.mark_dirty(&dirty, id, &tree, height);
+    while let Some((height, ids)) = dirty.pop_level(type_id, pass_direction) {
+        // The states of the nodes at the height are taken out of the storage, so each one is owned by the thread
+        // resolving its node while the storage is only read for the states of nodes at other heights
+        let nodes: Vec<_> = ids.into_iter().map(|id| (id, states.remove(id))).collect();
+
+        let shared: &ViewMut<S> = states;
+        let resolve = |(id, mut state): (NodeId, Option<S>)| {
+            let update = update_node(id, state.as_mut(), shared, ctx);
+            (id, state, update)
+        };
+        let resolved: Vec<_> = if dirty.parallel {
+            nodes.into_par_iter().map(resolve).collect()
+        } else {
+            nodes.into_iter().map(resolve).collect()
+        };
+
+        for (id, mut state, update) in resolved {
+            let changed = match update {
+                NodeUpdate::Updated(changed) => changed,
+                NodeUpdate::Created(created) => {
+                    state = Some(created);
+                    true
                 }
+            };
+            if let Some(state) = state {
+                states.add_component_unchecked(id, state);
             }
-        }
-        PassDirection::ChildToParent => {
-            while let Some((height, id)) = dirty.pop_back(type_id) {
-                if (update_node)(id, ctx) {
-                    nodes_updated.insert(id);
-                    dependants.mark_dirty(&dirty, id, &tree, height);
-                }
-            }
-        }
-        PassDirection::AnyOrder => {
-            while let Some((height, id)) = dirty.pop_back(type_id) {
-                if (update_node)(id, ctx) {
-                    nodes_updated.insert(id);
-                    dependants.mark_dirty(&dirty, id, &tree, height);
-                }
+            if changed {
+                nodes_updated.insert(id);
+                dependants.mark_dirty(&dirty, id, &tree, height);
             }
         }
     }
@@ -257,7 +279,7 @@ impl Dependants {
         } in &self.child
         {
             for id in tree.children_ids_advanced(id, enter_shadow_dom) {
-                dirty.insert(type_id, id, height + 1);
+                dirty.insert(type_id, id, height.saturating_add(1));
             }
         }
 
@@ -267,7 +289,7 @@ impl Dependants {
         } in &self.parent
         {
             if let Some(id) = tree.parent_id_advanced(id, enter_shadow_dom) {
-                dirty.insert(type_id, id, height - 1);
+                dirty.insert(type_id, id, height.saturating_sub(1));
             }
         }
 
@@ -0,0 +1,301 @@
+//! Find the nodes of the dom that match a selector.
+//!
+//! A [`Query`] matches elements by their tag, id, classes and attributes, like a compound css selector. Queries can be
+//! resolved once with [`Query::find`], or turned into a [`LiveQuery`] with [`Query::live`] that keeps its matches up to
+//! date as the dom changes, so renderers don't have to walk the whole tree to find the nodes they care about.
+//!
+//! ```rust
+//! use dioxus_native_core::query::Query;
+//!
+//! let text_inputs = Query::tag("input").attr("type", "text");
+//! // the same query written as a selector
+//! assert_eq!(Query::parse("input[type=text]"), Some(text_inputs));
+//! ```
+
+use std::sync::{Arc, Weak};
+
+use parking_lot::RwLock;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    node::{FromAnyValue, NodeType},
+    node_ref::AttributeMask,
+    node_watcher::{AttributeWatcher, NodeWatcher},
+    prelude::*,
+};
+
+/// A selector that matches elements in the dom.
+///
+/// Every part of the query must match for an element to match. A query without any parts matches every element.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Query {
+    tag: Option<String>,
+    attributes: Vec<(String, AttributeMatch)>,
+}
+
+/// How an attribute of an element is matched
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum AttributeMatch {
+    /// The element has the attribute, with any value
+    Exists,
+    /// The value of the attribute is the text
+    Equals(String),
+    /// The value of the attribute is a list separated by whitespace that contains the text
+    Contains(String),
+}
+
+impl Query {
+    /// Match every element.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Match the elements with a tag.
+    pub fn tag(tag: impl Into<String>) -> Self {
+        Self {
+            tag: Some(tag.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Only match the elements where an attribute has a value. Values that are not text are compared to the text they
+    /// are displayed as.
+    pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes
+            .push((name.into(), AttributeMatch::Equals(value.into())));
+        self
+    }
+
+    /// Only match the elements that have an attribute, with any value.
+    pub fn has_attr(mut self, name: impl Into<String>) -> Self {
+        self.attributes.push((name.into(), AttributeMatch::Exists));
+        self
+    }
+
+    /// Only match the elements with an id.
+    pub fn id(self, id: impl Into<String>) -> Self {
+        self.attr("id", id)
+    }
+
+    /// Only match the elements with a class.
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.attributes
+            .push(("class".to_string(), AttributeMatch::Contains(class.into())));
+        self
+    }
+
+    /// Parse a compound selector like `input#name.large[type=text][disabled]`. Combinators like `div > input` and
+    /// pseudo classes are not supported.
+    ///
+    /// Returns [`None`] if the selector is invalid.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let selector = selector.trim();
+        let tag_end = selector.find(['#', '.', '[']).unwrap_or(selector.len());
+        let mut query = match &selector[..tag_end] {
+            "" | "*" => Query::any(),
+            tag if tag.chars().all(is_name_char) => Query::tag(tag),
+            _ => return None,
+        };
+
+        let mut rest = &selector[tag_end..];
+        while let Some(prefix) = rest.chars().next() {
+            rest = &rest[prefix.len_utf8()..];
+            if prefix == '[' {
+                let end = rest.find(']')?;
+                let (name, value) = match rest[..end].split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(unquote(value.trim()))),
+                    None => (rest[..end].trim(), None),
+                };
+                if name.is_empty() || !name.chars().all(is_name_char) {
+                    return None;
+                }
+                query = match value {
+                    Some(value) => query.attr(name, value),
+                    None => query.has_attr(name),
+                };
+                rest = &rest[end + 1..];
+            } else {
+                let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+                let name = &rest[..end];
+                query = match prefix {
+                    '#' if !name.is_empty() => query.id(name),
+                    '.' if !name.is_empty() => query.class(name),
+                    _ => return None,
+                };
+                rest = &rest[end..];
+            }
+        }
+
+        Some(query)
+    }
+
+    /// Check if a node matches the query. Only elements can match.
+    pub fn matches<V: FromAnyValue>(&self, node_type: &NodeType<V>) -> bool {
+        let NodeType::Element(element) = node_type else {
+            return false;
+        };
+        if let Some(tag) = &self.tag {
+            if element.tag != *tag {
+                return false;
+            }
+        }
+        self.attributes.iter().all(|(name, expected)| {
+            let value = element
+                .attributes
+                .iter()
+                .find(|(attribute, _)| attribute.name == *name)
+                .map(|(_, value)| value);
+            match (expected, value) {
+                (_, None) => false,
+                (AttributeMatch::Exists, Some(_)) => true,
+                (AttributeMatch::Equals(expected), Some(value)) => match value.as_text() {
+                    Some(text) => text == expected,
+                    None => value.to_string() == *expected,
+                },
+                (AttributeMatch::Contains(expected), Some(value)) => value
+                    .as_text()
+                    .is_some_and(|text| text.split_whitespace().any(|item| item == expected)),
+            }
+        })
+    }
+
+    /// Find every node in the dom that matches the query, in the order of the tree.
+    pub fn find<V: FromAnyValue + Send + Sync>(&self, rdom: &RealDom<V>) -> Vec<NodeId> {
+        let mut matches = Vec::new();
+        rdom.traverse_depth_first(|node| {
+            if self.matches(&*node.node_type()) {
+                matches.push(node.id());
+            }
+        });
+        matches
+    }
+
+    /// Find the first node in the order of the tree that matches the query.
+    pub fn first<V: FromAnyValue + Send + Sync>(&self, rdom: &RealDom<V>) -> Option<NodeId> {
+        // the traversal can't stop early, so only the first match is kept
+        let mut first = None;
+        rdom.traverse_depth_first(|node| {
+            if first.is_none() && self.matches(&*node.node_type()) {
+                first = Some(node.id());
+            }
+        });
+        first
+    }
+
+    /// Keep the matches of the query up to date as the dom changes. Nodes that are added or have their attributes
+    /// changed are checked again when [`RealDom::update_state`] is called, and removed nodes are removed from the
+    /// matches right away. Once every clone of the live query is dropped, the dom removes its watchers.
+    pub fn live<V: FromAnyValue + Send + Sync>(self, rdom: &mut RealDom<V>) -> LiveQuery {
+        let matches: FxHashSet<NodeId> = self.find(rdom).into_iter().collect();
+        let matches = Arc::new(RwLock::new(matches));
+        let watcher = QueryWatcher {
+            query: Arc::new(self),
+            matches: Arc::downgrade(&matches),
+        };
+        rdom.add_node_watcher(watcher.clone());
+        rdom.add_attribute_watcher(watcher);
+        LiveQuery { matches }
+    }
+
+    /// Check if a change to some attributes can change if an element matches the query
+    fn depends_on(&self, attributes: &AttributeMask) -> bool {
+        match attributes {
+            AttributeMask::All => true,
+            AttributeMask::Some(_) => self
+                .attributes
+                .iter()
+                .any(|(name, _)| attributes.contains(name)),
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// The nodes that match a [`Query`], kept up to date as the dom changes. Created with [`Query::live`].
+///
+/// The matches are shared between clones of the live query. Once every clone is dropped, the dom stops updating the
+/// matches.
+#[derive(Clone)]
+pub struct LiveQuery {
+    matches: Arc<RwLock<FxHashSet<NodeId>>>,
+}
+
+impl LiveQuery {
+    /// The nodes that match the query, in no particular order.
+    pub fn ids(&self) -> Vec<NodeId> {
+        self.matches.read().iter().copied().collect()
+    }
+
+    /// Check if a node matches the query.
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.matches.read().contains(&id)
+    }
+
+    /// The number of nodes that match the query.
+    pub fn len(&self) -> usize {
+        self.matches.read().len()
+    }
+
+    /// Check if no node matches the query.
+    pub fn is_empty(&self) -> bool {
+        self.matches.read().is_empty()
+    }
+}
+
+/// Updates the matches of a [`LiveQuery`] when nodes are added, removed or changed
+#[derive(Clone)]
+struct QueryWatcher {
+    query: Arc<Query>,
+    matches: Weak<RwLock<FxHashSet<NodeId>>>,
+}
+
+impl QueryWatcher {
+    fn check<V: FromAnyValue + Send + Sync>(&self, node: &NodeMut<V>) {
+        if let Some(matches) = self.matches.upgrade() {
+            let mut matches = matches.write();
+            if self.query.matches(&*node.node_type()) {
+                matches.insert(node.id());
+            } else {
+                matches.remove(&node.id());
+            }
+        }
+    }
+}
+
+impl<V: FromAnyValue + Send + Sync> NodeWatcher<V> for QueryWatcher {
+    fn on_node_added(&mut self, node: NodeMut<V>) {
+        self.check(&node);
+    }
+
+    fn is_active(&self) -> bool {
+        self.matches.strong_count() > 0
+    }
+
+    fn on_node_removed(&mut self, node: NodeMut<V>) {
+        if let Some(matches) = self.matches.upgrade() {
+            matches.write().remove(&node.id());
+        }
+    }
+}
+
+impl<V: FromAnyValue + Send + Sync> AttributeWatcher<V> for QueryWatcher {
+    fn on_attributes_changed(&self, node: NodeMut<V>, attributes: &AttributeMask) {
+        if self.query.depends_on(attributes) {
+            self.check(&node);
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.matches.strong_count() > 0
+    }
+}
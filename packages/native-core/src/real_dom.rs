@@ -3,7 +3,10 @@
 use rustc_hash::{FxHashMap, FxHashSet};
 use shipyard::error::GetStorage;
 use shipyard::track::Untracked;
-use shipyard::{Component, Get, IntoBorrow, ScheduledWorkload, Unique, View, ViewMut, Workload};
+use shipyard::{
+    Component, Get, IntoBorrow, IntoIter, IntoWithId, ScheduledWorkload, Unique, View, ViewMut,
+    Workload,
+};
 use shipyard::{SystemModificator, World};
 use std::any::TypeId;
 use std::collections::VecDeque;
@@ -15,8 +18,10 @@ use crate::custom_element::{
     CustomElementUpdater,
 };
 use crate::node::{
-    ElementNode, FromAnyValue, NodeType, OwnedAttributeDiscription, OwnedAttributeValue, TextNode,
+    ElementNode, FromAnyValue, NodeType, OwnedAttributeDiscription, OwnedAttributeValue,
+    PseudoClass, PseudoClasses, TextNode,
 };
+use crate::node_data::{NodeData, NodeHandle};
 use crate::node_ref::{NodeMask, NodeMaskBuilder};
 use crate::node_watcher::{AttributeWatcher, NodeWatcher};
 use crate::passes::{Dependant, DirtyNodeStates, PassDirection, TypeErasedState};
@@ -49,17 +54,47 @@ impl Deref for DirtyNodesResult {
     }
 }
 
+/// The subtrees of the RealDom that are frozen. See [`RealDom::freeze`]
+#[derive(Default)]
+struct FrozenSubtrees {
+    /// The nodes in each frozen subtree, keyed by the root of the subtree
+    roots: FxHashMap<NodeId, Vec<NodeId>>,
+    /// The root of the frozen subtree every frozen node is in. This is shared with the passes so they can skip frozen nodes
+    nodes: Arc<FxHashMap<NodeId, NodeId>>,
+    /// The roots that will be frozen after the next state update
+    pending: Vec<NodeId>,
+}
+
 /// The nodes that have been marked as dirty in the RealDom
 pub(crate) struct NodesDirty<V: FromAnyValue + Send + Sync> {
     passes_updated: FxHashMap<NodeId, FxHashSet<TypeId>>,
     nodes_updated: FxHashMap<NodeId, NodeMask>,
     nodes_created: FxHashSet<NodeId>,
+    frozen: FrozenSubtrees,
     pub(crate) passes: Box<[TypeErasedState<V>]>,
 }
 
 impl<V: FromAnyValue + Send + Sync> NodesDirty<V> {
+    /// Unfreeze the frozen subtree a node is in, if it is in one. The states of the subtree were not updated while it
+    /// was frozen, so every state in the subtree is marked as dirty
+    fn unfreeze(&mut self, node_id: NodeId) {
+        let Some(&root) = self.frozen.nodes.get(&node_id) else {
+            return;
+        };
+        let nodes = self.frozen.roots.remove(&root).unwrap_or_default();
+        let frozen_nodes = Arc::make_mut(&mut self.frozen.nodes);
+        for id in nodes {
+            frozen_nodes.remove(&id);
+            self.passes_updated
+                .entry(id)
+                .or_default()
+                .extend(self.passes.iter().map(|x| x.this_type_id));
+        }
+    }
+
     /// Mark a node as dirty
     fn mark_dirty(&mut self, node_id: NodeId, mask: NodeMask) {
+        self.unfreeze(node_id);
         self.passes_updated.entry(node_id).or_default().extend(
             self.passes
                 .iter()
@@ -73,8 +108,25 @@ impl<V: FromAnyValue + Send + Sync> NodesDirty<V> {
         }
     }
 
+    /// Mark one state of a node as dirty, if the state is registered with the RealDom
+    pub(crate) fn mark_state_dirty(&mut self, node_id: NodeId, pass: TypeId) {
+        if self.passes.iter().any(|x| x.this_type_id == pass) {
+            self.unfreeze(node_id);
+            self.passes_updated.entry(node_id).or_default().insert(pass);
+        }
+    }
+
+    /// Mark a state of a node as up to date
+    #[cfg(feature = "serialize")]
+    pub(crate) fn mark_clean(&mut self, node_id: NodeId, pass: TypeId) {
+        if let Some(passes) = self.passes_updated.get_mut(&node_id) {
+            passes.remove(&pass);
+        }
+    }
+
     /// Mark a node that has had a parent changed
     fn mark_parent_added_or_removed(&mut self, node_id: NodeId) {
+        self.unfreeze(node_id);
         let hm = self.passes_updated.entry(node_id).or_default();
         for pass in &*self.passes {
             // If any of the states in this node depend on the parent then mark them as dirty
@@ -86,6 +138,7 @@ impl<V: FromAnyValue + Send + Sync> NodesDirty<V> {
 
     /// Mark a node as having a child added or removed
     fn mark_child_changed(&mut self, node_id: NodeId) {
+        self.unfreeze(node_id);
         let hm = self.passes_updated.entry(node_id).or_default();
         for pass in &*self.passes {
             // If any of the states in this node depend on the children then mark them as dirty
@@ -118,6 +171,7 @@ pub struct RealDom<V: FromAnyValue + Send + Sync = ()> {
     workload: ScheduledWorkload,
     root_id: NodeId,
     custom_elements: Arc<RwLock<CustomElementRegistry<V>>>,
+    node_data: NodeData,
     phantom: std::marker::PhantomData<V>,
 }
 
@@ -210,12 +264,14 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
                 nodes_updated,
                 passes: tracked_states,
                 nodes_created: [root_id].into_iter().collect(),
+                frozen: FrozenSubtrees::default(),
             },
             node_watchers: Default::default(),
             attribute_watchers: Default::default(),
             workload,
             root_id,
             custom_elements: Default::default(),
+            node_data: Default::default(),
             phantom: std::marker::PhantomData,
         }
     }
@@ -298,6 +354,41 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
         contains.then(|| NodeMut::new(id, self))
     }
 
+    /// Get a weak handle to a node that can be stored without borrowing the dom.
+    pub fn handle(&self, id: NodeId) -> Option<NodeHandle> {
+        self.contains(id).then(|| NodeHandle::new(id))
+    }
+
+    /// Attach data to a node, returning the data of the same type that was attached before. The data is dropped when the node is removed.
+    ///
+    /// This is useful for data that renderers keep next to nodes, like GPU textures or cached glyph runs. Unlike states, the dom never reads the data or marks nodes dirty when it changes.
+    pub fn insert_node_data<T: Send + Sync + 'static>(
+        &mut self,
+        id: NodeId,
+        value: T,
+    ) -> Option<T> {
+        debug_assert!(
+            self.contains(id),
+            "tried to attach data to a node that was removed"
+        );
+        self.node_data.insert(id, value)
+    }
+
+    /// Get the data of a type attached to a node.
+    pub fn node_data<T: Send + Sync + 'static>(&self, id: NodeId) -> Option<&T> {
+        self.node_data.get(id)
+    }
+
+    /// Get the data of a type attached to a node mutably.
+    pub fn node_data_mut<T: Send + Sync + 'static>(&mut self, id: NodeId) -> Option<&mut T> {
+        self.node_data.get_mut(id)
+    }
+
+    /// Detach the data of a type from a node.
+    pub fn remove_node_data<T: Send + Sync + 'static>(&mut self, id: NodeId) -> Option<T> {
+        self.node_data.remove(id)
+    }
+
     /// Borrow a component from the world without updating the dirty nodes.
     fn borrow_raw<'a, B: IntoBorrow>(&'a self) -> Result<B, GetStorage>
     where
@@ -315,6 +406,29 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
     pub fn update_state(
         &mut self,
         ctx: SendAnyMap,
+    ) -> (FxDashSet<NodeId>, FxHashMap<NodeId, NodeMask>) {
+        self.update_state_with(ctx, false)
+    }
+
+    /// Update the state of the dom like [`RealDom::update_state`], but resolve the nodes of each state on the rayon
+    /// thread pool.
+    ///
+    /// The nodes a state depends on are always at another height in the tree, so the dirty nodes at each height are
+    /// resolved in parallel: the subtrees of a node for states that depend on the parent, and the parents of the leaves
+    /// for states that depend on the children. This is faster for large doms with many dirty nodes, but the overhead
+    /// of moving the work to other threads makes it slower for small updates. Enable the `parallel` feature to also
+    /// resolve states that don't depend on each other at the same time.
+    pub fn update_state_parallel(
+        &mut self,
+        ctx: SendAnyMap,
+    ) -> (FxDashSet<NodeId>, FxHashMap<NodeId, NodeMask>) {
+        self.update_state_with(ctx, true)
+    }
+
+    fn update_state_with(
+        &mut self,
+        ctx: SendAnyMap,
+        parallel: bool,
     ) -> (FxDashSet<NodeId>, FxHashMap<NodeId, NodeMask>) {
         let nodes_created = std::mem::take(&mut self.dirty_nodes.nodes_created);
 
@@ -324,6 +438,7 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
 
             // ignore watchers if they are already being modified
             if let Ok(mut watchers) = watchers.try_write() {
+                watchers.retain(|watcher| watcher.is_active());
                 for id in &nodes_created {
                     for watcher in &mut *watchers {
                         watcher.on_node_added(NodeMut::new(*id, self));
@@ -332,7 +447,6 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
             };
         }
 
-        let passes = std::mem::take(&mut self.dirty_nodes.passes_updated);
         let nodes_updated = std::mem::take(&mut self.dirty_nodes.nodes_updated);
 
         for (node_id, mask) in &nodes_updated {
@@ -340,6 +454,7 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
                 // call attribute watchers but ignore watchers if they are already being modified
                 let watchers = self.attribute_watchers.clone();
                 if let Ok(mut watchers) = watchers.try_write() {
+                    watchers.retain(|watcher| watcher.is_active());
                     for watcher in &mut *watchers {
                         watcher.on_attributes_changed(
                             self.get_mut(*node_id).unwrap(),
@@ -358,8 +473,12 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
             }
         }
 
+        // take the passes after the watchers run so any states they mark as dirty are updated in this pass
+        let passes = std::mem::take(&mut self.dirty_nodes.passes_updated);
         let dirty_nodes =
-            DirtyNodeStates::with_passes(self.dirty_nodes.passes.iter().map(|p| p.this_type_id));
+            DirtyNodeStates::with_passes(self.dirty_nodes.passes.iter().map(|p| p.this_type_id))
+                .with_frozen(self.dirty_nodes.frozen.nodes.clone())
+                .with_parallel(parallel);
         let tree = self.tree_ref();
         for (node_id, passes) in passes {
             // remove any nodes that were created and then removed in the same mutations from the dirty nodes list
@@ -369,6 +488,7 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
                 }
             }
         }
+        drop(tree);
 
         let _ = self.world.remove_unique::<DirtyNodeStates>();
         let _ = self.world.remove_unique::<SendAnyMapWrapper>();
@@ -380,17 +500,87 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
 
         let dirty = self.world.remove_unique::<DirtyNodesResult>().unwrap();
 
+        self.freeze_pending();
+
         (dirty.0, nodes_updated)
     }
 
+    /// Freeze the subtree of a node. Frozen subtrees are static content: [`RealDom::update_state`] skips the states of
+    /// the nodes in them, even if the parent of the subtree changes.
+    ///
+    /// The subtree is frozen after the next call to [`RealDom::update_state`], so its states are up to date when it is
+    /// frozen. If a node in the subtree is changed, the subtree is unfrozen and all of its states are updated again.
+    pub fn freeze(&mut self, id: NodeId) {
+        self.dirty_nodes.frozen.pending.push(id);
+    }
+
+    /// Unfreeze the frozen subtree a node is in, and update all of its states in the next call to
+    /// [`RealDom::update_state`]. Does nothing if the node is not frozen.
+    pub fn unfreeze(&mut self, id: NodeId) {
+        self.dirty_nodes
+            .frozen
+            .pending
+            .retain(|&pending| pending != id);
+        self.dirty_nodes.unfreeze(id);
+    }
+
+    /// Check if a node is in a frozen subtree.
+    pub fn is_frozen(&self, id: NodeId) -> bool {
+        self.dirty_nodes.frozen.nodes.contains_key(&id)
+    }
+
+    fn freeze_pending(&mut self) {
+        let pending = std::mem::take(&mut self.dirty_nodes.frozen.pending);
+        for root in pending {
+            if !self.contains(root) || self.is_frozen(root) {
+                continue;
+            }
+            let mut subtree = Vec::new();
+            let mut stack = vec![root];
+            let tree = self.tree_ref();
+            while let Some(id) = stack.pop() {
+                subtree.push(id);
+                stack.extend(tree.children_ids_advanced(id, true));
+            }
+            drop(tree);
+
+            let frozen = &mut self.dirty_nodes.frozen;
+            let frozen_nodes = Arc::make_mut(&mut frozen.nodes);
+            for &id in &subtree {
+                // Frozen subtrees inside of this subtree are merged into it
+                if let Some(old_root) = frozen_nodes.insert(id, root) {
+                    frozen.roots.remove(&old_root);
+                }
+            }
+            frozen.roots.insert(root, subtree);
+        }
+    }
+
     /// Traverses the dom in a depth first manner, calling the provided function on each node.
     /// If `enter_shadow_dom` is true, then the traversal will enter shadow doms in the tree.
-    pub fn traverse_depth_first_advanced(
+    pub fn traverse_depth_first_advanced(&self, enter_shadow_dom: bool, f: impl FnMut(NodeRef<V>)) {
+        self.traverse_depth_first_from(self.root_id(), enter_shadow_dom, f)
+    }
+
+    /// Traverses the dom in a depth first manner, calling the provided function on each node.
+    pub fn traverse_depth_first(&self, f: impl FnMut(NodeRef<V>)) {
+        self.traverse_depth_first_advanced(true, f)
+    }
+
+    /// Traverses a node and its children in a depth first manner, calling the provided function on each node. Unlike
+    /// [`RealDom::traverse_depth_first`], this can walk subtrees that are not in the dom's tree, like the ones split off
+    /// with [`NodeMut::split_off`].
+    pub fn traverse_subtree_depth_first(&self, id: NodeId, f: impl FnMut(NodeRef<V>)) {
+        self.traverse_depth_first_from(id, true, f)
+    }
+
+    fn traverse_depth_first_from(
         &self,
+        id: NodeId,
         enter_shadow_dom: bool,
         mut f: impl FnMut(NodeRef<V>),
     ) {
-        let mut stack = vec![self.root_id()];
+        let mut stack = vec![id];
         let tree = self.tree_ref();
         while let Some(id) = stack.pop() {
             if let Some(node) = self.get(id) {
@@ -401,9 +591,19 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
         }
     }
 
-    /// Traverses the dom in a depth first manner, calling the provided function on each node.
-    pub fn traverse_depth_first(&self, f: impl FnMut(NodeRef<V>)) {
-        self.traverse_depth_first_advanced(true, f)
+    /// The nodes other than the root of the dom that don't have a parent, in no particular order. These are the
+    /// subtrees split off with [`NodeMut::split_off`] and the nodes that were created but never added to the dom. They
+    /// are kept until they are removed with [`NodeMut::remove`].
+    pub fn detached_roots(&self) -> Vec<NodeId> {
+        let tree = self.tree_ref();
+        tree.iter()
+            .ids()
+            .filter(|&id| {
+                id != self.root_id
+                    && tree.parent_id(id).is_none()
+                    && tree.root_for_light_tree(id).is_none()
+            })
+            .collect()
     }
 
     /// Traverses the dom in a breadth first manner, calling the provided function on each node.
@@ -486,7 +686,9 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
 
     /// Adds a [`NodeWatcher`] to the dom. Node watchers are called whenever a node is created or removed.
     pub fn add_node_watcher(&mut self, watcher: impl NodeWatcher<V> + 'static + Send + Sync) {
-        self.node_watchers.write().unwrap().push(Box::new(watcher));
+        let mut watchers = self.node_watchers.write().unwrap();
+        watchers.retain(|watcher| watcher.is_active());
+        watchers.push(Box::new(watcher));
     }
 
     /// Adds an [`AttributeWatcher`] to the dom. Attribute watchers are called whenever an attribute is changed.
@@ -494,10 +696,9 @@ impl<V: FromAnyValue + Send + Sync> RealDom<V> {
         &mut self,
         watcher: impl AttributeWatcher<V> + 'static + Send + Sync,
     ) {
-        self.attribute_watchers
-            .write()
-            .unwrap()
-            .push(Box::new(watcher));
+        let mut watchers = self.attribute_watchers.write().unwrap();
+        watchers.retain(|watcher| watcher.is_active());
+        watchers.push(Box::new(watcher));
     }
 
     /// Returns a reference to the underlying world. Any changes made to the world will not update the reactive system.
@@ -571,6 +772,58 @@ impl<'a, V: Component<Tracking = Untracked> + Send + Sync> DerefMut for ViewEntr
     }
 }
 
+/// The children of a node, borrowed mutably one at a time. See [`NodeMut::children_mut`]
+pub struct ChildNodeIteratorMut<'a, V: FromAnyValue + Send + Sync = ()> {
+    dom: &'a mut RealDom<V>,
+    children: std::vec::IntoIter<NodeId>,
+}
+
+impl<'a, V: FromAnyValue + Send + Sync> ChildNodeIteratorMut<'a, V> {
+    /// Get the next child mutably. The child borrows the iterator, so this can't implement [`Iterator`]
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<NodeMut<'_, V>> {
+        let id = self.children.next()?;
+        Some(NodeMut::new(id, self.dom))
+    }
+
+    /// The number of children that were not visited yet
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns true if every child was visited
+    pub fn is_empty(&self) -> bool {
+        self.children.len() == 0
+    }
+}
+
+/// A component of a node and the components of its children, borrowed mutably at the same time. See
+/// [`NodeMut::parent_child_mut`]
+pub struct ParentChildMut<'a, T: Component<Tracking = Untracked> + Send + Sync> {
+    // keeps the storage borrowed while the components are
+    _view: ViewMut<'a, T, Untracked>,
+    parent: *mut T,
+    children: Vec<(NodeId, *mut T)>,
+}
+
+impl<'a, T: Component<Tracking = Untracked> + Send + Sync> ParentChildMut<'a, T> {
+    /// Split the borrow into the component of the node and the components of its children, in the order of the
+    /// children
+    pub fn split(&mut self) -> (&mut T, Vec<(NodeId, &mut T)>) {
+        // Safety: the pointers point to the components of distinct nodes in the storage borrowed by the view, and they
+        // are only dereferenced while self is borrowed mutably
+        unsafe {
+            (
+                &mut *self.parent,
+                self.children
+                    .iter()
+                    .map(|&(id, child)| (id, &mut *child))
+                    .collect(),
+            )
+        }
+    }
+}
+
 /// A immutable view of a node
 pub trait NodeImmutable<V: FromAnyValue + Send + Sync = ()>: Sized {
     /// Get the real dom this node was created in
@@ -579,6 +832,12 @@ pub trait NodeImmutable<V: FromAnyValue + Send + Sync = ()>: Sized {
     /// Get the id of the current node
     fn id(&self) -> NodeId;
 
+    /// Get a weak handle to the current node that can be stored without borrowing the dom
+    #[inline]
+    fn handle(&self) -> NodeHandle {
+        NodeHandle::new(self.id())
+    }
+
     /// Get the type of the current node
     #[inline]
     fn node_type(&self) -> ViewEntry<NodeType<V>> {
@@ -594,6 +853,14 @@ pub trait NodeImmutable<V: FromAnyValue + Send + Sync = ()>: Sized {
             .then(|| ViewEntry::new(view, self.id()))
     }
 
+    /// Get the pseudo-classes that currently apply to the current node. Only elements have pseudo-classes.
+    #[inline]
+    fn pseudo_classes(&self) -> PseudoClasses {
+        self.get::<PseudoClasses>()
+            .map(|pseudo_classes| *pseudo_classes)
+            .unwrap_or_default()
+    }
+
     /// Get the ids of the children of the current node, if enter_shadow_dom is true and the current node is a shadow slot, the ids of the nodes under the node the shadow slot is attached to will be returned
     #[inline]
     fn children_ids_advanced(&self, id: NodeId, enter_shadow_dom: bool) -> Vec<NodeId> {
@@ -754,6 +1021,73 @@ impl<'a, V: FromAnyValue + Send + Sync> NodeMut<'a, V> {
         self.parent_id().map(|id| NodeMut { id, dom: self.dom })
     }
 
+    /// Get the children of this node mutably. Every child borrows the dom, so the children are visited one at a time:
+    ///
+    /// ```rust
+    /// use dioxus_native_core::prelude::*;
+    ///
+    /// let mut rdom: RealDom = RealDom::new([]);
+    /// let root = rdom.root_id();
+    /// let child = rdom.create_node(NodeType::Text(TextNode::new("hello".to_string()))).id();
+    /// rdom.get_mut(root).unwrap().add_child(child);
+    ///
+    /// let mut root = rdom.get_mut(root).unwrap();
+    /// let mut children = root.children_mut();
+    /// while let Some(mut child) = children.next() {
+    ///     child.set_type(NodeType::Text(TextNode::new("world".to_string())));
+    /// }
+    /// ```
+    #[inline]
+    pub fn children_mut(&mut self) -> ChildNodeIteratorMut<'_, V> {
+        let children = self.child_ids();
+        ChildNodeIteratorMut {
+            dom: self.dom,
+            children: children.into_iter(),
+        }
+    }
+
+    /// Get a component of this node and the same component of its children mutably at the same time, for example to
+    /// resolve the component of a node from the components of its children. The component is marked as dirty in every
+    /// node.
+    ///
+    /// Returns [`None`] if this node doesn't have the component. Children without the component are skipped.
+    pub fn parent_child_mut<T: Component<Tracking = Untracked> + Sync + Send>(
+        &mut self,
+    ) -> Option<ParentChildMut<'_, T>> {
+        let parent_id = self.id;
+        let children = self.child_ids();
+        for &id in std::iter::once(&parent_id).chain(&children) {
+            self.dom
+                .dirty_nodes
+                .passes_updated
+                .entry(id)
+                .or_default()
+                .insert(TypeId::of::<T>());
+        }
+
+        let mut view: ViewMut<T> = self.dom.borrow_raw().ok()?;
+        let parent = (&mut view).get(parent_id).ok()? as *mut T;
+        let mut borrowed = FxHashSet::default();
+        borrowed.insert(parent_id);
+        let children = children
+            .into_iter()
+            .filter_map(|id| {
+                // the tree never contains a node twice, but the borrows are only sound if every node is distinct
+                assert!(
+                    borrowed.insert(id),
+                    "{id:?} is borrowed twice as a child of {parent_id:?}"
+                );
+                let child = (&mut view).get(id).ok()?;
+                Some((id, child as *mut T))
+            })
+            .collect();
+        Some(ParentChildMut {
+            _view: view,
+            parent,
+            children,
+        })
+    }
+
     /// Get a component from the current node mutably
     #[inline]
     pub fn get_mut<T: Component<Tracking = Untracked> + Sync + Send>(
@@ -787,6 +1121,20 @@ impl<'a, V: FromAnyValue + Send + Sync> NodeMut<'a, V> {
         self.dom.world.add_component(self.id, value);
     }
 
+    /// Set if a pseudo-class applies to this element. This is called by the renderer when the user interacts with the
+    /// element.
+    pub fn set_pseudo_class(&mut self, class: PseudoClass, value: bool) {
+        let mut pseudo_classes = self.pseudo_classes();
+        if pseudo_classes.contains(class) != value {
+            pseudo_classes.set(class, value);
+            self.dom.dirty_nodes.mark_dirty(
+                self.id,
+                NodeMaskBuilder::new().with_pseudo_classes().build(),
+            );
+            self.dom.world.add_component(self.id, pseudo_classes);
+        }
+    }
+
     /// Get the next node
     #[inline]
     pub fn next_mut(self) -> Option<NodeMut<'a, V>> {
@@ -848,40 +1196,75 @@ impl<'a, V: FromAnyValue + Send + Sync> NodeMut<'a, V> {
         self.mark_moved();
     }
 
-    /// Remove this node from the RealDom
+    /// Remove this node and its children from the RealDom
     #[inline]
     pub fn remove(&mut self) {
         let id = self.id();
-        {
-            let RealDom {
-                world,
-                nodes_listening,
-                ..
-            } = &mut self.dom;
-            let mut view: ViewMut<NodeType<V>> = world.borrow().unwrap();
-            if let NodeType::Element(ElementNode { listeners, .. })
-            | NodeType::Text(TextNode { listeners, .. }) = (&mut view).get(id).unwrap()
+        let parent_id = { self.dom.tree_ref().parent_id(id) };
+        if let Some(parent_id) = parent_id {
+            self.dom.dirty_nodes.mark_child_changed(parent_id);
+        }
+
+        // The subtree is walked with a stack instead of recursion so removing very deep trees can't overflow the stack.
+        // Parents are removed from the RealDom before their children, and from the tree after them
+        let mut subtree = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            subtree.push(id);
+            stack.extend(self.dom.tree_ref().children_ids(id));
+        }
+        for &id in &subtree {
             {
-                let listeners = std::mem::take(listeners);
-                for event in listeners {
-                    nodes_listening.get_mut(&event).unwrap().remove(&id);
+                let RealDom {
+                    world,
+                    nodes_listening,
+                    ..
+                } = &mut self.dom;
+                let mut view: ViewMut<NodeType<V>> = world.borrow().unwrap();
+                if let NodeType::Element(ElementNode { listeners, .. })
+                | NodeType::Text(TextNode { listeners, .. }) = (&mut view).get(id).unwrap()
+                {
+                    let listeners = std::mem::take(listeners);
+                    for event in listeners {
+                        nodes_listening.get_mut(&event).unwrap().remove(&id);
+                    }
                 }
             }
+            NodeMut::new(id, self.dom).mark_removed();
+            self.dom.dirty_nodes.unfreeze(id);
+            self.dom.node_data.remove_node(id);
         }
-        self.mark_removed();
-        let parent_id = { self.dom.tree_ref().parent_id(id) };
-        if let Some(parent_id) = parent_id {
-            self.real_dom_mut()
-                .dirty_nodes
-                .mark_child_changed(parent_id);
+        for &id in subtree.iter().rev() {
+            self.dom.tree_mut().remove(id);
+            self.real_dom_mut().raw_world_mut().delete_entity(id);
+        }
+    }
+
+    /// Move this node and its children to the end of the children of another node. Unlike removing the node and
+    /// creating it again, the states and node data of the subtree are kept.
+    #[inline]
+    pub fn reparent(&mut self, new_parent: NodeId) {
+        let id = self.id();
+        if let Some(parent_id) = self.parent_id() {
+            self.dom.dirty_nodes.mark_child_changed(parent_id);
         }
-        let children_ids = self.child_ids();
-        let children_ids_vec = children_ids.to_vec();
-        for child in children_ids_vec {
-            self.dom.get_mut(child).unwrap().remove();
+        self.dom.dirty_nodes.mark_child_changed(new_parent);
+        self.dom.dirty_nodes.mark_parent_added_or_removed(id);
+        self.dom.tree_mut().reparent(id, new_parent);
+        self.mark_moved();
+    }
+
+    /// Detach this node and its children from their parent, making this node the root of its own tree. The subtree can
+    /// be added back to the dom later with [`NodeMut::add_child`] or [`NodeMut::reparent`], or dropped with
+    /// [`NodeMut::remove`]. Until then, it is listed in [`RealDom::detached_roots`].
+    #[inline]
+    pub fn split_off(&mut self) {
+        let id = self.id();
+        if let Some(parent_id) = self.parent_id() {
+            self.dom.dirty_nodes.mark_child_changed(parent_id);
+            self.dom.dirty_nodes.mark_parent_added_or_removed(id);
         }
-        self.dom.tree_mut().remove(id);
-        self.real_dom_mut().raw_world_mut().delete_entity(id);
+        self.dom.tree_mut().split_off(id);
     }
 
     /// Replace this node with a different node
@@ -0,0 +1,122 @@
+//! Save the resolved states of a [`RealDom`] and restore them later without running the passes again.
+//!
+//! Resolving styles and layout for a large tree is the most expensive part of starting a native app. If the app
+//! restores a session that it rendered before, it can save the resolved states with [`RealDom::snapshot_state`] and
+//! restore them with [`RealDom::restore_state`] after the nodes are created again. Restored states are marked as up
+//! to date, so the next call to [`RealDom::update_state`] only resolves the states of nodes that changed.
+
+use std::any::TypeId;
+use std::hash::{Hash, Hasher};
+
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use shipyard::Component;
+
+use crate::node::{FromAnyValue, NodeType, OwnedAttributeValue};
+use crate::prelude::{NodeImmutable, State};
+use crate::real_dom::RealDom;
+use crate::tree::TreeRef;
+use crate::NodeId;
+
+/// The resolved value of a state for every node of a [`RealDom`], in depth first order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshot<S> {
+    // A hash of the tags, attributes, text and shape of the tree the states were resolved for
+    structure: u64,
+    states: Vec<Option<S>>,
+}
+
+/// An error returned by [`RealDom::restore_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreStateError {
+    /// The snapshot was taken from a dom with different nodes. Run [`RealDom::update_state`] as usual instead.
+    StructureMismatch,
+}
+
+impl std::fmt::Display for RestoreStateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestoreStateError::StructureMismatch => {
+                write!(f, "the snapshot was taken from a dom with different nodes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RestoreStateError {}
+
+impl<V: FromAnyValue + Send + Sync> RealDom<V> {
+    /// Save the resolved value of the state `S` for every node in the dom.
+    ///
+    /// Call this after [`RealDom::update_state`] so the snapshot contains the resolved states.
+    pub fn snapshot_state<S: State<V> + Component + Clone>(&self) -> StateSnapshot<S> {
+        let mut states = Vec::new();
+        self.traverse_depth_first(|node| {
+            states.push(node.get::<S>().map(|state| (*state).clone()))
+        });
+        StateSnapshot {
+            structure: self.structure_hash(),
+            states,
+        }
+    }
+
+    /// Restore the states saved with [`RealDom::snapshot_state`].
+    ///
+    /// The dom must contain the same nodes in the same order as the dom the snapshot was taken from. States that depend
+    /// on the restored state are still resolved as usual.
+    pub fn restore_state<S: State<V> + Component>(
+        &mut self,
+        snapshot: StateSnapshot<S>,
+    ) -> Result<(), RestoreStateError> {
+        if snapshot.structure != self.structure_hash() {
+            return Err(RestoreStateError::StructureMismatch);
+        }
+
+        let mut ids = Vec::new();
+        self.traverse_depth_first(|node| ids.push(node.id()));
+        for (id, state) in ids.into_iter().zip(snapshot.states) {
+            if let Some(state) = state {
+                self.world.add_component(id, state);
+                self.dirty_nodes.mark_clean(id, TypeId::of::<S>());
+            }
+        }
+        Ok(())
+    }
+
+    fn structure_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        let tree = self.tree_ref();
+        self.traverse_depth_first(|node| {
+            match &*node.node_type() {
+                NodeType::Text(text) => {
+                    0u8.hash(&mut hasher);
+                    text.text.hash(&mut hasher);
+                }
+                NodeType::Element(element) => {
+                    1u8.hash(&mut hasher);
+                    element.tag.hash(&mut hasher);
+                    element.namespace.hash(&mut hasher);
+                    let mut attributes: Vec<_> = element.attributes.iter().collect();
+                    attributes.sort_by(|(a, _), (b, _)| {
+                        (&a.name, &a.namespace).cmp(&(&b.name, &b.namespace))
+                    });
+                    for (attribute, value) in attributes {
+                        attribute.hash(&mut hasher);
+                        match value {
+                            OwnedAttributeValue::Text(text) => text.hash(&mut hasher),
+                            OwnedAttributeValue::Float(float) => float.to_bits().hash(&mut hasher),
+                            OwnedAttributeValue::Int(int) => int.hash(&mut hasher),
+                            OwnedAttributeValue::Bool(bool) => bool.hash(&mut hasher),
+                            // Custom values can't be hashed, so only their presence is part of the structure
+                            OwnedAttributeValue::Custom(_) => {}
+                        }
+                    }
+                }
+                NodeType::Placeholder => 2u8.hash(&mut hasher),
+            }
+            let children: Vec<NodeId> = tree.children_ids_advanced(node.id(), true);
+            children.len().hash(&mut hasher);
+        });
+        hasher.finish()
+    }
+}
@@ -23,6 +23,7 @@ pub struct Node {
     slot_for_light_tree: Option<NodeId>,
     /// If this node is a root of a shadow_tree, this is the node whose child_subtree is that shadow_tree.
     root_for_light_tree: Option<NodeId>,
+    /// The number of ancestors of the node. Heights stop at [`u16::MAX`], so nodes deeper than that share a height.
     height: u16,
 }
 
@@ -78,7 +79,7 @@ pub trait TreeRef {
     fn slot_for_light_tree(&self, id: NodeId) -> Option<NodeId>;
     /// The node that contains the shadow tree this node is a root of
     fn root_for_light_tree(&self, id: NodeId) -> Option<NodeId>;
-    /// The height of the node.
+    /// The height of the node. Heights stop at [`u16::MAX`], so every node deeper than that has the same height.
     fn height(&self, id: NodeId) -> Option<u16>;
     /// Returns true if the node exists.
     fn contains(&self, id: NodeId) -> bool;
@@ -102,6 +103,13 @@ pub trait TreeMut: TreeRef {
     fn create_subtree(&mut self, id: NodeId, shadow_roots: Vec<NodeId>, slot: Option<NodeId>);
     /// Remove any shadow tree.
     fn remove_subtree(&mut self, id: NodeId);
+    /// Moves the node and its children to the end of the children of another node. The new parent must not be inside
+    /// of the moved subtree.
+    fn reparent(&mut self, id: NodeId, new_parent: NodeId);
+    /// Detaches the node and its children from their parent, making the node the root of its own tree. Unlike
+    /// [`TreeMut::remove`], the subtree is kept whole so it can be added back to the tree later, or removed with
+    /// [`TreeMut::remove`] once it is no longer needed.
+    fn split_off(&mut self, id: NodeId);
 }
 
 impl<'a> TreeRef for TreeRefView<'a> {
@@ -138,19 +146,20 @@ impl<'a> TreeRef for TreeRefView<'a> {
 
 impl<'a> TreeMut for TreeMutView<'a> {
     fn remove(&mut self, id: NodeId) {
-        fn recurse(tree: &mut TreeMutView<'_>, id: NodeId) {
+        detach(self, id);
+
+        // The subtree is walked with a stack instead of recursion so removing very deep trees can't overflow the stack
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
             let (light_tree, children) = {
-                let node = (&mut tree.1).get(id).unwrap();
+                let node = (&mut self.1).get(id).unwrap();
                 (node.slot_for_light_tree, std::mem::take(&mut node.children))
             };
-
-            for child in children {
-                recurse(tree, child);
-            }
+            stack.extend(children);
 
             // If this node is a slot in a shadow_tree, remove it from the shadow_tree.
             if let Some(light_tree) = light_tree {
-                let root_for_light_tree = (&mut tree.1).get(light_tree).unwrap();
+                let root_for_light_tree = (&mut self.1).get(light_tree).unwrap();
 
                 if let Some(shadow_tree) = &mut root_for_light_tree.child_subtree {
                     shadow_tree.slot = None;
@@ -162,16 +171,6 @@ impl<'a> TreeMut for TreeMutView<'a> {
                 );
             }
         }
-
-        {
-            let mut node_data_mut = &mut self.1;
-            if let Some(parent) = node_data_mut.get(id).unwrap().parent {
-                let parent = (&mut node_data_mut).get(parent).unwrap();
-                parent.children.retain(|&child| child != id);
-            }
-        }
-
-        recurse(self, id);
     }
 
     fn create_node(&mut self, id: NodeId) {
@@ -283,7 +282,7 @@ impl<'a> TreeMut for TreeMutView<'a> {
         // Now that we have created the shadow_tree, we need to update the height of the shadow_tree roots
         for root in shadow_roots {
             (&mut self.1).get(root).unwrap().root_for_light_tree = Some(id);
-            set_height(self, root, light_root_height + 1);
+            set_height(self, root, light_root_height.saturating_add(1));
         }
     }
 
@@ -306,7 +305,7 @@ impl<'a> TreeMut for TreeMutView<'a> {
                 let height = node.height;
                 for child in node.children.clone() {
                     println!("child: {:?}", child);
-                    set_height(self, child, height + 1);
+                    set_height(self, child, height.saturating_add(1));
                 }
 
                 // Reset the height of the shadow roots
@@ -316,6 +315,34 @@ impl<'a> TreeMut for TreeMutView<'a> {
             }
         }
     }
+
+    fn reparent(&mut self, id: NodeId, new_parent: NodeId) {
+        let mut ancestor = Some(new_parent);
+        while let Some(current) = ancestor {
+            assert_ne!(current, id, "tried to move a node inside of itself");
+            ancestor = self.parent_id(current);
+        }
+        detach(self, id);
+        self.add_child(new_parent, id);
+    }
+
+    fn split_off(&mut self, id: NodeId) {
+        if detach(self, id) {
+            set_height(self, id, 0);
+        }
+    }
+}
+
+/// Removes the link between a node and its parent without changing the height of the node. Returns if the node had a
+/// parent
+fn detach(tree: &mut TreeMutView<'_>, id: NodeId) -> bool {
+    let mut node_data_mut = &mut tree.1;
+    let Some(parent) = (&mut node_data_mut).get(id).unwrap().parent.take() else {
+        return false;
+    };
+    let parent = (&mut node_data_mut).get(parent).unwrap();
+    parent.children.retain(|&child| child != id);
+    true
 }
 
 fn child_height(parent: &Node, tree: &impl TreeRef) -> u16 {
@@ -324,47 +351,48 @@ fn child_height(parent: &Node, tree: &impl TreeRef) -> u16 {
             if let Some(slot) = shadow_tree.slot {
                 tree.height(slot)
                     .expect("Attempted to read a slot that does not exist")
-                    + 1
+                    .saturating_add(1)
             } else {
                 panic!("Attempted to read the height of a child of a node with a shadow tree, but the shadow tree does not have a slot. Every shadow tree attached to a node with children must have a slot.")
             }
         }
-        None => parent.height + 1,
+        None => parent.height.saturating_add(1),
     }
 }
 
 /// Sets the height of a node and updates the height of all its children
 fn set_height(tree: &mut TreeMutView<'_>, node: NodeId, height: u16) {
-    let (shadow_tree, light_tree, children) = {
-        let mut node_data_mut = &mut tree.1;
-        let node = (&mut node_data_mut).get(node).unwrap();
-        node.height = height;
-
-        (
-            node.child_subtree.clone(),
-            node.slot_for_light_tree,
-            node.children.clone(),
-        )
-    };
+    // The subtree is walked with a stack instead of recursion so very deep trees can't overflow the stack
+    let mut stack = vec![(node, height)];
+    while let Some((node, height)) = stack.pop() {
+        let child_height = height.saturating_add(1);
+        let (shadow_roots, light_tree, children) = {
+            let mut node_data_mut = &mut tree.1;
+            let node = (&mut node_data_mut).get(node).unwrap();
+            node.height = height;
+
+            (
+                node.child_subtree
+                    .as_ref()
+                    .map(|shadow_tree| shadow_tree.shadow_roots.clone()),
+                node.slot_for_light_tree,
+                node.children.clone(),
+            )
+        };
 
-    // If the children are actually part of a shadow_tree, there height is determined by the height of the shadow_tree
-    if let Some(shadow_tree) = shadow_tree {
-        // Set the height of the shadow_tree roots
-        for &shadow_root in &shadow_tree.shadow_roots {
-            set_height(tree, shadow_root, height + 1);
-        }
-    } else {
-        // Otherwise, we just set the height of the children to be one more than the height of the parent
-        for child in children {
-            set_height(tree, child, height + 1);
+        // If the children are actually part of a shadow_tree, there height is determined by the height of the shadow_tree
+        if let Some(shadow_roots) = shadow_roots {
+            // Set the height of the shadow_tree roots
+            stack.extend(shadow_roots.into_iter().map(|root| (root, child_height)));
+        } else {
+            // Otherwise, we just set the height of the children to be one more than the height of the parent
+            stack.extend(children.into_iter().map(|child| (child, child_height)));
         }
-    }
 
-    // If this nodes is a slot for a shadow_tree, we need to go to the super tree and update the height of its children
-    if let Some(light_tree) = light_tree {
-        let children = (&tree.1).get(light_tree).unwrap().children.clone();
-        for child in children {
-            set_height(tree, child, height + 1);
+        // If this nodes is a slot for a shadow_tree, we need to go to the super tree and update the height of its children
+        if let Some(light_tree) = light_tree {
+            let children = (&tree.1).get(light_tree).unwrap().children.clone();
+            stack.extend(children.into_iter().map(|child| (child, child_height)));
         }
     }
 }
@@ -597,3 +625,115 @@ fn deletion() {
     assert_eq!(tree.height(parent), Some(0));
     assert_eq!(tree.children_ids(parent), &[]);
 }
+
+#[test]
+fn reparenting() {
+    use shipyard::World;
+    #[derive(Component)]
+    struct Num(i32);
+
+    let mut world = World::new();
+    let root = world.add_entity(Num(0));
+    let first = world.add_entity(Num(1));
+    let second = world.add_entity(Num(2));
+    let child = world.add_entity(Num(3));
+
+    let mut tree = world.borrow::<TreeMutView>().unwrap();
+
+    tree.create_node(root);
+    tree.create_node(first);
+    tree.create_node(second);
+    tree.create_node(child);
+
+    tree.add_child(root, first);
+    tree.add_child(first, second);
+    tree.add_child(root, child);
+    assert_eq!(tree.height(child), Some(1));
+
+    tree.reparent(child, second);
+
+    assert_eq!(tree.children_ids(root), &[first]);
+    assert_eq!(tree.children_ids(second), &[child]);
+    assert_eq!(tree.parent_id(child), Some(second));
+    assert_eq!(tree.height(child), Some(3));
+
+    // The split off subtree keeps its children
+    tree.split_off(second);
+
+    assert_eq!(tree.children_ids(first), &[]);
+    assert_eq!(tree.parent_id(second), None);
+    assert_eq!(tree.children_ids(second), &[child]);
+    assert_eq!(tree.height(second), Some(0));
+    assert_eq!(tree.height(child), Some(1));
+
+    tree.add_child(root, second);
+
+    assert_eq!(tree.children_ids(root), &[first, second]);
+    assert_eq!(tree.height(child), Some(2));
+}
+
+#[test]
+fn deep_trees() {
+    use shipyard::World;
+    #[derive(Component)]
+    struct Num(i32);
+
+    // Deep enough to overflow the stack of a test thread if the tree was walked recursively
+    const DEPTH: usize = 50_000;
+
+    let mut world = World::new();
+    let root = world.add_entity(Num(0));
+    let other_root = world.add_entity(Num(0));
+    let nodes: Vec<_> = (0..DEPTH)
+        .map(|i| world.add_entity(Num(i as i32)))
+        .collect();
+
+    let mut tree = world.borrow::<TreeMutView>().unwrap();
+
+    tree.create_node(root);
+    tree.create_node(other_root);
+    tree.add_child(root, other_root);
+    let mut parent = root;
+    for &id in &nodes {
+        tree.create_node(id);
+        tree.add_child(parent, id);
+        parent = id;
+    }
+    assert_eq!(tree.height(nodes[DEPTH - 1]), Some(DEPTH as u16));
+
+    tree.reparent(nodes[0], other_root);
+
+    assert_eq!(tree.height(nodes[DEPTH - 1]), Some(DEPTH as u16 + 1));
+
+    tree.remove(nodes[0]);
+
+    assert_eq!(tree.children_ids(other_root), &[]);
+    assert_eq!(tree.children_ids(nodes[DEPTH - 2]), &[]);
+}
+
+#[test]
+fn heights_stop_at_the_limit() {
+    use shipyard::World;
+    #[derive(Component)]
+    struct Num(i32);
+
+    let mut world = World::new();
+    let nodes: Vec<_> = (0..u16::MAX as usize + 3)
+        .map(|i| world.add_entity(Num(i as i32)))
+        .collect();
+
+    let mut tree = world.borrow::<TreeMutView>().unwrap();
+
+    tree.create_node(nodes[0]);
+    for pair in nodes.windows(2) {
+        tree.create_node(pair[1]);
+        tree.add_child(pair[0], pair[1]);
+    }
+    let last = nodes.len() - 1;
+    assert_eq!(tree.height(nodes[u16::MAX as usize]), Some(u16::MAX));
+    assert_eq!(tree.height(nodes[last]), Some(u16::MAX));
+
+    // Moving the deep subtree up sets the heights that were cut off again
+    tree.reparent(nodes[4], nodes[0]);
+    assert_eq!(tree.height(nodes[last]), Some(u16::MAX - 1));
+}
@@ -53,14 +53,21 @@ pub trait TextEditable<T: Text + ?Sized>: AsRef<T> {
 
 impl TextEditable<str> for String {
     fn insert_character(&mut self, idx: usize, text: char) {
-        self.insert(idx, text);
+        self.insert(byte_index(self, idx), text);
     }
 
     fn delete_range(&mut self, range: Range<usize>) {
-        self.replace_range(range, "");
+        let start = byte_index(self, range.start);
+        let end = byte_index(self, range.end);
+        self.replace_range(start..end, "");
     }
 }
 
+/// The byte offset of a character index of the text
+fn byte_index(text: &str, idx: usize) -> usize {
+    text.char_indices().nth(idx).map_or(text.len(), |(i, _)| i)
+}
+
 /// A cursor position
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pos {
@@ -539,3 +546,12 @@ fn cursor_input() {
 
     assert_eq!(text.to_string(), goal_text);
 }
+
+#[test]
+fn edits_multibyte_text() {
+    let mut text = "中文".to_string();
+    text.insert_character(1, 'a');
+    assert_eq!(text, "中a文");
+    text.delete_range(0..2);
+    assert_eq!(text, "文");
+}
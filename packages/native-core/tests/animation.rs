@@ -0,0 +1,159 @@
+use dioxus_native_core::animation::{
+    interpolate, AnimatedStyle, AnimationTimeline, Keyframe, Keyframes,
+};
+use dioxus_native_core::node::{OwnedAttributeDiscription, OwnedAttributeValue};
+use dioxus_native_core::prelude::*;
+use dioxus_native_core::real_dom::NodeTypeMut;
+use rustc_hash::FxHashSet;
+use std::time::Duration;
+
+fn create_element(attributes: &[(&str, &str)]) -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: "div".to_owned(),
+        namespace: None,
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| {
+                (
+                    OwnedAttributeDiscription {
+                        name: name.to_string(),
+                        namespace: None,
+                    },
+                    OwnedAttributeValue::Text(value.to_string()),
+                )
+            })
+            .collect(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+fn set_attribute(rdom: &mut RealDom, id: NodeId, name: &str, value: &str) {
+    if let NodeTypeMut::Element(mut element) = rdom.get_mut(id).unwrap().node_type_mut() {
+        element.set_attribute(name.to_string(), value.to_string());
+    }
+}
+
+fn values(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn parses_keyframes() {
+    let keyframes = Keyframes::parse(
+        "from { width: 0px; color: red } 50%, 75% { width: 10px } to { width: 20px; }",
+    )
+    .unwrap();
+    let offsets: Vec<_> = keyframes
+        .frames()
+        .iter()
+        .map(|frame| frame.offset)
+        .collect();
+    assert_eq!(offsets, [0.0, 0.5, 0.75, 1.0]);
+    assert_eq!(
+        keyframes.frames()[1].properties,
+        values(&[("width", "10px")])
+    );
+
+    assert_eq!(Keyframes::parse("from { width: 0px"), None);
+    assert_eq!(Keyframes::parse("middle { width: 0px }"), None);
+    assert_eq!(Keyframes::parse("150% { width: 0px }"), None);
+    assert_eq!(Keyframes::parse("to { width }"), None);
+}
+
+#[test]
+fn interpolates_between_keyframes() {
+    let keyframes = Keyframes::new([
+        Keyframe {
+            offset: 1.0,
+            properties: values(&[("width", "20px")]),
+        },
+        Keyframe {
+            offset: 0.0,
+            properties: values(&[("width", "0px"), ("display", "none")]),
+        },
+        Keyframe {
+            offset: 0.5,
+            properties: values(&[("display", "flex"), ("opacity", "1")]),
+        },
+    ]);
+    assert_eq!(
+        keyframes.values(0.2),
+        values(&[("width", "4px"), ("display", "none"), ("opacity", "1")])
+    );
+    // properties keep the value of the last keyframe that sets them
+    assert_eq!(
+        keyframes.values(0.75),
+        values(&[("width", "15px"), ("display", "flex"), ("opacity", "1")])
+    );
+
+    assert_eq!(interpolate("0", "1", 0.25), "0.25");
+    assert_eq!(interpolate("10%", "-10%", 0.5), "0%");
+    assert_eq!(
+        interpolate("#000", "#ffffff", 0.5),
+        "rgba(128, 128, 128, 1)"
+    );
+    assert_eq!(
+        interpolate("rgb(255, 0, 0)", "rgba(0, 0, 255, 0)", 0.25),
+        "rgba(191, 0, 64, 0.75)"
+    );
+    // values that can't be interpolated switch halfway through
+    assert_eq!(interpolate("10px", "50%", 0.4), "10px");
+    assert_eq!(interpolate("block", "none", 0.5), "none");
+}
+
+#[test]
+fn plays_animations_on_the_timeline() {
+    let mut rdom: RealDom = RealDom::new([AnimatedStyle::to_type_erased()]);
+    let timeline = AnimationTimeline::new();
+    timeline.add_keyframes(
+        "grow",
+        Keyframes::parse("from { width: 0 } to { width: 10 }").unwrap(),
+    );
+
+    let animated = rdom
+        .create_node(create_element(&[("animation", "grow 1s linear")]))
+        .id();
+    let unknown = rdom
+        .create_node(create_element(&[("animation", "shrink 1s linear")]))
+        .id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(animated);
+    rdom.get_mut(root).unwrap().add_child(unknown);
+
+    let update = |rdom: &mut RealDom, now: u64| {
+        timeline.tick(rdom, Duration::from_millis(now));
+        let mut ctx = SendAnyMap::new();
+        ctx.insert(timeline.clone());
+        rdom.update_state(ctx);
+        let values = |id: NodeId| {
+            rdom.get(id)
+                .unwrap()
+                .get::<AnimatedStyle>()
+                .unwrap()
+                .values()
+                .to_vec()
+        };
+        (values(animated), values(unknown))
+    };
+
+    assert_eq!(update(&mut rdom, 0), (values(&[("width", "0")]), vec![]));
+    assert!(timeline.is_playing());
+    assert_eq!(update(&mut rdom, 250).0, values(&[("width", "2.5")]));
+    assert_eq!(update(&mut rdom, 500).0, values(&[("width", "5")]));
+
+    // pausing keeps the point the animation was at
+    set_attribute(&mut rdom, animated, "animation-play-state", "paused");
+    assert_eq!(update(&mut rdom, 700).0, values(&[("width", "7")]));
+    assert!(!timeline.is_playing());
+    assert_eq!(update(&mut rdom, 900).0, values(&[("width", "7")]));
+    set_attribute(&mut rdom, animated, "animation-play-state", "running");
+    assert_eq!(update(&mut rdom, 1000).0, values(&[("width", "7")]));
+    assert_eq!(update(&mut rdom, 1100).0, values(&[("width", "8")]));
+
+    // without a fill mode, the keyframes stop applying when the animation ends
+    assert_eq!(update(&mut rdom, 1500).0, vec![]);
+    assert!(!timeline.is_playing());
+}
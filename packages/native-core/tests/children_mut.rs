@@ -0,0 +1,91 @@
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::Component;
+
+fn create_blank_element() -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: "div".to_owned(),
+        namespace: None,
+        attributes: FxHashMap::default(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+struct Size(u32);
+
+fn create_tree(rdom: &mut RealDom) -> (NodeId, Vec<NodeId>) {
+    let children: Vec<_> = (0..3)
+        .map(|_| rdom.create_node(create_blank_element()).id())
+        .collect();
+    let mut parent = rdom.create_node(create_blank_element());
+    for &child in &children {
+        parent.add_child(child);
+    }
+    let parent = parent.id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(parent);
+    (parent, children)
+}
+
+#[test]
+fn visits_children_mutably() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let (parent, children) = create_tree(&mut rdom);
+
+    let mut parent = rdom.get_mut(parent).unwrap();
+    let mut iter = parent.children_mut();
+    assert_eq!(iter.len(), 3);
+    let mut size = 1;
+    while let Some(mut child) = iter.next() {
+        child.insert(Size(size));
+        size += 1;
+    }
+    assert!(iter.is_empty());
+    assert!(iter.next().is_none());
+
+    let sizes: Vec<_> = children
+        .iter()
+        .map(|&id| *rdom.get(id).unwrap().get::<Size>().unwrap())
+        .collect();
+    assert_eq!(sizes, [Size(1), Size(2), Size(3)]);
+}
+
+#[test]
+fn borrows_parent_and_children_at_once() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let (parent, children) = create_tree(&mut rdom);
+    for (i, &id) in children.iter().enumerate() {
+        // the last child doesn't have a size
+        if i < 2 {
+            rdom.get_mut(id).unwrap().insert(Size(i as u32 + 1));
+        }
+    }
+
+    // the parent needs the component
+    assert!(rdom
+        .get_mut(parent)
+        .unwrap()
+        .parent_child_mut::<Size>()
+        .is_none());
+
+    rdom.get_mut(parent).unwrap().insert(Size(0));
+    let mut node = rdom.get_mut(parent).unwrap();
+    let mut borrowed = node.parent_child_mut::<Size>().unwrap();
+    let (parent_size, child_sizes) = borrowed.split();
+    assert_eq!(
+        child_sizes.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+        children[..2]
+    );
+    for (_, child) in child_sizes {
+        parent_size.0 += child.0;
+        child.0 *= 10;
+    }
+    drop(borrowed);
+
+    let size = |id: NodeId| *rdom.get(id).unwrap().get::<Size>().unwrap();
+    assert_eq!(size(parent), Size(3));
+    assert_eq!(size(children[0]), Size(10));
+    assert_eq!(size(children[1]), Size(20));
+}
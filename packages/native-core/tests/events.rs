@@ -0,0 +1,65 @@
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+fn create_blank_element() -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: "div".to_owned(),
+        namespace: None,
+        attributes: FxHashMap::default(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+#[test]
+fn events_capture_then_bubble() {
+    let mut dom: RealDom = RealDom::new([]);
+    let target = dom.create_node(create_blank_element()).id();
+    let mut parent = dom.create_node(create_blank_element());
+    parent.add_child(target);
+    let parent = parent.id();
+    let root = dom.root_id();
+    dom.get_mut(root).unwrap().add_child(parent);
+
+    let path = dom.propagation_path(target, true);
+    assert_eq!(path.target(), target);
+    assert_eq!(path.nodes().collect::<Vec<_>>(), [target, parent, root]);
+
+    let mut visited = Vec::new();
+    let result = path.dispatch(|id, phase| {
+        visited.push((id, phase));
+        Propagation::Continue
+    });
+    assert_eq!(result, Propagation::Continue);
+    assert_eq!(
+        visited,
+        [
+            (root, EventPhase::Capturing),
+            (parent, EventPhase::Capturing),
+            (target, EventPhase::AtTarget),
+            (parent, EventPhase::Bubbling),
+            (root, EventPhase::Bubbling),
+        ]
+    );
+
+    // Events that don't bubble stop at the target
+    let mut visited = Vec::new();
+    dom.propagation_path(target, false).dispatch(|id, phase| {
+        visited.push((id, phase));
+        Propagation::Continue
+    });
+    assert_eq!(visited.last(), Some(&(target, EventPhase::AtTarget)));
+
+    // Stopping propagation skips the rest of the path
+    let mut visited = Vec::new();
+    let result = path.dispatch(|id, phase| {
+        visited.push(id);
+        if phase == EventPhase::AtTarget {
+            Propagation::Stop
+        } else {
+            Propagation::Continue
+        }
+    });
+    assert_eq!(result, Propagation::Stop);
+    assert_eq!(visited, [root, parent, target]);
+}
@@ -0,0 +1,81 @@
+use dioxus_native_core::focus::{next_focus, tab_order, FocusLevel, FocusState, Role};
+use dioxus_native_core::node::{OwnedAttributeDiscription, OwnedAttributeValue};
+use dioxus_native_core::prelude::*;
+use rustc_hash::FxHashSet;
+use std::num::NonZeroU16;
+
+fn create_element(tag: &str, attributes: &[(&str, &str)]) -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: tag.to_owned(),
+        namespace: None,
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| {
+                (
+                    OwnedAttributeDiscription {
+                        name: name.to_string(),
+                        namespace: None,
+                    },
+                    OwnedAttributeValue::Text(value.to_string()),
+                )
+            })
+            .collect(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+#[test]
+fn resolves_tab_order_and_roles() {
+    let mut rdom: RealDom = RealDom::new([FocusState::to_type_erased()]);
+    let nodes = [
+        create_element("div", &[]),
+        create_element("button", &[]),
+        create_element("div", &[("tabindex", "2")]),
+        create_element("input", &[("type", "checkbox"), ("disabled", "true")]),
+        create_element("div", &[("role", "dialog"), ("tabindex", "0")]),
+        create_element("a", &[("href", "/"), ("tabindex", "-1")]),
+        create_element("div", &[("tabindex", "1")]),
+    ];
+    let ids: Vec<_> = nodes
+        .into_iter()
+        .map(|node| {
+            let id = rdom.create_node(node).id();
+            let root = rdom.root_id();
+            rdom.get_mut(root).unwrap().add_child(id);
+            id
+        })
+        .collect();
+    // a node listening to keyboard events can be focused
+    rdom.get_mut(ids[0]).unwrap().add_event_listener("keydown");
+    rdom.update_state(SendAnyMap::new());
+
+    let focus = |id: NodeId| {
+        rdom.get(id)
+            .unwrap()
+            .get::<FocusState>()
+            .as_deref()
+            .cloned()
+            .unwrap()
+    };
+    assert_eq!(focus(ids[1]).level, FocusLevel::Focusable);
+    assert_eq!(focus(ids[1]).role, Some(Role::Button));
+    assert_eq!(
+        focus(ids[2]).level,
+        FocusLevel::Ordered(NonZeroU16::new(2).unwrap())
+    );
+    // disabled elements and elements with a negative tabindex keep their role, but can't be focused
+    assert_eq!(focus(ids[3]).level, FocusLevel::Unfocusable);
+    assert_eq!(focus(ids[3]).role, Some(Role::Checkbox));
+    assert_eq!(focus(ids[5]).level, FocusLevel::Unfocusable);
+    assert_eq!(focus(ids[5]).role, Some(Role::Link));
+    assert_eq!(focus(ids[4]).role, Some(Role::Dialog));
+
+    // positive tabindexes come first, then the other nodes in tree order
+    assert_eq!(tab_order(&rdom), [ids[6], ids[2], ids[0], ids[1], ids[4]]);
+    assert_eq!(next_focus(&rdom, None, true), Some(ids[6]));
+    assert_eq!(next_focus(&rdom, None, false), Some(ids[4]));
+    assert_eq!(next_focus(&rdom, Some(ids[2]), true), Some(ids[0]));
+    // the focus wraps around
+    assert_eq!(next_focus(&rdom, Some(ids[4]), true), Some(ids[6]));
+    assert_eq!(next_focus(&rdom, Some(ids[6]), false), Some(ids[4]));
+}
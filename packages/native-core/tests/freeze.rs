@@ -0,0 +1,123 @@
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::prelude::*;
+use dioxus_native_core::real_dom::NodeTypeMut;
+use dioxus_native_core_macro::partial_derive_state;
+use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::Component;
+
+fn create_blank_element() -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: "div".to_owned(),
+        namespace: None,
+        attributes: FxHashMap::default(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+/// Counts how many times the state of a node was updated. Updates are passed down to the children
+#[derive(Debug, Default, Clone, PartialEq, Component)]
+struct Updates(i32);
+
+#[partial_derive_state]
+impl State for Updates {
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+    type ParentDependencies = (Updates,);
+
+    const NODE_MASK: NodeMaskBuilder<'static> =
+        NodeMaskBuilder::new().with_attrs(AttributeMaskBuilder::All);
+
+    fn update<'a>(
+        &mut self,
+        _: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        self.0 += 1;
+        true
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+fn updates(rdom: &RealDom, id: NodeId) -> i32 {
+    rdom.get(id).unwrap().get::<Updates>().unwrap().0
+}
+
+#[test]
+fn frozen_subtrees_are_not_updated() {
+    let mut rdom: RealDom = RealDom::new([Updates::to_type_erased()]);
+    let child = rdom.create_node(create_blank_element()).id();
+    let mut frozen = rdom.create_node(create_blank_element());
+    frozen.add_child(child);
+    let frozen = frozen.id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(frozen);
+
+    // The subtree is frozen once its states are up to date
+    rdom.freeze(frozen);
+    assert!(!rdom.is_frozen(child));
+    rdom.update_state(SendAnyMap::new());
+    assert!(rdom.is_frozen(frozen));
+    assert!(rdom.is_frozen(child));
+    assert!(!rdom.is_frozen(root));
+    assert_eq!(updates(&rdom, child), 1);
+
+    // Changes to the parent of the subtree do not reach the frozen nodes
+    rdom.get_mut(root).unwrap().get_mut::<Updates>();
+    rdom.update_state(SendAnyMap::new());
+    assert_eq!(updates(&rdom, root), 2);
+    assert_eq!(updates(&rdom, frozen), 1);
+    assert_eq!(updates(&rdom, child), 1);
+
+    // Unfreezing the subtree updates all of its nodes
+    rdom.unfreeze(child);
+    assert!(!rdom.is_frozen(frozen));
+    rdom.update_state(SendAnyMap::new());
+    assert_eq!(updates(&rdom, frozen), 2);
+    assert_eq!(updates(&rdom, child), 2);
+}
+
+#[test]
+fn changing_a_frozen_node_unfreezes_it() {
+    let mut rdom: RealDom = RealDom::new([Updates::to_type_erased()]);
+    let child = rdom.create_node(create_blank_element()).id();
+    let mut frozen = rdom.create_node(create_blank_element());
+    frozen.add_child(child);
+    let frozen = frozen.id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(frozen);
+    rdom.freeze(frozen);
+    rdom.update_state(SendAnyMap::new());
+
+    if let NodeTypeMut::Element(mut element) = rdom.get_mut(child).unwrap().node_type_mut() {
+        element.set_attribute("hidden".to_string(), true);
+    }
+    assert!(!rdom.is_frozen(frozen));
+    assert!(!rdom.is_frozen(child));
+    rdom.update_state(SendAnyMap::new());
+    assert_eq!(updates(&rdom, frozen), 2);
+    assert_eq!(updates(&rdom, child), 2);
+
+    // Adding a node to a frozen subtree unfreezes it too
+    rdom.freeze(frozen);
+    rdom.update_state(SendAnyMap::new());
+    let new_child = rdom.create_node(create_blank_element()).id();
+    rdom.get_mut(frozen).unwrap().add_child(new_child);
+    assert!(!rdom.is_frozen(frozen));
+    rdom.update_state(SendAnyMap::new());
+    assert_eq!(updates(&rdom, new_child), 1);
+    assert_eq!(updates(&rdom, child), 3);
+}
@@ -152,3 +152,54 @@ fn native_core_is_okay() {
         }
     });
 }
+
+#[test]
+fn update_state_parallel_is_okay() {
+    fn app(cx: Scope) -> Element {
+        let count = use_state(cx, || 0);
+        use_effect(cx, count, |count| async move {
+            if *count < 5 {
+                count.with_mut(|count| *count += 1);
+            }
+        });
+
+        // siblings at the same height are resolved on different threads
+        cx.render(rsx! {
+            blabla {
+                blabla { blabla {} blabla {} }
+                blabla { blabla {} blabla {} }
+                (0..**count).map(|i| rsx! { blabla { key: "{i}", blabla {} } })
+            }
+        })
+    }
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let mut rdom = RealDom::new([BlablaState::to_type_erased()]);
+        let mut dioxus_state = DioxusState::create(&mut rdom);
+        let mut dom = VirtualDom::new(app);
+
+        let mutations = dom.rebuild();
+        dioxus_state.apply_mutations(&mut rdom, mutations);
+        rdom.update_state_parallel(SendAnyMap::new());
+
+        for _ in 0..5 {
+            dom.wait_for_work().await;
+
+            let mutations = dom.render_immediate();
+            dioxus_state.apply_mutations(&mut rdom, mutations);
+            rdom.update_state_parallel(SendAnyMap::new());
+        }
+
+        let mut states = 0;
+        rdom.traverse_depth_first(|node| {
+            states += node.get::<BlablaState>().is_some() as usize;
+        });
+        // every element and the root of the dom have the state
+        assert_eq!(states, 18);
+    });
+}
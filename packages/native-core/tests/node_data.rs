@@ -0,0 +1,66 @@
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+fn create_blank_element() -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: "div".to_owned(),
+        namespace: None,
+        attributes: FxHashMap::default(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+/// Data a renderer could keep for a node, like a texture
+#[derive(Debug, PartialEq)]
+struct Texture(u32);
+
+#[test]
+fn node_data_is_dropped_with_the_node() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let child = rdom.create_node(create_blank_element()).id();
+    let mut parent = rdom.create_node(create_blank_element());
+    parent.add_child(child);
+    let parent = parent.id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(parent);
+
+    assert_eq!(rdom.insert_node_data(child, Texture(1)), None);
+    assert_eq!(rdom.insert_node_data(child, Texture(2)), Some(Texture(1)));
+    rdom.insert_node_data(child, "glyphs");
+    rdom.insert_node_data(parent, Texture(3));
+    rdom.node_data_mut::<Texture>(parent).unwrap().0 += 1;
+    assert_eq!(rdom.node_data::<Texture>(child), Some(&Texture(2)));
+    assert_eq!(rdom.node_data::<&str>(child), Some(&"glyphs"));
+    assert_eq!(rdom.node_data::<Texture>(parent), Some(&Texture(4)));
+    assert_eq!(rdom.node_data::<Texture>(root), None);
+
+    // Removing a node drops the data of the node and its children
+    rdom.get_mut(parent).unwrap().remove();
+    assert_eq!(rdom.node_data::<Texture>(child), None);
+    assert_eq!(rdom.node_data::<&str>(child), None);
+    assert_eq!(rdom.node_data::<Texture>(parent), None);
+}
+
+#[test]
+fn handles_do_not_outlive_nodes() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let node = rdom.create_node(create_blank_element()).id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(node);
+
+    let handle = rdom.get(node).unwrap().handle();
+    assert_eq!(rdom.handle(node), Some(handle));
+    assert!(handle.is_alive(&rdom));
+    assert_eq!(handle.upgrade(&rdom).unwrap().id(), node);
+
+    rdom.get_mut(node).unwrap().remove();
+    assert!(!handle.is_alive(&rdom));
+    assert!(handle.upgrade(&rdom).is_none());
+    assert!(rdom.handle(node).is_none());
+
+    // A new node may reuse the index of the removed node, but the handle still points to the removed node
+    let new_node = rdom.create_node(create_blank_element()).id();
+    rdom.get_mut(root).unwrap().add_child(new_node);
+    assert!(handle.upgrade_mut(&mut rdom).is_none());
+}
@@ -1,5 +1,7 @@
 use dioxus_native_core::node::NodeType;
 use dioxus_native_core::prelude::*;
+use dioxus_native_core::real_dom::NodeTypeMut;
+use dioxus_native_core::tree::TreeRef;
 use dioxus_native_core_macro::partial_derive_state;
 use rustc_hash::{FxHashMap, FxHashSet};
 use shipyard::Component;
@@ -438,3 +440,270 @@ fn up_pass() {
     let grandchild2 = tree.get(grandchild2).unwrap();
     assert_eq!(grandchild2.get().as_deref(), Some(&AddNumber(1)));
 }
+
+#[test]
+fn pseudo_class_pass() {
+    #[derive(Debug, Default, Clone, PartialEq, Component)]
+    struct Hovered(bool);
+
+    #[partial_derive_state]
+    impl State for Hovered {
+        type ChildDependencies = ();
+        type NodeDependencies = ();
+        type ParentDependencies = ();
+
+        const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new().with_pseudo_classes();
+
+        fn update<'a>(
+            &mut self,
+            node_view: NodeView,
+            _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+            _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: &SendAnyMap,
+        ) -> bool {
+            let hovered = node_view
+                .pseudo_classes()
+                .is_some_and(|classes| classes.hover);
+            let changed = self.0 != hovered;
+            self.0 = hovered;
+            changed
+        }
+
+        fn create<'a>(
+            node_view: NodeView<()>,
+            node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+            parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+            children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+            context: &SendAnyMap,
+        ) -> Self {
+            let mut myself = Self::default();
+            myself.update(node_view, node, parent, children, context);
+            myself
+        }
+    }
+
+    let mut tree: RealDom = RealDom::new([Hovered::to_type_erased()]);
+    let child = tree.create_node(create_blank_element()).id();
+    tree.get_mut(tree.root_id()).unwrap().add_child(child);
+    tree.update_state(SendAnyMap::new());
+    assert_eq!(
+        tree.get(child).unwrap().get().as_deref(),
+        Some(&Hovered(false))
+    );
+
+    let set_hover = |tree: &mut RealDom, hover: bool| {
+        let mut node = tree.get_mut(child).unwrap();
+        node.set_pseudo_class(PseudoClass::Hover, hover);
+    };
+
+    set_hover(&mut tree, true);
+    assert!(tree.get(child).unwrap().pseudo_classes().hover);
+    let (dirty, _) = tree.update_state(SendAnyMap::new());
+    assert!(dirty.contains(&child));
+    assert_eq!(
+        tree.get(child).unwrap().get().as_deref(),
+        Some(&Hovered(true))
+    );
+
+    // setting the same state again does not update the node
+    set_hover(&mut tree, true);
+    let (dirty, _) = tree.update_state(SendAnyMap::new());
+    assert!(dirty.is_empty());
+
+    set_hover(&mut tree, false);
+    tree.update_state(SendAnyMap::new());
+    assert_eq!(
+        tree.get(child).unwrap().get().as_deref(),
+        Some(&Hovered(false))
+    );
+}
+
+#[test]
+fn parallel_passes() {
+    // The depth of every node
+    #[derive(Debug, Default, Clone, PartialEq, Component)]
+    struct Depth(usize);
+
+    #[partial_derive_state]
+    impl State for Depth {
+        type ChildDependencies = ();
+        type NodeDependencies = ();
+        type ParentDependencies = (Depth,);
+
+        const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+        fn update<'a>(
+            &mut self,
+            _: NodeView,
+            _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+            parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: &SendAnyMap,
+        ) -> bool {
+            let new = Depth(parent.map(|(parent,)| parent.0 + 1).unwrap_or_default());
+            let changed = *self != new;
+            *self = new;
+            changed
+        }
+    }
+
+    // The number of nodes in the subtree of every node
+    #[derive(Debug, Default, Clone, PartialEq, Component)]
+    struct Size(usize);
+
+    #[partial_derive_state]
+    impl State for Size {
+        type ChildDependencies = (Size,);
+        type NodeDependencies = ();
+        type ParentDependencies = ();
+
+        const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+        fn update<'a>(
+            &mut self,
+            _: NodeView,
+            _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+            _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+            children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: &SendAnyMap,
+        ) -> bool {
+            let new = Size(1 + children.iter().map(|(child,)| child.0).sum::<usize>());
+            let changed = *self != new;
+            *self = new;
+            changed
+        }
+    }
+
+    let mut tree: RealDom = RealDom::new([Depth::to_type_erased(), Size::to_type_erased()]);
+    // 20 subtrees with 10 children each
+    let mut children = Vec::new();
+    for _ in 0..20 {
+        let grandchildren: Vec<_> = (0..10)
+            .map(|_| tree.create_node(create_blank_element()).id())
+            .collect();
+        let mut child = tree.create_node(create_blank_element());
+        for grandchild in &grandchildren {
+            child.add_child(*grandchild);
+        }
+        children.push((child.id(), grandchildren));
+    }
+    let root = tree.root_id();
+    for (child, _) in &children {
+        tree.get_mut(root).unwrap().add_child(*child);
+    }
+
+    tree.update_state_parallel(SendAnyMap::new());
+
+    let root_node = tree.get(root).unwrap();
+    assert_eq!(root_node.get::<Depth>().as_deref(), Some(&Depth(0)));
+    assert_eq!(root_node.get::<Size>().as_deref(), Some(&Size(221)));
+    for (child, grandchildren) in &children {
+        let child = tree.get(*child).unwrap();
+        assert_eq!(child.get::<Depth>().as_deref(), Some(&Depth(1)));
+        assert_eq!(child.get::<Size>().as_deref(), Some(&Size(11)));
+        for grandchild in grandchildren {
+            let grandchild = tree.get(*grandchild).unwrap();
+            assert_eq!(grandchild.get::<Depth>().as_deref(), Some(&Depth(2)));
+            assert_eq!(grandchild.get::<Size>().as_deref(), Some(&Size(1)));
+        }
+    }
+
+    // adding a node to a leaf updates the sizes of its ancestors and the depth of the new node
+    let (child, grandchildren) = &children[3];
+    let new = tree.create_node(create_blank_element()).id();
+    tree.get_mut(grandchildren[5]).unwrap().add_child(new);
+    tree.update_state_parallel(SendAnyMap::new());
+
+    assert_eq!(
+        tree.get(new).unwrap().get::<Depth>().as_deref(),
+        Some(&Depth(3))
+    );
+    assert_eq!(
+        tree.get(grandchildren[5]).unwrap().get::<Size>().as_deref(),
+        Some(&Size(2))
+    );
+    assert_eq!(
+        tree.get(*child).unwrap().get::<Size>().as_deref(),
+        Some(&Size(12))
+    );
+    assert_eq!(
+        tree.get(root).unwrap().get::<Size>().as_deref(),
+        Some(&Size(222))
+    );
+}
+
+#[test]
+fn moved_subtrees() {
+    // The depth of every node
+    #[derive(Debug, Default, Clone, PartialEq, Component)]
+    struct Depth(usize);
+
+    #[partial_derive_state]
+    impl State for Depth {
+        type ChildDependencies = ();
+        type NodeDependencies = ();
+        type ParentDependencies = (Depth,);
+
+        const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+        fn update<'a>(
+            &mut self,
+            _: NodeView,
+            _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+            parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+            _: &SendAnyMap,
+        ) -> bool {
+            let new = Depth(parent.map(|(parent,)| parent.0 + 1).unwrap_or_default());
+            let changed = *self != new;
+            *self = new;
+            changed
+        }
+    }
+
+    let mut tree: RealDom = RealDom::new([Depth::to_type_erased()]);
+    let root = tree.root_id();
+    // a chain of nodes, each the child of the last
+    let mut chain = Vec::new();
+    let mut parent = root;
+    for _ in 0..10_000 {
+        let id = tree.create_node(create_blank_element()).id();
+        tree.get_mut(parent).unwrap().add_child(id);
+        chain.push(id);
+        parent = id;
+    }
+    let other = tree.create_node(create_blank_element()).id();
+    tree.get_mut(root).unwrap().add_child(other);
+    tree.update_state(SendAnyMap::new());
+
+    let depth = |tree: &RealDom, id| tree.get(id).unwrap().get::<Depth>().unwrap().0;
+    assert_eq!(depth(&tree, chain[9_999]), 10_000);
+
+    // moving the subtree updates the states of every node in it
+    tree.get_mut(chain[5_000]).unwrap().reparent(other);
+    tree.update_state(SendAnyMap::new());
+
+    assert_eq!(tree.tree_ref().children_ids(chain[4_999]), []);
+    assert_eq!(tree.tree_ref().children_ids(other), [chain[5_000]]);
+    assert_eq!(depth(&tree, chain[5_000]), 2);
+    assert_eq!(depth(&tree, chain[9_999]), 5_001);
+
+    // the split off subtree is kept until it is removed
+    tree.get_mut(other).unwrap().split_off();
+    assert!(tree.contains(chain[9_999]));
+    assert_eq!(tree.tree_ref().parent_id(other), None);
+    assert_eq!(tree.detached_roots(), [other]);
+    let mut split_off = 0;
+    tree.traverse_subtree_depth_first(other, |_| split_off += 1);
+    assert_eq!(split_off, 5_001);
+
+    tree.get_mut(other).unwrap().remove();
+    tree.get_mut(chain[0]).unwrap().remove();
+    tree.update_state(SendAnyMap::new());
+
+    assert!(!tree.contains(other));
+    assert!(!tree.contains(chain[9_999]));
+    assert_eq!(tree.tree_ref().children_ids(root), []);
+    assert_eq!(tree.detached_roots(), []);
+}
@@ -0,0 +1,154 @@
+use dioxus_native_core::node::{OwnedAttributeDiscription, OwnedAttributeValue};
+use dioxus_native_core::node_watcher::NodeWatcher;
+use dioxus_native_core::prelude::*;
+use dioxus_native_core::query::Query;
+use dioxus_native_core::real_dom::NodeTypeMut;
+use rustc_hash::FxHashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn create_element(tag: &str, attributes: &[(&str, &str)]) -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: tag.to_owned(),
+        namespace: None,
+        attributes: attributes
+            .iter()
+            .map(|(name, value)| {
+                (
+                    OwnedAttributeDiscription {
+                        name: name.to_string(),
+                        namespace: None,
+                    },
+                    OwnedAttributeValue::Text(value.to_string()),
+                )
+            })
+            .collect(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+#[test]
+fn parses_selectors() {
+    assert_eq!(
+        Query::parse("input#name.large[type=\"text\"][disabled]"),
+        Some(
+            Query::tag("input")
+                .id("name")
+                .class("large")
+                .attr("type", "text")
+                .has_attr("disabled")
+        )
+    );
+    assert_eq!(Query::parse("*.item"), Some(Query::any().class("item")));
+    assert_eq!(Query::parse("div > input"), None);
+    assert_eq!(Query::parse("input[type=text"), None);
+    assert_eq!(Query::parse("div."), None);
+    // names can have letters from any script, but not symbols
+    assert_eq!(
+        Query::parse("div.é#名前"),
+        Some(Query::tag("div").class("é").id("名前"))
+    );
+    assert_eq!(Query::parse(".a€"), None);
+}
+
+#[test]
+fn finds_and_tracks_matches() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let nodes = [
+        create_element("input", &[("type", "text")]),
+        create_element("input", &[("type", "checkbox")]),
+        create_element("div", &[("class", "item selected")]),
+        create_element("input", &[("type", "text"), ("class", "item")]),
+    ];
+    let ids: Vec<_> = nodes
+        .into_iter()
+        .map(|node| {
+            let id = rdom.create_node(node).id();
+            let root = rdom.root_id();
+            rdom.get_mut(root).unwrap().add_child(id);
+            id
+        })
+        .collect();
+    rdom.update_state(SendAnyMap::new());
+
+    let text_inputs = Query::tag("input").attr("type", "text");
+    assert_eq!(text_inputs.find(&rdom), [ids[0], ids[3]]);
+    assert_eq!(text_inputs.first(&rdom), Some(ids[0]));
+    assert_eq!(Query::any().class("item").find(&rdom), [ids[2], ids[3]]);
+    assert_eq!(Query::tag("button").first(&rdom), None);
+
+    let live = text_inputs.live(&mut rdom);
+    assert_eq!(live.len(), 2);
+
+    // changing an attribute updates the matches
+    for (id, value) in [(ids[1], "text"), (ids[0], "password")] {
+        if let NodeTypeMut::Element(mut element) = rdom.get_mut(id).unwrap().node_type_mut() {
+            element.set_attribute("type".to_string(), value.to_string());
+        }
+    }
+    // added nodes are checked
+    let added = rdom
+        .create_node(create_element("input", &[("type", "text")]))
+        .id();
+    let root = rdom.root_id();
+    rdom.get_mut(root).unwrap().add_child(added);
+    rdom.update_state(SendAnyMap::new());
+
+    assert!(!live.contains(ids[0]));
+    assert!(live.contains(ids[1]));
+    assert!(live.contains(ids[3]));
+    assert!(live.contains(added));
+    assert_eq!(live.len(), 3);
+
+    // removed nodes are removed right away
+    rdom.get_mut(ids[3]).unwrap().remove();
+    assert!(!live.contains(ids[3]));
+    assert_eq!(live.len(), 2);
+}
+
+struct CountingWatcher {
+    active: Arc<AtomicBool>,
+    added: Arc<AtomicUsize>,
+}
+
+impl NodeWatcher<()> for CountingWatcher {
+    fn on_node_added(&mut self, _: NodeMut) {
+        self.added.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn removes_inactive_watchers() {
+    let mut rdom: RealDom = RealDom::new([]);
+    let active = Arc::new(AtomicBool::new(true));
+    let added = Arc::new(AtomicUsize::new(0));
+    rdom.add_node_watcher(CountingWatcher {
+        active: active.clone(),
+        added: added.clone(),
+    });
+    let add_node = |rdom: &mut RealDom| {
+        let id = rdom.create_node(create_element("div", &[])).id();
+        let root = rdom.root_id();
+        rdom.get_mut(root).unwrap().add_child(id);
+        rdom.update_state(SendAnyMap::new());
+    };
+
+    add_node(&mut rdom);
+    let calls = added.load(Ordering::SeqCst);
+    assert!(calls > 0);
+    active.store(false, Ordering::SeqCst);
+    add_node(&mut rdom);
+    assert_eq!(added.load(Ordering::SeqCst), calls);
+    // the dom drops the watcher once it is no longer active
+    assert_eq!(Arc::strong_count(&added), 1);
+
+    // dropped live queries stop watching the dom
+    let live = Query::tag("div").live(&mut rdom);
+    assert_eq!(live.len(), 2);
+    drop(live);
+    add_node(&mut rdom);
+}
@@ -0,0 +1,91 @@
+use dioxus_native_core::node::NodeType;
+use dioxus_native_core::prelude::*;
+use dioxus_native_core::snapshot::RestoreStateError;
+use dioxus_native_core_macro::partial_derive_state;
+use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::Component;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RESOLVED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Default, PartialEq, Component)]
+struct Depth(usize);
+
+#[partial_derive_state]
+impl State for Depth {
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+    type ParentDependencies = (Depth,);
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new();
+
+    fn update<'a>(
+        &mut self,
+        _: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        RESOLVED.fetch_add(1, Ordering::Relaxed);
+        self.0 = parent.map_or(0, |(parent,)| parent.0 + 1);
+        true
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+fn create_element(tag: &str) -> NodeType {
+    NodeType::Element(ElementNode {
+        tag: tag.to_owned(),
+        namespace: None,
+        attributes: FxHashMap::default(),
+        listeners: FxHashSet::default(),
+    })
+}
+
+fn create_dom(tags: &[&str]) -> (RealDom, Vec<NodeId>) {
+    let mut dom: RealDom = RealDom::new([Depth::to_type_erased()]);
+    let mut parent = dom.root_id();
+    let mut ids = Vec::new();
+    for tag in tags {
+        let id = dom.create_node(create_element(tag)).id();
+        dom.get_mut(parent).unwrap().add_child(id);
+        ids.push(id);
+        parent = id;
+    }
+    (dom, ids)
+}
+
+#[test]
+fn restored_states_are_not_resolved_again() {
+    let (mut dom, _) = create_dom(&["div", "span"]);
+    dom.update_state(SendAnyMap::new());
+    let snapshot = dom.snapshot_state::<Depth>();
+
+    let (mut restored, ids) = create_dom(&["div", "span"]);
+    restored.restore_state(snapshot.clone()).unwrap();
+    let resolved = RESOLVED.load(Ordering::Relaxed);
+    restored.update_state(SendAnyMap::new());
+    assert_eq!(RESOLVED.load(Ordering::Relaxed), resolved);
+    assert_eq!(
+        restored.get(ids[1]).unwrap().get::<Depth>().as_deref(),
+        Some(&Depth(2))
+    );
+
+    let (mut different, _) = create_dom(&["div", "p"]);
+    assert_eq!(
+        different.restore_state(snapshot),
+        Err(RestoreStateError::StructureMismatch)
+    );
+}
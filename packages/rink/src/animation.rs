@@ -0,0 +1,718 @@
+//! CSS transitions and animations.
+//!
+//! The style passes only know the values an element is styled with. This module keeps track of the values that are
+//! shown while an element transitions between them, and of the elements that need to be drawn again every frame.
+//!
+//! Terminals don't have stylesheets to declare `@keyframes` in. Keyframes registered with
+//! [`TuiContext::add_keyframes`](crate::TuiContext::add_keyframes) are resolved with the rest of the style by
+//! [`AnimatedStyle`](dioxus_native_core::animation::AnimatedStyle), and `animation-name` can also pick one of the
+//! [`Keyframes`] built into the renderer.
+
+use std::time::{Duration, Instant};
+
+use dioxus_native_core::{
+    animation::{parse_time, split_list, split_words, Animation, Timing, TimingFunction},
+    prelude::*,
+    FxDashSet,
+};
+use rustc_hash::FxHashMap;
+use taffy::prelude::*;
+use tui::style::Color;
+
+use crate::{
+    layout::TaffyLayout,
+    style::{to_rgb, RinkColor, RinkStyle},
+    style_attributes::StyleModifier,
+    unit_to_layout_space,
+};
+
+/// The values of an element that can be transitioned and animated
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum AnimatedProperty {
+    Color,
+    BackgroundColor,
+    Width,
+    Height,
+}
+
+impl AnimatedProperty {
+    fn name(self) -> &'static str {
+        match self {
+            AnimatedProperty::Color => "color",
+            AnimatedProperty::BackgroundColor => "background-color",
+            AnimatedProperty::Width => "width",
+            AnimatedProperty::Height => "height",
+        }
+    }
+}
+
+/// The transitions of an element, set with the `transition` attributes. The lists of the attributes are matched up by
+/// index, and shorter lists are repeated like in css.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Transitions {
+    properties: Vec<String>,
+    durations: Vec<Duration>,
+    delays: Vec<Duration>,
+    timing_functions: Vec<TimingFunction>,
+}
+
+impl Transitions {
+    pub(crate) fn apply(&mut self, name: &str, value: &str) {
+        let list = split_list(value);
+        match name {
+            "transition" => {
+                *self = Self::default();
+                for transition in list {
+                    let mut property = "all";
+                    let mut duration = None;
+                    let mut delay = None;
+                    let mut timing_function = TimingFunction::default();
+                    for word in split_words(transition) {
+                        if let Some(time) = parse_time(word) {
+                            // the first time is the duration and the second time is the delay
+                            if duration.is_none() {
+                                duration = Some(time);
+                            } else {
+                                delay = Some(time);
+                            }
+                        } else if let Some(function) = TimingFunction::parse(word) {
+                            timing_function = function;
+                        } else {
+                            property = word;
+                        }
+                    }
+                    self.properties.push(property.to_string());
+                    self.durations.push(duration.unwrap_or_default());
+                    self.delays.push(delay.unwrap_or_default());
+                    self.timing_functions.push(timing_function);
+                }
+            }
+            "transition-delay" => self.delays = list.into_iter().filter_map(parse_time).collect(),
+            "transition-duration" => {
+                self.durations = list.into_iter().filter_map(parse_time).collect()
+            }
+            "transition-property" => {
+                self.properties = list.into_iter().map(str::to_string).collect()
+            }
+            "transition-timing-function" => {
+                self.timing_functions = list.into_iter().filter_map(TimingFunction::parse).collect()
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns true if no property of the element is transitioned
+    fn is_empty(&self) -> bool {
+        self.durations.iter().all(Duration::is_zero)
+    }
+
+    /// How a property transitions to a new value, or [`None`] if it changes immediately
+    fn timing(&self, property: AnimatedProperty) -> Option<Timing> {
+        fn repeated<T: Copy + Default>(list: &[T], index: usize) -> T {
+            list.get(index % list.len().max(1))
+                .copied()
+                .unwrap_or_default()
+        }
+
+        // like in css, all properties are transitioned if no property is set
+        let index = if self.properties.is_empty() {
+            0
+        } else {
+            self.properties
+                .iter()
+                .rposition(|p| p == "all" || p == property.name())?
+        };
+        let timing = Timing {
+            duration: repeated(&self.durations, index),
+            delay: repeated(&self.delays, index),
+            timing_function: repeated(&self.timing_functions, index),
+        };
+        (!timing.duration.is_zero()).then_some(timing)
+    }
+}
+
+/// The animations built into the renderer that can be used as the `animation-name` of an element. They fade the
+/// colors of the element and its children
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Keyframes {
+    /// hide the element for the second half of every iteration
+    Blink,
+    /// fade the element to half of its opacity and back
+    Pulse,
+    /// fade the element in from transparent
+    FadeIn,
+    /// fade the element out to transparent
+    FadeOut,
+}
+
+impl Keyframes {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blink" => Some(Self::Blink),
+            "pulse" => Some(Self::Pulse),
+            "fade-in" => Some(Self::FadeIn),
+            "fade-out" => Some(Self::FadeOut),
+            _ => None,
+        }
+    }
+
+    fn opacity(self, progress: f32) -> f32 {
+        match self {
+            Keyframes::Blink => (progress < 0.5) as u8 as f32,
+            Keyframes::Pulse => 0.5 + (progress - 0.5).abs(),
+            Keyframes::FadeIn => progress,
+            Keyframes::FadeOut => 1.0 - progress,
+        }
+    }
+}
+
+/// A value that can move between two values
+trait Interpolate: Copy + PartialEq {
+    fn interpolate(self, to: Self, progress: f32) -> Self;
+}
+
+fn lerp(from: f32, to: f32, progress: f32) -> f32 {
+    from + (to - from) * progress
+}
+
+impl Interpolate for Option<RinkColor> {
+    fn interpolate(self, to: Self, progress: f32) -> Self {
+        match (self, to) {
+            (Some(from), Some(to)) if from.color != Color::Reset && to.color != Color::Reset => {
+                let from_rgb = to_rgb(from.color);
+                let to_rgb = to_rgb(to.color);
+                let channel = |i: usize| {
+                    lerp(from_rgb[i] as f32, to_rgb[i] as f32, progress).clamp(0.0, 255.0) as u8
+                };
+                Some(RinkColor {
+                    color: Color::Rgb(channel(0), channel(1), channel(2)),
+                    alpha: lerp(from.alpha as f32, to.alpha as f32, progress).clamp(0.0, 255.0)
+                        as u8,
+                })
+            }
+            // a missing color fades in from transparent and out to transparent
+            (None, Some(to)) => Some(RinkColor { alpha: 0, ..to }).interpolate(Some(to), progress),
+            (Some(from), None) => {
+                Some(from).interpolate(Some(RinkColor { alpha: 0, ..from }), progress)
+            }
+            // colors that can't be mixed switch halfway through
+            _ if progress < 0.5 => self,
+            _ => to,
+        }
+    }
+}
+
+impl Interpolate for Dimension {
+    fn interpolate(self, to: Self, progress: f32) -> Self {
+        match (self, to) {
+            (Dimension::Points(from), Dimension::Points(to)) => {
+                Dimension::Points(lerp(from, to, progress))
+            }
+            (Dimension::Percent(from), Dimension::Percent(to)) => {
+                Dimension::Percent(lerp(from, to, progress))
+            }
+            // sizes that can't be mixed switch halfway through
+            _ if progress < 0.5 => self,
+            _ => to,
+        }
+    }
+}
+
+/// A value that is moving to the value an element is styled with
+#[derive(Clone, Copy)]
+struct Tween<T> {
+    from: T,
+    to: T,
+    start: Instant,
+    timing: Timing,
+}
+
+impl<T: Interpolate> Tween<T> {
+    fn value(&self, now: Instant) -> T {
+        let elapsed = now
+            .saturating_duration_since(self.start)
+            .saturating_sub(self.timing.delay);
+        let time = (elapsed.as_secs_f32() / self.timing.duration.as_secs_f32()).min(1.0);
+        self.from
+            .interpolate(self.to, self.timing.timing_function.ease(time))
+    }
+
+    fn finished(&self, now: Instant) -> bool {
+        now >= self.start + self.timing.delay + self.timing.duration
+    }
+
+    /// Start a transition to a new value from the value that is shown, or change the value immediately if it isn't
+    /// transitioned
+    fn retarget(tween: &mut Option<Self>, from: T, to: T, timing: Option<Timing>, now: Instant) {
+        if from == to {
+            return;
+        }
+        // transitions that are interrupted continue from where they are instead of jumping
+        let from = tween.as_ref().map_or(from, |tween| tween.value(now));
+        *tween = timing.map(|timing| Tween {
+            from,
+            to,
+            start: now,
+            timing,
+        });
+    }
+}
+
+/// An animation that started playing on an element
+struct PlayingAnimation {
+    animation: Animation,
+    keyframes: Keyframes,
+    start: Instant,
+    paused_at: Option<Instant>,
+    finished: bool,
+}
+
+impl PlayingAnimation {
+    fn elapsed(&self, now: Instant) -> Duration {
+        self.paused_at
+            .unwrap_or(now)
+            .saturating_duration_since(self.start)
+    }
+}
+
+/// The values of an element while they change
+struct NodeAnimations {
+    // the values the element was styled with the last time its state changed
+    fg: Option<RinkColor>,
+    bg: Option<RinkColor>,
+    size: Size<Dimension>,
+    fg_tween: Option<Tween<Option<RinkColor>>>,
+    bg_tween: Option<Tween<Option<RinkColor>>>,
+    width_tween: Option<Tween<Dimension>>,
+    height_tween: Option<Tween<Dimension>>,
+    animation: Option<PlayingAnimation>,
+}
+
+impl NodeAnimations {
+    fn is_playing(&self) -> bool {
+        self.fg_tween.is_some()
+            || self.bg_tween.is_some()
+            || self.width_tween.is_some()
+            || self.height_tween.is_some()
+            || self
+                .animation
+                .as_ref()
+                .is_some_and(|animation| !animation.finished && animation.paused_at.is_none())
+    }
+
+    /// The size the element is laid out with
+    fn size(&self, now: Instant) -> Size<Dimension> {
+        Size {
+            width: self
+                .width_tween
+                .map_or(self.size.width, |tween| tween.value(now)),
+            height: self
+                .height_tween
+                .map_or(self.size.height, |tween| tween.value(now)),
+        }
+    }
+}
+
+/// The values children inherit from the animations of their ancestors while they are drawn
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Inherited {
+    /// The color the parent is styled with and the color it is shown with, if they are different
+    fg: Option<(Option<RinkColor>, Option<RinkColor>)>,
+    opacity: f32,
+}
+
+impl Default for Inherited {
+    fn default() -> Self {
+        Self {
+            fg: None,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl Inherited {
+    /// Fade a color with the opacity of the animations of the element and its ancestors
+    pub fn fade(&self, color: RinkColor) -> RinkColor {
+        RinkColor {
+            alpha: (color.alpha as f32 * self.opacity.clamp(0.0, 1.0)) as u8,
+            ..color
+        }
+    }
+}
+
+/// The transitions and animations that play in the app
+pub(crate) struct Animations {
+    nodes: FxHashMap<NodeId, NodeAnimations>,
+    // the time of the frame that is drawn
+    now: Instant,
+}
+
+impl Animations {
+    pub fn new() -> Self {
+        Self {
+            nodes: FxHashMap::default(),
+            now: Instant::now(),
+        }
+    }
+
+    /// Start transitions and animations for the elements whose state changed
+    pub fn update(&mut self, rdom: &RealDom, taffy: &mut Taffy, changed: &FxDashSet<NodeId>) {
+        let now = Instant::now();
+        for id in changed.iter() {
+            let id = *id;
+            let node = rdom.get(id);
+            let states = node
+                .as_ref()
+                .and_then(|node| Some((node.get::<StyleModifier>()?, node.get::<TaffyLayout>()?)));
+            let Some((style, layout)) = states else {
+                self.nodes.remove(&id);
+                continue;
+            };
+            let transitions = &style.modifier.transitions;
+            let animation = &style.modifier.animation;
+            let keyframes = Keyframes::from_name(animation.name());
+
+            if transitions.is_empty() && keyframes.is_none() {
+                // a transition that stops early leaves the element at the size it is styled with
+                if let Some(node) = self.nodes.remove(&id) {
+                    if node.width_tween.is_some() || node.height_tween.is_some() {
+                        set_size(taffy, &layout, layout.style.size);
+                    }
+                }
+                continue;
+            }
+
+            // elements don't transition to the values they are created with
+            let node = self.nodes.entry(id).or_insert_with(|| NodeAnimations {
+                fg: style.core.fg,
+                bg: style.core.bg,
+                size: layout.style.size,
+                fg_tween: None,
+                bg_tween: None,
+                width_tween: None,
+                height_tween: None,
+                animation: None,
+            });
+
+            let timing = |property| transitions.timing(property);
+            Tween::retarget(
+                &mut node.fg_tween,
+                node.fg,
+                style.core.fg,
+                timing(AnimatedProperty::Color),
+                now,
+            );
+            Tween::retarget(
+                &mut node.bg_tween,
+                node.bg,
+                style.core.bg,
+                timing(AnimatedProperty::BackgroundColor),
+                now,
+            );
+            let size = layout.style.size;
+            Tween::retarget(
+                &mut node.width_tween,
+                node.size.width,
+                size.width,
+                timing(AnimatedProperty::Width),
+                now,
+            );
+            Tween::retarget(
+                &mut node.height_tween,
+                node.size.height,
+                size.height,
+                timing(AnimatedProperty::Height),
+                now,
+            );
+            node.fg = style.core.fg;
+            node.bg = style.core.bg;
+            node.size = size;
+
+            node.animation = match (node.animation.take(), keyframes) {
+                (_, None) => None,
+                // pausing and resuming an animation keeps the time it played for
+                (Some(mut playing), Some(_)) if playing.animation.same_as(animation) => {
+                    match (playing.paused_at, animation.paused()) {
+                        (None, true) => playing.paused_at = Some(now),
+                        (Some(paused_at), false) => {
+                            playing.start += now.saturating_duration_since(paused_at);
+                            playing.paused_at = None;
+                        }
+                        _ => {}
+                    }
+                    playing.animation = animation.clone();
+                    Some(playing)
+                }
+                (_, Some(keyframes)) => Some(PlayingAnimation {
+                    animation: animation.clone(),
+                    keyframes,
+                    start: now,
+                    paused_at: animation.paused().then_some(now),
+                    finished: false,
+                }),
+            };
+        }
+    }
+
+    /// Move the transitions and animations to the time of the next frame
+    pub fn advance(&mut self, rdom: &RealDom, taffy: &mut Taffy) {
+        self.now = Instant::now();
+        let now = self.now;
+        self.nodes.retain(|id, node| {
+            let Some(node_ref) = rdom.get(*id) else {
+                return false;
+            };
+
+            // sizes are animated by changing the style of the layout node, the final size is set once the transition
+            // is done
+            if node.width_tween.is_some() || node.height_tween.is_some() {
+                if let Some(layout) = node_ref.get::<TaffyLayout>() {
+                    set_size(taffy, &layout, node.size(now));
+                }
+            }
+
+            fn clear_finished<T: Interpolate>(tween: &mut Option<Tween<T>>, now: Instant) {
+                if tween.is_some_and(|tween| tween.finished(now)) {
+                    *tween = None;
+                }
+            }
+            clear_finished(&mut node.fg_tween, now);
+            clear_finished(&mut node.bg_tween, now);
+            clear_finished(&mut node.width_tween, now);
+            clear_finished(&mut node.height_tween, now);
+            if let Some(playing) = &mut node.animation {
+                playing.finished = playing.animation.finished(playing.elapsed(now));
+            }
+            true
+        });
+    }
+
+    /// The elements with a transition or animation that needs to draw more frames
+    pub fn playing(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.is_playing())
+            .map(|(id, _)| *id)
+    }
+
+    /// The style a node is shown with in the current frame, and the values its children inherit from it
+    pub fn style(
+        &self,
+        node: NodeId,
+        style: RinkStyle,
+        inherited: Inherited,
+    ) -> (RinkStyle, Inherited) {
+        let mut shown = style;
+        let mut opacity = inherited.opacity;
+
+        // children that inherit the color of their parent inherit the color it is shown with
+        if let Some((styled, shown_fg)) = inherited.fg {
+            if style.fg == styled {
+                shown.fg = shown_fg;
+            }
+        }
+        if let Some(node) = self.nodes.get(&node) {
+            if let Some(tween) = &node.fg_tween {
+                shown.fg = tween.value(self.now);
+            }
+            if let Some(tween) = &node.bg_tween {
+                shown.bg = tween.value(self.now);
+            }
+            if let Some(playing) = &node.animation {
+                if let Some(progress) = playing.animation.progress(playing.elapsed(self.now)) {
+                    opacity *= playing.keyframes.opacity(progress);
+                }
+            }
+        }
+
+        let children = Inherited {
+            fg: (shown.fg != style.fg).then_some((style.fg, shown.fg)),
+            opacity,
+        };
+        shown.fg = shown.fg.map(|c| children.fade(c));
+        shown.bg = shown.bg.map(|c| children.fade(c));
+        (shown, children)
+    }
+}
+
+/// Lay out an element with a size instead of the size it is styled with
+fn set_size(taffy: &mut Taffy, layout: &TaffyLayout, size: Size<Dimension>) {
+    fn scale_dimension(d: Dimension) -> Dimension {
+        match d {
+            Dimension::Points(p) => Dimension::Points(unit_to_layout_space(p)),
+            d => d,
+        }
+    }
+
+    let node = layout.node.unwrap();
+    let size = Size {
+        width: scale_dimension(size.width),
+        height: scale_dimension(size.height),
+    };
+    let style = taffy.style(node).unwrap();
+    if style.size != size {
+        let style = Style {
+            size,
+            ..style.clone()
+        };
+        taffy.set_style(node, style).unwrap();
+    }
+}
+
+#[test]
+fn parses_transitions() {
+    use dioxus_native_core::animation::StepPosition;
+
+    let mut transitions = Transitions::default();
+    assert!(transitions.is_empty());
+    assert_eq!(transitions.timing(AnimatedProperty::Color), None);
+
+    transitions.apply("transition", "color 1s steps(2), width 250ms 500ms");
+    assert!(!transitions.is_empty());
+    assert_eq!(
+        transitions.timing(AnimatedProperty::Color),
+        Some(Timing {
+            duration: Duration::from_secs(1),
+            delay: Duration::ZERO,
+            timing_function: TimingFunction::Steps(2, StepPosition::End),
+        })
+    );
+    assert_eq!(
+        transitions.timing(AnimatedProperty::Width),
+        Some(Timing {
+            duration: Duration::from_millis(250),
+            delay: Duration::from_millis(500),
+            timing_function: TimingFunction::default(),
+        })
+    );
+    assert_eq!(transitions.timing(AnimatedProperty::Height), None);
+
+    // shorter lists are repeated for every property
+    transitions.apply("transition-duration", "2s");
+    let duration = |transitions: &Transitions, property| {
+        transitions
+            .timing(property)
+            .map(|timing: Timing| timing.duration)
+    };
+    assert_eq!(
+        duration(&transitions, AnimatedProperty::Width),
+        Some(Duration::from_secs(2))
+    );
+
+    // the last property in the list that matches wins
+    transitions.apply("transition", "all 1s, height 3s");
+    assert_eq!(
+        duration(&transitions, AnimatedProperty::Color),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        duration(&transitions, AnimatedProperty::Height),
+        Some(Duration::from_secs(3))
+    );
+}
+
+#[test]
+fn interpolates_values() {
+    let rgb = |r, g, b, alpha| {
+        Some(RinkColor {
+            color: Color::Rgb(r, g, b),
+            alpha,
+        })
+    };
+    assert_eq!(
+        rgb(0, 0, 0, 255).interpolate(rgb(255, 100, 0, 255), 0.5),
+        rgb(127, 50, 0, 255)
+    );
+    // a missing color fades from transparent
+    assert_eq!(
+        None.interpolate(rgb(255, 0, 0, 255), 0.5),
+        rgb(255, 0, 0, 127)
+    );
+    let reset = Some(RinkColor {
+        color: Color::Reset,
+        alpha: 255,
+    });
+    assert_eq!(reset.interpolate(rgb(0, 0, 0, 255), 0.25), reset);
+    assert_eq!(
+        reset.interpolate(rgb(0, 0, 0, 255), 0.75),
+        rgb(0, 0, 0, 255)
+    );
+
+    assert_eq!(
+        Dimension::Points(0.0).interpolate(Dimension::Points(10.0), 0.5),
+        Dimension::Points(5.0)
+    );
+    assert_eq!(
+        Dimension::Percent(1.0).interpolate(Dimension::Percent(0.0), 0.25),
+        Dimension::Percent(0.75)
+    );
+    assert_eq!(
+        Dimension::Points(10.0).interpolate(Dimension::Auto, 0.4),
+        Dimension::Points(10.0)
+    );
+}
+
+#[test]
+fn plays_keyframes_and_tweens() {
+    use dioxus_native_core::animation::StepPosition;
+
+    assert_eq!(Keyframes::from_name("fade-out"), Some(Keyframes::FadeOut));
+    assert_eq!(Keyframes::from_name("spin"), None);
+    assert_eq!(Keyframes::Blink.opacity(0.25), 1.0);
+    assert_eq!(Keyframes::Blink.opacity(0.75), 0.0);
+    assert_eq!(Keyframes::Pulse.opacity(0.5), 0.5);
+    assert_eq!(Keyframes::FadeIn.opacity(0.25), 0.25);
+
+    let now = Instant::now();
+    let timing = Timing {
+        duration: Duration::from_secs(1),
+        delay: Duration::ZERO,
+        timing_function: TimingFunction::Steps(2, StepPosition::End),
+    };
+    let mut tween = None;
+    Tween::retarget(
+        &mut tween,
+        Dimension::Auto,
+        Dimension::Auto,
+        Some(timing),
+        now,
+    );
+    assert!(tween.is_none());
+
+    Tween::retarget(
+        &mut tween,
+        Dimension::Points(0.0),
+        Dimension::Points(10.0),
+        Some(timing),
+        now,
+    );
+    let running = tween.unwrap();
+    assert_eq!(running.value(now), Dimension::Points(0.0));
+    assert_eq!(
+        running.value(now + Duration::from_millis(750)),
+        Dimension::Points(5.0)
+    );
+    assert!(!running.finished(now + Duration::from_millis(500)));
+    assert!(running.finished(now + Duration::from_secs(1)));
+
+    // an interrupted tween continues from the value that is shown
+    let later = now + Duration::from_millis(750);
+    Tween::retarget(
+        &mut tween,
+        Dimension::Points(10.0),
+        Dimension::Points(0.0),
+        Some(timing),
+        later,
+    );
+    assert_eq!(tween.unwrap().from, Dimension::Points(5.0));
+
+    // values that are not transitioned change right away
+    Tween::retarget(
+        &mut tween,
+        Dimension::Points(0.0),
+        Dimension::Points(20.0),
+        None,
+        later,
+    );
+    assert!(tween.is_none());
+}
@@ -0,0 +1,10 @@
+use std::io::{self, Write};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+/// Copy text to the system clipboard with the OSC 52 escape sequence. Most terminals support it, including over ssh,
+/// but some only accept it after it is enabled in their settings. Terminals that don't support it ignore it.
+pub(crate) fn copy(w: &mut impl Write, text: &str) -> io::Result<()> {
+    write!(w, "\x1b]52;c;{}\x07", BASE64.encode(text))?;
+    w.flush()
+}
@@ -1,12 +1,25 @@
+use crate::GraphicsProtocol;
+
 #[derive(Clone, Copy)]
 #[non_exhaustive]
 pub struct Config {
     pub(crate) rendering_mode: RenderingMode,
+    pub(crate) palette: Palette,
     /// Controls if the terminal quit when the user presses `ctrl+c`?
     /// To handle quiting on your own, use the [crate::TuiContext] root context.
     pub(crate) ctrl_c_quit: bool,
     /// Controls if the terminal should dislay anything, usefull for testing.
     pub(crate) headless: bool,
+    /// The most edits the renderer applies between two frames.
+    pub(crate) mutation_budget: Option<usize>,
+    /// Controls if every frame drawn to the terminal is recorded.
+    pub(crate) record: bool,
+    /// How `img` elements are drawn.
+    pub(crate) graphics_protocol: GraphicsProtocol,
+    /// The most frames drawn per second while transitions and animations play.
+    pub(crate) frame_rate: u32,
+    /// The number of rows reserved below the prompt when the app is drawn inline instead of on the alternate screen.
+    pub(crate) inline: Option<u16>,
 }
 
 impl Config {
@@ -21,6 +34,12 @@ impl Config {
         }
     }
 
+    /// Remap the colors of the app before they are rendered, for example to make them easier to tell apart for color
+    /// blind users.
+    pub fn with_palette(self, palette: Palette) -> Self {
+        Self { palette, ..self }
+    }
+
     pub fn without_ctrl_c_quit(self) -> Self {
         Self {
             ctrl_c_quit: false,
@@ -34,14 +53,69 @@ impl Config {
             ..self
         }
     }
+
+    /// Limit the number of edits applied between two frames, so large updates are drawn over multiple frames
+    /// instead of freezing the terminal while they are applied.
+    pub fn with_mutation_budget(self, budget: usize) -> Self {
+        Self {
+            mutation_budget: Some(budget),
+            ..self
+        }
+    }
+
+    /// Record every frame drawn to the terminal, so the session can be exported with
+    /// [crate::TuiContext::export_asciinema] and shared without a screen recorder.
+    pub fn with_recording(self) -> Self {
+        Self {
+            record: true,
+            ..self
+        }
+    }
+
+    /// Draw images with a graphics protocol instead of the protocol detected with [`GraphicsProtocol::from_env`].
+    pub fn with_graphics_protocol(self, graphics_protocol: GraphicsProtocol) -> Self {
+        Self {
+            graphics_protocol,
+            ..self
+        }
+    }
+
+    /// Draw transitions and animations with at most `frame_rate` frames per second. Lower frame rates take less time
+    /// to draw over slow connections, like ssh sessions.
+    pub fn with_frame_rate(self, frame_rate: u32) -> Self {
+        Self { frame_rate, ..self }
+    }
+
+    /// Draw the app inline in `height` rows below the shell prompt, like fzf, instead of taking over the alternate
+    /// screen. The terminal scrolls up if there are not enough rows below the cursor. When the app quits, the rows are
+    /// cleared and the cursor is moved back to where it was.
+    ///
+    /// The height is limited to the height of the terminal.
+    pub fn inline(self, height: u16) -> Self {
+        Self {
+            inline: Some(height),
+            ..self
+        }
+    }
+
+    /// The most edits the renderer should apply between two frames, if there is a limit.
+    pub fn mutation_budget(&self) -> Option<usize> {
+        self.mutation_budget
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            rendering_mode: Default::default(),
+            rendering_mode: RenderingMode::from_env(),
+            palette: Default::default(),
             ctrl_c_quit: true,
             headless: false,
+            mutation_budget: None,
+            record: false,
+            graphics_protocol: GraphicsProtocol::from_env(),
+            frame_rate: 60,
+            inline: None,
         }
     }
 }
@@ -55,4 +129,40 @@ pub enum RenderingMode {
     /// 24 bit colors, most terminals support this
     #[default]
     Rgb,
+    /// no colors at all, the terminal's default colors are used everywhere
+    NoColor,
+}
+
+impl RenderingMode {
+    /// Pick the rendering mode from the environment of the terminal.
+    ///
+    /// This honors the [`NO_COLOR`](https://no-color.org) environment variable and only uses 24 bit colors if
+    /// `COLORTERM` is unset or advertises them.
+    pub fn from_env() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+            return Self::NoColor;
+        }
+        match std::env::var("COLORTERM") {
+            Ok(colorterm) if colorterm != "truecolor" && colorterm != "24bit" => Self::Ansi,
+            _ => Self::Rgb,
+        }
+    }
+}
+
+/// A transformation applied to every color before it is converted to the colors of the [`RenderingMode`].
+///
+/// Palettes are not applied in [`RenderingMode::BaseColors`] and [`RenderingMode::NoColor`].
+#[derive(Clone, Copy, Default)]
+pub enum Palette {
+    /// render colors as they are styled
+    #[default]
+    Default,
+    /// shift colors that are hard to tell apart without green cones into distinguishable colors
+    Deuteranopia,
+    /// shift colors that are hard to tell apart without red cones into distinguishable colors
+    Protanopia,
+    /// shift colors that are hard to tell apart without blue cones into distinguishable colors
+    Tritanopia,
+    /// remap every color with a custom function of the rgb channels
+    Custom(fn([u8; 3]) -> [u8; 3]),
 }
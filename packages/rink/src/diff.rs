@@ -0,0 +1,350 @@
+//! Draw only the cells of a frame that changed since the last frame was drawn.
+//!
+//! Every cell is packed into two words, so the rows of two frames can be compared a chunk of cells at a time with simd
+//! instructions. Only the chunks that differ are compared cell by cell, and the changed cells are grouped into spans
+//! that are drawn after a single cursor move.
+
+use std::io::{self, Write};
+
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::Color,
+};
+
+use crate::record::write_cells;
+
+/// The number of words a cell is packed into
+const WORDS_PER_CELL: usize = 2;
+/// The number of words that are compared at once
+const LANES: usize = 8;
+/// Unchanged cells between two changed cells in a row are drawn again if there are at most this many of them, because
+/// moving the cursor takes more bytes than drawing a few cells
+const MAX_GAP: u16 = 4;
+/// The symbol of cells without text, which doesn't collide with any char
+const EMPTY_SYMBOL: u64 = 0x11_0000;
+
+/// The packed cells of the last frame that was drawn, and a buffer to pack the next frame into
+#[derive(Default)]
+pub(crate) struct FrameDiff {
+    /// The area of the last frame, or [`None`] if the terminal needs to be drawn from scratch
+    area: Option<Rect>,
+    previous: PackedFrame,
+    next: PackedFrame,
+}
+
+impl FrameDiff {
+    /// Draw every cell of the next frame, for example after the terminal was cleared
+    pub(crate) fn invalidate(&mut self) {
+        self.area = None;
+    }
+
+    /// Draw the cells of `next` that changed since `previous` was drawn
+    pub(crate) fn draw(
+        &mut self,
+        out: &mut impl Write,
+        previous: Option<&Buffer>,
+        next: &Buffer,
+    ) -> io::Result<()> {
+        self.next.pack(next);
+
+        let mut output = String::new();
+        match previous {
+            Some(previous) if self.area == Some(next.area) && previous.area == next.area => {
+                let spans = self.changed_spans(previous, next);
+                let cells = spans.into_iter().flat_map(|span| {
+                    (span.start..span.end).map(move |x| (x, span.y, next.get(x, span.y)))
+                });
+                write_cells(cells, &mut output);
+            }
+            _ => {
+                let cells = next.content.iter().enumerate().map(|(i, cell)| {
+                    let (x, y) = next.pos_of(i);
+                    (x, y, cell)
+                });
+                write_cells(cells, &mut output);
+            }
+        }
+
+        std::mem::swap(&mut self.previous, &mut self.next);
+        self.area = Some(next.area);
+        out.write_all(output.as_bytes())
+    }
+
+    /// Find the spans of cells that changed in every row. `previous` and `next` must have the same area
+    fn changed_spans(&self, previous: &Buffer, next: &Buffer) -> Vec<Span> {
+        let area = next.area;
+        let row_words = area.width as usize * WORDS_PER_CELL;
+        let mut spans = Vec::new();
+        if row_words == 0 {
+            return spans;
+        }
+
+        let rows = self
+            .previous
+            .words
+            .chunks(row_words)
+            .zip(self.next.words.chunks(row_words));
+        for (row, (previous_row, next_row)) in rows.enumerate() {
+            let y = area.top() + row as u16;
+            let mut span: Option<Span> = None;
+            let mut changed = |x: u16| match &mut span {
+                Some(span) if x - span.end <= MAX_GAP => span.end = x + 1,
+                _ => {
+                    if let Some(span) = span.replace(Span {
+                        y,
+                        start: x,
+                        end: x + 1,
+                    }) {
+                        spans.push(span);
+                    }
+                }
+            };
+
+            // symbols that don't fit in a word are not compared by the packed words
+            if self.previous.complex_rows[row] || self.next.complex_rows[row] {
+                for x in area.left()..area.right() {
+                    if previous.get(x, y) != next.get(x, y) {
+                        changed(x);
+                    }
+                }
+            } else {
+                let chunks = previous_row.chunks(LANES).zip(next_row.chunks(LANES));
+                for (chunk, (previous_chunk, next_chunk)) in chunks.enumerate() {
+                    if chunk_eq(previous_chunk, next_chunk) {
+                        continue;
+                    }
+                    let cells = previous_chunk
+                        .chunks(WORDS_PER_CELL)
+                        .zip(next_chunk.chunks(WORDS_PER_CELL));
+                    for (i, (previous_cell, next_cell)) in cells.enumerate() {
+                        if previous_cell != next_cell {
+                            changed(area.left() + (chunk * LANES / WORDS_PER_CELL + i) as u16);
+                        }
+                    }
+                }
+            }
+
+            spans.extend(span);
+        }
+        spans
+    }
+}
+
+/// A run of cells in a row that are drawn together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    y: u16,
+    start: u16,
+    end: u16,
+}
+
+/// The cells of a frame, packed into words
+#[derive(Default)]
+struct PackedFrame {
+    words: Vec<u64>,
+    /// The rows with cells whose symbol is more than one char, like graphemes with combining marks. Wide glyphs are a
+    /// single char followed by cells without a symbol, so they are packed like any other cell
+    complex_rows: Vec<bool>,
+}
+
+impl PackedFrame {
+    fn pack(&mut self, buffer: &Buffer) {
+        self.words.clear();
+        self.complex_rows.clear();
+        self.complex_rows.resize(buffer.area.height as usize, false);
+        let width = buffer.area.width as usize;
+        for (i, cell) in buffer.content.iter().enumerate() {
+            let (symbol, complex) = pack_symbol(&cell.symbol);
+            if complex {
+                self.complex_rows[i / width] = true;
+            }
+            self.words.extend([
+                (symbol << 16) | cell.modifier.bits() as u64,
+                pack_style(cell),
+            ]);
+        }
+    }
+}
+
+/// Pack the symbol of a cell. Returns if the symbol was too long to pack
+fn pack_symbol(symbol: &str) -> (u64, bool) {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => (EMPTY_SYMBOL, false),
+        (Some(c), None) => (c as u64, false),
+        (Some(_), Some(_)) => (0, true),
+    }
+}
+
+fn pack_style(cell: &Cell) -> u64 {
+    (pack_color(cell.fg) << 32) | pack_color(cell.bg)
+}
+
+fn pack_color(color: Color) -> u64 {
+    match color {
+        Color::Reset => 0,
+        Color::Black => 1,
+        Color::Red => 2,
+        Color::Green => 3,
+        Color::Yellow => 4,
+        Color::Blue => 5,
+        Color::Magenta => 6,
+        Color::Cyan => 7,
+        Color::Gray => 8,
+        Color::DarkGray => 9,
+        Color::LightRed => 10,
+        Color::LightGreen => 11,
+        Color::LightYellow => 12,
+        Color::LightBlue => 13,
+        Color::LightMagenta => 14,
+        Color::LightCyan => 15,
+        Color::White => 16,
+        Color::Indexed(index) => (1 << 24) | index as u64,
+        Color::Rgb(r, g, b) => (2 << 24) | ((r as u64) << 16) | ((g as u64) << 8) | b as u64,
+    }
+}
+
+/// Check if two chunks of at most [`LANES`] words are equal
+#[cfg(feature = "simd")]
+fn chunk_eq(a: &[u64], b: &[u64]) -> bool {
+    use packed_simd::u64x8;
+    if a.len() == LANES && b.len() == LANES {
+        u64x8::from_slice_unaligned(a)
+            .eq(u64x8::from_slice_unaligned(b))
+            .all()
+    } else {
+        a == b
+    }
+}
+
+/// Check if two chunks of at most [`LANES`] words are equal. The comparison of the small fixed size chunks is
+/// vectorized by the compiler on stable rust. Enable the `simd` feature on nightly to use explicit simd instructions.
+#[cfg(not(feature = "simd"))]
+fn chunk_eq(a: &[u64], b: &[u64]) -> bool {
+    a == b
+}
+
+/// A crossterm backend that leaves drawing the cells of frames to [`FrameDiff`]. The cells tui finds changed are
+/// ignored, everything else is passed through to the terminal.
+pub(crate) struct DiffBackend<W: Write> {
+    inner: CrosstermBackend<W>,
+}
+
+impl<W: Write> DiffBackend<W> {
+    pub(crate) fn new(out: W) -> Self {
+        Self {
+            inner: CrosstermBackend::new(out),
+        }
+    }
+}
+
+impl<W: Write> Write for DiffBackend<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Write::flush(&mut self.inner)
+    }
+}
+
+impl<W: Write> Backend for DiffBackend<W> {
+    fn draw<'a, I>(&mut self, _: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        self.inner.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.inner.set_cursor(x, y)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        self.inner.size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+#[test]
+fn draws_changed_spans() {
+    use tui::style::Style;
+
+    let area = Rect::new(0, 0, 20, 3);
+    let mut previous = Buffer::empty(area);
+    previous.set_string(0, 0, "hello world", Style::default());
+    previous.set_string(0, 2, "same", Style::default());
+    let mut next = previous.clone();
+    // changes close together are drawn as one span
+    next.set_string(0, 0, "jelLo", Style::default());
+    next.set_string(15, 0, "x", Style::default().fg(Color::Red));
+    next.set_string(3, 1, "é", Style::default());
+
+    let mut diff = FrameDiff::default();
+    diff.previous.pack(&previous);
+    diff.next.pack(&next);
+    let span = |y, start, end| Span { y, start, end };
+    assert_eq!(
+        diff.changed_spans(&previous, &next),
+        [span(0, 0, 4), span(0, 15, 16), span(1, 3, 4)]
+    );
+
+    // only the changed cells are drawn once the first frame was drawn
+    let mut diff = FrameDiff::default();
+    let mut out = Vec::new();
+    diff.draw(&mut out, None, &previous).unwrap();
+    assert!(String::from_utf8_lossy(&out).contains("same"));
+    out.clear();
+    diff.draw(&mut out, Some(&previous), &next).unwrap();
+    let out = String::from_utf8_lossy(&out);
+    assert!(out.contains("jelL") && out.contains('x') && !out.contains("same"));
+}
+
+#[test]
+fn draws_wide_glyphs() {
+    let area = Rect::new(0, 0, 4, 1);
+    let wide = |cells: [&str; 4]| {
+        let mut buffer = Buffer::empty(area);
+        for (x, symbol) in cells.into_iter().enumerate() {
+            buffer.get_mut(x as u16, 0).symbol = symbol.to_string();
+        }
+        buffer
+    };
+    // the glyph covers the cell after it, which has no symbol
+    let previous = wide(["中", "", "a", " "]);
+    let next = wide(["x", "y", "a", "e\u{301}"]);
+
+    let mut diff = FrameDiff::default();
+    let mut out = Vec::new();
+    diff.draw(&mut out, None, &previous).unwrap();
+    // the cursor is past the glyph after it was drawn, so it is not moved before the next cell
+    assert_eq!(String::from_utf8_lossy(&out), "\x1b[1;1H\x1b[0m中a \x1b[0m");
+
+    out.clear();
+    diff.draw(&mut out, Some(&previous), &next).unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&out),
+        "\x1b[1;1H\x1b[0mxyae\u{301}\x1b[0m"
+    );
+}
@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use tui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+use crate::style::to_rgb;
+
+/// Convert a rendered screen into a `<pre>` element with a styled span for every run of cells with the same style.
+pub(crate) fn buffer_to_html(buffer: &Buffer) -> String {
+    let area = buffer.area();
+    let mut html = String::from("<pre style=\"font-family: monospace; line-height: 1.2\">");
+
+    for y in area.top()..area.bottom() {
+        if y != area.top() {
+            html.push('\n');
+        }
+        // Unstyled cells are written without a span
+        let mut current_style = String::new();
+        for x in area.left()..area.right() {
+            let cell = buffer.get(x, y);
+            let style = cell_style(cell.fg, cell.bg, cell.modifier);
+            if current_style != style {
+                if !current_style.is_empty() {
+                    html.push_str("</span>");
+                }
+                if !style.is_empty() {
+                    let _ = write!(html, "<span style=\"{style}\">");
+                }
+                current_style = style;
+            }
+            escape_into(&cell.symbol, &mut html);
+        }
+        if !current_style.is_empty() {
+            html.push_str("</span>");
+        }
+    }
+
+    html.push_str("</pre>");
+    html
+}
+
+fn cell_style(mut fg: Color, mut bg: Color, modifier: Modifier) -> String {
+    if modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+    }
+
+    let mut style = String::new();
+    if fg != Color::Reset {
+        let [r, g, b] = to_rgb(fg);
+        let _ = write!(style, "color: #{r:02x}{g:02x}{b:02x}; ");
+    }
+    if bg != Color::Reset {
+        let [r, g, b] = to_rgb(bg);
+        let _ = write!(style, "background-color: #{r:02x}{g:02x}{b:02x}; ");
+    }
+    if modifier.contains(Modifier::BOLD) {
+        style.push_str("font-weight: bold; ");
+    }
+    if modifier.contains(Modifier::DIM) {
+        style.push_str("opacity: 0.5; ");
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        style.push_str("font-style: italic; ");
+    }
+    match (
+        modifier.contains(Modifier::UNDERLINED),
+        modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => style.push_str("text-decoration: underline line-through; "),
+        (true, false) => style.push_str("text-decoration: underline; "),
+        (false, true) => style.push_str("text-decoration: line-through; "),
+        (false, false) => {}
+    }
+    style.truncate(style.trim_end().len());
+    style
+}
+
+fn escape_into(text: &str, html: &mut String) {
+    for c in text.chars() {
+        match c {
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '&' => html.push_str("&amp;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+#[test]
+fn export_styled_cells() {
+    use tui::{layout::Rect, style::Style};
+
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+    buffer.set_string(0, 0, "a<", Style::default().fg(Color::Rgb(255, 0, 0)));
+    buffer.set_string(
+        0,
+        1,
+        "b",
+        Style::default()
+            .bg(Color::Rgb(0, 0, 255))
+            .add_modifier(Modifier::BOLD),
+    );
+
+    assert_eq!(
+        buffer_to_html(&buffer),
+        "<pre style=\"font-family: monospace; line-height: 1.2\">\
+        <span style=\"color: #ff0000;\">a&lt;</span> \n\
+        <span style=\"background-color: #0000ff; font-weight: bold;\">b</span>  \
+        </pre>"
+    );
+}
@@ -2,9 +2,15 @@ use crossterm::event::{
     Event as TermEvent, KeyCode as TermKeyCode, KeyModifiers, ModifierKeyCode, MouseButton,
     MouseEventKind,
 };
+use dioxus_native_core::focus::FocusState;
+use dioxus_native_core::node::OwnedAttributeValue;
+use dioxus_native_core::node_ref::AttributeMask;
+use dioxus_native_core::node_watcher::AttributeWatcher;
 use dioxus_native_core::prelude::*;
 use dioxus_native_core::real_dom::NodeImmutable;
+use dioxus_native_core::tree::TreeRef;
 use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::UniqueView;
 
 use dioxus_html::geometry::euclid::{Point2D, Rect, Size2D};
 use dioxus_html::geometry::{
@@ -13,20 +19,25 @@ use dioxus_html::geometry::{
 use dioxus_html::input_data::keyboard_types::{Code, Key, Location, Modifiers};
 use dioxus_html::input_data::MouseButtonSet as DioxusMouseButtons;
 use dioxus_html::input_data::{MouseButton as DioxusMouseButton, MouseButtonSet};
-use dioxus_html::{event_bubbles, FocusData, KeyboardData, MouseData, WheelData};
+use dioxus_html::{event_bubbles, FocusData, KeyboardData, MouseData, SelectionData, WheelData};
 use std::any::Any;
 use std::collections::HashMap;
 use std::{
     cell::{RefCell, RefMut},
     rc::Rc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use taffy::geometry::{Point, Size};
 use taffy::{prelude::Layout, Taffy};
 
-use crate::focus::{Focus, Focused};
+use crate::focus::{FocusTracker, Focused};
 use crate::layout::TaffyLayout;
-use crate::{get_abs_layout, layout_to_screen_space, FocusState};
+use crate::prevent_default::PreventDefault;
+use crate::render::PaintOrder;
+use crate::scroll::{is_clipped, scroll, ScrollDelta};
+use crate::widgets::RinkWidgetTraitObject;
+use crate::{get_abs_layout, layout_to_screen_space, Selection};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Event {
@@ -43,6 +54,8 @@ pub enum EventData {
     Focus(FocusData),
     Wheel(WheelData),
     Form(FormData),
+    Clipboard(ClipboardData),
+    Selection(SelectionData),
 }
 
 impl EventData {
@@ -53,6 +66,8 @@ impl EventData {
             EventData::Focus(f) => Rc::new(f),
             EventData::Wheel(w) => Rc::new(w),
             EventData::Form(f) => Rc::new(f.into_html()),
+            EventData::Clipboard(c) => Rc::new(c.into_html()),
+            EventData::Selection(s) => Rc::new(s),
         }
     }
 }
@@ -76,6 +91,19 @@ impl FormData {
     }
 }
 
+/// The data of a paste event. Unlike the html clipboard data, it includes the text that was pasted so widgets can
+/// insert it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClipboardData {
+    pub text: String,
+}
+
+impl ClipboardData {
+    fn into_html(self) -> dioxus_html::ClipboardData {
+        dioxus_html::ClipboardData {}
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Files {
     files: FxHashMap<String, File>,
@@ -92,18 +120,61 @@ pub struct InnerInputState {
     mouse: Option<MouseData>,
     wheel: Option<WheelData>,
     last_key_pressed: Option<(KeyboardData, Instant)>,
-    pub(crate) focus_state: FocusState,
+    pub(crate) focus_state: FocusTracker,
+    // A node the app asked to focus, it is focused the next time events are resolved
+    requested_focus: Option<NodeId>,
+    // The nodes under the mouse with the :hover pseudo-class, from the innermost node to the root
+    hovered: Vec<NodeId>,
+    // The nodes with the :active pseudo-class while a mouse button is held
+    active: Vec<NodeId>,
+    // If an element was scrolled since the last time the screen was drawn
+    scrolled: bool,
+    // The text selected on the screen, shared with the TuiContext
+    pub(crate) selection: Arc<Mutex<Option<Selection>>>,
+    // Where the primary button was pressed, if the user may be dragging to select text
+    selection_start: Option<ScreenPoint>,
+    // If the selection changed since the last time the screen was drawn
+    selection_changed: bool,
     // subscribers: Vec<Rc<dyn Fn() + 'static>>,
 }
 
 impl InnerInputState {
-    fn create(rdom: &mut RealDom) -> Self {
+    fn create() -> Self {
         Self {
             mouse: None,
             wheel: None,
             last_key_pressed: None,
             // subscribers: Vec::new(),
-            focus_state: FocusState::create(rdom),
+            focus_state: FocusTracker::default(),
+            requested_focus: None,
+            hovered: Vec::new(),
+            active: Vec::new(),
+            scrolled: false,
+            selection: Default::default(),
+            selection_start: None,
+            selection_changed: false,
+        }
+    }
+
+    /// Returns true if an element was scrolled since this was last called
+    pub(crate) fn take_scrolled(&mut self) -> bool {
+        std::mem::take(&mut self.scrolled)
+    }
+
+    pub(crate) fn request_focus(&mut self, id: NodeId) {
+        self.requested_focus = Some(id);
+    }
+
+    /// Returns true if the selection changed since this was last called
+    pub(crate) fn take_selection_changed(&mut self) -> bool {
+        std::mem::take(&mut self.selection_changed)
+    }
+
+    pub(crate) fn set_selection(&mut self, selection: Option<Selection>) {
+        let mut current = self.selection.lock().unwrap();
+        if *current != selection {
+            *current = selection;
+            self.selection_changed = true;
         }
     }
 
@@ -175,6 +246,7 @@ impl InnerInputState {
 
         self.wheel = None;
 
+        self.focus_state.forget_removed(dom);
         let old_focus = self.focus_state.last_focused_id;
 
         evts.retain(|e| match &e.1 {
@@ -191,8 +263,24 @@ impl InnerInputState {
             self.apply_event(e);
         }
 
+        for e in evts.iter() {
+            if let ("keydown", EventData::Keyboard(k)) = e {
+                self.scroll_with_key(k, layout, dom);
+            }
+        }
+
         self.resolve_mouse_events(previous_mouse, resolved_events, layout, dom);
 
+        if let Some(id) = self.requested_focus.take() {
+            let focusable = dom.get(id).and_then(|node| {
+                node.get::<FocusState>()
+                    .map(|focus| focus.level.focusable())
+            });
+            if focusable == Some(true) {
+                self.focus_state.set_focus(dom, id);
+            }
+        }
+
         if old_focus != self.focus_state.last_focused_id {
             // elements with listeners will always have a element id
             if let Some(id) = self.focus_state.last_focused_id {
@@ -224,6 +312,36 @@ impl InnerInputState {
         // }
     }
 
+    /// Scroll the element that contains the focused element, or the element under the mouse if nothing is focused
+    fn scroll_with_key(&mut self, data: &KeyboardData, layout: &Taffy, dom: &mut RealDom) {
+        let delta = match data.code() {
+            Code::ArrowUp => ScrollDelta::Rows(-1),
+            Code::ArrowDown => ScrollDelta::Rows(1),
+            Code::PageUp => ScrollDelta::Pages(-1),
+            Code::PageDown => ScrollDelta::Pages(1),
+            Code::Home => ScrollDelta::Start,
+            Code::End => ScrollDelta::End,
+            _ => return,
+        };
+        let target = match self.focus_state.last_focused_id {
+            Some(id) => {
+                let focused = dom.get(id).unwrap();
+                // widgets like inputs and sliders use the arrow keys themselves
+                if focused.get::<RinkWidgetTraitObject>().is_some()
+                    || focused.get::<PreventDefault>().map(|p| *p) == Some(PreventDefault::KeyDown)
+                {
+                    return;
+                }
+                id
+            }
+            None => match self.hovered.first() {
+                Some(id) => *id,
+                None => return,
+            },
+        };
+        self.scrolled |= scroll(dom, layout, target, delta);
+    }
+
     fn resolve_mouse_events(
         &mut self,
         previous_mouse: Option<MouseData>,
@@ -247,6 +365,69 @@ impl InnerInputState {
             layout_rect.contains(point.cast())
         }
 
+        // check if the point is in the node, and not in the part of the node that is clipped by an ancestor or covered
+        // by a node that is drawn over it
+        fn node_contains_point(
+            node: NodeRef,
+            node_layout: &Layout,
+            layout: &Taffy,
+            point: ScreenPoint,
+        ) -> bool {
+            layout_contains_point(node_layout, point)
+                && !is_clipped(node, layout, point.x as f32, point.y as f32)
+                && !is_covered(node, layout, point)
+        }
+
+        // find the node that is on top at the point
+        fn topmost_node(dom: &RealDom, layout: &Taffy, point: ScreenPoint) -> Option<NodeId> {
+            let is_under_point = |id: &NodeId| {
+                dom.get(*id).is_some_and(|node| {
+                    layout_contains_point(&get_abs_layout(node, layout), point)
+                        && !is_clipped(node, layout, point.x as f32, point.y as f32)
+                })
+            };
+            // the node that was drawn last at the point is on top
+            match dom.raw_world().borrow::<UniqueView<PaintOrder>>() {
+                Ok(order) => order.0.iter().rev().copied().find(is_under_point),
+                // nodes are drawn in tree order, if the dom wasn't drawn yet
+                Err(_) => {
+                    let mut topmost = None;
+                    dom.traverse_depth_first(|node| {
+                        if is_under_point(&node.id()) {
+                            topmost = Some(node.id());
+                        }
+                    });
+                    topmost
+                }
+            }
+        }
+
+        // check if the node on top at the point is not the node or one of its descendants
+        fn is_covered(node: NodeRef, layout: &Taffy, point: ScreenPoint) -> bool {
+            let dom = node.real_dom();
+            let tree = dom.tree_ref();
+            let mut current = topmost_node(dom, layout, point);
+            while let Some(id) = current {
+                if id == node.id() {
+                    return false;
+                }
+                current = tree.parent_id_advanced(id, true);
+            }
+            true
+        }
+
+        // find the node on top at the point and all of its ancestors
+        fn hovered_nodes(dom: &RealDom, layout: &Taffy, point: ScreenPoint) -> Vec<NodeId> {
+            let tree = dom.tree_ref();
+            let mut hovered = Vec::new();
+            let mut current = topmost_node(dom, layout, point);
+            while let Some(id) = current {
+                hovered.push(id);
+                current = tree.parent_id_advanced(id, true);
+            }
+            hovered
+        }
+
         fn try_create_event(
             name: &'static str,
             data: EventData,
@@ -258,12 +439,7 @@ impl InnerInputState {
             // only trigger event if the event was not triggered already by a child
             let id = node.id();
             if will_bubble.insert(id) {
-                let mut parent = Some(node);
-                while let Some(current_parent) = parent {
-                    let parent_id = current_parent.id();
-                    will_bubble.insert(parent_id);
-                    parent = current_parent.parent_id().and_then(|id| dom.get(id));
-                }
+                will_bubble.extend(dom.propagation_path(id, true).nodes());
                 resolved_events.push(Event {
                     name,
                     id,
@@ -319,6 +495,31 @@ impl InnerInputState {
                 .map_or(false, |data| !data.delta().is_zero());
             let wheel_data = &self.wheel;
 
+            // update the :hover and :active pseudo-classes
+            if old_pos != Some(new_pos) {
+                let hovered = hovered_nodes(dom, layout, new_pos);
+                for &id in &self.hovered {
+                    if !hovered.contains(&id) {
+                        set_pseudo_class(dom, id, PseudoClass::Hover, false);
+                    }
+                }
+                for &id in &hovered {
+                    set_pseudo_class(dom, id, PseudoClass::Hover, true);
+                }
+                self.hovered = hovered;
+            }
+            if was_pressed || mouse_data.held_buttons().is_empty() {
+                for id in std::mem::take(&mut self.active) {
+                    set_pseudo_class(dom, id, PseudoClass::Active, false);
+                }
+            }
+            if was_pressed && !mouse_data.held_buttons().is_empty() {
+                for &id in &self.hovered {
+                    set_pseudo_class(dom, id, PseudoClass::Active, true);
+                }
+                self.active = self.hovered.clone();
+            }
+
             {
                 // mousemove
                 if old_pos != Some(new_pos) {
@@ -326,9 +527,10 @@ impl InnerInputState {
                     for node in dom.get_listening_sorted("mousemove") {
                         let node_layout = get_abs_layout(node, layout);
                         let previously_contained = old_pos
-                            .filter(|pos| layout_contains_point(&node_layout, *pos))
+                            .filter(|pos| node_contains_point(node, &node_layout, layout, *pos))
                             .is_some();
-                        let currently_contains = layout_contains_point(&node_layout, new_pos);
+                        let currently_contains =
+                            node_contains_point(node, &node_layout, layout, new_pos);
 
                         if currently_contains && previously_contained {
                             try_create_event(
@@ -350,9 +552,10 @@ impl InnerInputState {
                 for node in dom.get_listening_sorted("mouseenter") {
                     let node_layout = get_abs_layout(node, layout);
                     let previously_contained = old_pos
-                        .filter(|pos| layout_contains_point(&node_layout, *pos))
+                        .filter(|pos| node_contains_point(node, &node_layout, layout, *pos))
                         .is_some();
-                    let currently_contains = layout_contains_point(&node_layout, new_pos);
+                    let currently_contains =
+                        node_contains_point(node, &node_layout, layout, new_pos);
 
                     if currently_contains && !previously_contained {
                         try_create_event(
@@ -373,9 +576,10 @@ impl InnerInputState {
                 for node in dom.get_listening_sorted("mouseover") {
                     let node_layout = get_abs_layout(node, layout);
                     let previously_contained = old_pos
-                        .filter(|pos| layout_contains_point(&node_layout, *pos))
+                        .filter(|pos| node_contains_point(node, &node_layout, layout, *pos))
                         .is_some();
-                    let currently_contains = layout_contains_point(&node_layout, new_pos);
+                    let currently_contains =
+                        node_contains_point(node, &node_layout, layout, new_pos);
 
                     if currently_contains && !previously_contained {
                         try_create_event(
@@ -395,7 +599,8 @@ impl InnerInputState {
                 let mut will_bubble = FxHashSet::default();
                 for node in dom.get_listening_sorted("mousedown") {
                     let node_layout = get_abs_layout(node, layout);
-                    let currently_contains = layout_contains_point(&node_layout, new_pos);
+                    let currently_contains =
+                        node_contains_point(node, &node_layout, layout, new_pos);
 
                     if currently_contains {
                         try_create_event(
@@ -416,7 +621,8 @@ impl InnerInputState {
                     let mut will_bubble = FxHashSet::default();
                     for node in dom.get_listening_sorted("mouseup") {
                         let node_layout = get_abs_layout(node, layout);
-                        let currently_contains = layout_contains_point(&node_layout, new_pos);
+                        let currently_contains =
+                            node_contains_point(node, &node_layout, layout, new_pos);
 
                         if currently_contains {
                             try_create_event(
@@ -438,7 +644,8 @@ impl InnerInputState {
                     let mut will_bubble = FxHashSet::default();
                     for node in dom.get_listening_sorted("click") {
                         let node_layout = get_abs_layout(node, layout);
-                        let currently_contains = layout_contains_point(&node_layout, new_pos);
+                        let currently_contains =
+                            node_contains_point(node, &node_layout, layout, new_pos);
 
                         if currently_contains {
                             try_create_event(
@@ -461,7 +668,8 @@ impl InnerInputState {
                     let mut will_bubble = FxHashSet::default();
                     for node in dom.get_listening_sorted("contextmenu") {
                         let node_layout = get_abs_layout(node, layout);
-                        let currently_contains = layout_contains_point(&node_layout, new_pos);
+                        let currently_contains =
+                            node_contains_point(node, &node_layout, layout, new_pos);
 
                         if currently_contains {
                             try_create_event(
@@ -477,6 +685,15 @@ impl InnerInputState {
                 }
             }
 
+            // scroll the element under the mouse
+            if let (Some(w), Some(&id)) = (wheel_data, self.hovered.first()) {
+                let rows = w.delta().strip_units().y;
+                if rows != 0.0 {
+                    let delta = ScrollDelta::Rows(rows.signum() as i32);
+                    self.scrolled |= scroll(dom, layout, id, delta);
+                }
+            }
+
             {
                 // wheel
                 if let Some(w) = wheel_data {
@@ -485,7 +702,8 @@ impl InnerInputState {
                         for node in dom.get_listening_sorted("wheel") {
                             let node_layout = get_abs_layout(node, layout);
 
-                            let currently_contains = layout_contains_point(&node_layout, new_pos);
+                            let currently_contains =
+                                node_contains_point(node, &node_layout, layout, new_pos);
 
                             if currently_contains {
                                 try_create_event(
@@ -508,9 +726,10 @@ impl InnerInputState {
                 for node in dom.get_listening_sorted("mouseleave") {
                     let node_layout = get_abs_layout(node, layout);
                     let previously_contained = old_pos
-                        .filter(|pos| layout_contains_point(&node_layout, *pos))
+                        .filter(|pos| node_contains_point(node, &node_layout, layout, *pos))
                         .is_some();
-                    let currently_contains = layout_contains_point(&node_layout, new_pos);
+                    let currently_contains =
+                        node_contains_point(node, &node_layout, layout, new_pos);
 
                     if !currently_contains && previously_contained {
                         try_create_event(
@@ -531,9 +750,10 @@ impl InnerInputState {
                 for node in dom.get_listening_sorted("mouseout") {
                     let node_layout = get_abs_layout(node, layout);
                     let previously_contained = old_pos
-                        .filter(|pos| layout_contains_point(&node_layout, *pos))
+                        .filter(|pos| node_contains_point(node, &node_layout, layout, *pos))
                         .is_some();
-                    let currently_contains = layout_contains_point(&node_layout, new_pos);
+                    let currently_contains =
+                        node_contains_point(node, &node_layout, layout, new_pos);
 
                     if !currently_contains && previously_contained {
                         try_create_event(
@@ -548,6 +768,62 @@ impl InnerInputState {
                 }
             }
 
+            // select the text under the mouse while the primary button is dragged outside of widgets
+            if was_pressed {
+                self.selection_changed |= self.selection.lock().unwrap().take().is_some();
+                let in_widget = self.hovered.iter().any(|id| {
+                    dom.get(*id)
+                        .is_some_and(|node| node.get::<RinkWidgetTraitObject>().is_some())
+                });
+                let primary = mouse_data.trigger_button() == Some(DioxusMouseButton::Primary);
+                self.selection_start = (primary && !in_widget).then_some(new_pos);
+            } else if let Some(start) = self.selection_start {
+                let mut selection = self.selection.lock().unwrap();
+                let mut selection_event = None;
+                if mouse_data
+                    .held_buttons()
+                    .contains(DioxusMouseButton::Primary)
+                {
+                    if old_pos != Some(new_pos) {
+                        let cell = |point: ScreenPoint| Point {
+                            x: point.x as u16,
+                            y: point.y as u16,
+                        };
+                        let started = selection
+                            .replace(Selection::new(cell(start), cell(new_pos)))
+                            .is_none();
+                        self.selection_changed = true;
+                        if started {
+                            selection_event = Some("selectstart");
+                        }
+                    }
+                } else {
+                    self.selection_start = None;
+                    if selection.is_some() {
+                        selection_event = Some("select");
+                    }
+                }
+                drop(selection);
+
+                // the events target the element the selection started in
+                if let Some(name) = selection_event {
+                    let mut will_bubble = FxHashSet::default();
+                    for node in dom.get_listening_sorted(name) {
+                        let node_layout = get_abs_layout(node, layout);
+                        if node_contains_point(node, &node_layout, layout, start) {
+                            try_create_event(
+                                name,
+                                EventData::Selection(SelectionData {}),
+                                &mut will_bubble,
+                                resolved_events,
+                                node,
+                                dom,
+                            );
+                        }
+                    }
+                }
+            }
+
             // update focus
             if was_released {
                 let mut focus_id = None;
@@ -557,7 +833,7 @@ impl InnerInputState {
                         .unwrap();
                     let currently_contains = layout_contains_point(node_layout, new_pos);
 
-                    if currently_contains && node.get::<Focus>().unwrap().level.focusable() {
+                    if currently_contains && node.get::<FocusState>().unwrap().level.focusable() {
                         focus_id = Some(node.id());
                     }
                 });
@@ -573,6 +849,39 @@ impl InnerInputState {
     // }
 }
 
+/// Set a pseudo-class of an element. Nodes that were removed or are not elements are ignored.
+pub(crate) fn set_pseudo_class(dom: &mut RealDom, id: NodeId, class: PseudoClass, value: bool) {
+    if let Some(mut node) = dom.get_mut(id) {
+        let is_element = matches!(&*node.node_type(), NodeType::Element(_));
+        if is_element {
+            node.set_pseudo_class(class, value);
+        }
+    }
+}
+
+/// Keeps the `:disabled` pseudo-class of elements in sync with their `disabled` attribute
+pub(crate) struct DisabledWatcher;
+
+impl AttributeWatcher<()> for DisabledWatcher {
+    fn on_attributes_changed(&self, mut node: NodeMut, attributes: &AttributeMask) {
+        if !attributes.contains("disabled") {
+            return;
+        }
+        let disabled = match &*node.node_type() {
+            NodeType::Element(element) => {
+                match element.attributes.get(&"disabled".to_string().into()) {
+                    Some(OwnedAttributeValue::Bool(disabled)) => *disabled,
+                    Some(OwnedAttributeValue::Text(text)) => text != "false",
+                    Some(_) => true,
+                    None => false,
+                }
+            }
+            _ => return,
+        };
+        node.set_pseudo_class(PseudoClass::Disabled, disabled);
+    }
+}
+
 pub struct RinkInputHandler {
     state: Rc<RefCell<InnerInputState>>,
     queued_events: Rc<RefCell<Vec<EventCore>>>,
@@ -581,7 +890,7 @@ pub struct RinkInputHandler {
 impl RinkInputHandler {
     /// global context that handles events
     /// limitations: GUI key modifier is never detected, key up events are not detected, and only two mouse buttons may be pressed at once
-    pub fn create(rdom: &mut RealDom) -> (Self, impl FnMut(crossterm::event::Event)) {
+    pub fn create() -> (Self, impl FnMut(crossterm::event::Event)) {
         let queued_events = Rc::new(RefCell::new(Vec::new()));
         let queued_events2 = Rc::downgrade(&queued_events);
 
@@ -593,7 +902,7 @@ impl RinkInputHandler {
             }
         };
 
-        let state = Rc::new(RefCell::new(InnerInputState::create(rdom)));
+        let state = Rc::new(RefCell::new(InnerInputState::create()));
 
         (
             Self {
@@ -632,6 +941,8 @@ impl RinkInputHandler {
                     "wheel",
                     "click",
                     "contextmenu",
+                    "selectstart",
+                    "select",
                 ]
                 .contains(&e.0)
             })
@@ -737,6 +1048,8 @@ fn get_event(evt: TermEvent) -> Option<(&'static str, EventData)> {
                 MouseEventKind::ScrollUp => ("wheel", get_wheel_data(true)),
             }
         }
+        // text pasted with bracketed paste mode is sent as one event instead of a key press per character
+        TermEvent::Paste(text) => ("paste", EventData::Clipboard(ClipboardData { text })),
         _ => return None,
     };
 
@@ -748,7 +1061,11 @@ fn translate_key_event(event: crossterm::event::KeyEvent) -> Option<EventData> {
     // crossterm does not provide code. we make a guess as to which key might have been pressed
     // this is probably garbage if the user has a custom keyboard layout
     let code = guess_code_from_crossterm_key_code(event.code)?;
-    let modifiers = modifiers_from_crossterm_modifiers(event.modifiers);
+    let mut modifiers = modifiers_from_crossterm_modifiers(event.modifiers);
+    // Some terminals send BackTab for Shift+Tab without the shift modifier
+    if event.code == TermKeyCode::BackTab {
+        modifiers.insert(Modifiers::SHIFT);
+    }
 
     Some(EventData::Keyboard(KeyboardData::new(
         key,
@@ -0,0 +1,419 @@
+//! The `img` element draws the image in its `src` attribute. The image is drawn with the kitty graphics protocol or
+//! sixel graphics if the terminal supports them, and with half block characters otherwise.
+//!
+//! - `src`: the path of a png, jpeg, gif or bmp file, or a `data:` url with a base64 encoded image
+//!
+//! The image is stretched to fill the element, so the element needs a width and a height.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use crossterm::{cursor::MoveTo, queue};
+use dioxus_native_core::{
+    node_ref::{AttributeMaskBuilder, NodeMaskBuilder},
+    prelude::*,
+};
+use dioxus_native_core_macro::partial_derive_state;
+use image::{
+    imageops::{self, FilterType},
+    Rgba, RgbaImage,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use shipyard::Component;
+use tui::{buffer::Buffer, layout::Rect, style::Color};
+
+use crate::{
+    style::RinkColor,
+    terminal::{cell_size, GraphicsProtocol},
+    widget::{RinkBuffer, RinkCell, RinkWidget},
+};
+
+/// The tag of the image element
+pub(crate) const IMG_TAG: &str = "img";
+
+// Images are scaled down to this size when they are loaded. Terminals are rarely larger, and smaller images are faster
+// to scale and send to the terminal
+const MAX_IMAGE_SIZE: u32 = 2048;
+
+static NEXT_IMAGE_ID: AtomicU32 = AtomicU32::new(1);
+
+/// An image decoded from the `src` of an element
+#[derive(Debug)]
+pub(crate) struct DecodedImage {
+    /// A unique id of the image, used to refer to the image after it was sent to the terminal
+    pub id: u32,
+    pub pixels: RgbaImage,
+}
+
+/// The decoded image of an `img` element, cached until the `src` of the element changes
+#[derive(Clone, Default, Debug, Component)]
+pub(crate) struct Image {
+    src: Option<String>,
+    /// The image, or [`None`] if the element has no `src` or the image could not be loaded
+    pub image: Option<Arc<DecodedImage>>,
+}
+
+#[partial_derive_state]
+impl State for Image {
+    type ParentDependencies = ();
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new()
+        .with_tag()
+        .with_attrs(AttributeMaskBuilder::Some(&["src"]));
+
+    fn update<'a>(
+        &mut self,
+        node_view: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let src = if node_view.tag() == Some(IMG_TAG) {
+            node_view
+                .attributes()
+                .and_then(|mut attrs| attrs.find(|a| a.attribute.name == "src"))
+                .and_then(|a| a.value.as_text())
+        } else {
+            None
+        };
+        if src == self.src.as_deref() {
+            return false;
+        }
+
+        self.image = src.and_then(load_image).map(Arc::new);
+        self.src = src.map(str::to_string);
+        true
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+fn load_image(src: &str) -> Option<DecodedImage> {
+    let image = match src.strip_prefix("data:") {
+        Some(data) => {
+            let (_, data) = data.split_once(";base64,")?;
+            image::load_from_memory(&BASE64.decode(data.trim()).ok()?)
+        }
+        None => image::open(src.strip_prefix("file://").unwrap_or(src)),
+    }
+    .ok()?;
+
+    let image = if image.width() > MAX_IMAGE_SIZE || image.height() > MAX_IMAGE_SIZE {
+        image.thumbnail(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE)
+    } else {
+        image
+    };
+
+    Some(DecodedImage {
+        id: NEXT_IMAGE_ID.fetch_add(1, Ordering::Relaxed),
+        pixels: image.into_rgba8(),
+    })
+}
+
+/// Draws an image with two pixels in every cell: the top pixel is the color of the `▀` character and the bottom pixel
+/// is the background color
+pub(crate) struct HalfBlocks<'a>(pub &'a RgbaImage);
+
+impl RinkWidget for HalfBlocks<'_> {
+    fn render(self, area: Rect, mut buf: RinkBuffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let pixels = imageops::resize(
+            self.0,
+            area.width as u32,
+            area.height as u32 * 2,
+            FilterType::Triangle,
+        );
+        let color = |x: u16, y: u16| {
+            let Rgba([r, g, b, alpha]) = *pixels.get_pixel(x as u32, y as u32);
+            RinkColor {
+                color: Color::Rgb(r, g, b),
+                alpha,
+            }
+        };
+
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let cell = RinkCell {
+                    symbol: "▀".to_string(),
+                    fg: color(x, y * 2),
+                    bg: color(x, y * 2 + 1),
+                    ..Default::default()
+                };
+                buf.set(area.left() + x, area.top() + y, cell);
+            }
+        }
+    }
+}
+
+/// Clears the cells an image drawn with a graphics protocol covers
+pub(crate) struct GraphicsArea;
+
+impl RinkWidget for GraphicsArea {
+    fn render(self, area: Rect, mut buf: RinkBuffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = RinkCell {
+                    symbol: " ".to_string(),
+                    // The modifier makes the cells different from empty cells, so the cells are drawn again when the
+                    // image moves away. Drawing the cells clears the pixels sixel graphics leave behind
+                    modifier: tui::style::Modifier::HIDDEN,
+                    ..Default::default()
+                };
+                buf.set(x, y, cell);
+            }
+        }
+    }
+}
+
+/// The visible part of an image drawn with a graphics protocol
+#[derive(Clone, Debug)]
+pub(crate) struct ImagePlacement {
+    pub image: Arc<DecodedImage>,
+    /// The cells the visible part of the image covers
+    pub area: Rect,
+    /// The visible part of the image in pixels of the image, as `[x, y, width, height]`
+    pub crop: [u32; 4],
+}
+
+impl PartialEq for ImagePlacement {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.image, &other.image) && self.area == other.area && self.crop == other.crop
+    }
+}
+
+/// Draws images with a graphics protocol after the text of a frame is drawn
+pub(crate) struct Graphics {
+    protocol: GraphicsProtocol,
+    /// The images drawn in the last frame
+    placements: Vec<ImagePlacement>,
+    /// The images that were sent to the terminal with the kitty graphics protocol
+    transmitted: FxHashSet<u32>,
+    /// The sixel graphics of the images drawn in the last frame
+    sixels: FxHashMap<(u32, Rect, [u32; 4]), String>,
+}
+
+impl Graphics {
+    pub fn new(protocol: GraphicsProtocol) -> Self {
+        Self {
+            protocol,
+            placements: Vec::new(),
+            transmitted: FxHashSet::default(),
+            sixels: FxHashMap::default(),
+        }
+    }
+
+    /// Draw the images of a frame. Images are only drawn again if they moved, or if the text under them changed since
+    /// the `previous` frame.
+    pub fn draw(
+        &mut self,
+        out: &mut impl Write,
+        placements: Vec<ImagePlacement>,
+        previous: Option<&Buffer>,
+        current: &Buffer,
+    ) -> io::Result<()> {
+        let covered_changed = |placement: &ImagePlacement| match previous {
+            Some(previous) if previous.area == current.area => {
+                let area = placement.area;
+                (area.top()..area.bottom()).any(|y| {
+                    (area.left()..area.right()).any(|x| previous.get(x, y) != current.get(x, y))
+                })
+            }
+            _ => true,
+        };
+        if placements == self.placements && !placements.iter().any(covered_changed) {
+            return Ok(());
+        }
+
+        match self.protocol {
+            GraphicsProtocol::Kitty => self.draw_kitty(out, &placements)?,
+            GraphicsProtocol::Sixel => self.draw_sixel(out, &placements)?,
+            GraphicsProtocol::HalfBlocks => {}
+        }
+        out.flush()?;
+        self.placements = placements;
+        Ok(())
+    }
+
+    fn draw_kitty(
+        &mut self,
+        out: &mut impl Write,
+        placements: &[ImagePlacement],
+    ) -> io::Result<()> {
+        // remove the images of the last frame, but keep them in the memory of the terminal
+        write!(out, "\x1b_Ga=d,d=a,q=2\x1b\\")?;
+
+        for placement in placements {
+            let image = &placement.image;
+            if self.transmitted.insert(image.id) {
+                let data = BASE64.encode(image.pixels.as_raw());
+                let chunks: Vec<_> = data.as_bytes().chunks(4096).collect();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = (i + 1 < chunks.len()) as u8;
+                    if i == 0 {
+                        write!(
+                            out,
+                            "\x1b_Ga=t,f=32,s={},v={},i={},q=2,m={};",
+                            image.pixels.width(),
+                            image.pixels.height(),
+                            image.id,
+                            more
+                        )?;
+                    } else {
+                        write!(out, "\x1b_Gm={};", more)?;
+                    }
+                    out.write_all(chunk)?;
+                    write!(out, "\x1b\\")?;
+                }
+            }
+
+            let [x, y, width, height] = placement.crop;
+            queue!(out, MoveTo(placement.area.x, placement.area.y))?;
+            write!(
+                out,
+                "\x1b_Ga=p,i={},x={},y={},w={},h={},c={},r={},C=1,q=2\x1b\\",
+                image.id, x, y, width, height, placement.area.width, placement.area.height
+            )?;
+        }
+
+        // free the memory of images that are no longer drawn
+        let drawn: FxHashSet<_> = placements.iter().map(|p| p.image.id).collect();
+        for id in self.transmitted.difference(&drawn) {
+            write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)?;
+        }
+        self.transmitted = drawn;
+        Ok(())
+    }
+
+    fn draw_sixel(
+        &mut self,
+        out: &mut impl Write,
+        placements: &[ImagePlacement],
+    ) -> io::Result<()> {
+        let (cell_width, cell_height) = cell_size();
+        let mut sixels = FxHashMap::default();
+
+        for placement in placements {
+            let key = (placement.image.id, placement.area, placement.crop);
+            let sixel = self.sixels.remove(&key).unwrap_or_else(|| {
+                let [x, y, width, height] = placement.crop;
+                let cropped = imageops::crop_imm(&placement.image.pixels, x, y, width, height);
+                let scaled = imageops::resize(
+                    &*cropped,
+                    placement.area.width as u32 * cell_width as u32,
+                    placement.area.height as u32 * cell_height as u32,
+                    FilterType::Triangle,
+                );
+                encode_sixel(&scaled)
+            });
+
+            queue!(out, MoveTo(placement.area.x, placement.area.y))?;
+            out.write_all(sixel.as_bytes())?;
+            sixels.insert(key, sixel);
+        }
+
+        self.sixels = sixels;
+        Ok(())
+    }
+}
+
+/// Encode an image as sixel graphics with the 216 colors of a 6x6x6 color cube. Transparent pixels are not drawn.
+fn encode_sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let mut sixel = String::new();
+
+    // `1` in the second parameter leaves pixels that are not drawn transparent
+    let _ = write!(sixel, "\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for color in 0..216 {
+        let [r, g, b] = [color / 36, color / 6 % 6, color % 6].map(|c| c * 20);
+        let _ = write!(sixel, "#{};2;{};{};{}", color, r, g, b);
+    }
+
+    for band_top in (0..height).step_by(6) {
+        // every color in the band, with the pixels of that color in every column as a bit mask
+        let mut colors: Vec<(u8, Vec<u8>)> = Vec::new();
+        for x in 0..width {
+            for bit in 0..6 {
+                let y = band_top + bit;
+                if y >= height {
+                    break;
+                }
+                let Rgba([r, g, b, alpha]) = *image.get_pixel(x, y);
+                if alpha < 128 {
+                    continue;
+                }
+                let [r, g, b] = [r, g, b].map(|c| (c as u16 * 5 + 127) / 255);
+                let color = (r * 36 + g * 6 + b) as u8;
+                let columns = match colors.iter_mut().find(|(c, _)| *c == color) {
+                    Some((_, columns)) => columns,
+                    None => {
+                        colors.push((color, vec![0; width as usize]));
+                        &mut colors.last_mut().unwrap().1
+                    }
+                };
+                columns[x as usize] |= 1 << bit;
+            }
+        }
+
+        for (i, (color, columns)) in colors.iter().enumerate() {
+            if i > 0 {
+                // go back to the start of the band to draw the next color
+                sixel.push('$');
+            }
+            let _ = write!(sixel, "#{}", color);
+            let mut columns = columns.iter().peekable();
+            while let Some(&bits) = columns.next() {
+                let mut repeat = 1;
+                while columns.next_if_eq(&&bits).is_some() {
+                    repeat += 1;
+                }
+                let symbol = (63 + bits) as char;
+                if repeat > 3 {
+                    let _ = write!(sixel, "!{}{}", repeat, symbol);
+                } else {
+                    (0..repeat).for_each(|_| sixel.push(symbol));
+                }
+            }
+        }
+        sixel.push('-');
+    }
+
+    sixel.push_str("\x1b\\");
+    sixel
+}
+
+#[test]
+fn encode_sixel_bands() {
+    // a red column next to a transparent column, high enough to need two bands
+    let mut image = RgbaImage::new(2, 7);
+    for y in 0..7 {
+        image.put_pixel(0, y, Rgba([255, 0, 0, 255]));
+    }
+
+    let sixel = encode_sixel(&image);
+    assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;2;7#0;2;0;0;0"));
+    let (_, bands) = sixel.split_once("#215;2;100;100;100").unwrap();
+    // red is the color 180 of the color cube
+    assert_eq!(bands, "#180~?-#180@?-\x1b\\");
+}
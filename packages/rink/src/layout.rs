@@ -8,9 +8,12 @@ use dioxus_native_core::node::OwnedAttributeView;
 use dioxus_native_core::node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView};
 use dioxus_native_core::prelude::*;
 use dioxus_native_core_macro::partial_derive_state;
+use taffy::geometry::Point;
+use taffy::node::MeasureFunc;
 use taffy::prelude::*;
 
-use crate::{screen_to_layout_space, unit_to_layout_space};
+use crate::text::{TextAlign, TextLayout, TextOverflow};
+use crate::unit_to_layout_space;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum PossiblyUninitalized<T> {
@@ -32,20 +35,182 @@ impl<T> Default for PossiblyUninitalized<T> {
     }
 }
 
+/// Where an element is placed in the grid of a `fieldset`
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum FormItem {
+    /// A `label`, placed in the first column. The column is as wide as the widest label
+    Label,
+    /// Any other element, placed in the second column next to the label before it
+    #[default]
+    Field,
+    /// An element with `role="alert"`, placed in the second column in a new row below the field it belongs to
+    Error,
+    /// A `legend`, placed in a row that spans both columns
+    Legend,
+}
+
+/// How an element handles children that don't fit in it, set with the `overflow` and `overflow-y` attributes. Only
+/// vertical scrolling is supported
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum Overflow {
+    /// The children are drawn outside of the element
+    #[default]
+    Visible,
+    /// The children are clipped to the element
+    Hidden,
+    /// The children are clipped to the element, which can be scrolled and always shows a scrollbar
+    Scroll,
+    /// The children are clipped to the element, which can be scrolled and shows a scrollbar if they don't fit
+    Auto,
+}
+
+impl Overflow {
+    fn parse(value: &str) -> Option<Self> {
+        // the two value syntax sets the horizontal and then the vertical overflow
+        match value.split_whitespace().last()? {
+            "visible" => Some(Overflow::Visible),
+            "hidden" | "clip" => Some(Overflow::Hidden),
+            "scroll" => Some(Overflow::Scroll),
+            "auto" => Some(Overflow::Auto),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the children are clipped to the element
+    pub fn clips(&self) -> bool {
+        *self != Overflow::Visible
+    }
+
+    /// Returns true if the element can be scrolled
+    pub fn scrolls(&self) -> bool {
+        matches!(self, Overflow::Scroll | Overflow::Auto)
+    }
+}
+
+/// How an element is placed, set with the `position` attribute
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum Positioning {
+    /// The element is placed in the flow of its parent
+    #[default]
+    Static,
+    /// The element is placed in the flow of its parent, and offset by its insets
+    Relative,
+    /// The element is taken out of the flow, and placed by its insets in its parent
+    Absolute,
+    /// The element is taken out of the flow, and placed by its insets in the screen. It doesn't scroll with its
+    /// ancestors and isn't clipped by them
+    Fixed,
+}
+
+impl Positioning {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "static" => Some(Positioning::Static),
+            // sticky elements are not stuck to the edges of their scroll container
+            "relative" | "sticky" => Some(Positioning::Relative),
+            "absolute" => Some(Positioning::Absolute),
+            "fixed" => Some(Positioning::Fixed),
+            _ => None,
+        }
+    }
+}
+
+/// Assigns the rows and columns of the children of a `fieldset` in order
+#[derive(Default)]
+struct FormPlacer {
+    row: i16,
+    has_label: bool,
+    has_field: bool,
+}
+
+impl FormPlacer {
+    fn place(&mut self, item: FormItem) -> (Line<GridPlacement>, Line<GridPlacement>) {
+        // start a new row if the cell of the item in the current row is taken
+        let new_row = match item {
+            FormItem::Label => self.has_label || self.has_field,
+            FormItem::Field => self.has_field,
+            FormItem::Error | FormItem::Legend => self.has_label || self.has_field,
+        };
+        if new_row || self.row == 0 {
+            self.row += 1;
+            self.has_label = false;
+            self.has_field = false;
+        }
+
+        let column = match item {
+            FormItem::Label => {
+                self.has_label = true;
+                Line {
+                    start: line(1),
+                    end: span(1),
+                }
+            }
+            FormItem::Field | FormItem::Error => {
+                self.has_field = true;
+                Line {
+                    start: line(2),
+                    end: span(1),
+                }
+            }
+            FormItem::Legend => {
+                self.has_label = true;
+                self.has_field = true;
+                Line {
+                    start: line(1),
+                    end: span(2),
+                }
+            }
+        };
+        let row = Line {
+            start: line(self.row),
+            end: span(1),
+        };
+
+        (row, column)
+    }
+}
+
 #[derive(Clone, PartialEq, Default, Debug, Component)]
 pub(crate) struct TaffyLayout {
     pub style: Style,
     pub node: PossiblyUninitalized<Node>,
+    pub form_item: FormItem,
+    pub overflow: Overflow,
+    pub position: Positioning,
+    /// the order the element is drawn in, compared to the other positioned elements of its stacking context
+    pub z_index: Option<i32>,
+    /// the borders of the children overlap each other and the borders of the element, set with
+    /// `border-collapse: collapse`
+    pub border_collapse: bool,
+}
+
+impl TaffyLayout {
+    /// Returns true if the element is drawn on its own layer, after the other elements of its stacking context. The
+    /// element starts a stacking context for its children
+    pub fn is_layer(&self) -> bool {
+        match self.position {
+            Positioning::Static => false,
+            Positioning::Relative => self.z_index.is_some(),
+            Positioning::Absolute | Positioning::Fixed => true,
+        }
+    }
+
+    /// Returns true if an edge of the element is shared with the elements around it when its parent collapses
+    /// borders: the edge has a border, or the element collapses the borders of its own children into it
+    fn has_edge(&self, width: LengthPercentage) -> bool {
+        self.border_collapse || has_border(width)
+    }
 }
 
 #[partial_derive_state]
 impl State for TaffyLayout {
     type ChildDependencies = (Self,);
     type ParentDependencies = ();
-    type NodeDependencies = ();
+    type NodeDependencies = (TextLayout,);
 
     const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new()
         .with_attrs(AttributeMaskBuilder::Some(SORTED_LAYOUT_ATTRS))
+        .with_element()
         .with_text();
 
     // The layout state should be effected by the shadow dom
@@ -54,7 +219,7 @@ impl State for TaffyLayout {
     fn update<'a>(
         &mut self,
         node_view: NodeView,
-        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        (text_layout,): <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
         _: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
         children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
         ctx: &SendAnyMap,
@@ -64,34 +229,83 @@ impl State for TaffyLayout {
         let mut taffy = taffy.lock().expect("poisoned taffy");
         let mut style = Style::default();
         if let Some(text) = node_view.text() {
-            let char_len = text.chars().count();
-
-            style = Style {
-                size: Size {
-                    // characters are 1 point tall
-                    height: Dimension::Points(screen_to_layout_space(1)),
+            // aligned text fills the width of its element, so the lines can be aligned in it
+            if text_layout.align != TextAlign::Left {
+                style.size.width = Dimension::Percent(1.0);
+            }
+            // text that ends with an ellipsis can shrink below the width of its content
+            if text_layout.overflow == TextOverflow::Ellipsis {
+                style.min_size.width = Dimension::Points(0.0);
+            }
 
-                    // text is as long as it is declared
-                    width: Dimension::Points(screen_to_layout_space(char_len as u16)),
-                },
-                ..Default::default()
+            // the size of the text depends on the width it wraps in
+            let measure = {
+                let text = text.to_string();
+                let text_layout = text_layout.clone();
+                MeasureFunc::Boxed(Box::new(move |known, available| {
+                    text_layout.measure(&text, known, available)
+                }))
             };
             if let PossiblyUninitalized::Initialized(n) = self.node {
                 if self.style != style {
                     taffy.set_style(n, style.clone()).unwrap();
                 }
+                taffy.set_measure(n, Some(measure)).unwrap();
             } else {
-                self.node =
-                    PossiblyUninitalized::Initialized(taffy.new_leaf(style.clone()).unwrap());
+                self.node = PossiblyUninitalized::Initialized(
+                    taffy.new_leaf_with_measure(style.clone(), measure).unwrap(),
+                );
                 changed = true;
             }
         } else {
+            let tag = node_view
+                .namespace()
+                .is_none()
+                .then(|| node_view.tag())
+                .flatten();
+            let is_fieldset = tag == Some("fieldset");
+            let mut form_item = match tag {
+                Some("label") => FormItem::Label,
+                Some("legend") => FormItem::Legend,
+                _ => FormItem::Field,
+            };
+
+            // fieldsets align their labels and fields in two columns, the attributes can still override the layout
+            if is_fieldset {
+                style.display = Display::Grid;
+                style.grid_template_columns = vec![auto(), fr(1.0)];
+                style.gap.width = LengthPercentage::Points(1.0);
+            }
+
+            let mut overflow = None;
+            let mut overflow_y = None;
+            let mut position = Positioning::default();
+            let mut z_index = None;
+            let mut border_collapse = false;
+
             // gather up all the styles from the attribute list
             if let Some(attributes) = node_view.attributes() {
                 for OwnedAttributeView {
                     attribute, value, ..
                 } in attributes
                 {
+                    if attribute.name == "role" && value.as_text() == Some("alert") {
+                        form_item = FormItem::Error;
+                    }
+                    match &*attribute.name {
+                        "overflow" => overflow = value.as_text().and_then(Overflow::parse),
+                        "overflow-y" => overflow_y = value.as_text().and_then(Overflow::parse),
+                        "position" => {
+                            position = value
+                                .as_text()
+                                .and_then(Positioning::parse)
+                                .unwrap_or_default()
+                        }
+                        // `auto` and invalid values don't set a z-index
+                        "z-index" => z_index = value.as_text().and_then(|v| v.trim().parse().ok()),
+                        "border-collapse" => border_collapse = value.as_text() == Some("collapse"),
+                        _ => {}
+                    }
                     if let Some(text) = value.as_text() {
                         apply_layout_attributes_cfg(
                             &attribute.name,
@@ -109,10 +323,100 @@ impl State for TaffyLayout {
                 }
             }
 
+            if self.form_item != form_item {
+                self.form_item = form_item;
+                changed = true;
+            }
+
+            let overflow = overflow_y.or(overflow).unwrap_or_default();
+            if self.overflow != overflow {
+                self.overflow = overflow;
+                changed = true;
+            }
+
+            // fixed elements are laid out like absolute elements, and placed in the screen in a second pass
+            if position == Positioning::Fixed {
+                style.position = Position::Absolute;
+            }
+            if self.position != position || self.z_index != z_index {
+                self.position = position;
+                self.z_index = z_index;
+                changed = true;
+            }
+            if self.border_collapse != border_collapse {
+                self.border_collapse = border_collapse;
+                changed = true;
+            }
+
             // Set all direct nodes as our children
             let mut child_layout = vec![];
+            let mut form_placer = FormPlacer::default();
+            let in_flow = children
+                .iter()
+                .filter(|(l,)| !matches!(l.position, Positioning::Absolute | Positioning::Fixed))
+                .count();
+            let mut flow_index = 0;
+            // if the sibling before the child has a border on the edge it shares with the child
+            let mut previous_end = None;
             for (l,) in children {
-                child_layout.push(l.node.unwrap());
+                let child = l.node.unwrap();
+                // The borders of the children of an element that collapses borders are pulled over the borders of
+                // their siblings and the element, so the edges they share are drawn once
+                let mut margin = Rect {
+                    left: scale_length_percentage_auto(l.style.margin.left),
+                    right: scale_length_percentage_auto(l.style.margin.right),
+                    top: scale_length_percentage_auto(l.style.margin.top),
+                    bottom: scale_length_percentage_auto(l.style.margin.bottom),
+                };
+                if !matches!(l.position, Positioning::Absolute | Positioning::Fixed) {
+                    if border_collapse {
+                        let [parent_start, parent_end] =
+                            main_edges(style.flex_direction, edges(style.border));
+                        let before = previous_end.unwrap_or_else(|| has_border(parent_start));
+                        let after = flow_index + 1 == in_flow && has_border(parent_end);
+                        collapse_margin(&style, &l, before, after, &mut margin);
+                        let [_, end] = main_edges(style.flex_direction, edges(l.style.border));
+                        previous_end = Some(l.has_edge(end));
+                    }
+                    flow_index += 1;
+                }
+                let child_style = taffy.style(child).unwrap();
+                if child_style.margin != margin {
+                    let child_style = Style {
+                        margin,
+                        ..child_style.clone()
+                    };
+                    taffy.set_style(child, child_style).unwrap();
+                }
+                if is_fieldset {
+                    // The placement of a child in the fieldset depends on its siblings, so the fieldset places its children
+                    let (grid_row, grid_column) = form_placer.place(l.form_item);
+                    let child_style = taffy.style(child).unwrap();
+                    if child_style.grid_row != grid_row || child_style.grid_column != grid_column {
+                        let child_style = Style {
+                            grid_row,
+                            grid_column,
+                            ..child_style.clone()
+                        };
+                        taffy.set_style(child, child_style).unwrap();
+                    }
+                }
+                // The children of an element that clips them keep their size instead of shrinking to fit, the element
+                // is scrolled instead
+                let flex_shrink = if overflow.clips() {
+                    0.0
+                } else {
+                    l.style.flex_shrink
+                };
+                let child_style = taffy.style(child).unwrap();
+                if child_style.flex_shrink != flex_shrink {
+                    let child_style = Style {
+                        flex_shrink,
+                        ..child_style.clone()
+                    };
+                    taffy.set_style(child, child_style).unwrap();
+                }
+                child_layout.push(child);
             }
 
             fn scale_dimension(d: Dimension) -> Dimension {
@@ -223,6 +527,119 @@ impl State for TaffyLayout {
     }
 }
 
+/// The location of a node with `position: fixed` in the screen, or [`None`] if the node is not fixed. The node is
+/// placed by its insets, which are resolved against the size of the screen
+pub(crate) fn fixed_location(node: NodeRef, taffy: &Taffy) -> Option<Point<f32>> {
+    let taffy_layout = node.get::<TaffyLayout>()?;
+    if taffy_layout.position != Positioning::Fixed {
+        return None;
+    }
+    let rdom = node.real_dom();
+    let root = rdom
+        .get(rdom.root_id())?
+        .get::<TaffyLayout>()?
+        .node
+        .unwrap();
+    let screen = taffy.layout(root).ok()?.size;
+    let taffy_node = taffy_layout.node.unwrap();
+    let size = taffy.layout(taffy_node).ok()?.size;
+    let inset = taffy.style(taffy_node).ok()?.inset;
+
+    fn resolve(inset: LengthPercentageAuto, screen: f32) -> Option<f32> {
+        match inset {
+            LengthPercentageAuto::Points(p) => Some(p),
+            LengthPercentageAuto::Percent(p) => Some(p * screen),
+            LengthPercentageAuto::Auto => None,
+        }
+    }
+    // the start inset wins if both insets are set, nodes without insets are placed at the top left corner
+    let place = |start, end, screen: f32, size: f32| {
+        resolve(start, screen)
+            .or_else(|| resolve(end, screen).map(|end| screen - end - size))
+            .unwrap_or(0.0)
+    };
+    Some(Point {
+        x: place(inset.left, inset.right, screen.width, size.width),
+        y: place(inset.top, inset.bottom, screen.height, size.height),
+    })
+}
+
+/// Pull the margins of a child of an element with `border-collapse: collapse` one cell over the edges it shares with
+/// the sibling before it and the element. `before` and `after` are true if the sibling or element on that side of the
+/// child along the main axis has a border there
+fn collapse_margin(
+    parent: &Style,
+    child: &TaffyLayout,
+    before: bool,
+    after: bool,
+    margin: &mut Rect<LengthPercentageAuto>,
+) {
+    fn pull(margin: &mut LengthPercentageAuto) {
+        if let LengthPercentageAuto::Points(points) = margin {
+            *points -= unit_to_layout_space(1.0);
+        }
+    }
+
+    let direction = parent.flex_direction;
+    let [start, end] = main_edges(direction, edges(child.style.border));
+    let [margin_start, margin_end] = main_edges(direction, [0, 1, 2, 3]);
+    let [cross_start, cross_end] = cross_edges(direction, [0, 1, 2, 3]);
+    let [parent_cross_start, parent_cross_end] = cross_edges(direction, edges(parent.border));
+    let [child_cross_start, child_cross_end] = cross_edges(direction, edges(child.style.border));
+    let margins = [
+        &mut margin.left,
+        &mut margin.right,
+        &mut margin.top,
+        &mut margin.bottom,
+    ];
+
+    let collapsed = [
+        (margin_start, before && child.has_edge(start)),
+        (margin_end, after && child.has_edge(end)),
+        // every child overlaps the borders of the element on the cross axis
+        (
+            cross_start,
+            has_border(parent_cross_start) && child.has_edge(child_cross_start),
+        ),
+        (
+            cross_end,
+            has_border(parent_cross_end) && child.has_edge(child_cross_end),
+        ),
+    ];
+    for (edge, collapses) in collapsed {
+        if collapses {
+            pull(margins[edge]);
+        }
+    }
+}
+
+fn has_border(edge: LengthPercentage) -> bool {
+    matches!(edge, LengthPercentage::Points(width) if width > 0.0)
+}
+
+fn edges<T: Copy>(rect: Rect<T>) -> [T; 4] {
+    [rect.left, rect.right, rect.top, rect.bottom]
+}
+
+/// The start and end edges of a rect along the main axis of a flex container, in the order of the left, right, top
+/// and bottom edges
+fn main_edges<T: Copy>(direction: FlexDirection, [left, right, top, bottom]: [T; 4]) -> [T; 2] {
+    match direction {
+        FlexDirection::Row => [left, right],
+        FlexDirection::RowReverse => [right, left],
+        FlexDirection::Column => [top, bottom],
+        FlexDirection::ColumnReverse => [bottom, top],
+    }
+}
+
+/// The start and end edges of a rect along the cross axis of a flex container
+fn cross_edges<T: Copy>(direction: FlexDirection, [left, right, top, bottom]: [T; 4]) -> [T; 2] {
+    match direction {
+        FlexDirection::Row | FlexDirection::RowReverse => [top, bottom],
+        FlexDirection::Column | FlexDirection::ColumnReverse => [left, right],
+    }
+}
+
 // these are the attributes in layout_attiributes in native-core
 const SORTED_LAYOUT_ATTRS: &[&str] = &[
     "align-content",
@@ -358,6 +775,7 @@ const SORTED_LAYOUT_ATTRS: &[&str] = &[
     "quotes",
     "resize",
     "right",
+    "role",
     "tab-size",
     "table-layout",
     "top",
@@ -378,3 +796,31 @@ const SORTED_LAYOUT_ATTRS: &[&str] = &[
     "word-wrap",
     "z-index",
 ];
+
+#[test]
+fn places_form_items_in_rows() {
+    let cell = |row: i16, column: i16, columns: u16| {
+        let row = Line {
+            start: line(row),
+            end: span(1),
+        };
+        let column = Line {
+            start: line(column),
+            end: span(columns),
+        };
+        (row, column)
+    };
+
+    let mut placer = FormPlacer::default();
+    assert_eq!(placer.place(FormItem::Legend), cell(1, 1, 2));
+    assert_eq!(placer.place(FormItem::Label), cell(2, 1, 1));
+    assert_eq!(placer.place(FormItem::Field), cell(2, 2, 1));
+    // errors get their own row below the field
+    assert_eq!(placer.place(FormItem::Error), cell(3, 2, 1));
+    // a label after a label without a field starts a new row
+    assert_eq!(placer.place(FormItem::Label), cell(4, 1, 1));
+    assert_eq!(placer.place(FormItem::Label), cell(5, 1, 1));
+    // fields without a label stay in the second column
+    assert_eq!(placer.place(FormItem::Field), cell(5, 2, 1));
+    assert_eq!(placer.place(FormItem::Field), cell(6, 2, 1));
+}
@@ -1,19 +1,32 @@
-use crate::focus::Focus;
+use animation::Animations;
 use anyhow::Result;
 use crossterm::{
     cursor::{MoveTo, RestorePosition, SavePosition, Show},
-    event::{DisableMouseCapture, EnableMouseCapture, Event as TermEvent, KeyCode, KeyModifiers},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event as TermEvent, KeyCode, KeyModifiers,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
+use diff::{DiffBackend, FrameDiff};
+use dioxus_native_core::animation::{AnimatedStyle, AnimationTimeline, Keyframes};
+use dioxus_native_core::focus::FocusState;
 use dioxus_native_core::{prelude::*, tree::TreeRef};
 use dioxus_native_core::{real_dom::RealDom, FxDashSet, NodeId, SendAnyMap};
-use focus::FocusState;
 use futures::{channel::mpsc::UnboundedSender, pin_mut, Future, StreamExt};
 use futures_channel::mpsc::unbounded;
-use layout::TaffyLayout;
+use img::{Graphics, Image};
+use layout::{Positioning, TaffyLayout};
 use prevent_default::PreventDefault;
-use std::{io, time::Duration};
+use record::Recording;
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
 use std::{
     pin::Pin,
     sync::{Arc, Mutex},
@@ -21,25 +34,42 @@ use std::{
 use std::{rc::Rc, sync::RwLock};
 use style_attributes::StyleModifier;
 pub use taffy::{geometry::Point, prelude::*};
-use tokio::select;
-use tui::{backend::CrosstermBackend, Terminal};
+use text::TextLayout;
+use tokio::{select, sync::watch};
+use tui::{backend::TestBackend, Terminal, TerminalOptions, Viewport};
 use widgets::{register_widgets, RinkWidgetResponder, RinkWidgetTraitObject};
 
+mod animation;
+mod clipboard;
 mod config;
+mod diff;
+mod export;
 mod focus;
 mod hooks;
+mod img;
 mod layout;
+mod paint;
+mod plot;
 mod prevent_default;
 pub mod query;
+mod record;
 mod render;
+mod scroll;
+mod selection;
+mod snapshot;
 mod style;
 mod style_attributes;
+mod terminal;
+mod text;
 mod widget;
 mod widgets;
 
 pub use config::*;
 pub use hooks::*;
 pub use query::Query;
+pub use selection::Selection;
+pub use snapshot::TerminalGrid;
+pub use terminal::GraphicsProtocol;
 
 // the layout space has a multiplier of 10 to minimize rounding errors
 pub(crate) fn screen_to_layout_space(screen: u16) -> f32 {
@@ -57,17 +87,100 @@ pub(crate) fn layout_to_screen_space(layout: f32) -> f32 {
 #[derive(Clone)]
 pub struct TuiContext {
     tx: UnboundedSender<InputEvent>,
+    // The last frame drawn to the terminal
+    screen: Arc<Mutex<Option<tui::buffer::Buffer>>>,
+    // The frames drawn to the terminal, if recording is enabled
+    recording: Option<Arc<Mutex<Recording>>>,
+    // The last time the user pressed a key or used the mouse
+    activity: Arc<watch::Sender<Instant>>,
+    // The text selected on the screen, shared with the input handler
+    selection: Arc<Mutex<Option<Selection>>>,
+    // The clock and keyframes of the animations, shared with the style pass
+    timeline: AnimationTimeline,
 }
 
 impl TuiContext {
     pub fn new(tx: UnboundedSender<InputEvent>) -> Self {
-        Self { tx }
+        Self {
+            tx,
+            screen: Default::default(),
+            recording: None,
+            activity: Arc::new(watch::channel(Instant::now()).0),
+            selection: Default::default(),
+            timeline: AnimationTimeline::new(),
+        }
+    }
+
+    /// Register keyframes that elements can play with `animation-name`, like a `@keyframes` rule in css. The values
+    /// of the keyframes are applied over the style of the element.
+    pub fn add_keyframes(&self, name: &str, keyframes: Keyframes) {
+        self.timeline.add_keyframes(name, keyframes);
+    }
+
+    /// Watch the last time the user pressed a key, used the mouse or pasted text, for example to detect when the user
+    /// is idle.
+    pub fn activity(&self) -> watch::Receiver<Instant> {
+        self.activity.subscribe()
+    }
+
+    /// Export the current screen as HTML with inline colors, for example to embed the state of the app in docs or bug
+    /// reports.
+    ///
+    /// Returns [`None`] if nothing was drawn to the terminal yet, which is always the case in headless mode.
+    pub fn export_html(&self) -> Option<String> {
+        let screen = self.screen.lock().unwrap();
+        screen.as_ref().map(export::buffer_to_html)
+    }
+
+    /// Export the frames drawn since the app started as an [asciinema v2](https://docs.asciinema.org/manual/asciicast/v2/)
+    /// cast, that can be replayed with `asciinema play`.
+    ///
+    /// Returns [`None`] if recording is not enabled with [`Config::with_recording`], or if nothing was drawn to the
+    /// terminal yet.
+    pub fn export_asciinema(&self) -> Option<String> {
+        let recording = self.recording.as_ref()?.lock().unwrap();
+        recording.to_cast()
+    }
+
+    /// Copy text to the system clipboard. The text is sent to the terminal with the OSC 52 escape sequence, which most
+    /// terminals support, even over ssh. Nothing is copied in headless mode.
+    pub fn copy_to_clipboard(&self, text: &str) {
+        self.tx
+            .unbounded_send(InputEvent::Copy(text.to_string()))
+            .unwrap();
+    }
+
+    /// Select a region of the screen, or clear the selection with [`None`]. The user copies the text in the selection
+    /// with `ctrl+shift+c`.
+    pub fn set_selection(&self, selection: Option<Selection>) {
+        self.tx
+            .unbounded_send(InputEvent::Select(selection))
+            .unwrap();
+    }
+
+    /// The region of the screen that is selected. The user selects text by dragging the mouse outside of inputs.
+    pub fn selection(&self) -> Option<Selection> {
+        *self.selection.lock().unwrap()
+    }
+
+    /// The text in the selection, as it was last drawn to the terminal.
+    ///
+    /// Returns [`None`] if nothing is selected or nothing was drawn to the terminal yet.
+    pub fn selected_text(&self) -> Option<String> {
+        let selection = self.selection()?;
+        let screen = self.screen.lock().unwrap();
+        screen.as_ref().map(|screen| selection.text(screen))
     }
 
     pub fn quit(&self) {
         self.tx.unbounded_send(InputEvent::Close).unwrap();
     }
 
+    /// Move the focus to a node, as if the user tabbed to it. The node must be focusable.
+    pub fn focus(&self, id: NodeId) {
+        self.tx.unbounded_send(InputEvent::Focus(id)).unwrap();
+    }
+
     pub fn inject_event(&self, event: crossterm::event::Event) {
         self.tx
             .unbounded_send(InputEvent::UserInput(event))
@@ -83,13 +196,18 @@ pub fn render<R: Driver>(
         UnboundedSender<InputEvent>,
     ) -> R,
 ) -> Result<()> {
-    let mut rdom = RealDom::new([
-        TaffyLayout::to_type_erased(),
-        Focus::to_type_erased(),
-        StyleModifier::to_type_erased(),
-        PreventDefault::to_type_erased(),
-    ]);
+    render_with_context(cfg, |rdom, taffy, tui_ctx| {
+        create_renderer(rdom, taffy, tui_ctx.tx.clone())
+    })
+}
 
+/// Like [`render`], but the renderer receives the [`TuiContext`] of the app instead of the raw event sender. Only this
+/// context shares the screen, selection and animations with the terminal, so the apis that read them, like
+/// [`TuiContext::export_html`], only work through it.
+pub fn render_with_context<R: Driver>(
+    cfg: Config,
+    create_renderer: impl FnOnce(&Arc<RwLock<RealDom>>, &Arc<Mutex<Taffy>>, TuiContext) -> R,
+) -> Result<()> {
     // Setup input handling
 
     // The event channel for fully resolved events
@@ -116,13 +234,21 @@ pub fn render<R: Driver>(
         });
     }
 
-    register_widgets(&mut rdom, event_tx);
+    let rdom = create_rdom(event_tx);
 
-    let (handler, mut register_event) = RinkInputHandler::create(&mut rdom);
+    let (handler, mut register_event) = RinkInputHandler::create();
 
     let rdom = Arc::new(RwLock::new(rdom));
     let taffy = Arc::new(Mutex::new(Taffy::new()));
-    let mut renderer = create_renderer(&rdom, &taffy, event_tx_clone);
+    let mut tui_ctx = TuiContext::new(event_tx_clone);
+    let recording = cfg.record.then(|| Arc::new(Mutex::new(Recording::new())));
+    tui_ctx.recording = recording.clone();
+    tui_ctx.selection = handler.state().selection.clone();
+    let screen = tui_ctx.screen.clone();
+    let activity = tui_ctx.activity.clone();
+    let selection = tui_ctx.selection.clone();
+    let timeline = tui_ctx.timeline.clone();
+    let mut renderer = create_renderer(&rdom, &taffy, tui_ctx);
 
     // insert the query engine into the rdom
     let query_engine = Query::new(rdom.clone(), taffy.clone());
@@ -135,30 +261,58 @@ pub fn render<R: Driver>(
         .enable_all()
         .build()?
         .block_on(async {
+            let mut animations = Animations::new();
+            // frames of transitions and animations are drawn at most at the frame rate
+            let frame_time = Duration::from_secs_f32(1.0 / cfg.frame_rate.max(1) as f32);
+            let mut next_frame = Instant::now();
+            // the time the animation timeline started at
+            let start = Instant::now();
             {
                 renderer.update(&rdom);
                 let mut any_map = SendAnyMap::new();
                 any_map.insert(taffy.clone());
+                any_map.insert(timeline.clone());
                 let mut rdom = rdom.write().unwrap();
-                let _ = rdom.update_state(any_map);
+                let (changed, _) = rdom.update_state(any_map);
+                animations.update(
+                    &rdom,
+                    &mut taffy.lock().expect("taffy lock poisoned"),
+                    &changed,
+                );
             }
 
+            // the rows the app is drawn in and the position of the cursor before they were reserved, in inline mode
+            let mut inline_area = None;
             let mut terminal = (!cfg.headless).then(|| {
                 enable_raw_mode().unwrap();
                 let mut stdout = std::io::stdout();
-                execute!(
-                    stdout,
-                    EnterAlternateScreen,
-                    EnableMouseCapture,
-                    MoveTo(0, 1000)
-                )
-                .unwrap();
-                let backend = CrosstermBackend::new(io::stdout());
-                Terminal::new(backend).unwrap()
+                if let Some(height) = cfg.inline {
+                    let (area, cursor) = reserve_inline_area(&mut stdout, height).unwrap();
+                    inline_area = Some((area, cursor));
+                    execute!(stdout, EnableMouseCapture, EnableBracketedPaste).unwrap();
+                    let backend = DiffBackend::new(io::stdout());
+                    let options = TerminalOptions {
+                        viewport: Viewport::fixed(area),
+                    };
+                    Terminal::with_options(backend, options).unwrap()
+                } else {
+                    execute!(
+                        stdout,
+                        EnterAlternateScreen,
+                        EnableMouseCapture,
+                        EnableBracketedPaste,
+                        MoveTo(0, 1000)
+                    )
+                    .unwrap();
+                    let backend = DiffBackend::new(io::stdout());
+                    Terminal::new(backend).unwrap()
+                }
             });
-            if let Some(terminal) = &mut terminal {
+            if let (Some(terminal), None) = (&mut terminal, inline_area) {
                 terminal.clear().unwrap();
             }
+            let mut graphics = Graphics::new(cfg.graphics_protocol);
+            let mut frame_diff = FrameDiff::default();
 
             let mut to_rerender = FxDashSet::default();
             to_rerender.insert(rdom.read().unwrap().root_id());
@@ -170,53 +324,61 @@ pub fn render<R: Driver>(
                 -> wait for changes
                 -> resolve events
                 -> lazily update the layout and style based on nodes changed
-                use simd to compare lines for diffing?
                 todo: lazy re-rendering
                 */
 
                 if !to_rerender.is_empty() || updated {
                     updated = false;
-                    fn resize(dims: tui::layout::Rect, taffy: &mut Taffy, rdom: &RealDom) {
-                        let width = screen_to_layout_space(dims.width);
-                        let height = screen_to_layout_space(dims.height);
-                        let root_node = rdom
-                            .get(rdom.root_id())
-                            .unwrap()
-                            .get::<TaffyLayout>()
-                            .unwrap()
-                            .node
-                            .unwrap();
-
-                        // the root node fills the entire area
-                        let mut style = taffy.style(root_node).unwrap().clone();
-                        let new_size = Size {
-                            width: Dimension::Points(width),
-                            height: Dimension::Points(height),
-                        };
-                        if style.size != new_size {
-                            style.size = new_size;
-                            taffy.set_style(root_node, style).unwrap();
-                        }
-
-                        let size = Size {
-                            width: AvailableSpace::Definite(width),
-                            height: AvailableSpace::Definite(height),
-                        };
-                        taffy.compute_layout(root_node, size).unwrap();
-                    }
                     if let Some(terminal) = &mut terminal {
                         execute!(terminal.backend_mut(), SavePosition).unwrap();
-                        terminal.draw(|frame| {
+                        let mut paint = render::Paint::default();
+                        let frame = terminal.draw(|frame| {
                             let rdom = rdom.write().unwrap();
                             let mut taffy = taffy.lock().expect("taffy lock poisoned");
+                            animations.advance(&rdom, &mut taffy);
                             // size is guaranteed to not change when rendering
                             resize(frame.size(), &mut taffy, &rdom);
                             let root = rdom.get(rdom.root_id()).unwrap();
-                            render::render_vnode(frame, &taffy, root, cfg, Point::ZERO);
+                            let area = frame.size();
+                            render::render_vnode(
+                                frame,
+                                &taffy,
+                                root,
+                                cfg,
+                                render::Parent::root(area),
+                                &animations,
+                                &mut paint,
+                            );
+                            // the nodes on top are found in the order they were drawn in to resolve mouse events
+                            rdom.raw_world()
+                                .add_unique(render::PaintOrder(std::mem::take(&mut paint.order)));
+                            if let Some(selection) = *selection.lock().unwrap() {
+                                frame.render_widget(selection::Highlight(selection), area);
+                            }
                         })?;
+                        next_frame = Instant::now() + frame_time;
+                        if let Some(recording) = &recording {
+                            recording.lock().unwrap().record(frame.buffer);
+                        }
+                        let mut screen = screen.lock().unwrap();
+                        let previous = screen.replace(frame.buffer.clone());
+                        frame_diff.draw(
+                            terminal.backend_mut(),
+                            previous.as_ref(),
+                            screen.as_ref().unwrap(),
+                        )?;
+                        // images drawn with a graphics protocol are drawn over the text of the frame
+                        graphics.draw(
+                            terminal.backend_mut(),
+                            paint.images,
+                            previous.as_ref(),
+                            screen.as_ref().unwrap(),
+                        )?;
                         execute!(terminal.backend_mut(), RestorePosition, Show).unwrap();
                     } else {
                         let rdom = rdom.read().unwrap();
+                        let mut taffy = taffy.lock().expect("taffy lock poisoned");
+                        animations.advance(&rdom, &mut taffy);
                         resize(
                             tui::layout::Rect {
                                 x: 0,
@@ -224,7 +386,7 @@ pub fn render<R: Driver>(
                                 width: 1000,
                                 height: 1000,
                             },
-                            &mut taffy.lock().expect("taffy lock poisoned"),
+                            &mut taffy,
                             &rdom,
                         );
                     }
@@ -236,28 +398,92 @@ pub fn render<R: Driver>(
 
                     pin_mut!(wait);
 
+                    let frame = tokio::time::sleep_until(next_frame.into());
+
                     select! {
                         _ = wait => {
 
                         },
+                        _ = frame, if timeline.is_playing() || animations.playing().next().is_some() => {},
                         evt = raw_event_reciever.next() => {
                             match evt.as_ref().unwrap() {
                                 InputEvent::UserInput(event) => match event {
                                     TermEvent::Key(key) => {
-                                        if matches!(key.code, KeyCode::Char('C' | 'c'))
-                                        && key.modifiers.contains(KeyModifiers::CONTROL)
-                                        && cfg.ctrl_c_quit
-                                        {
+                                        let ctrl_c = matches!(key.code, KeyCode::Char('C' | 'c'))
+                                            && key.modifiers.contains(KeyModifiers::CONTROL);
+                                        // terminals that can't tell ctrl+shift+c apart from ctrl+c send ctrl+c
+                                        if ctrl_c && key.modifiers.contains(KeyModifiers::SHIFT) {
+                                            let selection = *selection.lock().unwrap();
+                                            let screen = screen.lock().unwrap();
+                                            if let (Some(selection), Some(screen), Some(terminal)) =
+                                                (selection, screen.as_ref(), &mut terminal)
+                                            {
+                                                let text = selection.text(screen);
+                                                clipboard::copy(terminal.backend_mut(), &text)?;
+                                            }
+                                        } else if ctrl_c && cfg.ctrl_c_quit {
                                             break;
                                         }
                                     }
-                                    TermEvent::Resize(_, _) => updated = true,
+                                    TermEvent::Resize(width, _) => {
+                                        // the inline rows stay in place, but follow the width of the terminal
+                                        if let (Some((area, _)), Some(terminal)) =
+                                            (&mut inline_area, &mut terminal)
+                                        {
+                                            area.width = *width;
+                                            // resizing the terminal would clear the whole screen, not just the inline rows
+                                            execute!(
+                                                terminal.backend_mut(),
+                                                MoveTo(0, area.top()),
+                                                Clear(ClearType::FromCursorDown)
+                                            )?;
+                                            let options = TerminalOptions {
+                                                viewport: Viewport::fixed(*area),
+                                            };
+                                            *terminal = Terminal::with_options(
+                                                DiffBackend::new(io::stdout()),
+                                                options,
+                                            )?;
+                                        }
+                                        // the terminal was cleared, so every cell is drawn again
+                                        frame_diff.invalidate();
+                                        updated = true;
+                                    }
                                     _ => {}
                                 },
                                 InputEvent::Close => break,
+                                InputEvent::Copy(text) => {
+                                    if let Some(terminal) = &mut terminal {
+                                        clipboard::copy(terminal.backend_mut(), text)?;
+                                    }
+                                }
+                                InputEvent::Focus(id) => {
+                                    handler.state().request_focus(*id);
+                                }
+                                InputEvent::Select(new_selection) => {
+                                    handler.state().set_selection(*new_selection);
+                                }
                             };
 
-                            if let InputEvent::UserInput(evt) = evt.unwrap() {
+                            if let InputEvent::UserInput(mut evt) = evt.unwrap() {
+                                // mouse events are relative to the inline rows, and ignored outside of them
+                                if let (TermEvent::Mouse(mouse), Some((area, _))) =
+                                    (&mut evt, inline_area)
+                                {
+                                    if mouse.row < area.top() {
+                                        continue;
+                                    }
+                                    mouse.row -= area.top();
+                                }
+                                // resizing or focusing the terminal doesn't mean the user is active
+                                if !matches!(
+                                    evt,
+                                    TermEvent::Resize(..)
+                                        | TermEvent::FocusGained
+                                        | TermEvent::FocusLost
+                                ) {
+                                    activity.send_replace(Instant::now());
+                                }
                                 register_event(evt);
                             }
                         },
@@ -283,6 +509,8 @@ pub fn render<R: Driver>(
                             &mut rdom.write().unwrap(),
                         );
                         updated |= handler.state().focus_state.clean();
+                        updated |= handler.state().take_scrolled();
+                        updated |= handler.state().take_selection_changed();
 
                         for e in evts {
                             bubble_event_to_widgets(&mut rdom.write().unwrap(), &e);
@@ -293,13 +521,34 @@ pub fn render<R: Driver>(
                     renderer.update(&rdom);
                     // update the style and layout
                     let mut rdom = rdom.write().unwrap();
+                    // the keyframes of the animations move on every frame
+                    if Instant::now() >= next_frame {
+                        timeline.tick(&mut rdom, start.elapsed());
+                    }
                     let mut any_map = SendAnyMap::new();
                     any_map.insert(taffy.clone());
+                    any_map.insert(timeline.clone());
                     let (new_to_rerender, dirty) = rdom.update_state(any_map);
                     to_rerender = new_to_rerender;
                     let text_mask = NodeMaskBuilder::new().with_text().build();
+                    // plots are drawn from their attributes, so they don't have a state that changes with them
+                    let plot_mask = NodeMaskBuilder::new()
+                        .with_attrs(AttributeMaskBuilder::Some(plot::PLOT_ATTRIBUTES))
+                        .build();
                     for (id, mask) in dirty {
-                        if mask.overlaps(&text_mask) {
+                        if mask.overlaps(&text_mask) || mask.overlaps(&plot_mask) {
+                            to_rerender.insert(id);
+                        }
+                    }
+
+                    animations.update(
+                        &rdom,
+                        &mut taffy.lock().expect("taffy lock poisoned"),
+                        &to_rerender,
+                    );
+                    // the elements that are animated change every frame
+                    if Instant::now() >= next_frame {
+                        for id in animations.playing() {
                             to_rerender.insert(id);
                         }
                     }
@@ -308,11 +557,23 @@ pub fn render<R: Driver>(
 
             if let Some(terminal) = &mut terminal {
                 disable_raw_mode()?;
-                execute!(
-                    terminal.backend_mut(),
-                    LeaveAlternateScreen,
-                    DisableMouseCapture
-                )?;
+                if let Some((area, (x, y))) = inline_area {
+                    execute!(
+                        terminal.backend_mut(),
+                        MoveTo(0, area.top()),
+                        Clear(ClearType::FromCursorDown),
+                        MoveTo(x, y),
+                        DisableMouseCapture,
+                        DisableBracketedPaste
+                    )?;
+                } else {
+                    execute!(
+                        terminal.backend_mut(),
+                        LeaveAlternateScreen,
+                        DisableMouseCapture,
+                        DisableBracketedPaste
+                    )?;
+                }
                 terminal.show_cursor()?;
             }
 
@@ -320,10 +581,154 @@ pub fn render<R: Driver>(
         })
 }
 
+/// Reserve `height` rows from the cursor down to draw the app inline, scrolling the terminal if there is not enough
+/// room below the cursor. Returns the reserved rows, and the position the cursor is restored to when the app quits.
+fn reserve_inline_area(
+    stdout: &mut io::Stdout,
+    height: u16,
+) -> io::Result<(tui::layout::Rect, (u16, u16))> {
+    let (columns, rows) = crossterm::terminal::size()?;
+    let (x, y) = crossterm::cursor::position()?;
+    let height = height.clamp(1, rows.max(1));
+    // every new line below the last row scrolls the terminal up by one row
+    for _ in 1..height {
+        stdout.write_all(b"\n")?;
+    }
+    stdout.flush()?;
+    let bottom = (y + height - 1).min(rows.saturating_sub(1));
+    let top = bottom + 1 - height;
+    let area = tui::layout::Rect::new(0, top, columns, height);
+    execute!(stdout, MoveTo(0, top), Clear(ClearType::FromCursorDown))?;
+    Ok((area, (x, top)))
+}
+
+/// Lay out and draw the first frame of an app into a grid of `width` by `height` cells, without a terminal. This is
+/// useful to compare the output of an app with a snapshot in tests.
+///
+/// Events are not handled, and transitions and animations are drawn at their start.
+pub fn render_to_grid<R: Driver>(
+    cfg: Config,
+    width: u16,
+    height: u16,
+    create_renderer: impl FnOnce(&Arc<RwLock<RealDom>>, &Arc<Mutex<Taffy>>, TuiContext) -> R,
+) -> Result<TerminalGrid> {
+    // the channels are kept open so the app can use the tui context, but nothing reads the events
+    let (event_tx, _events) = unbounded();
+    let (raw_event_tx, _raw_events) = unbounded();
+
+    let rdom = Arc::new(RwLock::new(create_rdom(event_tx)));
+    let taffy = Arc::new(Mutex::new(Taffy::new()));
+    let tui_ctx = TuiContext::new(raw_event_tx);
+    let timeline = tui_ctx.timeline.clone();
+    let mut renderer = create_renderer(&rdom, &taffy, tui_ctx);
+
+    let query_engine = Query::new(rdom.clone(), taffy.clone());
+    {
+        let mut rdom = rdom.write().unwrap();
+        rdom.raw_world_mut().add_unique(query_engine);
+    }
+
+    renderer.update(&rdom);
+    let mut animations = Animations::new();
+    {
+        let mut any_map = SendAnyMap::new();
+        any_map.insert(taffy.clone());
+        any_map.insert(timeline);
+        let mut rdom = rdom.write().unwrap();
+        let (changed, _) = rdom.update_state(any_map);
+        animations.update(
+            &rdom,
+            &mut taffy.lock().expect("taffy lock poisoned"),
+            &changed,
+        );
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let frame = terminal.draw(|frame| {
+        let rdom = rdom.read().unwrap();
+        let mut taffy = taffy.lock().expect("taffy lock poisoned");
+        let area = frame.size();
+        resize(area, &mut taffy, &rdom);
+        let root = rdom.get(rdom.root_id()).unwrap();
+        render::render_vnode(
+            frame,
+            &taffy,
+            root,
+            cfg,
+            render::Parent::root(area),
+            &animations,
+            &mut render::Paint::default(),
+        );
+    })?;
+
+    Ok(TerminalGrid {
+        buffer: frame.buffer.clone(),
+    })
+}
+
+/// Create a dom with the state the renderer needs to lay out and draw nodes
+fn create_rdom(event_tx: UnboundedSender<Event>) -> RealDom {
+    let mut rdom = RealDom::new([
+        TaffyLayout::to_type_erased(),
+        FocusState::to_type_erased(),
+        AnimatedStyle::to_type_erased(),
+        StyleModifier::to_type_erased(),
+        PreventDefault::to_type_erased(),
+        Image::to_type_erased(),
+        TextLayout::to_type_erased(),
+    ]);
+    register_widgets(&mut rdom, event_tx);
+    rdom.add_attribute_watcher(DisabledWatcher);
+    rdom
+}
+
+/// Lay out the dom to fill an area of the screen
+fn resize(dims: tui::layout::Rect, taffy: &mut Taffy, rdom: &RealDom) {
+    let width = screen_to_layout_space(dims.width);
+    let height = screen_to_layout_space(dims.height);
+    let root_node = rdom
+        .get(rdom.root_id())
+        .unwrap()
+        .get::<TaffyLayout>()
+        .unwrap()
+        .node
+        .unwrap();
+
+    // the root node fills the entire area
+    let mut style = taffy.style(root_node).unwrap().clone();
+    let new_size = Size {
+        width: Dimension::Points(width),
+        height: Dimension::Points(height),
+    };
+    if style.size != new_size {
+        style.size = new_size;
+        taffy.set_style(root_node, style).unwrap();
+    }
+
+    let size = Size {
+        width: AvailableSpace::Definite(width),
+        height: AvailableSpace::Definite(height),
+    };
+    taffy.compute_layout(root_node, size).unwrap();
+
+    // fixed nodes are laid out again with the screen as their containing block
+    rdom.traverse_depth_first(|node| {
+        let taffy_layout = node.get::<TaffyLayout>().unwrap();
+        if taffy_layout.position == Positioning::Fixed {
+            taffy
+                .compute_layout(taffy_layout.node.unwrap(), size)
+                .unwrap();
+        }
+    });
+}
+
 #[derive(Debug)]
 pub enum InputEvent {
     UserInput(TermEvent),
     Close,
+    Focus(NodeId),
+    Copy(String),
+    Select(Option<Selection>),
 }
 
 pub trait Driver {
@@ -341,17 +746,10 @@ pub trait Driver {
 
 /// Before sending the event to drivers, we need to bubble it up the tree to any widgets that are listening
 fn bubble_event_to_widgets(rdom: &mut RealDom, event: &Event) {
-    let id = event.id;
-    let mut node = Some(id);
-
-    while let Some(node_id) = node {
-        let parent_id = {
-            let tree = rdom.tree_ref();
-            tree.parent_id_advanced(node_id, true)
-        };
-
-        {
-            // println!("@ bubbling event to node {:?}", node_id);
+    let path = rdom.propagation_path(event.id, event.bubbles);
+    path.dispatch(|node_id, phase| {
+        // widgets only handle events at the target and while they bubble
+        if phase != EventPhase::Capturing {
             let mut node_mut = rdom.get_mut(node_id).unwrap();
             if let Some(mut widget) = node_mut
                 .get_mut::<RinkWidgetTraitObject>()
@@ -360,19 +758,19 @@ fn bubble_event_to_widgets(rdom: &mut RealDom, event: &Event) {
                 widget.handle_event(event, node_mut)
             }
         }
-
-        if !event.bubbles {
-            // println!("event does not bubble");
-            break;
-        }
-        node = parent_id;
-    }
+        Propagation::Continue
+    });
 }
 
 pub(crate) fn get_abs_layout(node: NodeRef, taffy: &Taffy) -> Layout {
     let mut node_layout = *taffy
         .layout(node.get::<TaffyLayout>().unwrap().node.unwrap())
         .unwrap();
+    // fixed nodes are placed in the screen instead of their parent
+    if let Some(location) = layout::fixed_location(node, taffy) {
+        node_layout.location = location;
+        return node_layout;
+    }
     let mut current = node;
 
     let dom = node.real_dom();
@@ -384,8 +782,14 @@ pub(crate) fn get_abs_layout(node: NodeRef, taffy: &Taffy) -> Layout {
         let parent_layout = taffy
             .layout(parent.get::<TaffyLayout>().unwrap().node.unwrap())
             .unwrap();
-        node_layout.location.x += parent_layout.location.x;
-        node_layout.location.y += parent_layout.location.y;
+        let fixed = layout::fixed_location(parent, taffy);
+        let location = fixed.unwrap_or(parent_layout.location);
+        node_layout.location.x += location.x;
+        node_layout.location.y +=
+            location.y - screen_to_layout_space(scroll::scroll_offset(parent, taffy));
+        if fixed.is_some() {
+            break;
+        }
     }
     node_layout
 }
@@ -0,0 +1,281 @@
+//! Backgrounds and shadows that are painted with a different color in every cell of an element.
+
+use dioxus_native_core::layout_attributes::parse_value;
+use taffy::prelude::Dimension;
+use tui::style::Color;
+
+use crate::style::{to_rgb, RinkColor};
+
+/// How the background of an element is painted
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum BackgroundPaint {
+    /// every cell has the background color of the element
+    #[default]
+    Color,
+    /// the color of every cell is interpolated along a `linear-gradient(...)`
+    LinearGradient(LinearGradient),
+}
+
+impl BackgroundPaint {
+    /// Parse the value of `background` or `background-image`. Returns [`None`] if the value is not a gradient.
+    pub fn parse(value: &str) -> Option<Self> {
+        let args = value
+            .trim()
+            .strip_prefix("linear-gradient(")?
+            .strip_suffix(')')?;
+        LinearGradient::parse(args).map(Self::LinearGradient)
+    }
+}
+
+/// A `linear-gradient(...)` background
+#[derive(Clone, PartialEq, Debug)]
+pub struct LinearGradient {
+    /// the direction of the gradient in degrees, clockwise from the top
+    angle: f32,
+    /// the colors of the gradient, and where they are on the gradient line from 0 to 1
+    stops: Vec<(RinkColor, f32)>,
+}
+
+impl LinearGradient {
+    /// Parse the arguments of `linear-gradient(...)`, like `to right, red, blue 80%`
+    fn parse(args: &str) -> Option<Self> {
+        let mut args = split_top_level(args, ',').peekable();
+        // the gradient goes to the bottom by default
+        let angle = match args.peek().and_then(|arg| parse_direction(arg)) {
+            Some(angle) => {
+                args.next();
+                angle
+            }
+            None => 180.0,
+        };
+
+        let mut stops: Vec<(RinkColor, Option<f32>)> = Vec::new();
+        for stop in args {
+            let mut parts = split_top_level(stop, ' ');
+            let color = parts.next()?.parse().ok()?;
+            let position = parts
+                .next()
+                .and_then(|position| position.strip_suffix('%'))
+                .and_then(|position| position.parse::<f32>().ok())
+                .map(|position| position / 100.0);
+            stops.push((color, position));
+        }
+        if stops.len() < 2 {
+            return None;
+        }
+
+        // stops without a position are spread evenly between the stops around them
+        let last = stops.len() - 1;
+        stops[0].1.get_or_insert(0.0);
+        stops[last].1.get_or_insert(1.0);
+        let mut start = 0;
+        for i in 1..=last {
+            if let Some(end_position) = stops[i].1 {
+                let start_position = stops[start].1.unwrap();
+                for (offset, stop) in stops[start + 1..i].iter_mut().enumerate() {
+                    let t = (offset + 1) as f32 / (i - start) as f32;
+                    stop.1 = Some(start_position + (end_position - start_position) * t);
+                }
+                start = i;
+            }
+        }
+
+        Some(Self {
+            angle,
+            stops: stops
+                .into_iter()
+                .map(|(color, position)| (color, position.unwrap()))
+                .collect(),
+        })
+    }
+
+    /// The color of the cell at `x` and `y` in an element that is `width` by `height` cells
+    pub fn color_at(&self, x: u16, y: u16, width: u16, height: u16) -> RinkColor {
+        // cells are about twice as tall as they are wide
+        let (width, height) = (width as f32, height as f32 * 2.0);
+        let (px, py) = (x as f32 + 0.5, (y as f32 + 0.5) * 2.0);
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        // the gradient line goes through the center, and is long enough to reach the corners
+        let length = (width * sin).abs() + (height * cos).abs();
+        let t = if length == 0.0 {
+            0.0
+        } else {
+            ((px - width / 2.0) * sin - (py - height / 2.0) * cos) / length + 0.5
+        };
+        self.color_at_position(t)
+    }
+
+    fn color_at_position(&self, t: f32) -> RinkColor {
+        let (first, last) = (self.stops[0], self.stops[self.stops.len() - 1]);
+        if t <= first.1 {
+            return first.0;
+        }
+        for window in self.stops.windows(2) {
+            let [(start, start_position), (end, end_position)] = [window[0], window[1]];
+            if t <= end_position {
+                let range = end_position - start_position;
+                let t = if range <= 0.0 {
+                    1.0
+                } else {
+                    (t - start_position) / range
+                };
+                return mix(start, end, t);
+            }
+        }
+        last.0
+    }
+}
+
+/// Parse the direction of a gradient like `45deg` or `to top right` into degrees
+fn parse_direction(direction: &str) -> Option<f32> {
+    let direction = direction.trim();
+    if let Some(side) = direction.strip_prefix("to ") {
+        let mut sides: Vec<_> = side.split_whitespace().collect();
+        sides.sort_unstable();
+        return match sides.as_slice() {
+            ["top"] => Some(0.0),
+            ["right", "top"] => Some(45.0),
+            ["right"] => Some(90.0),
+            ["bottom", "right"] => Some(135.0),
+            ["bottom"] => Some(180.0),
+            ["bottom", "left"] => Some(225.0),
+            ["left"] => Some(270.0),
+            ["left", "top"] => Some(315.0),
+            _ => None,
+        };
+    }
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ];
+    units.iter().find_map(|(unit, degrees)| {
+        let value = direction.strip_suffix(unit)?.parse::<f32>().ok()?;
+        Some(value * degrees)
+    })
+}
+
+/// Interpolate between two colors
+fn mix(start: RinkColor, end: RinkColor, t: f32) -> RinkColor {
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    let [sr, sg, sb] = to_rgb(start.color);
+    let [er, eg, eb] = to_rgb(end.color);
+    RinkColor {
+        color: Color::Rgb(lerp(sr, er), lerp(sg, eg), lerp(sb, eb)),
+        alpha: lerp(start.alpha, end.alpha),
+    }
+}
+
+/// A `box-shadow`, approximated by dimming the cells around an element
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoxShadow {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// the number of cells the edge of the shadow fades out over
+    pub blur: u16,
+    /// how many cells the shadow is larger than the element
+    pub spread: i32,
+    pub color: RinkColor,
+}
+
+impl BoxShadow {
+    /// Parse the value of `box-shadow`. Only the first shadow is used, and inset shadows are ignored.
+    pub fn parse(value: &str) -> Option<Self> {
+        let shadow = split_top_level(value, ',').next()?;
+        let mut lengths = Vec::new();
+        // shadows are half transparent black unless they set a color
+        let mut color = RinkColor {
+            color: Color::Black,
+            alpha: 128,
+        };
+        for part in split_top_level(shadow, ' ') {
+            if part == "inset" || part == "none" {
+                return None;
+            }
+            if let Some(length) = parse_length(part) {
+                lengths.push(length.round() as i32);
+            } else {
+                color = part.parse().ok()?;
+            }
+        }
+        match lengths.as_slice() {
+            [offset_x, offset_y, rest @ ..] if rest.len() <= 2 => Some(Self {
+                offset_x: *offset_x,
+                offset_y: *offset_y,
+                blur: rest.first().map_or(0, |blur| (*blur).max(0) as u16),
+                spread: rest.get(1).copied().unwrap_or(0),
+                color,
+            }),
+            _ => None,
+        }
+    }
+
+    /// How much the shadow covers a cell, from 0 to 1, given how many cells it is outside of the solid part of the
+    /// shadow
+    pub fn coverage(&self, distance: u16) -> f32 {
+        let fade = self.fade();
+        if distance == 0 {
+            1.0
+        } else if distance <= fade {
+            1.0 - distance as f32 / (fade + 1) as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// How far the blurred edge of the shadow reaches outside of its solid part. Like in browsers, half of the blur is
+    /// outside of the shadow.
+    pub fn fade(&self) -> u16 {
+        self.blur.div_ceil(2)
+    }
+}
+
+fn parse_length(value: &str) -> Option<f32> {
+    if value == "0" {
+        return Some(0.0);
+    }
+    match parse_value(value)? {
+        Dimension::Points(points) => Some(points),
+        _ => None,
+    }
+}
+
+/// Split a list at a separator that is not nested in parentheses
+fn split_top_level(list: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut depth = 0usize;
+    list.split(move |c: char| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        c == separator && depth == 0
+    })
+    .map(str::trim)
+    .filter(|part| !part.is_empty())
+}
+
+#[test]
+fn parses_gradients_and_shadows() {
+    let Some(BackgroundPaint::LinearGradient(gradient)) =
+        BackgroundPaint::parse("linear-gradient(to right, rgb(0, 0, 0), red 50%, #0000ff)")
+    else {
+        panic!("failed to parse gradient");
+    };
+    assert_eq!(gradient.angle, 90.0);
+    assert_eq!(
+        gradient.stops.iter().map(|(_, p)| *p).collect::<Vec<_>>(),
+        [0.0, 0.5, 1.0]
+    );
+    // the first and last columns are close to the colors at the ends
+    assert_eq!(gradient.color_at(0, 0, 4, 1).color, Color::Rgb(64, 0, 0));
+    assert_eq!(gradient.color_at(3, 0, 4, 1).color, Color::Rgb(64, 0, 191));
+    assert!(BackgroundPaint::parse("red").is_none());
+
+    let shadow = BoxShadow::parse("2px 1px 4px rgba(0, 0, 0, 50%)").unwrap();
+    assert_eq!((shadow.offset_x, shadow.offset_y, shadow.blur), (2, 1, 4));
+    assert_eq!(shadow.coverage(0), 1.0);
+    assert_eq!(shadow.coverage(3), 0.0);
+    assert!(BoxShadow::parse("inset 0 0 2px red").is_none());
+}
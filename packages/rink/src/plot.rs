@@ -0,0 +1,337 @@
+//! The `plot` element draws line and scatter charts with braille characters, so charts work in any terminal that can
+//! show unicode, without a graphics protocol like sixel.
+//!
+//! The element is configured with attributes:
+//! - `data`: the points of the chart separated by commas or whitespace. A point is either a `y` value, which is placed
+//!   at the index of the point, or a `x:y` pair
+//! - `kind`: `line` (the default) connects the points, `scatter` only draws the points
+//! - `color`: the color of the points. Defaults to the color of the text of the element
+//! - `range`: the range of the y axis as `min..max`. Defaults to the range of the data
+
+use dioxus_native_core::node::{ElementNode, OwnedAttributeDiscription};
+use tui::layout::Rect;
+
+use crate::{
+    style::RinkColor,
+    widget::{RinkBuffer, RinkCell, RinkWidget},
+};
+
+/// The tag of the plot element
+pub(crate) const PLOT_TAG: &str = "plot";
+
+/// The attributes that change how a plot is drawn
+pub(crate) const PLOT_ATTRIBUTES: &[&str] = &["data", "kind", "color", "range"];
+
+// The bit of every dot in a braille cell, indexed by [y][x]
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum PlotKind {
+    #[default]
+    Line,
+    Scatter,
+}
+
+/// A plot element ready to be drawn
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Plot {
+    points: Vec<[f64; 2]>,
+    kind: PlotKind,
+    range: Option<[f64; 2]>,
+    color: Option<RinkColor>,
+    /// The color of the labels and axes
+    fg: Option<RinkColor>,
+}
+
+impl Plot {
+    /// Read the plot from the attributes of an element. `fg` is the color of the text of the element.
+    pub(crate) fn from_element(element: &ElementNode, fg: Option<RinkColor>) -> Self {
+        let attribute = |name: &str| {
+            element
+                .attributes
+                .get(&OwnedAttributeDiscription {
+                    name: name.to_string(),
+                    namespace: None,
+                })
+                .and_then(|value| value.as_text())
+        };
+
+        let points = attribute("data").map(parse_points).unwrap_or_default();
+        let kind = match attribute("kind") {
+            Some("scatter") => PlotKind::Scatter,
+            _ => PlotKind::Line,
+        };
+        let range = attribute("range").and_then(|range| {
+            let (min, max) = range.split_once("..")?;
+            let range = [min.trim().parse().ok()?, max.trim().parse().ok()?];
+            range.iter().all(|v: &f64| v.is_finite()).then_some(range)
+        });
+        let color = attribute("color").and_then(|color| color.parse().ok());
+
+        Self {
+            points,
+            kind,
+            range,
+            color,
+            fg,
+        }
+    }
+
+    // The range of the x and y axis. Axes without a range are widened, so the points are drawn in the middle.
+    fn bounds(&self) -> Option<[[f64; 2]; 2]> {
+        let mut x = [f64::INFINITY, f64::NEG_INFINITY];
+        let mut y = [f64::INFINITY, f64::NEG_INFINITY];
+        for &[px, py] in &self.points {
+            x = [x[0].min(px), x[1].max(px)];
+            y = [y[0].min(py), y[1].max(py)];
+        }
+        if self.points.is_empty() {
+            return None;
+        }
+        if let Some(range) = self.range {
+            y = range;
+        }
+
+        let widen = |[min, max]: [f64; 2]| {
+            if max > min {
+                [min, max]
+            } else {
+                [min - 1.0, min + 1.0]
+            }
+        };
+        Some([widen(x), widen(y)])
+    }
+}
+
+impl RinkWidget for Plot {
+    fn render(self, area: Rect, mut buf: RinkBuffer) {
+        let Some([x_range, y_range]) = self.bounds() else {
+            return;
+        };
+
+        let y_labels = [format_label(y_range[1]), format_label(y_range[0])];
+        let x_labels = [format_label(x_range[0]), format_label(x_range[1])];
+        let label_width = y_labels.iter().map(|l| l.chars().count()).max().unwrap() as u16;
+
+        let mut label_cell = RinkCell::default();
+        if let Some(fg) = self.fg {
+            label_cell.fg = fg;
+        }
+        let write = |x: u16, y: u16, text: &str, buf: &mut RinkBuffer| {
+            for (i, c) in text.chars().enumerate() {
+                let mut cell = label_cell.clone();
+                cell.symbol = c.to_string();
+                buf.set(x + i as u16, y, cell);
+            }
+        };
+
+        // Draw the axes with their labels if they fit, otherwise the chart takes up the whole area
+        let chart = if area.width > label_width + 2 && area.height > 2 {
+            let chart = Rect::new(
+                area.x + label_width + 1,
+                area.y,
+                area.width - label_width - 1,
+                area.height - 2,
+            );
+            let axis_x = chart.x - 1;
+            let axis_y = chart.bottom();
+
+            for (label, y) in y_labels.iter().zip([chart.top(), chart.bottom() - 1]) {
+                let padding = label_width - label.chars().count() as u16;
+                write(area.x + padding, y, label, &mut buf);
+            }
+            for y in chart.top()..chart.bottom() {
+                let tick = y == chart.top() || y == chart.bottom() - 1;
+                write(axis_x, y, if tick { "┤" } else { "│" }, &mut buf);
+            }
+            write(axis_x, axis_y, "└", &mut buf);
+            for x in chart.left()..chart.right() {
+                write(x, axis_y, "─", &mut buf);
+            }
+
+            write(chart.x, axis_y + 1, &x_labels[0], &mut buf);
+            let max_width = x_labels[1].chars().count() as u16;
+            if chart.width > x_labels[0].chars().count() as u16 + max_width {
+                write(
+                    chart.right() - max_width,
+                    axis_y + 1,
+                    &x_labels[1],
+                    &mut buf,
+                );
+            }
+
+            chart
+        } else {
+            area
+        };
+
+        let dots = rasterize(
+            &self.points,
+            self.kind,
+            [x_range, y_range],
+            chart.width as usize,
+            chart.height as usize,
+        );
+
+        let mut dot_cell = RinkCell::default();
+        if let Some(color) = self.color.or(self.fg) {
+            dot_cell.fg = color;
+        }
+        for (i, &bits) in dots.iter().enumerate() {
+            if bits == 0 {
+                continue;
+            }
+            let x = chart.x + (i % chart.width as usize) as u16;
+            let y = chart.y + (i / chart.width as usize) as u16;
+            let mut cell = dot_cell.clone();
+            cell.symbol = char::from_u32(0x2800 + bits as u32).unwrap().to_string();
+            buf.set(x, y, cell);
+        }
+    }
+}
+
+fn parse_points(data: &str) -> Vec<[f64; 2]> {
+    data.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|point| !point.is_empty())
+        .enumerate()
+        .filter_map(|(i, point)| {
+            let point = match point.split_once(':') {
+                Some((x, y)) => [x.parse().ok()?, y.parse().ok()?],
+                None => [i as f64, point.parse().ok()?],
+            };
+            point.iter().all(|v: &f64| v.is_finite()).then_some(point)
+        })
+        .collect()
+}
+
+fn format_label(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    // avoid labels like -0
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+/// Draw the points into a grid of `width` by `height` braille cells. Every cell has 2 by 4 dots. Returns the dots of
+/// every cell, row by row.
+fn rasterize(
+    points: &[[f64; 2]],
+    kind: PlotKind,
+    [x_range, y_range]: [[f64; 2]; 2],
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    let mut cells = vec![0u8; width * height];
+    let dots_x = (width * 2) as i64;
+    let dots_y = (height * 4) as i64;
+    if dots_x == 0 || dots_y == 0 {
+        return cells;
+    }
+
+    let mut set = |x: i64, y: i64| {
+        if (0..dots_x).contains(&x) && (0..dots_y).contains(&y) {
+            let (x, y) = (x as usize, y as usize);
+            cells[(y / 4) * width + x / 2] |= BRAILLE_DOTS[y % 4][x % 2];
+        }
+    };
+
+    // Points far outside of the chart are moved closer, so lines to them don't take forever to draw
+    let to_dots = |[x, y]: [f64; 2]| {
+        let x = (x - x_range[0]) / (x_range[1] - x_range[0]) * (dots_x - 1) as f64;
+        let y = (y_range[1] - y) / (y_range[1] - y_range[0]) * (dots_y - 1) as f64;
+        [
+            x.clamp(-dots_x as f64, 2.0 * dots_x as f64).round() as i64,
+            y.clamp(-dots_y as f64, 2.0 * dots_y as f64).round() as i64,
+        ]
+    };
+
+    let mut last: Option<[i64; 2]> = None;
+    for &point in points {
+        let [x, y] = to_dots(point);
+        match (kind, last) {
+            (PlotKind::Line, Some([last_x, last_y])) => {
+                draw_line([last_x, last_y], [x, y], &mut set)
+            }
+            _ => set(x, y),
+        }
+        last = Some([x, y]);
+    }
+
+    cells
+}
+
+// Bresenham's line algorithm
+fn draw_line([mut x0, mut y0]: [i64; 2], [x1, y1]: [i64; 2], set: &mut impl FnMut(i64, i64)) {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    loop {
+        set(x0, y0);
+        if x0 == x1 && y0 == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y0 += sy;
+        }
+    }
+}
+
+#[test]
+fn parses_points() {
+    assert_eq!(parse_points("1, 2 3"), [[0.0, 1.0], [1.0, 2.0], [2.0, 3.0]]);
+    assert_eq!(parse_points("-1:2,4:0.5"), [[-1.0, 2.0], [4.0, 0.5]]);
+    // points that are not finite numbers are skipped, but keep their index
+    assert_eq!(parse_points("1,x,inf,NaN,2"), [[0.0, 1.0], [4.0, 2.0]]);
+    assert!(parse_points(" ,, ").is_empty());
+}
+
+#[test]
+fn draws_lines() {
+    let mut dots = Vec::new();
+    draw_line([0, 0], [3, 1], &mut |x, y| dots.push([x, y]));
+    assert_eq!(dots, [[0, 0], [1, 0], [2, 1], [3, 1]]);
+
+    // both ends are drawn in either direction
+    dots.clear();
+    draw_line([3, 1], [0, 0], &mut |x, y| dots.push([x, y]));
+    assert_eq!(
+        (dots.first(), dots.last(), dots.len()),
+        (Some(&[3, 1]), Some(&[0, 0]), 4)
+    );
+
+    dots.clear();
+    draw_line([2, 2], [2, 2], &mut |x, y| dots.push([x, y]));
+    assert_eq!(dots, [[2, 2]]);
+}
+
+#[test]
+fn rasterizes_points() {
+    let bounds = [[0.0, 1.0], [0.0, 1.0]];
+    let points = [[0.0, 0.0], [1.0, 1.0]];
+    // a single cell has 2 by 4 dots, and the y axis points up
+    assert_eq!(
+        rasterize(&points, PlotKind::Scatter, bounds, 1, 1),
+        [0x40 | 0x08]
+    );
+    assert_eq!(
+        rasterize(&points, PlotKind::Line, bounds, 1, 1),
+        [0x40 | 0x04 | 0x10 | 0x08]
+    );
+
+    // points outside of the chart are not drawn
+    assert_eq!(
+        rasterize(&[[2.0, 0.5]], PlotKind::Scatter, bounds, 2, 1),
+        [0, 0]
+    );
+    assert!(rasterize(&points, PlotKind::Line, bounds, 0, 3).is_empty());
+}
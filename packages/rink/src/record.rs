@@ -0,0 +1,234 @@
+//! Record the frames drawn to the terminal, and export them as an [asciinema v2](https://docs.asciinema.org/manual/asciicast/v2/)
+//! cast that can be replayed with `asciinema play` or embedded in a web page.
+
+use std::{
+    fmt::Write,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use tui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+use crate::text::text_width;
+
+/// The frames drawn to the terminal since the recording started
+pub(crate) struct Recording {
+    /// The time and unix timestamp of the first frame
+    start: Option<(Instant, u64)>,
+    /// The size of the first frame
+    size: Rect,
+    /// The last frame, the next frame only writes the cells that changed since it
+    last: Option<Buffer>,
+    /// The events of the cast, one json array per line
+    events: String,
+}
+
+impl Recording {
+    pub(crate) fn new() -> Self {
+        Self {
+            start: None,
+            size: Rect::default(),
+            last: None,
+            events: String::new(),
+        }
+    }
+
+    /// Record a frame that was drawn to the terminal
+    pub(crate) fn record(&mut self, frame: &Buffer) {
+        let now = Instant::now();
+        let (start, _) = *self.start.get_or_insert_with(|| {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default();
+            (now, timestamp)
+        });
+        let time = now.duration_since(start).as_secs_f64();
+
+        let mut output = String::new();
+        match &self.last {
+            Some(last) if last.area == frame.area => {
+                write_cells(last.diff(frame), &mut output);
+            }
+            last => {
+                let area = frame.area;
+                if last.is_none() {
+                    self.size = area;
+                } else {
+                    self.push_event(time, "r", &format!("{}x{}", area.width, area.height));
+                }
+                // Clear the screen and draw every cell of the frame
+                output.push_str("\x1b[0m\x1b[2J");
+                let cells = frame.content.iter().enumerate().map(|(i, cell)| {
+                    let (x, y) = frame.pos_of(i);
+                    (x, y, cell)
+                });
+                write_cells(cells, &mut output);
+            }
+        }
+
+        if !output.is_empty() {
+            self.push_event(time, "o", &output);
+        }
+        self.last = Some(frame.clone());
+    }
+
+    fn push_event(&mut self, time: f64, code: &str, data: &str) {
+        let _ = write!(self.events, "[{time:.6}, \"{code}\", ");
+        write_json_string(data, &mut self.events);
+        self.events.push_str("]\n");
+    }
+
+    /// Export the recording in the asciinema v2 format. Returns [`None`] if no frame was recorded yet.
+    pub(crate) fn to_cast(&self) -> Option<String> {
+        let (_, timestamp) = self.start?;
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {timestamp}}}\n",
+            self.size.width, self.size.height
+        );
+        cast.push_str(&self.events);
+        Some(cast)
+    }
+}
+
+/// Write the ansi escape codes that draw the cells, moving the cursor only between cells that are not next to each
+/// other and changing the style only between cells with a different style
+pub(crate) fn write_cells<'a>(
+    cells: impl IntoIterator<Item = (u16, u16, &'a Cell)>,
+    output: &mut String,
+) {
+    let mut cursor = None;
+    let mut style = None;
+    for (x, y, cell) in cells {
+        // the cells covered by a wide glyph were drawn with the glyph
+        if matches!(cursor, Some((end, row)) if row == y && x < end) {
+            continue;
+        }
+        if cursor != Some((x, y)) {
+            let _ = write!(output, "\x1b[{};{}H", y + 1, x + 1);
+        }
+        let cell_style = (cell.fg, cell.bg, cell.modifier);
+        if style != Some(cell_style) {
+            write_style(cell_style, output);
+            style = Some(cell_style);
+        }
+        output.push_str(&cell.symbol);
+        // the terminal moves the cursor past every cell the symbol takes up
+        cursor = Some((x + text_width(&cell.symbol) as u16, y));
+    }
+    if style.is_some() {
+        output.push_str("\x1b[0m");
+    }
+}
+
+pub(crate) fn write_style((fg, bg, modifier): (Color, Color, Modifier), output: &mut String) {
+    output.push_str("\x1b[0");
+    const MODIFIERS: [(Modifier, u8); 9] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::RAPID_BLINK, 6),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+    for (flag, code) in MODIFIERS {
+        if modifier.contains(flag) {
+            let _ = write!(output, ";{code}");
+        }
+    }
+    write_color(fg, 30, output);
+    write_color(bg, 40, output);
+    output.push('m');
+}
+
+/// Write the parameters of a select graphic rendition escape code that set a color. `base` is 30 for the foreground
+/// and 40 for the background.
+fn write_color(color: Color, base: u8, output: &mut String) {
+    let named = |offset: u8| base + offset;
+    let bright = |offset: u8| base + 60 + offset;
+    let code = match color {
+        Color::Reset => return,
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Indexed(idx) => {
+            let _ = write!(output, ";{};5;{idx}", base + 8);
+            return;
+        }
+        Color::Rgb(r, g, b) => {
+            let _ = write!(output, ";{};2;{r};{g};{b}", base + 8);
+            return;
+        }
+    };
+    let _ = write!(output, ";{code}");
+}
+
+fn write_json_string(text: &str, output: &mut String) {
+    output.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[test]
+fn record_changed_cells() {
+    use tui::style::Style;
+
+    let mut recording = Recording::new();
+    assert!(recording.to_cast().is_none());
+
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+    buffer.set_string(0, 0, "a", Style::default().fg(Color::Rgb(255, 0, 0)));
+    recording.record(&buffer);
+    // Frames without changes are not recorded
+    recording.record(&buffer);
+    buffer.set_string(1, 0, "\"", Style::default().add_modifier(Modifier::BOLD));
+    recording.record(&buffer);
+    recording.record(&Buffer::empty(Rect::new(0, 0, 1, 1)));
+
+    let cast = recording.to_cast().unwrap();
+    let mut lines = cast.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("{\"version\": 2, \"width\": 2, \"height\": 1, \"timestamp\": "));
+    // Strip the timestamps of the events
+    let events: Vec<_> = lines.map(|line| line.split_once(", ").unwrap().1).collect();
+    assert_eq!(
+        events,
+        [
+            "\"o\", \"\\u001b[0m\\u001b[2J\\u001b[1;1H\\u001b[0;38;2;255;0;0ma\\u001b[0m \\u001b[0m\"]",
+            "\"o\", \"\\u001b[1;2H\\u001b[0;1m\\\"\\u001b[0m\"]",
+            "\"r\", \"1x1\"]",
+            "\"o\", \"\\u001b[0m\\u001b[2J\\u001b[1;1H\\u001b[0m \\u001b[0m\"]",
+        ]
+    );
+}
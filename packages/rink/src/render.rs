@@ -1,100 +1,609 @@
 use dioxus_native_core::{prelude::*, tree::TreeRef};
-use std::io::Stdout;
+use shipyard::Unique;
+use std::sync::Arc;
 use taffy::{
     geometry::Point,
     prelude::{Dimension, Layout, Size},
     Taffy,
 };
-use tui::{backend::CrosstermBackend, layout::Rect, style::Color};
+use tui::{backend::Backend, buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
 
 use crate::{
+    animation::{Animations, Inherited},
     focus::Focused,
-    layout::TaffyLayout,
+    img::{DecodedImage, GraphicsArea, HalfBlocks, Image, ImagePlacement, IMG_TAG},
+    layout::{fixed_location, Overflow, TaffyLayout},
     layout_to_screen_space,
+    paint::{BackgroundPaint, BoxShadow},
+    plot::{Plot, PLOT_TAG},
+    screen_to_layout_space,
+    scroll::{scroll_offset, Viewport},
     style::{RinkColor, RinkStyle},
-    style_attributes::{BorderEdge, BorderStyle, StyleModifier},
+    style_attributes::{BorderEdge, BorderStyle, Borders, StyleModifier},
+    text::{graphemes, TextLayout},
     widget::{RinkBuffer, RinkCell, RinkWidget, WidgetWithContext},
-    Config,
+    Config, GraphicsProtocol,
 };
 
 const RADIUS_MULTIPLIER: [f32; 2] = [1.0, 0.5];
 
-pub(crate) fn render_vnode(
-    frame: &mut tui::Frame<CrosstermBackend<Stdout>>,
+/// What a node inherits from its parent while it is drawn
+#[derive(Clone, Copy)]
+pub(crate) struct Parent {
+    pub location: Point<f32>,
+    /// the area the node is clipped to
+    pub clip: Rect,
+    pub animated: Inherited,
+    /// the area the whole dom is drawn in, which fixed nodes are placed in
+    pub screen: Rect,
+}
+
+impl Parent {
+    /// The parent of the root node, which fills the area
+    pub fn root(area: Rect) -> Self {
+        Self {
+            // the area is offset from the top of the terminal when the app is drawn inline
+            location: Point {
+                x: screen_to_layout_space(area.x),
+                y: screen_to_layout_space(area.y),
+            },
+            clip: area,
+            animated: Inherited::default(),
+            screen: area,
+        }
+    }
+}
+
+/// What is collected while the nodes of a frame are drawn
+#[derive(Default)]
+pub(crate) struct Paint {
+    /// images to draw with a graphics protocol after the frame is drawn
+    pub images: Vec<ImagePlacement>,
+    /// the nodes in the order they were drawn, nodes that are drawn later are on top
+    pub order: Vec<NodeId>,
+    /// the positioned nodes of the stacking context that is being drawn
+    layers: Vec<Layer>,
+}
+
+/// A positioned node that is drawn after the other nodes of its stacking context
+struct Layer {
+    node: NodeId,
+    z_index: i32,
+    parent: Parent,
+}
+
+/// The parts of a node and its children that a pass over them draws
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Part {
+    /// the node and the children that are not positioned
+    All,
+    /// only the node itself
+    Box,
+    /// only the children that are not positioned
+    Children,
+    /// nothing, the pass only collects the positioned children
+    Layers,
+}
+
+/// The nodes in the order they were drawn in the last frame
+#[derive(Unique, Default)]
+pub(crate) struct PaintOrder(pub Vec<NodeId>);
+
+/// Draw a node and its children as a stacking context. The children that are positioned are drawn in the order of their
+/// z-index after the rest of the node, so dialogs and dropdowns are drawn over the content around them. Children with a
+/// negative z-index are drawn over the node, but below its other children
+pub(crate) fn render_vnode<B: Backend>(
+    frame: &mut tui::Frame<B>,
+    layout: &Taffy,
+    node: NodeRef,
+    cfg: Config,
+    parent: Parent,
+    animations: &Animations,
+    paint: &mut Paint,
+) {
+    let draw_node = |frame: &mut tui::Frame<B>, paint: &mut Paint, part| {
+        render_node(frame, layout, node, cfg, parent, animations, paint, part)
+    };
+    let draw_layers = |frame: &mut tui::Frame<B>, paint: &mut Paint, layers: &[Layer]| {
+        for layer in layers {
+            let node = node.real_dom().get(layer.node).unwrap();
+            render_vnode(frame, layout, node, cfg, layer.parent, animations, paint);
+        }
+    };
+
+    let outer_layers = std::mem::take(&mut paint.layers);
+    // the layers are collected before anything is drawn, because the layers below the content are drawn first
+    draw_node(frame, paint, Part::Layers);
+    let mut layers = std::mem::take(&mut paint.layers);
+    // the sort is stable, so layers with the same z-index are drawn in tree order
+    layers.sort_by_key(|layer| layer.z_index);
+    let below = layers.iter().take_while(|layer| layer.z_index < 0).count();
+
+    if below == 0 {
+        draw_node(frame, paint, Part::All);
+    } else {
+        draw_node(frame, paint, Part::Box);
+        draw_layers(frame, paint, &layers[..below]);
+        draw_node(frame, paint, Part::Children);
+    }
+    // the layers were collected again while the content was drawn
+    paint.layers = outer_layers;
+    draw_layers(frame, paint, &layers[below..]);
+}
+
+/// Draw a part of a node and the children that are not positioned
+#[allow(clippy::too_many_arguments)]
+fn render_node<B: Backend>(
+    frame: &mut tui::Frame<B>,
     layout: &Taffy,
     node: NodeRef,
     cfg: Config,
-    parent_location: Point<f32>,
+    parent: Parent,
+    animations: &Animations,
+    paint: &mut Paint,
+    part: Part,
 ) {
     if let NodeType::Placeholder = &*node.node_type() {
         return;
     }
+    let draw_box = matches!(part, Part::All | Part::Box);
+    let draw_children = matches!(part, Part::All | Part::Children);
+    if draw_box {
+        paint.order.push(node.id());
+    }
+    let Parent {
+        location: parent_location,
+        mut clip,
+        screen,
+        ..
+    } = parent;
 
     let Layout {
         mut location, size, ..
     } = layout
         .layout(node.get::<TaffyLayout>().unwrap().node.unwrap())
         .unwrap();
-    location.x += parent_location.x;
-    location.y += parent_location.y;
+    // fixed nodes are placed in the screen, and are not clipped by their ancestors
+    if let Some(fixed) = fixed_location(node, layout) {
+        location = Point {
+            x: screen_to_layout_space(screen.x) + fixed.x,
+            y: screen_to_layout_space(screen.y) + fixed.y,
+        };
+        clip = screen;
+    } else {
+        location.x += parent_location.x;
+        location.y += parent_location.y;
+    }
 
+    // nodes in scrolled elements can be above the screen
     let Point { x: fx, y: fy } = location;
-    let x = layout_to_screen_space(fx).round() as u16;
-    let y = layout_to_screen_space(fy).round() as u16;
+    let x = layout_to_screen_space(fx).round() as i32;
+    let y = layout_to_screen_space(fy).round() as i32;
     let Size { width, height } = *size;
-    let width = layout_to_screen_space(fx + width).round() as u16 - x;
-    let height = layout_to_screen_space(fy + height).round() as u16 - y;
+    let width = (layout_to_screen_space(fx + width).round() as i32 - x) as u16;
+    let height = (layout_to_screen_space(fy + height).round() as i32 - y) as u16;
+    let area = ScreenArea {
+        x,
+        y,
+        width,
+        height,
+    };
+
+    // the style the node is shown with in this frame of its transitions and animations
+    let (shown_style, animated) = animations.style(
+        node.id(),
+        node.get::<StyleModifier>().unwrap().core,
+        parent.animated,
+    );
 
     match &*node.node_type() {
+        NodeType::Text(_) if !draw_box => {}
         NodeType::Text(text) => {
-            #[derive(Default)]
             struct Label<'a> {
                 text: &'a str,
+                layout: &'a TextLayout,
                 style: RinkStyle,
             }
 
             impl<'a> RinkWidget for Label<'a> {
                 fn render(self, area: Rect, mut buf: RinkBuffer) {
-                    for (i, c) in self.text.char_indices() {
-                        let mut new_cell = RinkCell::default();
-                        new_cell.set_style(self.style);
-                        new_cell.symbol = c.to_string();
-                        buf.set(area.left() + i as u16, area.top(), new_cell);
+                    let width = area.width as usize;
+                    let lines = self.layout.lines(self.text, Some(width));
+                    for (row, line) in lines.iter().enumerate() {
+                        let (offset, line) = self.layout.align_line(line, width);
+                        let mut column = offset;
+                        for (grapheme, grapheme_width) in graphemes(&line) {
+                            // a grapheme without any width can't be drawn in a cell of its own
+                            if grapheme_width == 0 {
+                                continue;
+                            }
+                            let mut new_cell = RinkCell::default();
+                            new_cell.set_style(self.style);
+                            new_cell.symbol = grapheme.to_string();
+                            buf.set(
+                                area.left() + column as u16,
+                                area.top() + row as u16,
+                                new_cell,
+                            );
+                            column += grapheme_width;
+                        }
                     }
                 }
             }
 
             let label = Label {
                 text: &text.text,
-                style: node.get::<StyleModifier>().unwrap().core,
+                layout: &node.get::<TextLayout>().unwrap(),
+                style: shown_style,
             };
-            let area = Rect::new(x, y, width, height);
 
-            // the renderer will panic if a node is rendered out of range even if the size is zero
-            if area.width > 0 && area.height > 0 {
-                frame.render_widget(WidgetWithContext::new(label, cfg), area);
-            }
+            render_clipped(frame, WidgetWithContext::new(label, cfg), area, clip);
         }
-        NodeType::Element { .. } => {
-            let area = Rect::new(x, y, width, height);
+        NodeType::Element(element) => {
+            let style = node.get::<StyleModifier>().unwrap();
+            // the shadow is drawn below the element
+            if let Some(shadow) = style.modifier.shadow.as_ref().filter(|_| draw_box) {
+                let shadow_area = area.shadow(shadow);
+                let widget = Shadow {
+                    shadow,
+                    element: ScreenArea {
+                        x: area.x - shadow_area.x,
+                        y: area.y - shadow_area.y,
+                        ..area
+                    },
+                };
+                render_clipped(
+                    frame,
+                    WidgetWithContext::new(widget, cfg),
+                    shadow_area,
+                    clip,
+                );
+            }
+
+            let widget = ElementWidget {
+                node,
+                style: shown_style,
+                animated,
+                joins_borders: node.parent().map_or(false, |parent| {
+                    parent.get::<TaffyLayout>().unwrap().border_collapse
+                }),
+            };
+            if draw_box {
+                render_clipped(frame, WidgetWithContext::new(widget, cfg), area, clip);
+            }
+
+            if draw_box && element.tag == PLOT_TAG {
+                let plot = Plot::from_element(element, shown_style.fg);
+                let area = area.inside_borders(&style.modifier.borders);
+                render_clipped(frame, WidgetWithContext::new(plot, cfg), area, clip);
+            }
+            if draw_box && element.tag == IMG_TAG {
+                if let Some(image) = &node.get::<Image>().unwrap().image {
+                    let area = area.inside_borders(&style.modifier.borders);
+                    render_image(frame, image, area, clip, cfg, &mut paint.images);
+                }
+            }
+
+            // the children of elements that clip them are only drawn inside of the borders of the element
+            let overflow = node.get::<TaffyLayout>().unwrap().overflow;
+            let inner_area = area.inside_borders(&style.modifier.borders);
+            let children_clip = if overflow.clips() {
+                inner_area.visible(clip)
+            } else {
+                Some(clip)
+            };
+
+            // the children of the node are only walked to collect the layers in them
+            let children_part = match part {
+                Part::Layers => Some(Part::Layers),
+                Part::All | Part::Children => Some(Part::All),
+                Part::Box => None,
+            };
+            if let (Some(children_clip), Some(children_part)) = (children_clip, children_part) {
+                let mut children_location = location;
+                children_location.y -= screen_to_layout_space(scroll_offset(node, layout));
 
-            // the renderer will panic if a node is rendered out of range even if the size is zero
-            if area.width > 0 && area.height > 0 {
-                frame.render_widget(WidgetWithContext::new(node, cfg), area);
+                let node_id = node.id();
+                let rdom = node.real_dom();
+                for child_id in rdom.tree_ref().children_ids_advanced(node_id, true) {
+                    let c = rdom.get(child_id).unwrap();
+                    let parent = Parent {
+                        location: children_location,
+                        clip: children_clip,
+                        animated,
+                        screen,
+                    };
+                    let child_layout = c.get::<TaffyLayout>().unwrap();
+                    if child_layout.is_layer() {
+                        paint.layers.push(Layer {
+                            node: child_id,
+                            z_index: child_layout.z_index.unwrap_or(0),
+                            parent,
+                        });
+                    } else {
+                        render_node(
+                            frame,
+                            layout,
+                            c,
+                            cfg,
+                            parent,
+                            animations,
+                            paint,
+                            children_part,
+                        );
+                    }
+                }
             }
 
-            let node_id = node.id();
-            let rdom = node.real_dom();
-            for child_id in rdom.tree_ref().children_ids_advanced(node_id, true) {
-                let c = rdom.get(child_id).unwrap();
-                render_vnode(frame, layout, c, cfg, location);
+            if draw_children && overflow.scrolls() {
+                let viewport = Viewport::of(node, layout);
+                if overflow == Overflow::Scroll || viewport.max_offset > 0 {
+                    let scrollbar = Scrollbar {
+                        viewport,
+                        offset: scroll_offset(node, layout),
+                        color: shown_style.fg,
+                    };
+                    let area = ScreenArea {
+                        x: inner_area.x + inner_area.width as i32 - 1,
+                        width: inner_area.width.min(1),
+                        ..inner_area
+                    };
+                    render_clipped(frame, WidgetWithContext::new(scrollbar, cfg), area, clip);
+                }
             }
         }
         NodeType::Placeholder => unreachable!(),
     }
 }
 
-impl RinkWidget for NodeRef<'_> {
+/// Render an image with half blocks, or reserve the area of the image and collect it to draw it with a graphics
+/// protocol after the frame is drawn
+fn render_image<B: Backend>(
+    frame: &mut tui::Frame<B>,
+    image: &Arc<DecodedImage>,
+    area: ScreenArea,
+    clip: Rect,
+    cfg: Config,
+    images: &mut Vec<ImagePlacement>,
+) {
+    if cfg.graphics_protocol == GraphicsProtocol::HalfBlocks {
+        let widget = HalfBlocks(&image.pixels);
+        render_clipped(frame, WidgetWithContext::new(widget, cfg), area, clip);
+        return;
+    }
+
+    let Some(visible) = area.visible(clip) else {
+        return;
+    };
+    frame.render_widget(WidgetWithContext::new(GraphicsArea, cfg), visible);
+
+    // crop the image to the part of the area that is visible
+    let (width, height) = image.pixels.dimensions();
+    let scale_x = width as f32 / area.width as f32;
+    let scale_y = height as f32 / area.height as f32;
+    let x = ((visible.x as i32 - area.x) as f32 * scale_x) as u32;
+    let y = ((visible.y as i32 - area.y) as f32 * scale_y) as u32;
+    let crop = [
+        x.min(width - 1),
+        y.min(height - 1),
+        ((visible.width as f32 * scale_x).round() as u32).clamp(1, width - x.min(width - 1)),
+        ((visible.height as f32 * scale_y).round() as u32).clamp(1, height - y.min(height - 1)),
+    ];
+
+    images.push(ImagePlacement {
+        image: image.clone(),
+        area: visible,
+        crop,
+    });
+}
+
+/// The area of a node on the screen. Unlike a [`Rect`], the area can start above or left of the screen
+#[derive(Clone, Copy, Debug)]
+struct ScreenArea {
+    x: i32,
+    y: i32,
+    width: u16,
+    height: u16,
+}
+
+impl ScreenArea {
+    /// The area that is not covered by the borders
+    fn inside_borders(self, borders: &Borders) -> Self {
+        let width = |edge: &BorderEdge| edge.style.symbol_set().is_some() as u16;
+        let [top, right, bottom, left] = [
+            width(&borders.top),
+            width(&borders.right),
+            width(&borders.bottom),
+            width(&borders.left),
+        ];
+        Self {
+            x: self.x + left as i32,
+            y: self.y + top as i32,
+            width: self.width.saturating_sub(left + right),
+            height: self.height.saturating_sub(top + bottom),
+        }
+    }
+
+    /// The area covered by the shadow of an element in this area, including the blurred edge of the shadow
+    fn shadow(self, shadow: &BoxShadow) -> Self {
+        let grow = shadow.spread + shadow.fade() as i32;
+        Self {
+            x: self.x + shadow.offset_x - grow,
+            y: self.y + shadow.offset_y - grow,
+            width: (self.width as i32 + 2 * grow).max(0) as u16,
+            height: (self.height as i32 + 2 * grow).max(0) as u16,
+        }
+    }
+
+    /// The part of the area that is inside of the clip, or [`None`] if no part of the area is visible
+    fn visible(self, clip: Rect) -> Option<Rect> {
+        let left = self.x.max(clip.left() as i32);
+        let top = self.y.max(clip.top() as i32);
+        let right = (self.x + self.width as i32).min(clip.right() as i32);
+        let bottom = (self.y + self.height as i32).min(clip.bottom() as i32);
+        (left < right && top < bottom).then(|| {
+            Rect::new(
+                left as u16,
+                top as u16,
+                (right - left) as u16,
+                (bottom - top) as u16,
+            )
+        })
+    }
+}
+
+/// Render a widget in an area that may be partially outside of the clip
+fn render_clipped<B: Backend>(
+    frame: &mut tui::Frame<B>,
+    widget: impl Widget,
+    area: ScreenArea,
+    clip: Rect,
+) {
+    // the renderer will panic if a node is rendered out of range even if the size is zero
+    let Some(visible) = area.visible(clip) else {
+        return;
+    };
+    if visible.x as i32 == area.x
+        && visible.y as i32 == area.y
+        && visible.width == area.width
+        && visible.height == area.height
+    {
+        frame.render_widget(widget, visible);
+    } else {
+        frame.render_widget(Clipped { widget, area }, visible);
+    }
+}
+
+/// Renders a widget to a separate buffer, and copies the visible part of the widget to the screen
+struct Clipped<W: Widget> {
+    widget: W,
+    area: ScreenArea,
+}
+
+impl<W: Widget> Widget for Clipped<W> {
+    fn render(self, visible: Rect, buf: &mut Buffer) {
+        // the separate buffer starts at the top left corner of the widget, and ends where the visible part ends
+        let offset_x = visible.x as i32 - self.area.x;
+        let offset_y = visible.y as i32 - self.area.y;
+        let mut clipped = Buffer::empty(Rect::new(
+            0,
+            0,
+            (offset_x as u16).saturating_add(visible.width),
+            (offset_y as u16).saturating_add(visible.height),
+        ));
+
+        // copy what is already on the screen, so transparent parts of the widget blend with it
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                let (clipped_x, clipped_y) = (
+                    (x as i32 - self.area.x) as u16,
+                    (y as i32 - self.area.y) as u16,
+                );
+                *clipped.get_mut(clipped_x, clipped_y) = buf.get(x, y).clone();
+            }
+        }
+
+        let area = Rect::new(0, 0, self.area.width, self.area.height);
+        self.widget.render(area, &mut clipped);
+
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                let (clipped_x, clipped_y) = (
+                    (x as i32 - self.area.x) as u16,
+                    (y as i32 - self.area.y) as u16,
+                );
+                *buf.get_mut(x, y) = clipped.get(clipped_x, clipped_y).clone();
+            }
+        }
+    }
+}
+
+/// The scrollbar of an element with `overflow: scroll` or `overflow: auto`
+struct Scrollbar {
+    viewport: Viewport,
+    offset: u16,
+    color: Option<RinkColor>,
+}
+
+impl RinkWidget for Scrollbar {
+    fn render(self, area: Rect, mut buf: RinkBuffer) {
+        // the thumb is as much smaller than the track as the visible part is smaller than the content
+        let track = area.height as u32;
+        let content = self.viewport.rows as u32 + self.viewport.max_offset as u32;
+        let thumb = (track * self.viewport.rows as u32)
+            .checked_div(content)
+            .unwrap_or(track)
+            .clamp(1, track.max(1));
+        let thumb_start = ((track - thumb) * self.offset as u32)
+            .checked_div(self.viewport.max_offset as u32)
+            .unwrap_or(0);
+
+        for i in 0..area.height {
+            let mut new_cell = RinkCell::default();
+            if let Some(c) = self.color {
+                new_cell.fg = c;
+            }
+            let in_thumb = (thumb_start..thumb_start + thumb).contains(&(i as u32));
+            new_cell.symbol = if in_thumb {
+                tui::symbols::block::FULL
+            } else {
+                tui::symbols::line::VERTICAL
+            }
+            .to_string();
+            buf.set(area.left(), area.top() + i, new_cell);
+        }
+    }
+}
+
+/// Dims the cells around an element that are covered by its shadow
+struct Shadow<'a> {
+    shadow: &'a BoxShadow,
+    /// the area of the element, relative to the area of the shadow
+    element: ScreenArea,
+}
+
+impl RinkWidget for Shadow<'_> {
+    fn render(self, area: Rect, mut buf: RinkBuffer) {
+        let fade = self.shadow.fade() as i32;
+        let (width, height) = (area.width as i32, area.height as i32);
+        let element = self.element;
+        for y in 0..height {
+            for x in 0..width {
+                let in_element = (element.x..element.x + element.width as i32).contains(&x)
+                    && (element.y..element.y + element.height as i32).contains(&y);
+                if in_element {
+                    continue;
+                }
+                // how far the cell is outside of the solid part of the shadow
+                let distance_x = (fade - x).max(x - (width - 1 - fade)).max(0);
+                let distance_y = (fade - y).max(y - (height - 1 - fade)).max(0);
+                let coverage = self.shadow.coverage(distance_x.max(distance_y) as u16);
+                if coverage > 0.0 {
+                    let new_cell = RinkCell {
+                        bg: RinkColor {
+                            alpha: (self.shadow.color.alpha as f32 * coverage) as u8,
+                            ..self.shadow.color
+                        },
+                        ..RinkCell::default()
+                    };
+                    buf.set(area.left() + x as u16, area.top() + y as u16, new_cell);
+                }
+            }
+        }
+    }
+}
+
+/// The background and borders of an element
+struct ElementWidget<'a> {
+    node: NodeRef<'a>,
+    /// the style the element is shown with in this frame
+    style: RinkStyle,
+    animated: Inherited,
+    /// the parent collapses borders, so the borders of the element join the borders it overlaps
+    joins_borders: bool,
+}
+
+impl RinkWidget for ElementWidget<'_> {
     fn render(self, area: Rect, mut buf: RinkBuffer<'_>) {
         use tui::symbols::line::*;
 
@@ -266,14 +775,34 @@ impl RinkWidget for NodeRef<'_> {
             return;
         }
 
+        let style = self.node.get::<StyleModifier>().unwrap();
+
+        // the lines drawn on the edges of the element before it, which its borders join
+        let mut overlapped = Vec::new();
+        if self.joins_borders {
+            for y in area.top()..area.bottom() {
+                let on_edge = y == area.top() || y == area.bottom() - 1;
+                for x in area.left()..area.right() {
+                    if on_edge || x == area.left() || x == area.right() - 1 {
+                        overlapped.push((x, y, buf.symbol(x, y).to_string()));
+                    }
+                }
+            }
+        }
+
         // todo: only render inside borders
         for x in area.left()..area.right() {
             for y in area.top()..area.bottom() {
                 let mut new_cell = RinkCell::default();
-                if let Some(c) = self.get::<StyleModifier>().unwrap().core.bg {
+                if let Some(c) = self.style.bg {
                     new_cell.bg = c;
                 }
-                if let Some(focused) = self.get::<Focused>() {
+                // gradients are painted over the background color
+                if let BackgroundPaint::LinearGradient(gradient) = &style.modifier.background {
+                    new_cell.bg =
+                        gradient.color_at(x - area.left(), y - area.top(), area.width, area.height);
+                }
+                if let Some(focused) = self.node.get::<Focused>() {
                     if focused.0 {
                         new_cell.bg.alpha = 100;
                         new_cell.bg.color = new_cell.bg.blend(Color::White);
@@ -283,8 +812,6 @@ impl RinkWidget for NodeRef<'_> {
             }
         }
 
-        let style = self.get::<StyleModifier>().unwrap();
-
         let borders = &style.modifier.borders;
 
         let last_edge = &borders.left;
@@ -302,7 +829,10 @@ impl RinkWidget for NodeRef<'_> {
                 (last_r * RADIUS_MULTIPLIER[0]) as u16,
                 (last_r * RADIUS_MULTIPLIER[1]) as u16,
             ];
-            let color = current_edge.color.or(style.core.fg);
+            let color = current_edge
+                .color
+                .map(|c| self.animated.fade(c))
+                .or(self.style.fg);
             let mut new_cell = RinkCell::default();
             if let Some(c) = color {
                 new_cell.fg = c;
@@ -337,7 +867,10 @@ impl RinkWidget for NodeRef<'_> {
                 (last_r * RADIUS_MULTIPLIER[0]) as u16,
                 (last_r * RADIUS_MULTIPLIER[1]) as u16,
             ];
-            let color = current_edge.color.or(style.core.fg);
+            let color = current_edge
+                .color
+                .map(|c| self.animated.fade(c))
+                .or(self.style.fg);
             let mut new_cell = RinkCell::default();
             if let Some(c) = color {
                 new_cell.fg = c;
@@ -372,7 +905,10 @@ impl RinkWidget for NodeRef<'_> {
                 (last_r * RADIUS_MULTIPLIER[0]) as u16,
                 (last_r * RADIUS_MULTIPLIER[1]) as u16,
             ];
-            let color = current_edge.color.or(style.core.fg);
+            let color = current_edge
+                .color
+                .map(|c| self.animated.fade(c))
+                .or(self.style.fg);
             let mut new_cell = RinkCell::default();
             if let Some(c) = color {
                 new_cell.fg = c;
@@ -407,7 +943,10 @@ impl RinkWidget for NodeRef<'_> {
                 (last_r * RADIUS_MULTIPLIER[0]) as u16,
                 (last_r * RADIUS_MULTIPLIER[1]) as u16,
             ];
-            let color = current_edge.color.or(style.core.fg);
+            let color = current_edge
+                .color
+                .map(|c| self.animated.fade(c))
+                .or(self.style.fg);
             let mut new_cell = RinkCell::default();
             if let Some(c) = color {
                 new_cell.fg = c;
@@ -426,5 +965,115 @@ impl RinkWidget for NodeRef<'_> {
                 &color,
             );
         }
+
+        for (x, y, previous) in overlapped {
+            if let Some(joined) = join_lines(&previous, buf.symbol(x, y)) {
+                buf.set_symbol(x, y, joined);
+            }
+        }
     }
 }
+
+/// The line drawn on one side of a cell
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Line {
+    None,
+    Light,
+    Double,
+}
+
+/// The lines box drawing characters draw from the center of the cell to its top, right, bottom and left side. The
+/// rounded and dashed lines are only read, lines are joined into solid lines
+const BOX_DRAWING: &[(&str, [Line; 4])] = {
+    use Line::{Double as D, Light as L, None as N};
+    &[
+        ("─", [N, L, N, L]),
+        ("│", [L, N, L, N]),
+        ("┌", [N, L, L, N]),
+        ("┐", [N, N, L, L]),
+        ("└", [L, L, N, N]),
+        ("┘", [L, N, N, L]),
+        ("├", [L, L, L, N]),
+        ("┤", [L, N, L, L]),
+        ("┬", [N, L, L, L]),
+        ("┴", [L, L, N, L]),
+        ("┼", [L, L, L, L]),
+        ("═", [N, D, N, D]),
+        ("║", [D, N, D, N]),
+        ("╔", [N, D, D, N]),
+        ("╗", [N, N, D, D]),
+        ("╚", [D, D, N, N]),
+        ("╝", [D, N, N, D]),
+        ("╠", [D, D, D, N]),
+        ("╣", [D, N, D, D]),
+        ("╦", [N, D, D, D]),
+        ("╩", [D, D, N, D]),
+        ("╬", [D, D, D, D]),
+        ("╒", [N, D, L, N]),
+        ("╓", [N, L, D, N]),
+        ("╕", [N, N, L, D]),
+        ("╖", [N, N, D, L]),
+        ("╘", [L, D, N, N]),
+        ("╙", [D, L, N, N]),
+        ("╛", [L, N, N, D]),
+        ("╜", [D, N, N, L]),
+        ("╞", [L, D, L, N]),
+        ("╟", [D, L, D, N]),
+        ("╡", [L, N, L, D]),
+        ("╢", [D, N, D, L]),
+        ("╤", [N, D, L, D]),
+        ("╥", [N, L, D, L]),
+        ("╧", [L, D, N, D]),
+        ("╨", [D, L, N, L]),
+        ("╪", [L, D, L, D]),
+        ("╫", [D, L, D, L]),
+        ("╭", [N, L, L, N]),
+        ("╮", [N, N, L, L]),
+        ("╯", [L, N, N, L]),
+        ("╰", [L, L, N, N]),
+        ("╌", [N, L, N, L]),
+        ("┈", [N, L, N, L]),
+        ("╎", [L, N, L, N]),
+        ("┊", [L, N, L, N]),
+    ]
+};
+
+/// Join two overlapping box drawing characters into the junction that draws the lines of both of them, like `┐` and
+/// `┌` into `┬`. Where both draw a line on the same side, the line of `previous` is kept, so the borders of a table
+/// keep their style where the borders of its cells meet them.
+/// Returns [`None`] if either symbol is not a line, or there is no character for the junction
+fn join_lines(previous: &str, next: &str) -> Option<&'static str> {
+    let lines = |symbol: &str| {
+        BOX_DRAWING
+            .iter()
+            .find(|(s, _)| *s == symbol)
+            .map(|(_, lines)| *lines)
+    };
+    let mut joined = lines(previous)?;
+    let next = lines(next)?;
+    for (line, next) in joined.iter_mut().zip(next) {
+        if *line == Line::None {
+            *line = next;
+        }
+    }
+    BOX_DRAWING
+        .iter()
+        .find(|(_, lines)| *lines == joined)
+        .map(|(symbol, _)| *symbol)
+}
+
+#[test]
+fn joins_box_drawing_lines() {
+    assert_eq!(join_lines("┐", "┌"), Some("┬"));
+    assert_eq!(join_lines("─", "│"), Some("┼"));
+    assert_eq!(join_lines("┤", "├"), Some("┼"));
+    assert_eq!(join_lines("╯", "╰"), Some("┴"));
+    assert_eq!(join_lines("═", "│"), Some("╪"));
+    assert_eq!(join_lines("╗", "╔"), Some("╦"));
+    // the lines that were drawn first keep their style
+    assert_eq!(join_lines("╔", "┌"), Some("╔"));
+    assert_eq!(join_lines("═", "┐"), Some("╤"));
+    // there is no junction of a light and a double horizontal line
+    assert_eq!(join_lines("┘", "╚"), None);
+    assert_eq!(join_lines("a", "│"), None);
+}
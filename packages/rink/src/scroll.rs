@@ -0,0 +1,153 @@
+//! Scrolling of elements with `overflow: scroll` or `overflow: auto`. Elements can only be scrolled vertically, one
+//! row at a time.
+
+use dioxus_native_core::{prelude::*, real_dom::NodeImmutable, tree::TreeRef};
+use shipyard::Component;
+use taffy::{
+    prelude::{LengthPercentage, Node},
+    Taffy,
+};
+
+use crate::{
+    layout::{Positioning, TaffyLayout},
+    layout_to_screen_space,
+};
+
+/// The number of rows an element is scrolled down. Elements that were never scrolled don't have this component.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) struct ScrollOffset(pub u16);
+
+/// How far to scroll an element
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum ScrollDelta {
+    /// Scroll by a number of rows, negative values scroll up
+    Rows(i32),
+    /// Scroll by a number of pages, a page is the height of the visible part of the element
+    Pages(i32),
+    /// Scroll to the top
+    Start,
+    /// Scroll to the bottom
+    End,
+}
+
+/// The visible part of a scrollable element
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Viewport {
+    /// The number of rows that are visible
+    pub rows: u16,
+    /// The number of rows the element can be scrolled down
+    pub max_offset: u16,
+}
+
+impl Viewport {
+    /// Measure the visible part of an element, ignoring whether it is scrollable
+    pub fn of(node: NodeRef, taffy: &Taffy) -> Self {
+        fn points(length: LengthPercentage) -> f32 {
+            match length {
+                LengthPercentage::Points(points) => points,
+                // percentages can't be resolved after the layout is computed
+                LengthPercentage::Percent(_) => 0.0,
+            }
+        }
+
+        let taffy_node = taffy_node(node);
+        let style = taffy.style(taffy_node).unwrap();
+        let height = taffy.layout(taffy_node).unwrap().size.height;
+        let top = points(style.border.top) + points(style.padding.top);
+        let bottom = points(style.border.bottom) + points(style.padding.bottom);
+
+        let content_bottom = taffy
+            .children(taffy_node)
+            .unwrap()
+            .into_iter()
+            .map(|child| {
+                let layout = taffy.layout(child).unwrap();
+                layout.location.y + layout.size.height
+            })
+            .fold(top, f32::max);
+
+        Self {
+            rows: layout_to_screen_space(height - top - bottom)
+                .round()
+                .max(0.0) as u16,
+            max_offset: layout_to_screen_space(content_bottom + bottom - height)
+                .ceil()
+                .max(0.0) as u16,
+        }
+    }
+}
+
+fn taffy_node(node: NodeRef) -> Node {
+    node.get::<TaffyLayout>().unwrap().node.unwrap()
+}
+
+/// The number of rows an element is scrolled down. The content of the element may have shrunk since it was scrolled,
+/// so the offset is limited to the current size of the content.
+pub(crate) fn scroll_offset(node: NodeRef, taffy: &Taffy) -> u16 {
+    match node.get::<ScrollOffset>() {
+        Some(offset) if node.get::<TaffyLayout>().unwrap().overflow.scrolls() => {
+            offset.0.min(Viewport::of(node, taffy).max_offset)
+        }
+        _ => 0,
+    }
+}
+
+/// Scroll the innermost scrollable element that contains the node and can be scrolled further in that direction.
+/// Returns true if an element was scrolled.
+pub(crate) fn scroll(rdom: &mut RealDom, taffy: &Taffy, id: NodeId, delta: ScrollDelta) -> bool {
+    let mut current = Some(id);
+    while let Some(id) = current {
+        let node = rdom.get(id).unwrap();
+        current = rdom.tree_ref().parent_id_advanced(id, true);
+        if !node.get::<TaffyLayout>().unwrap().overflow.scrolls() {
+            continue;
+        }
+
+        let viewport = Viewport::of(node, taffy);
+        let offset = scroll_offset(node, taffy);
+        let new_offset = match delta {
+            ScrollDelta::Rows(rows) => offset as i32 + rows,
+            ScrollDelta::Pages(pages) => offset as i32 + pages * viewport.rows.max(1) as i32,
+            ScrollDelta::Start => 0,
+            ScrollDelta::End => viewport.max_offset as i32,
+        }
+        .clamp(0, viewport.max_offset as i32) as u16;
+
+        if new_offset != offset {
+            rdom.get_mut(id).unwrap().insert(ScrollOffset(new_offset));
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns true if the point is outside of an element that clips the node. Points are in screen space.
+pub(crate) fn is_clipped(node: NodeRef, taffy: &Taffy, x: f32, y: f32) -> bool {
+    let rdom = node.real_dom();
+    let tree = rdom.tree_ref();
+    let mut current = tree.parent_id_advanced(node.id(), true);
+    // the ancestors of fixed nodes don't clip them
+    if node.get::<TaffyLayout>().unwrap().position == Positioning::Fixed {
+        current = None;
+    }
+    while let Some(id) = current {
+        let ancestor = rdom.get(id).unwrap();
+        let ancestor_layout = ancestor.get::<TaffyLayout>().unwrap();
+        current = tree
+            .parent_id_advanced(id, true)
+            .filter(|_| ancestor_layout.position != Positioning::Fixed);
+        if !ancestor_layout.overflow.clips() {
+            continue;
+        }
+
+        let layout = crate::get_abs_layout(ancestor, taffy);
+        let left = layout_to_screen_space(layout.location.x).round();
+        let top = layout_to_screen_space(layout.location.y).round();
+        let right = layout_to_screen_space(layout.location.x + layout.size.width).round();
+        let bottom = layout_to_screen_space(layout.location.y + layout.size.height).round();
+        if x < left || x >= right || y < top || y >= bottom {
+            return true;
+        }
+    }
+    false
+}
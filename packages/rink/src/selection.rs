@@ -0,0 +1,95 @@
+use taffy::geometry::Point;
+use tui::{buffer::Buffer, layout::Rect, style::Modifier, widgets::Widget};
+
+/// A region of text selected on the terminal grid, in cell coordinates relative to the top left corner of the app.
+///
+/// Like in most terminals, the selection flows like text: it covers the rest of the first row from `start`, every row
+/// in between, and the last row up to and including `end`. `end` may come before `start` if the selection was made
+/// backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub start: Point<u16>,
+    pub end: Point<u16>,
+}
+
+impl Selection {
+    pub fn new(start: Point<u16>, end: Point<u16>) -> Self {
+        Self { start, end }
+    }
+
+    /// The first and last selected cells in reading order
+    pub fn ordered(&self) -> (Point<u16>, Point<u16>) {
+        if (self.start.y, self.start.x) <= (self.end.y, self.end.x) {
+            (self.start, self.end)
+        } else {
+            (self.end, self.start)
+        }
+    }
+
+    /// Check if the cell at the given column and row is selected
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        let (first, last) = self.ordered();
+        (first.y, first.x) <= (y, x) && (y, x) <= (last.y, last.x)
+    }
+
+    /// Read the selected text from a rendered screen. Trailing whitespace is removed from every row.
+    pub(crate) fn text(&self, buffer: &Buffer) -> String {
+        let area = buffer.area();
+        let (first, last) = self.ordered();
+        let rows = first.y..=last.y.min(area.height.saturating_sub(1));
+
+        let lines: Vec<_> = rows
+            .map(|y| {
+                let mut line = String::new();
+                for x in 0..area.width {
+                    if self.contains(x, y) {
+                        line.push_str(&buffer.get(area.x + x, area.y + y).symbol);
+                    }
+                }
+                line.trim_end().to_string()
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// Highlights a selection by inverting the colors of the selected cells
+pub(crate) struct Highlight(pub Selection);
+
+impl Widget for Highlight {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if self.0.contains(x, y) {
+                    let cell = buf.get_mut(area.x + x, area.y + y);
+                    cell.modifier.toggle(Modifier::REVERSED);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn reads_selected_text() {
+    use tui::{layout::Rect, style::Style};
+
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 3));
+    buffer.set_string(0, 0, "hello", Style::default());
+    buffer.set_string(0, 1, "big", Style::default());
+    buffer.set_string(0, 2, "world", Style::default());
+
+    let point = |x, y| Point { x, y };
+    // the selection flows like text from the start to the end
+    let selection = Selection::new(point(2, 0), point(1, 2));
+    assert_eq!(selection.text(&buffer), "llo\nbig\nwo");
+    // selecting backwards reads the same text
+    let backwards = Selection::new(point(1, 2), point(2, 0));
+    assert_eq!(backwards.text(&buffer), "llo\nbig\nwo");
+
+    assert_eq!(Selection::new(point(1, 1), point(1, 1)).text(&buffer), "i");
+    // rows past the end of the screen are ignored
+    assert_eq!(
+        Selection::new(point(3, 2), point(0, 10)).text(&buffer),
+        "ld"
+    );
+}
@@ -0,0 +1,102 @@
+//! Draw the app into an in-memory grid instead of a terminal, for example to compare it with a snapshot in a test.
+
+use std::fmt::{Display, Write};
+
+use tui::{buffer::Buffer, style::Modifier};
+
+use crate::record::write_style;
+
+/// The cells of a frame drawn without a terminal. See [`crate::render_to_grid`].
+///
+/// The grid is displayed as the text of every row, without trailing whitespace. Use
+/// [`TerminalGrid::to_ansi_string`] to include the colors and modifiers of the cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerminalGrid {
+    pub(crate) buffer: Buffer,
+}
+
+impl TerminalGrid {
+    /// The number of columns in the grid.
+    pub fn width(&self) -> u16 {
+        self.buffer.area.width
+    }
+
+    /// The number of rows in the grid.
+    pub fn height(&self) -> u16 {
+        self.buffer.area.height
+    }
+
+    /// The symbol drawn in the cell at the given column and row.
+    pub fn symbol(&self, x: u16, y: u16) -> &str {
+        &self.buffer.get(x, y).symbol
+    }
+
+    /// The text of every row with ansi escape codes for the colors and modifiers of the cells, like the grid would be
+    /// printed to a terminal.
+    pub fn to_ansi_string(&self) -> String {
+        let area = self.buffer.area;
+        let mut output = String::new();
+        for y in area.top()..area.bottom() {
+            if y != area.top() {
+                output.push('\n');
+            }
+            let mut style = None;
+            for x in area.left()..area.right() {
+                let cell = self.buffer.get(x, y);
+                let cell_style = (cell.fg, cell.bg, cell.modifier);
+                if style != Some(cell_style) {
+                    write_style(cell_style, &mut output);
+                    style = Some(cell_style);
+                }
+                output.push_str(&cell.symbol);
+            }
+            // reset the style at the end of every row, so the rows can be printed on their own
+            if style.is_some() {
+                output.push_str("\x1b[0m");
+            }
+        }
+        output
+    }
+}
+
+impl Display for TerminalGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let area = self.buffer.area;
+        for y in area.top()..area.bottom() {
+            if y != area.top() {
+                f.write_char('\n')?;
+            }
+            let mut line = String::new();
+            for x in area.left()..area.right() {
+                let cell = self.buffer.get(x, y);
+                // hidden cells are drawn without their text
+                if cell.modifier.contains(Modifier::HIDDEN) {
+                    line.push(' ');
+                } else {
+                    line.push_str(&cell.symbol);
+                }
+            }
+            f.write_str(line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn displays_rows_without_trailing_whitespace() {
+    use tui::{
+        layout::Rect,
+        style::{Color, Style},
+    };
+
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+    buffer.set_string(0, 0, "hi", Style::default().fg(Color::Red));
+    buffer.set_string(1, 1, "there", Style::default());
+    let grid = TerminalGrid { buffer };
+
+    assert_eq!(grid.to_string(), "hi\n there");
+    assert_eq!(
+        grid.to_ansi_string(),
+        "\x1b[0;31mhi\x1b[0m    \x1b[0m\n\x1b[0m there\x1b[0m"
+    );
+}
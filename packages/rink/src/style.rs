@@ -2,7 +2,7 @@ use std::{num::ParseFloatError, str::FromStr};
 
 use tui::style::{Color, Modifier, Style};
 
-use crate::RenderingMode;
+use crate::{Palette, RenderingMode};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct RinkColor {
@@ -297,7 +297,7 @@ impl FromStr for RinkColor {
     }
 }
 
-fn to_rgb(c: Color) -> [u8; 3] {
+pub(crate) fn to_rgb(c: Color) -> [u8; 3] {
     match c {
         Color::Black => [0, 0, 0],
         Color::Red => [255, 0, 0],
@@ -350,6 +350,7 @@ pub fn convert(mode: RenderingMode, c: Color) -> Color {
                 let rgb = to_rgb(c);
                 Color::Rgb(rgb[0], rgb[1], rgb[2])
             }
+            crate::RenderingMode::NoColor => Color::Reset,
             crate::RenderingMode::Ansi => match c {
                 Color::Indexed(_) => c,
                 _ => {
@@ -372,6 +373,74 @@ pub fn convert(mode: RenderingMode, c: Color) -> Color {
     }
 }
 
+/// Apply a palette to a color. This should happen before the color is blended or converted.
+pub fn remap(palette: Palette, c: Color) -> Color {
+    // Simulates each color blindness on a rgb color (Machado et al. 2009, full severity)
+    const PROTANOPIA: [[f32; 3]; 3] = [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ];
+    const DEUTERANOPIA: [[f32; 3]; 3] = [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ];
+    const TRITANOPIA: [[f32; 3]; 3] = [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ];
+    // Shifts the information lost to the color blindness into the channels that can still be seen
+    const RED_GREEN_SHIFT: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]];
+    const BLUE_YELLOW_SHIFT: [[f32; 3]; 3] = [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]];
+
+    fn mul(m: [[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+        m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+    }
+
+    fn daltonize(rgb: [u8; 3], simulate: [[f32; 3]; 3], shift: [[f32; 3]; 3]) -> [u8; 3] {
+        let rgb = rgb.map(|c| c as f32);
+        let simulated = mul(simulate, rgb);
+        let error = [0, 1, 2].map(|i| rgb[i] - simulated[i]);
+        let shifted = mul(shift, error);
+        [0, 1, 2].map(|i| (rgb[i] + shifted[i]).clamp(0.0, 255.0) as u8)
+    }
+
+    if let Color::Reset = c {
+        return c;
+    }
+    let rgb = to_rgb(c);
+    let [r, g, b] = match palette {
+        Palette::Default => return c,
+        Palette::Deuteranopia => daltonize(rgb, DEUTERANOPIA, RED_GREEN_SHIFT),
+        Palette::Protanopia => daltonize(rgb, PROTANOPIA, RED_GREEN_SHIFT),
+        Palette::Tritanopia => daltonize(rgb, TRITANOPIA, BLUE_YELLOW_SHIFT),
+        Palette::Custom(remap) => remap(rgb),
+    };
+    Color::Rgb(r, g, b)
+}
+
+#[test]
+fn daltonize_keeps_grays() {
+    for palette in [
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+    ] {
+        for l in [0, 128, 255] {
+            let [r, g, b] = to_rgb(remap(palette, Color::Rgb(l, l, l)));
+            assert!(r.abs_diff(l) <= 1 && g.abs_diff(l) <= 1 && b.abs_diff(l) <= 1);
+        }
+        assert_ne!(remap(palette, Color::Rgb(255, 0, 0)), Color::Rgb(255, 0, 0));
+    }
+    assert_eq!(remap(Palette::Default, Color::Red), Color::Red);
+    assert_eq!(
+        remap(Palette::Custom(|[r, g, b]| [b, g, r]), Color::Rgb(1, 2, 3)),
+        Color::Rgb(3, 2, 1)
+    );
+}
+
 #[test]
 fn rgb_to_ansi() {
     for idx in 17..=231 {
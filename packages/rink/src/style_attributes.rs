@@ -30,6 +30,7 @@
 */
 
 use dioxus_native_core::{
+    animation::{AnimatedStyle, Animation},
     layout_attributes::parse_value,
     node::OwnedAttributeView,
     node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView},
@@ -39,7 +40,11 @@ use dioxus_native_core_macro::partial_derive_state;
 use shipyard::Component;
 use taffy::prelude::*;
 
-use crate::style::{RinkColor, RinkStyle};
+use crate::{
+    animation::Transitions,
+    paint::{BackgroundPaint, BoxShadow},
+    style::{RinkColor, RinkStyle},
+};
 
 #[derive(Default, Clone, PartialEq, Debug, Component)]
 pub struct StyleModifier {
@@ -51,17 +56,18 @@ pub struct StyleModifier {
 impl State for StyleModifier {
     type ParentDependencies = (Self,);
     type ChildDependencies = ();
-    type NodeDependencies = ();
+    type NodeDependencies = (AnimatedStyle,);
 
     // todo: seperate each attribute into it's own class
     const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new()
         .with_attrs(AttributeMaskBuilder::Some(SORTED_STYLE_ATTRS))
-        .with_element();
+        .with_element()
+        .with_pseudo_classes();
 
     fn update<'a>(
         &mut self,
         node_view: NodeView,
-        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        (animated,): <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
         parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
         _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
         _: &SendAnyMap,
@@ -90,6 +96,15 @@ impl State for StyleModifier {
             }
         }
 
+        // validation errors in forms are red unless the element sets its own color
+        if let Some(mut attrs) = node_view.attributes() {
+            if attrs
+                .any(|attr| attr.attribute.name == "role" && attr.value.as_text() == Some("alert"))
+            {
+                apply_style_attributes("color", "red", &mut new);
+            }
+        }
+
         // gather up all the styles from the attribute list
         if let Some(attrs) = node_view.attributes() {
             for OwnedAttributeView {
@@ -102,6 +117,31 @@ impl State for StyleModifier {
             }
         }
 
+        // styles like `hover:color` override the other styles while the pseudo-class applies to the element
+        if let Some(classes) = node_view.pseudo_classes() {
+            for class in classes.iter() {
+                if let Some(attrs) = node_view.attributes() {
+                    for OwnedAttributeView {
+                        attribute, value, ..
+                    } in attrs
+                    {
+                        let name = attribute
+                            .name
+                            .strip_prefix(class.name())
+                            .and_then(|name| name.strip_prefix(':'));
+                        if let (Some(name), Some(text)) = (name, value.as_text()) {
+                            apply_style_attributes(name, text, &mut new);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the keyframes an element plays override all of its other styles
+        for (name, value) in animated.values() {
+            apply_style_attributes(name, value, &mut new);
+        }
+
         // keep the text styling from the parent element
         if let Some((parent,)) = parent {
             let mut new_style = new.core.merge(parent.core);
@@ -132,6 +172,10 @@ impl State for StyleModifier {
 #[derive(Default, Clone, PartialEq, Debug)]
 pub struct TuiModifier {
     pub borders: Borders,
+    pub background: BackgroundPaint,
+    pub shadow: Option<BoxShadow>,
+    pub transitions: Transitions,
+    pub animation: Animation,
 }
 
 #[derive(Default, Clone, PartialEq, Debug)]
@@ -279,7 +323,7 @@ pub fn apply_style_attributes(
         | "border-width" => apply_border(name, value, style),
 
         "bottom" => {}
-        "box-shadow" => {}
+        "box-shadow" => style.modifier.shadow = BoxShadow::parse(value),
         "box-sizing" => {}
         "caption-side" => {}
         "clear" => {}
@@ -359,10 +403,18 @@ fn apply_background(name: &str, value: &str, style: &mut StyleModifier) {
                 style.core.bg.replace(c);
             }
         }
-        "background" => {}
+        "background" => {
+            if let Some(paint) = BackgroundPaint::parse(value) {
+                style.modifier.background = paint;
+            } else if let Ok(c) = value.parse() {
+                style.core.bg.replace(c);
+            }
+        }
         "background-attachment" => {}
         "background-clip" => {}
-        "background-image" => {}
+        "background-image" => {
+            style.modifier.background = BackgroundPaint::parse(value).unwrap_or_default();
+        }
         "background-origin" => {}
         "background-position" => {}
         "background-repeat" => {}
@@ -554,19 +606,8 @@ fn apply_border(name: &str, value: &str, style: &mut StyleModifier) {
     }
 }
 
-fn apply_animation(name: &str, _value: &str, _style: &mut StyleModifier) {
-    match name {
-        "animation" => {}
-        "animation-delay" => {}
-        "animation-direction =>{}" => {}
-        "animation-duration" => {}
-        "animation-fill-mode" => {}
-        "animation-itera =>{}tion-count" => {}
-        "animation-name" => {}
-        "animation-play-state" => {}
-        "animation-timing-function" => {}
-        _ => {}
-    }
+fn apply_animation(name: &str, value: &str, style: &mut StyleModifier) {
+    style.modifier.animation.apply(name, value);
 }
 
 fn apply_font(name: &str, value: &str, style: &mut StyleModifier) {
@@ -596,7 +637,8 @@ fn apply_text(name: &str, value: &str, style: &mut StyleModifier) {
     use tui::style::Modifier;
 
     match name {
-        "text-align" => todo!(),
+        // text-align and text-overflow are part of the layout of text
+        "text-align" | "text-overflow" => (),
         "text-align-last" => todo!(),
         "text-decoration" | "text-decoration-line" => {
             for v in value.split(' ') {
@@ -611,18 +653,25 @@ fn apply_text(name: &str, value: &str, style: &mut StyleModifier) {
         "text-decoration-style" => todo!(),
         "text-indent" => todo!(),
         "text-justify" => todo!(),
-        "text-overflow" => todo!(),
         "text-shadow" => todo!(),
         "text-transform" => todo!(),
         _ => todo!(),
     }
 }
 
-fn apply_transition(_name: &str, _value: &str, _style: &mut StyleModifier) {
-    todo!()
+fn apply_transition(name: &str, value: &str, style: &mut StyleModifier) {
+    style.modifier.transitions.apply(name, value);
 }
 
 const SORTED_STYLE_ATTRS: &[&str] = &[
+    "active:background",
+    "active:background-color",
+    "active:border-color",
+    "active:border-style",
+    "active:color",
+    "active:font-style",
+    "active:font-weight",
+    "active:text-decoration",
     "animation",
     "animation-delay",
     "animation-direction",
@@ -642,6 +691,7 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "background-position",
     "background-repeat",
     "background-size",
+    "bold",
     "border",
     "border-bottom",
     "border-bottom-color",
@@ -687,8 +737,27 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "counter-increment",
     "counter-reset",
     "cursor",
+    "dashed",
+    "disabled:background",
+    "disabled:background-color",
+    "disabled:border-color",
+    "disabled:border-style",
+    "disabled:color",
+    "disabled:font-style",
+    "disabled:font-weight",
+    "disabled:text-decoration",
+    "dotted",
+    "double",
     "empty-cells",
     "float",
+    "focus:background",
+    "focus:background-color",
+    "focus:border-color",
+    "focus:border-style",
+    "focus:color",
+    "focus:font-style",
+    "focus:font-weight",
+    "focus:text-decoration",
     "font",
     "font-family",
     "font-size",
@@ -697,12 +766,28 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "font-style",
     "font-variant",
     "font-weight",
+    "groove",
+    "hidden",
+    "hover:background",
+    "hover:background-color",
+    "hover:border-color",
+    "hover:border-style",
+    "hover:color",
+    "hover:font-style",
+    "hover:font-weight",
+    "hover:text-decoration",
+    "inset",
+    "italic",
     "letter-spacing",
     "line-height",
+    "line-through",
     "list-style",
     "list-style-image",
     "list-style-position",
     "list-style-type",
+    "none",
+    "normal",
+    "oblique",
     "opacity",
     "order",
     "outline",
@@ -710,6 +795,7 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "outline-offset",
     "outline-style",
     "outline-width",
+    "outset",
     "page-break-after",
     "page-break-before",
     "page-break-inside",
@@ -718,6 +804,9 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "pointer-events",
     "quotes",
     "resize",
+    "ridge",
+    "role",
+    "solid",
     "tab-size",
     "table-layout",
     "text-align",
@@ -736,92 +825,12 @@ const SORTED_STYLE_ATTRS: &[&str] = &[
     "transition-duration",
     "transition-property",
     "transition-timing-function",
+    "underline",
     "visibility",
     "white-space",
-    "background-color",
-    "background",
-    "background-attachment",
-    "background-clip",
-    "background-image",
-    "background-origin",
-    "background-position",
-    "background-repeat",
-    "background-size",
-    "dotted",
-    "dashed",
-    "solid",
-    "double",
-    "groove",
-    "ridge",
-    "inset",
-    "outset",
-    "none",
-    "hidden",
-    "border",
-    "border-bottom",
-    "border-bottom-color",
-    "border-bottom-left-radius",
-    "border-bottom-right-radius",
-    "border-bottom-style",
-    "border-bottom-width",
-    "border-collapse",
-    "border-color",
-    "border-image",
-    "border-image-outset",
-    "border-image-repeat",
-    "border-image-slice",
-    "border-image-source",
-    "border-image-width",
-    "border-left",
-    "border-left-color",
-    "border-left-style",
-    "border-left-width",
-    "border-radius",
-    "border-right",
-    "border-right-color",
-    "border-right-style",
-    "border-right-width",
-    "border-spacing",
-    "border-style",
-    "border-top",
-    "border-top-color",
-    "border-top-left-radius",
-    "border-top-right-radius",
-    "border-top-style",
-    "border-top-width",
-    "border-width",
-    "animation",
-    "animation-delay",
-    "animation-direction",
-    "animation-duration",
-    "animation-fill-mode",
-    "animation-itera ",
-    "animation-name",
-    "animation-play-state",
-    "animation-timing-function",
-    "font",
-    "font-family",
-    "font-size",
-    "font-size-adjust",
-    "font-stretch",
-    "font-style",
-    "italic",
-    "oblique",
-    "font-variant",
-    "font-weight",
-    "bold",
-    "normal",
-    "text-align",
-    "text-align-last",
-    "text-decoration",
-    "text-decoration-line",
-    "line-through",
-    "underline",
-    "text-decoration-color",
-    "text-decoration-style",
-    "text-indent",
-    "text-justify",
-    "text-overflow",
-    "text-shadow",
-    "text-transform",
 ];
+
+#[test]
+fn style_attrs_are_sorted() {
+    assert!(SORTED_STYLE_ATTRS.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,74 @@
+//! Detection of what the terminal can draw besides text
+
+/// How images are drawn to the terminal
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GraphicsProtocol {
+    /// the [kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/), supported by kitty, WezTerm
+    /// and Ghostty
+    Kitty,
+    /// sixel graphics, supported by foot, mlterm, contour, iTerm2 and xterm if it is started with `-ti vt340`
+    Sixel,
+    /// two pixels per cell drawn with the `▀` character, works in any terminal with colors
+    #[default]
+    HalfBlocks,
+}
+
+impl GraphicsProtocol {
+    /// Pick the graphics protocol from the environment of the terminal.
+    ///
+    /// Terminals are recognized by `TERM`, `TERM_PROGRAM` and `KITTY_WINDOW_ID`. Inside of tmux or screen, images are
+    /// always drawn with [`GraphicsProtocol::HalfBlocks`], because the multiplexer doesn't pass the graphics to the
+    /// terminal.
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        let term = var("TERM");
+        let term_program = var("TERM_PROGRAM");
+
+        if std::env::var_os("TMUX").is_some() || term.starts_with("screen") {
+            Self::HalfBlocks
+        } else if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || term_program == "WezTerm"
+            || term_program == "ghostty"
+        {
+            Self::Kitty
+        } else if ["foot", "mlterm", "contour", "yaft"]
+            .iter()
+            .any(|sixel_term| term.starts_with(sixel_term))
+            || term_program == "iTerm.app"
+        {
+            Self::Sixel
+        } else {
+            Self::HalfBlocks
+        }
+    }
+}
+
+/// The size of a cell in pixels as `(width, height)`. Terminals that don't report their size in pixels are assumed to
+/// use a common font size.
+pub(crate) fn cell_size() -> (u16, u16) {
+    const FALLBACK: (u16, u16) = (10, 20);
+
+    #[cfg(unix)]
+    {
+        let mut size = libc::winsize {
+            ws_row: 0,
+            ws_col: 0,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        // Safety: TIOCGWINSZ only writes to the winsize struct
+        let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ as _, &mut size) };
+        if result == 0
+            && size.ws_row > 0
+            && size.ws_col > 0
+            && size.ws_xpixel > 0
+            && size.ws_ypixel > 0
+        {
+            return (size.ws_xpixel / size.ws_col, size.ws_ypixel / size.ws_row);
+        }
+    }
+
+    FALLBACK
+}
@@ -0,0 +1,478 @@
+//! Wrap and align text nodes with the `white-space`, `text-align` and `text-overflow` styles of their element.
+
+use dioxus_native_core::{
+    node::OwnedAttributeView,
+    node_ref::{AttributeMaskBuilder, NodeMaskBuilder, NodeView},
+    prelude::*,
+};
+use dioxus_native_core_macro::partial_derive_state;
+use shipyard::Component;
+use taffy::prelude::{AvailableSpace, Size};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{layout_to_screen_space, screen_to_layout_space};
+
+/// How whitespace in text is collapsed and where lines wrap, set with `white-space`
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum WhiteSpace {
+    /// Whitespace is collapsed and lines wrap
+    #[default]
+    Normal,
+    /// Whitespace is collapsed and lines never wrap
+    NoWrap,
+    /// Whitespace and newlines are kept and lines only break at newlines
+    Pre,
+    /// Whitespace and newlines are kept and lines wrap
+    PreWrap,
+    /// Whitespace is collapsed, newlines are kept and lines wrap
+    PreLine,
+}
+
+impl WhiteSpace {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "normal" => Some(Self::Normal),
+            "nowrap" => Some(Self::NoWrap),
+            "pre" => Some(Self::Pre),
+            "pre-wrap" | "break-spaces" => Some(Self::PreWrap),
+            "pre-line" => Some(Self::PreLine),
+            _ => None,
+        }
+    }
+
+    fn collapses_spaces(self) -> bool {
+        matches!(self, Self::Normal | Self::NoWrap | Self::PreLine)
+    }
+
+    fn keeps_newlines(self) -> bool {
+        matches!(self, Self::Pre | Self::PreWrap | Self::PreLine)
+    }
+
+    fn wraps(self) -> bool {
+        matches!(self, Self::Normal | Self::PreWrap | Self::PreLine)
+    }
+}
+
+/// How the lines of text are aligned in the width of their element, set with `text-align`
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretch the spaces between words so wrapped lines fill the width. The last line is aligned to the left
+    Justify,
+}
+
+impl TextAlign {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "left" | "start" => Some(Self::Left),
+            "center" => Some(Self::Center),
+            "right" | "end" => Some(Self::Right),
+            "justify" => Some(Self::Justify),
+            _ => None,
+        }
+    }
+}
+
+/// What is shown when a line of text is wider than its element, set with `text-overflow`
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub(crate) enum TextOverflow {
+    /// The line is cut off
+    #[default]
+    Clip,
+    /// The end of the line is replaced with `…`
+    Ellipsis,
+}
+
+/// A line of text after wrapping
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Line {
+    pub text: String,
+    /// the line was wrapped, rather than ending at a newline or the end of the text
+    pub wrapped: bool,
+}
+
+#[derive(Clone, PartialEq, Default, Debug, Component)]
+pub(crate) struct TextLayout {
+    pub white_space: WhiteSpace,
+    pub align: TextAlign,
+    pub overflow: TextOverflow,
+}
+
+#[partial_derive_state]
+impl State for TextLayout {
+    type ParentDependencies = (Self,);
+    type ChildDependencies = ();
+    type NodeDependencies = ();
+
+    const NODE_MASK: NodeMaskBuilder<'static> = NodeMaskBuilder::new()
+        .with_attrs(AttributeMaskBuilder::Some(&[
+            "text-align",
+            "text-overflow",
+            "white-space",
+        ]))
+        .with_element()
+        .with_text();
+
+    fn update<'a>(
+        &mut self,
+        node_view: NodeView,
+        _: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        _: &SendAnyMap,
+    ) -> bool {
+        let mut new = match parent {
+            // text is laid out with the styles of its element
+            Some((parent,)) if node_view.text().is_some() => parent.clone(),
+            // only text-overflow is not inherited
+            Some((parent,)) => TextLayout {
+                white_space: parent.white_space,
+                align: parent.align,
+                overflow: TextOverflow::default(),
+            },
+            None => TextLayout::default(),
+        };
+
+        if let Some(attrs) = node_view.attributes() {
+            for OwnedAttributeView {
+                attribute, value, ..
+            } in attrs
+            {
+                let Some(value) = value.as_text() else {
+                    continue;
+                };
+                match &*attribute.name {
+                    "white-space" => {
+                        if let Some(white_space) = WhiteSpace::parse(value) {
+                            new.white_space = white_space;
+                        }
+                    }
+                    "text-align" => {
+                        if let Some(align) = TextAlign::parse(value) {
+                            new.align = align;
+                        }
+                    }
+                    "text-overflow" => {
+                        new.overflow = match value.trim() {
+                            "ellipsis" => TextOverflow::Ellipsis,
+                            _ => TextOverflow::Clip,
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if new != *self {
+            *self = new;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn create<'a>(
+        node_view: NodeView<()>,
+        node: <Self::NodeDependencies as Dependancy>::ElementBorrowed<'a>,
+        parent: Option<<Self::ParentDependencies as Dependancy>::ElementBorrowed<'a>>,
+        children: Vec<<Self::ChildDependencies as Dependancy>::ElementBorrowed<'a>>,
+        context: &SendAnyMap,
+    ) -> Self {
+        let mut myself = Self::default();
+        myself.update(node_view, node, parent, children, context);
+        myself
+    }
+}
+
+impl TextLayout {
+    /// Split the text into the lines it is shown in, wrapping lines that are wider than `width` cells if the text
+    /// wraps. Words that are wider than the width are not split.
+    pub fn lines(&self, text: &str, width: Option<usize>) -> Vec<Line> {
+        let width = width.filter(|_| self.white_space.wraps());
+        let paragraphs: Vec<&str> = if self.white_space.keeps_newlines() {
+            text.split('\n').collect()
+        } else {
+            vec![text]
+        };
+
+        let mut lines = Vec::new();
+        for paragraph in paragraphs {
+            let paragraph = if self.white_space.collapses_spaces() {
+                collapse_whitespace(paragraph)
+            } else {
+                paragraph.to_string()
+            };
+            match width {
+                Some(width) => wrap(&paragraph, width, &mut lines),
+                None => lines.push(Line {
+                    text: paragraph,
+                    wrapped: false,
+                }),
+            }
+        }
+        lines
+    }
+
+    /// The size of the text in layout space, for the measure function of taffy
+    pub fn measure(
+        &self,
+        text: &str,
+        known: Size<Option<f32>>,
+        available: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        let max_width = known.width.or(match available.width {
+            AvailableSpace::Definite(width) => Some(width),
+            // wrap at every opportunity, so the text is as wide as its widest word
+            AvailableSpace::MinContent => Some(0.0),
+            AvailableSpace::MaxContent => None,
+        });
+        let max_width = max_width.map(|width| layout_to_screen_space(width).floor() as usize);
+        let lines = self.lines(text, max_width);
+        let width = lines
+            .iter()
+            .map(|line| text_width(&line.text))
+            .max()
+            .unwrap_or_default();
+
+        Size {
+            width: known
+                .width
+                .unwrap_or_else(|| screen_to_layout_space(width as u16)),
+            height: known
+                .height
+                .unwrap_or_else(|| screen_to_layout_space(lines.len() as u16)),
+        }
+    }
+
+    /// Align a line in the width of the text node. Returns the column the line starts at and the text of the line
+    pub fn align_line(&self, line: &Line, width: usize) -> (usize, String) {
+        let line_width = text_width(&line.text);
+        if line_width > width {
+            let text = match self.overflow {
+                TextOverflow::Clip => line.text.clone(),
+                TextOverflow::Ellipsis if width == 0 => String::new(),
+                TextOverflow::Ellipsis => {
+                    let mut text = String::new();
+                    let mut shown_width = 0;
+                    for (grapheme, grapheme_width) in graphemes(&line.text) {
+                        if shown_width + grapheme_width > width - 1 {
+                            break;
+                        }
+                        text.push_str(grapheme);
+                        shown_width += grapheme_width;
+                    }
+                    text.push('…');
+                    text
+                }
+            };
+            return (0, text);
+        }
+
+        let extra = width - line_width;
+        match self.align {
+            TextAlign::Left => (0, line.text.clone()),
+            TextAlign::Center => (extra / 2, line.text.clone()),
+            TextAlign::Right => (extra, line.text.clone()),
+            TextAlign::Justify if !line.wrapped => (0, line.text.clone()),
+            TextAlign::Justify => (0, justify(&line.text, extra)),
+        }
+    }
+}
+
+/// The number of cells the text takes up
+pub(crate) fn text_width(text: &str) -> usize {
+    graphemes(text).map(|(_, width)| width).sum()
+}
+
+/// Split text into the graphemes that are drawn in a cell each, with the number of cells they take up. Wide glyphs
+/// like CJK characters and emoji take up two cells, and combining marks are kept with the char they are drawn over.
+pub(crate) fn graphemes(text: &str) -> impl Iterator<Item = (&str, usize)> {
+    // sequences joined into a single emoji are as wide as that emoji, not as all of their parts
+    text.graphemes(true)
+        .map(|grapheme| (grapheme, grapheme.width().min(2)))
+}
+
+/// The number of chars before the grapheme drawn at a column of the text. Columns past the end of the text are at the
+/// end of the text
+pub(crate) fn char_index_at_column(text: &str, column: usize) -> usize {
+    let mut chars = 0;
+    let mut end = 0;
+    for (grapheme, grapheme_width) in graphemes(text) {
+        end += grapheme_width;
+        if end > column {
+            break;
+        }
+        chars += grapheme.chars().count();
+    }
+    chars
+}
+
+/// The byte offset of a char index of the text. Indexes past the end of the text are at the end of the text
+pub(crate) fn byte_index(text: &str, char_idx: usize) -> usize {
+    text.char_indices()
+        .nth(char_idx)
+        .map_or(text.len(), |(idx, _)| idx)
+}
+
+/// The number of chars in the grapheme after, or before, a char index of the text
+pub(crate) fn grapheme_len_at(text: &str, char_idx: usize, forward: bool) -> usize {
+    let (before, after) = text.split_at(byte_index(text, char_idx));
+    let grapheme = if forward {
+        after.graphemes(true).next()
+    } else {
+        before.graphemes(true).next_back()
+    };
+    grapheme.map_or(0, |grapheme| grapheme.chars().count())
+}
+
+/// Replace every run of whitespace with a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                collapsed.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            collapsed.push(c);
+            last_was_space = false;
+        }
+    }
+    collapsed
+}
+
+/// Wrap a paragraph at the spaces between words. Spaces at the end of wrapped lines are dropped
+fn wrap(paragraph: &str, width: usize, lines: &mut Vec<Line>) {
+    let mut line = String::new();
+    let mut line_width = 0;
+    for word in paragraph.split_inclusive(' ') {
+        let word_width = text_width(word.trim_end_matches(' '));
+        if line_width + word_width > width && !line.trim_end().is_empty() {
+            lines.push(Line {
+                text: line.trim_end().to_string(),
+                wrapped: true,
+            });
+            line.clear();
+            line_width = 0;
+        }
+        line.push_str(word);
+        line_width += text_width(word);
+    }
+    lines.push(Line {
+        text: line,
+        wrapped: false,
+    });
+}
+
+/// Spread `extra` spaces over the gaps between the words of a line
+fn justify(line: &str, extra: usize) -> String {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let gaps = line[indent..].matches(' ').count();
+    if gaps == 0 {
+        return line.to_string();
+    }
+
+    let mut justified = String::with_capacity(line.len() + extra);
+    let mut gap = 0;
+    for (i, c) in line.char_indices() {
+        justified.push(c);
+        if c == ' ' && i >= indent {
+            // the first gaps get the spaces that don't divide evenly
+            let spaces = extra / gaps + usize::from(gap < extra % gaps);
+            justified.extend(std::iter::repeat(' ').take(spaces));
+            gap += 1;
+        }
+    }
+    justified
+}
+
+#[test]
+fn wraps_and_aligns_text() {
+    let layout = TextLayout::default();
+    let lines = layout.lines("the  quick\nbrown fox jumps", Some(10));
+    let text: Vec<_> = lines.iter().map(|line| line.text.as_str()).collect();
+    assert_eq!(text, ["the quick", "brown fox", "jumps"]);
+
+    let pre = TextLayout {
+        white_space: WhiteSpace::Pre,
+        ..Default::default()
+    };
+    let text: Vec<_> = pre
+        .lines("a  b\nc", Some(1))
+        .into_iter()
+        .map(|line| line.text)
+        .collect();
+    assert_eq!(text, ["a  b", "c"]);
+
+    let justify = TextLayout {
+        align: TextAlign::Justify,
+        ..Default::default()
+    };
+    assert_eq!(
+        justify.align_line(&lines[0], 12),
+        (0, "the    quick".to_string())
+    );
+    assert_eq!(justify.align_line(&lines[2], 12), (0, "jumps".to_string()));
+
+    let center = TextLayout {
+        align: TextAlign::Center,
+        ..Default::default()
+    };
+    assert_eq!(center.align_line(&lines[2], 10), (2, "jumps".to_string()));
+
+    let ellipsis = TextLayout {
+        white_space: WhiteSpace::NoWrap,
+        overflow: TextOverflow::Ellipsis,
+        ..Default::default()
+    };
+    let line = &ellipsis.lines("hello world", Some(5))[0];
+    assert_eq!(ellipsis.align_line(line, 5), (0, "hell…".to_string()));
+}
+
+#[test]
+fn measures_wide_glyphs_and_graphemes() {
+    // CJK characters take up two cells, and combining marks none
+    assert_eq!(text_width("中文"), 4);
+    assert_eq!(text_width("e\u{301}a"), 2);
+    assert_eq!(text_width("👨\u{200d}👩\u{200d}👧"), 2);
+    let graphemes: Vec<_> = graphemes("a中e\u{301}").collect();
+    assert_eq!(graphemes, [("a", 1), ("中", 2), ("e\u{301}", 1)]);
+
+    // both halves of a wide glyph are at the same char
+    assert_eq!(char_index_at_column("a中b", 1), 1);
+    assert_eq!(char_index_at_column("a中b", 2), 1);
+    assert_eq!(char_index_at_column("a中b", 3), 2);
+    assert_eq!(char_index_at_column("a中b", 10), 3);
+
+    // the cursor steps over combining marks and joined emoji at once
+    assert_eq!(byte_index("a中b", 2), 4);
+    assert_eq!(byte_index("a中b", 10), 5);
+    assert_eq!(grapheme_len_at("ae\u{301}b", 1, true), 2);
+    assert_eq!(grapheme_len_at("ae\u{301}b", 3, false), 2);
+    assert_eq!(grapheme_len_at("👨\u{200d}👩", 0, true), 3);
+    assert_eq!(grapheme_len_at("ab", 2, true), 0);
+    assert_eq!(grapheme_len_at("ab", 0, false), 0);
+
+    let layout = TextLayout::default();
+    let text: Vec<_> = layout
+        .lines("你好 世界", Some(5))
+        .into_iter()
+        .map(|line| line.text)
+        .collect();
+    assert_eq!(text, ["你好", "世界"]);
+
+    // wide glyphs are never cut in half by the ellipsis
+    let ellipsis = TextLayout {
+        white_space: WhiteSpace::NoWrap,
+        overflow: TextOverflow::Ellipsis,
+        ..Default::default()
+    };
+    let line = &ellipsis.lines("你好世界", Some(4))[0];
+    assert_eq!(ellipsis.align_line(line, 4), (0, "你…".to_string()));
+}
@@ -6,8 +6,9 @@ use tui::{
 };
 
 use crate::{
-    style::{convert, RinkColor, RinkStyle},
-    Config,
+    style::{convert, remap, RinkColor, RinkStyle},
+    text::text_width,
+    Config, RenderingMode,
 };
 
 pub struct RinkBuffer<'a> {
@@ -20,23 +21,89 @@ impl<'a> RinkBuffer<'a> {
         Self { buf, cfg }
     }
 
+    /// Draw a cell. A wide glyph also covers the cells after it, which are left without a symbol of their own
     pub fn set(&mut self, x: u16, y: u16, new: RinkCell) {
         let area = self.buf.area();
         if x < area.x || x >= area.width + area.x || y < area.y || y >= area.height + area.y {
             // panic!("({x}, {y}) is not in {area:?}");
             return;
         }
+        let new = RinkCell {
+            fg: self.remap(new.fg),
+            bg: self.remap(new.bg),
+            ..new
+        };
+        let width = text_width(&new.symbol).max(1) as u16;
+        // a wide glyph that would be cut off by the edge of the buffer is not drawn
+        let draw_symbol =
+            !new.symbol.is_empty() && x as usize + width as usize <= area.right() as usize;
+        if draw_symbol {
+            for covered in x..x + width {
+                self.erase_wide_glyph(covered, y);
+            }
+        }
+
         let cell = self.buf.get_mut(x, y);
         cell.bg = convert(self.cfg.rendering_mode, new.bg.blend(cell.bg));
-        if new.symbol.is_empty() {
+        if !draw_symbol {
             if !cell.symbol.is_empty() {
                 // allows text to "shine through" transparent backgrounds
                 cell.fg = convert(self.cfg.rendering_mode, new.bg.blend(cell.fg));
             }
-        } else {
-            cell.modifier = new.modifier;
-            cell.symbol = new.symbol;
-            cell.fg = convert(self.cfg.rendering_mode, new.fg.blend(cell.bg));
+            return;
+        }
+        cell.modifier = new.modifier;
+        cell.symbol = new.symbol;
+        cell.fg = convert(self.cfg.rendering_mode, new.fg.blend(cell.bg));
+        for covered in x + 1..x + width {
+            let cell = self.buf.get_mut(covered, y);
+            cell.bg = convert(self.cfg.rendering_mode, new.bg.blend(cell.bg));
+            cell.symbol.clear();
+        }
+    }
+
+    /// Replace the wide glyph that covers a cell with spaces, so drawing over one half of the glyph doesn't leave the
+    /// other half behind
+    fn erase_wide_glyph(&mut self, x: u16, y: u16) {
+        let area = self.buf.area();
+        // the cells covered by a wide glyph have no symbol, so the glyph is the first cell before them with one
+        let mut start = x;
+        while start > area.left() && self.buf.get(start, y).symbol.is_empty() {
+            start -= 1;
+        }
+        let width = text_width(&self.buf.get(start, y).symbol) as u16;
+        if width > 1 && start + width > x {
+            for covered in start..(start + width).min(area.right()) {
+                self.buf.get_mut(covered, y).symbol = " ".to_string();
+            }
+        }
+    }
+
+    /// The symbol drawn in a cell, or an empty string if the cell is outside of the buffer
+    pub fn symbol(&self, x: u16, y: u16) -> &str {
+        let area = self.buf.area();
+        if x < area.x || x >= area.width + area.x || y < area.y || y >= area.height + area.y {
+            return "";
+        }
+        &self.buf.get(x, y).symbol
+    }
+
+    /// Replace the symbol drawn in a cell without changing its colors
+    pub fn set_symbol(&mut self, x: u16, y: u16, symbol: &str) {
+        let area = self.buf.area();
+        if x < area.x || x >= area.width + area.x || y < area.y || y >= area.height + area.y {
+            return;
+        }
+        self.buf.get_mut(x, y).symbol = symbol.to_string();
+    }
+
+    fn remap(&self, color: RinkColor) -> RinkColor {
+        match self.cfg.rendering_mode {
+            RenderingMode::Rgb | RenderingMode::Ansi => RinkColor {
+                color: remap(self.cfg.palette, color.color),
+                ..color
+            },
+            RenderingMode::BaseColors | RenderingMode::NoColor => color,
         }
     }
 }
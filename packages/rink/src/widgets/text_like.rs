@@ -1,20 +1,24 @@
-use std::{collections::HashMap, io::stdout};
+use std::collections::HashMap;
 
-use crossterm::{cursor::MoveTo, execute};
-use dioxus_html::{input_data::keyboard_types::Key, KeyboardData, MouseData};
+use dioxus_html::{
+    input_data::keyboard_types::{Code, Key, Modifiers},
+    KeyboardData, MouseData,
+};
 use dioxus_native_core::{
     custom_element::CustomElement,
     node::OwnedAttributeDiscription,
     node_ref::AttributeMask,
     prelude::{ElementNode, NodeType},
     real_dom::{ElementNodeMut, NodeImmutable, NodeMut, NodeTypeMut, RealDom},
-    utils::cursor::{Cursor, Pos},
+    utils::cursor::{Cursor, Pos, TextEditable},
     NodeId,
 };
 use shipyard::UniqueView;
-use taffy::geometry::Point;
 
-use crate::{query::get_layout, Event, EventData, FormData, Query};
+use crate::{
+    text::{byte_index, char_index_at_column, grapheme_len_at, graphemes},
+    ClipboardData, Event, EventData, FormData,
+};
 
 use super::{RinkWidget, WidgetContext};
 
@@ -38,6 +42,9 @@ pub(crate) struct TextLike<C: TextLikeController = EmptyController> {
     post_cursor_text: NodeId,
     cursor: Cursor,
     dragging: bool,
+    focused: bool,
+    /// The text when the input was last focused or changed, a change event is sent when the text differs from it
+    committed: String,
     border: bool,
     max_len: Option<usize>,
     controller: C,
@@ -120,26 +127,41 @@ impl<C: TextLikeController> TextLike<C> {
             .and_then(|value| value.as_text())
             .map(|value| value.to_string())
         {
+            self.committed = value.clone();
             self.text = value;
         }
     }
 
     pub(crate) fn set_text(&mut self, text: String, rdom: &mut RealDom, id: NodeId) {
         self.text = text;
-        self.write_value(rdom, id);
+        self.write_value(rdom);
+        self.send_event(rdom, id, "input");
     }
 
     pub(crate) fn text(&self) -> &str {
         self.text.as_str()
     }
 
-    fn write_value(&self, rdom: &mut RealDom, id: NodeId) {
-        let start_highlight = self.cursor.first().idx(self.text.as_str());
-        let end_highlight = self.cursor.last().idx(self.text.as_str());
+    fn write_value(&self, rdom: &mut RealDom) {
+        // The cursor is at a char index, and the text is split at byte offsets
+        let start_highlight = byte_index(&self.text, self.cursor.first().idx(self.text.as_str()));
+        let mut end_highlight = byte_index(&self.text, self.cursor.last().idx(self.text.as_str()));
+        // Without a selection, the focused input highlights the grapheme under the cursor
+        let show_cursor = self.focused && start_highlight == end_highlight;
+        if show_cursor {
+            end_highlight += graphemes(&self.text[start_highlight..])
+                .next()
+                .map_or(0, |(grapheme, _)| grapheme.len());
+        }
         let (text_before_first_cursor, text_after_first_cursor) =
             self.text.split_at(start_highlight);
         let (text_highlighted, text_after_second_cursor) =
             text_after_first_cursor.split_at(end_highlight - start_highlight);
+        let mut text_highlighted = self.controller.display_text(text_highlighted);
+        if show_cursor && text_highlighted.is_empty() {
+            // The cursor is after the last character
+            text_highlighted.push(' ');
+        }
 
         if let Some(mut text) = rdom.get_mut(self.pre_cursor_text) {
             let node_type = text.node_type_mut();
@@ -154,7 +176,7 @@ impl<C: TextLikeController> TextLike<C> {
             let NodeTypeMut::Text(mut text) = node_type else {
                 panic!("input must be an element")
             };
-            *text.text_mut() = self.controller.display_text(text_highlighted);
+            *text.text_mut() = text_highlighted;
         }
 
         if let Some(mut text) = rdom.get_mut(self.post_cursor_text) {
@@ -164,23 +186,31 @@ impl<C: TextLikeController> TextLike<C> {
             };
             *text.text_mut() = self.controller.display_text(text_after_second_cursor);
         }
+    }
 
-        // send the event
-        {
-            let world = rdom.raw_world_mut();
-            let data: FormData = FormData {
-                value: self.text.clone(),
-                values: HashMap::new(),
-                files: None,
-            };
-            let ctx: UniqueView<WidgetContext> = world.borrow().expect("expected widget context");
-
-            ctx.send(Event {
-                id,
-                name: "input",
-                data: EventData::Form(data),
-                bubbles: true,
-            });
+    /// Send a form event with the current text
+    fn send_event(&self, rdom: &mut RealDom, id: NodeId, name: &'static str) {
+        let world = rdom.raw_world_mut();
+        let data: FormData = FormData {
+            value: self.text.clone(),
+            values: HashMap::new(),
+            files: None,
+        };
+        let ctx: UniqueView<WidgetContext> = world.borrow().expect("expected widget context");
+
+        ctx.send(Event {
+            id,
+            name,
+            data: EventData::Form(data),
+            bubbles: true,
+        });
+    }
+
+    /// Send a change event if the text changed since the last change event
+    fn commit(&mut self, rdom: &mut RealDom, id: NodeId) {
+        if self.text != self.committed {
+            self.committed = self.text.clone();
+            self.send_event(rdom, id, "change");
         }
     }
 
@@ -189,99 +219,105 @@ impl<C: TextLikeController> TextLike<C> {
         let modifiers = data.modifiers();
         let code = data.code();
 
+        let id = root.id();
+        let rdom = root.real_dom_mut();
+
         if key == Key::Enter {
+            self.commit(rdom, id);
             return;
         }
-        self.cursor.handle_input(
-            &code,
-            &key,
-            &modifiers,
-            &mut self.text,
-            self.max_len.unwrap_or(1000),
-        );
+        let old_text = self.text.clone();
+        let forward = code == Code::ArrowRight;
+        if (forward || code == Code::ArrowLeft) && !modifiers.contains(Modifiers::CONTROL) {
+            // The cursor steps over a whole grapheme, so a combining mark is never left on its own
+            let text = self.text.as_str();
+            self.cursor.move_cursor(
+                |pos| {
+                    let chars = grapheme_len_at(text, pos.idx(text), forward) as i32;
+                    pos.move_col(if forward { chars } else { -chars }, text);
+                },
+                modifiers.contains(Modifiers::SHIFT),
+            );
+        } else {
+            self.cursor.handle_input(
+                &code,
+                &key,
+                &modifiers,
+                &mut self.text,
+                self.max_len.unwrap_or(1000),
+            );
+        }
 
-        let id = root.id();
+        self.write_value(rdom);
+        if self.text != old_text {
+            self.send_event(rdom, id, "input");
+        }
+    }
 
+    fn handle_paste(&mut self, mut root: NodeMut, data: &ClipboardData) {
+        let pasted = &data.text;
+        let id = root.id();
         let rdom = root.real_dom_mut();
-        self.write_value(rdom, id);
-        let world = rdom.raw_world_mut();
-
-        // move cursor to new position
-        let taffy = {
-            let query: UniqueView<Query> = world.borrow().unwrap();
-            query.stretch.clone()
-        };
-
-        let taffy = taffy.lock().unwrap();
-
-        let layout = get_layout(rdom.get(self.div_wrapper).unwrap(), &taffy).unwrap();
-        let Point { x, y } = layout.location;
 
-        let Pos { col, row } = self.cursor.start;
-        let (x, y) = (col as u16 + x as u16, row as u16 + y as u16);
-        if let Ok(pos) = crossterm::cursor::position() {
-            if pos != (x, y) {
-                execute!(stdout(), MoveTo(x, y)).unwrap();
+        let old_text = self.text.clone();
+        let max_len = self.max_len.unwrap_or(1000);
+        self.cursor.start.realize_col(self.text.as_str());
+        self.cursor.delete_selection(&mut self.text);
+        // inputs are one line tall, so the lines of the pasted text are joined
+        for character in pasted.chars().filter(|c| !matches!(c, '\n' | '\r')) {
+            if self.text.chars().count() >= max_len {
+                break;
             }
-        } else {
-            execute!(stdout(), MoveTo(x, y)).unwrap();
+            let idx = self.cursor.start.idx(self.text.as_str());
+            self.text.insert_character(idx, character);
+            self.cursor.start.right(self.text.as_str());
+        }
+
+        self.write_value(rdom);
+        if self.text != old_text {
+            self.send_event(rdom, id, "input");
         }
     }
 
+    /// The position of the char drawn at a column of the input. Wide glyphs take up two columns, so the column is
+    /// not the same as the number of chars before it
+    fn pos_at_column(&self, column: usize) -> Pos {
+        let display_text = self.controller.display_text(&self.text);
+        // textboxs are only one line tall
+        Pos::new(char_index_at_column(&display_text, column), 0)
+    }
+
     fn handle_mousemove(&mut self, mut root: NodeMut, data: &MouseData) {
         if self.dragging {
-            let id = root.id();
             let offset = data.element_coordinates();
-            let mut new = Pos::new(offset.x as usize, offset.y as usize);
-
-            // textboxs are only one line tall
-            new.row = 0;
+            let new = self.pos_at_column(offset.x as usize);
 
             if new != self.cursor.start {
                 self.cursor.end = Some(new);
             }
-            let rdom = root.real_dom_mut();
-            self.write_value(rdom, id);
+            self.write_value(root.real_dom_mut());
         }
     }
 
     fn handle_mousedown(&mut self, mut root: NodeMut, data: &MouseData) {
         let offset = data.element_coordinates();
-        let mut new = Pos::new(offset.x as usize, offset.y as usize);
-
-        // textboxs are only one line tall
-        new.row = 0;
-
+        let mut new = self.pos_at_column(offset.x as usize);
         new.realize_col(self.text.as_str());
         self.cursor = Cursor::from_start(new);
         self.dragging = true;
 
-        let id = root.id();
+        self.write_value(root.real_dom_mut())
+    }
 
-        // move cursor to new position
+    fn handle_focus(&mut self, mut root: NodeMut, focused: bool) {
+        self.focused = focused;
+        self.dragging = false;
+        let id = root.id();
         let rdom = root.real_dom_mut();
-        let world = rdom.raw_world_mut();
-        let taffy = {
-            let query: UniqueView<Query> = world.borrow().unwrap();
-            query.stretch.clone()
-        };
-
-        let taffy = taffy.lock().unwrap();
-
-        let layout = get_layout(rdom.get(self.div_wrapper).unwrap(), &taffy).unwrap();
-        let Point { x, y } = layout.location;
-
-        let Pos { col, row } = self.cursor.start;
-        let (x, y) = (col as u16 + x as u16, row as u16 + y as u16);
-        if let Ok(pos) = crossterm::cursor::position() {
-            if pos != (x, y) {
-                execute!(stdout(), MoveTo(x, y)).unwrap();
-            }
-        } else {
-            execute!(stdout(), MoveTo(x, y)).unwrap();
+        if !focused {
+            self.commit(rdom, id);
         }
-
-        self.write_value(rdom, id)
+        self.write_value(rdom);
     }
 }
 
@@ -356,6 +392,8 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> CustomElement for
         div_wrapper.add_event_listener("mouseleave");
         div_wrapper.add_event_listener("mouseenter");
         root.add_event_listener("keydown");
+        root.add_event_listener("paste");
+        root.add_event_listener("focus");
         root.add_event_listener("focusout");
 
         Self {
@@ -364,6 +402,7 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> CustomElement for
             post_cursor_text: post_text_id,
             div_wrapper: div_wrapper_id,
             cursor: Cursor::default(),
+            committed: value.clone().unwrap_or_default(),
             text: value.unwrap_or_default(),
             ..Default::default()
         }
@@ -385,8 +424,7 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> CustomElement for
                     self.update_size_attr(&mut el);
                     self.update_max_width_attr(&el);
                 }
-                let id = root.id();
-                self.write_value(root.real_dom_mut(), id);
+                self.write_value(root.real_dom_mut());
             }
             AttributeMask::Some(attrs) => {
                 {
@@ -405,8 +443,7 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> CustomElement for
                     }
                 }
                 if attrs.contains("value") {
-                    let id = root.id();
-                    self.write_value(root.real_dom_mut(), id);
+                    self.write_value(root.real_dom_mut());
                 }
             }
         }
@@ -422,6 +459,12 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> RinkWidget for Tex
                 }
             }
 
+            "paste" => {
+                if let EventData::Clipboard(data) = &event.data {
+                    self.handle_paste(node, data);
+                }
+            }
+
             "mousemove" => {
                 if let EventData::Mouse(data) = &event.data {
                     self.handle_mousemove(node, data);
@@ -446,8 +489,12 @@ impl<C: TextLikeController + Send + Sync + Default + 'static> RinkWidget for Tex
                 self.dragging = false;
             }
 
+            "focus" => {
+                self.handle_focus(node, true);
+            }
+
             "focusout" => {
-                execute!(stdout(), MoveTo(0, 1000)).unwrap();
+                self.handle_focus(node, false);
             }
 
             _ => {}
@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Ident, Path};
+
+use crate::{
+    layout::{Layout, LayoutId},
+    meta::RouteMeta,
+    nest::Nest,
+};
+
+/// The variant that every path that doesn't match a route parses to
+#[derive(Debug)]
+pub(crate) struct Fallback {
+    pub route_name: Ident,
+    pub component: Path,
+    pub field: Ident,
+    pub layouts: Vec<LayoutId>,
+    pub meta: Option<RouteMeta>,
+}
+
+impl Fallback {
+    pub fn parse(
+        attr: &syn::Attribute,
+        layouts: Vec<LayoutId>,
+        variant: &syn::Variant,
+    ) -> syn::Result<Self> {
+        let route_name = variant.ident.clone();
+        let component = match &attr.meta {
+            syn::Meta::Path(_) => parse_quote!(#route_name),
+            _ => attr.parse_args()?,
+        };
+
+        let field = match &variant.fields {
+            syn::Fields::Named(fields) if fields.named.len() == 1 => {
+                fields.named[0].ident.clone().unwrap()
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "The fallback route must have a single named field that holds the path that did not match: `NotFound { path: String }`",
+                ))
+            }
+        };
+
+        Ok(Self {
+            route_name,
+            component,
+            field,
+            layouts,
+            meta: RouteMeta::parse_attrs(&variant.attrs)?,
+        })
+    }
+
+    pub fn construct(&self, enum_name: &Ident) -> TokenStream {
+        let name = &self.route_name;
+        let field = &self.field;
+
+        quote! {
+            #enum_name::#name {
+                #field: s.to_string().into(),
+            }
+        }
+    }
+
+    pub fn display_match(&self) -> TokenStream {
+        let name = &self.route_name;
+        let field = &self.field;
+
+        quote! {
+            Self::#name { #field } => {
+                write!(f, "{}", #field)?;
+            }
+        }
+    }
+
+    pub fn meta_match(&self) -> Option<TokenStream> {
+        let name = &self.route_name;
+        let meta = self.meta.as_ref()?.construct();
+
+        Some(quote! {
+            Self::#name { .. } => #meta,
+        })
+    }
+
+    pub fn routable_match(&self, layouts: &[Layout], nests: &[Nest]) -> TokenStream {
+        let name = &self.route_name;
+        let field = &self.field;
+        let component = &self.component;
+
+        let mut tokens = TokenStream::new();
+
+        for (idx, layout_id) in self.layouts.iter().copied().enumerate() {
+            let render_layout = layouts[layout_id.0].routable_match(nests);
+            tokens.extend(quote! {
+                #[allow(unused)]
+                (#idx, Self::#name { .. }) => {
+                    #render_layout
+                }
+            });
+        }
+
+        let last_index = self.layouts.len();
+        tokens.extend(quote! {
+            #[allow(unused)]
+            (#last_index, Self::#name { #field }) => {
+                render! {
+                    #component {
+                        #field: #field,
+                    }
+                }
+            }
+        });
+
+        tokens
+    }
+}
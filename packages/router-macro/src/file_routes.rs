@@ -0,0 +1,436 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse::Parse, Ident, LitStr, Token, Visibility};
+
+mod kw {
+    syn::custom_keyword!(fallback);
+}
+
+/// The arguments of `routes_from_dir!("src/pages", pub Route, fallback NotFound)`
+pub struct RoutesFromDir {
+    dir: LitStr,
+    vis: Visibility,
+    name: Ident,
+    fallback: Option<Ident>,
+}
+
+impl Parse for RoutesFromDir {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let dir = input.parse()?;
+        let mut vis = Visibility::Inherited;
+        let mut name = format_ident!("Route");
+        if input.parse::<Option<Token![,]>>()?.is_some()
+            && !input.is_empty()
+            && !(input.peek(kw::fallback) && input.peek2(Ident))
+        {
+            vis = input.parse()?;
+            name = input.parse()?;
+            let _ = input.parse::<Option<Token![,]>>()?;
+        }
+
+        let mut fallback = None;
+        if input.parse::<Option<kw::fallback>>()?.is_some() {
+            fallback = Some(input.parse()?);
+            let _ = input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self {
+            dir,
+            vis,
+            name,
+            fallback,
+        })
+    }
+}
+
+impl RoutesFromDir {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| self.error("CARGO_MANIFEST_DIR is not set"))?;
+        let path = Path::new(&manifest_dir).join(self.dir.value());
+        if !path.is_dir() {
+            return Err(self.error(format!("{} is not a directory", path.display())));
+        }
+
+        let module = module_ident(&file_name(&path));
+        let root = PageDir::read(self, path, module.clone(), None)?;
+
+        let mut variants = Vec::new();
+        let mut names = HashSet::new();
+        let mut pending = Vec::new();
+        root.variants(
+            self,
+            &RouteScope {
+                module_path: vec![module.clone()],
+                fields: Vec::new(),
+                name_parts: Vec::new(),
+            },
+            &mut pending,
+            &mut variants,
+            &mut names,
+        )?;
+
+        // The fallback goes first so it is outside of every layout and nest
+        let fallback = match &self.fallback {
+            Some(fallback) => {
+                if !names.insert(fallback.to_string()) {
+                    return Err(syn::Error::new(
+                        fallback.span(),
+                        format!("The fallback {fallback} has the same name as a page"),
+                    ));
+                }
+                quote! {
+                    #[fallback]
+                    #fallback { path: String },
+                }
+            }
+            None => quote! {},
+        };
+
+        let modules = root.modules();
+        let vis = &self.vis;
+        let name = &self.name;
+
+        Ok(quote! {
+            mod #module {
+                #modules
+            }
+
+            #[derive(Clone, Debug, PartialEq, dioxus_router::prelude::Routable)]
+            #vis enum #name {
+                #fallback
+                #(#variants)*
+            }
+        })
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new(self.dir.span(), message)
+    }
+}
+
+/// A segment of the route taken from the name of a file or directory
+enum Segment {
+    /// `about.rs` or `about/`
+    Static(String),
+    /// `[id].rs` or `[id]/`
+    Dynamic(String),
+    /// `[...segments].rs`
+    CatchAll(String),
+}
+
+impl Segment {
+    fn parse(name: &str) -> Self {
+        match name
+            .strip_prefix('[')
+            .and_then(|name| name.strip_suffix(']'))
+        {
+            Some(name) => match name.strip_prefix("...") {
+                Some(name) => Self::CatchAll(name.to_string()),
+                None => Self::Dynamic(name.to_string()),
+            },
+            None => Self::Static(name.to_string()),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Static(name) | Self::Dynamic(name) | Self::CatchAll(name) => name,
+        }
+    }
+
+    fn route(&self) -> String {
+        match self {
+            Self::Static(name) => format!("/{name}"),
+            Self::Dynamic(name) => format!("/:{}", module_ident(name)),
+            Self::CatchAll(name) => format!("/:..{}", module_ident(name)),
+        }
+    }
+
+    fn field(&self) -> Option<TokenStream> {
+        match self {
+            Self::Static(_) => None,
+            Self::Dynamic(name) => {
+                let name = module_ident(name);
+                Some(quote! { #name: String })
+            }
+            Self::CatchAll(name) => {
+                let name = module_ident(name);
+                Some(quote! { #name: Vec<String> })
+            }
+        }
+    }
+}
+
+/// A file in the pages directory
+struct PageFile {
+    path: PathBuf,
+    module: Ident,
+    /// The segment of the page, or `None` for `index.rs`
+    segment: Option<Segment>,
+}
+
+/// A directory of pages. Directories other than the root are nested routes.
+struct PageDir {
+    module: Ident,
+    /// The segment of the directory, or `None` for the root directory
+    segment: Option<Segment>,
+    layout: Option<PathBuf>,
+    pages: Vec<PageFile>,
+    /// Files that start with an underscore are not routes, but they are included as modules so pages can share code
+    private: Vec<(Ident, PathBuf)>,
+    dirs: Vec<PageDir>,
+}
+
+/// The nests and modules a directory is in
+struct RouteScope {
+    module_path: Vec<Ident>,
+    fields: Vec<TokenStream>,
+    name_parts: Vec<String>,
+}
+
+impl PageDir {
+    fn read(
+        args: &RoutesFromDir,
+        path: PathBuf,
+        module: Ident,
+        segment: Option<Segment>,
+    ) -> syn::Result<Self> {
+        let mut entries = std::fs::read_dir(&path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .map_err(|err| args.error(format!("Failed to read {}: {err}", path.display())))?;
+        // The order of the files decides the order of the variants, so sort them to get the same enum on every platform
+        entries.sort();
+
+        let mut dir = Self {
+            module,
+            segment,
+            layout: None,
+            pages: Vec::new(),
+            private: Vec::new(),
+            dirs: Vec::new(),
+        };
+        let mut modules = HashSet::new();
+
+        for entry in entries {
+            let name = file_name(&entry);
+            let segment = Segment::parse(&name);
+            let module = module_ident(segment.name());
+
+            if entry.is_dir() {
+                if let Segment::CatchAll(_) = segment {
+                    return Err(args.error(format!(
+                        "Catch-all segments are not allowed in nested routes: {}",
+                        entry.display()
+                    )));
+                }
+                let child = Self::read(args, entry.clone(), module.clone(), Some(segment))?;
+                if child.is_empty() {
+                    continue;
+                }
+                dir.dirs.push(child);
+            } else if entry.extension().is_some_and(|ext| ext == "rs") {
+                match name.as_str() {
+                    "_layout" => dir.layout = Some(entry.clone()),
+                    _ if name.starts_with('_') => dir.private.push((module.clone(), entry.clone())),
+                    "index" => dir.pages.push(PageFile {
+                        path: entry.clone(),
+                        module: module.clone(),
+                        segment: None,
+                    }),
+                    _ => dir.pages.push(PageFile {
+                        path: entry.clone(),
+                        module: module.clone(),
+                        segment: Some(segment),
+                    }),
+                }
+            } else {
+                continue;
+            }
+
+            if !modules.insert(module.to_string()) {
+                return Err(args.error(format!(
+                    "More than one page or directory in {} is named {module}",
+                    path.display()
+                )));
+            }
+        }
+
+        // index.rs is the first route of a directory
+        dir.pages.sort_by_key(|page| page.segment.is_some());
+
+        Ok(dir)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pages.is_empty() && self.dirs.iter().all(Self::is_empty)
+    }
+
+    fn modules(&self) -> TokenStream {
+        let layout = self
+            .layout
+            .as_ref()
+            .map(|path| (format_ident!("_layout"), path));
+        let files = self
+            .pages
+            .iter()
+            .map(|page| (page.module.clone(), &page.path))
+            .chain(
+                self.private
+                    .iter()
+                    .map(|(module, path)| (module.clone(), path)),
+            )
+            .chain(layout)
+            .map(|(module, path)| {
+                let path = path.to_string_lossy();
+                quote! {
+                    #[path = #path]
+                    pub mod #module;
+                }
+            });
+        let dirs = self.dirs.iter().map(|dir| {
+            let module = &dir.module;
+            let modules = dir.modules();
+            quote! {
+                pub mod #module {
+                    #modules
+                }
+            }
+        });
+
+        quote! {
+            #(#files)*
+            #(#dirs)*
+        }
+    }
+
+    // Add the variants of the directory. Nests and layouts are opened on the first variant of the directory, and closed
+    // on the variant after the directory.
+    fn variants(
+        &self,
+        args: &RoutesFromDir,
+        scope: &RouteScope,
+        pending: &mut Vec<TokenStream>,
+        variants: &mut Vec<TokenStream>,
+        names: &mut HashSet<String>,
+    ) -> syn::Result<()> {
+        if let Some(segment) = &self.segment {
+            let route = segment.route();
+            pending.push(quote! { #[nest(#route)] });
+        }
+        let module_path = &scope.module_path;
+        if self.layout.is_some() {
+            pending.push(quote! { #[layout(#(#module_path::)*_layout::Layout)] });
+        }
+
+        for page in &self.pages {
+            let mut name_parts = scope.name_parts.clone();
+            let mut fields = scope.fields.clone();
+            let route = match &page.segment {
+                Some(segment) => {
+                    name_parts.push(segment.name().to_string());
+                    fields.extend(segment.field());
+                    segment.route()
+                }
+                None => "/".to_string(),
+            };
+            let name = variant_name(&name_parts);
+            if !names.insert(name.to_string()) {
+                return Err(args.error(format!(
+                    "More than one page is named {name}, rename {} to give it a unique name",
+                    page.path.display()
+                )));
+            }
+
+            let module = &page.module;
+            let attrs = pending.drain(..);
+            variants.push(quote! {
+                #(#attrs)*
+                #[route(#route, #(#module_path::)*#module::Page)]
+                #name { #(#fields,)* },
+            });
+        }
+
+        for dir in &self.dirs {
+            let segment = dir.segment.as_ref().unwrap();
+            let mut module_path = scope.module_path.clone();
+            module_path.push(dir.module.clone());
+            let mut fields = scope.fields.clone();
+            fields.extend(segment.field());
+            let mut name_parts = scope.name_parts.clone();
+            name_parts.push(segment.name().to_string());
+
+            dir.variants(
+                args,
+                &RouteScope {
+                    module_path,
+                    fields,
+                    name_parts,
+                },
+                pending,
+                variants,
+                names,
+            )?;
+        }
+
+        if self.layout.is_some() {
+            pending.push(quote! { #[end_layout] });
+        }
+        if self.segment.is_some() {
+            pending.push(quote! { #[end_nest] });
+        }
+
+        Ok(())
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// Turn a file name into a valid module name
+fn module_ident(name: &str) -> Ident {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let name = match name.chars().next() {
+        Some(c) if c.is_numeric() => format!("_{name}"),
+        _ => name,
+    };
+    syn::parse_str(&name).unwrap_or_else(|_| Ident::new_raw(&name, Span::call_site()))
+}
+
+// The name of the variant is the path of the page in PascalCase: blog/[id].rs is BlogId
+fn variant_name(parts: &[String]) -> Ident {
+    if parts.is_empty() {
+        return format_ident!("Index");
+    }
+
+    let name: String = parts
+        .iter()
+        .flat_map(|part| part.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_uppercase();
+            first.chain(chars).collect::<String>()
+        })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_numeric() => format_ident!("Page{}", name),
+        Some(_) => format_ident!("{}", name),
+        None => format_ident!("Index"),
+    }
+}
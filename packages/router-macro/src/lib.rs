@@ -1,5 +1,6 @@
 extern crate proc_macro;
 
+use fallback::Fallback;
 use layout::Layout;
 use nest::{Nest, NestId};
 use proc_macro::TokenStream;
@@ -11,9 +12,12 @@ use syn::{parse::ParseStream, parse_macro_input, Ident, Token, Type};
 
 use proc_macro2::TokenStream as TokenStream2;
 
-use crate::{layout::LayoutId, route_tree::RouteTree};
+use crate::{file_routes::RoutesFromDir, layout::LayoutId, route_tree::RouteTree};
 
+mod fallback;
+mod file_routes;
 mod layout;
+mod meta;
 mod nest;
 mod query;
 mod redirect;
@@ -33,6 +37,7 @@ mod segment;
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
 /// 4. Query Segments: "/?:query" (where query has a type that is FromQuery in all child Variants)
+/// 5. Query Struct Segments: "/?:..query" (where query has a type that is QueryStruct in all child Variants, which requires the `serde-query` feature of dioxus-router)
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -122,6 +127,8 @@ mod segment;
 ///
 /// Nests effect all nests, routes and redirects defined until the next `#[end_nest]` attribute. All children of nests are relative to the nest route and must include all dynamic parameters of the nest.
 ///
+/// The route at the root of a nest (`"/"` inside of the nest) is an ancestor of all other routes in the nest, and shows up in the breadcrumbs of the router.
+///
 /// ```rust, skip
 /// #[derive(Clone, Debug, PartialEq, Routable)]
 /// enum Route {
@@ -193,9 +200,130 @@ mod segment;
 ///     Home {},
 /// }
 /// ```
+///
+/// # `#[guard(function)]`
+///
+/// The `#[guard]` attribute is used to define a guard. It takes 1 parameter:
+/// - `function`: A function that takes a reference to the route and returns a `GuardResult`
+///
+/// Guards effect all routes defined until the next `#[end_guard]` attribute, or the end of the nest or layout they were defined in. They run before the route is rendered, in the order they were defined, and can allow the route, block it, or redirect to another route. The first guard that does not allow the route decides what happens.
+///
+/// ```rust, skip
+/// fn check_auth(route: &Route) -> GuardResult {
+///     if logged_in() {
+///         GuardResult::Allow
+///     } else {
+///         GuardResult::redirect(Route::Login {})
+///     }
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/login")]
+///     Login {},
+///     #[nest("/admin")]
+///     #[guard(check_auth)]
+///         // check_auth runs before the Dashboard is rendered
+///         #[route("/")]
+///         Dashboard {},
+/// }
+/// ```
+///
+/// # `#[end_guard]`
+///
+/// The `#[end_guard]` attribute is used to end a guard. It takes no parameters.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[guard(check_auth)]
+///         #[route("/settings")]
+///         Settings {},
+///     // Ends the guard
+///     #[end_guard]
+///     // Login is not guarded
+///     #[route("/login")]
+///     Login {},
+/// }
+/// ```
+///
+/// # `#[meta(title = "...", description = "...", hydration = "...")]`
+///
+/// The `#[meta]` attribute is used to set the metadata of a route. It takes 3 optional parameters:
+/// - `title`: The title of the document while the route is active
+/// - `description`: The content of the `description` meta tag while the route is active
+/// - `hydration`: When the fullstack client hydrates the page if it starts on the route: `critical` (the default), `visible` or `idle`
+///
+/// The router sets the title and meta tags of the document when it navigates to the route, and the server renderers write them to the head of the page. Read the metadata of the current route with the `use_route_meta` hook. Child routes without metadata use the metadata of the child route.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     #[meta(title = "Home", description = "The home page of the blog")]
+///     Home {},
+///     #[route("/about")]
+///     #[meta(title = "About", hydration = "idle")]
+///     About {},
+/// }
+/// ```
+///
+/// # `#[loader(function)]`
+///
+/// The `#[loader]` attribute is used to load the data of a route. It takes 1 parameter:
+/// - `function`: An async function that takes the route and returns the data of the route
+///
+/// The router runs the loader when the route is matched, and again when the parameters of the route change. Components read the data with the `use_loader_data` hook, which suspends the component until the data is loaded. Child routes without a loader use the loader of the child route.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/post/:id")]
+///     #[loader(fetch_post)]
+///     Post { id: usize },
+/// }
+///
+/// async fn fetch_post(route: Route) -> String {
+///     todo!()
+/// }
+///
+/// #[inline_props]
+/// fn Post(cx: Scope, id: usize) -> Element {
+///     let post = use_loader_data::<String>(cx)?;
+///     render! { "{post}" }
+/// }
+/// ```
+///
+/// # `#[fallback]`
+///
+/// The `#[fallback]` attribute marks the route that every path that doesn't match another route parses to, instead of
+/// failing with a `RouteParseError`. It takes 1 optional parameter:
+/// - `component`: The component to render when the fallback is matched. If not specified, the name of the variant is used
+///
+/// The variant must have a single named field, which holds the path that was attempted and is passed to the component
+/// as a prop. The fallback can be inside of layouts, but not inside of nests.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     // Renders the PageNotFound component with the path that was attempted
+///     #[fallback(PageNotFound)]
+///     NotFound { path: String },
+/// }
+///
+/// #[inline_props]
+/// fn PageNotFound(cx: Scope, path: String) -> Element {
+///     render! { "Nothing lives at {path}" }
+/// }
+/// ```
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route, nest, end_nest, layout, end_layout, guard, end_guard, redirect, child, fallback,
+        meta, loader
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -222,12 +350,65 @@ pub fn routable(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Generates an enum of routes from a directory of pages, like file based routing in Next.js
+///
+/// The path of the directory is relative to the root of the crate. Every file in the directory is a route, and every
+/// directory is a nest:
+/// - `index.rs` is the route of the directory itself: `pages/blog/index.rs` is at `/blog`
+/// - `about.rs` is a static segment: `pages/about.rs` is at `/about`
+/// - `[id].rs` and `[id]/` are dynamic segments: `pages/blog/[id].rs` is at `/blog/:id` with the field `id: String`
+/// - `[...segments].rs` is a catch all segment: `pages/[...segments].rs` matches every other route with the field
+///   `segments: Vec<String>`
+/// - `_layout.rs` is a layout for all routes in the directory and its sub directories
+/// - Other files that start with an underscore are not routes. They are included as modules, so pages can share code
+///
+/// Every page must contain a component named `Page`, and every layout a component named `Layout`. They take the
+/// dynamic segments of the route and the nests they are in as props, just like the components of [`Routable`] enums.
+///
+/// The name of each variant is the path of the page in PascalCase: `pages/blog/[id].rs` is `Route::BlogId` and
+/// `pages/index.rs` is `Route::Index`. The macro declares the modules of the pages itself, so don't add them with `mod`.
+///
+/// ```rust, skip
+/// // pages/_layout.rs
+/// #[inline_props]
+/// pub fn Layout(cx: Scope) -> Element {
+///     render! { Navbar {} Outlet::<Route> {} }
+/// }
+///
+/// // pages/blog/[id].rs
+/// #[inline_props]
+/// pub fn Page(cx: Scope, id: String) -> Element {
+///     render! { "Blog post {id}" }
+/// }
+///
+/// // main.rs
+/// // Generates `enum Route`. Pass a visibility and name to change them: routes_from_dir!("src/pages", pub AppRoute)
+/// routes_from_dir!("src/pages");
+/// ```
+///
+/// Add `fallback Component` after the name to render `Component` for every path that no page matches. The macro adds
+/// a `#[fallback]` variant named after the component, and passes the attempted path to it as the `path: String` prop:
+/// `routes_from_dir!("src/pages", pub Route, fallback PageNotFound)`.
+///
+/// Cargo only rebuilds the enum when the file that calls the macro or one of the pages changes. After adding or
+/// removing a page, save the file that calls the macro to pick it up.
+#[proc_macro]
+pub fn routes_from_dir(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as RoutesFromDir);
+
+    match args.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 struct RouteEnum {
     name: Ident,
     redirects: Vec<Redirect>,
     routes: Vec<Route>,
     nests: Vec<Nest>,
     layouts: Vec<Layout>,
+    fallback: Option<Fallback>,
     site_map: Vec<SiteMapSegment>,
 }
 
@@ -248,6 +429,14 @@ impl RouteEnum {
         let mut nests = Vec::new();
         let mut nest_stack = Vec::new();
 
+        let mut guard_stack: Vec<syn::Path> = Vec::new();
+        // The length of the guard stack when each active nest and layout started. Guards declared inside of a nest or
+        // layout end with it.
+        let mut nest_guards = Vec::new();
+        let mut layout_guards = Vec::new();
+
+        let mut fallback = None;
+
         for variant in &data.variants {
             let mut excluded = Vec::new();
             // Apply the any nesting attributes in order
@@ -307,8 +496,12 @@ impl RouteEnum {
 
                     nests.push(nest);
                     nest_stack.push(NestId(nest_index));
+                    nest_guards.push(guard_stack.len());
                 } else if attr.path().is_ident("end_nest") {
                     nest_stack.pop();
+                    if let Some(len) = nest_guards.pop() {
+                        guard_stack.truncate(len);
+                    }
                     // pop the current nest segment off the stack and add it to the parent or the site map
                     if let Some(segment) = site_map_stack.pop() {
                         let children = site_map_stack
@@ -347,9 +540,31 @@ impl RouteEnum {
                         let layout_index = layouts.len();
                         layouts.push(layout);
                         layout_stack.push(LayoutId(layout_index));
+                        layout_guards.push(guard_stack.len());
                     }
                 } else if attr.path().is_ident("end_layout") {
                     layout_stack.pop();
+                    if let Some(len) = layout_guards.pop() {
+                        guard_stack.truncate(len);
+                    }
+                } else if attr.path().is_ident("guard") {
+                    guard_stack.push(attr.parse_args()?);
+                } else if attr.path().is_ident("end_guard") {
+                    // Only end the guards of the current nest or layout
+                    let start = nest_guards
+                        .last()
+                        .into_iter()
+                        .chain(layout_guards.last())
+                        .max()
+                        .copied()
+                        .unwrap_or_default();
+                    if guard_stack.len() <= start {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "There is no guard in this nest or layout to end",
+                        ));
+                    }
+                    guard_stack.pop();
                 } else if attr.path().is_ident("redirect") {
                     let parser = |input: ParseStream| {
                         Redirect::parse(input, nest_stack.clone(), redirects.len())
@@ -363,7 +578,38 @@ impl RouteEnum {
             let mut active_layouts = layout_stack.clone();
             active_layouts.retain(|&id| !excluded.contains(&id));
 
-            let route = Route::parse(active_nests, active_layouts, variant.clone())?;
+            if let Some(attr) = variant
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("fallback"))
+            {
+                if fallback.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "Only one route can be the fallback route",
+                    ));
+                }
+                // The fallback matches any path, so it can't take dynamic segments from a nest
+                if !active_nests.is_empty()
+                    || active_layouts
+                        .iter()
+                        .any(|id| !layouts[id.0].active_nests.is_empty())
+                {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "The fallback route cannot be inside of a nest",
+                    ));
+                }
+                fallback = Some(Fallback::parse(attr, active_layouts, variant)?);
+                continue;
+            }
+
+            let route = Route::parse(
+                active_nests,
+                active_layouts,
+                guard_stack.clone(),
+                variant.clone(),
+            )?;
 
             // add the route to the site map
             let mut segment = SiteMapSegment::new(&route.segments);
@@ -428,6 +674,7 @@ impl RouteEnum {
             redirects,
             nests,
             layouts,
+            fallback,
             site_map,
         };
 
@@ -441,6 +688,10 @@ impl RouteEnum {
             display_match.push(route.display_match(&self.nests));
         }
 
+        if let Some(fallback) = &self.fallback {
+            display_match.push(fallback.display_match());
+        }
+
         let name = &self.name;
 
         quote! {
@@ -466,6 +717,42 @@ impl RouteEnum {
             route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
         });
 
+        let parse = quote! {
+            let route = s;
+            let (route, _hash) = route.split_once('#').unwrap_or((route, ""));
+            let (route, query) = route.split_once('?').unwrap_or((route, ""));
+            let mut segments = route.split('/');
+            // skip the first empty segment
+            if s.starts_with('/') {
+                let _ = segments.next();
+            }
+            else {
+                // if this route does not start with a slash, it is not a valid route
+                return Err(dioxus_router::routable::RouteParseError {
+                    attempted_routes: Vec::new(),
+                });
+            }
+            let mut errors = Vec::new();
+
+            #(#tokens)*
+
+            Err(dioxus_router::routable::RouteParseError {
+                attempted_routes: errors,
+            })
+        };
+
+        // Paths that don't match any route parse to the fallback route if there is one
+        let from_str = match &self.fallback {
+            Some(fallback) => {
+                let construct = fallback.construct(name);
+                quote! {
+                    let parse = |s: &str| -> Result<Self, Self::Err> { #parse };
+                    Ok(parse(s).unwrap_or_else(|_| #construct))
+                }
+            }
+            None => parse,
+        };
+
         quote! {
             impl<'a> core::convert::TryFrom<&'a str> for #name {
                 type Error = <Self as std::str::FromStr>::Err;
@@ -479,27 +766,7 @@ impl RouteEnum {
                 type Err = dioxus_router::routable::RouteParseError<#error_name>;
 
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
-                    let route = s;
-                    let (route, _hash) = route.split_once('#').unwrap_or((route, ""));
-                    let (route, query) = route.split_once('?').unwrap_or((route, ""));
-                    let mut segments = route.split('/');
-                    // skip the first empty segment
-                    if s.starts_with('/') {
-                        let _ = segments.next();
-                    }
-                    else {
-                        // if this route does not start with a slash, it is not a valid route
-                        return Err(dioxus_router::routable::RouteParseError {
-                            attempted_routes: Vec::new(),
-                        });
-                    }
-                    let mut errors = Vec::new();
-
-                    #(#tokens)*
-
-                    Err(dioxus_router::routable::RouteParseError {
-                        attempted_routes: errors,
-                    })
+                    #from_str
                 }
             }
         }
@@ -578,6 +845,25 @@ impl RouteEnum {
             matches.push(route.routable_match(&self.layouts, &self.nests));
         }
 
+        if let Some(fallback) = &self.fallback {
+            matches.push(fallback.routable_match(&self.layouts, &self.nests));
+        }
+
+        let guard_matches = self.routes.iter().filter_map(Route::guard_match);
+
+        let ancestor_matches = self
+            .routes
+            .iter()
+            .filter_map(|route| route.ancestors_match(&self.routes, &self.nests));
+
+        let loader_matches = self.routes.iter().filter_map(Route::loader_match);
+
+        let meta_matches = self
+            .routes
+            .iter()
+            .filter_map(Route::meta_match)
+            .chain(self.fallback.iter().filter_map(Fallback::meta_match));
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
@@ -585,12 +871,50 @@ impl RouteEnum {
                 ];
 
                 fn render<'a>(&self, cx: &'a dioxus::prelude::ScopeState, level: usize) -> dioxus::prelude::Element<'a> {
+                    // The guards run once, before the outermost layout of the route is rendered
+                    if level == 0 && !dioxus_router::routable::Routable::guard(self).apply(cx) {
+                        // Blocked and redirected routes render a placeholder
+                        return render! { () };
+                    }
                     let myself = self.clone();
                     match (level, myself) {
                         #(#matches)*
                         _ => None
                     }
                 }
+
+                fn guard(&self) -> dioxus_router::routable::GuardResult {
+                    #[allow(unused)]
+                    match self {
+                        #(#guard_matches)*
+                        _ => {}
+                    }
+                    dioxus_router::routable::GuardResult::Allow
+                }
+
+                fn ancestors(&self) -> Vec<Self> {
+                    #[allow(unused)]
+                    match self {
+                        #(#ancestor_matches)*
+                        _ => Vec::new(),
+                    }
+                }
+
+                fn load(&self) -> Option<dioxus_router::routable::RouteLoad> {
+                    #[allow(unused)]
+                    match self {
+                        #(#loader_matches)*
+                        _ => None,
+                    }
+                }
+
+                fn meta(&self) -> dioxus_router::routable::RouteMeta {
+                    #[allow(unused)]
+                    match self {
+                        #(#meta_matches)*
+                        _ => Default::default(),
+                    }
+                }
             }
         }
     }
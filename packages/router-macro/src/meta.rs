@@ -0,0 +1,88 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, Ident, LitStr, Token};
+
+/// The metadata of a route, declared with `#[meta(title = "...", description = "...", hydration = "...")]`
+#[derive(Debug, Default)]
+pub(crate) struct RouteMeta {
+    pub title: Option<LitStr>,
+    pub description: Option<LitStr>,
+    pub hydration: Option<LitStr>,
+}
+
+impl RouteMeta {
+    /// Parse the `#[meta(..)]` attribute of a variant, if it has one
+    pub fn parse_attrs(attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
+        match attrs.iter().find(|attr| attr.path().is_ident("meta")) {
+            Some(attr) => attr.parse_args_with(Self::parse).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut meta = Self::default();
+
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            let _ = input.parse::<Token![=]>()?;
+            let value: LitStr = input.parse()?;
+
+            let field = if name == "title" {
+                &mut meta.title
+            } else if name == "description" {
+                &mut meta.description
+            } else if name == "hydration" {
+                if !matches!(value.value().as_str(), "critical" | "visible" | "idle") {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "Unknown hydration priority. Expected `critical`, `visible` or `idle`",
+                    ));
+                }
+                &mut meta.hydration
+            } else {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Unknown route metadata. Expected `title`, `description` or `hydration`",
+                ));
+            };
+            if field.is_some() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "The route metadata is set more than once",
+                ));
+            }
+            *field = Some(value);
+
+            if !input.is_empty() {
+                let _ = input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(meta)
+    }
+
+    pub fn construct(&self) -> TokenStream {
+        let title = option_tokens(&self.title);
+        let description = option_tokens(&self.description);
+        let hydration = match self.hydration.as_ref().map(LitStr::value).as_deref() {
+            Some("visible") => quote! { Visible },
+            Some("idle") => quote! { Idle },
+            _ => quote! { Critical },
+        };
+
+        quote! {
+            dioxus_router::routable::RouteMeta {
+                title: #title,
+                description: #description,
+                hydration: dioxus_router::routable::HydrationPriority::#hydration,
+            }
+        }
+    }
+}
+
+fn option_tokens(value: &Option<LitStr>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
@@ -7,21 +7,62 @@ use proc_macro2::TokenStream as TokenStream2;
 pub struct QuerySegment {
     pub ident: Ident,
     pub ty: Type,
+    /// If the whole query string is deserialized into the field with serde (`?:..query`)
+    pub spread: bool,
 }
 
 impl QuerySegment {
-    pub fn parse(&self) -> TokenStream2 {
+    pub fn parse(
+        &self,
+        success_tokens: TokenStream2,
+        error_enum_name: &Ident,
+        enum_varient: &Ident,
+        varient_parse_error: &Ident,
+    ) -> TokenStream2 {
         let ident = &self.ident;
         let ty = &self.ty;
-        quote! {
-            let #ident = <#ty as dioxus_router::routable::FromQuery>::from_query(query);
+        if self.spread {
+            quote! {
+                match <#ty as dioxus_router::routable::QueryStruct>::from_query_string(query) {
+                    Ok(#ident) => {
+                        #success_tokens
+                    }
+                    Err(err) => {
+                        errors.push(#error_enum_name::#enum_varient(#varient_parse_error::QueryParseError(err)))
+                    }
+                }
+            }
+        } else {
+            quote! {
+                let #ident = <#ty as dioxus_router::routable::FromQuery>::from_query(query);
+                #success_tokens
+            }
         }
     }
 
     pub fn write(&self) -> TokenStream2 {
         let ident = &self.ident;
-        quote! {
-            write!(f, "?{}", #ident)?;
+        if self.spread {
+            quote! {
+                dioxus_router::routable::QueryStruct::display_query_string(#ident, f)?;
+            }
+        } else {
+            quote! {
+                write!(f, "?{}", #ident)?;
+            }
         }
     }
+
+    pub fn error_variant(&self) -> Option<(TokenStream2, TokenStream2)> {
+        if !self.spread {
+            return None;
+        }
+
+        let ident = &self.ident;
+        let ty = &self.ty;
+        Some((
+            quote! { QueryParseError(dioxus_router::routable::QueryStructError) },
+            quote! { Self::QueryParseError(err) => write!(f, "Query '({}:{})' did not match: {}", stringify!(#ident), stringify!(#ty), err)? },
+        ))
+    }
 }
@@ -1,5 +1,5 @@
 use proc_macro2::{Ident, TokenStream};
-use quote::{format_ident, quote};
+use quote::format_ident;
 use syn::LitStr;
 
 use crate::{
@@ -30,14 +30,7 @@ impl Redirect {
     pub fn error_type(&self) -> TokenStream {
         let error_name = self.error_ident();
 
-        create_error_type(error_name, &self.segments, None)
-    }
-
-    pub fn parse_query(&self) -> TokenStream {
-        match &self.query {
-            Some(query) => query.parse(),
-            None => quote! {},
-        }
+        create_error_type(error_name, &self.segments, self.query.as_ref(), None)
     }
 
     pub fn parse(
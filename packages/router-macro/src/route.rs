@@ -11,6 +11,7 @@ use proc_macro2::TokenStream as TokenStream2;
 
 use crate::layout::Layout;
 use crate::layout::LayoutId;
+use crate::meta::RouteMeta;
 use crate::nest::Nest;
 use crate::nest::NestId;
 use crate::query::QuerySegment;
@@ -58,6 +59,9 @@ pub(crate) struct Route {
     pub query: Option<QuerySegment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub guards: Vec<Path>,
+    pub meta: Option<RouteMeta>,
+    pub loader: Option<Path>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -65,6 +69,7 @@ impl Route {
     pub fn parse(
         nests: Vec<NestId>,
         layouts: Vec<LayoutId>,
+        guards: Vec<Path>,
         variant: syn::Variant,
     ) -> syn::Result<Self> {
         let route_attr = variant
@@ -144,6 +149,14 @@ impl Route {
             _ => Vec::new(),
         };
 
+        let meta = RouteMeta::parse_attrs(&variant.attrs)?;
+        let loader = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("loader"))
+            .map(|attr| attr.parse_args::<Path>())
+            .transpose()?;
+
         let (route_segments, query) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -160,6 +173,9 @@ impl Route {
             query,
             nests,
             layouts,
+            guards,
+            meta,
+            loader,
             fields,
         })
     }
@@ -260,6 +276,134 @@ impl Route {
         tokens
     }
 
+    pub fn guard_match(&self) -> Option<TokenStream2> {
+        if self.guards.is_empty() {
+            return None;
+        }
+
+        let name = &self.route_name;
+        let guards = &self.guards;
+        Some(quote! {
+            Self::#name { .. } => {
+                #(
+                    match (#guards)(self) {
+                        dioxus_router::routable::GuardResult::Allow => {}
+                        result => return result,
+                    }
+                )*
+            }
+        })
+    }
+
+    pub fn meta_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match (&self.meta, &self.ty) {
+            (Some(meta), _) => {
+                let meta = meta.construct();
+                Some(quote! {
+                    Self::#name { .. } => #meta,
+                })
+            }
+            // Child routes without metadata use the metadata of the child
+            (None, RouteType::Child(field)) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::meta(#child),
+                })
+            }
+            (None, RouteType::Leaf { .. }) => None,
+        }
+    }
+
+    pub fn loader_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+        match (&self.loader, &self.ty) {
+            (Some(loader), _) => Some(quote! {
+                Self::#name { .. } => Some(dioxus_router::routable::RouteLoad::new((#loader)(self.clone()))),
+            }),
+            // Child routes without a loader use the loader of the child
+            (None, RouteType::Child(field)) => {
+                let child = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #child, .. } => dioxus_router::routable::Routable::load(#child),
+                })
+            }
+            (None, RouteType::Leaf { .. }) => None,
+        }
+    }
+
+    /// The route at the root of a nest, that shows what is inside of the nest. It has no segments of its own
+    fn is_index(&self) -> bool {
+        // "/" is parsed as a single empty segment
+        let no_segments = self
+            .segments
+            .iter()
+            .all(|segment| matches!(segment, RouteSegment::Static(s) if s.is_empty()));
+        no_segments && self.query.is_none() && matches!(self.ty, RouteType::Leaf { .. })
+    }
+
+    pub fn ancestors_match(&self, routes: &[Route], nests: &[Nest]) -> Option<TokenStream2> {
+        let name = &self.route_name;
+
+        // The index route of every nest the route is in, starting with the index route outside of any nest
+        let ancestors: Vec<_> = (0..=self.nests.len())
+            .filter_map(|depth| {
+                let active_nests = &self.nests[..depth];
+                routes.iter().find(|route| {
+                    route.is_index()
+                        && route.route_name != *name
+                        && route
+                            .nests
+                            .iter()
+                            .map(|id| id.0)
+                            .eq(active_nests.iter().map(|id| id.0))
+                })
+            })
+            .map(|ancestor| {
+                let ancestor_name = &ancestor.route_name;
+                // The dynamic segments of the nests are shared with this route
+                let fields = ancestor.fields.iter().map(|(field, _)| {
+                    let from_nest = ancestor
+                        .nests
+                        .iter()
+                        .any(|id| nests[id.0].dynamic_segments_names().any(|i| &i == field));
+                    if from_nest {
+                        quote! { #field: #field.clone() }
+                    } else {
+                        quote! { #field: Default::default() }
+                    }
+                });
+                quote! { Self::#ancestor_name { #(#fields,)* } }
+            })
+            .collect();
+
+        let fields = self.fields.iter().map(|(field, _)| field);
+        match &self.ty {
+            RouteType::Child(field) => {
+                let child = field.ident.as_ref().unwrap();
+                let clone_fields = self
+                    .fields
+                    .iter()
+                    .map(|(field, _)| quote! { #field: #field.clone() });
+                Some(quote! {
+                    Self::#name { #(#fields,)* #child } => {
+                        let mut ancestors = vec![#(#ancestors,)*];
+                        ancestors.extend(
+                            dioxus_router::routable::Routable::ancestors(#child)
+                                .into_iter()
+                                .map(|#child| Self::#name { #(#clone_fields,)* #child }),
+                        );
+                        ancestors
+                    }
+                })
+            }
+            RouteType::Leaf { .. } if ancestors.is_empty() => None,
+            RouteType::Leaf { .. } => Some(quote! {
+                Self::#name { #(#fields,)* .. } => vec![#(#ancestors,)*],
+            }),
+        }
+    }
+
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         self.fields.iter().map(|(name, _)| {
             quote! {#name}
@@ -277,7 +421,9 @@ impl Route {
                 }
             }
             for segment in &self.segments {
-                if let RouteSegment::Dynamic(other, ..) = segment {
+                if let RouteSegment::Dynamic(other, ..) | RouteSegment::CatchAll(other, ..) =
+                    segment
+                {
                     if other == name {
                         from_route = true
                     }
@@ -330,14 +476,7 @@ impl Route {
             RouteType::Leaf { .. } => None,
         };
 
-        create_error_type(error_name, &self.segments, child_type)
-    }
-
-    pub fn parse_query(&self) -> TokenStream2 {
-        match &self.query {
-            Some(query) => query.parse(),
-            None => quote! {},
-        }
+        create_error_type(error_name, &self.segments, self.query.as_ref(), child_type)
     }
 }
 
@@ -5,6 +5,7 @@ use syn::Ident;
 
 use crate::{
     nest::{Nest, NestId},
+    query::QuerySegment,
     redirect::Redirect,
     route::{Route, RouteType},
     segment::{static_segment_idx, RouteSegment},
@@ -335,7 +336,6 @@ impl<'a> RouteTreeSegmentData<'a> {
                     .skip_while(|(_, seg)| matches!(seg, RouteSegment::Static(_)));
 
                 let construct_variant = route.construct(nests, enum_name);
-                let parse_query = route.parse_query();
 
                 let insure_not_trailing = match route.ty {
                     RouteType::Leaf { .. } => route
@@ -354,7 +354,7 @@ impl<'a> RouteTreeSegmentData<'a> {
                         &error_enum_name,
                         enum_varient,
                         &varient_parse_error,
-                        parse_query,
+                        route.query.as_ref(),
                     ),
                     &error_enum_name,
                     enum_varient,
@@ -424,8 +424,6 @@ impl<'a> RouteTreeSegmentData<'a> {
                     .enumerate()
                     .skip_while(|(_, seg)| matches!(seg, RouteSegment::Static(_)));
 
-                let parse_query = redirect.parse_query();
-
                 let insure_not_trailing = redirect
                     .segments
                     .last()
@@ -452,7 +450,7 @@ impl<'a> RouteTreeSegmentData<'a> {
                         &error_enum_name,
                         enum_varient,
                         &varient_parse_error,
-                        parse_query,
+                        redirect.query.as_ref(),
                     ),
                     &error_enum_name,
                     enum_varient,
@@ -499,8 +497,21 @@ fn return_constructed(
     error_enum_name: &Ident,
     enum_varient: &Ident,
     varient_parse_error: &Ident,
-    parse_query: TokenStream,
+    query: Option<&QuerySegment>,
 ) -> TokenStream {
+    let return_constructed = quote! {
+        return Ok(#construct_variant);
+    };
+    let return_constructed = match query {
+        Some(query) => query.parse(
+            return_constructed,
+            error_enum_name,
+            enum_varient,
+            varient_parse_error,
+        ),
+        None => return_constructed,
+    };
+
     if insure_not_trailing {
         quote! {
             let remaining_segments = segments.clone();
@@ -510,8 +521,7 @@ fn return_constructed(
             match (next_segment, segment_after_next) {
                 // This is the last segment, return the parsed route
                 (None, _) | (Some(""), None) => {
-                    #parse_query
-                    return Ok(#construct_variant);
+                    #return_constructed
                 }
                 _ => {
                     let mut trailing = String::new();
@@ -525,10 +535,7 @@ fn return_constructed(
             }
         }
     } else {
-        quote! {
-            #parse_query
-            return Ok(#construct_variant);
-        }
+        return_constructed
     }
 }
 
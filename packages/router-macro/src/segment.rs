@@ -197,6 +197,8 @@ pub fn parse_route_segments<'a>(
     let parsed_query = match query {
         Some(query) => {
             if let Some(query) = query.strip_prefix(':') {
+                let spread = query.starts_with("..");
+                let query = if spread { &query[2..] } else { query };
                 let query_ident = Ident::new(query, Span::call_site());
                 let field = fields.find(|(name, _)| *name == &query_ident);
 
@@ -212,6 +214,7 @@ pub fn parse_route_segments<'a>(
                 Some(QuerySegment {
                     ident: query_ident,
                     ty,
+                    spread,
                 })
             } else {
                 None
@@ -226,11 +229,17 @@ pub fn parse_route_segments<'a>(
 pub(crate) fn create_error_type(
     error_name: Ident,
     segments: &[RouteSegment],
+    query: Option<&QuerySegment>,
     child_type: Option<&Type>,
 ) -> TokenStream2 {
     let mut error_variants = Vec::new();
     let mut display_match = Vec::new();
 
+    if let Some((error_variant, display)) = query.and_then(QuerySegment::error_variant) {
+        error_variants.push(error_variant);
+        display_match.push(display);
+    }
+
     for (i, segment) in segments.iter().enumerate() {
         let error_name = segment.error_name(i);
         match segment {
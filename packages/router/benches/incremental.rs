@@ -146,8 +146,9 @@ fn Blog(cx: Scope) -> Element {
 #[inline_props]
 fn Post(cx: Scope, id: usize) -> Element {
     render! {
-        for _ in 0..*id {
+        for i in 0..*id {
             div {
+                key: "{i}",
                 "PostId: {id}"
             }
         }
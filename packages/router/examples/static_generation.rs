@@ -50,6 +50,19 @@ async fn main() {
     )
     .await
     .unwrap();
+
+    // Render every route, including the posts, to a static site that can be deployed to any static host
+    let pages = generate_static_site::<Route, _>(
+        &DefaultRenderer::default(),
+        "./dist",
+        &|_: &[SegmentType], name: &str| match name {
+            "id" => (0..3).map(|id| id.to_string()).collect(),
+            _ => Vec::new(),
+        },
+    )
+    .await
+    .unwrap();
+    println!("Generated {} pages", pages.len());
 }
 
 #[inline_props]
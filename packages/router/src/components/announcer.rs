@@ -0,0 +1,143 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+
+use crate::utils::use_router_internal::use_router_internal;
+
+/// Hides the live region visually, while keeping it readable by screen readers
+const VISUALLY_HIDDEN: &str = concat!(
+    "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; ",
+    "overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;"
+);
+
+/// Announces navigation to screen readers. Pass it to the `announcer` prop of the
+/// [`Router`](crate::prelude::Router) component.
+///
+/// Single page apps change the page without loading a new document, so screen readers don't notice the navigation.
+/// After every navigation, the announcer updates an `aria-live` region with the title of the new page, declared with
+/// the `#[meta(title = "...")]` attribute when deriving Routable, and moves the focus to the main content of the page.
+///
+/// ```rust, ignore
+/// render! {
+///     Router::<Route> {
+///         announcer: RouteAnnouncer::default(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteAnnouncer {
+    /// The CSS selector of the element that gets the focus after navigating, or [`None`] to leave the focus alone.
+    /// Defaults to the `main` landmark.
+    pub focus: Option<String>,
+    /// The announcement of a route without a title. The announcement is the path of the route if this is [`None`].
+    pub fallback: Option<String>,
+}
+
+impl Default for RouteAnnouncer {
+    fn default() -> Self {
+        Self {
+            focus: Some("main".to_string()),
+            fallback: None,
+        }
+    }
+}
+
+impl RouteAnnouncer {
+    /// Move the focus to the element matching the CSS selector after navigating
+    pub fn focus(mut self, selector: impl Into<String>) -> Self {
+        self.focus = Some(selector.into());
+        self
+    }
+
+    /// Don't move the focus after navigating
+    pub fn without_focus(mut self) -> Self {
+        self.focus = None;
+        self
+    }
+
+    /// Announce routes without a title with this message instead of their path
+    pub fn fallback(mut self, message: impl Into<String>) -> Self {
+        self.fallback = Some(message.into());
+        self
+    }
+}
+
+/// The live region that announces the current route, rendered by the router when it has a [`RouteAnnouncer`]
+#[inline_props]
+pub(crate) fn Announcer(cx: Scope, config: RouteAnnouncer) -> Element {
+    let router = match use_router_internal(cx) {
+        Some(router) => router,
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`Announcer` must have access to a parent router");
+            #[allow(unreachable_code)]
+            return None;
+        }
+    };
+    // The route and the announcement of the last render. Nothing is announced when the page is loaded
+    let state =
+        cx.use_hook(|| Rc::new(RefCell::new((router.current_route_string(), String::new()))));
+
+    let route = router.current_route_string();
+    let mut state_ref = state.borrow_mut();
+    if state_ref.0 != route {
+        let announcement = match router.current_meta().title {
+            Some(title) => title.to_string(),
+            None => config.fallback.clone().unwrap_or_else(|| route.clone()),
+        };
+        *state_ref = (route, announcement);
+
+        if let Some(selector) = &config.focus {
+            focus_main_content(cx, selector);
+        }
+    }
+    let announcement = state_ref.1.clone();
+    drop(state_ref);
+
+    render! {
+        div {
+            aria_live: "polite",
+            aria_atomic: "true",
+            style: VISUALLY_HIDDEN,
+            "{announcement}"
+        }
+    }
+}
+
+/// Focus the element matching the selector, once the new page is rendered
+fn focus_main_content(cx: &ScopeState, selector: &str) {
+    let eval_provider = match cx.consume_context::<Rc<dyn EvalProvider>>() {
+        Some(eval_provider) => eval_provider,
+        // Renderers without javascript, like the server, have nothing to focus
+        None => return,
+    };
+    let script = format!(
+        r#"let element = document.querySelector({});
+if (element) {{
+    if (!element.hasAttribute("tabindex")) {{
+        element.setAttribute("tabindex", "-1");
+    }}
+    element.focus();
+}}"#,
+        js_string(selector)
+    );
+    // Tasks run after the edits of the current render are applied, so the new page already exists
+    cx.spawn(async move {
+        // The script doesn't return anything, so there is nothing to wait for
+        let _ = eval_provider.new_evaluator(script);
+    });
+}
+
+fn js_string(value: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -29,6 +29,7 @@ impl<R: Routable> From<NavigationTarget<R>> for IntoRoutable {
         match value {
             NavigationTarget::Internal(route) => IntoRoutable::Route(Rc::new(route) as Rc<dyn Any>),
             NavigationTarget::External(url) => IntoRoutable::FromStr(url),
+            NavigationTarget::Relative(path) => IntoRoutable::FromStr(path),
         }
     }
 }
@@ -118,6 +119,10 @@ impl Debug for LinkProps<'_> {
 /// When the [`Link`]s target is an [`NavigationTarget::External`] target, that is used as the `href` directly. This
 /// means that a [`Link`] can always navigate to an [`NavigationTarget::External`] target, even if the [`HistoryProvider`] does not support it.
 ///
+/// # Relative targets
+/// A [`Link`] to a path that starts with `./` or `../` is resolved against the current route. A [`Link`] to `"../edit"`
+/// on `/posts/1/comments` points to `/posts/1/edit`, so deeply nested components don't need to know their full route.
+///
 /// # Panic
 /// - When the [`Link`] is not nested within a [`Router`], but
 ///   only in debug builds.
@@ -194,7 +199,7 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
 
     let current_url = router.current_route_string();
     let href = match to {
-        IntoRoutable::FromStr(url) => url.to_string(),
+        IntoRoutable::FromStr(url) => router.resolve_relative(url),
         IntoRoutable::Route(route) => router.any_route_to_string(&**route),
     };
     let parsed_route: NavigationTarget<Rc<dyn Any>> = router.resolve_into_routable(to.clone());
@@ -212,6 +217,11 @@ pub fn Link<'a>(cx: Scope<'a, LinkProps<'a>>) -> Element {
     let rel = rel
         .or_else(|| is_external.then_some("noopener noreferrer"))
         .unwrap_or_default();
+    let href = if is_external {
+        href
+    } else {
+        router.full_path(&href)
+    };
 
     let do_default = onclick.is_none() || !onclick_only;
     let action = move |event| {
@@ -1,7 +1,13 @@
-use dioxus::prelude::*;
-use std::{cell::RefCell, str::FromStr};
+use dioxus::{core::SuspenseContext, prelude::*};
+use std::{cell::RefCell, rc::Rc, str::FromStr};
 
-use crate::{prelude::Outlet, routable::Routable, router_cfg::RouterConfig};
+use crate::{
+    components::{Announcer, RouteAnnouncer},
+    history::HistoryMode,
+    prelude::{use_route_meta, Outlet},
+    routable::Routable,
+    router_cfg::RouterConfig,
+};
 
 /// The config for [`Router`].
 pub struct RouterConfigFactory<R: Routable> {
@@ -48,6 +54,13 @@ where
 {
     #[props(default, into)]
     config: RouterConfigFactory<R>,
+    /// Where the router stores the route in the URL of the browser. This is only used on the web, when the config
+    /// does not set a history provider.
+    #[props(default)]
+    history_mode: HistoryMode,
+    /// Announce navigation to screen readers and move the focus to the main content of the new page.
+    #[props(default, strip_option)]
+    announcer: Option<RouteAnnouncer>,
 }
 
 #[cfg(not(feature = "serde"))]
@@ -59,6 +72,13 @@ where
 {
     #[props(default, into)]
     config: RouterConfigFactory<R>,
+    /// Where the router stores the route in the URL of the browser. This is only used on the web, when the config
+    /// does not set a history provider.
+    #[props(default)]
+    history_mode: HistoryMode,
+    /// Announce navigation to screen readers and move the focus to the main content of the new page.
+    #[props(default, strip_option)]
+    announcer: Option<RouteAnnouncer>,
 }
 
 #[cfg(not(feature = "serde"))]
@@ -69,6 +89,8 @@ where
     fn default() -> Self {
         Self {
             config: RouterConfigFactory::default(),
+            history_mode: HistoryMode::default(),
+            announcer: None,
         }
     }
 }
@@ -82,6 +104,8 @@ where
     fn default() -> Self {
         Self {
             config: RouterConfigFactory::default(),
+            history_mode: HistoryMode::default(),
+            announcer: None,
         }
     }
 }
@@ -117,22 +141,28 @@ where
 {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
-    use_context_provider(cx, || {
-        RouterContext::new(
-            (cx.props
-                .config
-                .config
-                .take()
-                .expect("use_context_provider ran twice"))(),
-            cx.schedule_update_any(),
-        )
+    let suspense = use_context_provider(cx, || Rc::new(SuspenseContext::new(cx.scope_id())));
+    let router = use_context_provider(cx, || {
+        let mut config = (cx
+            .props
+            .config
+            .config
+            .take()
+            .expect("use_context_provider ran twice"))();
+        config.history_mode = cx.props.history_mode;
+        RouterContext::new(config, cx.schedule_update_any(), suspense.clone())
     });
+    router.settle_transition();
+    router.route_rendered();
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
+        held: None,
         _marker: std::marker::PhantomData,
     });
 
     render! {
+        RouteHead {}
+        cx.props.announcer.clone().map(|config| rsx! { Announcer { config: config } }),
         Outlet::<R> {}
     }
 }
@@ -144,22 +174,50 @@ where
     <R as FromStr>::Err: std::fmt::Display,
     R: serde::Serialize + serde::de::DeserializeOwned,
 {
-    use_context_provider(cx, || {
-        RouterContext::new(
-            (cx.props
-                .config
-                .config
-                .take()
-                .expect("use_context_provider ran twice"))(),
-            cx.schedule_update_any(),
-        )
+    let suspense = use_context_provider(cx, || Rc::new(SuspenseContext::new(cx.scope_id())));
+    let router = use_context_provider(cx, || {
+        let mut config = (cx
+            .props
+            .config
+            .config
+            .take()
+            .expect("use_context_provider ran twice"))();
+        config.history_mode = cx.props.history_mode;
+        RouterContext::new(config, cx.schedule_update_any(), suspense.clone())
     });
+    router.settle_transition();
+    router.route_rendered();
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
+        held: None,
         _marker: std::marker::PhantomData,
     });
 
     render! {
+        RouteHead {}
+        cx.props.announcer.clone().map(|config| rsx! { Announcer { config: config } }),
         Outlet::<R> {}
     }
 }
+
+/// Sets the title and meta tags of the document to the metadata of the current route
+fn RouteHead(cx: Scope) -> Element {
+    let meta = use_route_meta(cx);
+
+    render! {
+        meta.title.map(|title| rsx! { RouteTitle { title: title } }),
+        meta.description.map(|description| rsx! { RouteDescription { description: description } })
+    }
+}
+
+#[inline_props]
+fn RouteTitle(cx: Scope, title: &'static str) -> Element {
+    use_title(cx, title);
+    render! { () }
+}
+
+#[inline_props]
+fn RouteDescription(cx: Scope, description: &'static str) -> Element {
+    use_meta(cx, "description", description);
+    render! { () }
+}
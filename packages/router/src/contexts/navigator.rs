@@ -38,6 +38,16 @@ impl Navigator {
         self.0.push(target)
     }
 
+    /// Push a new location, and track the navigation until the new route is ready.
+    ///
+    /// See [`RouterContext::navigate_with_transition`].
+    pub fn navigate_with_transition(
+        &self,
+        target: impl Into<IntoRoutable>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.0.navigate_with_transition(target)
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -1,29 +1,37 @@
-use dioxus::prelude::*;
+use dioxus::{
+    core::{prelude::consume_context_from_scope, DynamicNode},
+    prelude::*,
+};
 
 use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
 
 pub(crate) struct OutletContext<R> {
     pub current_level: usize,
+    /// The route rendered below this outlet instead of the current route, while a transition holds the old route
+    pub held: Option<R>,
     pub _marker: std::marker::PhantomData<R>,
 }
 
-impl<R> Clone for OutletContext<R> {
+impl<R: Clone> Clone for OutletContext<R> {
     fn clone(&self) -> Self {
         OutletContext {
             current_level: self.current_level,
+            held: self.held.clone(),
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-pub(crate) fn use_outlet_context<R: 'static>(cx: &ScopeState) -> &OutletContext<R> {
-    let outlet_context = cx.use_hook(|| {
-        cx.consume_context().unwrap_or(OutletContext::<R> {
+pub(crate) fn use_outlet_context<R: Clone + 'static>(cx: &ScopeState) -> OutletContext<R> {
+    // The context is read from the parent on every render, because the outlet provides the context of the next level
+    // itself, and the outlets below a route slot start rendering the held route when a transition starts
+    cx.parent()
+        .and_then(consume_context_from_scope)
+        .unwrap_or(OutletContext::<R> {
             current_level: 1,
+            held: None,
             _marker: std::marker::PhantomData,
         })
-    });
-    outlet_context
 }
 
 impl<R> OutletContext<R> {
@@ -34,13 +42,12 @@ impl<R> OutletContext<R> {
         let router = use_router_internal(cx)
             .as_ref()
             .expect("Outlet must be inside of a router");
-        let outlet: &OutletContext<R> = use_outlet_context(cx);
+        let outlet: OutletContext<R> = use_outlet_context(cx);
         let current_level = outlet.current_level;
-        cx.provide_context({
-            OutletContext::<R> {
-                current_level: current_level + 1,
-                _marker: std::marker::PhantomData,
-            }
+        cx.provide_context(OutletContext::<R> {
+            current_level: current_level + 1,
+            held: outlet.held.clone(),
+            _marker: std::marker::PhantomData,
         });
 
         if let Some(error) = router.render_error(cx) {
@@ -51,6 +58,110 @@ impl<R> OutletContext<R> {
             }
         }
 
-        router.current::<R>().render(cx, current_level)
+        // Outlets below a held route keep rendering the old route
+        if let Some(route) = outlet.held {
+            return route.render(cx, current_level);
+        }
+
+        if current_level == 0 {
+            router.run_loader::<R>(cx);
+        }
+        let route = router.current::<R>();
+        let rendered = route.render(cx, current_level);
+
+        // While a transition is pending, the outlet where the old and the new route first differ keeps the old route
+        // mounted next to the new one, until none of the components of the new route are suspended
+        let held = router
+            .transition_from::<R>()
+            .filter(|from| first_difference(cx, from, &route, current_level) == Some(current_level))
+            .map(|from| (from.render(cx, current_level), from));
+
+        if current_level == 0 {
+            router.transition_rendered();
+            router.route_rendered();
+        }
+
+        // The slots are keyed by the components they render, so the old and the new route keep their components when
+        // the transition starts and finishes
+        let hydration = hydration_priority(cx, &route, current_level);
+        let slots = held
+            .map(|(node, from)| {
+                let hydration = hydration_priority(cx, &from, current_level);
+                (node, hydration, Some(from))
+            })
+            .into_iter()
+            .chain(std::iter::once((rendered, hydration, None)));
+        render! {
+            for (node, hydration, held) in slots {
+                RouteSlot::<R> {
+                    key: "{route_key(&node)}",
+                    node: node,
+                    level: current_level,
+                    hydration: hydration,
+                    held: held,
+                }
+            }
+        }
     }
 }
+
+/// Identify the layout or route component a route renders at a level
+fn route_key(node: &Element) -> String {
+    let Some(node) = node else {
+        return String::new();
+    };
+    let mut key = node.template.get().name.to_string();
+    for node in node.dynamic_nodes {
+        if let DynamicNode::Component(component) = node {
+            key.push(':');
+            key.push_str(component.name);
+        }
+    }
+    key
+}
+
+/// The hydration priority of the content a route renders at a level. Layouts are interactive right away, the component
+/// of the route itself follows the priority of the route.
+fn hydration_priority<R: Routable>(cx: &ScopeState, route: &R, level: usize) -> HydrationPriority {
+    match route.render(cx, level + 1) {
+        Some(_) => HydrationPriority::Critical,
+        None => route.meta().hydration,
+    }
+}
+
+/// Find the first level up to `max_level` where the two routes render a different layout or route component
+fn first_difference<R: Routable>(
+    cx: &ScopeState,
+    old: &R,
+    new: &R,
+    max_level: usize,
+) -> Option<usize> {
+    (0..=max_level)
+        .find(|&level| route_key(&old.render(cx, level)) != route_key(&new.render(cx, level)))
+}
+
+#[derive(Props)]
+struct RouteSlotProps<'a, R: Routable> {
+    node: Element<'a>,
+    level: usize,
+    hydration: HydrationPriority,
+    #[props(!optional)]
+    held: Option<R>,
+}
+
+/// Renders the route of an outlet in its own scope. The outlets inside of a slot that holds the old route of a
+/// transition render the old route as well.
+///
+/// The slot provides the hydration priority of its content, so renderers can make the content of outlets interactive in
+/// order.
+#[allow(non_snake_case)]
+fn RouteSlot<'a, R: Routable + Clone>(cx: Scope<'a, RouteSlotProps<'a, R>>) -> Element<'a> {
+    cx.provide_context(OutletContext::<R> {
+        current_level: cx.props.level + 1,
+        held: cx.props.held.clone(),
+        _marker: std::marker::PhantomData,
+    });
+    cx.provide_context(cx.props.hydration);
+
+    render! { &cx.props.node }
+}
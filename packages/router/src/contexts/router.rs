@@ -1,16 +1,17 @@
 use std::{
     any::Any,
+    cell::RefCell,
     collections::HashSet,
     rc::Rc,
     sync::{Arc, RwLock, RwLockWriteGuard},
 };
 
-use dioxus::prelude::*;
+use dioxus::{core::SuspenseContext, prelude::*};
 
 use crate::{
-    navigation::NavigationTarget,
-    prelude::{AnyHistoryProvider, IntoRoutable},
-    routable::Routable,
+    navigation::{is_relative, resolve_relative, NavigationTarget},
+    prelude::{AnyHistoryProvider, IntoRoutable, NavigationState},
+    routable::{Routable, RouteMeta},
     router_cfg::RouterConfig,
 };
 
@@ -31,6 +32,29 @@ struct MutableRouterState {
     history: Box<dyn AnyHistoryProvider>,
 
     unresolved_error: Option<ExternalNavigationFailure>,
+
+    /// The route of the modal that was opened by pushing it onto the history.
+    modal: Option<String>,
+
+    /// The navigation started with [`RouterContext::navigate_with_transition`] that is still pending.
+    transition: Option<Transition>,
+}
+
+struct Transition {
+    from: String,
+    to: String,
+    /// If the outlet rendered the new route, so its suspended components are tracked by the suspense boundary
+    rendered: bool,
+}
+
+/// The data of the current route, loaded by the loader of the route
+#[derive(Default)]
+struct LoaderState {
+    /// The route the data is loaded for
+    route: Option<String>,
+    data: Option<Rc<dyn Any>>,
+    /// The task that runs the loader, while the data is loading
+    task: Option<TaskId>,
 }
 
 /// A collection of router data that manages all routing functionality.
@@ -45,12 +69,20 @@ pub struct RouterContext {
     failure_external_navigation: fn(Scope) -> Element,
 
     any_route_to_string: fn(&dyn Any) -> String,
+
+    any_route_meta: fn(&dyn Any) -> RouteMeta,
+
+    /// The suspense boundary of the router component
+    suspense: Rc<SuspenseContext>,
+
+    loader: Rc<RefCell<LoaderState>>,
 }
 
 impl RouterContext {
     pub(crate) fn new<R: Routable + 'static>(
         mut cfg: RouterConfig<R>,
         mark_dirty: Arc<dyn Fn(ScopeId) + Sync + Send>,
+        suspense: Rc<SuspenseContext>,
     ) -> Self
     where
         R: Clone,
@@ -60,6 +92,8 @@ impl RouterContext {
             prefix: Default::default(),
             history: cfg.take_history(),
             unresolved_error: None,
+            modal: None,
+            transition: None,
         }));
 
         let subscriber_update = mark_dirty.clone();
@@ -81,6 +115,7 @@ impl RouterContext {
                             NavigationTarget::Internal(Rc::new(r) as Rc<dyn Any>)
                         }
                         NavigationTarget::External(s) => NavigationTarget::External(s),
+                        NavigationTarget::Relative(s) => NavigationTarget::Relative(s),
                     })
                 })
                     as Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>
@@ -101,6 +136,17 @@ impl RouterContext {
                     })
                     .to_string()
             },
+
+            any_route_meta: |route| {
+                route
+                    .downcast_ref::<R>()
+                    .map(Routable::meta)
+                    .unwrap_or_default()
+            },
+
+            suspense,
+
+            loader: Default::default(),
         };
 
         // set the updater
@@ -162,9 +208,12 @@ impl RouterContext {
         match target {
             NavigationTarget::Internal(p) => {
                 let mut state = self.state_mut();
+                // The entries after the current one are dropped, including the entry of a closed modal
+                state.modal = None;
                 state.history.push(p)
             }
             NavigationTarget::External(e) => return self.external(e),
+            NavigationTarget::Relative(path) => return self.push(path),
         }
 
         self.change_route()
@@ -178,26 +227,161 @@ impl RouterContext {
         match target {
             NavigationTarget::Internal(p) => {
                 let mut state = self.state_mut();
+                // The entries after the current one are dropped, including the entry of a closed modal
+                state.modal = None;
                 state.history.push(p)
             }
             NavigationTarget::External(e) => return self.external(e),
+            NavigationTarget::Relative(path) => return self.push(path),
         }
 
         self.change_route()
     }
 
+    /// Push a new location, and track the navigation until the new route is ready.
+    ///
+    /// The new route is rendered right away, but the navigation stays pending until none of the components of the
+    /// router are suspended. While the navigation is pending, the outlet that renders the first layout or route that
+    /// differs between the old and the new route keeps showing the old one, and only swaps it once the new one is
+    /// ready. Read the state of the navigation with [`use_navigation_state`](crate::prelude::use_navigation_state),
+    /// to show a progress bar while the new route loads its data for example.
+    pub fn navigate_with_transition(
+        &self,
+        target: impl Into<IntoRoutable>,
+    ) -> Option<ExternalNavigationFailure> {
+        // A transition that starts while another one is pending keeps holding the route that is still shown
+        let pending = self
+            .state
+            .read()
+            .unwrap()
+            .transition
+            .as_ref()
+            .map(|t| t.from.clone());
+        let from = pending.unwrap_or_else(|| self.current_route_string());
+        if let Some(failure) = self.push(target) {
+            return Some(failure);
+        }
+
+        let to = self.current_route_string();
+        self.state_mut().transition = Some(Transition {
+            from,
+            to,
+            rendered: false,
+        });
+        None
+    }
+
+    /// The state of the last navigation started with [`RouterContext::navigate_with_transition`].
+    pub fn navigation_state(&self) -> NavigationState {
+        match &self.state.read().unwrap().transition {
+            Some(transition) => NavigationState::Pending {
+                from: transition.from.clone(),
+                to: transition.to.clone(),
+            },
+            None => NavigationState::Idle,
+        }
+    }
+
+    /// The route a pending transition started at. The outlets keep rendering it until the new route is ready.
+    pub(crate) fn transition_from<R: Routable>(&self) -> Option<R> {
+        let state = self.state.read().unwrap();
+        R::from_str(&state.transition.as_ref()?.from).ok()
+    }
+
+    /// Called by the root outlet after it rendered the current route. Once the new route of a transition was
+    /// rendered, the router checks if the route is suspended.
+    pub(crate) fn transition_rendered(&self) {
+        let mut state = self.state_mut();
+        if let Some(transition) = &mut state.transition {
+            if !transition.rendered {
+                transition.rendered = true;
+                (self.subscriber_update)(self.suspense.id());
+            }
+        }
+    }
+
+    /// Called by the router component and the root outlet whenever they render. Web histories change the url once the
+    /// renderer shows the rendered route and none of its components are suspended.
+    pub(crate) fn route_rendered(&self) {
+        #[cfg(feature = "web")]
+        crate::history::web_history::route_rendered(self.suspense.clone());
+    }
+
+    /// Called by the root outlet before it renders the current route. Starts the loader of the route when the route
+    /// changes, including when only the parameters of the route change.
+    pub(crate) fn run_loader<R: Routable>(&self, cx: &ScopeState) {
+        let route = self.current::<R>();
+        let key = route.to_string();
+
+        let mut loader = self.loader.borrow_mut();
+        if loader.route.as_ref() == Some(&key) {
+            return;
+        }
+        if let Some(task) = loader.task.take() {
+            cx.remove_future(task);
+        }
+        loader.route = Some(key);
+        loader.data = None;
+
+        if let Some(load) = route.load() {
+            let router = self.clone();
+            loader.task = Some(cx.push_future(async move {
+                let data = load.await;
+                {
+                    let mut loader = router.loader.borrow_mut();
+                    loader.data = Some(data);
+                    loader.task = None;
+                }
+                router.update_subscribers();
+            }));
+        }
+    }
+
+    /// The data of the current route, if it was loaded
+    pub(crate) fn loader_data(&self) -> Option<Rc<dyn Any>> {
+        self.loader.borrow().data.clone()
+    }
+
+    /// Check whether the loader of the current route is still running
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loader.borrow().task.is_some()
+    }
+
+    /// Called by the router component whenever it renders. Finishes the pending transition once the new route was
+    /// rendered and none of its components are suspended.
+    pub(crate) fn settle_transition(&self) {
+        let finished = {
+            let mut state = self.state_mut();
+            let finished = matches!(&state.transition, Some(transition) if transition.rendered)
+                && !self.suspense.is_suspended();
+            if finished {
+                state.transition = None;
+            }
+            finished
+        };
+
+        if finished {
+            self.update_subscribers();
+        }
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
     pub fn replace(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
 
-        {
-            let mut state = self.state_mut();
-            match target {
-                NavigationTarget::Internal(p) => state.history.replace(p),
-                NavigationTarget::External(e) => return self.external(e),
+        match target {
+            NavigationTarget::Internal(p) => {
+                let current = self.current_route_string();
+                let mut state = self.state_mut();
+                if state.modal.as_ref() == Some(&current) {
+                    state.modal = None;
+                }
+                state.history.replace(p)
             }
+            NavigationTarget::External(e) => return self.external(e),
+            NavigationTarget::Relative(path) => return self.replace(path),
         }
 
         self.change_route()
@@ -216,21 +400,84 @@ impl RouterContext {
             .clone()
     }
 
+    /// The breadcrumbs of the route that is currently active: the ancestors of the route followed by the route
+    /// itself, with a label for each of them.
+    ///
+    /// The label is the title from the `#[meta]` of the route, or the last segment of its path.
+    pub fn breadcrumbs<R: Routable>(&self) -> Vec<(String, R)> {
+        let current = self.current::<R>();
+        let mut crumbs = current.ancestors();
+        crumbs.push(current);
+        crumbs
+            .into_iter()
+            .map(|route| (breadcrumb_label(&route), route))
+            .collect()
+    }
+
+    /// Push the route of a modal and remember that the new history entry belongs to that modal.
+    pub(crate) fn open_modal(&self, target: impl Into<IntoRoutable>) {
+        let target = self.resolve_into_routable(target.into());
+        let internal = matches!(target, NavigationTarget::Internal(_));
+        self.push_any(target);
+        if internal {
+            let route = self.current_route_string();
+            self.state_mut().modal = Some(route);
+        }
+    }
+
+    /// Check whether the current history entry was pushed by opening a modal.
+    pub(crate) fn modal_is_open(&self) -> bool {
+        let current = self.current_route_string();
+        self.state.read().unwrap().modal.as_ref() == Some(&current)
+    }
+
+    /// Pop the history entry of the open modal, if there is one.
+    pub(crate) fn close_modal(&self) {
+        // The modal stays in the forward history, so going forward reopens it
+        if self.modal_is_open() {
+            self.go_back();
+        }
+    }
+
     /// The route that is currently active.
     pub fn current_route_string(&self) -> String {
         self.any_route_to_string(&*self.state.read().unwrap().history.current_route())
     }
 
+    /// The metadata of the route that is currently active.
+    pub fn current_meta(&self) -> RouteMeta {
+        (self.any_route_meta)(&*self.state.read().unwrap().history.current_route())
+    }
+
     pub(crate) fn any_route_to_string(&self, route: &dyn Any) -> String {
         (self.any_route_to_string)(route)
     }
 
+    /// Add the prefix of the history provider to a route, to create an `href` that points to it
+    pub(crate) fn full_path(&self, route: &str) -> String {
+        match self.state.read().unwrap().history.current_prefix() {
+            Some(prefix) => format!("{}{}", prefix, route),
+            None => route.to_string(),
+        }
+    }
+
+    /// Resolve a path that is relative to the current route, like `../sibling`, to an absolute path. Other paths
+    /// are returned unchanged.
+    pub(crate) fn resolve_relative(&self, path: &str) -> String {
+        if is_relative(path) {
+            resolve_relative(&self.current_route_string(), path)
+        } else {
+            path.to_string()
+        }
+    }
+
     pub(crate) fn resolve_into_routable(
         &self,
         into_routable: IntoRoutable,
     ) -> NavigationTarget<Rc<dyn Any>> {
         match into_routable {
             IntoRoutable::FromStr(url) => {
+                let url = self.resolve_relative(&url);
                 let parsed_route: NavigationTarget<Rc<dyn Any>> = match self.route_from_str(&url) {
                     Ok(route) => NavigationTarget::Internal(route),
                     Err(err) => NavigationTarget::External(err),
@@ -301,20 +548,34 @@ impl RouterContext {
         if let Some(callback) = &self.routing_callback {
             let myself = self.clone();
             if let Some(new) = callback(myself) {
-                let mut state = self.state_mut();
                 match new {
-                    NavigationTarget::Internal(p) => state.history.replace(p),
+                    NavigationTarget::Internal(p) => self.state_mut().history.replace(p),
                     NavigationTarget::External(e) => return self.external(e),
+                    NavigationTarget::Relative(path) => return self.replace(path),
                 }
             }
         }
 
+        // Any other navigation cancels the pending transition
+        self.state_mut().transition = None;
         self.update_subscribers();
 
         None
     }
 }
 
+fn breadcrumb_label<R: Routable>(route: &R) -> String {
+    if let Some(title) = route.meta().title {
+        return title.to_string();
+    }
+    let path = route.to_string();
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    match path.trim_end_matches('/').rsplit('/').next() {
+        Some(segment) if !segment.is_empty() => segment.to_string(),
+        _ => "/".to_string(),
+    }
+}
+
 pub struct GenericRouterContext<R> {
     inner: RouterContext,
     _marker: std::marker::PhantomData<R>,
@@ -360,6 +621,21 @@ where
         self.inner.push(target.into())
     }
 
+    /// Push a new location, and track the navigation until the new route is ready.
+    ///
+    /// See [`RouterContext::navigate_with_transition`].
+    pub fn navigate_with_transition(
+        &self,
+        target: impl Into<NavigationTarget<R>>,
+    ) -> Option<ExternalNavigationFailure> {
+        self.inner.navigate_with_transition(target.into())
+    }
+
+    /// The state of the last navigation started with [`GenericRouterContext::navigate_with_transition`].
+    pub fn navigation_state(&self) -> NavigationState {
+        self.inner.navigation_state()
+    }
+
     /// Replace the current location.
     ///
     /// The previous location will **not** be available to go back to.
@@ -383,6 +659,11 @@ where
         self.inner.prefix()
     }
 
+    /// The breadcrumbs of the route that is currently active. See [`RouterContext::breadcrumbs`].
+    pub fn breadcrumbs(&self) -> Vec<(String, R)> {
+        self.inner.breadcrumbs()
+    }
+
     /// Manually subscribe to the current route
     pub fn subscribe(&self, id: ScopeId) {
         self.inner.subscribe(id)
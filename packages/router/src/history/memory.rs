@@ -9,6 +9,7 @@ pub struct MemoryHistory<R: Routable> {
     current: R,
     history: Vec<R>,
     future: Vec<R>,
+    prefix: Option<String>,
 }
 
 impl<R: Routable> MemoryHistory<R>
@@ -41,8 +42,16 @@ where
             current: path,
             history: Vec::new(),
             future: Vec::new(),
+            prefix: None,
         }
     }
+
+    /// Set the prefix of the links to the routes, like `#` for the links of an app that runs in
+    /// [`HistoryMode::Hash`](super::HistoryMode::Hash) in the browser.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
 }
 
 impl<R: Routable> Default for MemoryHistory<R>
@@ -56,6 +65,7 @@ where
             }),
             history: Vec::new(),
             future: Vec::new(),
+            prefix: None,
         }
     }
 }
@@ -65,6 +75,10 @@ impl<R: Routable> HistoryProvider<R> for MemoryHistory<R> {
         self.current.clone()
     }
 
+    fn current_prefix(&self) -> Option<String> {
+        self.prefix.clone()
+    }
+
     fn can_go_back(&self) -> bool {
         !self.history.is_empty()
     }
@@ -6,9 +6,10 @@
 //! To integrate dioxus-router with a any type of history, all you have to do is implement the
 //! [`HistoryProvider`] trait.
 //!
-//! dioxus-router contains two built in history providers:
+//! dioxus-router contains three built in history providers:
 //! 1) [`MemoryHistory`] for desktop/mobile/ssr platforms
 //! 2) [`WebHistory`] for web platforms
+//! 3) [`WebHashHistory`] for web platforms that store the route in the hash of the URL
 
 use std::{any::Any, rc::Rc, sync::Arc};
 
@@ -22,16 +23,46 @@ pub use web::*;
 #[cfg(feature = "web")]
 pub(crate) mod web_history;
 
-// #[cfg(feature = "web")]
-// mod web_hash;
-// #[cfg(feature = "web")]
-// pub use web_hash::*;
+#[cfg(feature = "web")]
+mod web_hash;
+#[cfg(feature = "web")]
+pub use web_hash::*;
 
 use crate::routable::Routable;
 
 #[cfg(feature = "web")]
 pub(crate) mod web_scroll;
 
+/// Where the router stores the route in the URL of the browser
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Store the route in the path of the URL: `/blog/1`
+    #[default]
+    Path,
+    /// Store the route in the hash of the URL: `/#/blog/1`. Use this for apps served from static file hosts that can't
+    /// serve the app for every path.
+    Hash,
+}
+
+/// What happens to the scroll position of the page when the route changes in a browser
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollBehavior {
+    /// Scroll to the top of the page after navigating to a new route, and restore the scroll position of a route when
+    /// going back or forward to it
+    #[default]
+    Restore,
+    /// Scroll to the top of the page after every route change, including going back and forward
+    Top,
+    /// Leave the scroll position to the browser. It stays where it is after navigating to a new route, and the browser
+    /// restores it when going back or forward.
+    Auto,
+}
+
+/// A CSS selector for the first heading or the main landmark of the page, whichever comes first. Pass it to
+/// [`RouteAnnouncer::focus`](crate::prelude::RouteAnnouncer::focus) to move the focus to the start of the new content
+/// after navigating.
+pub const HEADING_OR_MAIN: &str = "main, h1, h2, h3, h4, h5, h6";
+
 /// An integration with some kind of navigation history.
 ///
 /// Depending on your use case, your implementation may deviate from the described procedure. This
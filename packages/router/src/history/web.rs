@@ -1,6 +1,6 @@
-use std::sync::{Arc, Mutex};
+use std::{cell::RefCell, rc::Rc};
 
-use gloo::{console::error, events::EventListener, render::AnimationFrame};
+use gloo::{console::error, events::EventListener};
 
 use wasm_bindgen::JsValue;
 use web_sys::{window, History, ScrollRestoration, Window};
@@ -8,9 +8,12 @@ use web_sys::{window, History, ScrollRestoration, Window};
 use crate::routable::Routable;
 
 use super::{
-    web_history::{get_current, push_state_and_url, replace_state_with_url},
+    web_history::{
+        after_route_render, get_current, mark_route_transition, push_state_and_url,
+        replace_state_with_url,
+    },
     web_scroll::ScrollPosition,
-    HistoryProvider,
+    HistoryProvider, ScrollBehavior,
 };
 
 #[cfg(not(feature = "serde"))]
@@ -55,11 +58,16 @@ struct WebHistoryState<R> {
 ///
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
+///
+/// # Navigation
+/// The URL changes once the renderer shows the new route, together with the scroll position, so the URL never
+/// points to a page that isn't on the screen yet.
 pub struct WebHistory<R: Routable> {
-    do_scroll_restoration: bool,
+    scroll_behavior: ScrollBehavior,
     history: History,
     listener_navigation: Option<EventListener>,
-    listener_animation_frame: Arc<Mutex<Option<AnimationFrame>>>,
+    /// The routes that were navigated to, but aren't in the URL yet because the page doesn't show them yet
+    pending: Rc<RefCell<Vec<R>>>,
     prefix: Option<String>,
     window: Window,
     phantom: std::marker::PhantomData<R>,
@@ -161,22 +169,89 @@ impl<R: Routable> WebHistory<R> {
         }
 
         Self {
-            do_scroll_restoration,
+            scroll_behavior: match do_scroll_restoration {
+                true => ScrollBehavior::Restore,
+                false => ScrollBehavior::Auto,
+            },
             history,
             listener_navigation: None,
-            listener_animation_frame: Default::default(),
+            pending: Default::default(),
             prefix,
             window,
             phantom: Default::default(),
         }
     }
 
+    /// Set what happens to the scroll position when the route changes. Defaults to [`ScrollBehavior::Restore`] if
+    /// `do_scroll_restoration` is [`true`], and to [`ScrollBehavior::Auto`] otherwise.
+    pub fn with_scroll_behavior(mut self, behavior: ScrollBehavior) -> Self {
+        let restoration = match behavior {
+            ScrollBehavior::Auto => ScrollRestoration::Auto,
+            ScrollBehavior::Restore | ScrollBehavior::Top => ScrollRestoration::Manual,
+        };
+        if let Err(err) = self.history.set_scroll_restoration(restoration) {
+            error!(err);
+        }
+        self.scroll_behavior = behavior;
+        self
+    }
+
     fn scroll_pos(&self) -> ScrollPosition {
-        self.do_scroll_restoration
+        (self.scroll_behavior == ScrollBehavior::Restore)
             .then(|| ScrollPosition::of_window(&self.window))
             .unwrap_or_default()
     }
 
+    fn pending_route(&self) -> Option<R> {
+        self.pending.borrow().last().cloned()
+    }
+
+    /// Change the URL with `update` once the page shows the new route
+    fn navigate<S: 'static>(
+        &self,
+        route: R,
+        state: S,
+        update: impl FnOnce(&History, &S) -> Result<(), JsValue> + 'static,
+    ) {
+        mark_route_transition(&self.window);
+        self.pending.borrow_mut().push(route);
+
+        let history = self.history.clone();
+        let window = self.window.clone();
+        let pending = self.pending.clone();
+        let scroll = (self.scroll_behavior != ScrollBehavior::Auto).then(ScrollPosition::default);
+        after_route_render(move || {
+            // navigations finish in the order they started
+            pending.borrow_mut().remove(0);
+            match update(&history, &state) {
+                Ok(_) => {
+                    if let Some(scroll) = scroll {
+                        scroll.scroll_to(&window);
+                    }
+                }
+                Err(e) => error!("failed to change state: ", e),
+            }
+        });
+    }
+
+    /// Restore the scroll position once the page shows the route the browser navigated to
+    fn on_popstate(&self, saved_scroll: fn(&History) -> Option<ScrollPosition>) -> impl Fn() {
+        let window = self.window.clone();
+        let history = self.history.clone();
+        let behavior = self.scroll_behavior;
+        move || {
+            let scroll = match behavior {
+                ScrollBehavior::Restore => saved_scroll(&history),
+                ScrollBehavior::Top => Some(ScrollPosition::default()),
+                ScrollBehavior::Auto => None,
+            };
+            if let Some(scroll) = scroll {
+                let window = window.clone();
+                after_route_render(move || scroll.scroll_to(&window));
+            }
+        }
+    }
+
     #[cfg(not(feature = "serde"))]
     fn create_state(&self, _state: R) -> [f64; 2] {
         let scroll = self.scroll_pos();
@@ -212,17 +287,6 @@ where
         }
     }
 
-    fn handle_nav(&self, result: Result<(), JsValue>) {
-        match result {
-            Ok(_) => {
-                if self.do_scroll_restoration {
-                    self.window.scroll_to_with_x_and_y(0.0, 0.0)
-                }
-            }
-            Err(e) => error!("failed to change state: ", e),
-        }
-    }
-
     fn navigate_external(&mut self, url: String) -> bool {
         match self.window.location().set_href(&url) {
             Ok(_) => true,
@@ -241,6 +305,9 @@ where
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn current_route(&self) -> R {
+        if let Some(route) = self.pending_route() {
+            return route;
+        }
         match get_current::<WebHistoryState<_>>(&self.history) {
             // Try to get the route from the history state
             Some(route) => route.state,
@@ -280,20 +347,21 @@ where
 
         let path = self.full_path(&state);
 
-        let state = self.create_state(state);
+        let history_state = self.create_state(state.clone());
 
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        self.navigate(state, history_state, move |history, state| {
+            push_state_and_url(history, state, path)
+        });
     }
 
     fn replace(&mut self, state: R) {
-        let path = match &self.prefix {
-            None => format!("{state}"),
-            Some(prefix) => format!("{prefix}{state}"),
-        };
+        let path = self.full_path(&state);
 
-        let state = self.create_state(state);
+        let history_state = self.create_state(state.clone());
 
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.navigate(state, history_state, move |history, state| {
+            replace_state_with_url(history, state, Some(&path))
+        });
     }
 
     fn external(&mut self, url: String) -> bool {
@@ -302,18 +370,14 @@ where
 
     fn updater(&mut self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
-        let h = self.history.clone();
-        let s = self.listener_animation_frame.clone();
-        let d = self.do_scroll_restoration;
+        let on_popstate = self.on_popstate(|history| {
+            get_current::<WebHistoryState<R>>(history).map(|state| state.scroll)
+        });
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
+            mark_route_transition(&w);
             (*callback)();
-            if d {
-                let mut s = s.lock().expect("unpoisoned scroll mutex");
-                if let Some(current_state) = get_current::<WebHistoryState<R>>(&h) {
-                    *s = Some(current_state.scroll.scroll_to(w.clone()));
-                }
-            }
+            on_popstate();
         }));
     }
 }
@@ -324,7 +388,8 @@ where
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
     fn current_route(&self) -> R {
-        self.route_from_location()
+        self.pending_route()
+            .unwrap_or_else(|| self.route_from_location())
     }
 
     fn current_prefix(&self) -> Option<String> {
@@ -357,18 +422,19 @@ where
 
         let path = self.full_path(&state);
 
-        let state: [f64; 2] = self.create_state(state);
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        let history_state: [f64; 2] = self.create_state(state.clone());
+        self.navigate(state, history_state, move |history, state| {
+            push_state_and_url(history, state, path)
+        });
     }
 
     fn replace(&mut self, state: R) {
-        let path = match &self.prefix {
-            None => format!("{state}"),
-            Some(prefix) => format!("{prefix}{state}"),
-        };
+        let path = self.full_path(&state);
 
-        let state = self.create_state(state);
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        let history_state = self.create_state(state.clone());
+        self.navigate(state, history_state, move |history, state| {
+            replace_state_with_url(history, state, Some(&path))
+        });
     }
 
     fn external(&mut self, url: String) -> bool {
@@ -377,18 +443,13 @@ where
 
     fn updater(&mut self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
-        let h = self.history.clone();
-        let s = self.listener_animation_frame.clone();
-        let d = self.do_scroll_restoration;
+        let on_popstate =
+            self.on_popstate(|history| get_current(history).map(|[x, y]| ScrollPosition { x, y }));
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
+            mark_route_transition(&w);
             (*callback)();
-            if d {
-                let mut s = s.lock().expect("unpoisoned scroll mutex");
-                if let Some([x, y]) = get_current(&h) {
-                    *s = Some(ScrollPosition { x, y }.scroll_to(w.clone()));
-                }
-            }
+            on_popstate();
         }));
     }
 }
@@ -1,125 +1,126 @@
-use std::sync::{Arc, Mutex};
+use std::{cell::RefCell, rc::Rc};
 
-use gloo::{events::EventListener, render::AnimationFrame, utils::window};
-use log::error;
-use serde::{de::DeserializeOwned, Serialize};
-use url::Url;
-use web_sys::{History, ScrollRestoration, Window};
+use gloo::{console::error, events::EventListener};
+use wasm_bindgen::JsValue;
+use web_sys::{window, History, ScrollRestoration, Window};
 
 use crate::routable::Routable;
 
-use super::HistoryProvider;
-
-const INITIAL_URL: &str = "dioxus-router-core://initial_url.invalid/";
+use super::{
+    web_history::{after_route_render, mark_route_transition},
+    web_scroll::ScrollPosition,
+    HistoryProvider, ScrollBehavior,
+};
+
+// Hashes that are not a route show the not-found route of the app. Apps without a fallback route show the root route
+// instead, the same as hashes that are not paths, like links to an anchor on the page.
+fn route_from_hash<R: std::str::FromStr>(hash: &str) -> R
+where
+    R::Err: std::fmt::Display,
+{
+    let path = hash.trim_start_matches('#');
+    path.starts_with('/')
+        .then(|| R::from_str(path).ok())
+        .flatten()
+        .unwrap_or_else(|| {
+            R::from_str("/").unwrap_or_else(|err| panic!("index route does not exist:\n{}", err))
+        })
+}
 
-/// A [`HistoryProvider`] that integrates with a browser via the [History API]. It uses the URLs
-/// hash instead of its path.
+/// A [`HistoryProvider`] that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
+/// It stores the route in the hash of the URL instead of its path: `/#/blog/1` instead of `/blog/1`.
 ///
-/// Early web applications used the hash to store the current path because there was no other way
-/// for them to interact with the history without triggering a browser navigation, as the
-/// [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API) did not yet exist. While this implementation could have been written that way, it
-/// was not, because no browser supports WebAssembly without the [History API].
-pub struct WebHashHistory<R: Serialize + DeserializeOwned> {
-    do_scroll_restoration: bool,
+/// Static file hosts that can't serve the app for every path only ever see requests for `/`, so the app works there
+/// without rewriting URLs. [`Link`](crate::components::Link)s point to the hash of their route.
+///
+/// Like [`WebHistory`](super::WebHistory), the URL changes once the renderer shows the new route. The history state
+/// doesn't store scroll positions, so [`ScrollBehavior::Restore`] leaves restoring them to the browser.
+pub struct WebHashHistory<R: Routable> {
+    scroll_behavior: ScrollBehavior,
     history: History,
     listener_navigation: Option<EventListener>,
-    #[allow(dead_code)]
-    listener_scroll: Option<EventListener>,
-    listener_animation_frame: Arc<Mutex<Option<AnimationFrame>>>,
+    /// The routes that were navigated to, but aren't in the URL yet because the page doesn't show them yet
+    pending: Rc<RefCell<Vec<R>>>,
     window: Window,
     phantom: std::marker::PhantomData<R>,
 }
 
-impl<R: Serialize + DeserializeOwned> WebHashHistory<R> {
+impl<R: Routable> Default for WebHashHistory<R>
+where
+    <R as std::str::FromStr>::Err: std::fmt::Display,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Routable> WebHashHistory<R>
+where
+    <R as std::str::FromStr>::Err: std::fmt::Display,
+{
     /// Create a new [`WebHashHistory`].
-    ///
-    /// If `do_scroll_restoration` is [`true`], [`WebHashHistory`] will take control of the history
-    /// state. It'll also set the browsers scroll restoration to `manual`.
-    pub fn new(do_scroll_restoration: bool) -> Self {
-        let window = window();
+    pub fn new() -> Self {
+        let window = window().expect("access to `window`");
         let history = window.history().expect("`window` has access to `history`");
 
-        history
-            .set_scroll_restoration(ScrollRestoration::Manual)
-            .expect("`history` can set scroll restoration");
-
-        let listener_scroll = match do_scroll_restoration {
-            true => {
-                history
-                    .set_scroll_restoration(ScrollRestoration::Manual)
-                    .expect("`history` can set scroll restoration");
-                let w = window.clone();
-                let h = history.clone();
-                let document = w.document().expect("`window` has access to `document`");
-
-                Some(EventListener::new(&document, "scroll", move |_| {
-                    update_history(&w, &h);
-                }))
-            }
-            false => None,
-        };
-
         Self {
-            do_scroll_restoration,
+            scroll_behavior: ScrollBehavior::Restore,
             history,
             listener_navigation: None,
-            listener_scroll,
-            listener_animation_frame: Default::default(),
+            pending: Default::default(),
             window,
             phantom: Default::default(),
         }
     }
-}
 
-impl<R: Serialize + DeserializeOwned> WebHashHistory<R> {
-    fn join_url_to_hash(&self, path: R) -> Option<String> {
-        let url = match self.url() {
-            Some(c) => match c.join(&path) {
-                Ok(new) => new,
-                Err(e) => {
-                    error!("failed to join location with target: {e}");
-                    return None;
-                }
-            },
-            None => {
-                error!("current location unknown");
-                return None;
-            }
-        };
-
-        Some(format!(
-            "#{path}{query}",
-            path = url.path(),
-            query = url.query().map(|q| format!("?{q}")).unwrap_or_default()
-        ))
+    fn route_from_location(&self) -> R {
+        route_from_hash(&self.window.location().hash().unwrap_or_default())
     }
 
-    fn url(&self) -> Option<Url> {
-        let mut path = self.window.location().hash().ok()?;
-
-        if path.starts_with('#') {
-            path.remove(0);
-        }
-
-        if path.starts_with('/') {
-            path.remove(0);
+    /// Set what happens to the scroll position when the route changes. Defaults to [`ScrollBehavior::Restore`].
+    pub fn with_scroll_behavior(mut self, behavior: ScrollBehavior) -> Self {
+        let restoration = match behavior {
+            ScrollBehavior::Top => ScrollRestoration::Manual,
+            ScrollBehavior::Restore | ScrollBehavior::Auto => ScrollRestoration::Auto,
+        };
+        if let Err(err) = self.history.set_scroll_restoration(restoration) {
+            error!(err);
         }
+        self.scroll_behavior = behavior;
+        self
+    }
 
-        match Url::parse(&format!("{INITIAL_URL}/{path}")) {
-            Ok(url) => Some(url),
-            Err(e) => {
-                error!("failed to parse hash path: {e}");
-                None
+    /// Change the URL with `update` once the page shows the new route
+    fn navigate(&self, route: R, update: impl FnOnce(&History) -> Result<(), JsValue> + 'static) {
+        mark_route_transition(&self.window);
+        self.pending.borrow_mut().push(route);
+
+        let history = self.history.clone();
+        let window = self.window.clone();
+        let pending = self.pending.clone();
+        let scroll = (self.scroll_behavior != ScrollBehavior::Auto).then(ScrollPosition::default);
+        after_route_render(move || {
+            // navigations finish in the order they started
+            pending.borrow_mut().remove(0);
+            match update(&history) {
+                Ok(_) => {
+                    if let Some(scroll) = scroll {
+                        scroll.scroll_to(&window);
+                    }
+                }
+                Err(e) => error!("failed to change state: ", e),
             }
-        }
+        });
     }
 }
 
-impl<R: Serialize + DeserializeOwned + Routable> HistoryProvider<R> for WebHashHistory<R> {
+impl<R: Routable> HistoryProvider<R> for WebHashHistory<R>
+where
+    <R as std::str::FromStr>::Err: std::fmt::Display,
+{
     fn current_route(&self) -> R {
-        self.url()
-            .map(|url| url.path().to_string())
-            .unwrap_or(String::from("/"))
+        let pending = self.pending.borrow().last().cloned();
+        pending.unwrap_or_else(|| self.route_from_location())
     }
 
     fn current_prefix(&self) -> Option<String> {
@@ -128,67 +129,40 @@ impl<R: Serialize + DeserializeOwned + Routable> HistoryProvider<R> for WebHashH
 
     fn go_back(&mut self) {
         if let Err(e) = self.history.back() {
-            error!("failed to go back: {e:?}")
+            error!("failed to go back: ", e)
         }
     }
 
     fn go_forward(&mut self) {
         if let Err(e) = self.history.forward() {
-            error!("failed to go forward: {e:?}")
+            error!("failed to go forward: ", e)
         }
     }
 
-    fn push(&mut self, path: R) {
-        let hash = match self.join_url_to_hash(path) {
-            Some(hash) => hash,
-            None => return,
-        };
-
-        let state = match self.do_scroll_restoration {
-            true => top_left(),
-            false => self.history.state().unwrap_or_default(),
-        };
-
-        let nav = self.history.push_state_with_url(&state, "", Some(&hash));
-
-        match nav {
-            Ok(_) => {
-                if self.do_scroll_restoration {
-                    self.window.scroll_to_with_x_and_y(0.0, 0.0)
-                }
-            }
-            Err(e) => error!("failed to push state: {e:?}"),
+    fn push(&mut self, state: R) {
+        if state.to_string() == self.current_route().to_string() {
+            // don't push the same state twice
+            return;
         }
-    }
-
-    fn replace(&mut self, path: R) {
-        let hash = match self.join_url_to_hash(path) {
-            Some(hash) => hash,
-            None => return,
-        };
-
-        let state = match self.do_scroll_restoration {
-            true => top_left(),
-            false => self.history.state().unwrap_or_default(),
-        };
 
-        let nav = self.history.replace_state_with_url(&state, "", Some(&hash));
+        let url = format!("#{}", state);
+        self.navigate(state, move |history| {
+            history.push_state_with_url(&JsValue::NULL, "", Some(&url))
+        });
+    }
 
-        match nav {
-            Ok(_) => {
-                if self.do_scroll_restoration {
-                    self.window.scroll_to_with_x_and_y(0.0, 0.0)
-                }
-            }
-            Err(e) => error!("failed to replace state: {e:?}"),
-        }
+    fn replace(&mut self, state: R) {
+        let url = format!("#{}", state);
+        self.navigate(state, move |history| {
+            history.replace_state_with_url(&JsValue::NULL, "", Some(&url))
+        });
     }
 
     fn external(&mut self, url: String) -> bool {
         match self.window.location().set_href(&url) {
             Ok(_) => true,
             Err(e) => {
-                error!("failed to navigate to external url (`{url}): {e:?}");
+                error!("failed to navigate to external url (", url, "): ", e);
                 false
             }
         }
@@ -196,16 +170,49 @@ impl<R: Serialize + DeserializeOwned + Routable> HistoryProvider<R> for WebHashH
 
     fn updater(&mut self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
-        let h = self.history.clone();
-        let s = self.listener_animation_frame.clone();
-        let d = self.do_scroll_restoration;
+        let scroll = (self.scroll_behavior == ScrollBehavior::Top).then(ScrollPosition::default);
 
+        // Browsers fire popstate when the user edits the hash, as well as for the back and forward buttons
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
+            mark_route_transition(&w);
             (*callback)();
-            if d {
-                let mut s = s.lock().expect("unpoisoned scroll mutex");
-                *s = Some(update_scroll(&w, &h));
+            if let Some(scroll) = scroll {
+                let window = w.clone();
+                after_route_render(move || scroll.scroll_to(&window));
             }
         }));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Route {
+        Home,
+        Post(usize),
+    }
+
+    impl std::str::FromStr for Route {
+        type Err = String;
+
+        fn from_str(path: &str) -> Result<Self, Self::Err> {
+            match path.strip_prefix("/post/") {
+                Some(id) => id.parse().map(Route::Post).map_err(|_| path.to_string()),
+                None if path == "/" => Ok(Route::Home),
+                None => Err(path.to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_the_route_from_the_hash() {
+        assert_eq!(route_from_hash::<Route>(""), Route::Home);
+        assert_eq!(route_from_hash::<Route>("#/"), Route::Home);
+        assert_eq!(route_from_hash::<Route>("#/post/1"), Route::Post(1));
+        // hashes that are not a route show the root route instead of panicking
+        assert_eq!(route_from_hash::<Route>("#/post/abc"), Route::Home);
+        assert_eq!(route_from_hash::<Route>("#top"), Route::Home);
+    }
+}
@@ -1,8 +1,11 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::{core::SuspenseContext, prelude::after_render};
 use gloo::console::error;
 #[cfg(feature = "serde")]
 use gloo_utils::format::JsValueSerdeExt;
 use wasm_bindgen::JsValue;
-use web_sys::History;
+use web_sys::{History, Window};
 
 #[cfg(not(feature = "serde"))]
 pub(crate) fn replace_state_with_url(
@@ -82,3 +85,41 @@ pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
         Some([x, y])
     })
 }
+
+/// Mark the start of a route transition on the performance timeline. Renderers measure the transition once they
+/// rendered the new route. Without the `performance` feature, nothing is recorded.
+pub(crate) fn mark_route_transition(window: &Window) {
+    #[cfg(feature = "performance")]
+    if let Some(performance) = window.performance() {
+        let _ = performance.mark("dioxus-route-transition-start");
+    }
+    #[cfg(not(feature = "performance"))]
+    let _ = window;
+}
+
+thread_local! {
+    /// The navigations that wait for the page to show their route
+    static AFTER_ROUTE_RENDER: RefCell<Vec<Box<dyn FnOnce()>>> = RefCell::new(Vec::new());
+}
+
+/// Run a navigation once the page shows the new route, so the url and the scroll position change together with the
+/// page. The navigation waits for the router to render the route with [`route_rendered`].
+pub(crate) fn after_route_render(navigation: impl FnOnce() + 'static) {
+    AFTER_ROUTE_RENDER.with(|navigations| navigations.borrow_mut().push(Box::new(navigation)));
+}
+
+/// Called whenever the router rendered the current route. Finishes the waiting navigations once the renderer applied
+/// the edits and none of the components of the router are suspended, so slow routes keep the old url until they are
+/// ready.
+pub(crate) fn route_rendered(suspense: Rc<SuspenseContext>) {
+    after_render(move || {
+        if suspense.is_suspended() {
+            return;
+        }
+        // navigations finish in the order they started
+        let navigations = AFTER_ROUTE_RENDER.with(|navigations| navigations.take());
+        for navigation in navigations {
+            navigation();
+        }
+    });
+}
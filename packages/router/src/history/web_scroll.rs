@@ -1,4 +1,3 @@
-use gloo::render::{request_animation_frame, AnimationFrame};
 use web_sys::Window;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -16,8 +15,7 @@ impl ScrollPosition {
         }
     }
 
-    pub(crate) fn scroll_to(&self, window: Window) -> AnimationFrame {
-        let Self { x, y } = *self;
-        request_animation_frame(move |_| window.scroll_to_with_x_and_y(x, y))
+    pub(crate) fn scroll_to(&self, window: &Window) {
+        window.scroll_to_with_x_and_y(self.x, self.y)
     }
 }
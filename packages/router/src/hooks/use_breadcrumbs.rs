@@ -0,0 +1,39 @@
+use dioxus::prelude::ScopeState;
+
+use crate::routable::Routable;
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that provides the breadcrumbs of the current route: the routes it is nested in followed by the route itself,
+/// with a label for each of them. The component is rerendered when the route changes.
+///
+/// The label of a breadcrumb is the title from the `#[meta]` attribute of the route, or the last segment of its path.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::prelude::Router) component during a debug build.
+///
+/// # Example
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Breadcrumbs(cx: Scope) -> Element {
+///     let crumbs = use_breadcrumbs::<Route>(cx);
+///
+///     render! {
+///         nav {
+///             crumbs.into_iter().map(|(label, route)| rsx! {
+///                 Link { to: route, "{label}" }
+///             })
+///         }
+///     }
+/// }
+/// ```
+pub fn use_breadcrumbs<R: Routable>(cx: &ScopeState) -> Vec<(String, R)> {
+    match use_router_internal(cx) {
+        Some(router) => router.breadcrumbs(),
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`use_breadcrumbs` must have access to a parent router");
+            #[allow(unreachable_code)]
+            Vec::new()
+        }
+    }
+}
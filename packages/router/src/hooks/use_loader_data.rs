@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use dioxus::prelude::ScopeState;
+
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that provides the data of the current route, loaded by the loader declared with the `#[loader(function)]`
+/// attribute when deriving Routable. The component is rerendered when the data is loaded, and when the route changes.
+///
+/// While the data is loading, the component is suspended and the hook returns [`None`], so the component can return
+/// early with `?`. The hook also returns [`None`] if the route has no loader, or if the data is not a `T`.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::prelude::Router) component during a debug build.
+///
+/// # Example
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Post(cx: Scope, id: usize) -> Element {
+///     let post = use_loader_data::<String>(cx)?;
+///
+///     render! {
+///         p { "{post}" }
+///     }
+/// }
+/// ```
+pub fn use_loader_data<T: 'static>(cx: &ScopeState) -> Option<Rc<T>> {
+    let router = match use_router_internal(cx) {
+        Some(router) => router,
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`use_loader_data` must have access to a parent router");
+            #[allow(unreachable_code)]
+            return None;
+        }
+    };
+
+    match router.loader_data() {
+        Some(data) => match data.downcast::<T>() {
+            Ok(data) => Some(data),
+            Err(_) => {
+                log::error!(
+                    "The data of the current route is not a {}",
+                    std::any::type_name::<T>()
+                );
+                None
+            }
+        },
+        None => {
+            if router.is_loading() {
+                cx.suspend();
+            }
+            None
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use dioxus::html::input_data::keyboard_types::Key;
+use dioxus::prelude::{KeyboardData, ScopeState};
+
+use crate::prelude::*;
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that ties the open state of a modal to the navigation history.
+///
+/// Opening a modal pushes the modal's route onto the history, so the browsers back button closes
+/// the modal instead of leaving the page. The modal counts as open as long as the history entry
+/// pushed for it is the current one. Every [`ModalRoute`] of a router shares that state, so the
+/// modal can be opened by one component and closed by another.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     #[route("/login")]
+///     Login {},
+/// }
+///
+/// fn App(cx: Scope) -> Element {
+///     render! {
+///         Router::<Route> {
+///             config: || RouterConfig::default().history(MemoryHistory::default())
+///         }
+///     }
+/// }
+///
+/// #[inline_props]
+/// fn Index(cx: Scope) -> Element {
+///     let modal = use_modal_route(cx);
+///
+///     render! {
+///         button {
+///             onclick: move |_| modal.open(Route::Login {}),
+///             "Log in"
+///         }
+///     }
+/// }
+///
+/// #[inline_props]
+/// fn Login(cx: Scope) -> Element {
+///     let modal = use_modal_route(cx);
+///
+///     render! {
+///         div {
+///             onkeydown: move |evt| modal.close_on_escape(&evt),
+///             "Login form"
+///         }
+///     }
+/// }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # let _ = vdom.rebuild();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<button>Log in</button>")
+/// ```
+pub fn use_modal_route(cx: &ScopeState) -> &ModalRoute {
+    let router = use_router_internal(cx)
+        .as_ref()
+        .expect("`use_modal_route` must have access to a parent router");
+
+    cx.use_hook(|| ModalRoute(router.clone()))
+}
+
+/// A handle to the modal whose open state is stored in the navigation history.
+///
+/// Created by [`use_modal_route`].
+#[derive(Clone)]
+pub struct ModalRoute(RouterContext);
+
+impl ModalRoute {
+    /// Open a modal by pushing its route onto the history.
+    pub fn open(&self, target: impl Into<IntoRoutable>) {
+        self.0.open_modal(target);
+    }
+
+    /// Check whether a modal is currently open.
+    ///
+    /// This is [`false`] once the user navigated away from the modal's history entry, for example
+    /// by pressing the browsers back button.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.0.modal_is_open()
+    }
+
+    /// Close the open modal.
+    ///
+    /// The history entry pushed by [`ModalRoute::open`] is popped, so the browsers forward button
+    /// can reopen the modal. Does nothing if no modal is open.
+    pub fn close(&self) {
+        self.0.close_modal();
+    }
+
+    /// Close the open modal if the pressed key is the escape key.
+    ///
+    /// Meant to be called from an `onkeydown` handler.
+    pub fn close_on_escape(&self, event: &KeyboardData) {
+        if event.key() == Key::Escape {
+            self.close();
+        }
+    }
+}
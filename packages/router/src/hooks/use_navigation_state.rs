@@ -0,0 +1,58 @@
+use dioxus::prelude::ScopeState;
+
+use crate::utils::use_router_internal::use_router_internal;
+
+/// The state of a navigation started with [`RouterContext::navigate_with_transition`](crate::prelude::RouterContext::navigate_with_transition)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NavigationState {
+    /// No navigation is pending
+    #[default]
+    Idle,
+    /// The router is waiting on suspended components of the new route
+    Pending {
+        /// The route the navigation started at
+        from: String,
+        /// The route the navigation leads to
+        to: String,
+    },
+}
+
+impl NavigationState {
+    /// Check whether a navigation is pending
+    pub fn is_pending(&self) -> bool {
+        matches!(self, NavigationState::Pending { .. })
+    }
+}
+
+/// A hook that provides the state of the last navigation started with
+/// [`RouterContext::navigate_with_transition`](crate::prelude::RouterContext::navigate_with_transition). The component
+/// is rerendered when a navigation starts and when it finishes.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::prelude::Router) component during a debug build.
+///
+/// # Example
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Layout(cx: Scope) -> Element {
+///     let navigation = use_navigation_state(cx);
+///
+///     render! {
+///         if navigation.is_pending() {
+///             rsx! { div { class: "progress-bar" } }
+///         }
+///         Outlet::<Route> {}
+///     }
+/// }
+/// ```
+pub fn use_navigation_state(cx: &ScopeState) -> NavigationState {
+    match use_router_internal(cx) {
+        Some(router) => router.navigation_state(),
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`use_navigation_state` must have access to a parent router");
+            #[allow(unreachable_code)]
+            NavigationState::Idle
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use dioxus::prelude::ScopeState;
+
+use crate::routable::RouteMeta;
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that provides the metadata of the current route, declared with the `#[meta(title = "...")]` attribute when
+/// deriving Routable. The component is rerendered when the route changes.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::prelude::Router) component during a debug build.
+///
+/// # Example
+/// ```rust, ignore
+/// #[inline_props]
+/// fn Header(cx: Scope) -> Element {
+///     let meta = use_route_meta(cx);
+///
+///     render! {
+///         h1 { meta.title.unwrap_or("My Blog") }
+///     }
+/// }
+/// ```
+pub fn use_route_meta(cx: &ScopeState) -> RouteMeta {
+    match use_router_internal(cx) {
+        Some(router) => router.current_meta(),
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`use_route_meta` must have access to a parent router");
+            #[allow(unreachable_code)]
+            RouteMeta::default()
+        }
+    }
+}
@@ -87,20 +87,6 @@ where
     <Rt as FromStr>::Err: std::fmt::Display,
     W: tokio::io::AsyncWrite + Unpin + Send,
 {
-    #[inline_props]
-    fn RenderPath<R>(cx: Scope, path: R) -> Element
-    where
-        R: Routable,
-        <R as FromStr>::Err: std::fmt::Display,
-    {
-        let path = path.clone();
-        render! {
-            Router::<R> {
-                config: || RouterConfig::default().history(MemoryHistory::with_initial_path(path))
-            }
-        }
-    }
-
     renderer
         .render(
             route.to_string(),
@@ -112,3 +98,18 @@ where
         )
         .await
 }
+
+/// Render the router for `R` at `path`
+#[inline_props]
+pub(crate) fn RenderPath<R>(cx: Scope, path: R) -> Element
+where
+    R: Routable,
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    let path = path.clone();
+    render! {
+        Router::<R> {
+            config: || RouterConfig::default().history(MemoryHistory::with_initial_path(path))
+        }
+    }
+}
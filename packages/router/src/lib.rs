@@ -4,13 +4,22 @@
 #![allow(non_snake_case)]
 
 pub mod navigation;
+pub mod pagination;
 pub mod routable;
+pub mod sitemap;
 
 #[cfg(feature = "ssr")]
 pub mod incremental;
+#[cfg(feature = "ssr")]
+pub mod static_site;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 /// Components interacting with the router.
 pub mod components {
+    mod announcer;
+    pub use announcer::*;
+
     mod default_errors;
     pub use default_errors::*;
 
@@ -49,6 +58,21 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_modal_route;
+    pub use use_modal_route::*;
+
+    mod use_navigation_state;
+    pub use use_navigation_state::*;
+
+    mod use_route_meta;
+    pub use use_route_meta::*;
+
+    mod use_breadcrumbs;
+    pub use use_breadcrumbs::*;
+
+    mod use_loader_data;
+    pub use use_loader_data::*;
 }
 
 /// A collection of useful items most applications might need.
@@ -58,13 +82,17 @@ pub mod prelude {
     pub use crate::history::*;
     pub use crate::hooks::*;
     pub use crate::navigation::*;
+    pub use crate::pagination::*;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
-    pub use dioxus_router_macro::Routable;
+    pub use crate::sitemap::SegmentValues;
+    pub use dioxus_router_macro::{routes_from_dir, Routable};
 
     #[cfg(feature = "ssr")]
     pub use crate::incremental::*;
     #[cfg(feature = "ssr")]
+    pub use crate::static_site::*;
+    #[cfg(feature = "ssr")]
     pub use dioxus_ssr::incremental::*;
 
     #[doc(hidden)]
@@ -52,6 +52,30 @@ pub enum NavigationTarget<R> {
     /// assert_eq!(explicit, implicit);
     /// ```
     External(String),
+    /// A path relative to the current route.
+    ///
+    /// The target is resolved against the path of the current route when the router navigates to it: `..` moves up
+    /// one segment, `.` stays at the current segment and every other segment is appended. Relative targets must
+    /// start with `./` or `../`, so they can't be confused with internal paths.
+    ///
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus_router::navigation::NavigationTarget;
+    /// # #[inline_props]
+    /// # fn Index(cx: Scope) -> Element {
+    /// #     todo!()
+    /// # }
+    /// #[derive(Clone, Routable, PartialEq, Debug)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Index {},
+    /// }
+    /// let explicit = NavigationTarget::<Route>::Relative(String::from("../sibling"));
+    /// let implicit: NavigationTarget::<Route> = "../sibling".parse().unwrap();
+    /// assert_eq!(explicit, implicit);
+    /// ```
+    Relative(String),
 }
 
 impl<R: Routable> From<&str> for NavigationTarget<R> {
@@ -85,6 +109,7 @@ impl<R: Routable> Display for NavigationTarget<R> {
         match self {
             NavigationTarget::Internal(r) => write!(f, "{}", r),
             NavigationTarget::External(s) => write!(f, "{}", s),
+            NavigationTarget::Relative(s) => write!(f, "{}", s),
         }
     }
 }
@@ -112,6 +137,10 @@ impl<R: Routable> FromStr for NavigationTarget<R> {
     type Err = NavigationTargetParseError<R>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_relative(s) {
+            return Ok(Self::Relative(s.to_string()));
+        }
+
         match Url::parse(s) {
             Ok(_) => Ok(Self::External(s.to_string())),
             Err(ParseError::RelativeUrlWithoutBase) => {
@@ -123,3 +152,31 @@ impl<R: Routable> FromStr for NavigationTarget<R> {
         }
     }
 }
+
+/// Check if a path is relative to the current route.
+pub(crate) fn is_relative(path: &str) -> bool {
+    path == "." || path == ".." || path.starts_with("./") || path.starts_with("../")
+}
+
+/// Resolve a relative path against the path of the current route.
+pub(crate) fn resolve_relative(current: &str, path: &str) -> String {
+    let is_suffix = |c: char| c == '?' || c == '#';
+    let current = current.split(is_suffix).next().unwrap_or_default();
+    let (path, suffix) = match path.find(is_suffix) {
+        Some(index) => path.split_at(index),
+        None => (path, ""),
+    };
+
+    let mut segments: Vec<_> = current.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    format!("/{}{}", segments.join("/"), suffix)
+}
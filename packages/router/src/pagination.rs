@@ -0,0 +1,187 @@
+//! Paginate lists with the `page` and `per_page` query parameters of a route.
+
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::prelude::*;
+
+/// The number of items on a page if the query does not set `per_page`.
+pub const DEFAULT_PER_PAGE: usize = 20;
+
+/// The pagination state of a list page, stored in the `page` and `per_page` query parameters.
+///
+/// Use it as the type of a query segment to keep the pagination of a route in the URL:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/posts?:pagination")]
+///     Posts { pagination: Pagination },
+/// }
+/// # #[inline_props]
+/// # fn Posts(cx: Scope, pagination: Pagination) -> Element { None }
+/// ```
+///
+/// Pages are numbered from 1. Missing or invalid parameters fall back to the first page and [`DEFAULT_PER_PAGE`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pagination {
+    /// The current page, starting at 1
+    pub page: usize,
+    /// The number of items on each page
+    pub per_page: usize,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            page: 1,
+            per_page: DEFAULT_PER_PAGE,
+        }
+    }
+}
+
+impl Pagination {
+    /// Create a new pagination state. Both values are at least 1.
+    pub fn new(page: usize, per_page: usize) -> Self {
+        Self {
+            page: page.max(1),
+            per_page: per_page.max(1),
+        }
+    }
+
+    /// The number of pages needed to show `total_items` items. There is always at least one page.
+    pub fn page_count(&self, total_items: usize) -> usize {
+        total_items.div_ceil(self.per_page).max(1)
+    }
+
+    /// Move the page into the pages that exist for `total_items` items.
+    pub fn clamp(self, total_items: usize) -> Self {
+        Self {
+            page: self.page.min(self.page_count(total_items)),
+            ..self
+        }
+    }
+
+    /// The range of items shown on the page, clamped to `total_items`.
+    pub fn items(&self, total_items: usize) -> Range<usize> {
+        let start = (self.page - 1)
+            .saturating_mul(self.per_page)
+            .min(total_items);
+        start..start.saturating_add(self.per_page).min(total_items)
+    }
+
+    /// The next page, if there is one for `total_items` items.
+    pub fn next(&self, total_items: usize) -> Option<Self> {
+        (self.page < self.page_count(total_items)).then(|| Self {
+            page: self.page + 1,
+            ..*self
+        })
+    }
+
+    /// The previous page, if this is not the first page.
+    pub fn prev(&self) -> Option<Self> {
+        (self.page > 1).then(|| Self {
+            page: self.page - 1,
+            ..*self
+        })
+    }
+
+    /// Paginate a list of `total_items` items.
+    ///
+    /// `route` creates the route of a page of the list, it is used for the [`Paginated::next`] and
+    /// [`Paginated::prev`] link targets. If this page is past the last page, the last page is shown.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_router::prelude::*;
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/posts?:pagination")]
+    ///     Posts { pagination: Pagination },
+    /// }
+    ///
+    /// #[inline_props]
+    /// fn Posts(cx: Scope, pagination: Pagination) -> Element {
+    ///     let posts: Vec<String> = (0..45).map(|i| format!("Post {i}")).collect();
+    ///     let pages = pagination.paginate(posts.len(), |pagination| Route::Posts { pagination });
+    ///
+    ///     render! {
+    ///         for post in &posts[pages.items.clone()] {
+    ///             p { "{post}" }
+    ///         }
+    ///         if let Some(prev) = pages.prev.clone() {
+    ///             rsx! { Link { to: prev, "Previous" } }
+    ///         }
+    ///         "Page {pages.current.page} of {pages.page_count}"
+    ///         if let Some(next) = pages.next.clone() {
+    ///             rsx! { Link { to: next, "Next" } }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn paginate<R: Routable + Clone>(
+        self,
+        total_items: usize,
+        route: impl Fn(Pagination) -> R,
+    ) -> Paginated<R> {
+        let current = self.clamp(total_items);
+
+        Paginated {
+            current,
+            page_count: current.page_count(total_items),
+            items: current.items(total_items),
+            next: current.next(total_items).map(&route),
+            prev: current.prev().map(&route),
+        }
+    }
+}
+
+impl From<&str> for Pagination {
+    fn from(query: &str) -> Self {
+        let mut pagination = Self::default();
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match (key, value.parse::<usize>()) {
+                ("page", Ok(page)) => pagination.page = page,
+                ("per_page", Ok(per_page)) => pagination.per_page = per_page,
+                _ => {}
+            }
+        }
+        Self::new(pagination.page, pagination.per_page)
+    }
+}
+
+impl Display for Pagination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "page={}&per_page={}", self.page, self.per_page)
+    }
+}
+
+/// A paginated list created with [`Pagination::paginate`].
+#[derive(Clone, Debug)]
+pub struct Paginated<R> {
+    /// The pagination of the current page, clamped to the pages that exist
+    pub current: Pagination,
+    /// The number of pages
+    pub page_count: usize,
+    /// The range of items shown on the current page
+    pub items: Range<usize>,
+    /// The route of the next page, if there is one
+    pub next: Option<R>,
+    /// The route of the previous page, if there is one
+    pub prev: Option<R>,
+}
+
+impl<R: Routable + Clone> Paginated<R> {
+    /// Check if the current page is the first page
+    pub fn is_first(&self) -> bool {
+        self.prev.is_none()
+    }
+
+    /// Check if the current page is the last page
+    pub fn is_last(&self) -> bool {
+        self.next.is_none()
+    }
+}
@@ -1,9 +1,20 @@
 //! # Routable
 
 #![allow(non_snake_case)]
+use crate::{components::IntoRoutable, contexts::router::RouterContext};
 use dioxus::prelude::*;
 
-use std::{fmt::Display, str::FromStr};
+pub use dioxus::prelude::HydrationPriority;
+
+use std::{
+    any::Any,
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    str::FromStr,
+    task::{Context, Poll},
+};
 
 /// An error that occurs when parsing a route
 #[derive(Debug, PartialEq)]
@@ -34,6 +45,51 @@ impl<T: for<'a> From<&'a str>> FromQuery for T {
     }
 }
 
+/// The error that occurs when a query string cannot be deserialized into a [`QueryStruct`]
+#[cfg(feature = "serde-query")]
+pub type QueryStructError = serde_urlencoded::de::Error;
+
+/// Something that can be created from and written to a whole query string. This is used for query struct segments
+/// (`"/?:..query"`), and implemented for every type that implements [`serde::Serialize`] and [`serde::de::DeserializeOwned`].
+///
+/// ```rust, ignore
+/// #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// struct Search {
+///     q: String,
+///     page: Option<usize>,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     // "/search?q=dioxus&page=2" is parsed into Route::Search { search: Search { q: "dioxus", page: Some(2) } }
+///     #[route("/search?:..search")]
+///     Search { search: Search },
+/// }
+/// ```
+#[cfg(feature = "serde-query")]
+pub trait QueryStruct: Sized {
+    /// Deserialize an instance of `Self` from a query string
+    fn from_query_string(query: &str) -> Result<Self, QueryStructError>;
+
+    /// Write `Self` as a query string, starting with `?`. Nothing is written if the query string is empty.
+    fn display_query_string(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+}
+
+#[cfg(feature = "serde-query")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned> QueryStruct for T {
+    fn from_query_string(query: &str) -> Result<Self, QueryStructError> {
+        serde_urlencoded::from_str(query)
+    }
+
+    fn display_query_string(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let query = serde_urlencoded::to_string(self).map_err(|_| std::fmt::Error)?;
+        if !query.is_empty() {
+            write!(f, "?{}", query)?;
+        }
+        Ok(())
+    }
+}
+
 /// Something that can be created from a route segment
 pub trait FromRouteSegment: Sized {
     /// The error that can occur when parsing a route segment
@@ -93,6 +149,91 @@ impl<I: std::iter::FromIterator<String>> FromRouteSegments for I {
     }
 }
 
+/// The result of a route guard
+///
+/// ```rust, ignore
+/// fn check_auth(route: &Route) -> GuardResult {
+///     if logged_in() {
+///         GuardResult::Allow
+///     } else {
+///         GuardResult::redirect(Route::Login {})
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub enum GuardResult {
+    /// Render the route
+    Allow,
+    /// Render nothing instead of the route
+    Block,
+    /// Replace the route with another one. The target must be a route of the enum the router was created with, or a
+    /// path.
+    Redirect(IntoRoutable),
+}
+
+impl GuardResult {
+    /// Replace the route with another one
+    pub fn redirect(target: impl Into<IntoRoutable>) -> Self {
+        Self::Redirect(target.into())
+    }
+
+    /// Apply the result of the guards to the router of the component. Returns `true` if the route should be rendered.
+    ///
+    /// Redirects are not applied while the component renders. They run in a task of the component after the render,
+    /// which is dropped if the component unmounts first.
+    #[doc(hidden)]
+    pub fn apply(self, cx: &ScopeState) -> bool {
+        match self {
+            GuardResult::Allow => true,
+            GuardResult::Block => false,
+            GuardResult::Redirect(target) => {
+                match cx.consume_context::<RouterContext>() {
+                    Some(router) => {
+                        cx.spawn(async move {
+                            router.replace(target);
+                        });
+                    }
+                    None => log::error!("A route guard redirected outside of a router"),
+                }
+                false
+            }
+        }
+    }
+}
+
+/// The data of a route that is being loaded by the loader of the route, declared with the `#[loader(function)]`
+/// attribute when deriving Routable
+pub struct RouteLoad(Pin<Box<dyn Future<Output = Rc<dyn Any>>>>);
+
+impl RouteLoad {
+    /// Load the data of a route with a future
+    pub fn new<T: 'static>(future: impl Future<Output = T> + 'static) -> Self {
+        Self(Box::pin(
+            async move { Rc::new(future.await) as Rc<dyn Any> },
+        ))
+    }
+}
+
+impl Future for RouteLoad {
+    type Output = Rc<dyn Any>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// The metadata of a route, declared with the `#[meta(title = "...", description = "...")]` attribute when deriving
+/// Routable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RouteMeta {
+    /// The title of the document while the route is active
+    pub title: Option<&'static str>,
+    /// The content of the `description` meta tag while the route is active
+    pub description: Option<&'static str>,
+    /// When the content the route renders in outlets becomes interactive after a server rendered page is hydrated
+    pub hydration: HydrationPriority,
+}
+
 /// Something that can be:
 /// 1. Converted from a route
 /// 2. Converted to a route
@@ -106,6 +247,37 @@ pub trait Routable: std::fmt::Display + std::str::FromStr + Clone + 'static {
     /// Render the route at the given level
     fn render<'a>(&self, cx: &'a ScopeState, level: usize) -> Element<'a>;
 
+    /// Run the guards of the route, in the order they were declared, until one of them does not allow the route
+    ///
+    /// The guards of a route are declared with the `#[guard(function)]` attribute when deriving Routable
+    fn guard(&self) -> GuardResult {
+        GuardResult::Allow
+    }
+
+    /// The metadata of the route
+    ///
+    /// The metadata of a route is declared with the `#[meta(title = "...", description = "...")]` attribute when
+    /// deriving Routable
+    fn meta(&self) -> RouteMeta {
+        RouteMeta::default()
+    }
+
+    /// The routes this route is nested in, from the outermost to the innermost. This does not include the route
+    /// itself
+    ///
+    /// When deriving Routable, the ancestors of a route are the routes at the root (`"/"`) of the nests it is in, and
+    /// the root route outside of any nest
+    fn ancestors(&self) -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// Start loading the data of the route, if the route has a loader
+    ///
+    /// The loader of a route is declared with the `#[loader(function)]` attribute when deriving Routable
+    fn load(&self) -> Option<RouteLoad> {
+        None
+    }
+
     /// Checks if this route is a child of the given route
     ///
     /// # Example
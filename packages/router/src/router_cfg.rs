@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::contexts::router::RoutingCallback;
-use crate::history::HistoryProvider;
+use crate::history::{HistoryMode, HistoryProvider, ScrollBehavior};
 use crate::routable::Routable;
 use dioxus::prelude::*;
 
@@ -28,6 +28,9 @@ pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn(Scope) -> Element,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) history_mode: HistoryMode,
+    #[cfg_attr(not(all(target_arch = "wasm32", feature = "web")), allow(dead_code))]
+    pub(crate) scroll_behavior: ScrollBehavior,
 }
 
 #[cfg(feature = "serde")]
@@ -41,6 +44,8 @@ where
             failure_external_navigation: FailureExternalNavigation::<R>,
             history: None,
             on_update: None,
+            history_mode: HistoryMode::Path,
+            scroll_behavior: ScrollBehavior::default(),
         }
     }
 }
@@ -52,11 +57,25 @@ where
     R: serde::Serialize + serde::de::DeserializeOwned,
 {
     pub(crate) fn get_history(self) -> Box<dyn HistoryProvider<R>> {
+        let history_mode = self.history_mode;
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        let scroll_behavior = self.scroll_behavior;
         self.history.unwrap_or_else(|| {
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            let history = Box::<WebHistory<R>>::default();
+            let history: Box<dyn HistoryProvider<R>> = match history_mode {
+                HistoryMode::Path => {
+                    Box::new(WebHistory::<R>::default().with_scroll_behavior(scroll_behavior))
+                }
+                HistoryMode::Hash => {
+                    Box::new(WebHashHistory::<R>::default().with_scroll_behavior(scroll_behavior))
+                }
+            };
+            // Outside of the browser, links still point to the hash of their route
             #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-            let history = Box::<MemoryHistory<R>>::default();
+            let history = Box::new(match history_mode {
+                HistoryMode::Path => MemoryHistory::<R>::default(),
+                HistoryMode::Hash => MemoryHistory::<R>::default().with_prefix("#"),
+            });
             history
         })
     }
@@ -72,6 +91,8 @@ where
             failure_external_navigation: FailureExternalNavigation,
             history: None,
             on_update: None,
+            history_mode: HistoryMode::Path,
+            scroll_behavior: ScrollBehavior::default(),
         }
     }
 }
@@ -84,9 +105,22 @@ where
     pub(crate) fn take_history(&mut self) -> Box<dyn AnyHistoryProvider> {
         self.history.take().unwrap_or_else(|| {
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            let history = Box::<AnyHistoryProviderImplWrapper<R, WebHistory<R>>>::default();
+            let history: Box<dyn AnyHistoryProvider> = match self.history_mode {
+                HistoryMode::Path => Box::new(AnyHistoryProviderImplWrapper::new(
+                    WebHistory::<R>::default().with_scroll_behavior(self.scroll_behavior),
+                )),
+                HistoryMode::Hash => Box::new(AnyHistoryProviderImplWrapper::new(
+                    WebHashHistory::<R>::default().with_scroll_behavior(self.scroll_behavior),
+                )),
+            };
+            // Outside of the browser, links still point to the hash of their route
             #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-            let history = Box::<AnyHistoryProviderImplWrapper<R, MemoryHistory<R>>>::default();
+            let history = Box::new(AnyHistoryProviderImplWrapper::new(
+                match self.history_mode {
+                    HistoryMode::Path => MemoryHistory::<R>::default(),
+                    HistoryMode::Hash => MemoryHistory::<R>::default().with_prefix("#"),
+                },
+            ));
             history
         })
     }
@@ -130,6 +164,17 @@ where
         }
     }
 
+    /// What happens to the scroll position of the page when the route changes in a browser.
+    ///
+    /// Only used on the web, when the config does not set a history provider. Defaults to
+    /// [`ScrollBehavior::Restore`].
+    pub fn scroll_behavior(self, behavior: ScrollBehavior) -> Self {
+        Self {
+            scroll_behavior: behavior,
+            ..self
+        }
+    }
+
     /// A component to render when an external navigation fails.
     ///
     /// Defaults to a router-internal component called [`FailureExternalNavigation`]
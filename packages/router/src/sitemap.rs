@@ -0,0 +1,160 @@
+//! Generate a [`sitemap.xml`](https://www.sitemaps.org/protocol.html) from the site map of a router.
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::routable::{Routable, SegmentType};
+
+/// Provides the values of the dynamic and catch all segments of routes.
+///
+/// A route is expanded once for every combination of the values of its segments. Routes with a segment that has no
+/// values are skipped.
+pub trait SegmentValues {
+    /// Get the values of the segment `name` of `route`.
+    fn segment_values(&self, route: &[SegmentType], name: &str) -> Vec<String>;
+}
+
+/// Only include the static routes.
+impl SegmentValues for () {
+    fn segment_values(&self, _: &[SegmentType], _: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+impl<F: Fn(&[SegmentType], &str) -> Vec<String>> SegmentValues for F {
+    fn segment_values(&self, route: &[SegmentType], name: &str) -> Vec<String> {
+        self(route, name)
+    }
+}
+
+/// Get every route in the site map of `R`, with the dynamic segments filled in by `segments`.
+///
+/// Paths that don't parse as a route of `R` are skipped, and every route is only returned once.
+pub fn routes<R>(segments: &impl SegmentValues) -> Vec<R>
+where
+    R: Routable,
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    let mut seen = HashSet::new();
+    let mut routes = Vec::new();
+
+    for route in R::SITE_MAP.iter().flat_map(|seg| seg.flatten().into_iter()) {
+        for full_path in route_paths(&route, segments) {
+            match R::from_str(&full_path) {
+                Ok(route) => {
+                    if seen.insert(route.to_string()) {
+                        routes.push(route);
+                    }
+                }
+                Err(e) => {
+                    log::info!("@ route: {}", full_path);
+                    log::error!("Error expanding route: {}", e);
+                }
+            }
+        }
+    }
+
+    routes
+}
+
+/// Generate a `sitemap.xml` with every route in the site map of `R`.
+///
+/// `base_url` is the absolute url the site is hosted at, like `https://dioxuslabs.com`. The dynamic segments of routes
+/// are filled in by `segments`, see [`SegmentValues`]. Search engines only read the first 50,000 urls of a sitemap.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/post/:id")]
+///     Post { id: usize },
+/// }
+/// # #[inline_props]
+/// # fn Home(cx: Scope) -> Element { None }
+/// # #[inline_props]
+/// # fn Post(cx: Scope, id: usize) -> Element { None }
+///
+/// let xml = dioxus_router::sitemap::to_xml::<Route>(
+///     "https://example.com",
+///     &|_: &[SegmentType], name: &str| match name {
+///         "id" => (0..10).map(|id| id.to_string()).collect(),
+///         _ => Vec::new(),
+///     },
+/// );
+/// std::fs::write("dist/sitemap.xml", xml).unwrap();
+/// ```
+pub fn to_xml<R>(base_url: &str, segments: &impl SegmentValues) -> String
+where
+    R: Routable,
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    let base_url = base_url.trim_end_matches('/');
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for route in routes::<R>(segments) {
+        xml += "  <url><loc>";
+        escape_xml(&format!("{}{}", base_url, route), &mut xml);
+        xml += "</loc></url>\n";
+    }
+    xml += "</urlset>\n";
+
+    xml
+}
+
+// Get every path of a route in the site map, with the dynamic segments filled in
+fn route_paths(route: &[SegmentType], segments: &impl SegmentValues) -> Vec<String> {
+    let mut paths = vec![String::new()];
+    for segment in route {
+        match segment {
+            SegmentType::Child => {}
+            SegmentType::Static(s) => {
+                if !s.is_empty() {
+                    for path in &mut paths {
+                        *path += "/";
+                        *path += s;
+                    }
+                }
+            }
+            SegmentType::Dynamic(name) | SegmentType::CatchAll(name) => {
+                let values = segments.segment_values(route, name);
+                paths = paths
+                    .iter()
+                    .flat_map(|path| {
+                        values
+                            .iter()
+                            .map(move |value| format!("{}/{}", path, value))
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            if path.is_empty() {
+                "/".to_string()
+            } else {
+                path
+            }
+        })
+        .collect()
+}
+
+fn escape_xml(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
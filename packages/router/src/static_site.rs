@@ -0,0 +1,231 @@
+//! Generate a static site from the site map of a router.
+use std::cell::RefCell;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_ssr::incremental::{IncrementalRendererError, WrapBody};
+
+use crate::incremental::{RenderPath, RenderPathProps};
+use crate::prelude::*;
+
+/// Provides the values of the dynamic and catch all segments of routes when generating a static site.
+pub use crate::sitemap::SegmentValues as DynamicSegmentProvider;
+
+/// Render every route in the site map of `Rt` to an `index.html` file in `out_dir`, so the site can be deployed to a
+/// static host.
+///
+/// `app` renders the HTML around the body of every page. Absolute paths in the `src` and `href` attributes of `link`,
+/// `script`, `img` and `source` tags are rewritten to be relative to the page, so the site keeps working when it is
+/// hosted under a sub path. Links to other pages are left as they are.
+///
+/// Returns the paths of the files that were written. Fails if the value of a segment contains a character that changes
+/// the route, like `/`, `?` or `#`, or if the path of a route would leave `out_dir`, for example because a dynamic
+/// segment is `..`.
+///
+/// # Example
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/post/:id")]
+///     Post { id: usize },
+/// }
+/// # #[inline_props]
+/// # fn Home(cx: Scope) -> Element { None }
+/// # #[inline_props]
+/// # fn Post(cx: Scope, id: usize) -> Element { None }
+///
+/// # async fn generate() -> Result<(), IncrementalRendererError> {
+/// generate_static_site::<Route, _>(
+///     &DefaultRenderer::default(),
+///     "./dist",
+///     &|_: &[SegmentType], name: &str| match name {
+///         "id" => (0..10).map(|id| id.to_string()).collect(),
+///         _ => Vec::new(),
+///     },
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn generate_static_site<Rt, R: WrapBody>(
+    app: &R,
+    out_dir: impl AsRef<Path>,
+    segments: &impl DynamicSegmentProvider,
+) -> Result<Vec<PathBuf>, IncrementalRendererError>
+where
+    Rt: Routable,
+    <Rt as FromStr>::Err: std::fmt::Display,
+{
+    let out_dir = out_dir.as_ref();
+    let mut renderer = dioxus_ssr::Renderer::new();
+    let mut written = Vec::new();
+
+    let segments = CheckedSegments {
+        segments,
+        invalid: RefCell::new(None),
+    };
+    let routes = crate::sitemap::routes::<Rt>(&segments);
+    if let Some(error) = segments.invalid.into_inner() {
+        return Err(IncrementalRendererError::Other(error.into()));
+    }
+
+    for route in routes {
+        let route_str = route.to_string();
+        let mut vdom = VirtualDom::new_with_props(RenderPath, RenderPathProps { path: route });
+        // Collect the title and meta tags of the route
+        let head = HeadContext::new();
+        vdom.base_scope().provide_context(head.clone());
+        let _ = vdom.rebuild();
+        vdom.wait_for_suspense().await;
+
+        let mut before_body = Vec::new();
+        app.render_before_body_of_route(&route_str, &mut before_body)?;
+        let mut html = with_head(&String::from_utf8_lossy(&before_body), &head).into_bytes();
+        let mut body = String::new();
+        renderer.render_to(&mut body, &vdom)?;
+        html.extend_from_slice(body.as_bytes());
+        app.render_after_body(&mut html)?;
+
+        let page_path = route_str.split(['?', '#']).next().unwrap_or_default();
+        let depth = page_path.split('/').filter(|s| !s.is_empty()).count();
+        let html = relative_asset_paths(&String::from_utf8_lossy(&html), depth);
+
+        let mut file_path = out_dir.to_path_buf();
+        for segment in page_path.split('/').filter(|s| !s.is_empty()) {
+            // Segments like `..` from the values of dynamic segments would write outside of the output directory
+            let mut components = Path::new(segment).components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(_)), None) => file_path.push(segment),
+                _ => {
+                    let error =
+                        format!("the route `{route_str}` is not a path in the output directory");
+                    return Err(IncrementalRendererError::Other(error.into()));
+                }
+            }
+        }
+        tokio::fs::create_dir_all(&file_path).await?;
+        file_path.push("index.html");
+        tokio::fs::write(&file_path, html).await?;
+        written.push(file_path);
+    }
+
+    Ok(written)
+}
+
+// Checks that the values of segments are only one segment of a path, or several for catch all segments, and remembers
+// the first value that isn't
+struct CheckedSegments<'a, S> {
+    segments: &'a S,
+    invalid: RefCell<Option<String>>,
+}
+
+impl<S: DynamicSegmentProvider> DynamicSegmentProvider for CheckedSegments<'_, S> {
+    fn segment_values(&self, route: &[SegmentType], name: &str) -> Vec<String> {
+        let catch_all = route
+            .iter()
+            .any(|segment| matches!(segment, SegmentType::CatchAll(n) if *n == name));
+        let (valid, invalid): (Vec<_>, Vec<_>) = self
+            .segments
+            .segment_values(route, name)
+            .into_iter()
+            .partition(|value| {
+                !value.chars().any(|c| {
+                    matches!(c, '?' | '#' | '\\') || c.is_control() || (c == '/' && !catch_all)
+                })
+            });
+        if let Some(value) = invalid.into_iter().next() {
+            self.invalid.borrow_mut().get_or_insert_with(|| {
+                format!("the value `{value}` of the segment `{name}` is not a path segment")
+            });
+        }
+        valid
+    }
+}
+
+// Add the metadata collected in the head context to the end of the head. The title of the route replaces the title of
+// the page.
+fn with_head(before_body: &str, head: &HeadContext) -> String {
+    let head_end = match before_body.find("</head>") {
+        Some(head_end) => head_end,
+        None => return before_body.to_string(),
+    };
+
+    let (page_head, rest) = before_body.split_at(head_end);
+    let mut page_head = page_head.to_string();
+    if head.title().is_some() {
+        if let Some(start) = page_head.find("<title") {
+            if let Some(end) = page_head[start..].find("</title>") {
+                page_head.replace_range(start..start + end + "</title>".len(), "");
+            }
+        }
+    }
+
+    format!("{}{}{}", page_head, head.render(), rest)
+}
+
+// Make absolute paths in the src and href attributes of asset tags relative to a page `depth` directories below the
+// root
+fn relative_asset_paths(html: &str, depth: usize) -> String {
+    const ASSET_TAGS: [&str; 4] = ["link", "script", "img", "source"];
+
+    let prefix = if depth == 0 {
+        "./".to_string()
+    } else {
+        "../".repeat(depth)
+    };
+
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        let (before, tag) = rest.split_at(start);
+        out.push_str(before);
+        let end = tag.find('>').map_or(tag.len(), |end| end + 1);
+        let (tag, after) = tag.split_at(end);
+        let name_end = tag[1..]
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .map_or(tag.len(), |end| end + 1);
+        let name = &tag[1..name_end];
+        if ASSET_TAGS
+            .iter()
+            .any(|asset| name.eq_ignore_ascii_case(asset))
+        {
+            out.push_str(&relative_attributes(tag, &prefix));
+        } else {
+            out.push_str(tag);
+        }
+        rest = after;
+    }
+    out.push_str(rest);
+
+    out
+}
+
+// Prefix absolute paths in the src and href attributes of a tag
+fn relative_attributes(tag: &str, prefix: &str) -> String {
+    let mut out = String::with_capacity(tag.len());
+    let mut rest = tag;
+    while let Some(idx) = [" src=\"/", " href=\"/"]
+        .iter()
+        .filter_map(|attr| rest.find(attr).map(|idx| idx + attr.len()))
+        .min()
+    {
+        // the slash that starts the absolute path
+        let (before, after) = rest.split_at(idx - 1);
+        out.push_str(before);
+        if after.starts_with("//") {
+            // protocol relative urls point to another host
+            out.push('/');
+        } else {
+            out.push_str(prefix);
+        }
+        rest = &after[1..];
+    }
+    out.push_str(rest);
+
+    out
+}
@@ -0,0 +1,240 @@
+//! Test a router without a browser.
+use std::{cell::RefCell, rc::Rc, str::FromStr, sync::Arc};
+
+use dioxus::prelude::*;
+
+use crate::prelude::*;
+
+/// The maximum number of times [`MemoryRouter::settle`] renders the app, so redirect loops don't hang tests
+const MAX_SETTLE_RENDERS: usize = 32;
+
+/// A router for tests, that keeps the history in memory and renders the app in a [`VirtualDom`].
+///
+/// Navigate with paths or routes, and check which route is active and what was rendered:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::{prelude::*, testing::MemoryRouter};
+/// #[derive(Clone, Routable, Debug, PartialEq)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/post/:id")]
+///     Post { id: usize },
+/// }
+///
+/// #[inline_props]
+/// fn Home(cx: Scope) -> Element {
+///     render! { "home" }
+/// }
+///
+/// #[inline_props]
+/// fn Post(cx: Scope, id: usize) -> Element {
+///     render! { "post {id}" }
+/// }
+///
+/// let mut router = MemoryRouter::<Route>::new("/").unwrap();
+/// assert_eq!(router.render(), "home");
+///
+/// router.navigate("/post/1").unwrap();
+/// assert_eq!(router.current(), Route::Post { id: 1 });
+/// assert_eq!(router.render(), "post 1");
+///
+/// router.go_back();
+/// assert_eq!(router.current(), Route::Home {});
+/// ```
+pub struct MemoryRouter<R: Routable> {
+    vdom: VirtualDom,
+    history: SharedHistory<R>,
+}
+
+impl<R: Routable> MemoryRouter<R>
+where
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    /// Create a router that starts at `path`.
+    pub fn new(path: &str) -> Result<Self, <R as FromStr>::Err> {
+        Ok(Self::with_route(path.parse()?))
+    }
+
+    /// Create a router that starts at `route`.
+    pub fn with_route(route: R) -> Self {
+        let history = SharedHistory {
+            inner: Rc::new(RefCell::new(SharedHistoryInner {
+                history: MemoryHistory::with_initial_path(route),
+                updater: None,
+            })),
+        };
+        let mut vdom = VirtualDom::new_with_props(
+            TestRouter::<R>,
+            TestRouterProps {
+                history: history.clone(),
+            },
+        );
+        let _ = vdom.rebuild();
+
+        let mut router = Self { vdom, history };
+        router.settle();
+        router
+    }
+
+    /// Navigate to `path`, and render the new route.
+    pub fn navigate(&mut self, path: &str) -> Result<(), <R as FromStr>::Err> {
+        self.push(path.parse()?);
+        Ok(())
+    }
+
+    /// Navigate to `route`, and render the new route. The current route is available to go back to.
+    pub fn push(&mut self, route: R) {
+        self.history.update(|history| history.push(route));
+        self.settle();
+    }
+
+    /// Replace the current route with `route`, and render the new route.
+    pub fn replace(&mut self, route: R) {
+        self.history.update(|history| history.replace(route));
+        self.settle();
+    }
+
+    /// Go back to the previous route, and render it. Does nothing if there is no previous route.
+    pub fn go_back(&mut self) {
+        self.history.update(|history| history.go_back());
+        self.settle();
+    }
+
+    /// Go forward to the next route, and render it. Does nothing if there is no next route.
+    pub fn go_forward(&mut self) {
+        self.history.update(|history| history.go_forward());
+        self.settle();
+    }
+
+    /// Check whether there is a previous route to go back to.
+    pub fn can_go_back(&self) -> bool {
+        self.history.inner.borrow().history.can_go_back()
+    }
+
+    /// Check whether there is a next route to go forward to.
+    pub fn can_go_forward(&self) -> bool {
+        self.history.inner.borrow().history.can_go_forward()
+    }
+
+    /// The route that is currently active.
+    pub fn current(&self) -> R {
+        self.history.inner.borrow().history.current_route()
+    }
+
+    /// Render the app to HTML.
+    pub fn render(&self) -> String {
+        dioxus_ssr::render(&self.vdom)
+    }
+
+    /// Process the events and updates of the app, and render it until it stops changing. This runs after every
+    /// navigation, call it after changing the app through [`MemoryRouter::vdom`].
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_SETTLE_RENDERS {
+            // Updates that are scheduled while rendering, like the redirect of a guard, are handled by the next render
+            self.vdom.process_events();
+            if self.vdom.render_immediate().edits.is_empty() {
+                return;
+            }
+        }
+        log::warn!(
+            "The app kept rendering after {} renders, is there a redirect loop?",
+            MAX_SETTLE_RENDERS
+        );
+    }
+
+    /// Wait for the suspended components of the current route to finish loading, and render them.
+    pub async fn wait_for_suspense(&mut self) {
+        self.vdom.wait_for_suspense().await;
+        self.settle();
+    }
+
+    /// The [`VirtualDom`] the app is rendered in, to send events to it for example.
+    pub fn vdom(&mut self) -> &mut VirtualDom {
+        &mut self.vdom
+    }
+}
+
+struct SharedHistoryInner<R: Routable> {
+    history: MemoryHistory<R>,
+    updater: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+/// A [`MemoryHistory`] that is shared between the router and the [`MemoryRouter`], so the test can navigate without
+/// access to the router.
+struct SharedHistory<R: Routable> {
+    inner: Rc<RefCell<SharedHistoryInner<R>>>,
+}
+
+impl<R: Routable> Clone for SharedHistory<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<R: Routable> SharedHistory<R> {
+    // Change the history from outside of the router, and tell the router about it
+    fn update(&self, f: impl FnOnce(&mut MemoryHistory<R>)) {
+        let updater = {
+            let mut inner = self.inner.borrow_mut();
+            f(&mut inner.history);
+            inner.updater.clone()
+        };
+        if let Some(updater) = updater {
+            updater();
+        }
+    }
+}
+
+impl<R: Routable> HistoryProvider<R> for SharedHistory<R> {
+    fn current_route(&self) -> R {
+        self.inner.borrow().history.current_route()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.inner.borrow().history.can_go_back()
+    }
+
+    fn go_back(&mut self) {
+        self.inner.borrow_mut().history.go_back()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.inner.borrow().history.can_go_forward()
+    }
+
+    fn go_forward(&mut self) {
+        self.inner.borrow_mut().history.go_forward()
+    }
+
+    fn push(&mut self, route: R) {
+        self.inner.borrow_mut().history.push(route)
+    }
+
+    fn replace(&mut self, route: R) {
+        self.inner.borrow_mut().history.replace(route)
+    }
+
+    fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {
+        self.inner.borrow_mut().updater = Some(callback);
+    }
+}
+
+struct TestRouterProps<R: Routable> {
+    history: SharedHistory<R>,
+}
+
+fn TestRouter<R: Routable>(cx: Scope<TestRouterProps<R>>) -> Element
+where
+    <R as FromStr>::Err: std::fmt::Display,
+{
+    let history = cx.props.history.clone();
+    render! {
+        Router::<R> {
+            config: move || RouterConfig::default().history(history)
+        }
+    }
+}
@@ -0,0 +1,141 @@
+#![allow(non_snake_case)]
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    #[meta(title = "Home")]
+    Home {},
+    #[route("/about")]
+    #[meta(title = "About")]
+    About {},
+    #[route("/plain")]
+    Plain {},
+}
+
+/// Where the home page stores the navigator, so the tests can navigate
+#[derive(Clone, Default)]
+struct NavigatorSlot(Rc<RefCell<Option<Navigator>>>);
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    if let Some(slot) = cx.consume_context::<NavigatorSlot>() {
+        *slot.0.borrow_mut() = Some(navigator.clone());
+    }
+    render! { "home" }
+}
+
+#[inline_props]
+fn About(cx: Scope) -> Element {
+    render! { "about" }
+}
+
+#[inline_props]
+fn Plain(cx: Scope) -> Element {
+    render! { "plain" }
+}
+
+#[inline_props]
+fn App(cx: Scope, announcer: RouteAnnouncer) -> Element {
+    render! {
+        Router::<Route> {
+            config: || RouterConfig::default().history(MemoryHistory::default()),
+            announcer: announcer.clone(),
+        }
+    }
+}
+
+const STYLE: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; border: 0;";
+
+/// Records the scripts the app runs
+#[derive(Default)]
+struct Scripts(RefCell<Vec<String>>);
+
+impl Scripts {
+    /// The scripts that move the focus, the router also runs scripts to set the title of the document
+    fn focus(&self) -> Vec<String> {
+        self.0
+            .borrow()
+            .iter()
+            .filter(|script| script.contains(".focus()"))
+            .cloned()
+            .collect()
+    }
+}
+
+impl EvalProvider for Scripts {
+    fn new_evaluator(&self, js: String) -> Result<Rc<dyn Evaluator>, EvalError> {
+        self.0.borrow_mut().push(js);
+        Err(EvalError::Finished)
+    }
+}
+
+fn render_after(announcer: RouteAnnouncer, route: Route) -> (String, Rc<Scripts>) {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { announcer });
+    let scripts = Rc::new(Scripts::default());
+    let slot = NavigatorSlot::default();
+    vdom.base_scope()
+        .provide_context(scripts.clone() as Rc<dyn EvalProvider>);
+    vdom.base_scope().provide_context(slot.clone());
+    let _ = vdom.rebuild();
+
+    let navigator = slot.0.borrow_mut().take().unwrap();
+    navigator.push(route);
+    vdom.process_events();
+    let _ = vdom.render_immediate();
+    // Run the tasks that were spawned while rendering
+    vdom.process_events();
+
+    (dioxus_ssr::render(&vdom), scripts)
+}
+
+#[test]
+fn nothing_is_announced_on_load() {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            announcer: RouteAnnouncer::default(),
+        },
+    );
+    let _ = vdom.rebuild();
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        format!(
+            "<div aria-live=\"polite\" aria-atomic=\"true\" style=\"{}\"></div>home",
+            STYLE
+        )
+    );
+}
+
+#[test]
+fn announces_the_title_of_the_new_route() {
+    let (html, scripts) = render_after(RouteAnnouncer::default(), Route::About {});
+    assert_eq!(
+        html,
+        format!(
+            "<div aria-live=\"polite\" aria-atomic=\"true\" style=\"{}\">About</div>about",
+            STYLE
+        )
+    );
+    let scripts = scripts.focus();
+    assert_eq!(scripts.len(), 1);
+    assert!(scripts[0].contains("document.querySelector(\"main\")"));
+}
+
+#[test]
+fn announces_the_fallback_of_routes_without_title() {
+    let (html, scripts) = render_after(RouteAnnouncer::default().without_focus(), Route::Plain {});
+    assert!(html.contains(">/plain</div>"));
+    assert!(scripts.focus().is_empty());
+
+    let (html, _) = render_after(
+        RouteAnnouncer::default().fallback("New page"),
+        Route::Plain {},
+    );
+    assert!(html.contains(">New page</div>"));
+}
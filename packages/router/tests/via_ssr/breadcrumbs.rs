@@ -0,0 +1,136 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Crumbs)]
+        #[route("/")]
+        Home {},
+        #[nest("/blog")]
+            #[route("/")]
+            #[meta(title = "Blog")]
+            BlogIndex {},
+            #[nest("/:id")]
+                #[route("/")]
+                Post { id: usize },
+                #[route("/edit")]
+                Edit { id: usize },
+            #[end_nest]
+        #[end_nest]
+        #[route("/about")]
+        About {},
+        #[child("/docs")]
+        Docs { child: DocsRoute },
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum DocsRoute {
+    #[route("/")]
+    DocsIndex {},
+    #[route("/intro")]
+    Intro {},
+}
+
+#[inline_props]
+fn Crumbs(cx: Scope) -> Element {
+    let crumbs = use_breadcrumbs::<Route>(cx);
+    let labels = crumbs
+        .iter()
+        .map(|(label, _)| label.as_str())
+        .collect::<Vec<_>>()
+        .join(" > ");
+    render! { "{labels}" }
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn BlogIndex(cx: Scope) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn Post(cx: Scope, id: usize) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn Edit(cx: Scope, id: usize) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn About(cx: Scope) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn DocsIndex(cx: Scope) -> Element {
+    render! { () }
+}
+
+#[inline_props]
+fn Intro(cx: Scope) -> Element {
+    render! { () }
+}
+
+fn render_route(route: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { route });
+    let _ = vdom.rebuild();
+    return dioxus_ssr::render(&vdom);
+
+    #[inline_props]
+    fn App(cx: Scope, route: Route) -> Element {
+        let route = route.clone();
+        render! {
+            Router::<Route> {
+                config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(route.clone()))
+            }
+        }
+    }
+}
+
+#[test]
+fn ancestors_of_nested_routes() {
+    assert_eq!(Route::Home {}.ancestors(), []);
+    assert_eq!(Route::About {}.ancestors(), [Route::Home {}]);
+    assert_eq!(Route::BlogIndex {}.ancestors(), [Route::Home {}]);
+    assert_eq!(
+        Route::Edit { id: 1 }.ancestors(),
+        [Route::Home {}, Route::BlogIndex {}, Route::Post { id: 1 }]
+    );
+    assert_eq!(
+        Route::Docs {
+            child: DocsRoute::Intro {}
+        }
+        .ancestors(),
+        [
+            Route::Home {},
+            Route::Docs {
+                child: DocsRoute::DocsIndex {}
+            }
+        ]
+    );
+}
+
+#[test]
+fn breadcrumbs_of_current_route() {
+    assert_eq!(render_route(Route::Home {}), "/");
+    assert_eq!(render_route(Route::About {}), "/ &gt; about");
+    assert_eq!(
+        render_route(Route::Edit { id: 3 }),
+        "/ &gt; Blog &gt; 3 &gt; edit"
+    );
+    assert_eq!(
+        render_route(Route::Docs {
+            child: DocsRoute::Intro {}
+        }),
+        "/ &gt; docs &gt; intro"
+    );
+}
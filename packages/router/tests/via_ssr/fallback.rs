@@ -0,0 +1,88 @@
+#![allow(non_snake_case)]
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Frame)]
+        #[route("/")]
+        Home {},
+        #[route("/blog/:id")]
+        BlogPost { id: usize },
+        #[fallback(PageNotFound)]
+        NotFound { path: String },
+}
+
+#[inline_props]
+fn Frame(cx: Scope) -> Element {
+    render! { "frame:" Outlet::<Route> {} }
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { "home" }
+}
+
+#[inline_props]
+fn BlogPost(cx: Scope, id: usize) -> Element {
+    render! { "post {id}" }
+}
+
+#[inline_props]
+fn PageNotFound(cx: Scope, path: String) -> Element {
+    render! { "nothing at {path}" }
+}
+
+fn render_path(path: &str) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: path.to_string(),
+        },
+    );
+    let _ = vdom.rebuild();
+    return dioxus_ssr::render(&vdom);
+
+    #[inline_props]
+    fn App(cx: Scope, path: String) -> Element {
+        let route = Route::from_str(path).unwrap();
+        render! {
+            Router::<Route> {
+                config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(route.clone()))
+            }
+        }
+    }
+}
+
+#[test]
+fn unmatched_paths_parse_to_fallback() {
+    assert_eq!(Route::from_str("/"), Ok(Route::Home {}));
+    assert_eq!(Route::from_str("/blog/1"), Ok(Route::BlogPost { id: 1 }));
+    assert_eq!(
+        Route::from_str("/blog/not-a-number"),
+        Ok(Route::NotFound {
+            path: "/blog/not-a-number".to_string()
+        })
+    );
+    assert_eq!(
+        Route::from_str("no-slash"),
+        Ok(Route::NotFound {
+            path: "no-slash".to_string()
+        })
+    );
+}
+
+#[test]
+fn fallback_displays_attempted_path() {
+    let route = Route::from_str("/missing/page").unwrap();
+    assert_eq!(route.to_string(), "/missing/page");
+}
+
+#[test]
+fn fallback_renders_component_with_path() {
+    assert_eq!(render_path("/blog/2"), "frame:post 2");
+    assert_eq!(render_path("/missing"), "frame:nothing at /missing");
+}
@@ -0,0 +1,94 @@
+#![allow(non_snake_case, unused)]
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+routes_from_dir!("tests/via_ssr/pages");
+
+fn prepare(path: impl Into<String>) -> VirtualDom {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path: path.into() });
+    let _ = vdom.rebuild();
+    return vdom;
+
+    #[derive(Debug, Props, PartialEq)]
+    struct AppProps {
+        path: String,
+    }
+
+    fn App(cx: Scope<AppProps>) -> Element {
+        render! {
+            Router::<Route> {
+                config: {
+                    let path = cx.props.path.parse().unwrap();
+                    move || RouterConfig::default().history(MemoryHistory::with_initial_path(path))
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn variants_are_named_after_pages() {
+    assert_eq!(Route::Index {}.to_string(), "/");
+    assert_eq!(Route::About {}.to_string(), "/about");
+    assert_eq!(Route::Blog {}.to_string(), "/blog/");
+    assert_eq!(
+        Route::BlogId {
+            id: "hello".to_string()
+        }
+        .to_string(),
+        "/blog/hello"
+    );
+    assert_eq!(
+        Route::UsersUserIdSettings {
+            user_id: "ferris".to_string()
+        }
+        .to_string(),
+        "/users/ferris/settings"
+    );
+}
+
+#[test]
+fn index() {
+    let html = dioxus_ssr::render(&prepare("/"));
+
+    assert_eq!(html, "<h1>App</h1><h2>Home</h2>");
+}
+
+#[test]
+fn static_page() {
+    let html = dioxus_ssr::render(&prepare("/about"));
+
+    assert_eq!(html, "<h1>App</h1><h2>About</h2>");
+}
+
+#[test]
+fn nested_index() {
+    let html = dioxus_ssr::render(&prepare("/blog"));
+
+    assert_eq!(html, "<h1>App</h1><h2>Blog</h2>");
+}
+
+#[test]
+fn dynamic_page() {
+    let html = dioxus_ssr::render(&prepare("/blog/hello"));
+
+    assert_eq!(html, "<h1>App</h1><h2>Blog post hello</h2>");
+}
+
+#[test]
+fn nested_layout() {
+    let html = dioxus_ssr::render(&prepare("/users/ferris/settings"));
+
+    assert_eq!(
+        html,
+        "<h1>App</h1><h2>User ferris</h2><h3>Settings of ferris</h3>"
+    );
+}
+
+#[test]
+fn catch_all() {
+    let html = dioxus_ssr::render(&prepare("/does/not/exist"));
+
+    assert_eq!(html, "<h1>App</h1><h2>Not found: does/not/exist</h2>");
+}
@@ -0,0 +1,184 @@
+#![allow(non_snake_case)]
+use std::{cell::RefCell, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static GUARD_CALLS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn log_guard(name: &'static str) {
+    GUARD_CALLS.with(|calls| calls.borrow_mut().push(name));
+}
+
+fn take_guard_calls() -> Vec<&'static str> {
+    GUARD_CALLS.with(|calls| std::mem::take(&mut *calls.borrow_mut()))
+}
+
+fn check_admin(_: &Route) -> GuardResult {
+    log_guard("check_admin");
+    GuardResult::Allow
+}
+
+fn check_auth(route: &Route) -> GuardResult {
+    log_guard("check_auth");
+    match route {
+        Route::Settings { user } if user == "guest" => GuardResult::redirect(Route::Login {}),
+        Route::Settings { user } if user == "banned" => GuardResult::Block,
+        _ => GuardResult::Allow,
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[nest("/admin")]
+    #[guard(check_admin)]
+        #[layout(AdminFrame)]
+            #[guard(check_auth)]
+                #[route("/:user")]
+                Settings { user: String },
+            #[end_guard]
+            #[route("/")]
+            Dashboard {},
+        #[end_layout]
+    #[end_guard]
+    #[end_nest]
+    #[route("/login")]
+    Login {},
+}
+
+#[inline_props]
+fn Login(cx: Scope) -> Element {
+    render! { "login" }
+}
+
+#[inline_props]
+fn AdminFrame(cx: Scope) -> Element {
+    render! { "admin:" Outlet::<Route> {} }
+}
+
+#[inline_props]
+fn Settings(cx: Scope, user: String) -> Element {
+    render! { "settings for {user}" }
+}
+
+#[inline_props]
+fn Dashboard(cx: Scope) -> Element {
+    render! { "dashboard" }
+}
+
+async fn render_route(route: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { route });
+    let _ = vdom.rebuild();
+    // A redirect from a guard replaces the route after the render, which renders the router again
+    let _ = tokio::time::timeout(Duration::from_millis(50), vdom.wait_for_work()).await;
+    let _ = vdom.render_immediate();
+    return dioxus_ssr::render(&vdom);
+
+    #[inline_props]
+    fn App(cx: Scope, route: Route) -> Element {
+        let route = route.clone();
+        render! {
+            Router::<Route> {
+                config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(route.clone()))
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn guards_run_in_order() {
+    take_guard_calls();
+    assert_eq!(
+        render_route(Route::Settings {
+            user: "alice".to_string()
+        })
+        .await,
+        "admin:settings for alice"
+    );
+    assert_eq!(take_guard_calls(), ["check_admin", "check_auth"]);
+
+    assert_eq!(render_route(Route::Dashboard {}).await, "admin:dashboard");
+    assert_eq!(take_guard_calls(), ["check_admin"]);
+
+    assert_eq!(render_route(Route::Login {}).await, "login");
+    assert!(take_guard_calls().is_empty());
+}
+
+#[tokio::test]
+async fn guards_block_routes() {
+    assert_eq!(
+        render_route(Route::Settings {
+            user: "banned".to_string()
+        })
+        .await,
+        ""
+    );
+}
+
+#[tokio::test]
+async fn guards_redirect_routes() {
+    assert_eq!(
+        render_route(Route::Settings {
+            user: "guest".to_string()
+        })
+        .await,
+        "login"
+    );
+}
+
+// Routables with nests need their own module
+mod scoped {
+    use super::*;
+
+    fn deny(_: &ScopedRoute) -> GuardResult {
+        GuardResult::Block
+    }
+
+    // The guard of the nest is not ended, so it ends with the nest
+    #[derive(Routable, Clone, Debug, PartialEq)]
+    #[rustfmt::skip]
+    enum ScopedRoute {
+        #[nest("/private")]
+        #[guard(deny)]
+            #[route("/")]
+            Private {},
+        #[end_nest]
+        #[route("/")]
+        Public {},
+    }
+
+    #[inline_props]
+    fn Private(cx: Scope) -> Element {
+        render! { "private" }
+    }
+
+    #[inline_props]
+    fn Public(cx: Scope) -> Element {
+        render! { "public" }
+    }
+
+    #[test]
+    fn guards_end_with_their_nest() {
+        fn render_scoped(route: ScopedRoute) -> String {
+            let mut vdom = VirtualDom::new_with_props(App, AppProps { route });
+            let _ = vdom.rebuild();
+            return dioxus_ssr::render(&vdom);
+
+            #[inline_props]
+            fn App(cx: Scope, route: ScopedRoute) -> Element {
+                let route = route.clone();
+                render! {
+                    Router::<ScopedRoute> {
+                        config: move || RouterConfig::default().history(MemoryHistory::with_initial_path(route.clone()))
+                    }
+                }
+            }
+        }
+
+        assert_eq!(render_scoped(ScopedRoute::Private {}), "");
+        assert_eq!(render_scoped(ScopedRoute::Public {}), "public");
+    }
+}
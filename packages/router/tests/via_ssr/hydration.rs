@@ -0,0 +1,65 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Shell)]
+        #[route("/")]
+        Home {},
+        #[route("/lazy")]
+        #[meta(hydration = "visible")]
+        Lazy {},
+}
+
+// Renders the hydration priority the component is in
+fn Priority(cx: Scope) -> Element {
+    let priority = cx.consume_context::<HydrationPriority>();
+    render! { "{priority:?} " }
+}
+
+#[inline_props]
+fn Shell(cx: Scope) -> Element {
+    render! {
+        Priority {}
+        Outlet::<Route> {}
+    }
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { Priority {} }
+}
+
+#[inline_props]
+fn Lazy(cx: Scope) -> Element {
+    render! { Priority {} }
+}
+
+#[inline_props]
+fn App(cx: Scope, route: Route) -> Element {
+    let route = route.clone();
+    render! {
+        Priority {}
+        Router::<Route> {
+            config: || RouterConfig::default().history(MemoryHistory::with_initial_path(route))
+        }
+    }
+}
+
+fn render(route: Route) -> String {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { route });
+    let _ = vdom.rebuild();
+    dioxus_ssr::render(&vdom)
+}
+
+#[test]
+fn outlets_provide_the_priority_of_their_content() {
+    // The rest of the page and layouts are interactive right away, the component of the route follows its priority
+    assert_eq!(render(Route::Lazy {}), "None Some(Critical) Some(Visible) ");
+    assert_eq!(
+        render(Route::Home {}),
+        "None Some(Critical) Some(Critical) "
+    );
+}
@@ -376,3 +376,53 @@ fn with_rel() {
 
     assert_eq!(prepare::<Route>(), expected);
 }
+
+#[test]
+fn href_with_history_prefix() {
+    #[derive(Routable, Clone)]
+    enum Route {
+        #[route("/")]
+        Root {},
+        #[route("/test")]
+        Test {},
+    }
+
+    #[inline_props]
+    fn Test(cx: Scope) -> Element {
+        todo!()
+    }
+
+    #[inline_props]
+    fn Root(cx: Scope) -> Element {
+        render! {
+            Link {
+                to: Route::Test {},
+                "Link"
+            }
+        }
+    }
+
+    // Outside of the browser, the router uses a memory history with the prefix of the hash history
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router::<Route> {
+                history_mode: HistoryMode::Hash,
+            }
+        }
+    }
+
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+
+    let expected = format!(
+        "<a {href} {default} {class} {id} {rel} {target}>Link</a>",
+        href = r##"href="#/test""##,
+        default = r#"dioxus-prevent-default="onclick""#,
+        class = r#"class="""#,
+        id = r#"id="""#,
+        rel = r#"rel="""#,
+        target = r#"target="""#
+    );
+
+    assert_eq!(dioxus_ssr::render(&vdom), expected);
+}
@@ -0,0 +1,55 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::{prelude::*, testing::MemoryRouter};
+
+async fn fetch_post(route: Route) -> String {
+    match route {
+        Route::Post { id } => format!("content of {}", id),
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[loader(fetch_post)]
+    #[route("/post/:id")]
+    Post { id: usize },
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    let data = use_loader_data::<String>(cx);
+    render! { "home {data.is_none()}" }
+}
+
+#[inline_props]
+fn Post(cx: Scope, id: usize) -> Element {
+    let post = use_loader_data::<String>(cx)?;
+    render! { "{id}: {post}" }
+}
+
+#[tokio::test]
+async fn renders_loaded_data() {
+    let mut router = MemoryRouter::<Route>::new("/post/1").unwrap();
+    router.wait_for_suspense().await;
+    assert_eq!(router.render(), "1: content of 1");
+}
+
+#[tokio::test]
+async fn reloads_when_params_change() {
+    let mut router = MemoryRouter::<Route>::new("/post/1").unwrap();
+    router.wait_for_suspense().await;
+
+    router.push(Route::Post { id: 2 });
+    router.wait_for_suspense().await;
+    assert_eq!(router.render(), "2: content of 2");
+}
+
+#[test]
+fn route_without_loader() {
+    let router = MemoryRouter::<Route>::new("/").unwrap();
+    assert_eq!(router.render(), "home true");
+}
@@ -1,2 +1,19 @@
+mod announcer;
+mod breadcrumbs;
+mod fallback;
+mod file_routes;
+mod guards;
+mod hydration;
 mod link;
+mod loader;
+mod meta;
+mod modal;
 mod outlet;
+mod pagination;
+mod query_struct;
+mod relative;
+mod sitemap;
+#[cfg(feature = "ssr")]
+mod static_site;
+mod testing;
+mod transition;
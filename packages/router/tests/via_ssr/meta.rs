@@ -0,0 +1,164 @@
+#![allow(non_snake_case)]
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static NAVIGATOR: RefCell<Option<Navigator>> = RefCell::new(None);
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    #[meta(title = "Home", description = "The home page")]
+    Home {},
+    #[route("/about")]
+    #[meta(title = "About", hydration = "idle")]
+    About {},
+    #[route("/plain")]
+    Plain {},
+    #[child("/docs")]
+    Docs { child: DocsRoute },
+    #[fallback]
+    #[meta(title = "Not found")]
+    NotFound { path: String },
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum DocsRoute {
+    #[route("/")]
+    #[meta(title = "Docs")]
+    DocsIndex {},
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx).clone();
+    NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator));
+    render! { "home" }
+}
+
+#[inline_props]
+fn About(cx: Scope) -> Element {
+    let meta = use_route_meta(cx);
+    render! { meta.title.unwrap_or_default() }
+}
+
+#[inline_props]
+fn Plain(cx: Scope) -> Element {
+    render! { "plain" }
+}
+
+#[inline_props]
+fn DocsIndex(cx: Scope) -> Element {
+    render! { "docs" }
+}
+
+#[inline_props]
+fn NotFound(cx: Scope, path: String) -> Element {
+    render! { "nothing at {path}" }
+}
+
+#[inline_props]
+fn App(cx: Scope, route: Route) -> Element {
+    let route = route.clone();
+    render! {
+        Router::<Route> {
+            config: || RouterConfig::default().history(MemoryHistory::with_initial_path(route))
+        }
+    }
+}
+
+fn render_head(route: Route) -> (String, HeadContext) {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { route });
+    let head = HeadContext::new();
+    vdom.base_scope().provide_context(head.clone());
+    let _ = vdom.rebuild();
+    (dioxus_ssr::render(&vdom), head)
+}
+
+#[test]
+fn meta_of_routes() {
+    assert_eq!(
+        Route::Home {}.meta(),
+        RouteMeta {
+            title: Some("Home"),
+            description: Some("The home page"),
+            hydration: HydrationPriority::Critical,
+        }
+    );
+    assert_eq!(Route::About {}.meta().title, Some("About"));
+    assert_eq!(Route::About {}.meta().hydration, HydrationPriority::Idle);
+    assert_eq!(Route::Plain {}.meta(), RouteMeta::default());
+    // Child routes use the metadata of the child
+    assert_eq!(
+        "/docs/".parse::<Route>().unwrap().meta().title,
+        Some("Docs")
+    );
+    assert_eq!(
+        "/missing".parse::<Route>().unwrap().meta().title,
+        Some("Not found")
+    );
+}
+
+#[test]
+fn use_route_meta_reads_current_route() {
+    let (html, _) = render_head(Route::About {});
+    assert_eq!(html, "About");
+}
+
+#[test]
+fn head_is_collected_while_rendering() {
+    let (html, head) = render_head(Route::Home {});
+    assert_eq!(html, "home");
+    assert_eq!(head.title().as_deref(), Some("Home"));
+    assert_eq!(head.meta("description").as_deref(), Some("The home page"));
+    assert_eq!(
+        head.render(),
+        r#"<title>Home</title><meta name="description" content="The home page">"#
+    );
+
+    let (_, head) = render_head(Route::Plain {});
+    assert_eq!(head.title(), None);
+    assert_eq!(head.render(), "");
+}
+
+/// Records the scripts the app runs
+#[derive(Default)]
+struct Scripts(RefCell<Vec<String>>);
+
+impl EvalProvider for Scripts {
+    fn new_evaluator(&self, js: String) -> Result<Rc<dyn Evaluator>, EvalError> {
+        self.0.borrow_mut().push(js);
+        Err(EvalError::Finished)
+    }
+}
+
+#[test]
+fn head_is_reset_on_navigation() {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            route: Route::Home {},
+        },
+    );
+    let scripts = Rc::new(Scripts::default());
+    vdom.base_scope()
+        .provide_context(scripts.clone() as Rc<dyn EvalProvider>);
+    let _ = vdom.rebuild();
+    assert!(scripts.0.borrow()[0].contains(r#"document.title = "Home""#));
+
+    // The title and description of the previous route don't stay on a route without metadata
+    let navigator = NAVIGATOR.with(|n| n.borrow().clone().unwrap());
+    scripts.0.borrow_mut().clear();
+    navigator.push(Route::Plain {});
+    vdom.process_events();
+    let _ = vdom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&vdom), "plain");
+    let reset = scripts.0.borrow();
+    assert_eq!(reset.len(), 2);
+    assert!(reset[0].contains("document.title = previous"));
+    assert!(reset[1].contains("meta?.remove()"));
+}
@@ -0,0 +1,93 @@
+#![allow(non_snake_case)]
+use std::cell::RefCell;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static HANDLES: RefCell<Option<(ModalRoute, Navigator)>> = RefCell::new(None);
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Frame)]
+        #[route("/")]
+        Index {},
+        #[route("/login")]
+        Login {},
+}
+
+fn App(cx: Scope) -> Element {
+    render! {
+        Router::<Route> {
+            config: || RouterConfig::default().history(MemoryHistory::default())
+        }
+    }
+}
+
+#[inline_props]
+fn Frame(cx: Scope) -> Element {
+    let modal = use_modal_route(cx);
+    let navigator = use_navigator(cx);
+    HANDLES.with(|handles| *handles.borrow_mut() = Some((modal.clone(), navigator.clone())));
+    render! { Outlet::<Route> {} }
+}
+
+#[inline_props]
+fn Index(cx: Scope) -> Element {
+    render! { "index" }
+}
+
+#[inline_props]
+fn Login(cx: Scope) -> Element {
+    render! { "login" }
+}
+
+fn handles() -> (ModalRoute, Navigator) {
+    HANDLES.with(|handles| handles.borrow().clone().unwrap())
+}
+
+#[test]
+fn the_modal_is_open_while_its_history_entry_is_current() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    let (modal, navigator) = handles();
+    assert!(!modal.is_open());
+
+    modal.open(Route::Login {});
+    assert!(modal.is_open());
+
+    // Reading the state while the modal is in the back history doesn't forget it, so going forward reopens it
+    navigator.go_back();
+    assert!(!modal.is_open());
+    assert!(!modal.is_open());
+    navigator.go_forward();
+    assert!(modal.is_open());
+
+    modal.close();
+    assert!(!modal.is_open());
+    navigator.go_forward();
+    assert!(modal.is_open());
+    modal.close();
+
+    // Navigating to the route of the modal without opening it doesn't open the modal
+    navigator.push(Route::Login {});
+    assert!(!modal.is_open());
+}
+
+#[test]
+fn pushing_a_route_forgets_the_modal() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    let (modal, navigator) = handles();
+
+    modal.open(Route::Login {});
+    navigator.go_back();
+    // The entry of the modal is dropped from the history
+    navigator.push(Route::Login {});
+    assert!(!modal.is_open());
+    navigator.go_back();
+    navigator.go_forward();
+    assert!(!modal.is_open());
+}
@@ -0,0 +1,7 @@
+use dioxus::prelude::*;
+
+#[inline_props]
+pub fn Page(cx: Scope, segments: Vec<String>) -> Element {
+    let path = segments.join("/");
+    render! { h2 { "Not found: {path}" } }
+}
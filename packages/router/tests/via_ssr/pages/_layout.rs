@@ -0,0 +1,10 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[inline_props]
+pub fn Layout(cx: Scope) -> Element {
+    render! {
+        h1 { "App" }
+        Outlet::<crate::file_routes::Route> {}
+    }
+}
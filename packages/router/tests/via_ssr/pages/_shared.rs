@@ -0,0 +1,7 @@
+use dioxus::prelude::*;
+
+// Not a route, pages share this component
+#[inline_props]
+pub fn Title<'a>(cx: Scope<'a>, text: &'a str) -> Element {
+    render! { h2 { "{text}" } }
+}
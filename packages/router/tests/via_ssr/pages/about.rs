@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[inline_props]
+pub fn Page(cx: Scope) -> Element {
+    render! { super::_shared::Title { text: "About" } }
+}
@@ -0,0 +1,10 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[inline_props]
+pub fn Layout(cx: Scope, user_id: String) -> Element {
+    render! {
+        h2 { "User {user_id}" }
+        Outlet::<crate::file_routes::Route> {}
+    }
+}
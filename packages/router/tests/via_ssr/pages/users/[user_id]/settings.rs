@@ -0,0 +1,6 @@
+use dioxus::prelude::*;
+
+#[inline_props]
+pub fn Page(cx: Scope, user_id: String) -> Element {
+    render! { h3 { "Settings of {user_id}" } }
+}
@@ -0,0 +1,99 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone)]
+enum Route {
+    #[route("/posts?:pagination")]
+    Posts { pagination: Pagination },
+}
+
+#[inline_props]
+fn Posts(cx: Scope, pagination: Pagination) -> Element {
+    let pages = pagination.paginate(25, |pagination| Route::Posts { pagination });
+    let range = pages.items.clone();
+
+    render! {
+        p { "{range.start}..{range.end} of {pages.page_count}" }
+        if let Some(prev) = pages.prev.clone() {
+            rsx! { Link { to: prev, "Previous" } }
+        }
+        if let Some(next) = pages.next.clone() {
+            rsx! { Link { to: next, "Next" } }
+        }
+    }
+}
+
+fn render_page(path: &str) -> String {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: path.to_string(),
+        },
+    );
+    let _ = vdom.rebuild();
+    return dioxus_ssr::render(&vdom);
+
+    #[inline_props]
+    fn App(cx: Scope, path: String) -> Element {
+        let path = path.clone();
+        render! {
+            Router::<Route> {
+                config: move || {
+                    RouterConfig::default().history(MemoryHistory::with_initial_path(
+                        path.parse().unwrap_or_else(|_| panic!("invalid route")),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn pagination_links() {
+    assert_eq!(
+        render_page("/posts?page=2&per_page=10"),
+        concat!(
+            "<p>10..20 of 3</p>",
+            r#"<a href="/posts?page=1&per_page=10" dioxus-prevent-default="onclick" class="" id="" rel="" target="">Previous</a>"#,
+            r#"<a href="/posts?page=3&per_page=10" dioxus-prevent-default="onclick" class="" id="" rel="" target="">Next</a>"#,
+        )
+    );
+}
+
+#[test]
+fn pagination_parses_query() {
+    assert_eq!(
+        Pagination::from("per_page=5&page=3&sort=new"),
+        Pagination::new(3, 5)
+    );
+    assert_eq!(
+        Pagination::from("page=0&per_page=abc"),
+        Pagination::default()
+    );
+    assert_eq!(Pagination::new(9, 10).clamp(25), Pagination::new(3, 10));
+    assert_eq!(Pagination::new(3, 10).items(25), 20..25);
+    assert_eq!(Pagination::new(1, 10).page_count(0), 1);
+}
+
+#[test]
+fn pagination_handles_huge_values() {
+    let pagination =
+        Pagination::from(format!("page={}&per_page={}", usize::MAX, usize::MAX).as_str());
+    assert_eq!(pagination.page_count(25), 1);
+    assert_eq!(pagination.items(25), 25..25);
+    assert_eq!(pagination.clamp(25), Pagination::new(1, usize::MAX));
+    assert_eq!(pagination.clamp(25).items(25), 0..25);
+}
+
+#[test]
+fn pagination_clamps_pages_past_the_end() {
+    // the last page is shown without redirecting the router
+    assert_eq!(
+        render_page("/posts?page=9&per_page=10"),
+        concat!(
+            "<p>20..25 of 3</p>",
+            r#"<a href="/posts?page=2&per_page=10" dioxus-prevent-default="onclick" class="" id="" rel="" target="">Previous</a>"#,
+        )
+    );
+}
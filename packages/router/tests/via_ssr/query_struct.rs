@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Search {
+    q: String,
+    page: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Filters {
+    role: Option<String>,
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/search?:..search")]
+    Search { search: Search },
+    #[route("/users/:id?:..filters")]
+    Users { id: usize, filters: Filters },
+}
+
+#[inline_props]
+fn Search(cx: Scope, search: Search) -> Element {
+    render! { "{search.q}" }
+}
+
+#[inline_props]
+fn Users(cx: Scope, id: usize, filters: Filters) -> Element {
+    render! { "{id}" }
+}
+
+#[test]
+fn query_structs_round_trip() {
+    let route = Route::Search {
+        search: Search {
+            q: "dioxus router".to_string(),
+            page: Some(2),
+        },
+    };
+    assert_eq!(route.to_string(), "/search?q=dioxus+router&page=2");
+    assert_eq!(route.to_string().parse::<Route>().unwrap(), route);
+
+    assert_eq!(
+        "/search?page=3&q=rust".parse::<Route>().unwrap(),
+        Route::Search {
+            search: Search {
+                q: "rust".to_string(),
+                page: Some(3),
+            },
+        }
+    );
+}
+
+#[test]
+fn empty_query_structs_are_not_written() {
+    let route = Route::Users {
+        id: 1,
+        filters: Filters { role: None },
+    };
+    assert_eq!(route.to_string(), "/users/1");
+    assert_eq!("/users/1".parse::<Route>().unwrap(), route);
+
+    let route = Route::Users {
+        id: 1,
+        filters: Filters {
+            role: Some("admin".to_string()),
+        },
+    };
+    assert_eq!(route.to_string(), "/users/1?role=admin");
+}
+
+#[test]
+fn invalid_query_structs_do_not_match() {
+    // `q` is required
+    assert!("/search?page=1".parse::<Route>().is_err());
+    assert!("/search?q=rust&page=first".parse::<Route>().is_err());
+}
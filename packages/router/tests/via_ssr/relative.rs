@@ -0,0 +1,100 @@
+#![allow(non_snake_case)]
+use std::cell::RefCell;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static NAVIGATOR: RefCell<Option<Navigator>> = const { RefCell::new(None) };
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/posts/:id")]
+        #[route("/comments")]
+        Comments { id: usize },
+        #[route("/edit")]
+        Edit { id: usize },
+    #[end_nest]
+    #[route("/about")]
+    About {},
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { "home" }
+}
+
+#[inline_props]
+fn Comments(cx: Scope, id: usize) -> Element {
+    NAVIGATOR.with(|nav| *nav.borrow_mut() = Some(use_navigator(cx).clone()));
+
+    render! {
+        "comments {id}"
+        Link { to: "../edit", "edit" }
+        Link { to: "./replies?page=2", "replies" }
+        Link { to: "../../../about", "about" }
+    }
+}
+
+#[inline_props]
+fn Edit(cx: Scope, id: usize) -> Element {
+    render! { "edit {id}" }
+}
+
+#[inline_props]
+fn About(cx: Scope) -> Element {
+    render! { "about" }
+}
+
+fn App(cx: Scope) -> Element {
+    render! {
+        Router::<Route> {
+            config: || {
+                RouterConfig::default()
+                    .history(MemoryHistory::with_initial_path(Route::Comments { id: 1 }))
+            }
+        }
+    }
+}
+
+#[test]
+fn relative_link_href() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    let html = dioxus_ssr::render(&vdom);
+
+    assert!(html.contains(r#"href="/posts/1/edit""#), "{}", html);
+    assert!(
+        html.contains(r#"href="/posts/1/comments/replies?page=2""#),
+        "{}",
+        html
+    );
+    assert!(html.contains(r#"href="/about""#), "{}", html);
+}
+
+#[test]
+fn relative_navigation() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+
+    let navigator = NAVIGATOR.with(|nav| nav.borrow().clone().unwrap());
+    assert!(navigator.push("../edit").is_none());
+    vdom.process_events();
+    let _ = vdom.render_immediate();
+
+    assert_eq!(dioxus_ssr::render(&vdom), "edit 1");
+}
+
+#[test]
+fn parse_relative_target() {
+    let target: NavigationTarget<Route> = "../edit".parse().unwrap();
+    assert_eq!(target, NavigationTarget::Relative("../edit".to_string()));
+    assert_eq!(
+        NavigationTarget::<Route>::from("/about"),
+        NavigationTarget::Internal(Route::About {})
+    );
+}
@@ -0,0 +1,60 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/blog")]
+        #[route("/")]
+        Blog {},
+        #[route("/:id")]
+        BlogPost { id: usize },
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { "home" }
+}
+
+#[inline_props]
+fn Blog(cx: Scope) -> Element {
+    render! { "blog" }
+}
+
+#[inline_props]
+fn BlogPost(cx: Scope, id: usize) -> Element {
+    render! { "post {id}" }
+}
+
+#[test]
+fn static_routes_only() {
+    assert_eq!(
+        dioxus_router::sitemap::to_xml::<Route>("https://example.com/", &()),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+        <url><loc>https://example.com/</loc></url>\n  \
+        <url><loc>https://example.com/blog/</loc></url>\n\
+        </urlset>\n"
+    );
+}
+
+#[test]
+fn dynamic_segments_are_expanded() {
+    let routes =
+        dioxus_router::sitemap::routes::<Route>(&|_: &[SegmentType], name: &str| match name {
+            "id" => vec!["1".to_string(), "2".to_string(), "not-a-number".to_string()],
+            _ => Vec::new(),
+        });
+    assert_eq!(
+        routes,
+        [
+            Route::Home {},
+            Route::Blog {},
+            Route::BlogPost { id: 1 },
+            Route::BlogPost { id: 2 },
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+#![allow(non_snake_case)]
+use std::path::PathBuf;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/post/:id")]
+    Post { id: usize },
+    #[route("/page/:name")]
+    Page { name: String },
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! {
+        img { src: "/logo.png" }
+        a { href: "/post/1", "first post" }
+    }
+}
+
+#[inline_props]
+fn Post(cx: Scope, id: usize) -> Element {
+    use_title(cx, format!("post {id}"));
+    render! { "post {id}" }
+}
+
+#[inline_props]
+fn Page(cx: Scope, name: String) -> Element {
+    render! { "page {name}" }
+}
+
+fn app() -> DefaultRenderer {
+    DefaultRenderer {
+        before_body: r#"<html><head><title>site</title><link href="/style.css"></head><body>"#
+            .to_string(),
+        after_body: "</body></html>".to_string(),
+    }
+}
+
+// A directory for the output of a test, with the output directory nested inside it
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "dioxus-router-static-site-{}-{name}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn renders_every_route_to_a_file() {
+    let dir = test_dir("renders");
+    let out_dir = dir.join("dist");
+    let written =
+        generate_static_site::<Route, _>(&app(), &out_dir, &|_: &[SegmentType], name: &str| {
+            match name {
+                "id" => vec!["1".to_string(), "2".to_string()],
+                _ => Vec::new(),
+            }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        written,
+        [
+            out_dir.join("index.html"),
+            out_dir.join("post/1/index.html"),
+            out_dir.join("post/2/index.html"),
+        ]
+    );
+
+    // Absolute asset paths are relative to the page
+    let home = std::fs::read_to_string(out_dir.join("index.html")).unwrap();
+    assert!(home.contains(r#"<link href="./style.css">"#));
+    assert!(home.contains(r#"<img src="./logo.png"/>"#));
+    // Links to other pages are not assets
+    assert!(home.contains(r#"<a href="/post/1">first post</a>"#));
+    assert!(home.contains("<title>site</title>"));
+
+    // The title of the route replaces the title of the page
+    let post = std::fs::read_to_string(out_dir.join("post/1/index.html")).unwrap();
+    assert!(post.contains(r#"<link href="../../style.css">"#));
+    assert!(post.contains("post 1"));
+    assert!(post.contains("<title>post 1</title>"));
+    assert!(!post.contains("<title>site</title>"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_routes_outside_of_the_output_directory() {
+    let dir = test_dir("rejects");
+    let out_dir = dir.join("dist");
+    for name in ["..", "."] {
+        let result = generate_static_site::<Route, _>(
+            &app(),
+            &out_dir,
+            &|_: &[SegmentType], segment: &str| match segment {
+                "name" => vec![name.to_string()],
+                _ => Vec::new(),
+            },
+        )
+        .await;
+        let error = result.expect_err("the route was written").to_string();
+        assert!(error.contains(&format!("the route `/page/{name}` is not a path")));
+    }
+
+    assert!(!dir.join("index.html").exists());
+    assert!(!dir.join("page").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_segment_values_that_change_the_route() {
+    let dir = test_dir("segments");
+    let out_dir = dir.join("dist");
+    for name in ["a/b", "a?b=c", "a#b"] {
+        let result = generate_static_site::<Route, _>(
+            &app(),
+            &out_dir,
+            &|_: &[SegmentType], segment: &str| match segment {
+                "name" => vec![name.to_string()],
+                _ => Vec::new(),
+            },
+        )
+        .await;
+        let error = result.expect_err("the route was written").to_string();
+        assert!(error.contains(&format!(
+            "the value `{name}` of the segment `name` is not a path segment"
+        )));
+    }
+
+    assert!(!out_dir.exists());
+}
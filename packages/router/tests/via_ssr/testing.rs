@@ -0,0 +1,99 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+use dioxus_router::{prelude::*, testing::MemoryRouter};
+
+fn require_login(route: &Route) -> GuardResult {
+    match route {
+        Route::Settings {} => GuardResult::redirect(Route::Login {}),
+        _ => GuardResult::Allow,
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Nav)]
+        #[route("/")]
+        Home {},
+        #[route("/post/:id")]
+        Post { id: usize },
+        #[route("/login")]
+        Login {},
+        #[guard(require_login)]
+        #[route("/settings")]
+        Settings {},
+}
+
+#[inline_props]
+fn Nav(cx: Scope) -> Element {
+    render! {
+        nav { "nav" }
+        Outlet::<Route> {}
+    }
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { "home" }
+}
+
+#[inline_props]
+fn Post(cx: Scope, id: usize) -> Element {
+    render! { "post {id}" }
+}
+
+#[inline_props]
+fn Login(cx: Scope) -> Element {
+    render! { "login" }
+}
+
+#[inline_props]
+fn Settings(cx: Scope) -> Element {
+    render! { "settings" }
+}
+
+#[test]
+fn renders_initial_path() {
+    let router = MemoryRouter::<Route>::new("/post/3").unwrap();
+    assert_eq!(router.current(), Route::Post { id: 3 });
+    assert_eq!(router.render(), "<nav>nav</nav>post 3");
+    assert!(!router.can_go_back());
+}
+
+#[test]
+fn invalid_path() {
+    assert!(MemoryRouter::<Route>::new("/post/not-a-number").is_err());
+}
+
+#[test]
+fn navigates_through_history() {
+    let mut router = MemoryRouter::<Route>::new("/").unwrap();
+    assert_eq!(router.render(), "<nav>nav</nav>home");
+
+    router.navigate("/post/1").unwrap();
+    assert_eq!(router.render(), "<nav>nav</nav>post 1");
+
+    router.push(Route::Post { id: 2 });
+    assert_eq!(router.render(), "<nav>nav</nav>post 2");
+
+    router.go_back();
+    assert_eq!(router.current(), Route::Post { id: 1 });
+    assert_eq!(router.render(), "<nav>nav</nav>post 1");
+    assert!(router.can_go_forward());
+
+    router.go_forward();
+    assert_eq!(router.current(), Route::Post { id: 2 });
+
+    router.replace(Route::Home {});
+    assert_eq!(router.render(), "<nav>nav</nav>home");
+    router.go_back();
+    assert_eq!(router.current(), Route::Post { id: 1 });
+}
+
+#[test]
+fn follows_redirects_of_guards() {
+    let mut router = MemoryRouter::<Route>::new("/").unwrap();
+    router.navigate("/settings").unwrap();
+    assert_eq!(router.current(), Route::Login {});
+    assert_eq!(router.render(), "<nav>nav</nav>login");
+}
@@ -0,0 +1,125 @@
+#![allow(non_snake_case)]
+use std::{
+    cell::{Cell, RefCell},
+    sync::Arc,
+};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+thread_local! {
+    static LOADED: Cell<bool> = const { Cell::new(false) };
+    static WAKE_SLOW: RefCell<Option<Arc<dyn Fn()>>> = const { RefCell::new(None) };
+    static NAVIGATOR: RefCell<Option<Navigator>> = const { RefCell::new(None) };
+    static STATES: RefCell<Vec<NavigationState>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Frame)]
+        #[route("/")]
+        Home {},
+        #[route("/slow")]
+        Slow {},
+}
+
+#[inline_props]
+fn Frame(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    NAVIGATOR.with(|nav| *nav.borrow_mut() = Some(navigator.clone()));
+    let state = use_navigation_state(cx);
+    STATES.with(|states| states.borrow_mut().push(state));
+
+    render! { Outlet::<Route> {} }
+}
+
+#[inline_props]
+fn Home(cx: Scope) -> Element {
+    render! { "home" }
+}
+
+#[inline_props]
+fn Slow(cx: Scope) -> Element {
+    WAKE_SLOW.with(|wake| *wake.borrow_mut() = Some(cx.schedule_update()));
+    if !LOADED.with(Cell::get) {
+        return cx.suspend()?;
+    }
+
+    render! { "slow" }
+}
+
+fn App(cx: Scope) -> Element {
+    render! {
+        Router::<Route> {
+            config: || RouterConfig::default().history(MemoryHistory::default())
+        }
+    }
+}
+
+fn take_states() -> Vec<NavigationState> {
+    STATES.with(|states| std::mem::take(&mut *states.borrow_mut()))
+}
+
+fn render(vdom: &mut VirtualDom) {
+    // Updates scheduled by the router are sent as messages, so process them until the dom settles
+    for _ in 0..4 {
+        vdom.process_events();
+        let _ = vdom.render_immediate();
+    }
+}
+
+#[test]
+fn transition_waits_for_suspended_route() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    assert_eq!(take_states(), [NavigationState::Idle]);
+
+    let navigator = NAVIGATOR.with(|nav| nav.borrow().clone().unwrap());
+    navigator.navigate_with_transition(Route::Slow {});
+    render(&mut vdom);
+    let pending = NavigationState::Pending {
+        from: "/".to_string(),
+        to: "/slow".to_string(),
+    };
+    assert_eq!(take_states(), [pending]);
+    // The old route is shown until the new one is ready
+    assert_eq!(dioxus_ssr::render(&vdom), "home");
+
+    LOADED.with(|loaded| loaded.set(true));
+    WAKE_SLOW.with(|wake| (wake.borrow().as_ref().unwrap())());
+    render(&mut vdom);
+    assert_eq!(take_states(), [NavigationState::Idle]);
+    assert_eq!(dioxus_ssr::render(&vdom), "slow");
+}
+
+#[test]
+fn transition_without_suspense_finishes_after_render() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    take_states();
+
+    let navigator = NAVIGATOR.with(|nav| nav.borrow().clone().unwrap());
+    navigator.navigate_with_transition(Route::Home {});
+    render(&mut vdom);
+    assert!(!take_states().last().unwrap().is_pending());
+    assert_eq!(dioxus_ssr::render(&vdom), "home");
+}
+
+#[test]
+fn navigation_cancels_transition() {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    take_states();
+
+    let navigator = NAVIGATOR.with(|nav| nav.borrow().clone().unwrap());
+    LOADED.with(|loaded| loaded.set(false));
+    navigator.navigate_with_transition(Route::Slow {});
+    render(&mut vdom);
+    assert!(take_states().last().unwrap().is_pending());
+
+    navigator.push(Route::Home {});
+    render(&mut vdom);
+    assert_eq!(take_states().last(), Some(&NavigationState::Idle));
+    assert_eq!(dioxus_ssr::render(&vdom), "home");
+}
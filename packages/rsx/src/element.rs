@@ -3,8 +3,9 @@ use std::fmt::{Display, Formatter};
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseBuffer, ParseStream},
     punctuated::Punctuated,
     spanned::Spanned,
@@ -73,6 +74,8 @@ impl Parse for Element {
                 continue;
             }
 
+            check_attribute_name(&content)?;
+
             if content.peek(Ident) && content.peek2(Token![:]) && !content.peek3(Token![:]) {
                 let name = content.parse::<Ident>()?;
 
@@ -196,6 +199,60 @@ impl ToTokens for Element {
     }
 }
 
+/// Returns an error for attribute names that are valid in HTML, but can't be parsed as an attribute or event name in
+/// rsx. Names that parse are checked against the element definitions by the rsx macros.
+fn check_attribute_name(stream: ParseStream) -> Result<()> {
+    let fork = stream.fork();
+    let name = match Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(
+        &fork,
+        Ident::parse_any,
+    ) {
+        Ok(name) => name,
+        Err(_) => return Ok(()),
+    };
+    // only check attributes, not elements or component paths
+    if !fork.peek(Token![:]) || fork.peek(Token![::]) {
+        return Ok(());
+    }
+
+    let tokens = name.to_token_stream();
+    let name = name
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    let (problem, suggestion) = if name.contains('-') {
+        if name.starts_with("data-") {
+            (
+                "custom attributes are written as strings in rsx",
+                format!("\"{}\"", name),
+            )
+        } else {
+            (
+                "attribute names use underscores instead of dashes in rsx",
+                name.replace('-', "_"),
+            )
+        }
+    } else if syn::parse_str::<Ident>(&name).is_err() {
+        ("this is a reserved keyword in Rust", format!("r#{}", name))
+    } else if name.starts_with("on") && name.chars().any(char::is_uppercase) {
+        ("event names are lowercase in rsx", name.to_lowercase())
+    } else {
+        return Ok(());
+    };
+
+    let mut error = Error::new_spanned(
+        &tokens,
+        format!("invalid attribute `{}`: {}", name, problem),
+    );
+    error.combine(Error::new_spanned(
+        tokens,
+        format!("help: did you mean `{}`?", suggestion),
+    ));
+    Err(error)
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub enum ElementName {
     Ident(Ident),
@@ -205,7 +262,7 @@ pub enum ElementName {
 impl ElementName {
     pub(crate) fn tag_name(&self) -> TokenStream2 {
         match self {
-            ElementName::Ident(i) => quote! { dioxus_elements::#i::TAG_NAME },
+            ElementName::Ident(i) => quote_spanned! { i.span()=> dioxus_elements::#i::TAG_NAME },
             ElementName::Custom(s) => quote! { #s },
         }
     }
@@ -259,7 +316,10 @@ impl Parse for ElementName {
 impl ToTokens for ElementName {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         match self {
-            ElementName::Ident(i) => tokens.append_all(quote! { dioxus_elements::#i }),
+            // errors about unknown elements and attributes point to the element instead of the whole macro
+            ElementName::Ident(i) => {
+                tokens.append_all(quote_spanned! { i.span()=> dioxus_elements::#i })
+            }
             ElementName::Custom(s) => tokens.append_all(quote! { #s }),
         }
     }
@@ -317,7 +377,7 @@ impl ToTokens for ElementAttrNamed {
         let ElementAttrNamed { el_name, attr } = self;
 
         let ns = |name| match el_name {
-            ElementName::Ident(i) => quote! { dioxus_elements::#i::#name.1 },
+            ElementName::Ident(_) => quote! { #el_name::#name.1 },
             ElementName::Custom(_) => quote! { None },
         };
         let volitile = |name| match el_name {
@@ -380,7 +440,7 @@ impl ToTokens for ElementAttrNamed {
                 }
             }
             ElementAttr::EventTokens { name, tokens } => {
-                quote! {
+                quote_spanned! { name.span()=>
                     dioxus_elements::events::#name(__cx, #tokens)
                 }
             }
@@ -244,6 +244,7 @@ impl<'a> ToTokens for TemplateRenderer<'a> {
                 node_paths: &[ #(#node_paths),* ],
                 attr_paths: &[ #(#attr_paths),* ],
             };
+            ::dioxus::core::register_template!(TEMPLATE);
             ::dioxus::core::VNode {
                 parent: None,
                 key: #key_tokens,
@@ -461,7 +462,7 @@ impl<'a> DynamicContext<'a> {
             BodyNode::Element(el) => {
                 let el_name = &el.name;
                 let ns = |name| match el_name {
-                    ElementName::Ident(i) => quote! { dioxus_elements::#i::#name },
+                    ElementName::Ident(_) => quote! { #el_name::#name },
                     ElementName::Custom(_) => quote! { None },
                 };
                 let static_attrs = el.attributes.iter().map(|attr| match &attr.attr {
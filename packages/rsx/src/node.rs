@@ -1,7 +1,7 @@
 use super::*;
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens, TokenStreamExt};
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     braced,
     parse::{Parse, ParseStream},
@@ -137,11 +137,21 @@ impl ToTokens for BodyNode {
                     location: None,
                 };
 
-                tokens.append_all(quote! {
-                     __cx.make_node(
-                        (#expr).into_iter().map(|#pat| { #renderer })
-                     )
-                })
+                match exp.missing_key_lint() {
+                    Some(lint) => tokens.append_all(quote! {
+                        {
+                            #lint
+                            __cx.make_node(
+                                (#expr).into_iter().map(|#pat| { #renderer })
+                            )
+                        }
+                    }),
+                    None => tokens.append_all(quote! {
+                         __cx.make_node(
+                            (#expr).into_iter().map(|#pat| { #renderer })
+                         )
+                    }),
+                }
             }
             BodyNode::IfChain(chain) => {
                 if is_if_chain_terminated(chain) {
@@ -247,6 +257,45 @@ impl Parse for ForLoop {
     }
 }
 
+impl ForLoop {
+    /// Elements rendered in a loop are diffed by their position if the body of the loop has no key, which rerenders
+    /// every item after an insertion and moves the state of components to the wrong item. This returns a warning about
+    /// the missing key for the loop, or an error with the `deny_missing_keys` feature.
+    fn missing_key_lint(&self) -> Option<TokenStream2> {
+        let has_elements = self
+            .body
+            .iter()
+            .any(|node| matches!(node, BodyNode::Element(_) | BodyNode::Component(_)));
+        let key = match self.body.as_slice() {
+            [BodyNode::Element(el)] => el.key.as_ref(),
+            [BodyNode::Component(comp)] => comp.key(),
+            _ => None,
+        };
+        if !has_elements || key.is_some() {
+            return None;
+        }
+
+        let message = if self.body.len() == 1 {
+            "elements rendered in a for loop should have a unique `key` attribute, like `key: \"{id}\"`, so they are diffed by their key instead of their position"
+        } else {
+            "for loops that render multiple elements should wrap them in a single element with a unique `key` attribute, so they are diffed by their key instead of their position"
+        };
+        let span = self.for_token.span();
+
+        if cfg!(feature = "deny_missing_keys") {
+            return Some(syn::Error::new(span, message).to_compile_error());
+        }
+
+        // there are no custom warnings in proc macros, so the loop uses a deprecated constant to emit one
+        Some(quote_spanned! { span=>
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const for_loop_without_key: () = ();
+            let _ = for_loop_without_key;
+        })
+    }
+}
+
 fn is_if_chain_terminated(chain: &ExprIf) -> bool {
     let mut current = chain;
     loop {
@@ -1,7 +1,7 @@
 use convert_case::{Case, Converter};
 use proc_macro::TokenStream;
 use proc_macro2::Literal;
-use quote::{ToTokens, __private::TokenStream as TokenStream2};
+use quote::{__private::TokenStream as TokenStream2, ToTokens};
 use server_fn_macro::*;
 use syn::{
     parse::{Parse, ParseStream},
@@ -58,6 +58,12 @@ use syn::{
 /// - **The [DioxusServerContext](https::/docs.rs/dioxus-fullstack/latest/dixous_server/prelude/struct.DioxusServerContext.html) comes from the server.** Optionally, the first argument of a server function
 ///   can be a [DioxusServerContext](https::/docs.rs/dioxus-fullstack/latest/dixous_server/prelude/struct.DioxusServerContext.html). This scope can be used to inject dependencies like the HTTP request
 ///   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
+///
+/// ## Transactions
+///
+/// Adding `transactional` to the arguments (e.g. `#[server(AddPost, transactional)]`) runs the body of the server function
+/// inside a transaction. The transaction is begun on the `TransactionSource` registered with `ServerResources::with_transactions`,
+/// committed if the server function returns `Ok` and rolled back otherwise. The body can extract the transaction with `extract::<Transaction<_>, _>()`.
 #[proc_macro_attribute]
 pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     // before we pass this off to the server function macro, we apply extractors and middleware
@@ -81,26 +87,34 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
         }
     });
 
+    let mut args: ServerFnArgs = match syn::parse(args) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
     let ItemFn {
         attrs,
         vis,
         sig,
         block,
     } = function;
+    let block = if args.transactional {
+        quote::quote! {
+            {
+                ::dioxus_fullstack::prelude::run_transactional(async move #block).await
+            }
+        }
+    } else {
+        block.to_token_stream()
+    };
     let mapped_body = quote::quote! {
         #(#attrs)*
-        #vis #sig {
-            #block
-        }
+        #vis #sig #block
     };
 
     let server_fn_path: syn::Path = syn::parse_quote!(::dioxus_fullstack::prelude::server_fn);
     let trait_obj_wrapper: syn::Type =
         syn::parse_quote!(::dioxus_fullstack::prelude::ServerFnTraitObj);
-    let mut args: ServerFnArgs = match syn::parse(args) {
-        Ok(args) => args,
-        Err(e) => return e.to_compile_error().into(),
-    };
     if args.struct_name.is_none() {
         let upper_cammel_case_name = Converter::new()
             .from_case(Case::Snake)
@@ -137,6 +151,78 @@ pub fn server(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
     }
 }
 
+/// Marks a component as server only. The component is rendered during server side rendering, but
+/// its body is not compiled into the client and its subtree is never hydrated.
+///
+/// This is useful for content heavy parts of a page that do not need any interactivity, like a
+/// rendered blog post. Any code and dependencies only used by the component are left out of the
+/// client bundle.
+///
+/// The rendered content is wrapped in a `div` with a `data-dioxus-server-only` attribute and
+/// `display: contents`, so the wrapper does not affect the layout of the page. On the client, the
+/// component renders the same wrapper without any children. Hydration only walks the nodes the
+/// client renders, so the content rendered by the server is left untouched.
+///
+/// ```ignore
+/// # use dioxus::prelude::*; use dioxus_fullstack::prelude::*;
+/// #[server_only]
+/// fn Article(cx: Scope) -> Element {
+///     let html = markdown_to_html(include_str!("article.md"));
+///     render! { div { dangerous_inner_html: "{html}" } }
+/// }
+/// ```
+///
+/// Note the following:
+/// - **Server only components should not have state or event handlers.** Their body only runs on
+///   the server, so the client never re-renders them.
+/// - **Props are still required on the client.** The component is still created by its parent on
+///   the client, so the props type must be available there.
+#[proc_macro_attribute]
+pub fn server_only(_args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
+    let function: syn::ItemFn = match syn::parse(s).map_err(|e| e.to_compile_error()) {
+        Ok(f) => f,
+        Err(e) => return e.into(),
+    };
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    quote::quote! {
+        #(#attrs)*
+        #[cfg_attr(not(feature = "ssr"), allow(unused_variables))]
+        #vis #sig {
+            use ::dioxus::prelude::*;
+
+            #[cfg(feature = "ssr")]
+            {
+                let body: Element = (move || #block)();
+                cx.render(rsx! {
+                    div {
+                        "data-dioxus-server-only": "true",
+                        style: "display: contents",
+                        body
+                    }
+                })
+            }
+
+            #[cfg(not(feature = "ssr"))]
+            {
+                cx.render(rsx! {
+                    div {
+                        "data-dioxus-server-only": "true",
+                        style: "display: contents",
+                    }
+                })
+            }
+        }
+    }
+    .into()
+}
+
 #[derive(Debug)]
 struct Middleware {
     expr: syn::Expr,
@@ -158,7 +244,12 @@ impl Parse for Middleware {
     }
 }
 
+mod kw {
+    syn::custom_keyword!(transactional);
+}
+
 struct ServerFnArgs {
+    transactional: bool,
     struct_name: Option<Ident>,
     _comma: Option<Token![,]>,
     prefix: Option<Literal>,
@@ -185,6 +276,14 @@ impl ToTokens for ServerFnArgs {
 
 impl Parse for ServerFnArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `transactional` may be passed as the first or the last argument
+        let mut transactional = false;
+        if input.peek(kw::transactional) {
+            input.parse::<kw::transactional>()?;
+            input.parse::<Option<Token![,]>>()?;
+            transactional = true;
+        }
+
         let struct_name = input.parse()?;
         let _comma = input.parse()?;
         let prefix = input.parse()?;
@@ -193,7 +292,14 @@ impl Parse for ServerFnArgs {
         let _comma3 = input.parse()?;
         let fn_path = input.parse()?;
 
+        if !transactional && input.peek(kw::transactional) {
+            input.parse::<kw::transactional>()?;
+            input.parse::<Option<Token![,]>>()?;
+            transactional = true;
+        }
+
         Ok(Self {
+            transactional,
             struct_name,
             _comma,
             prefix,
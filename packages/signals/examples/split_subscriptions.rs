@@ -113,8 +113,9 @@ fn ReadsManySignals(cx: Scope) -> Element {
             },
             "Increase First Item"
         }
-        for signal in data.many_signals {
+        for (i, signal) in data.many_signals.into_iter().enumerate() {
             Child {
+                key: "{i}",
                 count: signal,
             }
         }
@@ -8,8 +8,8 @@ use dioxus_signals::*;
 fn create_signals_global() {
     let mut dom = VirtualDom::new(|cx| {
         render! {
-            for _ in 0..10 {
-                Child {}
+            for i in 0..10 {
+                Child { key: "{i}" }
             }
         }
     });
@@ -36,8 +36,8 @@ fn drop_signals() {
 
         let count = if generation % 2 == 0 { 10 } else { 0 };
         render! {
-            for _ in 0..count {
-                Child {}
+            for i in 0..count {
+                Child { key: "{i}" }
             }
         }
     });
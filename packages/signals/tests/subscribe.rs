@@ -31,6 +31,7 @@ fn reading_subscribes() {
             render! {
                 for id in 0..10 {
                     Child {
+                        key: "{id}",
                         signal: signal,
                         counter: cx.props.clone()
                     }
@@ -0,0 +1,159 @@
+//! A cache for the HTML rendered by pure components
+
+use dioxus_core::ScopeState;
+use rustc_hash::FxHasher;
+use std::{
+    hash::{BuildHasherDefault, Hash, Hasher},
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// Mark a component as cacheable. The HTML the component renders is reused by every [`crate::Renderer`] with the same
+/// [`ComponentCache`] when the component is rendered with the same props again.
+///
+/// Returns `true` if the HTML of the component is already cached. The component should return `None` right away, so
+/// neither it nor its children are rendered again. Cache hits are only detected while rendering if the cache of the
+/// renderer was provided to the [`dioxus_core::VirtualDom`] with [`crate::Renderer::provide_component_cache`] before
+/// it was built.
+///
+/// Only mark components as cacheable if their output depends on nothing but the props passed in. Any components
+/// rendered by a cached component are part of its cached HTML.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Props, PartialEq, Hash)]
+/// struct CardProps {
+///     title: String,
+/// }
+///
+/// fn Card(cx: Scope<CardProps>) -> Element {
+///     if dioxus_ssr::component_cache::cache_component(cx, cx.props) {
+///         return None;
+///     }
+///     render! { div { class: "card", "{cx.props.title}" } }
+/// }
+/// ```
+pub fn cache_component(cx: &ScopeState, props: &impl Hash) -> bool {
+    let mut hasher = FxHasher::default();
+    props.hash(&mut hasher);
+    let props = cx.provide_context(PropsHash(hasher.finish()));
+
+    // Take the HTML out of the cache now, so it can't expire before the renderer writes it
+    let html = cx
+        .consume_context::<SharedCache>()
+        .and_then(|shared| shared.cache.get(cx.name(), props, shared.pre_render));
+    cx.provide_context(CachedHtml(html)).0.is_some()
+}
+
+/// The hash of the props of a cacheable component
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct PropsHash(u64);
+
+/// The cached HTML of a component that skipped rendering
+#[derive(Clone)]
+pub(crate) struct CachedHtml(pub(crate) Option<String>);
+
+/// The cache of a renderer, provided to the components of a [`dioxus_core::VirtualDom`]
+#[derive(Clone)]
+pub(crate) struct SharedCache {
+    pub(crate) cache: ComponentCache,
+    pub(crate) pre_render: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    component: String,
+    props: PropsHash,
+    pre_render: bool,
+}
+
+/// A cache of the HTML rendered by components marked with [`cache_component`].
+///
+/// The cache is cheap to clone and can be shared between renderers, so the HTML is reused across requests.
+#[derive(Clone)]
+pub struct ComponentCache {
+    #[allow(clippy::type_complexity)]
+    entries:
+        Arc<Mutex<lru::LruCache<CacheKey, (SystemTime, String), BuildHasherDefault<FxHasher>>>>,
+    invalidate_after: Option<Duration>,
+}
+
+impl Default for ComponentCache {
+    fn default() -> Self {
+        Self::new(10000)
+    }
+}
+
+impl ComponentCache {
+    /// Create a new cache that holds the HTML of at most `limit` component renders.
+    pub fn new(limit: usize) -> Self {
+        let limit = NonZeroUsize::new(limit).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Arc::new(Mutex::new(lru::LruCache::with_hasher(
+                limit,
+                Default::default(),
+            ))),
+            invalidate_after: None,
+        }
+    }
+
+    /// Set the time after which a cached render is discarded.
+    pub fn invalidate_after(mut self, invalidate_after: Duration) -> Self {
+        self.invalidate_after = Some(invalidate_after);
+        self
+    }
+
+    /// Remove all cached renders of the component with the given name.
+    pub fn purge_component(&self, component: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        let keys: Vec<_> = entries
+            .iter()
+            .filter(|(key, _)| key.component == component)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            entries.pop(&key);
+        }
+    }
+
+    /// Remove all cached renders.
+    pub fn purge(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub(crate) fn get(
+        &self,
+        component: &str,
+        props: PropsHash,
+        pre_render: bool,
+    ) -> Option<String> {
+        let key = CacheKey {
+            component: component.to_string(),
+            props,
+            pre_render,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let (timestamp, html) = entries.get(&key)?;
+        let expired = self.invalidate_after.is_some_and(|invalidate_after| {
+            timestamp.elapsed().unwrap_or(Duration::MAX) > invalidate_after
+        });
+        if expired {
+            entries.pop(&key);
+            return None;
+        }
+        Some(html.clone())
+    }
+
+    pub(crate) fn insert(&self, component: &str, props: PropsHash, pre_render: bool, html: String) {
+        let key = CacheKey {
+            component: component.to_string(),
+            props,
+            pre_render,
+        };
+        self.entries
+            .lock()
+            .unwrap()
+            .put(key, (SystemTime::now(), html));
+    }
+}
@@ -0,0 +1,383 @@
+//! Extract the critical CSS of a rendered page.
+//!
+//! The critical CSS is the part of a stylesheet that applies to the elements of the page. Inlining it in the head of
+//! the page lets the browser show the page before the full stylesheet is loaded.
+//!
+//! ```rust
+//! use dioxus::prelude::*;
+//! use dioxus_ssr::critical_css::{Stylesheet, UsedSelectors};
+//!
+//! fn app(cx: Scope) -> Element {
+//!     render! { div { class: "card", "Hello world" } }
+//! }
+//!
+//! let mut dom = VirtualDom::new(app);
+//! _ = dom.rebuild();
+//!
+//! let stylesheet = Stylesheet::parse(".card { padding: 1rem; } .modal { position: fixed; }");
+//! let used = UsedSelectors::from_dom(&dom);
+//! assert_eq!(stylesheet.critical(&used), ".card{padding: 1rem;}");
+//! ```
+
+use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+use rustc_hash::FxHashSet;
+
+/// The tags, classes and ids of the elements in a rendered page.
+#[derive(Debug, Default, Clone)]
+pub struct UsedSelectors {
+    tags: FxHashSet<String>,
+    classes: FxHashSet<String>,
+    ids: FxHashSet<String>,
+}
+
+impl UsedSelectors {
+    /// Collect the tags, classes and ids of the elements rendered in a VirtualDom.
+    pub fn from_dom(dom: &VirtualDom) -> Self {
+        let mut used = Self::default();
+        used.add_scope(dom, ScopeId(0));
+        used
+    }
+
+    /// Check if an element with the tag was rendered.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag.to_ascii_lowercase())
+    }
+
+    /// Check if an element with the class was rendered.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.contains(class)
+    }
+
+    /// Check if an element with the id was rendered.
+    pub fn has_id(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    fn add_scope(&mut self, dom: &VirtualDom, scope: ScopeId) {
+        if let RenderReturn::Ready(node) = dom.get_scope(scope).unwrap().root_node() {
+            self.add_vnode(dom, node);
+        }
+    }
+
+    fn add_vnode(&mut self, dom: &VirtualDom, node: &VNode) {
+        for root in node.template.get().roots {
+            self.add_template_node(root);
+        }
+
+        for attr in node.dynamic_attrs {
+            if attr.namespace.is_none() {
+                if let AttributeValue::Text(value) = attr.value {
+                    self.add_attribute(attr.name, value);
+                }
+            }
+        }
+
+        for dynamic in node.dynamic_nodes {
+            match dynamic {
+                DynamicNode::Component(component) => {
+                    if let Some(scope) = component.mounted_scope() {
+                        self.add_scope(dom, scope);
+                    }
+                }
+                DynamicNode::Fragment(children) => {
+                    for child in *children {
+                        self.add_vnode(dom, child);
+                    }
+                }
+                DynamicNode::Text(_) | DynamicNode::Placeholder(_) => {}
+            }
+        }
+    }
+
+    fn add_template_node(&mut self, node: &TemplateNode) {
+        if let TemplateNode::Element {
+            tag,
+            attrs,
+            children,
+            ..
+        } = node
+        {
+            self.tags.insert(tag.to_ascii_lowercase());
+            for attr in *attrs {
+                if let TemplateAttribute::Static {
+                    name,
+                    value,
+                    namespace: None,
+                } = attr
+                {
+                    self.add_attribute(name, value);
+                }
+            }
+            for child in *children {
+                self.add_template_node(child);
+            }
+        }
+    }
+
+    fn add_attribute(&mut self, name: &str, value: &str) {
+        match name {
+            "class" => self
+                .classes
+                .extend(value.split_whitespace().map(str::to_string)),
+            "id" => {
+                self.ids.insert(value.to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A parsed stylesheet that the critical CSS of pages can be extracted from.
+#[derive(Debug, Default, Clone)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+enum Rule {
+    /// A rule like `.card { padding: 1rem; }`
+    Style {
+        selectors: String,
+        declarations: String,
+    },
+    /// A conditional group rule like `@media` or `@supports` that contains other rules
+    Group { prelude: String, rules: Vec<Rule> },
+    /// An at-rule that is always kept, like `@font-face` or `@keyframes`
+    Verbatim(String),
+}
+
+impl Stylesheet {
+    /// Parse a stylesheet. Statement at-rules like `@import` are dropped, because the full stylesheet loads them.
+    pub fn parse(css: &str) -> Self {
+        let mut css = css;
+        Self {
+            rules: parse_rules(&mut css),
+        }
+    }
+
+    /// The rules of the stylesheet that may apply to the used selectors.
+    ///
+    /// A rule is kept if any of its selectors only refers to tags, classes and ids that were used. Pseudo-classes and
+    /// attribute selectors are ignored, so the critical CSS may contain more rules than the page needs, but never fewer.
+    pub fn critical(&self, used: &UsedSelectors) -> String {
+        let mut css = String::new();
+        write_critical_rules(&self.rules, used, &mut css);
+        css
+    }
+}
+
+fn write_critical_rules(rules: &[Rule], used: &UsedSelectors, css: &mut String) {
+    for rule in rules {
+        match rule {
+            Rule::Style {
+                selectors,
+                declarations,
+            } => {
+                if split_top_level(selectors, ',').any(|selector| selector_is_used(selector, used))
+                {
+                    css.push_str(selectors);
+                    css.push('{');
+                    css.push_str(declarations);
+                    css.push('}');
+                }
+            }
+            Rule::Group { prelude, rules } => {
+                let mut inner = String::new();
+                write_critical_rules(rules, used, &mut inner);
+                if !inner.is_empty() {
+                    css.push_str(prelude);
+                    css.push('{');
+                    css.push_str(&inner);
+                    css.push('}');
+                }
+            }
+            Rule::Verbatim(rule) => css.push_str(rule),
+        }
+    }
+}
+
+/// Parse rules until the end of the stylesheet or the end of the current block
+fn parse_rules(css: &mut &str) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    loop {
+        skip_whitespace_and_comments(css);
+        if css.is_empty() {
+            break;
+        }
+        if let Some(rest) = css.strip_prefix('}') {
+            *css = rest;
+            break;
+        }
+
+        let prelude_end = find_top_level(css, &['{', ';']).unwrap_or(css.len());
+        let prelude = css[..prelude_end].trim();
+        let terminator = css[prelude_end..].chars().next();
+        *css = css.get(prelude_end + 1..).unwrap_or_default();
+
+        if terminator != Some('{') {
+            // statement at-rules like @import and @charset have no block
+            continue;
+        }
+
+        let group = ["@media", "@supports", "@layer", "@container", "@document"]
+            .iter()
+            .any(|at_rule| prelude.starts_with(at_rule));
+        if group {
+            rules.push(Rule::Group {
+                prelude: prelude.to_string(),
+                rules: parse_rules(css),
+            });
+        } else {
+            let block_end = find_top_level(css, &['}']).unwrap_or(css.len());
+            let block = css[..block_end].trim();
+            *css = css.get(block_end + 1..).unwrap_or_default();
+            if prelude.starts_with('@') {
+                rules.push(Rule::Verbatim(format!("{prelude}{{{block}}}")));
+            } else {
+                rules.push(Rule::Style {
+                    selectors: prelude.to_string(),
+                    declarations: block.to_string(),
+                });
+            }
+        }
+    }
+    rules
+}
+
+fn skip_whitespace_and_comments(css: &mut &str) {
+    loop {
+        *css = css.trim_start();
+        match css.strip_prefix("/*") {
+            Some(comment) => *css = comment.split_once("*/").map_or("", |(_, rest)| rest),
+            None => break,
+        }
+    }
+}
+
+/// Find the first of the characters that is not nested in a block, parentheses, a string or a comment
+fn find_top_level(css: &str, targets: &[char]) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut chars = css.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if chars.peek().map(|(_, c)| *c) == Some('*') => {
+                let end = css[i + 2..]
+                    .find("*/")
+                    .map_or(css.len(), |end| i + 2 + end + 2);
+                while chars.peek().is_some_and(|(j, _)| *j < end) {
+                    chars.next();
+                }
+            }
+            (None, _) if depth == 0 && targets.contains(&c) => return Some(i),
+            (None, '{' | '(' | '[') => depth += 1,
+            (None, '}' | ')' | ']') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a string at a separator that is not nested in parentheses or strings
+fn split_top_level(list: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut rest = Some(list);
+    std::iter::from_fn(move || {
+        let current = rest?;
+        match find_top_level(current, &[separator]) {
+            Some(i) => {
+                rest = Some(&current[i + 1..]);
+                Some(&current[..i])
+            }
+            None => {
+                rest = None;
+                Some(current)
+            }
+        }
+    })
+}
+
+/// Check if every tag, class and id in a selector was used
+fn selector_is_used(selector: &str, used: &UsedSelectors) -> bool {
+    let mut chars = selector.trim().chars().peekable();
+    // tags are only at the start of a compound selector
+    let mut compound_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '.' if !used.has_class(&read_ident(&mut chars)) => return false,
+            '#' if !used.has_id(&read_ident(&mut chars)) => return false,
+            // pseudo-classes, pseudo-elements and attribute selectors are ignored
+            ':' => {
+                if chars.peek() == Some(&':') {
+                    chars.next();
+                }
+                read_ident(&mut chars);
+                if chars.peek() == Some(&'(') {
+                    skip_nested(&mut chars, '(', ')');
+                }
+            }
+            '[' => skip_nested(&mut chars, '[', ']'),
+            ' ' | '\t' | '\n' | '>' | '+' | '~' => {
+                compound_start = true;
+                continue;
+            }
+            '*' => {}
+            c if compound_start && is_ident_char(c) => {
+                let mut tag = c.to_string();
+                tag.push_str(&read_ident(&mut chars));
+                // the document element and body are part of every page
+                let always_used = ["html", "body"]
+                    .iter()
+                    .any(|root| tag.eq_ignore_ascii_case(root));
+                if !always_used && !used.has_tag(&tag) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+        compound_start = false;
+    }
+    true
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Read an identifier, unescaping escaped characters like the colon in `.hover\:underline`
+fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut ident = String::new();
+    while let Some(&c) = chars.peek() {
+        if c == '\\' {
+            chars.next();
+            if let Some(escaped) = chars.next() {
+                ident.push(escaped);
+            }
+        } else if is_ident_char(c) {
+            ident.push(c);
+            chars.next();
+        } else {
+            break;
+        }
+    }
+    ident
+}
+
+fn skip_nested(chars: &mut std::iter::Peekable<std::str::Chars>, open: char, close: char) {
+    let mut depth = 0usize;
+    for c in chars.by_ref() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth <= 1 {
+                break;
+            }
+            depth -= 1;
+        }
+    }
+}
@@ -83,7 +83,7 @@ impl IncrementalRenderer {
             let mut vdom = VirtualDom::new_with_props(comp, props);
             rebuild_with(&mut vdom).await;
 
-            renderer.render_before_body(&mut *html_buffer)?;
+            renderer.render_before_body_of_route(&route, &mut *html_buffer)?;
             self.ssr_renderer.render_to(&mut html_buffer, &vdom)?;
         }
         renderer.render_after_body(&mut *html_buffer)?;
@@ -17,6 +17,14 @@ pub trait WrapBody {
     fn render_before_body<R: Write>(&self, to: &mut R) -> Result<(), IncrementalRendererError>;
     /// Render the HTML after the body
     fn render_after_body<R: Write>(&self, to: &mut R) -> Result<(), IncrementalRendererError>;
+    /// Render the HTML before the body of a specific route. Defaults to [`WrapBody::render_before_body`]
+    fn render_before_body_of_route<R: Write>(
+        &self,
+        _route: &str,
+        to: &mut R,
+    ) -> Result<(), IncrementalRendererError> {
+        self.render_before_body(to)
+    }
 }
 
 /// The default page renderer
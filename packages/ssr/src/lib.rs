@@ -1,7 +1,9 @@
 #![doc = include_str!("../README.md")]
 
 mod cache;
+pub mod component_cache;
 pub mod config;
+pub mod critical_css;
 mod fs_cache;
 pub mod incremental;
 mod incremental_cfg;
@@ -1,5 +1,6 @@
 use super::cache::Segment;
 use crate::cache::StringCache;
+use crate::component_cache::{CachedHtml, ComponentCache, PropsHash, SharedCache};
 use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
 use std::collections::HashMap;
 use std::fmt::Write;
@@ -20,10 +21,17 @@ pub struct Renderer {
     /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
     pub pre_render: bool,
 
+    /// Write the events each element listens to into the element so the client can hydrate the page lazily, once the
+    /// user interacts with it. Only used if `pre_render` is enabled
+    pub lazy_hydration: bool,
+
     // Currently not implemented
     // Don't proceed onto new components. Instead, put the name of the component.
     pub skip_components: bool,
 
+    /// A cache for the HTML of components marked with [`crate::component_cache::cache_component`]
+    pub component_cache: Option<ComponentCache>,
+
     /// A cache of templates that have been rendered
     template_cache: HashMap<&'static str, Arc<StringCache>>,
 }
@@ -33,6 +41,18 @@ impl Renderer {
         Self::default()
     }
 
+    /// Provide the component cache of this renderer to the components of a [`VirtualDom`], so components marked with
+    /// [`crate::component_cache::cache_component`] can skip rendering if their HTML is cached. Call this before the
+    /// [`VirtualDom`] is built.
+    pub fn provide_component_cache(&self, dom: &VirtualDom) {
+        if let Some(cache) = &self.component_cache {
+            dom.base_scope().provide_context(SharedCache {
+                cache: cache.clone(),
+                pre_render: self.pre_render,
+            });
+        }
+    }
+
     pub fn render(&mut self, dom: &VirtualDom) -> String {
         let mut buf = String::new();
         self.render_to(&mut buf, dom).unwrap();
@@ -93,6 +113,13 @@ impl Renderer {
                             AttributeValue::Float(value) => {
                                 write!(buf, " {}={}", attr.name, value)?
                             }
+                            AttributeValue::Listener(_) if self.pre_render && self.lazy_hydration => {
+                                write!(
+                                    buf,
+                                    " data-dioxus-on-{}=\"\"",
+                                    attr.name.trim_start_matches("on")
+                                )?
+                            }
                             _ => {}
                         };
                     }
@@ -102,16 +129,48 @@ impl Renderer {
                         if self.skip_components {
                             write!(buf, "<{}><{}/>", node.name, node.name)?;
                         } else {
+                            let name = node.name;
                             let id = node.mounted_scope().unwrap();
                             let scope = dom.get_scope(id).unwrap();
-                            let node = scope.root_node();
-                            match node {
-                                RenderReturn::Ready(node) => {
-                                    self.render_template(buf, dom, node)?
+                            // The component skipped rendering because its HTML is cached
+                            if let Some(CachedHtml(Some(html))) = scope.has_context() {
+                                write!(buf, "{html}")?;
+                                continue;
+                            }
+                            let node = match scope.root_node() {
+                                RenderReturn::Ready(node) => node,
+                                // Suspended components are mounted as a placeholder
+                                RenderReturn::Aborted(_) => {
+                                    if self.pre_render {
+                                        write!(buf, "<pre></pre>")?;
+                                    }
+                                    continue;
+                                }
+                            };
+
+                            let cached = self
+                                .component_cache
+                                .clone()
+                                .zip(scope.has_context::<PropsHash>());
+                            match cached {
+                                Some((cache, props)) => {
+                                    let html = match cache.get(name, props, self.pre_render) {
+                                        Some(html) => html,
+                                        None => {
+                                            let mut html = String::new();
+                                            self.render_template(&mut html, dom, node)?;
+                                            cache.insert(
+                                                name,
+                                                props,
+                                                self.pre_render,
+                                                html.clone(),
+                                            );
+                                            html
+                                        }
+                                    };
+                                    write!(buf, "{html}")?;
                                 }
-                                _ => todo!(
-                                    "generally, scopes should be sync, only if being traversed"
-                                ),
+                                None => self.render_template(buf, dom, node)?,
                             }
                         }
                     }
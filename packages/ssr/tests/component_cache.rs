@@ -0,0 +1,59 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use dioxus_ssr::component_cache::{cache_component, ComponentCache};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Props, PartialEq, Hash)]
+struct CardProps {
+    title: &'static str,
+}
+
+fn Card(cx: Scope<CardProps>) -> Element {
+    if cache_component(cx, cx.props) {
+        return None;
+    }
+    let renders = RENDERS.fetch_add(1, Ordering::Relaxed);
+    render! { div { "{cx.props.title} {renders}" } }
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Card { title: "a" }
+        Card { title: "b" }
+    }
+}
+
+fn render(renderer: &mut dioxus_ssr::Renderer) -> String {
+    let mut dom = VirtualDom::new(app);
+    renderer.provide_component_cache(&dom);
+    _ = dom.rebuild();
+    renderer.render(&dom)
+}
+
+#[test]
+fn cached_components_are_reused() {
+    let cache = ComponentCache::default();
+    let mut first = dioxus_ssr::Renderer::new();
+    first.component_cache = Some(cache.clone());
+    let mut second = dioxus_ssr::Renderer::new();
+    second.component_cache = Some(cache.clone());
+
+    let html = render(&mut first);
+    assert_eq!(html, "<div>a 0</div><div>b 1</div>");
+
+    // The cache is shared between renderers, and cached components are not rendered again
+    assert_eq!(render(&mut second), html);
+    assert_eq!(RENDERS.load(Ordering::Relaxed), 2);
+
+    cache.purge_component("Card");
+    assert_eq!(render(&mut first), "<div>a 2</div><div>b 3</div>");
+
+    // Without a cache every render is fresh
+    assert_eq!(
+        render(&mut dioxus_ssr::Renderer::new()),
+        "<div>a 4</div><div>b 5</div>"
+    );
+}
@@ -0,0 +1,60 @@
+use dioxus::prelude::*;
+use dioxus_ssr::critical_css::{Stylesheet, UsedSelectors};
+
+#[test]
+fn critical_css_only_contains_used_rules() {
+    fn app(cx: Scope) -> Element {
+        let active = "active";
+        render! {
+            nav { id: "menu", class: "bar {active}",
+                Link {}
+            }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn Link(cx: Scope) -> Element {
+        render! { a { class: "hover:underline", "Home" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let stylesheet = Stylesheet::parse(
+        r#"
+        @import url("fonts.css");
+        /* the page */
+        body { margin: 0; }
+        nav.bar > a, .modal a { color: red; }
+        #menu .active:hover { color: blue; }
+        .hover\:underline:hover { text-decoration: underline; }
+        .modal { position: fixed; }
+        table { border-collapse: collapse; }
+        @media (max-width: 600px) {
+            .bar { flex-direction: column; }
+            .modal { inset: 0; }
+        }
+        @media print {
+            .modal { display: none; }
+        }
+        @font-face { font-family: "Inter"; src: url("inter.woff2"); }
+        "#,
+    );
+
+    let used = UsedSelectors::from_dom(&dom);
+    assert!(used.has_tag("NAV"));
+    assert!(used.has_class("active"));
+    assert!(used.has_id("menu"));
+
+    assert_eq!(
+        stylesheet.critical(&used),
+        concat!(
+            "body{margin: 0;}",
+            "nav.bar > a, .modal a{color: red;}",
+            "#menu .active:hover{color: blue;}",
+            r#".hover\:underline:hover{text-decoration: underline;}"#,
+            "@media (max-width: 600px){.bar{flex-direction: column;}}",
+            r#"@font-face{font-family: "Inter"; src: url("inter.woff2");}"#,
+        )
+    );
+}
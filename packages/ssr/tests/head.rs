@@ -0,0 +1,55 @@
+use dioxus::prelude::*;
+
+#[test]
+fn head_metadata_is_collected() {
+    fn app(cx: Scope) -> Element {
+        use_title(cx, "Home");
+        use_meta(cx, "description", "A \"quoted\" description");
+        render! { Settings {} }
+    }
+
+    #[allow(non_snake_case)]
+    fn Settings(cx: Scope) -> Element {
+        // nested components override the title of their parents
+        use_title(cx, "Home | <Settings>");
+        use_favicon(cx, "/favicon.png");
+        render! { div { "settings" } }
+    }
+
+    let head = HeadContext::new();
+    let mut dom = VirtualDom::new(app);
+    dom.base_scope().provide_context(head.clone());
+    _ = dom.rebuild();
+
+    assert_eq!(dioxus_ssr::render(&dom), "<div>settings</div>");
+    assert_eq!(head.title().as_deref(), Some("Home | <Settings>"));
+    assert_eq!(
+        head.render(),
+        concat!(
+            "<title>Home | &lt;Settings&gt;</title>",
+            r#"<link rel="icon" href="/favicon.png">"#,
+            r#"<meta name="description" content="A &quot;quoted&quot; description">"#,
+        )
+    );
+}
+
+#[test]
+fn head_is_only_updated_on_change() {
+    fn app(cx: Scope) -> Element {
+        let count = cx.use_hook(|| 0);
+        *count += 1;
+        use_title(cx, "Static title");
+        render! { "{count}" }
+    }
+
+    let head = HeadContext::new();
+    let mut dom = VirtualDom::new(app);
+    dom.base_scope().provide_context(head.clone());
+    _ = dom.rebuild();
+
+    head.set_title("Changed outside the app");
+    dom.mark_dirty(ScopeId(0));
+    _ = dom.render_immediate();
+
+    assert_eq!(head.title().as_deref(), Some("Changed outside the app"));
+}
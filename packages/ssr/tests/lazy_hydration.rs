@@ -0,0 +1,31 @@
+use dioxus::prelude::*;
+
+#[test]
+fn listeners_are_written_for_lazy_hydration() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                button { onclick: |_| {}, onmouseenter: |_| {}, "click me" }
+                input { oninput: |_| {} }
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.pre_render = true;
+    renderer.lazy_hydration = true;
+    assert_eq!(
+        renderer.render(&dom),
+        r#"<div><button data-dioxus-on-click="" data-dioxus-on-mouseenter="">click me</button><input data-dioxus-on-input=""/></div>"#
+    );
+
+    // Listeners are only written if the page can be hydrated
+    renderer.pre_render = false;
+    assert_eq!(
+        renderer.render(&dom),
+        "<div><button>click me</button><input/></div>"
+    );
+}
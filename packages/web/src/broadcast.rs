@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+
+use dioxus_core::ScopeState;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::MessageEvent;
+
+/// Subscribe to a [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel) that
+/// is shared between every tab and window of the app.
+///
+/// Messages posted by one tab are delivered to the handler of every other tab subscribed to a channel with the same
+/// name. Messages are never delivered back to the channel that posted them. The channel is closed when the component
+/// is dropped.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let user = use_state(cx, || None::<String>);
+///     let channel = dioxus_web::use_broadcast_channel(cx, "auth", {
+///         to_owned![user];
+///         move |message: Option<String>| user.set(message)
+///     });
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 user.set(None);
+///                 channel.post(&None);
+///             },
+///             "Log out everywhere"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_broadcast_channel<'a, T: Serialize + DeserializeOwned + 'static>(
+    cx: &'a ScopeState,
+    name: &str,
+    on_message: impl FnMut(T) + 'static,
+) -> &'a BroadcastChannel<T> {
+    cx.use_hook(|| BroadcastChannel::new(name, on_message))
+}
+
+/// A channel created with [`use_broadcast_channel`].
+pub struct BroadcastChannel<T> {
+    channel: Option<web_sys::BroadcastChannel>,
+    _on_message: Option<Closure<dyn FnMut(MessageEvent)>>,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<T: Serialize + DeserializeOwned + 'static> BroadcastChannel<T> {
+    fn new(name: &str, mut on_message: impl FnMut(T) + 'static) -> Self {
+        let channel = match web_sys::BroadcastChannel::new(name) {
+            Ok(channel) => channel,
+            Err(err) => {
+                log::error!("Failed to open broadcast channel {name}: {err:?}");
+                return Self {
+                    channel: None,
+                    _on_message: None,
+                    phantom: PhantomData,
+                };
+            }
+        };
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(data) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str(&data) {
+                Ok(message) => on_message(message),
+                Err(err) => log::error!("Failed to deserialize broadcast message: {err}"),
+            }
+        });
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        Self {
+            channel: Some(channel),
+            _on_message: Some(on_message),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Send a message to every other subscriber of the channel.
+    pub fn post(&self, message: &T) {
+        let Some(channel) = &self.channel else {
+            return;
+        };
+        match serde_json::to_string(message) {
+            Ok(data) => {
+                if let Err(err) = channel.post_message(&JsValue::from_str(&data)) {
+                    log::error!("Failed to post broadcast message: {err:?}");
+                }
+            }
+            Err(err) => log::error!("Failed to serialize broadcast message: {err}"),
+        }
+    }
+}
+
+impl<T> Drop for BroadcastChannel<T> {
+    fn drop(&mut self) {
+        if let Some(channel) = &self.channel {
+            channel.set_onmessage(None);
+            channel.close();
+        }
+    }
+}
@@ -10,9 +10,14 @@
 pub struct Config {
     #[cfg(feature = "hydrate")]
     pub(crate) hydrate: bool,
+    #[cfg(feature = "hydrate")]
+    pub(crate) lazy_hydration: bool,
     pub(crate) rootname: String,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
+    pub(crate) mutation_budget: Option<usize>,
+    pub(crate) trusted_types_policy: Option<String>,
+    pub(crate) html_sanitizer: Option<Box<dyn Fn(String) -> String>>,
 }
 
 impl Default for Config {
@@ -20,9 +25,14 @@ impl Default for Config {
         Self {
             #[cfg(feature = "hydrate")]
             hydrate: false,
+            #[cfg(feature = "hydrate")]
+            lazy_hydration: false,
             rootname: "main".to_string(),
             cached_strings: Vec::new(),
             default_panic_hook: true,
+            mutation_budget: None,
+            trusted_types_policy: None,
+            html_sanitizer: None,
         }
     }
 }
@@ -42,11 +52,31 @@ impl Config {
     /// work and suspended nodes.
     ///
     /// Dioxus will load up all the elements with the `dio_el` data attribute into memory when the page is loaded.
+    ///
+    /// Events the user triggers before the page is hydrated are replayed once it is, if the page loads
+    /// `dioxus_interpreter_js::EVENT_REPLAY_JS` in its head. dioxus-fullstack serves it for hydrated pages.
     pub fn hydrate(mut self, f: bool) -> Self {
         self.hydrate = f;
         self
     }
 
+    #[cfg(feature = "hydrate")]
+    /// Experimental: hydrate a server rendered page lazily, once the user interacts with it
+    ///
+    /// Instead of rendering the app as soon as the page loads, Dioxus waits for the first event one of the server
+    /// rendered elements listens to. It then hydrates the page and replays the event. If the browser becomes idle
+    /// before the user interacts with the page, the page is hydrated then. The page must be rendered with
+    /// `dioxus_ssr::Renderer::lazy_hydration` enabled. Hooks that need the state the server rendered with should read it
+    /// from the HTML, for example with `server_cached` from dioxus-fullstack.
+    /// This is not resumability: hook state is not serialized, and the whole app still renders on the client before it
+    /// handles the first event.
+    ///
+    /// Implies [`Config::hydrate`].
+    pub fn lazy_hydration(mut self, f: bool) -> Self {
+        self.lazy_hydration = f;
+        self
+    }
+
     /// Set the name of the element that Dioxus will use as the root.
     ///
     /// This is akin to calling React.render() on the element with the specified name.
@@ -71,4 +101,41 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Limit the number of edits applied to the page in a single frame. Large updates are split up and applied over
+    /// multiple frames so they don't block the main thread.
+    ///
+    /// See [`VirtualDom::with_mutation_budget`](dioxus_core::VirtualDom::with_mutation_budget).
+    pub fn with_mutation_budget(mut self, budget: usize) -> Self {
+        self.mutation_budget = Some(budget);
+        self
+    }
+
+    /// Write to dangerous sinks like `innerHTML` through a [Trusted Types](https://developer.mozilla.org/en-US/docs/Web/API/Trusted_Types_API)
+    /// policy with this name, so the app can be deployed with the `require-trusted-types-for 'script'` CSP directive.
+    /// The policy name must be allowed by the `trusted-types` directive of the CSP.
+    ///
+    /// The policy only trusts what the interpreter writes itself. Dioxus does not sanitize html: `dangerous_inner_html`
+    /// is passed through the sanitizer set with [`Config::with_html_sanitizer`], or DOMPurify if the page loaded it.
+    /// Without either, it is written with the [Sanitizer API](https://developer.mozilla.org/en-US/docs/Web/API/HTML_Sanitizer_API)
+    /// in browsers that support it, and rejected in the others. Script urls must come from the same origin as the page,
+    /// and `javascript:` urls written to attributes like `href` and `src` are blocked even in browsers without Trusted
+    /// Types support. Rejected html, blocked urls and Trusted Types violations are logged as errors.
+    pub fn with_trusted_types_policy(mut self, name: impl Into<String>) -> Self {
+        self.trusted_types_policy = Some(name.into());
+        self
+    }
+
+    /// Sanitize the html written with `dangerous_inner_html` under the policy set with
+    /// [`Config::with_trusted_types_policy`]. Use a maintained sanitizer like [ammonia](https://docs.rs/ammonia).
+    ///
+    /// ```rust, ignore
+    /// Config::new()
+    ///     .with_trusted_types_policy("dioxus")
+    ///     .with_html_sanitizer(|html| ammonia::clean(&html))
+    /// ```
+    pub fn with_html_sanitizer(mut self, sanitize: impl Fn(String) -> String + 'static) -> Self {
+        self.html_sanitizer = Some(Box::new(sanitize));
+        self
+    }
 }
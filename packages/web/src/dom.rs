@@ -11,11 +11,13 @@ use dioxus_core::{
     BorrowedAttributeValue, ElementId, Mutation, Template, TemplateAttribute, TemplateNode,
 };
 use dioxus_html::{event_bubbles, CompositionData, FormData, MountedData};
-use dioxus_interpreter_js::{get_node, minimal_bindings, save_template, Channel};
+use dioxus_interpreter_js::{
+    get_node, minimal_bindings, save_template, set_trusted_sinks, Channel,
+};
 use futures_channel::mpsc;
 use js_sys::Array;
 use rustc_hash::FxHashMap;
-use std::{any::Any, rc::Rc};
+use std::{any::Any, cell::RefCell, rc::Rc};
 use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
 use web_sys::{Document, Element, Event};
 
@@ -29,17 +31,27 @@ pub struct WebsysDom {
     max_template_id: u32,
     pub(crate) interpreter: Channel,
     event_channel: mpsc::UnboundedSender<UiEvent>,
+    // The generation of every element id with a listener, read when an event fires so stale events can be dropped
+    generations: Rc<RefCell<FxHashMap<ElementId, u32>>>,
+    // The regions of the page with their own hydration priority, found while hydrating it
+    #[cfg(feature = "hydrate")]
+    pub(crate) regions: crate::priority::HydrationRegions,
+    #[cfg(debug_assertions)]
+    last_orphan_check: f64,
 }
 
 pub struct UiEvent {
     pub name: String,
     pub bubbles: bool,
     pub element: ElementId,
+    /// The generation of the element's id when the event fired, if the element's listeners were created by the
+    /// VirtualDom
+    pub generation: Option<u32>,
     pub data: Rc<dyn Any>,
 }
 
 impl WebsysDom {
-    pub fn new(cfg: Config, event_channel: mpsc::UnboundedSender<UiEvent>) -> Self {
+    pub fn new(mut cfg: Config, event_channel: mpsc::UnboundedSender<UiEvent>) -> Self {
         // eventually, we just want to let the interpreter do all the work of decoding events into our event type
         // a match here in order to avoid some error during runtime browser test
         let document = load_document();
@@ -48,9 +60,11 @@ impl WebsysDom {
             None => document.create_element("body").ok().unwrap(),
         };
         let interpreter = Channel::default();
+        let generations: Rc<RefCell<FxHashMap<ElementId, u32>>> = Default::default();
 
         let handler: Closure<dyn FnMut(&Event)> = Closure::wrap(Box::new({
             let event_channel = event_channel.clone();
+            let generations = generations.clone();
             move |event: &web_sys::Event| {
                 let name = event.type_();
                 let element = walk_event_for_id(event);
@@ -80,10 +94,12 @@ impl WebsysDom {
                     }
 
                     let data = virtual_event_from_websys_event(event.clone(), target);
+                    let generation = generations.borrow().get(&element).copied();
                     let _ = event_channel.unbounded_send(UiEvent {
                         name,
                         bubbles,
                         element,
+                        generation,
                         data,
                     });
                 }
@@ -95,6 +111,30 @@ impl WebsysDom {
             handler.as_ref().unchecked_ref(),
         );
         handler.forget();
+
+        if let Some(policy_name) = &cfg.trusted_types_policy {
+            let report: Closure<dyn FnMut(String, String)> =
+                Closure::wrap(Box::new(|sink: String, sample: String| {
+                    log::error!("Blocked a write to the trusted types sink {sink}: {sample}");
+                }));
+            let sanitize = cfg
+                .html_sanitizer
+                .take()
+                .map(|sanitize| Closure::<dyn Fn(String) -> String>::wrap(sanitize));
+            let sinks = minimal_bindings::createTrustedSinks(
+                policy_name,
+                sanitize
+                    .as_ref()
+                    .map_or(&JsValue::NULL, |sanitize| sanitize.as_ref()),
+                report.as_ref().unchecked_ref(),
+            );
+            set_trusted_sinks(sinks);
+            report.forget();
+            if let Some(sanitize) = sanitize {
+                sanitize.forget();
+            }
+        }
+
         Self {
             document,
             root,
@@ -102,6 +142,11 @@ impl WebsysDom {
             templates: FxHashMap::default(),
             max_template_id: 0,
             event_channel,
+            generations,
+            #[cfg(feature = "hydrate")]
+            regions: Default::default(),
+            #[cfg(debug_assertions)]
+            last_orphan_check: 0.0,
         }
     }
 
@@ -173,7 +218,13 @@ impl WebsysDom {
         let i = &mut self.interpreter;
         // we need to apply the mount events last, so we collect them here
         let mut to_mount = Vec::new();
-        for edit in &edits {
+        // the batched paths are read by the interpreter when the channel is flushed, so they need to outlive the loop
+        let mut path_batches = Vec::new();
+        let mut batched = vec![false; edits.len()];
+        for (idx, edit) in edits.iter().enumerate() {
+            if batched[idx] {
+                continue;
+            }
             match edit {
                 AppendChildren { id, m } => i.append_children(id.0 as u32, *m as u32),
                 AssignId { path, id } => {
@@ -186,7 +237,50 @@ impl WebsysDom {
                 }
                 LoadTemplate { name, index, id } => {
                     if let Some(tmpl_id) = self.templates.get(*name) {
-                        i.load_template(*tmpl_id, *index as u32, id.0 as u32)
+                        i.load_template(*tmpl_id, *index as u32, id.0 as u32);
+
+                        // Hydrate all the nodes in the fresh template in one walk instead of one walk per node
+                        let window = edits[idx + 1..].iter().take_while(|edit| {
+                            matches!(
+                                edit,
+                                AssignId { .. }
+                                    | HydrateText { .. }
+                                    | SetAttribute { .. }
+                                    | NewEventListener { .. }
+                            )
+                        });
+                        let mut targets = Vec::new();
+                        for (offset, edit) in window.enumerate() {
+                            match edit {
+                                AssignId { path, id } | HydrateText { path, id, .. } => {
+                                    targets.push((idx + 1 + offset, *path, *id))
+                                }
+                                _ => {}
+                            }
+                        }
+                        if targets.len() > 1 {
+                            targets.sort_by_key(|(_, path, _)| *path);
+                            let mut batch = Vec::new();
+                            for (edit_idx, path, id) in &targets {
+                                batch.push(path.len() as u8);
+                                batch.extend_from_slice(path);
+                                batch.extend_from_slice(&(id.0 as u32).to_le_bytes());
+                                batched[*edit_idx] = true;
+                            }
+                            i.hydrate_paths(
+                                *tmpl_id,
+                                *index as u32,
+                                batch.as_ptr() as u32,
+                                batch.len() as u32,
+                            );
+                            // the text nodes in templates are always text nodes, so only their text needs to be set
+                            for (edit_idx, _, id) in &targets {
+                                if let HydrateText { value, .. } = &edits[*edit_idx] {
+                                    i.set_text(id.0 as u32, value);
+                                }
+                            }
+                            path_batches.push(batch);
+                        }
                     }
                 }
                 ReplaceWith { id, m } => i.replace_with(id.0 as u32, *m as u32),
@@ -222,11 +316,16 @@ impl WebsysDom {
                     _ => unreachable!(),
                 },
                 SetText { value, id } => i.set_text(id.0 as u32, value),
-                NewEventListener { name, id, .. } => {
+                NewEventListener {
+                    name,
+                    id,
+                    generation,
+                } => {
+                    self.generations.borrow_mut().insert(*id, *generation);
                     match *name {
                         // mounted events are fired immediately after the element is mounted.
                         "mounted" => {
-                            to_mount.push(*id);
+                            to_mount.push((*id, *generation));
                         }
                         _ => {
                             i.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8);
@@ -241,12 +340,17 @@ impl WebsysDom {
                 },
                 Remove { id } => i.remove(id.0 as u32),
                 PushRoot { id } => i.push_root(id.0 as u32),
+                MarkerStart { .. } | MarkerEnd { .. } => {}
             }
         }
         edits.clear();
         i.flush();
+        drop(path_batches);
 
-        for id in to_mount {
+        #[cfg(debug_assertions)]
+        self.check_orphaned_nodes();
+
+        for (id, generation) in to_mount {
             let node = get_node(id.0 as u32);
             if let Some(element) = node.dyn_ref::<Element>() {
                 log::info!("mounted event fired: {}", id.0);
@@ -256,6 +360,7 @@ impl WebsysDom {
                     name: "mounted".to_string(),
                     bubbles: false,
                     element: id,
+                    generation: Some(generation),
                     data,
                 });
             }
@@ -263,6 +368,23 @@ impl WebsysDom {
     }
 }
 
+impl WebsysDom {
+    /// Warn about nodes the interpreter still tracks after they were removed from the document. Every node the
+    /// interpreter tracks should be mounted once a batch of edits is applied.
+    #[cfg(debug_assertions)]
+    fn check_orphaned_nodes(&mut self) {
+        let now = js_sys::Date::now();
+        if now - self.last_orphan_check < 1000.0 {
+            return;
+        }
+        self.last_orphan_check = now;
+        let orphans = dioxus_interpreter_js::orphaned_nodes();
+        if !orphans.is_empty() {
+            log::warn!("The interpreter tracks removed nodes with the ids {orphans:?}");
+        }
+    }
+}
+
 // todo: some of these events are being casted to the wrong event type.
 // We need tests that simulate clicks/etc and make sure every event type works.
 pub fn virtual_event_from_websys_event(event: web_sys::Event, target: Element) -> Rc<dyn Any> {
@@ -385,6 +507,23 @@ fn read_input_to_data(target: Element) -> Rc<FormData> {
         }
     }
 
+    // select elements with the multiple attribute report all selected options under their name
+    if let Some(select) = target.dyn_ref::<web_sys::HtmlSelectElement>() {
+        if select.multiple() {
+            let options = select.selected_options();
+            let selected = (0..options.length())
+                .filter_map(|i| options.item(i))
+                .filter_map(|option| option.dyn_into::<web_sys::HtmlOptionElement>().ok())
+                .map(|option| option.value())
+                .collect();
+            let name = match select.name() {
+                name if name.is_empty() => "value".to_string(),
+                name => name,
+            };
+            values.insert(name, selected);
+        }
+    }
+
     #[cfg(not(feature = "file_engine"))]
     let files = None;
     #[cfg(feature = "file_engine")]
@@ -0,0 +1,63 @@
+// Wait for the first event the server rendered page listens to, without hydrating the page. If the browser becomes
+// idle first, the callback is called without an event so the page still hydrates.
+export function waitForFirstEvent(root, callback) {
+  const prefix = "data-dioxus-on-";
+  const names = new Set();
+  for (const element of root.querySelectorAll("*")) {
+    for (const attribute of element.getAttributeNames()) {
+      if (attribute.startsWith(prefix)) {
+        names.add(attribute.slice(prefix.length));
+      }
+    }
+  }
+
+  const handler = (event) => {
+    // Find the closest element that listens to the event
+    let target = event.target;
+    while (target != null && target !== root) {
+      if (target.hasAttribute && target.hasAttribute(prefix + event.type)) {
+        break;
+      }
+      target = target.parentNode;
+    }
+    if (target == null || target === root) {
+      return;
+    }
+
+    const preventDefaultRequests = target.getAttribute("dioxus-prevent-default");
+    if (preventDefaultRequests && preventDefaultRequests.includes(`on${event.type}`)) {
+      event.preventDefault();
+    }
+    // The event is replayed once the page is hydrated
+    event.stopImmediatePropagation();
+    finish(event);
+  };
+
+  let finished = false;
+  const finish = (event) => {
+    if (finished) {
+      return;
+    }
+    finished = true;
+    for (const name of names) {
+      root.removeEventListener(name, handler, true);
+    }
+    callback(event);
+  };
+
+  for (const name of names) {
+    root.addEventListener(name, handler, true);
+  }
+
+  // Pages without listeners never see an event, and the rest still need to hydrate eventually
+  if (typeof requestIdleCallback === "undefined") {
+    setTimeout(() => finish(null), 1);
+  } else {
+    requestIdleCallback(() => finish(null));
+  }
+}
+
+// Dispatch a copy of an event that was captured before the page was hydrated
+export function replayEvent(event) {
+  event.target.dispatchEvent(new event.constructor(event.type, event));
+}
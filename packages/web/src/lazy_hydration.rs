@@ -0,0 +1,36 @@
+//! Experimental lazy hydration: start from a server rendered page without hydrating it until the user interacts with it.
+//!
+//! The server writes the events each element listens to into the page (see `dioxus_ssr::Renderer::lazy_hydration`). The
+//! client waits for the first of those events before it builds the VirtualDom and hydrates the page, then replays the
+//! event so the app sees it. If the browser becomes idle before the user interacts with the page, the page is hydrated
+//! then, so hydration is moved off the critical path without waiting on the user forever.
+
+use futures_channel::oneshot;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, Event};
+
+#[wasm_bindgen(module = "/src/lazy_hydration.js")]
+extern "C" {
+    fn waitForFirstEvent(root: &Element, callback: &js_sys::Function);
+
+    fn replayEvent(event: &Event);
+}
+
+/// Wait until the user triggers an event that the server rendered page listens to, or until the browser is idle.
+///
+/// Returns the event that should be replayed after the page is hydrated, or [`None`] if the browser became idle first.
+pub(crate) async fn wait_for_first_event(root: &Element) -> Option<Event> {
+    let (tx, rx) = oneshot::channel();
+    let callback = Closure::once(move |event: Option<Event>| {
+        let _ = tx.send(event);
+    });
+    waitForFirstEvent(root, callback.as_ref().unchecked_ref());
+    let event = rx.await.ok().flatten();
+    drop(callback);
+    event
+}
+
+/// Dispatch a copy of an event that was captured before the page was hydrated.
+pub(crate) fn replay_event(event: &Event) {
+    replayEvent(event)
+}
@@ -53,14 +53,22 @@
 //     - Do the VDOM work during the idlecallback
 //     - Do DOM work in the next requestAnimationFrame callback
 
+#[cfg(feature = "broadcast_channel")]
+pub use crate::broadcast::{use_broadcast_channel, BroadcastChannel};
 pub use crate::cfg::Config;
 pub use crate::file_engine::WebFileEngineExt;
+#[cfg(feature = "performance")]
+pub use crate::performance::{use_web_vitals, WebVital};
+#[cfg(feature = "system_status")]
+pub use crate::system_status::{use_battery, use_online, BatteryStatus};
 use dioxus_core::{Element, Scope, VirtualDom};
 use futures_util::{
     future::{select, Either},
     pin_mut, FutureExt, StreamExt,
 };
 
+#[cfg(feature = "broadcast_channel")]
+mod broadcast;
 mod cache;
 mod cfg;
 mod dom;
@@ -70,8 +78,16 @@ mod eval;
 mod file_engine;
 #[cfg(all(feature = "hot_reload", debug_assertions))]
 mod hot_reload;
+#[cfg(feature = "performance")]
+mod performance;
+#[cfg(feature = "hydrate")]
+mod priority;
 #[cfg(feature = "hydrate")]
 mod rehydrate;
+#[cfg(feature = "hydrate")]
+mod lazy_hydration;
+#[cfg(feature = "system_status")]
+mod system_status;
 
 // Currently disabled since it actually slows down immediate rendering
 // todo: only schedule non-immediate renders through ric/raf
@@ -173,6 +189,9 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     log::info!("Starting up");
 
     let mut dom = VirtualDom::new_with_props(root, root_props);
+    if let Some(budget) = cfg.mutation_budget {
+        dom = dom.with_mutation_budget(budget);
+    }
 
     #[cfg(feature = "eval")]
     {
@@ -199,14 +218,33 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     let (tx, mut rx) = futures_channel::mpsc::unbounded();
 
     #[cfg(feature = "hydrate")]
-    let should_hydrate = cfg.hydrate;
+    let hydrate_lazily = cfg.lazy_hydration;
+    #[cfg(feature = "hydrate")]
+    let should_hydrate = cfg.hydrate || hydrate_lazily;
     #[cfg(not(feature = "hydrate"))]
     let should_hydrate = false;
 
     let mut websys_dom = dom::WebsysDom::new(cfg, tx);
 
+    // Create every template in the app up front so rendering only has to clone them
+    #[cfg(feature = "template_registry")]
+    websys_dom.load_templates(&dom.prebake_templates().templates);
+
+    // Don't render anything until the user interacts with the server rendered page
+    #[cfg(feature = "hydrate")]
+    let first_event = match hydrate_lazily {
+        true => lazy_hydration::wait_for_first_event(&websys_dom.root).await,
+        false => None,
+    };
+
     log::info!("rebuilding app");
 
+    #[cfg(feature = "performance")]
+    let finish_first_render = performance::measure(match should_hydrate {
+        true => "dioxus-hydrate",
+        false => "dioxus-first-render",
+    });
+
     if should_hydrate {
         #[cfg(feature = "hydrate")]
         {
@@ -239,6 +277,18 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     // the mutations come back with nothing - we need to actually mount them
     websys_dom.mount();
 
+    #[cfg(feature = "performance")]
+    finish_first_render();
+
+    // Replay the events the user triggered before the page was hydrated. The buffered events include the event that
+    // started hydrating the page, so it is only replayed on its own if the page did not buffer events.
+    #[cfg(feature = "hydrate")]
+    if should_hydrate && !dioxus_interpreter_js::minimal_bindings::replayQueuedEvents() {
+        if let Some(event) = first_event {
+            lazy_hydration::replay_event(&event);
+        }
+    }
+
     loop {
         log::trace!("waiting for work");
 
@@ -275,7 +325,16 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // Dequeue all of the events from the channel in send order
         // todo: we should re-order these if possible
         while let Some(evt) = res {
-            dom.handle_event(evt.name.as_str(), evt.data, evt.element, evt.bubbles);
+            match evt.generation {
+                Some(generation) => dom.handle_generational_event(
+                    evt.name.as_str(),
+                    evt.data,
+                    evt.element,
+                    generation,
+                    evt.bubbles,
+                ),
+                None => dom.handle_event(evt.name.as_str(), evt.data, evt.element, evt.bubbles),
+            }
             res = rx.try_next().transpose().unwrap().ok();
         }
 
@@ -298,5 +357,11 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
+
+        // the page shows the new edits now, so libraries can sync the url and focus with it
+        dioxus_html::prelude::run_after_render();
+
+        #[cfg(feature = "performance")]
+        performance::finish_route_transition();
     }
 }
@@ -0,0 +1,160 @@
+//! Performance marks around the work the renderer does and a hook that reports the Web Vitals of the page.
+//!
+//! The renderer emits these entries to the [Performance timeline](https://developer.mozilla.org/en-US/docs/Web/API/Performance_API):
+//! - `dioxus-hydrate`: hydrating a server rendered page
+//! - `dioxus-first-render`: rendering the app into an empty page
+//! - `dioxus-route-transition`: from a route change until the new route is rendered
+//!
+//! Nothing is recorded unless the opt-in `performance` feature is enabled. Route transitions are only measured if the
+//! `performance` feature of `dioxus-router` is enabled as well, because the router marks where they start.
+
+use dioxus_core::ScopeState;
+use js_sys::{Object, Reflect};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{Performance, PerformanceObserver, PerformanceObserverEntryList};
+
+/// The mark routers emit when they start a route transition.
+const ROUTE_TRANSITION_START: &str = "dioxus-route-transition-start";
+
+fn performance() -> Option<Performance> {
+    web_sys::window()?.performance()
+}
+
+/// Measure the time from `{name}-start` to `{name}-end`. Call the returned function when the work is done.
+pub(crate) fn measure(name: &'static str) -> impl FnOnce() {
+    let performance = performance();
+    let start = format!("{name}-start");
+    if let Some(performance) = &performance {
+        let _ = performance.mark(&start);
+    }
+    move || {
+        if let Some(performance) = performance {
+            let end = format!("{name}-end");
+            let _ = performance.mark(&end);
+            let _ = performance.measure_with_start_mark_and_end_mark(name, &start, &end);
+        }
+    }
+}
+
+/// Finish the route transition a router started, if there is one.
+pub(crate) fn finish_route_transition() {
+    let Some(performance) = performance() else {
+        return;
+    };
+    if performance
+        .get_entries_by_name(ROUTE_TRANSITION_START)
+        .length()
+        > 0
+    {
+        let _ =
+            performance.measure_with_start_mark("dioxus-route-transition", ROUTE_TRANSITION_START);
+        performance.clear_marks_with_mark_name(ROUTE_TRANSITION_START);
+    }
+}
+
+/// A [Web Vital](https://web.dev/vitals/) of the page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebVital {
+    /// The time in milliseconds from the start of the navigation until the largest element in the viewport was
+    /// rendered. Reported every time a larger element is rendered.
+    LargestContentfulPaint(f64),
+    /// The time in milliseconds from the first interaction until the browser started to process it. Reported once.
+    FirstInputDelay(f64),
+    /// The sum of all unexpected layout shifts. Reported every time the layout shifts.
+    CumulativeLayoutShift(f64),
+}
+
+/// Report the [Web Vitals](https://web.dev/vitals/) of the page, for example to send them to a real user monitoring
+/// service. Vitals that happened before the component was created are reported as well.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     dioxus_web::use_web_vitals(cx, |vital| match vital {
+///         WebVital::LargestContentfulPaint(ms) => log::info!("LCP: {ms}ms"),
+///         WebVital::FirstInputDelay(ms) => log::info!("FID: {ms}ms"),
+///         WebVital::CumulativeLayoutShift(score) => log::info!("CLS: {score}"),
+///     });
+///
+///     render! { "hello world" }
+/// }
+/// ```
+pub fn use_web_vitals(cx: &ScopeState, reporter: impl FnMut(WebVital) + 'static) {
+    cx.use_hook(|| WebVitalsObserver::new(reporter));
+}
+
+struct WebVitalsObserver {
+    observers: Vec<PerformanceObserver>,
+    _callback: Option<Closure<dyn FnMut(PerformanceObserverEntryList)>>,
+}
+
+impl WebVitalsObserver {
+    fn new(mut reporter: impl FnMut(WebVital) + 'static) -> Self {
+        let mut cumulative_layout_shift = 0.0;
+        let callback = Closure::<dyn FnMut(PerformanceObserverEntryList)>::new(
+            move |entries: PerformanceObserverEntryList| {
+                for entry in entries.get_entries().iter() {
+                    let number = |name: &str| {
+                        Reflect::get(&entry, &JsValue::from_str(name))
+                            .ok()
+                            .and_then(|value| value.as_f64())
+                            .unwrap_or_default()
+                    };
+                    let entry_type = Reflect::get(&entry, &JsValue::from_str("entryType"))
+                        .ok()
+                        .and_then(|value| value.as_string());
+                    match entry_type.as_deref() {
+                        Some("largest-contentful-paint") => {
+                            reporter(WebVital::LargestContentfulPaint(number("startTime")))
+                        }
+                        Some("first-input") => reporter(WebVital::FirstInputDelay(
+                            number("processingStart") - number("startTime"),
+                        )),
+                        Some("layout-shift") => {
+                            // Shifts right after user input are expected
+                            let had_recent_input =
+                                Reflect::get(&entry, &JsValue::from_str("hadRecentInput"))
+                                    .ok()
+                                    .and_then(|value| value.as_bool())
+                                    .unwrap_or_default();
+                            if !had_recent_input {
+                                cumulative_layout_shift += number("value");
+                                reporter(WebVital::CumulativeLayoutShift(cumulative_layout_shift));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            },
+        );
+
+        let observers = ["largest-contentful-paint", "first-input", "layout-shift"]
+            .iter()
+            .filter_map(|entry_type| {
+                let observer = PerformanceObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+                // `type` is required to receive the entries from before the observer was created
+                let options = Object::new();
+                Reflect::set(&options, &"type".into(), &JsValue::from_str(entry_type)).ok()?;
+                Reflect::set(&options, &"buffered".into(), &true.into()).ok()?;
+                observer.observe(options.unchecked_ref());
+                Some(observer)
+            })
+            .collect::<Vec<_>>();
+
+        if observers.is_empty() {
+            log::warn!("Web Vitals are not supported in this browser");
+        }
+
+        Self {
+            observers,
+            _callback: Some(callback),
+        }
+    }
+}
+
+impl Drop for WebVitalsObserver {
+    fn drop(&mut self) {
+        for observer in &self.observers {
+            observer.disconnect();
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Buffer the events of a region of the page until it should be hydrated, then replay them in order. Events on the
+// regions nested in it follow the priority of the nested region instead.
+export function deferRegion(nodes, nested, events, visible) {
+  const contains = (list, target) => list.some((node) => node.contains(target));
+  const buffered = [];
+
+  const handler = (event) => {
+    if (!contains(nodes, event.target) || contains(nested, event.target)) {
+      return;
+    }
+    const target = event.target.closest && event.target.closest("[data-dioxus-id]");
+    const preventDefaultRequests = target && target.getAttribute("dioxus-prevent-default");
+    // Copies of events that are replayed already ran their default action
+    if (
+      !event.isTrusted ||
+      (preventDefaultRequests && preventDefaultRequests.includes(`on${event.type}`))
+    ) {
+      event.preventDefault();
+    }
+    // The event is replayed once the region is hydrated
+    event.stopImmediatePropagation();
+    buffered.push(event);
+  };
+  for (const name of events) {
+    window.addEventListener(name, handler, true);
+  }
+
+  const hydrate = () => {
+    for (const name of events) {
+      window.removeEventListener(name, handler, true);
+    }
+    for (const event of buffered) {
+      // Nodes that were removed since can't receive the event
+      if (!event.target.isConnected) {
+        continue;
+      }
+      const copy = new event.constructor(event.type, event);
+      const preventDefault = (replayed) => {
+        if (replayed === copy) {
+          replayed.preventDefault();
+        }
+      };
+      window.addEventListener(event.type, preventDefault);
+      event.target.dispatchEvent(copy);
+      window.removeEventListener(event.type, preventDefault);
+    }
+  };
+
+  if (visible) {
+    waitUntilVisible(nodes, hydrate);
+  } else {
+    waitUntilIdle(hydrate);
+  }
+}
+
+// Call the callback once any of the elements is scrolled into view.
+function waitUntilVisible(nodes, callback) {
+  const elements = nodes.filter((node) => node instanceof Element);
+  if (typeof IntersectionObserver === "undefined" || elements.length === 0) {
+    callback();
+    return;
+  }
+  const observer = new IntersectionObserver((entries) => {
+    if (entries.some((entry) => entry.isIntersecting)) {
+      observer.disconnect();
+      callback();
+    }
+  });
+  for (const element of elements) {
+    observer.observe(element);
+  }
+}
+
+// Call the callback once the browser is idle.
+function waitUntilIdle(callback) {
+  if (typeof requestIdleCallback === "undefined") {
+    setTimeout(callback, 1);
+    return;
+  }
+  requestIdleCallback(() => callback());
+}
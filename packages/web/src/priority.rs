@@ -0,0 +1,107 @@
+//! Make the content of outlets interactive according to the [`HydrationPriority`] of their route, so the rest of the
+//! page and above-the-fold routes become interactive first.
+//!
+//! Scopes that provide a [`HydrationPriority`] context, like the outlets of dioxus-router, start a region of the page
+//! while the page is hydrated. Events on a region that isn't critical are buffered until the region is scrolled into
+//! view or the browser is idle, then replayed in order.
+
+use dioxus_html::prelude::HydrationPriority;
+use wasm_bindgen::prelude::*;
+use web_sys::Node;
+
+#[wasm_bindgen(module = "/src/priority.js")]
+extern "C" {
+    fn deferRegion(
+        nodes: &js_sys::Array,
+        nested: &js_sys::Array,
+        events: &js_sys::Array,
+        visible: bool,
+    );
+}
+
+/// The content of a scope with its own hydration priority
+struct Region {
+    priority: HydrationPriority,
+    /// The top level nodes of the content
+    nodes: Vec<Node>,
+    /// The events the elements of the content listen to
+    events: Vec<String>,
+    /// The regions nested in the content. Their events follow their own priority.
+    nested: Vec<usize>,
+}
+
+/// The regions of the page found while hydrating it
+#[derive(Default)]
+pub(crate) struct HydrationRegions {
+    regions: Vec<Region>,
+    current: Option<usize>,
+}
+
+impl HydrationRegions {
+    fn current_priority(&self) -> HydrationPriority {
+        self.current
+            .map(|current| self.regions[current].priority)
+            .unwrap_or_default()
+    }
+
+    /// Start the region of a scope that provides a hydration priority. Returns the region the scope is nested in if
+    /// the priority differs from it, to pass to [`HydrationRegions::exit`].
+    pub(crate) fn enter(&mut self, priority: HydrationPriority) -> Option<Option<usize>> {
+        if priority == self.current_priority() {
+            return None;
+        }
+        let parent = self.current;
+        let region = self.regions.len();
+        self.regions.push(Region {
+            priority,
+            nodes: Vec::new(),
+            events: Vec::new(),
+            nested: Vec::new(),
+        });
+        if let Some(parent) = parent {
+            self.regions[parent].nested.push(region);
+        }
+        self.current = Some(region);
+        Some(parent)
+    }
+
+    /// Finish the current region with the top level nodes of its content
+    pub(crate) fn exit(&mut self, parent: Option<usize>, nodes: Vec<Node>) {
+        if let Some(current) = self.current {
+            self.regions[current].nodes = nodes;
+        }
+        self.current = parent;
+    }
+
+    /// Record an event an element of the current region listens to
+    pub(crate) fn listen(&mut self, event: &str) {
+        if let Some(current) = self.current {
+            let events = &mut self.regions[current].events;
+            if !events.iter().any(|listened| listened == event) {
+                events.push(event.to_string());
+            }
+        }
+    }
+
+    /// Buffer the events of the regions that are not critical until they should be hydrated
+    pub(crate) fn defer(self) {
+        for region in &self.regions {
+            let visible = match region.priority {
+                HydrationPriority::Critical => continue,
+                HydrationPriority::Visible => true,
+                HydrationPriority::Idle => false,
+            };
+            if region.events.is_empty() {
+                continue;
+            }
+            let nodes: js_sys::Array = region.nodes.iter().collect();
+            let nested: js_sys::Array = region
+                .nested
+                .iter()
+                .flat_map(|&nested| &self.regions[nested].nodes)
+                .collect();
+            let events: js_sys::Array = region.events.iter().map(JsValue::from).collect();
+            deferRegion(&nodes, &nested, &events, visible);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::dom::WebsysDom;
 use dioxus_core::{
     AttributeValue, DynamicNode, ElementId, ScopeState, TemplateNode, VNode, VirtualDom,
 };
-use dioxus_html::event_bubbles;
+use dioxus_html::{event_bubbles, prelude::HydrationPriority};
 use wasm_bindgen::JsCast;
 use web_sys::{Comment, Node};
 
@@ -41,6 +41,8 @@ impl WebsysDom {
 
         let mut hydrated = vec![true];
 
+        self.regions = Default::default();
+
         let mut last_node_was_static_text = false;
 
         // Recursively rehydrate the dom from the VirtualDom
@@ -52,6 +54,8 @@ impl WebsysDom {
             &mut last_node_was_static_text,
         )?;
 
+        std::mem::take(&mut self.regions).defer();
+
         self.interpreter.flush();
         Ok(())
     }
@@ -68,13 +72,29 @@ impl WebsysDom {
             dioxus_core::RenderReturn::Ready(ready) => ready,
             _ => return Err(VNodeNotInitialized),
         };
+
+        // Scopes with their own hydration priority, like the content of router outlets, become interactive on their own
+        let region = scope
+            .has_context::<HydrationPriority>()
+            .and_then(|priority| self.regions.enter(priority));
+        let first_node = current_child.clone().ok();
+
         self.rehydrate_vnode(
             current_child,
             hydrated,
             dom,
             vnode,
             last_node_was_static_text,
-        )
+        )?;
+
+        if let Some(parent) = region {
+            let last_node = current_child.clone().ok();
+            let nodes = std::iter::successors(first_node, |node| node.next_sibling())
+                .take_while(|node| Some(node) != last_node.as_ref())
+                .collect();
+            self.regions.exit(parent, nodes);
+        }
+        Ok(())
     }
 
     fn rehydrate_vnode(
@@ -134,6 +154,7 @@ impl WebsysDom {
                         let name = attribute.name;
                         if let AttributeValue::Listener(_) = value {
                             let event_name = &name[2..];
+                            self.regions.listen(event_name);
                             self.interpreter.new_event_listener(
                                 event_name,
                                 id.0 as u32,
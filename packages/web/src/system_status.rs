@@ -0,0 +1,177 @@
+//! Hooks that rerender a component when the battery or the network connection of the device changes. They are only
+//! available with the `system_status` feature.
+
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use dioxus_core::ScopeState;
+use wasm_bindgen::{prelude::Closure, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{BatteryManager, Event, Window};
+
+/// The charge of the battery of the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryStatus {
+    /// How full the battery is, from 0 to 1
+    pub level: f64,
+    /// The battery is being charged
+    pub charging: bool,
+}
+
+/// Get the charge of the battery of the device, for example to do less work in the background while it is not
+/// charging. The component rerenders when the level or the charging status changes.
+///
+/// Returns [`None`] until the browser reports the battery, and in browsers that don't support the
+/// [Battery Status API](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API).
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let battery = dioxus_web::use_battery(cx);
+///     let low_power = matches!(battery, Some(battery) if !battery.charging && battery.level < 0.2);
+///
+///     render! {
+///         if low_power {
+///             rsx! { "Syncing is paused to save battery" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_battery(cx: &ScopeState) -> Option<BatteryStatus> {
+    let status = cx.use_hook(|| {
+        let status = Rc::new(Cell::new(None));
+        cx.spawn(watch_battery(status.clone(), cx.schedule_update()));
+        status
+    });
+    status.get()
+}
+
+async fn watch_battery(
+    status: Rc<Cell<Option<BatteryStatus>>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+) {
+    let Some(navigator) = web_sys::window().map(|window| window.navigator()) else {
+        return;
+    };
+    // getBattery is missing in browsers that don't support the API, and in the web-sys bindings, so it is looked up
+    // on the navigator
+    let Ok(get_battery) = js_sys::Reflect::get(&navigator, &"getBattery".into()) else {
+        return;
+    };
+    let Some(get_battery) = get_battery.dyn_ref::<js_sys::Function>() else {
+        return;
+    };
+    let Ok(promise) = get_battery.call0(&navigator) else {
+        return;
+    };
+    let promise: js_sys::Promise = promise.unchecked_into();
+    let Ok(battery) = JsFuture::from(promise).await else {
+        return;
+    };
+    let battery: BatteryManager = battery.unchecked_into();
+
+    let read = {
+        let battery = battery.clone();
+        move || {
+            let new = Some(BatteryStatus {
+                level: battery.level(),
+                charging: battery.charging(),
+            });
+            if status.replace(new) != new {
+                update();
+            }
+        }
+    };
+    read();
+
+    let on_change = Closure::<dyn FnMut(Event)>::new(move |_| read());
+    battery.set_onlevelchange(Some(on_change.as_ref().unchecked_ref()));
+    battery.set_onchargingchange(Some(on_change.as_ref().unchecked_ref()));
+    // The handlers are removed when the task is dropped with the component
+    let _listener = BatteryListener {
+        battery,
+        _on_change: on_change,
+    };
+    futures_util::future::pending::<()>().await;
+}
+
+struct BatteryListener {
+    battery: BatteryManager,
+    _on_change: Closure<dyn FnMut(Event)>,
+}
+
+impl Drop for BatteryListener {
+    fn drop(&mut self) {
+        self.battery.set_onlevelchange(None);
+        self.battery.set_onchargingchange(None);
+    }
+}
+
+/// Check if the device is connected to a network, for example to show a banner while the app is offline. The
+/// component rerenders when the device goes online or offline.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let online = dioxus_web::use_online(cx);
+///
+///     render! {
+///         if !online {
+///             rsx! { div { class: "banner", "You are offline" } }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_online(cx: &ScopeState) -> bool {
+    let listener = cx.use_hook(|| OnlineListener::new(cx.schedule_update()));
+    listener.online.get()
+}
+
+struct OnlineListener {
+    online: Rc<Cell<bool>>,
+    window: Option<Window>,
+    on_change: Closure<dyn FnMut(Event)>,
+}
+
+impl OnlineListener {
+    fn new(update: Arc<dyn Fn() + Send + Sync>) -> Self {
+        let window = web_sys::window();
+        // Assume the device is online if the browser can't tell
+        let online = Rc::new(Cell::new(
+            window
+                .as_ref()
+                .map_or(true, |window| window.navigator().on_line()),
+        ));
+        let on_change = Closure::<dyn FnMut(Event)>::new({
+            let online = online.clone();
+            move |event: Event| {
+                let now_online = event.type_() == "online";
+                if online.replace(now_online) != now_online {
+                    update();
+                }
+            }
+        });
+        if let Some(window) = &window {
+            for event in ["online", "offline"] {
+                let _ = window
+                    .add_event_listener_with_callback(event, on_change.as_ref().unchecked_ref());
+            }
+        }
+
+        Self {
+            online,
+            window,
+            on_change,
+        }
+    }
+}
+
+impl Drop for OnlineListener {
+    fn drop(&mut self) {
+        if let Some(window) = &self.window {
+            for event in ["online", "offline"] {
+                let _ = window.remove_event_listener_with_callback(
+                    event,
+                    self.on_change.as_ref().unchecked_ref(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+use dioxus_web::Config;
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::{window, HtmlElement};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static SHELL_CLICKS: Cell<usize> = const { Cell::new(0) };
+    static LAZY_CLICKS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        button {
+            id: "shell",
+            onclick: |_| SHELL_CLICKS.with(|clicks| clicks.set(clicks.get() + 1)),
+            "shell"
+        }
+        Lazy {}
+    }
+}
+
+// Content far below the fold that only becomes interactive once it is scrolled into view
+fn Lazy(cx: Scope) -> Element {
+    cx.provide_context(HydrationPriority::Visible);
+    render! {
+        div {
+            style: "margin-top: 10000px",
+            button {
+                id: "lazy",
+                onclick: |_| LAZY_CLICKS.with(|clicks| clicks.set(clicks.get() + 1)),
+                "lazy"
+            }
+        }
+    }
+}
+
+fn element(id: &str) -> HtmlElement {
+    let document = window().unwrap().document().unwrap();
+    document.get_element_by_id(id).unwrap().unchecked_into()
+}
+
+#[wasm_bindgen_test]
+async fn content_becomes_interactive_when_visible() {
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+    let out = dioxus_ssr::pre_render(&dom);
+    window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .body()
+        .unwrap()
+        .set_inner_html(&format!("<div id='main'>{out}</div>"));
+
+    dioxus_web::launch_cfg(app, Config::new().hydrate(true));
+    TimeoutFuture::new(50).await;
+
+    // The rest of the page is interactive right away, while clicks on the lazy content wait until it is visible
+    element("shell").click();
+    element("lazy").click();
+    TimeoutFuture::new(50).await;
+    assert_eq!(SHELL_CLICKS.with(Cell::get), 1);
+    assert_eq!(LAZY_CLICKS.with(Cell::get), 0);
+
+    // The buffered click is replayed once the content is scrolled into view
+    element("lazy").scroll_into_view();
+    TimeoutFuture::new(200).await;
+    assert_eq!(LAZY_CLICKS.with(Cell::get), 1);
+
+    element("lazy").click();
+    TimeoutFuture::new(50).await;
+    assert_eq!(LAZY_CLICKS.with(Cell::get), 2);
+}
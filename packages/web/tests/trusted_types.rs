@@ -0,0 +1,114 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_interpreter_js::minimal_bindings::createTrustedSinks;
+use js_sys::{Function, Object, Reflect};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::{window, Element};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// The sinks of a policy, and the sinks that were reported as blocked
+struct Sinks {
+    sinks: JsValue,
+    reported: Rc<RefCell<Vec<String>>>,
+    _report: Closure<dyn FnMut(String, String)>,
+}
+
+impl Sinks {
+    fn new(policy_name: &str, sanitize: &JsValue) -> Self {
+        let reported = Rc::new(RefCell::new(Vec::new()));
+        let report: Closure<dyn FnMut(String, String)> = Closure::wrap(Box::new({
+            let reported = reported.clone();
+            move |sink: String, _sample: String| reported.borrow_mut().push(sink)
+        }));
+        let sinks = createTrustedSinks(policy_name, sanitize, report.as_ref().unchecked_ref());
+        Self {
+            sinks,
+            reported,
+            _report: report,
+        }
+    }
+
+    fn method(&self, name: &str) -> Function {
+        Reflect::get(&self.sinks, &name.into())
+            .unwrap()
+            .unchecked_into()
+    }
+
+    /// Convert the value of an attribute the way the interpreter does before it writes it
+    fn attribute(&self, tag: &str, name: &str, value: &str) -> String {
+        let node = element(tag);
+        let value = self
+            .method("attribute")
+            .call3(&self.sinks, &node, &name.into(), &value.into())
+            .unwrap();
+        // Browsers with Trusted Types return a TrustedHTML or TrustedScriptURL instead of a string
+        value.unchecked_into::<Object>().to_string().into()
+    }
+
+    /// Write the inner html of a div the way the interpreter writes `dangerous_inner_html`
+    fn inner_html(&self, html: &str) -> String {
+        let node = element("div");
+        self.method("html")
+            .call2(&self.sinks, &node, &html.into())
+            .unwrap();
+        node.inner_html()
+    }
+}
+
+fn element(tag: &str) -> Element {
+    window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_element(tag)
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn html_goes_through_the_app_sanitizer() {
+    let sanitize: Closure<dyn Fn(String) -> String> = Closure::wrap(Box::new(|html: String| {
+        html.replace("<i>", "").replace("</i>", "")
+    }));
+    let sinks = Sinks::new("dioxus-test-sanitizer", sanitize.as_ref());
+
+    assert_eq!(
+        sinks.inner_html("<b>bold</b><i>italic</i>"),
+        "<b>bold</b>italic"
+    );
+    assert_eq!(sinks.attribute("iframe", "srcdoc", "<i>page</i>"), "page");
+    assert!(sinks.reported.borrow().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn html_is_never_written_unsanitized() {
+    let sinks = Sinks::new("dioxus-test-no-sanitizer", &JsValue::NULL);
+
+    // Browsers with the Sanitizer API sanitize the html, the others reject it
+    let html = sinks.inner_html(r#"<b onclick="alert(1)">bold</b><script>alert(1)</script>"#);
+    assert!(html == "<b>bold</b>" || html.is_empty(), "{html}");
+    assert_eq!(sinks.attribute("iframe", "srcdoc", "<p>page</p>"), "");
+    assert!(sinks.reported.borrow().contains(&"html".to_string()));
+}
+
+#[wasm_bindgen_test]
+fn blocks_script_urls() {
+    let sinks = Sinks::new("dioxus-test-urls", &JsValue::NULL);
+
+    // The browser ignores whitespace and tabs in the scheme of a url
+    assert_eq!(
+        sinks.attribute("a", "href", " javascript:alert(1)"),
+        "about:blank"
+    );
+    assert_eq!(
+        sinks.attribute("a", "href", "java\tscript:alert(1)"),
+        "about:blank"
+    );
+    assert_eq!(sinks.attribute("a", "href", "/home"), "/home");
+    assert_eq!(
+        sinks.attribute("div", "class", "<b>not html</b>"),
+        "<b>not html</b>"
+    );
+    assert_eq!(sinks.reported.borrow().len(), 2);
+}